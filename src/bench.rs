@@ -0,0 +1,227 @@
+//! Knull Benchmarks
+//!
+//! `knull test --bench` times each `#[bench] fn` on `monotonic_ns`: a warmup
+//! that also learns what one iteration costs, then a fixed number of samples,
+//! each a batch of iterations sized so the samples fill the measurement time.
+//! Samples outside the Tukey fences (1.5 × IQR past the quartiles) are dropped
+//! as outliers before the statistics are taken. A run can be saved as a JSON
+//! baseline and later runs compared against it.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::interpreter::{monotonic_ns, RuntimeError};
+
+/// Changes in median time smaller than this are reported as noise
+pub const NOISE_PERCENT: f64 = 3.0;
+
+/// Fewest samples taken, however slow an iteration is
+const MIN_SAMPLES: usize = 5;
+
+/// How long to warm up and measure each benchmark for
+#[derive(Debug, Clone)]
+pub struct BenchConfig {
+    pub warmup_ns: i64,
+    pub measure_ns: i64,
+    pub samples: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig { warmup_ns: 300_000_000, measure_ns: 2_000_000_000, samples: 50 }
+    }
+}
+
+/// Timings of one benchmark, per iteration, after outlier rejection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchStats {
+    pub mean_ns: f64,
+    pub median_ns: f64,
+    pub stddev_ns: f64,
+    pub min_ns: f64,
+    pub max_ns: f64,
+    /// Samples kept, and dropped as outliers
+    pub samples: usize,
+    pub outliers: usize,
+    /// Iterations run across all samples
+    pub iterations: u64,
+}
+
+impl BenchStats {
+    /// Statistics over per-iteration sample times, each from a batch of `batch` iterations
+    pub fn from_samples(mut samples: Vec<f64>, batch: u64) -> BenchStats {
+        let taken = samples.len();
+        samples.sort_by(|a, b| a.total_cmp(b));
+        let (q1, q3) = (quantile(&samples, 0.25), quantile(&samples, 0.75));
+        let fence = 1.5 * (q3 - q1);
+        samples.retain(|&s| s >= q1 - fence && s <= q3 + fence);
+
+        let n = samples.len().max(1) as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let variance = if samples.len() > 1 {
+            samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+        BenchStats {
+            mean_ns: mean,
+            median_ns: quantile(&samples, 0.5),
+            stddev_ns: variance.sqrt(),
+            min_ns: samples.first().copied().unwrap_or(0.0),
+            max_ns: samples.last().copied().unwrap_or(0.0),
+            samples: samples.len(),
+            outliers: taken - samples.len(),
+            iterations: taken as u64 * batch,
+        }
+    }
+
+    /// Iterations per second
+    pub fn throughput(&self) -> f64 {
+        if self.mean_ns > 0.0 { 1e9 / self.mean_ns } else { f64::INFINITY }
+    }
+
+    /// Change in median time from `baseline`, in percent; positive is slower
+    pub fn change_from(&self, baseline: &BenchStats) -> f64 {
+        (self.median_ns - baseline.median_ns) / baseline.median_ns * 100.0
+    }
+}
+
+/// Linear-interpolated quantile of sorted `samples`
+fn quantile(samples: &[f64], q: f64) -> f64 {
+    match samples.len() {
+        0 => 0.0,
+        1 => samples[0],
+        n => {
+            let pos = q * (n - 1) as f64;
+            let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+            samples[lo] + (samples[hi] - samples[lo]) * (pos - lo as f64)
+        }
+    }
+}
+
+/// Time `iteration`, which runs the benchmark once
+pub fn measure(
+    mut iteration: impl FnMut() -> Result<(), RuntimeError>,
+    config: &BenchConfig,
+) -> Result<BenchStats, RuntimeError> {
+    let mut time_batch = |batch: u64| -> Result<i64, RuntimeError> {
+        let start = monotonic_ns();
+        for _ in 0..batch {
+            iteration()?;
+        }
+        Ok(monotonic_ns() - start)
+    };
+
+    // Warm up, doubling the batch while it is short, to learn the cost of an iteration
+    let warmup_start = monotonic_ns();
+    let mut batch = 1u64;
+    let mut per_iter;
+    loop {
+        let elapsed = time_batch(batch)?;
+        per_iter = elapsed.max(1) as f64 / batch as f64;
+        if monotonic_ns() - warmup_start >= config.warmup_ns {
+            break;
+        }
+        if elapsed < config.warmup_ns / 10 {
+            batch *= 2;
+        }
+    }
+
+    let budget = config.measure_ns as f64;
+    let samples = ((budget / per_iter) as usize).clamp(MIN_SAMPLES, config.samples.max(MIN_SAMPLES));
+    let batch = ((budget / samples as f64 / per_iter) as u64).max(1);
+    let mut times = Vec::with_capacity(samples);
+    for _ in 0..samples {
+        times.push(time_batch(batch)? as f64 / batch as f64);
+    }
+    Ok(BenchStats::from_samples(times, batch))
+}
+
+/// Saved statistics, by benchmark id
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    pub benchmarks: BTreeMap<String, BenchStats>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Baseline, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read baseline '{}': {}", path.display(), e))?;
+        serde_json::from_str(&text).map_err(|e| format!("Invalid baseline '{}': {}", path.display(), e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        std::fs::write(path, self.to_json()).map_err(|e| format!("Cannot write '{}': {}", path.display(), e))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// A nanosecond count in the largest unit that keeps it above 1
+pub fn format_ns(ns: f64) -> String {
+    if ns < 1e3 {
+        format!("{:.1} ns", ns)
+    } else if ns < 1e6 {
+        format!("{:.2} µs", ns / 1e3)
+    } else if ns < 1e9 {
+        format!("{:.2} ms", ns / 1e6)
+    } else {
+        format!("{:.2} s", ns / 1e9)
+    }
+}
+
+/// Iterations per second, shortened with K/M/G
+pub fn format_throughput(per_sec: f64) -> String {
+    if per_sec >= 1e9 {
+        format!("{:.2}G/s", per_sec / 1e9)
+    } else if per_sec >= 1e6 {
+        format!("{:.2}M/s", per_sec / 1e6)
+    } else if per_sec >= 1e3 {
+        format!("{:.2}K/s", per_sec / 1e3)
+    } else {
+        format!("{:.2}/s", per_sec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::ErrorKind;
+
+    #[test]
+    fn test_stats_drop_outliers() {
+        let mut samples = vec![100.0, 102.0, 98.0, 101.0, 99.0, 100.0, 103.0, 97.0];
+        samples.push(5000.0);
+        let stats = BenchStats::from_samples(samples, 10);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.samples, 8);
+        assert_eq!(stats.iterations, 90);
+        assert_eq!(stats.median_ns, 100.0);
+        assert_eq!(stats.mean_ns, 100.0);
+        assert_eq!(stats.max_ns, 103.0);
+        assert!((stats.stddev_ns - 2.0).abs() < 0.1, "{}", stats.stddev_ns);
+        assert_eq!(stats.throughput(), 1e7);
+    }
+
+    #[test]
+    fn test_measure_and_compare() {
+        let config = BenchConfig { warmup_ns: 1_000_000, measure_ns: 5_000_000, samples: 10 };
+        let mut calls = 0u64;
+        let stats = measure(|| { calls += 1; Ok(()) }, &config).unwrap();
+        assert!(stats.samples + stats.outliers >= MIN_SAMPLES);
+        assert!(calls >= stats.iterations);
+
+        let slower = BenchStats { median_ns: stats.median_ns * 1.5, ..stats.clone() };
+        assert!((slower.change_from(&stats) - 50.0).abs() < 1e-9);
+
+        let failing = measure(|| Err(RuntimeError::new(ErrorKind::Error, "boom")), &config);
+        assert!(failing.is_err());
+
+        assert_eq!(format_ns(1500.0), "1.50 µs");
+        assert_eq!(format_throughput(2500.0), "2.50K/s");
+    }
+}
@@ -0,0 +1,141 @@
+//! Extracts the names matched by `Interpreter::call_builtin` so the rest of
+//! the crate can ask whether a name is a builtin without calling it, and the
+//! names of each arm of it and of the method matches, which the builtin
+//! table's test checks its entries against.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Methods on builtin values, by receiver type and the function matching them
+const METHOD_TABLES: &[(&str, &str)] = &[
+    ("string", "fn call_string_method("),
+    ("array", "fn call_array_method("),
+    ("map", "fn call_map_method("),
+    ("int", "fn call_int_method("),
+    ("float", "fn call_float_method("),
+];
+
+fn main() {
+    println!("cargo:rerun-if-changed=interpreter.rs");
+    let source = fs::read_to_string("interpreter.rs").expect("read interpreter.rs");
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).to_path_buf();
+
+    let builtins = arms(&source, "fn call_builtin(", "match name {");
+    let mut names: Vec<&str> = builtins.iter().flatten().map(String::as_str).collect();
+    names.sort();
+    names.dedup();
+    let list: String = names.iter().map(|n| format!("    {:?},\n", n)).collect();
+    fs::write(out.join("builtin_names.rs"), format!("&[\n{}]\n", list)).expect("write builtin_names.rs");
+
+    fs::write(out.join("builtin_arms.rs"), format!("&[\n{}]\n", arm_list(&builtins))).expect("write builtin_arms.rs");
+    let methods: String = METHOD_TABLES
+        .iter()
+        .map(|(receiver, function)| {
+            format!("    ({:?}, &[\n{}    ]),\n", receiver, arm_list(&arms(&source, function, "match method {")))
+        })
+        .collect();
+    fs::write(out.join("builtin_method_arms.rs"), format!("&[\n{}]\n", methods))
+        .expect("write builtin_method_arms.rs");
+}
+
+/// `arms` as lines of a Rust `&[&[&str]]` literal
+fn arm_list(arms: &[Vec<String>]) -> String {
+    arms.iter().map(|names| format!("    &{:?},\n", names)).collect()
+}
+
+/// The string patterns of each top-level arm of the `match_marker` match in the
+/// function starting at `function`
+fn arms(source: &str, function: &str, match_marker: &str) -> Vec<Vec<String>> {
+    let start = source.find(function).unwrap_or_else(|| panic!("{} not found", function));
+    let body = &source[start..];
+    let open = body.find(match_marker).unwrap_or_else(|| panic!("{} has no `{}`", function, match_marker))
+        + match_marker.len();
+    let bytes = body.as_bytes();
+
+    let mut arms: Vec<Vec<String>> = Vec::new();
+    let mut in_pattern = false;
+    let mut braces = 1;
+    let mut parens = 0;
+    let mut i = open;
+    while i < bytes.len() && braces > 0 {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i = body[i..].find('\n').map_or(bytes.len(), |n| i + n);
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = body[i..].find("*/").map_or(bytes.len(), |n| i + n + 2);
+                continue;
+            }
+            b'r' if !is_ident(bytes[i - 1]) && matches!(bytes.get(i + 1), Some(b'"' | b'#')) => {
+                let hashes = body[i + 1..].bytes().take_while(|&b| b == b'#').count();
+                if bytes.get(i + 1 + hashes) == Some(&b'"') {
+                    let close = format!("\"{}", "#".repeat(hashes));
+                    let from = i + 2 + hashes;
+                    i = body[from..].find(&close).map_or(bytes.len(), |n| from + n + close.len());
+                    continue;
+                }
+            }
+            b'"' => {
+                let end = string_end(bytes, i);
+                if braces == 1 && parens == 0 && is_arm_pattern(&body[end..]) {
+                    let name = body[i + 1..end - 1].to_string();
+                    if in_pattern {
+                        arms.last_mut().unwrap().push(name);
+                    } else {
+                        arms.push(vec![name]);
+                        in_pattern = true;
+                    }
+                    if body[end..].trim_start().starts_with("=>") {
+                        in_pattern = false;
+                    }
+                }
+                i = end;
+                continue;
+            }
+            b'\'' => {
+                // A char literal ('x', '\n', '\u{..}'); otherwise a lifetime
+                if bytes.get(i + 1) == Some(&b'\\') {
+                    i = body[i + 2..].find('\'').map_or(bytes.len(), |n| i + 3 + n);
+                    continue;
+                }
+                let ch_len = body[i + 1..].chars().next().map_or(1, char::len_utf8);
+                if bytes.get(i + 1 + ch_len) == Some(&b'\'') {
+                    i += ch_len + 2;
+                    continue;
+                }
+            }
+            b'{' => braces += 1,
+            b'}' => braces -= 1,
+            b'(' | b'[' => parens += 1,
+            b')' | b']' => parens -= 1,
+            _ => {}
+        }
+        i += 1;
+    }
+    arms
+}
+
+/// Index just past the closing quote of the string literal starting at `start`
+fn string_end(bytes: &[u8], start: usize) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Whether the text after a string literal continues a match arm pattern
+fn is_arm_pattern(rest: &str) -> bool {
+    let rest = rest.trim_start();
+    rest.starts_with("=>") || rest.starts_with('|') && !rest.starts_with("||")
+}
+
+fn is_ident(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
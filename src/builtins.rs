@@ -0,0 +1,132 @@
+//! Knull Builtin Registry
+//!
+//! Every builtin function, and every method on strings, arrays, maps and
+//! numbers, with its parameters, return type, doc and category. The entries
+//! live in `builtins/table.rs`, written out by hand; a test checks them against
+//! the arms `build.rs` finds in `Interpreter::call_builtin` and the method
+//! matches.
+
+mod table;
+
+pub use table::{BUILTINS, METHODS};
+
+/// A builtin function or method
+#[derive(Debug)]
+pub struct Builtin {
+    pub name: &'static str,
+    /// Other names matched by the same arm
+    pub aliases: &'static [&'static str],
+    /// The type a method is called on; `None` for functions
+    pub receiver: Option<&'static str>,
+    /// Parameter names; optional ones end in `?`, and `args...` takes the rest
+    pub params: &'static [&'static str],
+    pub min_args: usize,
+    /// `None` when any number of arguments is accepted
+    pub max_args: Option<usize>,
+    /// Empty when the result type depends on the arguments
+    pub returns: &'static str,
+    pub doc: &'static str,
+    pub category: &'static str,
+}
+
+impl Builtin {
+    /// `name(a, b?) -> type`, or `receiver.name(a) -> type` for a method
+    pub fn signature(&self) -> String {
+        let mut signature = match self.receiver {
+            Some(receiver) => format!("{}.{}({})", receiver, self.name, self.params.join(", ")),
+            None => format!("{}({})", self.name, self.params.join(", ")),
+        };
+        if !self.returns.is_empty() {
+            signature.push_str(" -> ");
+            signature.push_str(self.returns);
+        }
+        signature
+    }
+
+    pub fn accepts(&self, count: usize) -> bool {
+        count >= self.min_args && self.max_args.is_none_or(|max| count <= max)
+    }
+
+    fn named(&self, name: &str) -> bool {
+        self.name == name || self.aliases.contains(&name)
+    }
+}
+
+/// The builtin function called `name`, under its own name or an alias
+pub fn lookup(name: &str) -> Option<&'static Builtin> {
+    BUILTINS.iter().find(|builtin| builtin.named(name))
+}
+
+/// The methods called `name`, one per receiver type that has it
+pub fn methods(name: &str) -> impl Iterator<Item = &'static Builtin> + '_ {
+    METHODS.iter().filter(move |method| method.named(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::BUILTIN_NAMES;
+    use std::collections::HashSet;
+
+    /// The names of each arm of `call_builtin`, in order, as `build.rs` finds them
+    const ARMS: &[&[&str]] = include!(concat!(env!("OUT_DIR"), "/builtin_arms.rs"));
+
+    /// The arms of each method match, by receiver
+    const METHOD_ARMS: &[(&str, &[&[&str]])] = include!(concat!(env!("OUT_DIR"), "/builtin_method_arms.rs"));
+
+    /// Check that each arm has exactly one entry, named by the arm's names that no
+    /// earlier arm took, and that no entry lacks an arm
+    fn assert_one_entry_per_arm(arms: &[&[&str]], entries: &[&Builtin]) {
+        let mut seen = HashSet::new();
+        let mut reachable = 0;
+        for arm in arms {
+            let mut names: Vec<&str> = arm.iter().copied().filter(|name| seen.insert(*name)).collect();
+            if names.is_empty() {
+                continue;
+            }
+            reachable += 1;
+            names.sort();
+            let matching: Vec<_> = entries
+                .iter()
+                .filter(|entry| {
+                    let mut entry_names: Vec<&str> = entry.aliases.to_vec();
+                    entry_names.push(entry.name);
+                    entry_names.sort();
+                    entry_names == names
+                })
+                .collect();
+            assert_eq!(matching.len(), 1, "arm {:?} has {} table entries", names, matching.len());
+        }
+        assert_eq!(entries.len(), reachable, "the table has entries with no arm");
+    }
+
+    #[test]
+    fn test_table_matches_the_arms() {
+        assert_one_entry_per_arm(ARMS, &BUILTINS.iter().collect::<Vec<_>>());
+        for (receiver, arms) in METHOD_ARMS {
+            let entries: Vec<_> = METHODS.iter().filter(|method| method.receiver == Some(receiver)).collect();
+            assert_one_entry_per_arm(arms, &entries);
+        }
+        assert_eq!(METHODS.iter().filter(|method| method.receiver.is_none()).count(), 0);
+    }
+
+    #[test]
+    fn test_registry_covers_every_builtin() {
+        for name in BUILTIN_NAMES {
+            assert!(lookup(name).is_some(), "{} is not in the registry", name);
+        }
+        assert_eq!(BUILTINS.iter().map(|b| 1 + b.aliases.len()).sum::<usize>(), BUILTIN_NAMES.len());
+
+        let recv = lookup("tcp_recv").unwrap();
+        assert_eq!(recv.signature(), "tcp_recv(handle, max_bytes?) -> string");
+        assert_eq!(recv.category, "TCP Sockets");
+        let substring = lookup("substring").unwrap();
+        assert_eq!((substring.min_args, substring.max_args), (3, None));
+        assert!(lookup("println").unwrap().accepts(5));
+        assert!(lookup("dir_list").unwrap().accepts(0));
+        assert_eq!(lookup("eprintln").unwrap().name, "eprint");
+
+        assert!(methods("push").any(|m| m.receiver == Some("array")));
+        assert_eq!(methods("upper").next().unwrap().name, "to_uppercase");
+    }
+}
@@ -0,0 +1,1290 @@
+//! The builtin table: one entry per arm of `Interpreter::call_builtin` and of
+//! the method matches, kept in the same order. The test in `builtins.rs`
+//! fails when an arm and its entry go out of step.
+
+use super::Builtin;
+
+/// The builtin functions, in the order `call_builtin` matches them
+#[rustfmt::skip]
+pub static BUILTINS: &[Builtin] = &[
+    Builtin { name: "print", aliases: &["println"], receiver: None, params: &["args..."], min_args: 0, max_args: None, returns: "null", doc: "", category: "" },
+    Builtin { name: "eprint", aliases: &["eprintln"], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "null", doc: "", category: "" },
+    Builtin { name: "len", aliases: &[], receiver: None, params: &["arg"], min_args: 1, max_args: None, returns: "int", doc: "", category: "" },
+    Builtin { name: "to_int", aliases: &[], receiver: None, params: &["arg"], min_args: 1, max_args: None, returns: "int", doc: "", category: "" },
+    Builtin { name: "to_float", aliases: &[], receiver: None, params: &["arg"], min_args: 1, max_args: None, returns: "float", doc: "", category: "" },
+    Builtin { name: "to_string", aliases: &[], receiver: None, params: &["arg"], min_args: 1, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "typeof", aliases: &[], receiver: None, params: &["arg"], min_args: 1, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "implements", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "" },
+    Builtin { name: "exit", aliases: &[], receiver: None, params: &["code?"], min_args: 0, max_args: None, returns: "", doc: "", category: "" },
+    Builtin { name: "file_read", aliases: &[], receiver: None, params: &["arg"], min_args: 1, max_args: None, returns: "string", doc: "", category: "File I/O operations" },
+    Builtin { name: "file_write", aliases: &[], receiver: None, params: &["path", "contents"], min_args: 2, max_args: None, returns: "null", doc: "", category: "File I/O operations" },
+    Builtin { name: "file_append", aliases: &[], receiver: None, params: &["path", "contents"], min_args: 2, max_args: None, returns: "null", doc: "", category: "File I/O operations" },
+    Builtin { name: "file_exists", aliases: &[], receiver: None, params: &["arg"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "File I/O operations" },
+    Builtin { name: "file_remove", aliases: &[], receiver: None, params: &["arg"], min_args: 1, max_args: None, returns: "null", doc: "", category: "File I/O operations" },
+    Builtin { name: "mkdir", aliases: &[], receiver: None, params: &["arg"], min_args: 1, max_args: None, returns: "null", doc: "", category: "File I/O operations" },
+    Builtin { name: "dir_list", aliases: &[], receiver: None, params: &["path?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "File I/O operations" },
+    Builtin { name: "spawn", aliases: &[], receiver: None, params: &["arg"], min_args: 1, max_args: None, returns: "null", doc: "", category: "Threading operations" },
+    Builtin { name: "sleep", aliases: &[], receiver: None, params: &["millis?"], min_args: 0, max_args: None, returns: "null", doc: "", category: "Threading operations" },
+    Builtin { name: "thread_id", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Threading operations" },
+    Builtin { name: "get_hostname", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "Networking" },
+    Builtin { name: "ffi_open", aliases: &[], receiver: None, params: &["arg"], min_args: 1, max_args: None, returns: "int", doc: "", category: "FFI functions" },
+    Builtin { name: "ffi_get_symbol", aliases: &[], receiver: None, params: &["lib_handle", "name"], min_args: 2, max_args: None, returns: "int", doc: "", category: "FFI functions" },
+    Builtin { name: "ffi_malloc", aliases: &[], receiver: None, params: &["size?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "FFI functions" },
+    Builtin { name: "ffi_free", aliases: &[], receiver: None, params: &["arg"], min_args: 1, max_args: None, returns: "null", doc: "", category: "FFI functions" },
+    Builtin { name: "gc_collect", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "GC functions" },
+    Builtin { name: "gc_stats", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "GC functions" },
+    Builtin { name: "time", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Time functions" },
+    Builtin { name: "time_millis", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Time functions" },
+    Builtin { name: "env_get", aliases: &[], receiver: None, params: &["arg"], min_args: 1, max_args: None, returns: "string or null", doc: "", category: "Environment functions" },
+    Builtin { name: "env_set", aliases: &[], receiver: None, params: &["key", "val"], min_args: 2, max_args: None, returns: "null", doc: "", category: "Environment functions" },
+    Builtin { name: "exec", aliases: &[], receiver: None, params: &["arg"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Command execution" },
+    Builtin { name: "strlen", aliases: &[], receiver: None, params: &["arg"], min_args: 1, max_args: None, returns: "int", doc: "", category: "String utilities" },
+    Builtin { name: "substring", aliases: &[], receiver: None, params: &["s", "start", "end"], min_args: 3, max_args: None, returns: "string", doc: "", category: "String utilities" },
+    Builtin { name: "push", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Array utilities" },
+    Builtin { name: "format", aliases: &[], receiver: None, params: &["fmt", "args..."], min_args: 1, max_args: None, returns: "string", doc: "", category: "String operations" },
+    Builtin { name: "input", aliases: &["readline", "read_line"], receiver: None, params: &["prompt?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "String operations" },
+    Builtin { name: "split", aliases: &[], receiver: None, params: &["s", "sep"], min_args: 2, max_args: None, returns: "array", doc: "", category: "String operations" },
+    Builtin { name: "join", aliases: &[], receiver: None, params: &["arg1", "sep"], min_args: 2, max_args: None, returns: "string", doc: "", category: "String operations" },
+    Builtin { name: "contains", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "String operations" },
+    Builtin { name: "starts_with", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "String operations" },
+    Builtin { name: "ends_with", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "String operations" },
+    Builtin { name: "replace", aliases: &[], receiver: None, params: &["s", "from", "to"], min_args: 3, max_args: None, returns: "string", doc: "", category: "String operations" },
+    Builtin { name: "trim", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "String operations" },
+    Builtin { name: "trim_start", aliases: &["ltrim"], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "String operations" },
+    Builtin { name: "trim_end", aliases: &["rtrim"], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "String operations" },
+    Builtin { name: "upper", aliases: &["to_upper", "to_uppercase"], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "String operations" },
+    Builtin { name: "lower", aliases: &["to_lower", "to_lowercase"], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "String operations" },
+    Builtin { name: "repeat", aliases: &[], receiver: None, params: &["s", "n"], min_args: 2, max_args: None, returns: "string", doc: "", category: "String operations" },
+    Builtin { name: "index_of", aliases: &["find"], receiver: None, params: &["arg1", "needle"], min_args: 2, max_args: None, returns: "int", doc: "", category: "String operations" },
+    Builtin { name: "char_at", aliases: &[], receiver: None, params: &["s", "idx"], min_args: 2, max_args: None, returns: "", doc: "", category: "String operations" },
+    Builtin { name: "parse_int", aliases: &["int"], receiver: None, params: &["v", "base?"], min_args: 1, max_args: None, returns: "", doc: "", category: "String operations" },
+    Builtin { name: "parse_float", aliases: &["float"], receiver: None, params: &["v"], min_args: 1, max_args: None, returns: "", doc: "", category: "String operations" },
+    Builtin { name: "chars", aliases: &[], receiver: None, params: &["v"], min_args: 1, max_args: None, returns: "array", doc: "", category: "String operations" },
+    Builtin { name: "bytes", aliases: &[], receiver: None, params: &["v"], min_args: 1, max_args: None, returns: "array", doc: "", category: "String operations" },
+    Builtin { name: "pad_left", aliases: &[], receiver: None, params: &["s", "width", "pad?"], min_args: 2, max_args: None, returns: "string", doc: "", category: "String operations" },
+    Builtin { name: "pad_right", aliases: &[], receiver: None, params: &["s", "width", "pad_char?"], min_args: 2, max_args: None, returns: "string", doc: "", category: "String operations" },
+    Builtin { name: "abs", aliases: &[], receiver: None, params: &["v"], min_args: 1, max_args: None, returns: "", doc: "", category: "Math operations" },
+    Builtin { name: "min", aliases: &[], receiver: None, params: &["arg1?", "arg2?"], min_args: 0, max_args: None, returns: "", doc: "", category: "Math operations" },
+    Builtin { name: "max", aliases: &[], receiver: None, params: &["arg1?", "arg2?"], min_args: 0, max_args: None, returns: "", doc: "", category: "Math operations" },
+    Builtin { name: "sqrt", aliases: &[], receiver: None, params: &["v"], min_args: 1, max_args: None, returns: "float", doc: "", category: "Math operations" },
+    Builtin { name: "pow", aliases: &[], receiver: None, params: &["base", "exp"], min_args: 2, max_args: None, returns: "", doc: "", category: "Math operations" },
+    Builtin { name: "floor", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math operations" },
+    Builtin { name: "ceil", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math operations" },
+    Builtin { name: "round", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math operations" },
+    Builtin { name: "trunc", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math operations" },
+    Builtin { name: "sin", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math operations" },
+    Builtin { name: "cos", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math operations" },
+    Builtin { name: "tan", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math operations" },
+    Builtin { name: "asin", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math operations" },
+    Builtin { name: "acos", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math operations" },
+    Builtin { name: "atan", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math operations" },
+    Builtin { name: "atan2", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "float", doc: "", category: "Math operations" },
+    Builtin { name: "log", aliases: &["ln"], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math operations" },
+    Builtin { name: "log2", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math operations" },
+    Builtin { name: "log10", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math operations" },
+    Builtin { name: "exp", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math operations" },
+    Builtin { name: "random", aliases: &["rand"], receiver: None, params: &["lo?", "hi?"], min_args: 0, max_args: None, returns: "", doc: "", category: "Math operations" },
+    Builtin { name: "pi", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math operations" },
+    Builtin { name: "e", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math operations" },
+    Builtin { name: "pop", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Array operations" },
+    Builtin { name: "shift", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "", doc: "", category: "Array operations" },
+    Builtin { name: "insert", aliases: &[], receiver: None, params: &["arg1", "idx", "arg3"], min_args: 3, max_args: None, returns: "array", doc: "", category: "Array operations" },
+    Builtin { name: "remove", aliases: &[], receiver: None, params: &["arg1", "idx"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Array operations" },
+    Builtin { name: "sort", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Array operations" },
+    Builtin { name: "reverse", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "", doc: "", category: "Array operations" },
+    Builtin { name: "concat", aliases: &[], receiver: None, params: &["args..."], min_args: 0, max_args: None, returns: "array", doc: "", category: "Array operations" },
+    Builtin { name: "slice", aliases: &[], receiver: None, params: &["s", "start", "arg3"], min_args: 3, max_args: None, returns: "", doc: "", category: "Array operations" },
+    Builtin { name: "flatten", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Array operations" },
+    Builtin { name: "unique", aliases: &["dedup"], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Array operations" },
+    Builtin { name: "sum", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "", doc: "", category: "Array operations" },
+    Builtin { name: "product", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "", doc: "", category: "Array operations" },
+    Builtin { name: "range", aliases: &[], receiver: None, params: &["arg1", "arg2?", "arg3?"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Array operations" },
+    Builtin { name: "zip", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Array operations" },
+    Builtin { name: "count", aliases: &[], receiver: None, params: &["s?", "needle?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "Array operations" },
+    Builtin { name: "any", aliases: &[], receiver: None, params: &["arg1", "f?"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Array operations" },
+    Builtin { name: "all", aliases: &[], receiver: None, params: &["arg1", "f?"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Array operations" },
+    Builtin { name: "first", aliases: &["head"], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "", doc: "", category: "Array operations" },
+    Builtin { name: "last", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "", doc: "", category: "Array operations" },
+    Builtin { name: "is_empty", aliases: &[], receiver: None, params: &["v"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Array operations" },
+    Builtin { name: "is_int", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Type checks" },
+    Builtin { name: "is_float", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Type checks" },
+    Builtin { name: "is_string", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Type checks" },
+    Builtin { name: "is_bool", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Type checks" },
+    Builtin { name: "is_null", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Type checks" },
+    Builtin { name: "is_array", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Type checks" },
+    Builtin { name: "is_number", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Type checks" },
+    Builtin { name: "panic", aliases: &[], receiver: None, params: &["msg?"], min_args: 0, max_args: None, returns: "", doc: "", category: "I/O & system" },
+    Builtin { name: "dbg", aliases: &[], receiver: None, params: &["v"], min_args: 1, max_args: None, returns: "", doc: "", category: "I/O & system" },
+    Builtin { name: "print_err", aliases: &[], receiver: None, params: &["args..."], min_args: 0, max_args: None, returns: "null", doc: "", category: "I/O & system" },
+    Builtin { name: "println_err", aliases: &[], receiver: None, params: &["args..."], min_args: 0, max_args: None, returns: "null", doc: "", category: "I/O & system" },
+    Builtin { name: "flush", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "null", doc: "", category: "I/O & system" },
+    Builtin { name: "read_file", aliases: &[], receiver: None, params: &["arg"], min_args: 1, max_args: None, returns: "string", doc: "", category: "I/O & system" },
+    Builtin { name: "write_file", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "null", doc: "", category: "I/O & system" },
+    Builtin { name: "args", aliases: &[], receiver: None, params: &["args..."], min_args: 0, max_args: None, returns: "array", doc: "", category: "I/O & system" },
+    Builtin { name: "cwd", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "", doc: "", category: "I/O & system" },
+    Builtin { name: "chdir", aliases: &[], receiver: None, params: &["v"], min_args: 1, max_args: None, returns: "null", doc: "", category: "I/O & system" },
+    Builtin { name: "getpid", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "I/O & system" },
+    Builtin { name: "map_fn", aliases: &["map"], receiver: None, params: &["arg1?", "closure?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "Functional programming" },
+    Builtin { name: "filter_fn", aliases: &["filter"], receiver: None, params: &["arg1?", "closure?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "Functional programming" },
+    Builtin { name: "reduce", aliases: &["fold"], receiver: None, params: &["arg1?", "closure?", "acc?"], min_args: 0, max_args: None, returns: "", doc: "", category: "Functional programming" },
+    Builtin { name: "for_each", aliases: &[], receiver: None, params: &["arg1?", "closure?"], min_args: 0, max_args: None, returns: "null", doc: "", category: "Functional programming" },
+    Builtin { name: "find_fn", aliases: &[], receiver: None, params: &["arg1?", "closure?"], min_args: 0, max_args: None, returns: "", doc: "", category: "Functional programming" },
+    Builtin { name: "flat_map", aliases: &[], receiver: None, params: &["arg1?", "closure?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "Functional programming" },
+    Builtin { name: "call", aliases: &[], receiver: None, params: &["callable", "args..."], min_args: 1, max_args: None, returns: "", doc: "", category: "Functional programming" },
+    Builtin { name: "sorted_by", aliases: &["sort_by"], receiver: None, params: &["arg1?", "cmp_fn?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "Functional programming" },
+    Builtin { name: "map_new", aliases: &["dict", "hashmap"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "map", doc: "", category: "Map/Dict operations" },
+    Builtin { name: "keys", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Map/Dict operations" },
+    Builtin { name: "values", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "", doc: "", category: "Map/Dict operations" },
+    Builtin { name: "get", aliases: &[], receiver: None, params: &["arg1?", "key?", "default?"], min_args: 0, max_args: None, returns: "", doc: "", category: "Map/Dict operations" },
+    Builtin { name: "set_key", aliases: &["set"], receiver: None, params: &["arg1?", "arg2?", "arg3?"], min_args: 0, max_args: None, returns: "map", doc: "", category: "Map/Dict operations" },
+    Builtin { name: "del_key", aliases: &["delete"], receiver: None, params: &["arg1?", "arg2?"], min_args: 0, max_args: None, returns: "map", doc: "", category: "Map/Dict operations" },
+    Builtin { name: "has_key", aliases: &["has"], receiver: None, params: &["arg1?", "arg2?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Map/Dict operations" },
+    Builtin { name: "merge_maps", aliases: &["merge"], receiver: None, params: &["arg1?", "arg2?"], min_args: 0, max_args: None, returns: "map", doc: "", category: "Map/Dict operations" },
+    Builtin { name: "from_pairs", aliases: &["from_entries"], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "map", doc: "", category: "Map/Dict operations" },
+    Builtin { name: "entries", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Map/Dict operations" },
+    Builtin { name: "tuple", aliases: &[], receiver: None, params: &["args..."], min_args: 0, max_args: None, returns: "tuple", doc: "", category: "Tuple operations" },
+    Builtin { name: "tuple_get", aliases: &[], receiver: None, params: &["arg1?", "idx?"], min_args: 0, max_args: None, returns: "", doc: "", category: "Tuple operations" },
+    Builtin { name: "json_encode", aliases: &["to_json", "json", "json_stringify"], receiver: None, params: &["val"], min_args: 1, max_args: None, returns: "string", doc: "", category: "JSON serialization" },
+    Builtin { name: "json_decode", aliases: &["from_json", "parse_json", "json_parse"], receiver: None, params: &["val"], min_args: 1, max_args: None, returns: "", doc: "", category: "JSON serialization" },
+    Builtin { name: "http_post", aliases: &[], receiver: None, params: &["url", "body", "content_type?", "args..."], min_args: 2, max_args: None, returns: "string", doc: "", category: "HTTP" },
+    Builtin { name: "http_put", aliases: &[], receiver: None, params: &["url", "body", "args..."], min_args: 2, max_args: None, returns: "string", doc: "", category: "HTTP" },
+    Builtin { name: "http_delete", aliases: &[], receiver: None, params: &["v", "args..."], min_args: 1, max_args: None, returns: "string", doc: "", category: "HTTP" },
+    Builtin { name: "simple_regex_match", aliases: &[], receiver: None, params: &["pat", "text"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "Regex" },
+    Builtin { name: "str", aliases: &["string"], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Type conversions" },
+    Builtin { name: "num", aliases: &["number"], receiver: None, params: &["v"], min_args: 1, max_args: None, returns: "", doc: "", category: "Type conversions" },
+    Builtin { name: "bool", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Type conversions" },
+    Builtin { name: "array", aliases: &["list"], receiver: None, params: &["arg1?", "args..."], min_args: 0, max_args: None, returns: "array", doc: "", category: "Type conversions" },
+    Builtin { name: "hash", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "Hashing / UUID" },
+    Builtin { name: "uuid", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "Hashing / UUID" },
+    Builtin { name: "bit_and", aliases: &[], receiver: None, params: &["arg1?", "arg2?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "Bitwise operations" },
+    Builtin { name: "bit_or", aliases: &[], receiver: None, params: &["arg1?", "arg2?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "Bitwise operations" },
+    Builtin { name: "bit_xor", aliases: &[], receiver: None, params: &["arg1?", "arg2?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "Bitwise operations" },
+    Builtin { name: "bit_not", aliases: &[], receiver: None, params: &["v"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Bitwise operations" },
+    Builtin { name: "bit_shl", aliases: &[], receiver: None, params: &["arg1?", "arg2?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "Bitwise operations" },
+    Builtin { name: "bit_shr", aliases: &[], receiver: None, params: &["arg1?", "arg2?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "Bitwise operations" },
+    Builtin { name: "shell", aliases: &[], receiver: None, params: &["cmd?"], min_args: 0, max_args: None, returns: "map", doc: "", category: "Process / OS" },
+    Builtin { name: "pipe", aliases: &[], receiver: None, params: &["arg1?", "args..."], min_args: 0, max_args: None, returns: "string or null", doc: "", category: "Process / OS" },
+    Builtin { name: "not", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Misc missing" },
+    Builtin { name: "if_null", aliases: &["coalesce"], receiver: None, params: &["args..."], min_args: 0, max_args: None, returns: "", doc: "", category: "Misc missing" },
+    Builtin { name: "identity", aliases: &["id"], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "", doc: "", category: "Misc missing" },
+    Builtin { name: "noop", aliases: &["pass"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "null", doc: "", category: "Misc missing" },
+    Builtin { name: "black", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "red", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "green", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "yellow", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "blue", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "magenta", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "cyan", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "white", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "bright_black", aliases: &["gray", "grey"], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "bright_red", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "bright_green", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "bright_yellow", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "bright_blue", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "bright_magenta", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "bright_cyan", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "bright_white", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "bold", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "dim", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "italic", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "underline", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "blink", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "strikethrough", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "reset_color", aliases: &["ansi_reset"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "color_256", aliases: &[], receiver: None, params: &["code", "text"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "bg_color_256", aliases: &[], receiver: None, params: &["code", "text"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "rgb", aliases: &[], receiver: None, params: &["r", "g", "b", "text"], min_args: 4, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "bg_rgb", aliases: &[], receiver: None, params: &["r", "g", "b", "text"], min_args: 4, max_args: None, returns: "string", doc: "", category: "Terminal / ANSI Colors" },
+    Builtin { name: "clear_screen", aliases: &["clear"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "null", doc: "", category: "Terminal Cursor / Screen Control" },
+    Builtin { name: "clear_line", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "null", doc: "", category: "Terminal Cursor / Screen Control" },
+    Builtin { name: "cursor_up", aliases: &[], receiver: None, params: &["n?"], min_args: 0, max_args: None, returns: "null", doc: "", category: "Terminal Cursor / Screen Control" },
+    Builtin { name: "cursor_down", aliases: &[], receiver: None, params: &["n?"], min_args: 0, max_args: None, returns: "null", doc: "", category: "Terminal Cursor / Screen Control" },
+    Builtin { name: "cursor_right", aliases: &[], receiver: None, params: &["n?"], min_args: 0, max_args: None, returns: "null", doc: "", category: "Terminal Cursor / Screen Control" },
+    Builtin { name: "cursor_left", aliases: &[], receiver: None, params: &["n?"], min_args: 0, max_args: None, returns: "null", doc: "", category: "Terminal Cursor / Screen Control" },
+    Builtin { name: "cursor_move", aliases: &["set_cursor"], receiver: None, params: &["r?", "c?"], min_args: 0, max_args: None, returns: "null", doc: "", category: "Terminal Cursor / Screen Control" },
+    Builtin { name: "cursor_save", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "null", doc: "", category: "Terminal Cursor / Screen Control" },
+    Builtin { name: "cursor_restore", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "null", doc: "", category: "Terminal Cursor / Screen Control" },
+    Builtin { name: "cursor_hide", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "null", doc: "", category: "Terminal Cursor / Screen Control" },
+    Builtin { name: "cursor_show", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "null", doc: "", category: "Terminal Cursor / Screen Control" },
+    Builtin { name: "term_size", aliases: &["terminal_size"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "map", doc: "", category: "Terminal Cursor / Screen Control" },
+    Builtin { name: "box_line", aliases: &["hline"], receiver: None, params: &["w?", "ch?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Box Drawing / TUI" },
+    Builtin { name: "box_draw", aliases: &[], receiver: None, params: &["w?", "h?", "title?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "Box Drawing / TUI" },
+    Builtin { name: "table_format", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Box Drawing / TUI" },
+    Builtin { name: "progress_bar", aliases: &[], receiver: None, params: &["current", "total", "width?"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Box Drawing / TUI" },
+    Builtin { name: "spinner_frames", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "Box Drawing / TUI" },
+    Builtin { name: "path_join", aliases: &[], receiver: None, params: &["args..."], min_args: 0, max_args: None, returns: "string", doc: "", category: "Path operations" },
+    Builtin { name: "path_dirname", aliases: &["dirname"], receiver: None, params: &["p?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Path operations" },
+    Builtin { name: "path_basename", aliases: &["basename"], receiver: None, params: &["p?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Path operations" },
+    Builtin { name: "path_ext", aliases: &["file_ext"], receiver: None, params: &["p?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Path operations" },
+    Builtin { name: "path_stem", aliases: &["file_stem"], receiver: None, params: &["p?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Path operations" },
+    Builtin { name: "path_exists", aliases: &["exists"], receiver: None, params: &["p?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Path operations" },
+    Builtin { name: "path_is_file", aliases: &["is_file"], receiver: None, params: &["p?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Path operations" },
+    Builtin { name: "path_is_dir", aliases: &["is_dir"], receiver: None, params: &["p?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Path operations" },
+    Builtin { name: "path_abs", aliases: &["abs_path"], receiver: None, params: &["p?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Path operations" },
+    Builtin { name: "getcwd", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "Path operations" },
+    Builtin { name: "set_cwd", aliases: &[], receiver: None, params: &["p?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Path operations" },
+    Builtin { name: "ls", aliases: &["list_dir", "read_dir"], receiver: None, params: &["p?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "Path operations" },
+    Builtin { name: "make_dir", aliases: &[], receiver: None, params: &["p?", "recursive?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Path operations" },
+    Builtin { name: "rmdir", aliases: &["remove_dir"], receiver: None, params: &["p?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Path operations" },
+    Builtin { name: "copy_file", aliases: &["file_copy"], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Path operations" },
+    Builtin { name: "move_file", aliases: &["rename"], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "Path operations" },
+    Builtin { name: "file_meta", aliases: &["stat"], receiver: None, params: &["p?"], min_args: 0, max_args: None, returns: "map", doc: "", category: "Path operations" },
+    Builtin { name: "now", aliases: &["timestamp"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Date / Time" },
+    Builtin { name: "now_ms", aliases: &["timestamp_ms"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Date / Time" },
+    Builtin { name: "now_ns", aliases: &["timestamp_ns"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Date / Time" },
+    Builtin { name: "format_time", aliases: &["strftime"], receiver: None, params: &["ts?", "fmt?", "args..."], min_args: 0, max_args: None, returns: "string", doc: "", category: "Date / Time" },
+    Builtin { name: "parse_time", aliases: &["strptime"], receiver: None, params: &["s?", "args..."], min_args: 0, max_args: None, returns: "int", doc: "", category: "Date / Time" },
+    Builtin { name: "base64_encode", aliases: &["b64_encode"], receiver: None, params: &["s?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Base64" },
+    Builtin { name: "base64_decode", aliases: &["b64_decode"], receiver: None, params: &["s?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Base64" },
+    Builtin { name: "url_encode", aliases: &["urlencode"], receiver: None, params: &["s?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "URL encode/decode" },
+    Builtin { name: "url_decode", aliases: &["urldecode"], receiver: None, params: &["s?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "URL encode/decode" },
+    Builtin { name: "hypot", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "float", doc: "", category: "Advanced Math" },
+    Builtin { name: "factorial", aliases: &[], receiver: None, params: &["n?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "Advanced Math" },
+    Builtin { name: "gcd", aliases: &[], receiver: None, params: &["a", "b"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Advanced Math" },
+    Builtin { name: "lcm", aliases: &[], receiver: None, params: &["a", "b"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Advanced Math" },
+    Builtin { name: "is_prime", aliases: &[], receiver: None, params: &["n?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Advanced Math" },
+    Builtin { name: "primes_up_to", aliases: &["sieve"], receiver: None, params: &["n?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "Advanced Math" },
+    Builtin { name: "log_b", aliases: &["log_base"], receiver: None, params: &["x", "base"], min_args: 2, max_args: None, returns: "float", doc: "", category: "Advanced Math" },
+    Builtin { name: "deg2rad", aliases: &["to_radians"], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Advanced Math" },
+    Builtin { name: "rad2deg", aliases: &["to_degrees"], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Advanced Math" },
+    Builtin { name: "tau", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "Advanced Math" },
+    Builtin { name: "phi", aliases: &["golden_ratio"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "Advanced Math" },
+    Builtin { name: "inf", aliases: &["infinity"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "Advanced Math" },
+    Builtin { name: "nan", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "Advanced Math" },
+    Builtin { name: "epsilon", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "Advanced Math" },
+    Builtin { name: "max_int", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Advanced Math" },
+    Builtin { name: "min_int", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Advanced Math" },
+    Builtin { name: "lerp", aliases: &[], receiver: None, params: &["a", "b", "t"], min_args: 3, max_args: None, returns: "float", doc: "", category: "Advanced Math" },
+    Builtin { name: "map_range", aliases: &["remap"], receiver: None, params: &["v", "in_min", "in_max", "out_min", "out_max"], min_args: 5, max_args: None, returns: "float", doc: "", category: "Advanced Math" },
+    Builtin { name: "sign", aliases: &["signum"], receiver: None, params: &["v"], min_args: 1, max_args: None, returns: "", doc: "", category: "Advanced Math" },
+    Builtin { name: "between", aliases: &["in_range"], receiver: None, params: &["v", "lo", "hi"], min_args: 3, max_args: None, returns: "bool", doc: "", category: "Advanced Math" },
+    Builtin { name: "sort_by_desc", aliases: &[], receiver: None, params: &["arg1?", "key_fn?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "Sorting with comparator" },
+    Builtin { name: "group_by", aliases: &[], receiver: None, params: &["arg1?", "key_fn?"], min_args: 0, max_args: None, returns: "map", doc: "", category: "Sorting with comparator" },
+    Builtin { name: "zip_with", aliases: &[], receiver: None, params: &["arg1?", "arg2?", "f?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "Sorting with comparator" },
+    Builtin { name: "take", aliases: &[], receiver: None, params: &["arg1?", "n?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "Sorting with comparator" },
+    Builtin { name: "drop", aliases: &["skip"], receiver: None, params: &["arg1?", "n?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "Sorting with comparator" },
+    Builtin { name: "take_while", aliases: &[], receiver: None, params: &["arg1?", "f?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "Sorting with comparator" },
+    Builtin { name: "drop_while", aliases: &["skip_while"], receiver: None, params: &["arg1?", "f?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "Sorting with comparator" },
+    Builtin { name: "partition", aliases: &[], receiver: None, params: &["arg1?", "f?"], min_args: 0, max_args: None, returns: "tuple", doc: "", category: "Sorting with comparator" },
+    Builtin { name: "chunks", aliases: &["chunk"], receiver: None, params: &["arg1?", "n?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "Sorting with comparator" },
+    Builtin { name: "windows", aliases: &["sliding"], receiver: None, params: &["arg1?", "n?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "Sorting with comparator" },
+    Builtin { name: "count_if", aliases: &["count_where"], receiver: None, params: &["arg1?", "f?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "Sorting with comparator" },
+    Builtin { name: "sum_by", aliases: &["sum_map"], receiver: None, params: &["arg1?", "f?"], min_args: 0, max_args: None, returns: "", doc: "", category: "Sorting with comparator" },
+    Builtin { name: "max_by", aliases: &[], receiver: None, params: &["arg1?", "f?"], min_args: 0, max_args: None, returns: "", doc: "", category: "Sorting with comparator" },
+    Builtin { name: "min_by", aliases: &[], receiver: None, params: &["arg1?", "f?"], min_args: 0, max_args: None, returns: "", doc: "", category: "Sorting with comparator" },
+    Builtin { name: "sprintf", aliases: &["format_str"], receiver: None, params: &["fmt_val", "args..."], min_args: 1, max_args: None, returns: "string", doc: "", category: "String utilities" },
+    Builtin { name: "ljust", aliases: &[], receiver: None, params: &["s?", "n?", "ch?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "String utilities" },
+    Builtin { name: "rjust", aliases: &[], receiver: None, params: &["s?", "n?", "ch?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "String utilities" },
+    Builtin { name: "center", aliases: &["center_str"], receiver: None, params: &["s?", "n?", "ch?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "String utilities" },
+    Builtin { name: "word_wrap", aliases: &[], receiver: None, params: &["text?", "width?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "String utilities" },
+    Builtin { name: "lcs", aliases: &["longest_common_subsequence"], receiver: None, params: &["a?", "b?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "String utilities" },
+    Builtin { name: "levenshtein", aliases: &["edit_distance"], receiver: None, params: &["a?", "b?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "String utilities" },
+    Builtin { name: "assert", aliases: &[], receiver: None, params: &["cond?", "msg?"], min_args: 0, max_args: None, returns: "null", doc: "", category: "Assert / Error handling" },
+    Builtin { name: "assert_eq", aliases: &["assert_ne"], receiver: None, params: &["arg1", "arg2", "arg3?"], min_args: 2, max_args: None, returns: "null", doc: "", category: "Assert / Error handling" },
+    Builtin { name: "raise", aliases: &[], receiver: None, params: &["msg?"], min_args: 0, max_args: None, returns: "", doc: "", category: "Assert / Error handling" },
+    Builtin { name: "ok", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "", doc: "", category: "Assert / Error handling" },
+    Builtin { name: "err", aliases: &["error"], receiver: None, params: &["msg?"], min_args: 0, max_args: None, returns: "map", doc: "", category: "Assert / Error handling" },
+    Builtin { name: "is_error", aliases: &["is_err"], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Assert / Error handling" },
+    Builtin { name: "unwrap", aliases: &[], receiver: None, params: &["val", "msg?"], min_args: 1, max_args: None, returns: "", doc: "", category: "Assert / Error handling" },
+    Builtin { name: "unwrap_or", aliases: &[], receiver: None, params: &["val?", "default?"], min_args: 0, max_args: None, returns: "", doc: "", category: "Assert / Error handling" },
+    Builtin { name: "is_none", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Type checking extended" },
+    Builtin { name: "is_list", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Type checking extended" },
+    Builtin { name: "is_map", aliases: &["is_dict"], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Type checking extended" },
+    Builtin { name: "is_tuple", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Type checking extended" },
+    Builtin { name: "is_closure", aliases: &["is_fn", "is_function"], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Type checking extended" },
+    Builtin { name: "is_struct", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Type checking extended" },
+    Builtin { name: "env_all", aliases: &["env_vars"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "map", doc: "", category: "Environment / args" },
+    Builtin { name: "cli_args", aliases: &[], receiver: None, params: &["args..."], min_args: 0, max_args: None, returns: "array", doc: "", category: "Environment / args" },
+    Builtin { name: "memoize", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "", doc: "", category: "Memoize / Lazy" },
+    Builtin { name: "once", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "", doc: "", category: "Memoize / Lazy" },
+    Builtin { name: "dns_lookup", aliases: &["resolve"], receiver: None, params: &["host?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "Networking utilities" },
+    Builtin { name: "port_open", aliases: &["tcp_check"], receiver: None, params: &["host", "port"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "Networking utilities" },
+    Builtin { name: "tcp_connect", aliases: &[], receiver: None, params: &["host", "port?"], min_args: 1, max_args: None, returns: "handle_int or null", doc: "", category: "TCP Sockets" },
+    Builtin { name: "tcp_send", aliases: &[], receiver: None, params: &["handle", "data"], min_args: 2, max_args: None, returns: "bytes_written", doc: "", category: "TCP Sockets" },
+    Builtin { name: "tcp_recv", aliases: &[], receiver: None, params: &["handle", "max_bytes?"], min_args: 1, max_args: None, returns: "string", doc: "", category: "TCP Sockets" },
+    Builtin { name: "tcp_recv_all", aliases: &[], receiver: None, params: &["handle"], min_args: 1, max_args: None, returns: "string", doc: "reads until connection closed", category: "TCP Sockets" },
+    Builtin { name: "tcp_close", aliases: &[], receiver: None, params: &["handle"], min_args: 1, max_args: None, returns: "null", doc: "", category: "TCP Sockets" },
+    Builtin { name: "tcp_listen", aliases: &[], receiver: None, params: &["host", "port?"], min_args: 1, max_args: None, returns: "listener_handle", doc: "", category: "TCP Sockets" },
+    Builtin { name: "tcp_accept", aliases: &[], receiver: None, params: &["listener_handle"], min_args: 1, max_args: None, returns: "[stream_handle, peer_addr_str]", doc: "", category: "TCP Sockets" },
+    Builtin { name: "tcp_listen_close", aliases: &[], receiver: None, params: &["handle"], min_args: 1, max_args: None, returns: "null", doc: "", category: "TCP Sockets" },
+    Builtin { name: "tcp_set_timeout", aliases: &[], receiver: None, params: &["handle", "read_ms", "write_ms"], min_args: 3, max_args: None, returns: "null", doc: "", category: "TCP Sockets" },
+    Builtin { name: "tcp_peer_addr", aliases: &[], receiver: None, params: &["handle"], min_args: 1, max_args: None, returns: "string", doc: "", category: "TCP Sockets" },
+    Builtin { name: "tcp_local_addr", aliases: &[], receiver: None, params: &["handle"], min_args: 1, max_args: None, returns: "string", doc: "", category: "TCP Sockets" },
+    Builtin { name: "udp_bind", aliases: &[], receiver: None, params: &["addr", "port"], min_args: 2, max_args: None, returns: "handle", doc: "", category: "UDP Sockets" },
+    Builtin { name: "udp_send", aliases: &[], receiver: None, params: &["handle", "dest_host", "dest_port", "data"], min_args: 4, max_args: None, returns: "bytes_sent", doc: "", category: "UDP Sockets" },
+    Builtin { name: "udp_recv", aliases: &[], receiver: None, params: &["handle", "max_bytes?"], min_args: 1, max_args: None, returns: "[data_str, sender_addr_str]", doc: "", category: "UDP Sockets" },
+    Builtin { name: "udp_close", aliases: &[], receiver: None, params: &["handle"], min_args: 1, max_args: None, returns: "null", doc: "", category: "UDP Sockets" },
+    Builtin { name: "udp_set_timeout", aliases: &[], receiver: None, params: &["handle", "read_ms"], min_args: 2, max_args: None, returns: "null", doc: "", category: "UDP Sockets" },
+    Builtin { name: "udp_broadcast", aliases: &[], receiver: None, params: &["handle", "port", "data"], min_args: 3, max_args: None, returns: "bytes_sent", doc: "", category: "UDP Sockets" },
+    Builtin { name: "http_get", aliases: &[], receiver: None, params: &["url?"], min_args: 0, max_args: None, returns: "response_body_string", doc: "", category: "HTTP" },
+    Builtin { name: "http_status", aliases: &["http_response_code"], receiver: None, params: &["url?"], min_args: 0, max_args: None, returns: "status int", doc: "", category: "HTTP" },
+    Builtin { name: "sha256", aliases: &["sha256_hex"], receiver: None, params: &["input?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Crypto / Hashing" },
+    Builtin { name: "md5", aliases: &["md5_hex"], receiver: None, params: &["input?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Crypto / Hashing" },
+    Builtin { name: "random_bytes", aliases: &[], receiver: None, params: &["n?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Crypto / Hashing" },
+    Builtin { name: "random_string", aliases: &[], receiver: None, params: &["n?", "charset?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Crypto / Hashing" },
+    Builtin { name: "vars", aliases: &["globals"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "map", doc: "", category: "Scope introspection" },
+    Builtin { name: "defined", aliases: &[], receiver: None, params: &["name?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Scope introspection" },
+    Builtin { name: "flat_mat_new", aliases: &[], receiver: None, params: &["rows", "cols"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Matrix / Vector math" },
+    Builtin { name: "mat_get", aliases: &[], receiver: None, params: &["mat", "rows", "cols", "r", "c"], min_args: 5, max_args: None, returns: "float", doc: "", category: "Matrix / Vector math" },
+    Builtin { name: "mat_set", aliases: &[], receiver: None, params: &["mat", "rows", "cols", "r", "c", "val"], min_args: 6, max_args: None, returns: "new mat", doc: "", category: "Matrix / Vector math" },
+    Builtin { name: "flat_mat_mul", aliases: &[], receiver: None, params: &["arg1", "arg2", "ar", "ac", "bc"], min_args: 5, max_args: None, returns: "array", doc: "mat_mul(a, b, ar, ac_br, bc) -> flat result", category: "Matrix / Vector math" },
+    Builtin { name: "flat_mat_add", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "array", doc: "mat_add(a, b) -> element-wise sum", category: "Matrix / Vector math" },
+    Builtin { name: "flat_mat_transpose", aliases: &[], receiver: None, params: &["arg1", "rows", "cols"], min_args: 3, max_args: None, returns: "array", doc: "mat_transpose(mat, rows, cols) -> flat transposed", category: "Matrix / Vector math" },
+    Builtin { name: "dot", aliases: &["flat_vec_dot"], receiver: None, params: &["a", "b"], min_args: 2, max_args: None, returns: "scalar", doc: "", category: "Matrix / Vector math" },
+    Builtin { name: "cross", aliases: &["flat_vec_cross"], receiver: None, params: &["a", "b"], min_args: 2, max_args: None, returns: "vec3", doc: "", category: "Matrix / Vector math" },
+    Builtin { name: "vec_norm", aliases: &["vec_magnitude", "magnitude"], receiver: None, params: &["v"], min_args: 1, max_args: None, returns: "float magnitude", doc: "", category: "Matrix / Vector math" },
+    Builtin { name: "vec_normalize", aliases: &["normalize"], receiver: None, params: &["v"], min_args: 1, max_args: None, returns: "unit vector", doc: "", category: "Matrix / Vector math" },
+    Builtin { name: "vec_scale", aliases: &[], receiver: None, params: &["v", "scalar"], min_args: 2, max_args: None, returns: "scaled vector", doc: "", category: "Matrix / Vector math" },
+    Builtin { name: "vec_add", aliases: &[], receiver: None, params: &["a", "b"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Matrix / Vector math" },
+    Builtin { name: "vec_sub", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Matrix / Vector math" },
+    Builtin { name: "mem_alloc", aliases: &["malloc"], receiver: None, params: &["size?"], min_args: 0, max_args: None, returns: "ptr_int", doc: "", category: "Raw Memory / Pointers" },
+    Builtin { name: "mem_free", aliases: &["free"], receiver: None, params: &["ptr", "size"], min_args: 2, max_args: None, returns: "null", doc: "", category: "Raw Memory / Pointers" },
+    Builtin { name: "mem_read_u8", aliases: &[], receiver: None, params: &["a"], min_args: 1, max_args: None, returns: "int", doc: "mem_read_u8/u16/u32/u64/i8/i32/i64/f32/f64(ptr) -> int/float", category: "Raw Memory / Pointers" },
+    Builtin { name: "mem_read_u16", aliases: &[], receiver: None, params: &["a"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Raw Memory / Pointers" },
+    Builtin { name: "mem_read_u32", aliases: &[], receiver: None, params: &["a"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Raw Memory / Pointers" },
+    Builtin { name: "mem_read_u64", aliases: &["mem_read_i64"], receiver: None, params: &["a"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Raw Memory / Pointers" },
+    Builtin { name: "mem_read_i32", aliases: &[], receiver: None, params: &["a"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Raw Memory / Pointers" },
+    Builtin { name: "mem_read_f32", aliases: &[], receiver: None, params: &["a"], min_args: 1, max_args: None, returns: "float", doc: "", category: "Raw Memory / Pointers" },
+    Builtin { name: "mem_read_f64", aliases: &[], receiver: None, params: &["a"], min_args: 1, max_args: None, returns: "float", doc: "", category: "Raw Memory / Pointers" },
+    Builtin { name: "mem_write_u8", aliases: &[], receiver: None, params: &["ptr", "arg2"], min_args: 2, max_args: None, returns: "null", doc: "mem_write_u8/u16/u32/u64/i32/f32/f64(ptr, val)", category: "Raw Memory / Pointers" },
+    Builtin { name: "mem_write_u16", aliases: &[], receiver: None, params: &["ptr", "arg2"], min_args: 2, max_args: None, returns: "null", doc: "", category: "Raw Memory / Pointers" },
+    Builtin { name: "mem_write_u32", aliases: &[], receiver: None, params: &["ptr", "arg2"], min_args: 2, max_args: None, returns: "null", doc: "", category: "Raw Memory / Pointers" },
+    Builtin { name: "mem_write_u64", aliases: &["mem_write_i64"], receiver: None, params: &["ptr", "arg2"], min_args: 2, max_args: None, returns: "null", doc: "", category: "Raw Memory / Pointers" },
+    Builtin { name: "mem_write_i32", aliases: &[], receiver: None, params: &["ptr", "arg2"], min_args: 2, max_args: None, returns: "null", doc: "", category: "Raw Memory / Pointers" },
+    Builtin { name: "mem_write_f32", aliases: &[], receiver: None, params: &["ptr", "arg2"], min_args: 2, max_args: None, returns: "null", doc: "", category: "Raw Memory / Pointers" },
+    Builtin { name: "mem_write_f64", aliases: &[], receiver: None, params: &["ptr", "arg2"], min_args: 2, max_args: None, returns: "null", doc: "", category: "Raw Memory / Pointers" },
+    Builtin { name: "mem_copy", aliases: &["memcpy"], receiver: None, params: &["dst", "src", "bytes"], min_args: 3, max_args: None, returns: "null", doc: "", category: "Raw Memory / Pointers" },
+    Builtin { name: "mem_set", aliases: &["memset"], receiver: None, params: &["ptr", "byte_val", "count"], min_args: 3, max_args: None, returns: "null", doc: "", category: "Raw Memory / Pointers" },
+    Builtin { name: "mem_cmp", aliases: &["memcmp"], receiver: None, params: &["a", "b", "bytes"], min_args: 3, max_args: None, returns: "int", doc: "like memcmp", category: "Raw Memory / Pointers" },
+    Builtin { name: "ptr_add", aliases: &[], receiver: None, params: &["ptr", "offset"], min_args: 2, max_args: None, returns: "ptr", doc: "", category: "Raw Memory / Pointers" },
+    Builtin { name: "ptr_diff", aliases: &[], receiver: None, params: &["a", "b"], min_args: 2, max_args: None, returns: "int", doc: "a - b", category: "Raw Memory / Pointers" },
+    Builtin { name: "ptr_null", aliases: &["null_ptr"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "0", doc: "", category: "Raw Memory / Pointers" },
+    Builtin { name: "ptr_is_null", aliases: &[], receiver: None, params: &["ptr?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Raw Memory / Pointers" },
+    Builtin { name: "str_to_ptr", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "ptr to utf8 bytes", doc: "leaks — for FFI use", category: "Raw Memory / Pointers" },
+    Builtin { name: "ptr_to_str", aliases: &[], receiver: None, params: &["ptr", "len"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Raw Memory / Pointers" },
+    Builtin { name: "syscall", aliases: &[], receiver: None, params: &["nr", "a0?", "a1?", "a2?", "a3?", "a4?", "a5?"], min_args: 1, max_args: None, returns: "int", doc: "Linux x86-64 only", category: "Raw Memory / Pointers" },
+    Builtin { name: "thread_spawn", aliases: &[], receiver: None, params: &["fn", "arg0?", "arg1?", "..."], min_args: 1, max_args: None, returns: "thread_handle", doc: "", category: "OS Threads" },
+    Builtin { name: "thread_join", aliases: &[], receiver: None, params: &["handle"], min_args: 1, max_args: None, returns: "return_value", doc: "", category: "OS Threads" },
+    Builtin { name: "thread_try_recv", aliases: &["thread_poll"], receiver: None, params: &["handle"], min_args: 1, max_args: None, returns: "value or null", doc: "non-blocking", category: "OS Threads" },
+    Builtin { name: "thread_sleep", aliases: &["sleep_ms"], receiver: None, params: &["ms?"], min_args: 0, max_args: None, returns: "null", doc: "", category: "OS Threads" },
+    Builtin { name: "thread_current_id", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "OS Threads" },
+    Builtin { name: "mutex_new", aliases: &[], receiver: None, params: &["initial_val?"], min_args: 0, max_args: None, returns: "handle", doc: "", category: "Mutexes" },
+    Builtin { name: "mutex_lock", aliases: &[], receiver: None, params: &["handle"], min_args: 1, max_args: None, returns: "current_value", doc: "", category: "Mutexes" },
+    Builtin { name: "mutex_set", aliases: &[], receiver: None, params: &["handle", "value"], min_args: 2, max_args: None, returns: "null", doc: "", category: "Mutexes" },
+    Builtin { name: "mutex_clone", aliases: &[], receiver: None, params: &["handle"], min_args: 1, max_args: None, returns: "new handle sharing same Arc", doc: "", category: "Mutexes" },
+    Builtin { name: "sigmoid", aliases: &[], receiver: None, params: &["x?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "AI / ML Math" },
+    Builtin { name: "sigmoid_deriv", aliases: &[], receiver: None, params: &["x?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "AI / ML Math" },
+    Builtin { name: "relu", aliases: &[], receiver: None, params: &["x?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "AI / ML Math" },
+    Builtin { name: "relu_deriv", aliases: &[], receiver: None, params: &["x?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "AI / ML Math" },
+    Builtin { name: "leaky_relu", aliases: &[], receiver: None, params: &["x", "alpha"], min_args: 2, max_args: None, returns: "float", doc: "", category: "AI / ML Math" },
+    Builtin { name: "tanh_act", aliases: &["tanh_activation"], receiver: None, params: &["x?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "AI / ML Math" },
+    Builtin { name: "softmax", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "AI / ML Math" },
+    Builtin { name: "mse", aliases: &["mean_squared_error"], receiver: None, params: &["predicted_arr", "target_arr"], min_args: 2, max_args: None, returns: "float", doc: "", category: "AI / ML Math" },
+    Builtin { name: "cross_entropy", aliases: &[], receiver: None, params: &["predicted_arr", "target_arr"], min_args: 2, max_args: None, returns: "float", doc: "", category: "AI / ML Math" },
+    Builtin { name: "mat_scalar_mul", aliases: &[], receiver: None, params: &["mat", "scalar"], min_args: 2, max_args: None, returns: "scaled mat", doc: "", category: "AI / ML Math" },
+    Builtin { name: "mat_apply", aliases: &[], receiver: None, params: &["mat", "fn"], min_args: 2, max_args: None, returns: "mat with fn applied to each element", doc: "", category: "AI / ML Math" },
+    Builtin { name: "argmax", aliases: &[], receiver: None, params: &["arr"], min_args: 1, max_args: None, returns: "index of max element", doc: "", category: "AI / ML Math" },
+    Builtin { name: "argmin", aliases: &[], receiver: None, params: &["arr"], min_args: 1, max_args: None, returns: "index of min element", doc: "", category: "AI / ML Math" },
+    Builtin { name: "linspace", aliases: &[], receiver: None, params: &["start", "end", "n"], min_args: 3, max_args: None, returns: "array of n evenly spaced floats", doc: "", category: "AI / ML Math" },
+    Builtin { name: "arange", aliases: &[], receiver: None, params: &["start", "end", "step"], min_args: 3, max_args: None, returns: "array", doc: "", category: "AI / ML Math" },
+    Builtin { name: "mean", aliases: &["average"], receiver: None, params: &["arr"], min_args: 1, max_args: None, returns: "float", doc: "", category: "AI / ML Math" },
+    Builtin { name: "std_dev", aliases: &["stddev"], receiver: None, params: &["arr"], min_args: 1, max_args: None, returns: "float", doc: "", category: "AI / ML Math" },
+    Builtin { name: "re_match", aliases: &[], receiver: None, params: &["pat", "text"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "Regex" },
+    Builtin { name: "re_find", aliases: &[], receiver: None, params: &["pat", "text"], min_args: 2, max_args: None, returns: "string or null", doc: "regex_find(pattern, text) -> first match string or null", category: "Regex" },
+    Builtin { name: "re_findall", aliases: &[], receiver: None, params: &["pat", "text"], min_args: 2, max_args: None, returns: "array", doc: "regex_findall(pattern, text) -> array of match strings", category: "Regex" },
+    Builtin { name: "re_replace", aliases: &[], receiver: None, params: &["pat", "rep", "text"], min_args: 3, max_args: None, returns: "string", doc: "regex_replace(pattern, replacement, text) -> string", category: "Regex" },
+    Builtin { name: "re_split", aliases: &[], receiver: None, params: &["pat", "text"], min_args: 2, max_args: None, returns: "array", doc: "regex_split(pattern, text) -> array of parts", category: "Regex" },
+    Builtin { name: "re_captures", aliases: &[], receiver: None, params: &["pat", "text"], min_args: 2, max_args: None, returns: "array or null", doc: "regex_captures(pattern, text) -> array of captured groups (or null)", category: "Regex" },
+    Builtin { name: "ansi", aliases: &[], receiver: None, params: &["code?"], min_args: 0, max_args: None, returns: "escape string e.g. ansi(\"31\") = red fg", doc: "", category: "ANSI Terminal" },
+    Builtin { name: "purple", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Color shortcuts" },
+    Builtin { name: "bg_red", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Color shortcuts" },
+    Builtin { name: "bg_green", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Color shortcuts" },
+    Builtin { name: "bg_blue", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Color shortcuts" },
+    Builtin { name: "hide_cursor", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "Cursor control" },
+    Builtin { name: "show_cursor", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "Cursor control" },
+    Builtin { name: "term_width", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Cursor control" },
+    Builtin { name: "term_height", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Cursor control" },
+    Builtin { name: "print_raw", aliases: &["print_no_newline"], receiver: None, params: &["s?"], min_args: 0, max_args: None, returns: "null", doc: "", category: "Cursor control" },
+    Builtin { name: "file_read_bytes", aliases: &["read_bytes"], receiver: None, params: &["path"], min_args: 1, max_args: None, returns: "array of ints", doc: "0-255", category: "Binary File I/O" },
+    Builtin { name: "file_write_bytes", aliases: &["write_bytes"], receiver: None, params: &["path", "array_of_ints"], min_args: 2, max_args: None, returns: "null", doc: "", category: "Binary File I/O" },
+    Builtin { name: "file_append_bytes", aliases: &[], receiver: None, params: &["path", "bytes_array"], min_args: 2, max_args: None, returns: "null", doc: "", category: "Binary File I/O" },
+    Builtin { name: "hex_encode", aliases: &["to_hex", "bytes_to_hex"], receiver: None, params: &["array_of_ints or string"], min_args: 1, max_args: None, returns: "hex string", doc: "", category: "Encoding" },
+    Builtin { name: "hex_decode", aliases: &["from_hex", "hex_to_bytes"], receiver: None, params: &["hex_string"], min_args: 1, max_args: None, returns: "array of ints", doc: "", category: "Encoding" },
+    Builtin { name: "hex_decode_str", aliases: &[], receiver: None, params: &["hex_string"], min_args: 1, max_args: None, returns: "utf8 string", doc: "", category: "Encoding" },
+    Builtin { name: "base64_decode_bytes", aliases: &["b64_decode_bytes"], receiver: None, params: &["b64_string"], min_args: 1, max_args: None, returns: "array of ints", doc: "", category: "Encoding" },
+    Builtin { name: "kill", aliases: &["process_kill"], receiver: None, params: &["pid?", "signal?"], min_args: 0, max_args: None, returns: "bool success", doc: "", category: "Process Control" },
+    Builtin { name: "getppid", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "parent pid", doc: "", category: "Process Control" },
+    Builtin { name: "unsetenv", aliases: &["env_unset"], receiver: None, params: &["v"], min_args: 1, max_args: None, returns: "null", doc: "setenv(key, val) already exists; also unsetenv", category: "Process Control" },
+    Builtin { name: "fork", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "pid", doc: "0 = child, >0 = parent with child pid", category: "Process Control" },
+    Builtin { name: "waitpid", aliases: &[], receiver: None, params: &["pid?"], min_args: 0, max_args: None, returns: "exit_code", doc: "", category: "Process Control" },
+    Builtin { name: "SIGTERM", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "signal constants", category: "Process Control" },
+    Builtin { name: "SIGKILL", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Process Control" },
+    Builtin { name: "SIGINT", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Process Control" },
+    Builtin { name: "SIGHUP", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Process Control" },
+    Builtin { name: "SIGUSR1", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Process Control" },
+    Builtin { name: "SIGUSR2", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Process Control" },
+    Builtin { name: "SIGSTOP", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Process Control" },
+    Builtin { name: "SIGCONT", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Process Control" },
+    Builtin { name: "sha256_native", aliases: &["sha256n"], receiver: None, params: &["string_or_bytes"], min_args: 1, max_args: None, returns: "hex string", doc: "", category: "Native Crypto" },
+    Builtin { name: "hmac_sha256", aliases: &[], receiver: None, params: &["key", "data"], min_args: 2, max_args: None, returns: "hex", doc: "", category: "Native Crypto" },
+    Builtin { name: "xor_bytes", aliases: &["xor_encrypt", "xor_decrypt"], receiver: None, params: &["bytes_array", "key_byte"], min_args: 2, max_args: None, returns: "bytes_array", doc: "XOR cipher", category: "Native Crypto" },
+    Builtin { name: "xor_key", aliases: &["xor_encrypt_key"], receiver: None, params: &["bytes_array", "key_bytes_array"], min_args: 2, max_args: None, returns: "bytes_array", doc: "repeating XOR", category: "Native Crypto" },
+    Builtin { name: "crc32", aliases: &[], receiver: None, params: &["bytes_array or string"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Native Crypto" },
+    Builtin { name: "file_stat", aliases: &[], receiver: None, params: &["path"], min_args: 1, max_args: None, returns: "map {size, mode, uid, gid, mtime, is_dir, is_file, is_link}", doc: "", category: "File System Ops" },
+    Builtin { name: "file_lstat", aliases: &["lstat"], receiver: None, params: &["path"], min_args: 1, max_args: None, returns: "map", doc: "", category: "File System Ops" },
+    Builtin { name: "chmod", aliases: &["file_chmod"], receiver: None, params: &["path", "mode_oct"], min_args: 2, max_args: None, returns: "null", doc: "", category: "File System Ops" },
+    Builtin { name: "symlink", aliases: &["file_symlink"], receiver: None, params: &["target", "link_path"], min_args: 2, max_args: None, returns: "null", doc: "", category: "File System Ops" },
+    Builtin { name: "readlink", aliases: &[], receiver: None, params: &["path"], min_args: 1, max_args: None, returns: "target string", doc: "", category: "File System Ops" },
+    Builtin { name: "hardlink", aliases: &["file_hardlink"], receiver: None, params: &["src", "dst"], min_args: 2, max_args: None, returns: "null", doc: "", category: "File System Ops" },
+    Builtin { name: "truncate", aliases: &["file_truncate"], receiver: None, params: &["path", "size"], min_args: 2, max_args: None, returns: "null", doc: "", category: "File System Ops" },
+    Builtin { name: "glob", aliases: &["file_glob"], receiver: None, params: &["pattern"], min_args: 1, max_args: None, returns: "array of paths", doc: "", category: "File System Ops" },
+    Builtin { name: "mmap_file", aliases: &["mmap_read"], receiver: None, params: &["path"], min_args: 1, max_args: None, returns: "[ptr_int, size_int]", doc: "── mmap (memory-mapped files) ─────────────────────────────────── readonly", category: "File System Ops" },
+    Builtin { name: "mmap_anon", aliases: &[], receiver: None, params: &["size"], min_args: 1, max_args: None, returns: "[ptr_int, size_int]", doc: "anonymous RW mapping", category: "File System Ops" },
+    Builtin { name: "munmap", aliases: &[], receiver: None, params: &["ptr", "size"], min_args: 2, max_args: None, returns: "null", doc: "", category: "File System Ops" },
+    Builtin { name: "mprotect", aliases: &[], receiver: None, params: &["ptr", "size", "prot_flags"], min_args: 3, max_args: None, returns: "bool", doc: "", category: "File System Ops" },
+    Builtin { name: "PROT_READ", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "mmap protection constants", category: "File System Ops" },
+    Builtin { name: "PROT_WRITE", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "File System Ops" },
+    Builtin { name: "PROT_EXEC", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "File System Ops" },
+    Builtin { name: "PROT_NONE", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "File System Ops" },
+    Builtin { name: "db_open", aliases: &[], receiver: None, params: &["path"], min_args: 1, max_args: None, returns: "int handle", doc: "", category: "SQLite / embedded database" },
+    Builtin { name: "db_open_memory", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int handle", doc: "in-memory DB", category: "SQLite / embedded database" },
+    Builtin { name: "db_exec", aliases: &[], receiver: None, params: &["handle", "sql"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "SQLite / embedded database" },
+    Builtin { name: "db_query", aliases: &[], receiver: None, params: &["handle", "sql"], min_args: 2, max_args: None, returns: "array of maps", doc: "", category: "SQLite / embedded database" },
+    Builtin { name: "db_query_one", aliases: &[], receiver: None, params: &["handle", "sql"], min_args: 2, max_args: None, returns: "map or null", doc: "", category: "SQLite / embedded database" },
+    Builtin { name: "db_last_insert_id", aliases: &[], receiver: None, params: &["handle"], min_args: 1, max_args: None, returns: "int", doc: "", category: "SQLite / embedded database" },
+    Builtin { name: "db_close", aliases: &[], receiver: None, params: &["handle"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "SQLite / embedded database" },
+    Builtin { name: "gzip_compress", aliases: &[], receiver: None, params: &["bytes_array"], min_args: 1, max_args: None, returns: "bytes_array", doc: "", category: "Compression" },
+    Builtin { name: "gzip_decompress", aliases: &[], receiver: None, params: &["bytes_array"], min_args: 1, max_args: None, returns: "bytes_array", doc: "", category: "Compression" },
+    Builtin { name: "zlib_compress", aliases: &[], receiver: None, params: &["bytes_array"], min_args: 1, max_args: None, returns: "bytes_array", doc: "", category: "Compression" },
+    Builtin { name: "zlib_decompress", aliases: &[], receiver: None, params: &["bytes_array"], min_args: 1, max_args: None, returns: "bytes_array", doc: "", category: "Compression" },
+    Builtin { name: "deflate_compress", aliases: &[], receiver: None, params: &["bytes_array"], min_args: 1, max_args: None, returns: "bytes_array", doc: "", category: "Compression" },
+    Builtin { name: "deflate_decompress", aliases: &[], receiver: None, params: &["bytes_array"], min_args: 1, max_args: None, returns: "bytes_array", doc: "", category: "Compression" },
+    Builtin { name: "gzip_compress_level", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "array", doc: "compress_level(bytes, level) -> bytes  (gzip with custom level 0-9)", category: "Compression" },
+    Builtin { name: "gzip_decompress_str", aliases: &[], receiver: None, params: &["bytes_array"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Compression" },
+    Builtin { name: "chr", aliases: &[], receiver: None, params: &["n"], min_args: 1, max_args: None, returns: "single-char string", doc: "e.g. chr(65) == \"A\"", category: "Fundamental string / byte primitives" },
+    Builtin { name: "ord", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "int", doc: "code point of first character", category: "Fundamental string / byte primitives" },
+    Builtin { name: "str_bytes", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "array of ints", doc: "UTF-8 bytes", category: "Fundamental string / byte primitives" },
+    Builtin { name: "bytes_str", aliases: &[], receiver: None, params: &["array"], min_args: 1, max_args: None, returns: "string", doc: "from UTF-8 byte array", category: "Fundamental string / byte primitives" },
+    Builtin { name: "str_chars", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "array of single-char strings", doc: "", category: "Fundamental string / byte primitives" },
+    Builtin { name: "str_codepoints", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "array of ints", doc: "Unicode code points", category: "Fundamental string / byte primitives" },
+    Builtin { name: "str_repeat", aliases: &[], receiver: None, params: &["s", "n"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Fundamental string / byte primitives" },
+    Builtin { name: "str_pad_left", aliases: &[], receiver: None, params: &["s", "width", "pad_char"], min_args: 3, max_args: None, returns: "string", doc: "", category: "Fundamental string / byte primitives" },
+    Builtin { name: "str_pad_right", aliases: &[], receiver: None, params: &["s", "width", "pad_char"], min_args: 3, max_args: None, returns: "string", doc: "", category: "Fundamental string / byte primitives" },
+    Builtin { name: "printf", aliases: &[], receiver: None, params: &["fmt", "...args?"], min_args: 1, max_args: None, returns: "", doc: "", category: "Fundamental string / byte primitives" },
+    Builtin { name: "aes128_ecb_encrypt", aliases: &[], receiver: None, params: &["plaintext_bytes", "key_bytes"], min_args: 2, max_args: None, returns: "bytes", doc: "", category: "AES-128" },
+    Builtin { name: "aes128_ecb_decrypt", aliases: &[], receiver: None, params: &["ciphertext_bytes", "key_bytes"], min_args: 2, max_args: None, returns: "bytes", doc: "", category: "AES-128" },
+    Builtin { name: "aes128_cbc_encrypt", aliases: &[], receiver: None, params: &["plaintext_bytes", "key_bytes", "iv_bytes"], min_args: 3, max_args: None, returns: "bytes", doc: "", category: "AES-128" },
+    Builtin { name: "aes128_cbc_decrypt", aliases: &[], receiver: None, params: &["ciphertext_bytes", "key_bytes", "iv_bytes"], min_args: 3, max_args: None, returns: "bytes", doc: "", category: "AES-128" },
+    Builtin { name: "aes128_key_from_str", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "bytes", doc: "takes first 16 bytes, zero-pads", category: "AES-128" },
+    Builtin { name: "foreach", aliases: &[], receiver: None, params: &["array", "closure"], min_args: 2, max_args: None, returns: "null", doc: "", category: "Functional array helpers" },
+    Builtin { name: "enumerate", aliases: &[], receiver: None, params: &["array"], min_args: 1, max_args: None, returns: "array of", doc: "index, value] pairs", category: "Functional array helpers" },
+    Builtin { name: "distinct", aliases: &[], receiver: None, params: &["array"], min_args: 1, max_args: None, returns: "array", doc: "removes duplicates, preserves order", category: "Functional array helpers" },
+    Builtin { name: "array_sum", aliases: &[], receiver: None, params: &["array"], min_args: 1, max_args: None, returns: "number", doc: "", category: "Functional array helpers" },
+    Builtin { name: "array_min", aliases: &[], receiver: None, params: &["array"], min_args: 1, max_args: None, returns: "value", doc: "", category: "Functional array helpers" },
+    Builtin { name: "array_max", aliases: &[], receiver: None, params: &["array"], min_args: 1, max_args: None, returns: "value", doc: "", category: "Functional array helpers" },
+    Builtin { name: "array_contains", aliases: &[], receiver: None, params: &["array_or_string", "value"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "Functional array helpers" },
+    Builtin { name: "array_index", aliases: &[], receiver: None, params: &["array", "value"], min_args: 2, max_args: None, returns: "int", doc: "-1 if not found", category: "Functional array helpers" },
+    Builtin { name: "sort_cmp", aliases: &[], receiver: None, params: &["array", "closure"], min_args: 2, max_args: None, returns: "sorted array", doc: "fn(a,b) returns negative/0/positive", category: "Functional array helpers" },
+    Builtin { name: "map_keys", aliases: &[], receiver: None, params: &["map"], min_args: 1, max_args: None, returns: "sorted array of keys", doc: "", category: "Functional array helpers" },
+    Builtin { name: "map_values", aliases: &[], receiver: None, params: &["map"], min_args: 1, max_args: None, returns: "array of values", doc: "", category: "Functional array helpers" },
+    Builtin { name: "map_entries", aliases: &[], receiver: None, params: &["map"], min_args: 1, max_args: None, returns: "array of", doc: "key, value] pairs", category: "Functional array helpers" },
+    Builtin { name: "map_merge", aliases: &[], receiver: None, params: &["m1", "m2"], min_args: 2, max_args: None, returns: "merged map", doc: "m2 overwrites m1", category: "Functional array helpers" },
+    Builtin { name: "map_has", aliases: &[], receiver: None, params: &["map", "key"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "Functional array helpers" },
+    Builtin { name: "map_remove", aliases: &[], receiver: None, params: &["map", "key"], min_args: 2, max_args: None, returns: "new map without key", doc: "", category: "Functional array helpers" },
+    Builtin { name: "map_from_arrays", aliases: &[], receiver: None, params: &["keys", "values"], min_args: 2, max_args: None, returns: "map", doc: "", category: "Functional array helpers" },
+    Builtin { name: "https_get", aliases: &[], receiver: None, params: &["url"], min_args: 1, max_args: None, returns: "string", doc: "", category: "HTTPS" },
+    Builtin { name: "https_post", aliases: &[], receiver: None, params: &["url", "body", "content_type?"], min_args: 2, max_args: None, returns: "string", doc: "", category: "HTTPS" },
+    Builtin { name: "https_request", aliases: &[], receiver: None, params: &["method", "url", "headers_map?", "body?"], min_args: 2, max_args: None, returns: "{status, body, headers}", doc: "", category: "HTTPS" },
+    Builtin { name: "ws_connect", aliases: &[], receiver: None, params: &["url"], min_args: 1, max_args: None, returns: "handle", doc: "", category: "WebSocket" },
+    Builtin { name: "ws_send", aliases: &[], receiver: None, params: &["handle", "message"], min_args: 2, max_args: None, returns: "null", doc: "", category: "WebSocket" },
+    Builtin { name: "ws_recv", aliases: &[], receiver: None, params: &["handle"], min_args: 1, max_args: None, returns: "string", doc: "", category: "WebSocket" },
+    Builtin { name: "ws_close", aliases: &[], receiver: None, params: &["handle"], min_args: 1, max_args: None, returns: "null", doc: "", category: "WebSocket" },
+    Builtin { name: "dlopen", aliases: &[], receiver: None, params: &["path"], min_args: 1, max_args: None, returns: "handle", doc: "", category: "Dynamic Libraries" },
+    Builtin { name: "dlsym", aliases: &[], receiver: None, params: &["handle", "symbol"], min_args: 2, max_args: None, returns: "fn_handle", doc: "", category: "Dynamic Libraries" },
+    Builtin { name: "dlcall_i", aliases: &[], receiver: None, params: &["sym_handle", "args..."], min_args: 1, max_args: None, returns: "int", doc: "call fn returning i64", category: "Dynamic Libraries" },
+    Builtin { name: "dlclose", aliases: &[], receiver: None, params: &["handle"], min_args: 1, max_args: None, returns: "null", doc: "", category: "Dynamic Libraries" },
+    Builtin { name: "raw_socket", aliases: &[], receiver: None, params: &["proto?"], min_args: 0, max_args: None, returns: "fd", doc: "proto: 1=ICMP, 6=TCP, 17=UDP, 255=RAW", category: "Raw sockets" },
+    Builtin { name: "raw_send", aliases: &[], receiver: None, params: &["fd_handle", "dest_ip", "payload_bytes"], min_args: 3, max_args: None, returns: "int", doc: "", category: "Raw sockets" },
+    Builtin { name: "raw_recv", aliases: &[], receiver: None, params: &["fd_handle", "max_bytes?"], min_args: 1, max_args: None, returns: "array of bytes", doc: "", category: "Raw sockets" },
+    Builtin { name: "raw_close", aliases: &[], receiver: None, params: &["fd_handle"], min_args: 1, max_args: None, returns: "null", doc: "", category: "Raw sockets" },
+    Builtin { name: "signal_send", aliases: &[], receiver: None, params: &["pid", "signum"], min_args: 2, max_args: None, returns: "0 on success", doc: "", category: "Signal handling" },
+    Builtin { name: "signal_check", aliases: &[], receiver: None, params: &["signum?"], min_args: 0, max_args: None, returns: "count of times received", doc: "0 if none", category: "Signal handling" },
+    Builtin { name: "signal_trap", aliases: &[], receiver: None, params: &["signum?"], min_args: 0, max_args: None, returns: "null", doc: "", category: "Signal handling" },
+    Builtin { name: "signal_reset", aliases: &[], receiver: None, params: &["signum?"], min_args: 0, max_args: None, returns: "null", doc: "", category: "Signal handling" },
+    Builtin { name: "gui_window", aliases: &[], receiver: None, params: &["title", "width", "height"], min_args: 3, max_args: None, returns: "handle", doc: "", category: "GUI window" },
+    Builtin { name: "gui_set_pixel", aliases: &[], receiver: None, params: &["handle", "x", "y", "rgb"], min_args: 4, max_args: None, returns: "null", doc: "", category: "GUI window" },
+    Builtin { name: "gui_fill", aliases: &[], receiver: None, params: &["handle", "rgb"], min_args: 2, max_args: None, returns: "null", doc: "", category: "GUI window" },
+    Builtin { name: "gui_present", aliases: &[], receiver: None, params: &["handle"], min_args: 1, max_args: None, returns: "null", doc: "", category: "GUI window" },
+    Builtin { name: "gui_is_open", aliases: &[], receiver: None, params: &["handle"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "GUI window" },
+    Builtin { name: "gui_get_keys", aliases: &[], receiver: None, params: &["handle"], min_args: 1, max_args: None, returns: "array of key names", doc: "", category: "GUI window" },
+    Builtin { name: "gui_get_mouse", aliases: &[], receiver: None, params: &["handle"], min_args: 1, max_args: None, returns: "{x, y, left, right, middle}", doc: "", category: "GUI window" },
+    Builtin { name: "gui_close", aliases: &[], receiver: None, params: &["handle"], min_args: 1, max_args: None, returns: "null", doc: "", category: "GUI window" },
+    Builtin { name: "gui_rgb", aliases: &[], receiver: None, params: &["r", "g", "b"], min_args: 3, max_args: None, returns: "packed 0xRRGGBB color", doc: "", category: "GUI window" },
+    Builtin { name: "gui_size", aliases: &[], receiver: None, params: &["handle"], min_args: 1, max_args: None, returns: "{w, h}", doc: "", category: "GUI window" },
+    Builtin { name: "gui_rect", aliases: &[], receiver: None, params: &["handle", "x", "y", "w", "h", "rgb"], min_args: 6, max_args: None, returns: "null", doc: "", category: "GUI window" },
+    Builtin { name: "gui_rect_outline", aliases: &[], receiver: None, params: &["handle", "x", "y", "w", "h", "rgb"], min_args: 6, max_args: None, returns: "null", doc: "", category: "GUI window" },
+    Builtin { name: "gui_line", aliases: &[], receiver: None, params: &["handle", "x0", "y0", "x1", "y1", "rgb"], min_args: 6, max_args: None, returns: "null", doc: "", category: "GUI window" },
+    Builtin { name: "gui_circle", aliases: &[], receiver: None, params: &["handle", "cx", "cy", "r", "rgb"], min_args: 5, max_args: None, returns: "null", doc: "", category: "GUI window" },
+    Builtin { name: "gui_circle_outline", aliases: &[], receiver: None, params: &["handle", "cx", "cy", "r", "rgb"], min_args: 5, max_args: None, returns: "null", doc: "", category: "GUI window" },
+    Builtin { name: "gui_set_title", aliases: &[], receiver: None, params: &["handle", "title"], min_args: 2, max_args: None, returns: "null", doc: "", category: "GUI window" },
+    Builtin { name: "execve", aliases: &[], receiver: None, params: &["path", "args_array?", "env_map?"], min_args: 1, max_args: None, returns: "never returns on success", doc: "", category: "Process" },
+    Builtin { name: "getuid", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "UID / capabilities" },
+    Builtin { name: "getgid", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "UID / capabilities" },
+    Builtin { name: "geteuid", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "UID / capabilities" },
+    Builtin { name: "getegid", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "UID / capabilities" },
+    Builtin { name: "setuid", aliases: &[], receiver: None, params: &["uid?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "UID / capabilities" },
+    Builtin { name: "setgid", aliases: &[], receiver: None, params: &["gid?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "UID / capabilities" },
+    Builtin { name: "chroot", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "int", doc: "", category: "UID / capabilities" },
+    Builtin { name: "umask", aliases: &[], receiver: None, params: &["mask?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "UID / capabilities" },
+    Builtin { name: "ioctl", aliases: &[], receiver: None, params: &["fd", "request", "arg?"], min_args: 2, max_args: None, returns: "int", doc: "── ioctl ──────────────────────────────────────────────────────────", category: "UID / capabilities" },
+    Builtin { name: "shm_create", aliases: &[], receiver: None, params: &["key_or_size", "size?"], min_args: 1, max_args: None, returns: "{id, addr}", doc: "", category: "Shared memory" },
+    Builtin { name: "shm_write", aliases: &[], receiver: None, params: &["addr", "bytes_or_string"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Shared memory" },
+    Builtin { name: "shm_read", aliases: &[], receiver: None, params: &["addr", "size"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Shared memory" },
+    Builtin { name: "shm_remove", aliases: &[], receiver: None, params: &["id_or_map"], min_args: 1, max_args: None, returns: "null", doc: "", category: "Shared memory" },
+    Builtin { name: "tar_create", aliases: &[], receiver: None, params: &["output_path", "files_array"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "TAR archive" },
+    Builtin { name: "tar_list", aliases: &[], receiver: None, params: &["path"], min_args: 1, max_args: None, returns: "array of filenames", doc: "", category: "TAR archive" },
+    Builtin { name: "tar_extract", aliases: &[], receiver: None, params: &["path", "dest_dir"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "TAR archive" },
+    Builtin { name: "ptrace_attach", aliases: &[], receiver: None, params: &["pid"], min_args: 1, max_args: None, returns: "0 on success", doc: "── ptrace ─────────────────────────────────────────────────────────", category: "TAR archive" },
+    Builtin { name: "ptrace_detach", aliases: &[], receiver: None, params: &["pid"], min_args: 1, max_args: None, returns: "int", doc: "", category: "TAR archive" },
+    Builtin { name: "ptrace_peekdata", aliases: &[], receiver: None, params: &["pid", "addr"], min_args: 2, max_args: None, returns: "i64", doc: "", category: "TAR archive" },
+    Builtin { name: "ptrace_pokedata", aliases: &[], receiver: None, params: &["pid", "addr", "value"], min_args: 3, max_args: None, returns: "int", doc: "", category: "TAR archive" },
+    Builtin { name: "ptrace_cont", aliases: &[], receiver: None, params: &["pid", "sig?"], min_args: 1, max_args: None, returns: "int", doc: "", category: "TAR archive" },
+    Builtin { name: "old_regex_replace", aliases: &[], receiver: None, params: &["pat", "rep", "text"], min_args: 3, max_args: None, returns: "string", doc: "", category: "Regex" },
+    Builtin { name: "regex_find_all", aliases: &[], receiver: None, params: &["pattern", "text"], min_args: 2, max_args: None, returns: "array of strings", doc: "", category: "Regex" },
+    Builtin { name: "old_regex_captures", aliases: &[], receiver: None, params: &["pat", "text"], min_args: 2, max_args: None, returns: "array or null", doc: "regex_captures(pattern, text) -> array of [full, group1, group2, ...]  or null", category: "Regex" },
+    Builtin { name: "regex_captures_all", aliases: &[], receiver: None, params: &["pattern", "text"], min_args: 2, max_args: None, returns: "array of arrays", doc: "", category: "Regex" },
+    Builtin { name: "old_regex_split", aliases: &[], receiver: None, params: &["pat", "text"], min_args: 2, max_args: None, returns: "array", doc: "regex_split(pattern, text) -> array of strings", category: "Regex" },
+    Builtin { name: "mkfifo", aliases: &[], receiver: None, params: &["path", "mode?"], min_args: 1, max_args: None, returns: "0 on success", doc: "", category: "Named pipes" },
+    Builtin { name: "pipe_create", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "{read_fd, write_fd}", doc: "", category: "Named pipes" },
+    Builtin { name: "fd_read", aliases: &[], receiver: None, params: &["fd", "size?"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Named pipes" },
+    Builtin { name: "fd_write", aliases: &[], receiver: None, params: &["fd", "data"], min_args: 2, max_args: None, returns: "bytes_written", doc: "", category: "Named pipes" },
+    Builtin { name: "fd_close", aliases: &[], receiver: None, params: &["fd"], min_args: 1, max_args: None, returns: "null", doc: "", category: "Named pipes" },
+    Builtin { name: "env_list", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "map of all env vars", doc: "", category: "Environment variables" },
+    Builtin { name: "setenv", aliases: &[], receiver: None, params: &["key", "value"], min_args: 2, max_args: None, returns: "null", doc: "", category: "Environment variables" },
+    Builtin { name: "env_del", aliases: &[], receiver: None, params: &["key"], min_args: 1, max_args: None, returns: "null", doc: "", category: "Environment variables" },
+    Builtin { name: "sendfile", aliases: &[], receiver: None, params: &["out_fd", "in_fd", "offset?", "count?"], min_args: 3, max_args: None, returns: "bytes_sent", doc: "── sendfile (fast file copy to socket/fd) ─────────────────────────", category: "Environment variables" },
+    Builtin { name: "epoll_create", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "epoll_fd", doc: "── epoll (Linux event notification) ──────────────────────────────", category: "Environment variables" },
+    Builtin { name: "epoll_add", aliases: &[], receiver: None, params: &["epoll_fd", "watch_fd", "events?"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "epoll_wait", aliases: &[], receiver: None, params: &["epoll_fd", "max_events?", "timeout_ms?"], min_args: 1, max_args: None, returns: "array of ready fds", doc: "", category: "Environment variables" },
+    Builtin { name: "fcntl", aliases: &[], receiver: None, params: &["fd", "cmd", "arg?"], min_args: 2, max_args: None, returns: "int", doc: "── fd-level control ───────────────────────────────────────────────", category: "Environment variables" },
+    Builtin { name: "F_GETFL", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "fcntl constants as values", category: "Environment variables" },
+    Builtin { name: "F_SETFL", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "O_NONBLOCK", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "O_CLOEXEC", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "F_DUPFD", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "dup", aliases: &[], receiver: None, params: &["fd"], min_args: 1, max_args: None, returns: "new_fd", doc: "", category: "Environment variables" },
+    Builtin { name: "dup2", aliases: &[], receiver: None, params: &["old_fd", "new_fd"], min_args: 2, max_args: None, returns: "new_fd", doc: "", category: "Environment variables" },
+    Builtin { name: "lseek", aliases: &[], receiver: None, params: &["fd", "offset", "whence?"], min_args: 2, max_args: None, returns: "new_pos", doc: "whence: 0=SET 1=CUR 2=END", category: "Environment variables" },
+    Builtin { name: "flock", aliases: &[], receiver: None, params: &["fd", "op"], min_args: 2, max_args: None, returns: "0", doc: "op: 1=LOCK_SH 2=LOCK_EX 4=LOCK_NB 8=LOCK_UN", category: "Environment variables" },
+    Builtin { name: "LOCK_SH", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "LOCK_EX", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "LOCK_UN", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "LOCK_NB", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "ftruncate", aliases: &[], receiver: None, params: &["fd", "length"], min_args: 2, max_args: None, returns: "0", doc: "", category: "Environment variables" },
+    Builtin { name: "open", aliases: &["fd_open"], receiver: None, params: &["path", "flags?", "mode?"], min_args: 1, max_args: None, returns: "fd", doc: "", category: "Environment variables" },
+    Builtin { name: "O_RDONLY", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "O_WRONLY", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "O_RDWR", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "O_CREAT", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "O_TRUNC", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "O_APPEND", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "setsockopt", aliases: &[], receiver: None, params: &["fd", "level", "name", "value"], min_args: 4, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "getsockopt", aliases: &[], receiver: None, params: &["fd", "level", "name"], min_args: 3, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "SOL_SOCKET", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "socket options constants", category: "Environment variables" },
+    Builtin { name: "SO_REUSEADDR", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "SO_REUSEPORT", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "SO_KEEPALIVE", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "SO_SNDBUF", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "SO_RCVBUF", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "IPPROTO_TCP", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "TCP_NODELAY", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "IN_ACCESS", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "── inotify constants ──────────────────────────────────────────────", category: "Environment variables" },
+    Builtin { name: "IN_MODIFY", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "IN_ATTRIB", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "IN_CLOSE_WRITE", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "IN_CLOSE_NOWRITE", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "IN_OPEN", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "IN_MOVED_FROM", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "IN_MOVED_TO", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "IN_CREATE", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "IN_DELETE", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "IN_DELETE_SELF", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "IN_MOVE_SELF", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "IN_ALL_EVENTS", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Environment variables" },
+    Builtin { name: "inotify_init", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "fd", doc: "", category: "Environment variables" },
+    Builtin { name: "inotify_add_watch", aliases: &[], receiver: None, params: &["fd", "path", "mask"], min_args: 3, max_args: None, returns: "wd", doc: "", category: "Environment variables" },
+    Builtin { name: "inotify_rm_watch", aliases: &[], receiver: None, params: &["fd", "wd"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "Environment variables" },
+    Builtin { name: "inotify_read", aliases: &[], receiver: None, params: &["fd"], min_args: 1, max_args: None, returns: "array of {wd, mask, cookie, name}", doc: "", category: "Environment variables" },
+    Builtin { name: "getrandom", aliases: &[], receiver: None, params: &["n?"], min_args: 0, max_args: None, returns: "array of n random bytes", doc: "", category: "Secure random" },
+    Builtin { name: "random_hex", aliases: &[], receiver: None, params: &["n?"], min_args: 0, max_args: None, returns: "hex string of n random bytes", doc: "", category: "Secure random" },
+    Builtin { name: "random_int", aliases: &["randint"], receiver: None, params: &["min?", "max?"], min_args: 2, max_args: None, returns: "random i64 in", doc: "min, max", category: "Secure random" },
+    Builtin { name: "random_float", aliases: &["randf"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "f64 in", doc: "0.0, 1.0", category: "Secure random" },
+    Builtin { name: "shuffle", aliases: &[], receiver: None, params: &["array"], min_args: 1, max_args: None, returns: "shuffled copy", doc: "", category: "Secure random" },
+    Builtin { name: "proc_self_maps", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "array of {addr, perms, name} maps", doc: "── /proc filesystem ───────────────────────────────────────────────", category: "Secure random" },
+    Builtin { name: "proc_status", aliases: &[], receiver: None, params: &["pid?"], min_args: 0, max_args: None, returns: "map of key=value from /proc/PID/status", doc: "", category: "Secure random" },
+    Builtin { name: "proc_fds", aliases: &[], receiver: None, params: &["pid?"], min_args: 0, max_args: None, returns: "array of open fd numbers", doc: "", category: "Secure random" },
+    Builtin { name: "proc_cmdline", aliases: &[], receiver: None, params: &["pid?"], min_args: 0, max_args: None, returns: "array of argv strings", doc: "", category: "Secure random" },
+    Builtin { name: "proc_list", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "array of {pid, name} for all processes", doc: "", category: "Secure random" },
+    Builtin { name: "mem_read", aliases: &[], receiver: None, params: &["pid", "addr", "size"], min_args: 3, max_args: None, returns: "bytes", doc: "reads /proc/PID/mem", category: "Secure random" },
+    Builtin { name: "str_is_alpha", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Extra string predicates" },
+    Builtin { name: "str_is_digit", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Extra string predicates" },
+    Builtin { name: "str_is_alnum", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Extra string predicates" },
+    Builtin { name: "str_is_upper", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Extra string predicates" },
+    Builtin { name: "str_is_lower", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Extra string predicates" },
+    Builtin { name: "str_is_space", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Extra string predicates" },
+    Builtin { name: "str_is_hex", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Extra string predicates" },
+    Builtin { name: "str_reverse", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Extra string predicates" },
+    Builtin { name: "str_count", aliases: &[], receiver: None, params: &["s", "sub"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Extra string predicates" },
+    Builtin { name: "str_center", aliases: &[], receiver: None, params: &["s", "w", "fill?"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Extra string predicates" },
+    Builtin { name: "str_zfill", aliases: &[], receiver: None, params: &["s", "w"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Extra string predicates" },
+    Builtin { name: "str_indent", aliases: &[], receiver: None, params: &["s", "prefix"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Extra string predicates" },
+    Builtin { name: "str_dedent", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Extra string predicates" },
+    Builtin { name: "monotonic_ns", aliases: &["clock_mono"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "i64 nanoseconds", doc: "CLOCK_MONOTONIC", category: "High-resolution time" },
+    Builtin { name: "realtime_ns", aliases: &["clock_real"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "i64 nanoseconds since epoch", doc: "CLOCK_REALTIME", category: "High-resolution time" },
+    Builtin { name: "sleep_us", aliases: &["usleep"], receiver: None, params: &["n?"], min_args: 0, max_args: None, returns: "sleep microseconds", doc: "", category: "High-resolution time" },
+    Builtin { name: "sleep_ns", aliases: &["nanosleep"], receiver: None, params: &["n?"], min_args: 0, max_args: None, returns: "nanosleep", doc: "", category: "High-resolution time" },
+    Builtin { name: "hostname", aliases: &["gethostname"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "System information" },
+    Builtin { name: "uname", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "{sysname, nodename, release, version, machine}", doc: "", category: "System information" },
+    Builtin { name: "sysinfo", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "{uptime, totalram, freeram, procs, loads}", doc: "", category: "System information" },
+    Builtin { name: "self_pid", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "i64", doc: "", category: "System information" },
+    Builtin { name: "nprocs", aliases: &["ncpus"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "number of CPU cores", doc: "", category: "System information" },
+    Builtin { name: "page_size", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "system page size", doc: "", category: "System information" },
+    Builtin { name: "winsize", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "{rows, cols}", doc: "", category: "Terminal dimensions" },
+    Builtin { name: "getaddrinfo_ips", aliases: &[], receiver: None, params: &["host"], min_args: 1, max_args: None, returns: "array of IP strings", doc: "", category: "DNS / networking" },
+    Builtin { name: "ip_to_int", aliases: &["inet_aton"], receiver: None, params: &["ip_str"], min_args: 1, max_args: None, returns: "i64", doc: "IPv4 only", category: "DNS / networking" },
+    Builtin { name: "int_to_ip", aliases: &["inet_ntoa"], receiver: None, params: &["n"], min_args: 1, max_args: None, returns: "string", doc: "", category: "DNS / networking" },
+    Builtin { name: "is_ipv4", aliases: &[], receiver: None, params: &["s?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "DNS / networking" },
+    Builtin { name: "is_ipv6", aliases: &[], receiver: None, params: &["s?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "DNS / networking" },
+    Builtin { name: "WNOHANG", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "WNOHANG constant" },
+    Builtin { name: "fd_select", aliases: &["select"], receiver: None, params: &["read_fds", "write_fds?", "except_fds?", "timeout_us?"], min_args: 1, max_args: None, returns: "{readable, writable}", doc: "", category: "WNOHANG constant" },
+    Builtin { name: "b64enc", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Base64" },
+    Builtin { name: "b64dec", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Base64" },
+    Builtin { name: "bytes_to_str", aliases: &["bytes_to_string"], receiver: None, params: &["array"], min_args: 1, max_args: None, returns: "utf8 string", doc: "── bytes <-> string helpers ──────────────────────────────────────", category: "Base64" },
+    Builtin { name: "str_to_bytes", aliases: &["string_to_bytes"], receiver: None, params: &["str"], min_args: 1, max_args: None, returns: "array of ints", doc: "", category: "Base64" },
+    Builtin { name: "file_size", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "int", doc: "", category: "File metadata" },
+    Builtin { name: "file_mtime", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "int", doc: "", category: "File metadata" },
+    Builtin { name: "file_is_dir", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "File metadata" },
+    Builtin { name: "file_is_file", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "File metadata" },
+    Builtin { name: "dir_files", aliases: &["dir_entries"], receiver: None, params: &["path"], min_args: 1, max_args: None, returns: "array of {name, size, is_dir} entries", doc: "", category: "File metadata" },
+    Builtin { name: "type_of", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Type inspection" },
+    Builtin { name: "is_func", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Type inspection" },
+    Builtin { name: "popcount", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "Bit operations" },
+    Builtin { name: "leading_zeros", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "Bit operations" },
+    Builtin { name: "trailing_zeros", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "Bit operations" },
+    Builtin { name: "bit_reverse", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "Bit operations" },
+    Builtin { name: "rotate_left", aliases: &[], receiver: None, params: &["n", "arg2"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Bit operations" },
+    Builtin { name: "rotate_right", aliases: &[], receiver: None, params: &["n", "arg2"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Bit operations" },
+    Builtin { name: "tcp_server_fd", aliases: &[], receiver: None, params: &["port", "backlog?"], min_args: 1, max_args: None, returns: "fd", doc: "non-blocking capable", category: "Raw TCP server fd" },
+    Builtin { name: "tcp_accept_fd", aliases: &[], receiver: None, params: &["server_fd"], min_args: 1, max_args: None, returns: "{fd, addr, port}", doc: "", category: "Raw TCP server fd" },
+    Builtin { name: "tcp_connect_fd", aliases: &[], receiver: None, params: &["host", "port"], min_args: 2, max_args: None, returns: "fd", doc: "", category: "Raw TCP server fd" },
+    Builtin { name: "udp_socket", aliases: &[], receiver: None, params: &["port?"], min_args: 1, max_args: None, returns: "fd", doc: "", category: "Raw UDP socket" },
+    Builtin { name: "udp_sendto", aliases: &[], receiver: None, params: &["fd", "data", "host", "port"], min_args: 4, max_args: None, returns: "bytes_sent", doc: "", category: "Raw UDP socket" },
+    Builtin { name: "udp_recvfrom", aliases: &[], receiver: None, params: &["fd", "size?"], min_args: 1, max_args: None, returns: "{data, addr, port}", doc: "", category: "Raw UDP socket" },
+    Builtin { name: "try_int", aliases: &[], receiver: None, params: &["s?", "base?"], min_args: 0, max_args: None, returns: "int or null", doc: "── try/parse helpers ─────────────────────────────────────────────", category: "Raw UDP socket" },
+    Builtin { name: "try_float", aliases: &[], receiver: None, params: &["s?"], min_args: 0, max_args: None, returns: "float or null", doc: "", category: "Raw UDP socket" },
+    Builtin { name: "int_to_str", aliases: &["to_base"], receiver: None, params: &["n", "base?"], min_args: 1, max_args: None, returns: "string", doc: "e.g. int_to_str(255, 16) -> \"ff\"", category: "Raw UDP socket" },
+    Builtin { name: "sinh", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "cosh", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "tanh", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "asinh", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "acosh", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "atanh", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "exp2", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "fract", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "PI", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "TAU", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "E", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "INF", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "NAN", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "is_nan", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "is_inf", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "is_finite", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "degrees", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "radians", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "aes_encrypt", aliases: &[], receiver: None, params: &["arg1", "arg2", "arg3"], min_args: 3, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "aes_decrypt", aliases: &[], receiver: None, params: &["arg1", "arg2", "arg3"], min_args: 3, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "chacha_encrypt", aliases: &["chacha20_encrypt"], receiver: None, params: &["arg1", "arg2", "arg3"], min_args: 3, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "chacha_decrypt", aliases: &["chacha20_decrypt"], receiver: None, params: &["arg1", "arg2", "arg3"], min_args: 3, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "ed25519_keygen", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "ed25519_sign", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "ed25519_verify", aliases: &[], receiver: None, params: &["arg1", "arg2", "arg3"], min_args: 3, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "x25519_keygen", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "x25519_dh", aliases: &["x25519_exchange"], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "rsa_keygen", aliases: &["rsa_gen"], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "rsa_encrypt", aliases: &[], receiver: None, params: &["arg1", "arg2", "arg3"], min_args: 3, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "rsa_decrypt", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "sha3_256", aliases: &["sha3256"], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "sha3_512", aliases: &["sha3512"], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "blake3_hash", aliases: &["blake3"], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "blake3_keyed", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "hmac_sha512", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "argon2_hash", aliases: &[], receiver: None, params: &["pwd"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "argon2_verify", aliases: &[], receiver: None, params: &["hash_str", "pwd"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "pbkdf2_sha256", aliases: &[], receiver: None, params: &["arg1", "arg2?", "arg3?", "arg4?"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "sha1_hash", aliases: &["sha1"], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "bignum", aliases: &["big_int"], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "big_add", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "big_sub", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "big_mul", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "big_div", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "big_mod", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "big_pow", aliases: &[], receiver: None, params: &["arg1", "exp_val"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "big_cmp", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "big_to_hex", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "big_from_hex", aliases: &[], receiver: None, params: &["hex_str"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "big_gcd", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "big_lcm", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "big_is_prime", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "big_sqrt", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "big_factorial", aliases: &[], receiver: None, params: &["n"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "big_to_int", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "uuid_v4", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "uuid_v7", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "uuid_parse", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "uuid_nil", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "datetime_now", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "utc_now", aliases: &["datetime_utc"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "date_format", aliases: &[], receiver: None, params: &["ts", "fmt"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "date_parse", aliases: &[], receiver: None, params: &["date_str", "fmt_str"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "date_add", aliases: &[], receiver: None, params: &["ts", "secs"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "date_diff", aliases: &[], receiver: None, params: &["ts1", "ts2"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "date_weekday", aliases: &[], receiver: None, params: &["ts"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "date_components", aliases: &[], receiver: None, params: &["ts"], min_args: 1, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "arr_unique", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "arr_flatten", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "arr_flatten_deep", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "arr_zip", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "arr_unzip", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "arr_chunk", aliases: &[], receiver: None, params: &["arg1", "size"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "arr_window", aliases: &[], receiver: None, params: &["arg1", "size"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "arr_take", aliases: &[], receiver: None, params: &["arg1", "n"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "arr_drop", aliases: &[], receiver: None, params: &["arg1", "n"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "arr_count_by", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "arr_mean", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "arr_median", aliases: &["median"], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "arr_mode", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "arr_variance", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "arr_stdev", aliases: &["stdev"], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "arr_product", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "arr_percentile", aliases: &[], receiver: None, params: &["arg1", "p"], min_args: 2, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "arr_rotate", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "arr_repeat", aliases: &["repeat_arr"], receiver: None, params: &["val", "n"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "arr_interleave", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "arr_partition", aliases: &[], receiver: None, params: &["arg1", "n"], min_args: 2, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "arr_tally", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "arr_combinations", aliases: &[], receiver: None, params: &["arg1", "r"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "arr_permutations", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "map_invert", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "map_filter", aliases: &[], receiver: None, params: &["arg1", "arg2?"], min_args: 1, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "map_count", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "map_update", aliases: &["map_set"], receiver: None, params: &["arg1", "key", "val"], min_args: 3, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "map_get_or", aliases: &[], receiver: None, params: &["arg1", "key", "default"], min_args: 3, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "lz4_compress", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "lz4_decompress", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "lz4_compress_hex", aliases: &[], receiver: None, params: &["data"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "lz4_decompress_hex", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "zstd_compress", aliases: &[], receiver: None, params: &["data?", "arg2?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "zstd_decompress", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "zstd_compress_hex", aliases: &[], receiver: None, params: &["data?", "arg2?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "zstd_decompress_hex", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "csv_parse", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "csv_parse_maps", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "csv_stringify", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "csv_from_maps", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "xml_parse", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "xml_stringify", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "xml_get", aliases: &["xml_find"], receiver: None, params: &["arg1", "tag_name"], min_args: 2, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "yaml_parse", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "yaml_stringify", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "yaml_to_json", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "json_to_yaml", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "chan_create", aliases: &[], receiver: None, params: &["n?"], min_args: 0, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "chan_send", aliases: &[], receiver: None, params: &["id", "val"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "chan_recv", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "chan_try_recv", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "chan_close", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "chan_len", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "atomic_new", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "atomic_load", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "atomic_store", aliases: &[], receiver: None, params: &["id", "val"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "atomic_add", aliases: &[], receiver: None, params: &["id", "delta"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "atomic_sub", aliases: &[], receiver: None, params: &["id", "delta"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "atomic_cas", aliases: &["atomic_compare_exchange"], receiver: None, params: &["id", "expected", "new_val"], min_args: 3, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "atomic_swap", aliases: &[], receiver: None, params: &["id", "new_val"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "img_load", aliases: &[], receiver: None, params: &["path"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "img_save", aliases: &[], receiver: None, params: &["id", "path"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "img_new", aliases: &[], receiver: None, params: &["w", "h", "r", "g", "b"], min_args: 5, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "img_width", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "img_height", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "img_resize", aliases: &[], receiver: None, params: &["id", "w", "h"], min_args: 3, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "img_crop", aliases: &[], receiver: None, params: &["id", "x", "y", "w", "h"], min_args: 5, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "img_rotate90", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "img_rotate180", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "img_rotate270", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "img_fliph", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "img_flipv", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "img_grayscale", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "img_blur", aliases: &["img_gaussian_blur"], receiver: None, params: &["id", "sigma"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "img_brighten", aliases: &[], receiver: None, params: &["id", "val"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "img_contrast", aliases: &[], receiver: None, params: &["id", "c"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "img_get_pixel", aliases: &[], receiver: None, params: &["id", "x", "y"], min_args: 3, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "img_set_pixel", aliases: &[], receiver: None, params: &["id", "x", "y", "r", "g", "b"], min_args: 6, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "img_to_bytes", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "img_info", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "img_thumbnail", aliases: &[], receiver: None, params: &["id", "max_w", "max_h"], min_args: 3, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "img_overlay", aliases: &[], receiver: None, params: &["base_id", "overlay_id", "x", "y"], min_args: 4, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "img_free", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "tui_init", aliases: &["term_raw"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "tui_exit", aliases: &["term_restore"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "tui_clear", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "tui_move", aliases: &["term_goto"], receiver: None, params: &["col", "row"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "tui_print", aliases: &["term_print"], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "tui_color", aliases: &["term_color"], receiver: None, params: &["r", "g", "b", "s"], min_args: 4, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "tui_reset_color", aliases: &["term_reset"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "tui_read_key", aliases: &["term_read_key"], receiver: None, params: &[], min_args: 0, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "tui_poll_key", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "map or null", doc: "", category: "Math extras" },
+    Builtin { name: "tui_hide_cursor", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "tui_show_cursor", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "tui_bold", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "tui_dim", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "tui_italic", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "tui_underline", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "tui_blink", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "tui_bg_color", aliases: &[], receiver: None, params: &["r", "g", "b", "s"], min_args: 4, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "tui_box", aliases: &[], receiver: None, params: &["col", "row", "width", "height"], min_args: 4, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "par_map", aliases: &[], receiver: None, params: &["arg1", "fname"], min_args: 2, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "par_filter", aliases: &[], receiver: None, params: &["arg1", "fname"], min_args: 2, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "par_reduce", aliases: &[], receiver: None, params: &["arg1", "fname", "init"], min_args: 3, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "par_sum", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "par_any", aliases: &[], receiver: None, params: &["arg1", "fname"], min_args: 2, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "par_all", aliases: &[], receiver: None, params: &["arg1", "fname"], min_args: 2, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "par_for_each", aliases: &[], receiver: None, params: &["arg1", "fname"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "par_find", aliases: &[], receiver: None, params: &["arg1", "fname"], min_args: 2, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "par_count", aliases: &[], receiver: None, params: &["arg1", "fname"], min_args: 2, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "par_flat_map", aliases: &[], receiver: None, params: &["arg1", "fname"], min_args: 2, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "par_zip_with", aliases: &[], receiver: None, params: &["arg1", "arg2", "fname"], min_args: 3, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "str_levenshtein", aliases: &[], receiver: None, params: &["a", "b"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "str_jaro", aliases: &[], receiver: None, params: &["a", "b"], min_args: 2, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "str_jaro_winkler", aliases: &[], receiver: None, params: &["a", "b"], min_args: 2, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "str_hamming", aliases: &[], receiver: None, params: &["a", "b"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "str_damerau_levenshtein", aliases: &["str_osa"], receiver: None, params: &["a", "b"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "str_sorensen_dice", aliases: &[], receiver: None, params: &["a", "b"], min_args: 2, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "str_normalize", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "str_word_count", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "str_line_count", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "str_char_count", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "str_bytes_len", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "str_is_palindrome", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "str_camel_to_snake", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "str_snake_to_camel", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "str_kebab_to_snake", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "str_title_case", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "str_wrap", aliases: &[], receiver: None, params: &["s", "width"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "str_truncate", aliases: &[], receiver: None, params: &["s", "arg2", "arg3?"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "str_common_prefix", aliases: &[], receiver: None, params: &["a", "b"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "str_common_suffix", aliases: &[], receiver: None, params: &["a", "b"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "str_rot13", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "str_caesar", aliases: &[], receiver: None, params: &["s", "shift"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "str_expand_tabs", aliases: &[], receiver: None, params: &["s", "arg2?"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "str_from_chars", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "str_to_chars", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "str_each_line", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "str_join_lines", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "whois", aliases: &[], receiver: None, params: &["query"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "net_interfaces", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "tcp_port_open", aliases: &[], receiver: None, params: &["host", "port", "arg3?"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "net_gateway", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "string or null", doc: "", category: "Math extras" },
+    Builtin { name: "net_arp_table", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "net_connections", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "net_ping", aliases: &[], receiver: None, params: &["host"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "mat_new", aliases: &[], receiver: None, params: &["rows", "cols?", "val?"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "mat_identity", aliases: &[], receiver: None, params: &["n"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "mat_add", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "mat_sub", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "mat_mul", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "mat_transpose", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "mat_det", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "mat_inv", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "mat_trace", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "mat_norm", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "mat_rank", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "mat_svd", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "mat_lu", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "mat_qr", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "mat_eigenvalues", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "mat_solve", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "", doc: "", category: "Math extras" },
+    Builtin { name: "mat_dot", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "mat_cross", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "mat_normalize", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "mat_outer", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "vec_dot", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "vec_cross", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "vec_angle", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "vec_lerp", aliases: &[], receiver: None, params: &["arg1", "arg2", "t"], min_args: 3, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "vec_project", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "fft", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "ifft", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "fft_magnitude", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "fft_phase", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "fft_power_spectrum", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "fft_frequencies", aliases: &[], receiver: None, params: &["n", "sample_rate"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "integrate_simpson", aliases: &[], receiver: None, params: &["arg1", "dx"], min_args: 2, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "integrate_trapezoidal", aliases: &[], receiver: None, params: &["arg1", "dx"], min_args: 2, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "ode_rk4", aliases: &[], receiver: None, params: &["y", "t0", "t1", "dt", "k"], min_args: 5, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "interpolate_linear", aliases: &[], receiver: None, params: &["arg1", "arg2", "x"], min_args: 3, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "interpolate_cubic", aliases: &[], receiver: None, params: &["arg1", "arg2", "x"], min_args: 3, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "numerical_diff", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "logspace", aliases: &[], receiver: None, params: &["start", "end", "n"], min_args: 3, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "meshgrid", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "stat_correlation", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "stat_covariance", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "stat_regression_linear", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "stat_histogram", aliases: &[], receiver: None, params: &["arg1", "bins"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "stat_entropy", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "stat_zscore", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "stat_normalize_minmax", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "stat_iqr", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "stat_skewness", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "stat_kurtosis", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "float", doc: "", category: "Math extras" },
+    Builtin { name: "graph_new", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "graph_add_node", aliases: &[], receiver: None, params: &["id", "label"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "graph_add_edge", aliases: &[], receiver: None, params: &["id", "from", "to", "arg4?"], min_args: 3, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "graph_remove_node", aliases: &[], receiver: None, params: &["id", "arg2"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "graph_remove_edge", aliases: &[], receiver: None, params: &["id", "arg2", "arg3"], min_args: 3, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "graph_node_count", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "graph_edge_count", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "graph_neighbors", aliases: &[], receiver: None, params: &["id", "arg2"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "graph_node_label", aliases: &[], receiver: None, params: &["id", "arg2"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "graph_bfs", aliases: &[], receiver: None, params: &["id", "arg2"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "graph_dfs", aliases: &[], receiver: None, params: &["id", "arg2"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "graph_dijkstra", aliases: &[], receiver: None, params: &["id", "arg2"], min_args: 2, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "graph_shortest_path", aliases: &[], receiver: None, params: &["id", "arg2", "arg3"], min_args: 3, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "graph_bellman_ford", aliases: &[], receiver: None, params: &["id", "arg2"], min_args: 2, max_args: None, returns: "map", doc: "", category: "Math extras" },
+    Builtin { name: "graph_topo_sort", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "graph_scc", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "graph_connected_components", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "graph_is_cyclic", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "graph_min_spanning_tree", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "graph_node_degree", aliases: &[], receiver: None, params: &["id", "arg2"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Math extras" },
+    Builtin { name: "graph_all_nodes", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "graph_all_edges", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Math extras" },
+    Builtin { name: "graph_has_edge", aliases: &[], receiver: None, params: &["id", "arg2", "arg3"], min_args: 3, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "graph_free", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Math extras" },
+    Builtin { name: "graph_to_dot", aliases: &[], receiver: None, params: &["id"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Math extras" },
+    Builtin { name: "xxhash64", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "int", doc: "", category: "HASHING" },
+    Builtin { name: "xxhash32", aliases: &[], receiver: None, params: &["arg1", "arg2?"], min_args: 1, max_args: None, returns: "int", doc: "", category: "HASHING" },
+    Builtin { name: "murmur3_32", aliases: &[], receiver: None, params: &["arg1?", "arg2?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "HASHING" },
+    Builtin { name: "fnv1a_32", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "HASHING" },
+    Builtin { name: "fnv1a_64", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "HASHING" },
+    Builtin { name: "djb2", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "HASHING" },
+    Builtin { name: "adler32", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "HASHING" },
+    Builtin { name: "crc16", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "HASHING" },
+    Builtin { name: "base32_encode", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "ENCODING" },
+    Builtin { name: "base32_decode", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "ENCODING" },
+    Builtin { name: "base58_encode", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "ENCODING" },
+    Builtin { name: "base58_decode", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "ENCODING" },
+    Builtin { name: "base16_encode", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "ENCODING" },
+    Builtin { name: "base16_decode", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "ENCODING" },
+    Builtin { name: "html_encode", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "ENCODING" },
+    Builtin { name: "html_decode", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "ENCODING" },
+    Builtin { name: "punycode_encode", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "ENCODING" },
+    Builtin { name: "morse_encode", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "ENCODING" },
+    Builtin { name: "morse_decode", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "ENCODING" },
+    Builtin { name: "msgpack_encode", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "string", doc: "", category: "MESSAGEPACK" },
+    Builtin { name: "msgpack_decode", aliases: &[], receiver: None, params: &["hex"], min_args: 1, max_args: None, returns: "", doc: "", category: "MESSAGEPACK" },
+    Builtin { name: "bincode_encode", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "string", doc: "", category: "BINCODE" },
+    Builtin { name: "bincode_decode", aliases: &[], receiver: None, params: &["hex"], min_args: 1, max_args: None, returns: "", doc: "", category: "BINCODE" },
+    Builtin { name: "jwt_sign", aliases: &[], receiver: None, params: &["arg1", "secret_str", "alg_str"], min_args: 3, max_args: None, returns: "string", doc: "", category: "JWT" },
+    Builtin { name: "jwt_verify", aliases: &[], receiver: None, params: &["token", "secret_str", "alg_str"], min_args: 3, max_args: None, returns: "bool", doc: "", category: "JWT" },
+    Builtin { name: "jwt_decode", aliases: &[], receiver: None, params: &["token"], min_args: 1, max_args: None, returns: "map", doc: "", category: "JWT" },
+    Builtin { name: "template_render", aliases: &[], receiver: None, params: &["template_str", "arg2"], min_args: 2, max_args: None, returns: "string", doc: "", category: "HANDLEBARS TEMPLATING" },
+    Builtin { name: "template_register", aliases: &[], receiver: None, params: &["name", "template_str"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "HANDLEBARS TEMPLATING" },
+    Builtin { name: "template_render_named", aliases: &[], receiver: None, params: &["name", "arg2"], min_args: 2, max_args: None, returns: "string", doc: "", category: "HANDLEBARS TEMPLATING" },
+    Builtin { name: "template_escape", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "HANDLEBARS TEMPLATING" },
+    Builtin { name: "bit_rotl", aliases: &[], receiver: None, params: &["a", "arg2"], min_args: 2, max_args: None, returns: "int", doc: "", category: "BIT MANIPULATION" },
+    Builtin { name: "bit_rotr", aliases: &[], receiver: None, params: &["a", "arg2"], min_args: 2, max_args: None, returns: "int", doc: "", category: "BIT MANIPULATION" },
+    Builtin { name: "bit_popcnt", aliases: &[], receiver: None, params: &["a"], min_args: 1, max_args: None, returns: "int", doc: "", category: "BIT MANIPULATION" },
+    Builtin { name: "bit_clz", aliases: &[], receiver: None, params: &["a"], min_args: 1, max_args: None, returns: "int", doc: "", category: "BIT MANIPULATION" },
+    Builtin { name: "bit_ctz", aliases: &[], receiver: None, params: &["a"], min_args: 1, max_args: None, returns: "int", doc: "", category: "BIT MANIPULATION" },
+    Builtin { name: "bit_get", aliases: &[], receiver: None, params: &["a", "arg2"], min_args: 2, max_args: None, returns: "int", doc: "", category: "BIT MANIPULATION" },
+    Builtin { name: "bit_set", aliases: &[], receiver: None, params: &["a", "arg2"], min_args: 2, max_args: None, returns: "int", doc: "", category: "BIT MANIPULATION" },
+    Builtin { name: "bit_clear", aliases: &[], receiver: None, params: &["a", "arg2"], min_args: 2, max_args: None, returns: "int", doc: "", category: "BIT MANIPULATION" },
+    Builtin { name: "bit_toggle", aliases: &[], receiver: None, params: &["a", "arg2"], min_args: 2, max_args: None, returns: "int", doc: "", category: "BIT MANIPULATION" },
+    Builtin { name: "bytes_to_int_be", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "int", doc: "", category: "BIT MANIPULATION" },
+    Builtin { name: "bytes_to_int_le", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "int", doc: "", category: "BIT MANIPULATION" },
+    Builtin { name: "int_to_bytes_be", aliases: &[], receiver: None, params: &["n", "size"], min_args: 2, max_args: None, returns: "array", doc: "", category: "BIT MANIPULATION" },
+    Builtin { name: "int_to_bytes_le", aliases: &[], receiver: None, params: &["n", "size"], min_args: 2, max_args: None, returns: "array", doc: "", category: "BIT MANIPULATION" },
+    Builtin { name: "sys_cpu_count", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "System Info" },
+    Builtin { name: "sys_cpu_usage", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "System Info" },
+    Builtin { name: "sys_cpu_freq", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "System Info" },
+    Builtin { name: "sys_memory_total", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "System Info" },
+    Builtin { name: "sys_memory_used", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "System Info" },
+    Builtin { name: "sys_memory_available", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "System Info" },
+    Builtin { name: "sys_swap_total", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "System Info" },
+    Builtin { name: "sys_swap_used", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "System Info" },
+    Builtin { name: "sys_uptime", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "System Info" },
+    Builtin { name: "sys_boot_time", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "System Info" },
+    Builtin { name: "sys_hostname", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "System Info" },
+    Builtin { name: "sys_os_name", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "System Info" },
+    Builtin { name: "sys_os_version", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "System Info" },
+    Builtin { name: "sys_kernel_version", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "System Info" },
+    Builtin { name: "sys_arch", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "System Info" },
+    Builtin { name: "sys_load_avg", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "map", doc: "", category: "System Info" },
+    Builtin { name: "sys_process_list", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "System Info" },
+    Builtin { name: "sys_process_by_name", aliases: &[], receiver: None, params: &["target_name"], min_args: 1, max_args: None, returns: "array", doc: "", category: "System Info" },
+    Builtin { name: "sys_process_kill", aliases: &[], receiver: None, params: &["pid_val"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "System Info" },
+    Builtin { name: "sys_disk_list", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "System Info" },
+    Builtin { name: "sys_network_list", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "System Info" },
+    Builtin { name: "sys_refresh", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "System Info" },
+    Builtin { name: "proc_run", aliases: &[], receiver: None, params: &["cmd", "arg2", "args..."], min_args: 2, max_args: None, returns: "map", doc: "", category: "Process Control" },
+    Builtin { name: "proc_run_shell", aliases: &[], receiver: None, params: &["shell_cmd"], min_args: 1, max_args: None, returns: "map", doc: "", category: "Process Control" },
+    Builtin { name: "proc_spawn_bg", aliases: &[], receiver: None, params: &["cmd", "arg2", "args..."], min_args: 2, max_args: None, returns: "int", doc: "", category: "Process Control" },
+    Builtin { name: "proc_which", aliases: &[], receiver: None, params: &["prog_name"], min_args: 1, max_args: None, returns: "string or null", doc: "", category: "Process Control" },
+    Builtin { name: "proc_pid", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Process Control" },
+    Builtin { name: "proc_exit", aliases: &[], receiver: None, params: &["arg1?"], min_args: 0, max_args: None, returns: "", doc: "", category: "Process Control" },
+    Builtin { name: "env_cwd", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "Process Control" },
+    Builtin { name: "env_set_cwd", aliases: &[], receiver: None, params: &["path"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Process Control" },
+    Builtin { name: "env_args", aliases: &[], receiver: None, params: &["args..."], min_args: 0, max_args: None, returns: "array", doc: "", category: "Process Control" },
+    Builtin { name: "env_exe", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "Process Control" },
+    Builtin { name: "fs_glob", aliases: &[], receiver: None, params: &["pattern"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Advanced File System" },
+    Builtin { name: "fs_walk", aliases: &[], receiver: None, params: &["dir"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Advanced File System" },
+    Builtin { name: "fs_walk_dirs", aliases: &[], receiver: None, params: &["dir"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Advanced File System" },
+    Builtin { name: "fs_find", aliases: &[], receiver: None, params: &["dir", "pattern"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Advanced File System" },
+    Builtin { name: "fs_symlink", aliases: &[], receiver: None, params: &["target", "link"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "Advanced File System" },
+    Builtin { name: "fs_hardlink", aliases: &[], receiver: None, params: &["src", "dst"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "Advanced File System" },
+    Builtin { name: "fs_chmod", aliases: &[], receiver: None, params: &["path", "mode"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "Advanced File System" },
+    Builtin { name: "fs_stat", aliases: &[], receiver: None, params: &["path"], min_args: 1, max_args: None, returns: "map", doc: "", category: "Advanced File System" },
+    Builtin { name: "fs_disk_usage", aliases: &[], receiver: None, params: &["dir"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Advanced File System" },
+    Builtin { name: "fs_tempdir", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "Advanced File System" },
+    Builtin { name: "fs_tempfile", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "Advanced File System" },
+    Builtin { name: "fs_mime_type", aliases: &[], receiver: None, params: &["path"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Advanced File System" },
+    Builtin { name: "fs_hash_file", aliases: &[], receiver: None, params: &["path", "algorithm"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Advanced File System" },
+    Builtin { name: "fs_watch_once", aliases: &[], receiver: None, params: &["watch_path", "timeout_ms"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "Advanced File System" },
+    Builtin { name: "timer_now_ns", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Timer / High-resolution time" },
+    Builtin { name: "timer_now_us", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Timer / High-resolution time" },
+    Builtin { name: "timer_now_ms", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "Timer / High-resolution time" },
+    Builtin { name: "rand_seed", aliases: &[], receiver: None, params: &["seed"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Random" },
+    Builtin { name: "rand_float_range", aliases: &[], receiver: None, params: &["min", "max"], min_args: 2, max_args: None, returns: "float", doc: "", category: "Random" },
+    Builtin { name: "rand_int_range", aliases: &[], receiver: None, params: &["min", "max"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Random" },
+    Builtin { name: "rand_bytes", aliases: &[], receiver: None, params: &["n"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Random" },
+    Builtin { name: "rand_choice", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "", doc: "", category: "Random" },
+    Builtin { name: "diff_lines", aliases: &[], receiver: None, params: &["old", "new_s"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Diff" },
+    Builtin { name: "diff_chars", aliases: &[], receiver: None, params: &["old", "new_s"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Diff" },
+    Builtin { name: "diff_words", aliases: &[], receiver: None, params: &["old", "new_s"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Diff" },
+    Builtin { name: "diff_unified", aliases: &[], receiver: None, params: &["old", "new_s", "ctx"], min_args: 3, max_args: None, returns: "string", doc: "", category: "Diff" },
+    Builtin { name: "diff_ratio", aliases: &[], receiver: None, params: &["old", "new_s"], min_args: 2, max_args: None, returns: "float", doc: "", category: "Diff" },
+    Builtin { name: "diff_patch", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "string", doc: "", category: "Diff" },
+    Builtin { name: "diff_is_same", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "Diff" },
+    Builtin { name: "regex_match", aliases: &[], receiver: None, params: &["pat", "txt"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "PCRE Regex" },
+    Builtin { name: "regex_find", aliases: &[], receiver: None, params: &["pat", "txt"], min_args: 2, max_args: None, returns: "string or null", doc: "", category: "PCRE Regex" },
+    Builtin { name: "regex_captures", aliases: &[], receiver: None, params: &["pat", "txt"], min_args: 2, max_args: None, returns: "array", doc: "", category: "PCRE Regex" },
+    Builtin { name: "regex_replace", aliases: &[], receiver: None, params: &["pat", "txt", "rep"], min_args: 3, max_args: None, returns: "string", doc: "", category: "PCRE Regex" },
+    Builtin { name: "regex_replace_all", aliases: &[], receiver: None, params: &["pat", "txt", "rep"], min_args: 3, max_args: None, returns: "string", doc: "", category: "PCRE Regex" },
+    Builtin { name: "regex_split", aliases: &[], receiver: None, params: &["pat", "txt"], min_args: 2, max_args: None, returns: "array", doc: "", category: "PCRE Regex" },
+    Builtin { name: "regex_test", aliases: &[], receiver: None, params: &["pat"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "PCRE Regex" },
+    Builtin { name: "regex_named_captures", aliases: &[], receiver: None, params: &["pat", "txt"], min_args: 2, max_args: None, returns: "map", doc: "", category: "PCRE Regex" },
+    Builtin { name: "vec2", aliases: &[], receiver: None, params: &["x", "y"], min_args: 2, max_args: None, returns: "map", doc: "── 3D Math (glam) ───────────────────────────────────────────", category: "PCRE Regex" },
+    Builtin { name: "vec3", aliases: &[], receiver: None, params: &["x", "y", "z"], min_args: 3, max_args: None, returns: "map", doc: "", category: "PCRE Regex" },
+    Builtin { name: "vec4", aliases: &[], receiver: None, params: &["x", "y", "z", "w"], min_args: 4, max_args: None, returns: "map", doc: "", category: "PCRE Regex" },
+    Builtin { name: "vec3_add", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "", doc: "", category: "PCRE Regex" },
+    Builtin { name: "vec3_sub", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "", doc: "", category: "PCRE Regex" },
+    Builtin { name: "vec3_mul", aliases: &[], receiver: None, params: &["arg1", "s"], min_args: 2, max_args: None, returns: "", doc: "", category: "PCRE Regex" },
+    Builtin { name: "vec3_dot", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "", doc: "", category: "PCRE Regex" },
+    Builtin { name: "vec3_cross", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "", doc: "", category: "PCRE Regex" },
+    Builtin { name: "vec3_len", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "", doc: "", category: "PCRE Regex" },
+    Builtin { name: "vec3_normalize", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "", doc: "", category: "PCRE Regex" },
+    Builtin { name: "vec3_lerp", aliases: &[], receiver: None, params: &["arg1", "arg2", "t"], min_args: 3, max_args: None, returns: "", doc: "", category: "PCRE Regex" },
+    Builtin { name: "vec3_distance", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "", doc: "", category: "PCRE Regex" },
+    Builtin { name: "vec3_reflect", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "", doc: "", category: "PCRE Regex" },
+    Builtin { name: "vec3_angle", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "", doc: "", category: "PCRE Regex" },
+    Builtin { name: "mat4_identity", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "PCRE Regex" },
+    Builtin { name: "mat4_mul", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "", doc: "", category: "PCRE Regex" },
+    Builtin { name: "mat4_translate", aliases: &[], receiver: None, params: &["x", "y", "z"], min_args: 3, max_args: None, returns: "array", doc: "", category: "PCRE Regex" },
+    Builtin { name: "mat4_scale", aliases: &[], receiver: None, params: &["x", "y", "z"], min_args: 3, max_args: None, returns: "array", doc: "", category: "PCRE Regex" },
+    Builtin { name: "mat4_rotate_x", aliases: &[], receiver: None, params: &["angle"], min_args: 1, max_args: None, returns: "array", doc: "", category: "PCRE Regex" },
+    Builtin { name: "mat4_rotate_y", aliases: &[], receiver: None, params: &["angle"], min_args: 1, max_args: None, returns: "array", doc: "", category: "PCRE Regex" },
+    Builtin { name: "mat4_rotate_z", aliases: &[], receiver: None, params: &["angle"], min_args: 1, max_args: None, returns: "array", doc: "", category: "PCRE Regex" },
+    Builtin { name: "mat4_perspective", aliases: &[], receiver: None, params: &["fov", "aspect", "near", "far"], min_args: 4, max_args: None, returns: "array", doc: "", category: "PCRE Regex" },
+    Builtin { name: "mat4_look_at", aliases: &[], receiver: None, params: &["arg1", "arg2", "arg3"], min_args: 3, max_args: None, returns: "", doc: "", category: "PCRE Regex" },
+    Builtin { name: "mat4_transform_point", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "map", doc: "", category: "PCRE Regex" },
+    Builtin { name: "quat_identity", aliases: &[], receiver: None, params: &[], min_args: 0, max_args: None, returns: "map", doc: "", category: "PCRE Regex" },
+    Builtin { name: "quat_from_axis_angle", aliases: &[], receiver: None, params: &["arg1", "angle"], min_args: 2, max_args: None, returns: "map", doc: "", category: "PCRE Regex" },
+    Builtin { name: "quat_mul", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "", doc: "", category: "PCRE Regex" },
+    Builtin { name: "quat_slerp", aliases: &[], receiver: None, params: &["arg1", "arg2", "t"], min_args: 3, max_args: None, returns: "", doc: "", category: "PCRE Regex" },
+    Builtin { name: "quat_to_euler", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "map", doc: "", category: "PCRE Regex" },
+    Builtin { name: "quat_from_euler", aliases: &[], receiver: None, params: &["ex", "ey", "ez"], min_args: 3, max_args: None, returns: "map", doc: "", category: "PCRE Regex" },
+    Builtin { name: "quat_rotate_vec3", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "map", doc: "", category: "PCRE Regex" },
+    Builtin { name: "quat_conjugate", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "map", doc: "", category: "PCRE Regex" },
+    Builtin { name: "quat_normalize", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "map", doc: "", category: "PCRE Regex" },
+    Builtin { name: "quat_dot", aliases: &[], receiver: None, params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "", doc: "", category: "PCRE Regex" },
+    Builtin { name: "wav_read", aliases: &[], receiver: None, params: &["path"], min_args: 1, max_args: None, returns: "map", doc: "", category: "Audio" },
+    Builtin { name: "wav_write", aliases: &[], receiver: None, params: &["path", "arg2", "sample_rate", "channels"], min_args: 4, max_args: None, returns: "null", doc: "", category: "Audio" },
+    Builtin { name: "wav_info", aliases: &[], receiver: None, params: &["path"], min_args: 1, max_args: None, returns: "map", doc: "", category: "Audio" },
+    Builtin { name: "wav_generate_sine", aliases: &[], receiver: None, params: &["freq", "dur", "sr"], min_args: 3, max_args: None, returns: "array", doc: "", category: "Audio" },
+    Builtin { name: "wav_generate_square", aliases: &[], receiver: None, params: &["freq", "dur", "sr"], min_args: 3, max_args: None, returns: "array", doc: "", category: "Audio" },
+    Builtin { name: "wav_generate_sawtooth", aliases: &[], receiver: None, params: &["freq", "dur", "sr"], min_args: 3, max_args: None, returns: "array", doc: "", category: "Audio" },
+    Builtin { name: "wav_mix", aliases: &[], receiver: None, params: &["arg1", "arg2", "ratio"], min_args: 3, max_args: None, returns: "array", doc: "", category: "Audio" },
+    Builtin { name: "wav_volume", aliases: &[], receiver: None, params: &["arg1", "factor"], min_args: 2, max_args: None, returns: "array", doc: "", category: "Audio" },
+    Builtin { name: "wav_reverse", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Audio" },
+    Builtin { name: "wav_to_pcm16", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Audio" },
+    Builtin { name: "finance_pv", aliases: &[], receiver: None, params: &["r", "n", "pmt", "fv"], min_args: 4, max_args: None, returns: "float", doc: "", category: "Finance / Math" },
+    Builtin { name: "finance_fv", aliases: &[], receiver: None, params: &["r", "n", "pmt", "pv"], min_args: 4, max_args: None, returns: "float", doc: "", category: "Finance / Math" },
+    Builtin { name: "finance_nper", aliases: &[], receiver: None, params: &["r", "pmt", "pv", "fv"], min_args: 4, max_args: None, returns: "float", doc: "", category: "Finance / Math" },
+    Builtin { name: "finance_pmt", aliases: &[], receiver: None, params: &["r", "n", "pv", "fv"], min_args: 4, max_args: None, returns: "float", doc: "", category: "Finance / Math" },
+    Builtin { name: "finance_npv", aliases: &[], receiver: None, params: &["r", "arg2"], min_args: 2, max_args: None, returns: "float", doc: "", category: "Finance / Math" },
+    Builtin { name: "finance_irr", aliases: &[], receiver: None, params: &["arg1"], min_args: 1, max_args: None, returns: "float", doc: "", category: "Finance / Math" },
+    Builtin { name: "finance_compound", aliases: &[], receiver: None, params: &["p", "r", "n", "t"], min_args: 4, max_args: None, returns: "float", doc: "", category: "Finance / Math" },
+    Builtin { name: "finance_simple_interest", aliases: &[], receiver: None, params: &["p", "r", "t"], min_args: 3, max_args: None, returns: "float", doc: "", category: "Finance / Math" },
+    Builtin { name: "finance_black_scholes_call", aliases: &[], receiver: None, params: &["s", "k", "t", "r", "sigma"], min_args: 5, max_args: None, returns: "float", doc: "", category: "Finance / Math" },
+    Builtin { name: "finance_black_scholes_put", aliases: &[], receiver: None, params: &["s", "k", "t", "r", "sigma"], min_args: 5, max_args: None, returns: "float", doc: "", category: "Finance / Math" },
+    Builtin { name: "finance_sharpe", aliases: &[], receiver: None, params: &["arg1", "rf"], min_args: 2, max_args: None, returns: "float", doc: "", category: "Finance / Math" },
+    Builtin { name: "finance_roi", aliases: &[], receiver: None, params: &["gain", "cost"], min_args: 2, max_args: None, returns: "float", doc: "", category: "Finance / Math" },
+    Builtin { name: "finance_cagr", aliases: &[], receiver: None, params: &["begin", "end", "years"], min_args: 3, max_args: None, returns: "float", doc: "", category: "Finance / Math" },
+    Builtin { name: "finance_mortgage", aliases: &[], receiver: None, params: &["p", "annual_rate", "years"], min_args: 3, max_args: None, returns: "float", doc: "", category: "Finance / Math" },
+    Builtin { name: "str_format_number", aliases: &[], receiver: None, params: &["n", "decimals", "thousands_sep", "decimal_sep"], min_args: 4, max_args: None, returns: "string", doc: "", category: "Advanced String Operations" },
+    Builtin { name: "str_parse_number", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "float", doc: "", category: "Advanced String Operations" },
+    Builtin { name: "str_lorem_ipsum", aliases: &[], receiver: None, params: &["n"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Advanced String Operations" },
+    Builtin { name: "str_uuid_like", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Advanced String Operations" },
+    Builtin { name: "str_email_valid", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Advanced String Operations" },
+    Builtin { name: "str_ip_valid", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "bool", doc: "", category: "Advanced String Operations" },
+    Builtin { name: "str_mask", aliases: &[], receiver: None, params: &["s", "start", "end", "mask_ch_s"], min_args: 4, max_args: None, returns: "string", doc: "", category: "Advanced String Operations" },
+    Builtin { name: "str_contains_all", aliases: &[], receiver: None, params: &["s", "arg2"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "Advanced String Operations" },
+    Builtin { name: "str_contains_any", aliases: &[], receiver: None, params: &["s", "arg2"], min_args: 2, max_args: None, returns: "bool", doc: "", category: "Advanced String Operations" },
+    Builtin { name: "str_count_occurrences", aliases: &[], receiver: None, params: &["s", "sub"], min_args: 2, max_args: None, returns: "int", doc: "", category: "Advanced String Operations" },
+    Builtin { name: "str_extract_numbers", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Advanced String Operations" },
+    Builtin { name: "str_extract_urls", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "", doc: "", category: "Advanced String Operations" },
+    Builtin { name: "str_extract_emails", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Advanced String Operations" },
+    Builtin { name: "str_banner", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Advanced String Operations" },
+    Builtin { name: "str_soundex", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Advanced String Operations" },
+    Builtin { name: "str_metaphone", aliases: &[], receiver: None, params: &["s"], min_args: 1, max_args: None, returns: "string", doc: "", category: "Advanced String Operations" },
+];
+
+/// Methods on builtin values, by receiver
+#[rustfmt::skip]
+pub static METHODS: &[Builtin] = &[
+    Builtin { name: "len", aliases: &["length"], receiver: Some("string"), params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "" },
+    Builtin { name: "is_empty", aliases: &[], receiver: Some("string"), params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "" },
+    Builtin { name: "to_uppercase", aliases: &["upper"], receiver: Some("string"), params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "to_lowercase", aliases: &["lower"], receiver: Some("string"), params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "trim", aliases: &[], receiver: Some("string"), params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "trim_start", aliases: &["ltrim"], receiver: Some("string"), params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "trim_end", aliases: &["rtrim"], receiver: Some("string"), params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "chars", aliases: &[], receiver: Some("string"), params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "bytes", aliases: &[], receiver: Some("string"), params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "lines", aliases: &[], receiver: Some("string"), params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "split", aliases: &[], receiver: Some("string"), params: &["sep?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "split_whitespace", aliases: &[], receiver: Some("string"), params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "contains", aliases: &[], receiver: Some("string"), params: &["needle?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "" },
+    Builtin { name: "starts_with", aliases: &[], receiver: Some("string"), params: &["prefix?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "" },
+    Builtin { name: "ends_with", aliases: &[], receiver: Some("string"), params: &["suffix?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "" },
+    Builtin { name: "replace", aliases: &[], receiver: Some("string"), params: &["arg1", "arg2"], min_args: 2, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "replace_n", aliases: &[], receiver: Some("string"), params: &["arg1", "arg2", "n"], min_args: 3, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "repeat", aliases: &[], receiver: Some("string"), params: &["n?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "find", aliases: &["index_of"], receiver: Some("string"), params: &["needle?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "" },
+    Builtin { name: "rfind", aliases: &[], receiver: Some("string"), params: &["needle?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "" },
+    Builtin { name: "char_at", aliases: &["get"], receiver: Some("string"), params: &["idx?"], min_args: 0, max_args: None, returns: "", doc: "", category: "" },
+    Builtin { name: "slice", aliases: &["substring"], receiver: Some("string"), params: &["start?", "end?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "to_int", aliases: &["parse_int"], receiver: Some("string"), params: &[], min_args: 0, max_args: None, returns: "", doc: "", category: "" },
+    Builtin { name: "to_float", aliases: &["parse_float"], receiver: Some("string"), params: &[], min_args: 0, max_args: None, returns: "", doc: "", category: "" },
+    Builtin { name: "to_string", aliases: &[], receiver: Some("string"), params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "pad_left", aliases: &[], receiver: Some("string"), params: &["width?", "pad?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "pad_right", aliases: &[], receiver: Some("string"), params: &["width?", "pad?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "reverse", aliases: &[], receiver: Some("string"), params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "count", aliases: &[], receiver: Some("string"), params: &["needle?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "" },
+    Builtin { name: "format", aliases: &[], receiver: Some("string"), params: &["args..."], min_args: 0, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "len", aliases: &["length", "count"], receiver: Some("array"), params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "" },
+    Builtin { name: "is_empty", aliases: &[], receiver: Some("array"), params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "" },
+    Builtin { name: "first", aliases: &["head"], receiver: Some("array"), params: &[], min_args: 0, max_args: None, returns: "", doc: "", category: "" },
+    Builtin { name: "last", aliases: &[], receiver: Some("array"), params: &[], min_args: 0, max_args: None, returns: "", doc: "", category: "" },
+    Builtin { name: "push", aliases: &["append"], receiver: Some("array"), params: &["val"], min_args: 1, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "pop", aliases: &[], receiver: Some("array"), params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "shift", aliases: &["dequeue"], receiver: Some("array"), params: &[], min_args: 0, max_args: None, returns: "", doc: "", category: "" },
+    Builtin { name: "insert", aliases: &[], receiver: Some("array"), params: &["idx", "arg2"], min_args: 2, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "remove", aliases: &[], receiver: Some("array"), params: &["idx?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "contains", aliases: &["includes"], receiver: Some("array"), params: &["needle?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "" },
+    Builtin { name: "index_of", aliases: &["find_index"], receiver: Some("array"), params: &["needle?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "" },
+    Builtin { name: "sort", aliases: &[], receiver: Some("array"), params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "sort_desc", aliases: &[], receiver: Some("array"), params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "sort_by", aliases: &["sorted_by"], receiver: Some("array"), params: &["cmp_fn"], min_args: 1, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "reverse", aliases: &[], receiver: Some("array"), params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "join", aliases: &[], receiver: Some("array"), params: &["sep?"], min_args: 0, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "sum", aliases: &[], receiver: Some("array"), params: &[], min_args: 0, max_args: None, returns: "", doc: "", category: "" },
+    Builtin { name: "product", aliases: &[], receiver: Some("array"), params: &[], min_args: 0, max_args: None, returns: "", doc: "", category: "" },
+    Builtin { name: "min", aliases: &[], receiver: Some("array"), params: &[], min_args: 0, max_args: None, returns: "null", doc: "", category: "" },
+    Builtin { name: "max", aliases: &[], receiver: Some("array"), params: &[], min_args: 0, max_args: None, returns: "null", doc: "", category: "" },
+    Builtin { name: "flatten", aliases: &[], receiver: Some("array"), params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "unique", aliases: &["dedup"], receiver: Some("array"), params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "slice", aliases: &[], receiver: Some("array"), params: &["start?", "end?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "concat", aliases: &["extend"], receiver: Some("array"), params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "zip", aliases: &[], receiver: Some("array"), params: &["arg1"], min_args: 1, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "any", aliases: &[], receiver: Some("array"), params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "" },
+    Builtin { name: "all", aliases: &[], receiver: Some("array"), params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "" },
+    Builtin { name: "none", aliases: &[], receiver: Some("array"), params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "" },
+    Builtin { name: "to_string", aliases: &[], receiver: Some("array"), params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "map", aliases: &[], receiver: Some("array"), params: &["closure"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Functional methods" },
+    Builtin { name: "filter", aliases: &[], receiver: Some("array"), params: &["closure"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Functional methods" },
+    Builtin { name: "reduce", aliases: &["fold"], receiver: Some("array"), params: &["closure?", "acc?"], min_args: 0, max_args: None, returns: "", doc: "", category: "Functional methods" },
+    Builtin { name: "for_each", aliases: &["each"], receiver: Some("array"), params: &["closure"], min_args: 1, max_args: None, returns: "null", doc: "", category: "Functional methods" },
+    Builtin { name: "find", aliases: &[], receiver: Some("array"), params: &["closure"], min_args: 1, max_args: None, returns: "", doc: "", category: "Functional methods" },
+    Builtin { name: "count_if", aliases: &[], receiver: Some("array"), params: &["closure"], min_args: 1, max_args: None, returns: "int", doc: "", category: "Functional methods" },
+    Builtin { name: "flat_map", aliases: &[], receiver: Some("array"), params: &["closure"], min_args: 1, max_args: None, returns: "array", doc: "", category: "Functional methods" },
+    Builtin { name: "len", aliases: &["length", "count"], receiver: Some("map"), params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "" },
+    Builtin { name: "is_empty", aliases: &[], receiver: Some("map"), params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "" },
+    Builtin { name: "keys", aliases: &[], receiver: Some("map"), params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "values", aliases: &[], receiver: Some("map"), params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "entries", aliases: &["items"], receiver: Some("map"), params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "get", aliases: &[], receiver: Some("map"), params: &["key?"], min_args: 0, max_args: None, returns: "", doc: "", category: "" },
+    Builtin { name: "set", aliases: &["insert"], receiver: Some("map"), params: &["key", "val"], min_args: 2, max_args: None, returns: "map", doc: "", category: "" },
+    Builtin { name: "remove", aliases: &["delete"], receiver: Some("map"), params: &["key?"], min_args: 0, max_args: None, returns: "map", doc: "", category: "" },
+    Builtin { name: "has", aliases: &["has_key", "contains_key"], receiver: Some("map"), params: &["key?"], min_args: 0, max_args: None, returns: "bool", doc: "", category: "" },
+    Builtin { name: "merge", aliases: &[], receiver: Some("map"), params: &["arg1"], min_args: 1, max_args: None, returns: "map", doc: "", category: "" },
+    Builtin { name: "to_array", aliases: &["to_list"], receiver: Some("map"), params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "to_string", aliases: &[], receiver: Some("map"), params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "for_each", aliases: &["each"], receiver: Some("map"), params: &["closure"], min_args: 1, max_args: None, returns: "null", doc: "", category: "" },
+    Builtin { name: "to_string", aliases: &["str"], receiver: Some("int"), params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "to_float", aliases: &[], receiver: Some("int"), params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "" },
+    Builtin { name: "abs", aliases: &[], receiver: Some("int"), params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "" },
+    Builtin { name: "pow", aliases: &[], receiver: Some("int"), params: &["exp?"], min_args: 0, max_args: None, returns: "", doc: "", category: "" },
+    Builtin { name: "min", aliases: &[], receiver: Some("int"), params: &["other?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "" },
+    Builtin { name: "max", aliases: &[], receiver: Some("int"), params: &["other?"], min_args: 0, max_args: None, returns: "int", doc: "", category: "" },
+    Builtin { name: "clamp", aliases: &[], receiver: Some("int"), params: &["lo", "hi"], min_args: 2, max_args: None, returns: "int", doc: "", category: "" },
+    Builtin { name: "is_even", aliases: &[], receiver: Some("int"), params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "" },
+    Builtin { name: "is_odd", aliases: &[], receiver: Some("int"), params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "" },
+    Builtin { name: "is_zero", aliases: &[], receiver: Some("int"), params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "" },
+    Builtin { name: "is_positive", aliases: &[], receiver: Some("int"), params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "" },
+    Builtin { name: "is_negative", aliases: &[], receiver: Some("int"), params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "" },
+    Builtin { name: "signum", aliases: &[], receiver: Some("int"), params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "" },
+    Builtin { name: "times", aliases: &[], receiver: Some("int"), params: &[], min_args: 0, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "repeat", aliases: &[], receiver: Some("int"), params: &["arg1?"], min_args: 0, max_args: None, returns: "array", doc: "", category: "" },
+    Builtin { name: "to_char", aliases: &[], receiver: Some("int"), params: &[], min_args: 0, max_args: None, returns: "", doc: "", category: "" },
+    Builtin { name: "to_bits", aliases: &[], receiver: Some("int"), params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "to_hex", aliases: &[], receiver: Some("int"), params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "to_oct", aliases: &[], receiver: Some("int"), params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "to_string", aliases: &["str"], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "string", doc: "", category: "" },
+    Builtin { name: "to_int", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "int", doc: "", category: "" },
+    Builtin { name: "abs", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "" },
+    Builtin { name: "floor", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "" },
+    Builtin { name: "ceil", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "" },
+    Builtin { name: "round", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "" },
+    Builtin { name: "trunc", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "" },
+    Builtin { name: "fract", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "" },
+    Builtin { name: "sqrt", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "" },
+    Builtin { name: "cbrt", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "" },
+    Builtin { name: "exp", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "" },
+    Builtin { name: "ln", aliases: &["log"], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "" },
+    Builtin { name: "log2", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "" },
+    Builtin { name: "log10", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "" },
+    Builtin { name: "sin", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "" },
+    Builtin { name: "cos", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "" },
+    Builtin { name: "tan", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "" },
+    Builtin { name: "asin", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "" },
+    Builtin { name: "acos", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "" },
+    Builtin { name: "atan", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "" },
+    Builtin { name: "signum", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "float", doc: "", category: "" },
+    Builtin { name: "is_nan", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "" },
+    Builtin { name: "is_finite", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "" },
+    Builtin { name: "is_infinite", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "" },
+    Builtin { name: "is_zero", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "" },
+    Builtin { name: "is_positive", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "" },
+    Builtin { name: "is_negative", aliases: &[], receiver: Some("float"), params: &[], min_args: 0, max_args: None, returns: "bool", doc: "", category: "" },
+];
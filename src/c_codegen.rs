@@ -506,7 +506,7 @@ impl CCodeGen {
                 Ok(tmp)
             }
             // ── Impl block ────────────────────────────────────────────────────
-            ASTNode::Impl { ty: _, methods } => {
+            ASTNode::Impl { methods, .. } => {
                 for method in methods {
                     self.compile_node(method)?;
                 }
@@ -597,7 +597,7 @@ impl CCodeGen {
                     if *is_expr {
                         fmt.push_str("%lld");
                         // Parse the expression and compile it
-                        let mut sub_parser = crate::parser::Parser::new(content);
+                        let mut sub_parser = crate::parser::Parser::new(content).without_spans();
                         if let Ok(sub_ast) = sub_parser.parse_expression_pub() {
                             if let Ok(code) = self.compile_node(&sub_ast) {
                                 args_list.push(code);
@@ -729,7 +729,7 @@ pub fn compile_to_binary(source: &str, output_path: &str) -> Result<(), String>
     let mut lexer = Lexer::new(source);
     let _tokens = lexer.tokenize();
 
-    let mut parser = Parser::new(source);
+    let mut parser = Parser::new(source).without_spans();
     let ast = parser.parse().map_err(|e| format!("Parse error: {}", e))?;
 
     let mut codegen = CCodeGen::new();
@@ -0,0 +1,645 @@
+//! Knull Static Checker
+//!
+//! Finds mistakes that would otherwise only surface when the offending line
+//! runs: names that are never defined, calls with the wrong number of
+//! arguments, fields a struct does not declare, values that do not match
+//! their type annotations, code after a `return`, and `let` bindings that are
+//! never read. Files in `mode expert` or `mode god` also go through the
+//! linear, effect and ownership checkers. `knull check` prints the findings;
+//! the language server publishes them as diagnostics on every edit.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::builtins;
+use crate::effects::EffectChecker;
+use crate::interpreter::is_builtin;
+use crate::linear_check::LinearChecker;
+use crate::ownership::OwnershipChecker;
+use crate::parser::{ASTNode, Param, Parser, Pattern, Span, Type, VariantData};
+use crate::type_system::TypeChecker;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FindingKind {
+    /// A variable or function that is defined nowhere in reach
+    UnknownName,
+    /// A call with more or fewer arguments than the function takes
+    Arity,
+    /// A struct literal or field access naming a field the struct lacks
+    UnknownField,
+    /// A value that does not match the type it is annotated with
+    Type,
+    /// A linear value used twice or never (expert and god modes)
+    Linear,
+    /// An expression doing more than its effect annotation allows (expert and god modes)
+    Effect,
+    /// A use of a moved value (expert and god modes)
+    Ownership,
+    /// Statements after a `return`, `break`, `continue` or `throw`
+    Unreachable,
+    /// A `let` binding that is never read
+    Unused,
+    /// A warning from the match exhaustiveness or builtin shadowing lints
+    Lint,
+}
+
+impl FindingKind {
+    pub fn is_error(self) -> bool {
+        !matches!(self, FindingKind::Unreachable | FindingKind::Unused | FindingKind::Lint)
+    }
+
+    /// Name of the check, as reported by `knull check --format json`
+    pub fn name(self) -> &'static str {
+        match self {
+            FindingKind::UnknownName => "unknown-name",
+            FindingKind::Arity => "arity",
+            FindingKind::UnknownField => "unknown-field",
+            FindingKind::Type => "type",
+            FindingKind::Linear => "linear",
+            FindingKind::Effect => "effect",
+            FindingKind::Ownership => "ownership",
+            FindingKind::Unreachable => "unreachable",
+            FindingKind::Unused => "unused",
+            FindingKind::Lint => "lint",
+        }
+    }
+}
+
+/// One problem found in a program
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub kind: FindingKind,
+    pub message: String,
+    pub span: Option<Span>,
+}
+
+/// Check a parsed program; `file` is where its imports are resolved from.
+/// Findings come back in source order.
+pub fn check_program(ast: &ASTNode, file: Option<&Path>) -> Vec<Finding> {
+    let mut checker = Checker {
+        file,
+        scopes: vec![HashMap::new()],
+        arity: HashMap::new(),
+        structs: HashMap::new(),
+        open: false,
+        span: None,
+        findings: Vec::new(),
+    };
+    if let ASTNode::Program(items) = ast {
+        checker.declare_items(items, None);
+    }
+    checker.walk(ast);
+
+    let mut findings = checker.findings;
+    let lint = |message, span| Finding { kind: FindingKind::Lint, message, span };
+    findings.extend(crate::match_check::check_program(ast).into_iter().map(|w| lint(w.message, w.span)));
+    findings.extend(crate::shadow_check::check_program(ast).into_iter().map(|w| lint(w.message, w.span)));
+
+    let mut types = TypeChecker::new();
+    let _ = types.check(ast);
+    findings.extend(found(FindingKind::Type, types.errors()));
+    if strict_mode(ast) {
+        let mut linear = LinearChecker::new();
+        let _ = linear.check(ast);
+        findings.extend(found(FindingKind::Linear, linear.errors()));
+        let mut effects = EffectChecker::new();
+        let _ = effects.check(ast);
+        findings.extend(found(FindingKind::Effect, effects.errors()));
+        let mut ownership = OwnershipChecker::new();
+        let _ = ownership.check(ast);
+        findings.extend(found(FindingKind::Ownership, ownership.errors()));
+    }
+    findings.sort_by_key(|f| f.span.as_ref().map(|s| (s.line, s.col)));
+    findings
+}
+
+/// Whether the program declares `mode expert` or `mode god`
+fn strict_mode(ast: &ASTNode) -> bool {
+    let ASTNode::Program(items) = ast else {
+        return false;
+    };
+    items.iter().any(|item| matches!(item.inner(), ASTNode::Mode(mode) if mode == "expert" || mode == "god"))
+}
+
+fn found<'a>(kind: FindingKind, errors: &'a [(String, Option<Span>)]) -> impl Iterator<Item = Finding> + 'a {
+    errors.iter().map(move |(message, span)| Finding { kind, message: message.clone(), span: span.clone() })
+}
+
+struct Binding {
+    span: Option<Span>,
+    read: bool,
+    /// Report the binding if it is never read
+    lint: bool,
+    /// The struct the value is known to be, from its annotation or literal
+    ty: Option<String>,
+}
+
+struct Checker<'a> {
+    file: Option<&'a Path>,
+    scopes: Vec<HashMap<String, Binding>>,
+    /// Parameter counts of top-level and imported functions, impl methods
+    /// (`Type::method`) and tuple enum variants
+    arity: HashMap<String, usize>,
+    /// Field names of top-level and imported structs
+    structs: HashMap<String, Vec<String>>,
+    /// An import could not be read, so any name may come from it
+    open: bool,
+    /// Span of the innermost node being walked that has one
+    span: Option<Span>,
+    findings: Vec<Finding>,
+}
+
+impl Checker<'_> {
+    fn report(&mut self, kind: FindingKind, message: String, span: Option<Span>) {
+        self.findings.push(Finding { kind, message, span });
+    }
+
+    /// Bind a program's top-level names up front, since functions may use
+    /// globals defined further down. `exported` holds the `pub` names of an
+    /// imported module (all of its names if it has none).
+    fn declare_items(&mut self, items: &[ASTNode], exported: Option<&[String]>) {
+        let exports = |name: &str| exported.is_none_or(|public| public.is_empty() || public.iter().any(|p| p == name));
+        for item in items {
+            let name = match item.inner() {
+                ASTNode::Function { name, params, .. } | ASTNode::AsyncFunction { name, params, .. } => {
+                    if exports(name) {
+                        self.arity.insert(name.clone(), params.len());
+                    }
+                    name
+                }
+                ASTNode::EnumDef { name, variants } => {
+                    for variant in variants.iter().filter(|_| exports(name)) {
+                        let count = match &variant.data {
+                            VariantData::Unit => 0,
+                            VariantData::Tuple(types) => types.len(),
+                            VariantData::Struct(_) => continue,
+                        };
+                        self.arity.insert(format!("{}::{}", name, variant.name), count);
+                    }
+                    name
+                }
+                ASTNode::Impl { ty, methods, .. } => {
+                    for method in methods.iter().filter(|_| exports(ty)) {
+                        if let ASTNode::Function { name, params, .. } = method.inner() {
+                            self.arity.insert(format!("{}::{}", ty, name), params.len());
+                        }
+                    }
+                    ty
+                }
+                ASTNode::StructDef { name, fields } => {
+                    if exports(name) {
+                        self.structs.insert(name.clone(), fields.iter().map(|(field, _)| field.clone()).collect());
+                    }
+                    name
+                }
+                ASTNode::TraitDef { name, .. }
+                | ASTNode::TypeAlias { name, .. }
+                | ASTNode::Let { name, .. }
+                | ASTNode::Const { name, .. } => name,
+                ASTNode::Assign { target, .. } => match target.inner() {
+                    ASTNode::Identifier(name) => name,
+                    _ => continue,
+                },
+                // An imported module's own imports are not passed on
+                ASTNode::Use { .. } if exported.is_some() => continue,
+                ASTNode::Use { alias: Some(alias), .. } => alias,
+                ASTNode::Use { path, alias: None } => {
+                    self.import(path);
+                    continue;
+                }
+                _ => continue,
+            };
+            if exports(name) {
+                let binding = Binding { span: None, read: false, lint: false, ty: None };
+                self.scopes.last_mut().unwrap().entry(name.clone()).or_insert(binding);
+            }
+        }
+    }
+
+    /// Bind what `use "path"` brings in
+    fn import(&mut self, path: &str) {
+        let module = crate::modules::resolve_import(path, self.file, None)
+            .and_then(|file| fs::read_to_string(file).map_err(|e| e.to_string()))
+            .and_then(|source| {
+                let mut parser = Parser::new(&source).without_spans();
+                let ast = parser.parse()?;
+                Ok((ast, parser.public_names().to_vec()))
+            });
+        match module {
+            Ok((ASTNode::Program(items), public)) => self.declare_items(&items, Some(&public)),
+            _ => self.open = true,
+        }
+    }
+
+    fn scoped(&mut self, walk: impl FnOnce(&mut Self)) {
+        self.scopes.push(HashMap::new());
+        walk(self);
+        let scope = self.scopes.pop().unwrap_or_default();
+        for (name, binding) in scope {
+            self.unused(&name, binding);
+        }
+    }
+
+    fn unused(&mut self, name: &str, binding: Binding) {
+        if binding.lint && !binding.read {
+            self.report(FindingKind::Unused, format!("unused variable `{}`", name), binding.span);
+        }
+    }
+
+    /// Bind `name` in the innermost scope; `lint` marks a `let` whose value
+    /// should be read before the binding goes away
+    fn declare(&mut self, name: &str, lint: bool) {
+        let binding = Binding { span: self.span.clone(), read: false, lint: lint && !name.starts_with('_'), ty: None };
+        if self.scopes.len() == 1 {
+            self.scopes[0].entry(name.to_string()).or_insert(binding);
+            return;
+        }
+        let replaced = self.scopes.last_mut().unwrap().insert(name.to_string(), binding);
+        if let Some(old) = replaced {
+            self.unused(name, old);
+        }
+    }
+
+    fn lookup(&mut self, name: &str) -> Option<&mut Binding> {
+        self.scopes.iter_mut().rev().find_map(|scope| scope.get_mut(name))
+    }
+
+    /// Record that `name` holds a `ty` struct, or nothing known with `None`
+    fn typed(&mut self, name: &str, ty: Option<String>) {
+        if let Some(binding) = self.lookup(name) {
+            binding.ty = ty;
+        }
+    }
+
+    /// The declared struct an annotation or value names
+    fn struct_of(&self, annotation: Option<&Type>, value: Option<&ASTNode>) -> Option<String> {
+        let name = match (annotation, value.map(ASTNode::inner)) {
+            (Some(Type::Custom(name)), _) | (None, Some(ASTNode::StructLiteral { name, .. })) => name,
+            _ => return None,
+        };
+        self.structs.contains_key(name).then(|| name.clone())
+    }
+
+    /// Report `field` if the `ty` struct does not declare it
+    fn field(&mut self, ty: &str, field: &str) {
+        let known = self.structs.get(ty).is_none_or(|fields| fields.iter().any(|f| f == field));
+        if !known {
+            let message = format!("struct `{}` has no field `{}`", ty, field);
+            self.report(FindingKind::UnknownField, message, self.span.clone());
+        }
+    }
+
+    /// A read of `name`, or of the module or type that starts a `A::B` path
+    fn reference(&mut self, name: &str) {
+        if name == "self" || name.starts_with("Self::") || name.starts_with("builtin::") {
+            return;
+        }
+        let first = name.split("::").next().unwrap_or(name);
+        let open = self.open;
+        match self.lookup(first) {
+            Some(binding) => binding.read = true,
+            None if is_builtin(first) || open => {}
+            None => self.report(FindingKind::UnknownName, format!("undefined variable `{}`", first), self.span.clone()),
+        }
+    }
+
+    fn call(&mut self, name: &str, args: &[ASTNode], span: Option<Span>) {
+        let first = name.split("::").next().unwrap_or(name);
+        let expected = if let Some(builtin) = name.strip_prefix("builtin::") {
+            if !is_builtin(builtin) {
+                self.report(FindingKind::UnknownName, format!("unknown builtin `{}`", builtin), span.clone());
+            }
+            builtin_arity(builtin)
+        } else if name == "self" || first == "Self" {
+            None
+        } else if self.scopes[1..].iter().any(|scope| scope.contains_key(first)) {
+            // A closure or a local shadowing the function
+            self.reference(name);
+            None
+        } else if let Some(binding) = self.scopes[0].get_mut(first) {
+            binding.read = true;
+            self.arity.get(name).map(|&n| (n, Some(n)))
+        } else if is_builtin(name) {
+            builtin_arity(name)
+        } else {
+            if !self.open {
+                self.report(FindingKind::UnknownName, format!("unknown function `{}`", name), span.clone());
+            }
+            None
+        };
+
+        let spread = args.iter().any(|arg| matches!(arg.inner(), ASTNode::Spread(_)));
+        let count = args.len();
+        let expected = match expected {
+            _ if spread => None,
+            Some((min, Some(max))) if min == max && count != min => Some(min.to_string()),
+            Some((min, _)) if count < min => Some(format!("at least {}", min)),
+            Some((_, Some(max))) if count > max => Some(format!("at most {}", max)),
+            _ => None,
+        };
+        if let Some(expected) = expected {
+            self.report(
+                FindingKind::Arity,
+                format!("`{}` expects {} argument(s), got {}", name, expected, count),
+                span,
+            );
+        }
+    }
+
+    fn function<'n>(&mut self, params: impl IntoIterator<Item = &'n str>, body: &ASTNode) {
+        self.scoped(|this| {
+            for param in params {
+                this.declare(param, false);
+            }
+            this.walk(body);
+        });
+    }
+
+    /// A function with typed params; `owner` is the struct an impl method's `self` is
+    fn method(&mut self, params: &[Param], body: &ASTNode, owner: Option<&str>) {
+        self.scoped(|this| {
+            for param in params {
+                this.declare(&param.name, false);
+                let ty = match owner {
+                    Some(owner) if param.name == "self" => this.structs.contains_key(owner).then(|| owner.to_string()),
+                    _ => this.struct_of(param.ty.as_ref(), None),
+                };
+                this.typed(&param.name, ty);
+            }
+            this.walk(body);
+        });
+    }
+
+    /// A block's statements, flagging those after one that always leaves it
+    fn block(&mut self, stmts: &[ASTNode]) {
+        let exit = stmts.iter().position(|stmt| {
+            matches!(stmt.inner(), ASTNode::Return(_) | ASTNode::Break(_) | ASTNode::Continue(_) | ASTNode::Throw(_))
+        });
+        if let Some(dead) = exit.and_then(|i| stmts.get(i + 1..)).filter(|dead| !dead.is_empty()) {
+            let span = match (dead[0].span(), dead[dead.len() - 1].span()) {
+                (Some(first), Some(last)) => {
+                    Some(Span { end_line: last.end_line, end_col: last.end_col, ..first.clone() })
+                }
+                (first, _) => first.cloned(),
+            };
+            self.report(FindingKind::Unreachable, "unreachable code".to_string(), span);
+        }
+        for stmt in stmts {
+            self.walk(stmt);
+        }
+    }
+
+    fn pattern(&mut self, pattern: &Pattern) {
+        match pattern {
+            Pattern::Identifier(name) => self.declare(name, false),
+            Pattern::Or(patterns) | Pattern::Tuple(patterns) => {
+                for pattern in patterns {
+                    self.pattern(pattern);
+                }
+            }
+            Pattern::Struct { fields, .. } => {
+                for (_, pattern) in fields {
+                    self.pattern(pattern);
+                }
+            }
+            Pattern::Enum { data: Some(data), .. } => self.pattern(data),
+            Pattern::Enum { data: None, .. } | Pattern::Wildcard | Pattern::Literal(_) => {}
+        }
+    }
+
+    fn walk(&mut self, node: &ASTNode) {
+        use ASTNode::*;
+        match node {
+            Spanned { span, node } => {
+                let outer = self.span.replace(span.clone());
+                self.walk(node);
+                self.span = outer;
+            }
+            Block(stmts) => self.scoped(|this| this.block(stmts)),
+            Function { name, params, body, .. } | AsyncFunction { name, params, body, .. } => {
+                self.declare(name, false);
+                self.method(params, body, None);
+            }
+            Test { params, body, .. } => self.method(params, body, None),
+            Lambda { params, body } => self.function(params.iter().map(String::as_str), body),
+            Impl { ty, methods, .. } => {
+                for method in methods {
+                    if let Function { params, body, .. } = method.inner() {
+                        self.method(params, body, Some(ty));
+                    }
+                }
+            }
+            TraitDef { methods, .. } => {
+                for method in methods {
+                    if let Function { params, body, .. } = method.inner() {
+                        self.method(params, body, None);
+                    }
+                }
+            }
+            Let { name, value, ty, .. } | Const { name, value, ty } => {
+                self.walk(value);
+                self.declare(name, true);
+                let ty = self.struct_of(ty.as_ref(), Some(value));
+                self.typed(name, ty);
+            }
+            Assign { target, value } => {
+                self.walk(value);
+                match target.inner() {
+                    // Assigning to an unbound name creates it
+                    Identifier(name) if !name.contains("::") => {
+                        if self.lookup(name).is_none() {
+                            self.declare(name, false);
+                        }
+                        let ty = self.struct_of(None, Some(value));
+                        self.typed(name, ty);
+                    }
+                    _ => self.walk(target),
+                }
+            }
+            StructLiteral { name, fields } => {
+                for (field, value) in fields {
+                    self.field(name, field);
+                    self.walk(value);
+                }
+            }
+            FieldAccess { obj, field } => {
+                if let Identifier(name) = obj.inner() {
+                    let ty = self.lookup(name).and_then(|binding| binding.ty.clone());
+                    if let Some(ty) = ty {
+                        self.field(&ty, field);
+                    }
+                }
+                self.walk(obj);
+            }
+            For { var, iter, body } => {
+                self.walk(iter);
+                self.scoped(|this| {
+                    this.declare(var, false);
+                    this.walk(body);
+                });
+            }
+            ListComp { expr, var, iter, filter } => {
+                self.walk(iter);
+                self.scoped(|this| {
+                    this.declare(var, false);
+                    if let Some(filter) = filter {
+                        this.walk(filter);
+                    }
+                    this.walk(expr);
+                });
+            }
+            IfLet { pattern, value, then_body, else_body } => {
+                self.walk(value);
+                self.scoped(|this| {
+                    this.declare(pattern, false);
+                    this.walk(then_body);
+                });
+                if let Some(else_body) = else_body {
+                    self.walk(else_body);
+                }
+            }
+            TryCatch { try_body, catch_var, catch_body } => {
+                self.walk(try_body);
+                self.scoped(|this| {
+                    this.declare(catch_var, false);
+                    this.walk(catch_body);
+                });
+            }
+            Match { expr, arms } => {
+                self.walk(expr);
+                for arm in arms {
+                    self.scoped(|this| {
+                        this.pattern(&arm.pattern);
+                        if let Some(guard) = &arm.guard {
+                            this.walk(guard);
+                        }
+                        this.walk(&arm.body);
+                    });
+                }
+            }
+            // Top-level imports were bound before the walk
+            Use { path, alias } if self.scopes.len() > 1 => match alias {
+                Some(alias) => self.declare(alias, false),
+                None => self.import(path),
+            },
+            Identifier(name) => self.reference(name),
+            Call { func, args } => {
+                match func.inner() {
+                    Identifier(name) => {
+                        // A call's span starts at the callee; narrow it to the name
+                        let span = self.span.as_ref().map(|span| Span {
+                            end_line: span.line,
+                            end_col: span.col + name.chars().count(),
+                            ..span.clone()
+                        });
+                        self.call(name, args, span);
+                    }
+                    _ => self.walk(func),
+                }
+                for arg in args {
+                    self.walk(arg);
+                }
+            }
+            InterpolatedString(segments) => {
+                for (_, expr) in segments.iter().filter(|(is_expr, _)| *is_expr) {
+                    if let Ok(ast) = Parser::new(expr).without_spans().parse() {
+                        self.walk(&ast);
+                    }
+                }
+            }
+            other => {
+                for child in other.children() {
+                    self.walk(child);
+                }
+            }
+        }
+    }
+}
+
+/// The fewest and most arguments a builtin takes
+fn builtin_arity(name: &str) -> Option<(usize, Option<usize>)> {
+    builtins::lookup(name).map(|builtin| (builtin.min_args, builtin.max_args))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(src: &str) -> Vec<(FindingKind, String, usize)> {
+        let ast = Parser::new(src).parse().unwrap();
+        check_program(&ast, None)
+            .into_iter()
+            .map(|f| (f.kind, f.message, f.span.map_or(0, |s| s.line)))
+            .collect()
+    }
+
+    #[test]
+    fn test_names_and_arity() {
+        let src = "fn add(a, b) { a + b }\nfn main() {\n    let total = add(1, 2, 3)\n    println(totl)\n    println(len(\"ab\", 1), add(1))\n    missing(total)\n    println(f\"{add(1, 2)} {nope}\")\n    tcp_recv()\n}\nlet counter = 0\nfn bump() { counter = counter + 1 }\n";
+        let findings = check(src);
+        let expected = [
+            (FindingKind::Arity, "`add` expects 2 argument(s), got 3", 3),
+            (FindingKind::UnknownName, "undefined variable `totl`", 4),
+            (FindingKind::Arity, "`add` expects 2 argument(s), got 1", 5),
+            (FindingKind::UnknownName, "unknown function `missing`", 6),
+            (FindingKind::UnknownName, "undefined variable `nope`", 7),
+            (FindingKind::Arity, "`tcp_recv` expects at least 1 argument(s), got 0", 8),
+        ];
+        let expected: Vec<_> = expected.iter().map(|&(k, m, l)| (k, m.to_string(), l)).collect();
+        assert_eq!(findings, expected);
+    }
+
+    #[test]
+    fn test_unreachable_and_unused() {
+        let src = "fn f(x) {\n    let unused = 1\n    let _ignored = 2\n    let y = x\n    let y = 3\n    return y\n    println(y)\n}\nlet top = 1\n";
+        let findings = check(src);
+        let kinds: Vec<_> = findings.iter().map(|(k, m, l)| (*k, m.as_str(), *l)).collect();
+        assert_eq!(
+            kinds,
+            [
+                (FindingKind::Unused, "unused variable `unused`", 2),
+                (FindingKind::Unused, "unused variable `y`", 4),
+                (FindingKind::Unreachable, "unreachable code", 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fields_and_annotations() {
+        let src = "struct Point { x: int, y: int }\nimpl Point {\n    fn norm(self) -> int { self.x + self.z }\n}\nfn label(n: int) -> string {\n    return n\n}\nlet p = Point { x: 1, y: 2, w: 3 }\nlet n: int = \"three\"\nlet f: float = 2\nprintln(label(\"x\") - 1, p.q)\n";
+        let findings = check(src);
+        let kinds: Vec<_> = findings.iter().map(|(k, m, l)| (*k, m.as_str(), *l)).collect();
+        assert_eq!(
+            kinds,
+            [
+                (FindingKind::UnknownField, "struct `Point` has no field `z`", 3),
+                (FindingKind::Type, "`label` returns string but this returns int", 6),
+                (FindingKind::UnknownField, "struct `Point` has no field `w`", 8),
+                (FindingKind::Type, "`n` is declared int but assigned string", 9),
+                (FindingKind::Type, "argument 1 of `label` should be int, not string", 11),
+                (FindingKind::Type, "cannot apply `-` to string and int", 11),
+                (FindingKind::UnknownField, "struct `Point` has no field `q`", 11),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strict_mode_checks() {
+        let body = "fn own(v) { v }\nfn main() {\n    let name = \"knull\"\n    own(name)\n    println(name)\n    let n = 5\n    own(n)\n    println(n)\n    let h: &int = 1\n    println(h)\n    println(h)\n}\neffect { } println(\"hi\")\n";
+        let strict: Vec<_> = check(&format!("mode expert\n{}", body))
+            .into_iter()
+            .filter(|(kind, _, _)| matches!(kind, FindingKind::Linear | FindingKind::Effect | FindingKind::Ownership))
+            .collect();
+        let expected = [
+            (FindingKind::Ownership, "use of moved value `name`", 6),
+            (FindingKind::Linear, "linear value `h` used more than once", 12),
+            (FindingKind::Effect, "expression performs io but its effect annotation allows only {}", 14),
+        ];
+        let expected: Vec<_> = expected.iter().map(|&(k, m, l)| (k, m.to_string(), l)).collect();
+        assert_eq!(strict, expected);
+
+        // Novice programs skip these checks
+        assert!(!check(body).iter().any(|(kind, _, _)| matches!(kind, FindingKind::Linear | FindingKind::Ownership)));
+    }
+}
@@ -1,14 +1,18 @@
 //! Knull CLI - Command Line Interface
 //! Professional compiler interface for the Knull programming language
 
-use crate::pkg::manager::PackageManager;
+use crate::bench::{self, Baseline};
+use crate::interpreter::{ErrorKind, RuntimeError};
+use crate::parser::TestKind;
+use crate::pkg::manager::{FetchMode, PackageManager};
+use crate::test_runner::{self, Outcome, TestOptions};
 use colored::Colorize;
 use std::fs;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 
 /// Run a Knull file with rich error output
-pub fn run_file(path: &Path, verbose: bool) -> Result<(), String> {
+pub fn run_file(path: &Path, verbose: bool, vm: bool, max_depth: Option<usize>) -> Result<(), String> {
     if verbose {
         println!("{} {}", "Running".bright_blue().bold(), path.display());
     }
@@ -20,9 +24,11 @@ pub fn run_file(path: &Path, verbose: bool) -> Result<(), String> {
         println!("  {} Parsing...", "→".bright_black());
     }
 
-    let mut parser = crate::parser::Parser::new(&source);
-    let ast = parser.parse().map_err(|e| {
-        format_error_in_source(&source, path.to_str().unwrap_or("<file>"), &e)
+    let file = path.to_str().unwrap_or("<file>");
+    let mut parser = crate::parser::Parser::new(&source).with_file(file);
+    let ast = parser.parse().map_err(|e| match parser.error_span() {
+        Some(span) => format_error_at_span(&source, &e, span),
+        None => format_error_in_source(&source, file, &e),
     })?;
 
     if verbose {
@@ -30,10 +36,43 @@ pub fn run_file(path: &Path, verbose: bool) -> Result<(), String> {
         println!("  {} Executing...", "→".bright_black());
     }
 
-    let mut interp = crate::interpreter::Interpreter::new();
-    interp.execute(&ast).map_err(|e| {
-        format_error_in_source(&source, path.to_str().unwrap_or("<file>"), &e)
-    })
+    let mut interp = crate::interpreter::Interpreter::new()
+        .with_file(std::path::Path::new(file))
+        .with_max_depth(resolve_max_depth(path, max_depth));
+    let result = if vm {
+        crate::vm::run(&ast, &mut interp, verbose)
+    } else {
+        interp.execute(&ast)
+    };
+    result.map_err(|e| format_runtime_error(&source, file, &e))
+}
+
+/// Run the entry point named in the nearest knull.toml (`knull run` without a file),
+/// after fetching and checking its locked dependencies, resolving them first if need be
+pub fn run_project(verbose: bool, vm: bool, max_depth: Option<usize>, mode: FetchMode) -> Result<(), String> {
+    let manifest = crate::pkg::manager::find_nearest_manifest()
+        .ok_or("No file given and no knull.toml found; use `knull run <file>`")?;
+    let root = manifest.parent().map(Path::to_path_buf).unwrap_or_default();
+    let mut pm = PackageManager::new(root)?.with_mode(fetch_mode(mode)?);
+    pm.fetch_dependencies()?;
+    run_file(&pm.entry_path(), verbose, vm, max_depth)
+}
+
+/// Call depth limit for running `path`: `--max-depth`, else the project's, else the default
+pub(crate) fn resolve_max_depth(path: &Path, max_depth: Option<usize>) -> usize {
+    max_depth
+        .or_else(|| manifest_max_depth(path))
+        .unwrap_or(crate::interpreter::DEFAULT_MAX_DEPTH)
+}
+
+/// `[run] max-depth` from the knull.toml of the project a file belongs to
+fn manifest_max_depth(path: &Path) -> Option<usize> {
+    let path = path.canonicalize().ok()?;
+    let root = crate::modules::project_root(path.parent()?)?;
+    crate::pkg::manager::PackageManifest::load(&root.join("knull.toml"))
+        .ok()?
+        .run
+        .max_depth
 }
 
 /// Evaluate a Knull expression/snippet from a string (for `knull eval`)
@@ -75,6 +114,66 @@ fn format_error_in_source(source: &str, file: &str, err: &str) -> String {
     format!("{}: {}", "error".bright_red().bold(), err)
 }
 
+/// Format an error with the offending span underlined by carets
+fn format_error_at_span(source: &str, err: &str, span: &crate::parser::Span) -> String {
+    let lines: Vec<&str> = source.lines().collect();
+    let ln = span.line;
+    if ln == 0 || ln > lines.len() {
+        return format!("{}: {}\n → {}", "error".bright_red().bold(), err, span);
+    }
+    let text = lines[ln - 1];
+    let width = text.chars().count();
+    let start = span.col.saturating_sub(1).min(width);
+    let end = if span.end_line == ln { span.end_col.saturating_sub(1) } else { width };
+    let carets = end.clamp(start + 1, width.max(start + 1)) - start;
+    let before = if ln > 1 { format!("{:>4} │ {}\n", ln - 1, lines[ln - 2]) } else { String::new() };
+    let at = format!("{:>4} │ {}\n", ln, text);
+    let marker = format!("     │ {}{}\n", " ".repeat(start), "^".repeat(carets).red());
+    let after = if ln < lines.len() { format!("{:>4} │ {}\n", ln + 1, lines[ln]) } else { String::new() };
+    format!(
+        "{}: {}\n → {}\n{}{}{}{}",
+        "error".bright_red().bold(),
+        err,
+        span,
+        before, at, marker, after
+    )
+}
+
+/// Format a runtime error with its source location and Knull call stack
+pub(crate) fn format_runtime_error(source: &str, file: &str, err: &RuntimeError) -> String {
+    let kind = err.kind.name();
+    let msg = if err.kind == ErrorKind::Error || err.message.starts_with(kind) {
+        err.message.clone()
+    } else {
+        format!("{}: {}", kind, err.message)
+    };
+    let mut out = match &err.span {
+        Some(span) if span.file.as_deref() == Some(file) => format_error_at_span(source, &msg, span),
+        Some(span) => format!("{}: {}\n → {}\n", "error".bright_red().bold(), msg, span),
+        None => format_error_in_source(source, file, &msg),
+    };
+    if !err.stack.is_empty() {
+        if !out.ends_with('\n') {
+            out.push('\n');
+        }
+        out.push_str(&format!("{}\n", "stack trace:".bright_black()));
+        // Runaway recursion repeats the same frame thousands of times
+        let frames: Vec<String> = err.stack.iter().map(|f| f.to_string()).collect();
+        let mut i = 0;
+        while i < frames.len() {
+            let run = frames[i..].iter().take_while(|f| **f == frames[i]).count();
+            for frame in &frames[i..i + run.min(3)] {
+                out.push_str(&format!("   at {}\n", frame));
+            }
+            if run > 3 {
+                out.push_str(&format!("   ... previous frame repeated {} more times\n", run - 3));
+            }
+            i += run;
+        }
+    }
+    out
+}
+
 fn extract_line_number(s: &str) -> Option<usize> {
     // Matches "line N" or "[N:" or ":N:"
     if let Some(pos) = s.find("line ") {
@@ -184,70 +283,154 @@ pub fn generate_asm(path: &Path, output: Option<&Path>) -> Result<(), String> {
     Err("LLVM backend not available".to_string())
 }
 
-/// Check syntax and types without building
-pub fn check_file(path: &Path) -> Result<(), String> {
+/// Check a file without running it: parse it, then report what
+/// `checker::check_program` finds. With `json` the findings are printed as
+/// one JSON document, for CI. Fails if there are any errors.
+pub fn check_file(path: &Path, json: bool) -> Result<(), String> {
     let source = fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
 
-    print!("Checking {}... ", path.display());
-    io::stdout().flush().ok();
+    if !json {
+        print!("Checking {}... ", path.display());
+        io::stdout().flush().ok();
+    }
 
-    let mut parser = crate::parser::Parser::new(&source);
-    let _ast = parser.parse().map_err(|e| format!("Parse error: {}", e))?;
+    let mut parser = crate::parser::Parser::new(&source).with_file(path.to_str().unwrap_or("<file>"));
+    let (findings, parse_error) = match parser.parse() {
+        Ok(ast) => (crate::checker::check_program(&ast, Some(path)), None),
+        Err(e) => (Vec::new(), Some((e, parser.error_span().cloned()))),
+    };
+    let errors = findings.iter().filter(|f| f.kind.is_error()).count() + parse_error.iter().count();
+
+    if json {
+        println!("{}", check_report(path, parse_error.as_ref(), &findings));
+    } else if let Some((e, span)) = parse_error {
+        return Err(match span {
+            Some(span) => format!("Parse error: {} at {}", e, span),
+            None => format!("Parse error: {}", e),
+        });
+    } else if !findings.is_empty() {
+        println!();
+        for finding in &findings {
+            let label = if finding.kind.is_error() { "error:".red().bold() } else { "warning:".yellow().bold() };
+            match &finding.span {
+                Some(span) => println!("  {} {} at {}", label, finding.message, span),
+                None => println!("  {} {}", label, finding.message),
+            }
+        }
+    }
 
-    println!("{}", "✓ No errors found".green());
+    if errors > 0 {
+        return Err(format!("{} error(s) in {}", errors, path.display()));
+    }
+    if !json {
+        println!("{}", "✓ No errors found".green());
+    }
     Ok(())
 }
 
-/// Format a Knull file
-pub fn format_file(path: &Path) -> Result<(), String> {
-    let source = fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
-
-    println!("{} {}", "Formatting".bright_blue(), path.display());
+/// `knull check --format json` output: every finding with its severity,
+/// check and range, and a parse error as a `syntax` finding
+fn check_report(
+    path: &Path,
+    parse_error: Option<&(String, Option<crate::parser::Span>)>,
+    findings: &[crate::checker::Finding],
+) -> String {
+    let entry = |severity: &str, kind: &str, message: &str, span: Option<&crate::parser::Span>| {
+        serde_json::json!({
+            "severity": severity,
+            "kind": kind,
+            "message": message,
+            "line": span.map(|s| s.line),
+            "column": span.map(|s| s.col),
+            "end_line": span.map(|s| s.end_line),
+            "end_column": span.map(|s| s.end_col),
+        })
+    };
+    let mut diagnostics = Vec::new();
+    if let Some((message, span)) = parse_error {
+        diagnostics.push(entry("error", "syntax", message, span.as_ref()));
+    }
+    for finding in findings {
+        let severity = if finding.kind.is_error() { "error" } else { "warning" };
+        diagnostics.push(entry(severity, finding.kind.name(), &finding.message, finding.span.as_ref()));
+    }
+    let errors = diagnostics.iter().filter(|d| d["severity"] == "error").count();
+    let report = serde_json::json!({
+        "file": path.display().to_string(),
+        "errors": errors,
+        "warnings": diagnostics.len() - errors,
+        "diagnostics": diagnostics,
+    });
+    serde_json::to_string_pretty(&report).unwrap()
+}
 
-    let mut lexer = crate::lexer::Lexer::new(&source);
-    let tokens = lexer.tokenize();
+/// Format Knull files in place, or every `.knull` file under a directory.
+/// With `check`, write nothing and fail if any file would change; with
+/// `stdin`, format standard input to standard output.
+pub fn format_files(paths: &[PathBuf], check: bool, stdin: bool) -> Result<(), String> {
+    if stdin {
+        let mut source = String::new();
+        io::Read::read_to_string(&mut io::stdin(), &mut source)
+            .map_err(|e| format!("Cannot read standard input: {}", e))?;
+        let formatted = crate::formatter::format_source(&source).map_err(|e| format!("<stdin>: {}", e))?;
+        if check {
+            return if formatted == source { Ok(()) } else { Err("<stdin> is not formatted".to_string()) };
+        }
+        print!("{}", formatted);
+        return Ok(());
+    }
 
-    let mut formatted = String::new();
-    let mut indent = 0;
-    let mut prev_was_newline = true;
+    let roots = if paths.is_empty() { vec![PathBuf::from(".")] } else { paths.to_vec() };
+    let mut files = Vec::new();
+    for root in &roots {
+        if root.is_dir() {
+            files.extend(
+                walkdir::WalkDir::new(root)
+                    .into_iter()
+                    .flatten()
+                    .map(|entry| entry.into_path())
+                    .filter(|path| path.extension().is_some_and(|e| e == "knull")),
+            );
+        } else {
+            files.push(root.clone());
+        }
+    }
+    files.sort();
+    files.dedup();
 
-    for token in tokens {
-        use crate::lexer::TokenKind;
-        match token.kind {
-            TokenKind::LBrace => {
-                formatted.push_str(" {\n");
-                indent += 4;
-                prev_was_newline = true;
-            }
-            TokenKind::RBrace => {
-                if indent >= 4 { indent -= 4; }
-                if !prev_was_newline { formatted.push('\n'); }
-                formatted.push_str(&" ".repeat(indent));
-                formatted.push_str("}\n");
-                prev_was_newline = true;
-            }
-            TokenKind::Semicolon => {
-                formatted.push('\n');
-                prev_was_newline = true;
-            }
-            TokenKind::Eof => break,
-            _ => {
-                if prev_was_newline {
-                    formatted.push_str(&" ".repeat(indent));
-                    prev_was_newline = false;
-                } else if token.kind != TokenKind::LParen
-                    && token.kind != TokenKind::RParen
-                    && token.kind != TokenKind::Comma
-                {
-                    formatted.push(' ');
-                }
-                formatted.push_str(&token.value);
+    let mut unformatted = 0;
+    let mut failed = 0;
+    for path in &files {
+        let source = fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read '{}': {}", path.display(), e))?;
+        let formatted = match crate::formatter::format_source(&source) {
+            Ok(formatted) => formatted,
+            Err(e) => {
+                eprintln!("{} {}: {}", "error:".red().bold(), path.display(), e);
+                failed += 1;
+                continue;
             }
+        };
+        if formatted == source {
+            continue;
+        }
+        unformatted += 1;
+        if check {
+            println!("{} {}", "Would reformat".bright_yellow(), path.display());
+            let diff = similar::TextDiff::from_lines(&source, &formatted);
+            print!("{}", diff.unified_diff().context_radius(2).header(&path.display().to_string(), &path.display().to_string()));
+        } else {
+            fs::write(path, &formatted).map_err(|e| format!("Cannot write '{}': {}", path.display(), e))?;
+            println!("{} Formatted {}", "✓".green().bold(), path.display());
         }
     }
 
-    fs::write(path, formatted).map_err(|e| format!("Failed to write: {}", e))?;
-    println!("{} Formatted {}", "✓".green().bold(), path.display());
+    if failed > 0 {
+        return Err(format!("{} file(s) could not be formatted", failed));
+    }
+    if check && unformatted > 0 {
+        return Err(format!("{} of {} file(s) would be reformatted", unformatted, files.len()));
+    }
     Ok(())
 }
 
@@ -267,38 +450,31 @@ pub fn new_project(name: &str) -> Result<(), String> {
 }
 
 /// Add a dependency to the project
-pub fn add_dependency(package: &str, version: Option<&str>) -> Result<(), String> {
+pub fn add_dependency(package: &str, version: Option<&str>, mode: FetchMode) -> Result<(), String> {
     let ver = version.unwrap_or("^1.0");
     println!("{} {} {}", "Adding".bright_yellow(), package.bright_cyan(), ver.bright_black());
-    let current_dir =
-        std::env::current_dir().map_err(|e| format!("Failed to get current directory: {}", e))?;
-    let mut pm = PackageManager::new(current_dir)?;
+    let mut pm = project_manager(mode)?;
     pm.add_dependency(package, ver)?;
     println!("{} Added {}", "✓".green().bold(), package);
     Ok(())
 }
 
 /// Remove a dependency from the project
-pub fn remove_dependency(package: &str) -> Result<(), String> {
+pub fn remove_dependency(package: &str, mode: FetchMode) -> Result<(), String> {
     println!("{} {}", "Removing".bright_yellow(), package.bright_cyan());
-    let current_dir =
-        std::env::current_dir().map_err(|e| format!("Failed to get current directory: {}", e))?;
-    let mut pm = PackageManager::new(current_dir)?;
+    let mut pm = project_manager(mode)?;
     pm.remove_dependency(package)?;
     println!("{} Removed {}", "✓".green().bold(), package);
     Ok(())
 }
 
 /// Update all dependencies or a specific package
-pub fn update_dependencies(package: Option<&str>) -> Result<(), String> {
-    let current_dir =
-        std::env::current_dir().map_err(|e| format!("Failed to get current directory: {}", e))?;
-    let mut pm = PackageManager::new(current_dir)?;
+pub fn update_dependencies(package: Option<&str>, mode: FetchMode) -> Result<(), String> {
+    let mut pm = project_manager(mode)?;
     match package {
         Some(pkg) => {
             println!("{} {}", "Updating".bright_yellow(), pkg.bright_cyan());
-            let constraint = pm.manifest().dependencies.get(pkg).cloned().unwrap_or_else(|| "^1.0".to_string());
-            pm.update_package(pkg, &constraint)?;
+            pm.update_package(pkg)?;
             println!("{} Updated {}", "✓".green().bold(), pkg);
         }
         None => { pm.update_all_dependencies()?; }
@@ -307,10 +483,8 @@ pub fn update_dependencies(package: Option<&str>) -> Result<(), String> {
 }
 
 /// Publish package to registry
-pub fn publish(local: bool, token: Option<&str>) -> Result<(), String> {
-    let current_dir =
-        std::env::current_dir().map_err(|e| format!("Failed to get current directory: {}", e))?;
-    let pm = PackageManager::new(current_dir)?;
+pub fn publish(local: bool, token: Option<&str>, mode: FetchMode) -> Result<(), String> {
+    let pm = project_manager(mode)?;
     if local {
         println!("{}", "Publishing to local registry...".bright_yellow());
         pm.publish_local()?;
@@ -319,6 +493,9 @@ pub fn publish(local: bool, token: Option<&str>) -> Result<(), String> {
             .map(|s| s.to_string())
             .or_else(|| std::env::var("KNULL_REGISTRY_TOKEN").ok())
             .ok_or("Authentication token required. Use --token or set KNULL_REGISTRY_TOKEN")?;
+        if fetch_mode(mode)?.offline {
+            return Err("--offline forbids publishing to the registry; use --local".to_string());
+        }
         println!("{}", "Publishing to registry...".bright_yellow());
         pm.publish_registry(&auth_token)?;
     }
@@ -326,16 +503,67 @@ pub fn publish(local: bool, token: Option<&str>) -> Result<(), String> {
 }
 
 /// Fetch dependencies
-pub fn fetch_dependencies() -> Result<(), String> {
+pub fn fetch_dependencies(mode: FetchMode) -> Result<(), String> {
     println!("{}", "Fetching dependencies...".bright_yellow());
-    let current_dir =
-        std::env::current_dir().map_err(|e| format!("Failed to get current directory: {}", e))?;
-    let pm = PackageManager::new(current_dir)?;
+    let mut pm = project_manager(mode)?;
     pm.fetch_dependencies()?;
     println!("{} Dependencies fetched", "✓".green().bold());
     Ok(())
 }
 
+/// Copy every locked package into the project's vendor/ directory
+pub fn vendor_dependencies(mode: FetchMode) -> Result<(), String> {
+    println!("{}", "Vendoring dependencies...".bright_yellow());
+    let mut pm = project_manager(mode)?;
+    let count = pm.vendor()?;
+    println!("{} Vendored {} package{} into vendor/", "✓".green().bold(), count, if count == 1 { "" } else { "s" });
+    Ok(())
+}
+
+/// Print the project's locked dependency graph as a tree
+pub fn dependency_tree(mode: FetchMode) -> Result<(), String> {
+    let mut pm = project_manager(mode)?;
+    print!("{}", pm.tree()?);
+    Ok(())
+}
+
+/// Search the registry and the local registry for packages whose name contains `query`
+pub fn search_packages(query: &str, mode: FetchMode) -> Result<(), String> {
+    let mut found: Vec<String> = crate::pkg::local_registry::list_local_packages()?
+        .into_iter()
+        .filter(|name| name.contains(query))
+        .collect();
+    if !fetch_mode(mode)?.offline {
+        match crate::pkg::http_registry::search_registry(query) {
+            Ok(remote) => found.extend(remote),
+            Err(e) if found.is_empty() => return Err(e),
+            Err(e) => eprintln!("{} {}", "warning:".bright_yellow().bold(), e),
+        }
+    }
+    found.sort();
+    found.dedup();
+    if found.is_empty() {
+        println!("No packages match '{}'", query);
+    }
+    for name in found {
+        println!("  {}", name.bright_cyan());
+    }
+    Ok(())
+}
+
+/// `mode`, made offline if ~/.knull/config.toml says so
+fn fetch_mode(mut mode: FetchMode) -> Result<FetchMode, String> {
+    mode.offline |= crate::pkg::config::Config::load()?.net.offline;
+    Ok(mode)
+}
+
+/// The package manager for the project in the current directory
+fn project_manager(mode: FetchMode) -> Result<PackageManager, String> {
+    let current_dir =
+        std::env::current_dir().map_err(|e| format!("Failed to get current directory: {}", e))?;
+    Ok(PackageManager::new(current_dir)?.with_mode(fetch_mode(mode)?))
+}
+
 /// List project dependencies
 pub fn list_dependencies() -> Result<(), String> {
     let current_dir =
@@ -346,15 +574,15 @@ pub fn list_dependencies() -> Result<(), String> {
     if deps.is_empty() {
         println!("  (none)");
     } else {
-        for (name, version) in deps {
-            println!("  {} {}", name.bright_cyan(), version.bright_black());
+        for (name, dep) in deps {
+            println!("  {} {}", name.bright_cyan(), dep.to_string().bright_black());
         }
     }
     let dev_deps = &pm.manifest().dev_dependencies;
     if !dev_deps.is_empty() {
         println!("\n{}", "Dev Dependencies:".bright_yellow().bold());
-        for (name, version) in dev_deps {
-            println!("  {} {}", name.bright_cyan(), version.bright_black());
+        for (name, dep) in dev_deps {
+            println!("  {} {}", name.bright_cyan(), dep.to_string().bright_black());
         }
     }
     Ok(())
@@ -362,259 +590,140 @@ pub fn list_dependencies() -> Result<(), String> {
 
 /// Run tests
 pub fn run_tests() -> Result<(), String> {
-    run_tests_with_options(false, false)
+    run_tests_with_options(false, false, &TestOptions::default())
 }
 
 /// Run tests with options
-pub fn run_tests_with_options(bench: bool, property_test: bool) -> Result<(), String> {
+pub fn run_tests_with_options(bench: bool, property_test: bool, options: &TestOptions) -> Result<(), String> {
     if bench {
         println!("{}", "Running benchmarks...".bright_yellow().bold());
     } else if property_test {
-        println!("{}", "Running property-based tests...".bright_yellow().bold());
+        let banner = format!("Running property-based tests (seed {})...", options.property.seed);
+        println!("{}", banner.bright_yellow().bold());
     } else {
         println!("{}", "Running tests...".bright_yellow().bold());
     }
 
-    let test_dirs = vec!["tests", "test", "src/tests"];
-    let mut found_tests = false;
-    let mut passed = 0;
-    let mut failed = 0;
-    let mut skipped = 0;
-
-    for dir in test_dirs {
-        if let Ok(entries) = fs::read_dir(dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.extension().map_or(false, |e| e == "knull") {
-                    found_tests = true;
-                    print!("  {} ... ", path.display());
-                    io::stdout().flush().ok();
-                    if path.to_string_lossy().contains("@ignore") {
-                        println!("{}", "SKIP".yellow());
-                        skipped += 1;
-                    } else {
-                        match run_file(&path, false) {
-                            Ok(_) => { println!("{}", "PASS".green()); passed += 1; }
-                            Err(e) => { println!("{}\n       {}", "FAIL".red().bold(), e); failed += 1; }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    if !found_tests {
-        println!("  No test files found in tests/ directory.");
+    let roots: Vec<PathBuf> = if options.paths.is_empty() {
+        test_runner::TEST_DIRS.iter().map(PathBuf::from).collect()
     } else {
-        println!();
-        let summary = format!(
-            "{} passed  {}  {}",
-            passed.to_string().green().bold(),
-            if failed > 0 { format!("{} failed", failed).red().bold().to_string() }
-              else { format!("{} failed", failed).bright_black().to_string() },
-            format!("{} skipped", skipped).bright_black()
-        );
-        println!("Results: {}", summary);
-    }
-
-    Ok(())
-}
-
-// ── REPL ──────────────────────────────────────────────────────────────────────
-
-const REPL_HELP: &str = r#"
-Knull REPL — interactive session
-
-  Type any Knull expression or statement and press Enter.
-  Multi-line blocks: keep typing after an opening '{'; enter a blank line to submit.
-
-Commands:
-  :help   :h        Show this help
-  :quit   :q        Exit the REPL
-  :vars             List all variables in scope
-  :fns              List all defined functions
-  :reset            Reset interpreter state
-  :load <file>      Load and execute a .knull file into this session
-  :type <expr>      Show the type of an expression
-  :clear            Clear the screen
-"#;
-
-/// Start the interactive REPL with persistent state, multi-line support, and commands
-pub fn start_repl() -> Result<(), String> {
-    println!("{}", r#"
-  _  __              _ _
- | |/ /_ __  _   _| | |
- | ' /| '_ \| | | | | |
- | . \| | | | |_| | | |
- |_|\_\_| |_|\__,_|_|_|
-"#.bright_purple().bold());
-    println!(
-        "  {} {}  —  {}",
-        "Knull".bright_purple().bold(),
-        "v2.0.0".bright_white(),
-        "The God Programming Language".bright_yellow()
-    );
-    println!("  Type {} for available commands\n", ":help".bright_cyan());
-
-    let mut interp = crate::interpreter::Interpreter::new();
-    let mut input_buf = String::new();
-    let mut continuation = false; // true when we're inside a multi-line block
-
-    loop {
-        // Prompt
-        if continuation {
-            print!("{}", "  ... ".bright_black());
-        } else {
-            print!("{}", "knull❯ ".bright_magenta().bold());
-        }
-        io::stdout().flush().ok();
-
-        let mut line = String::new();
-        match io::stdin().read_line(&mut line) {
-            Ok(0) => { println!(); break; } // EOF / Ctrl-D
-            Ok(_) => {}
-            Err(_) => break,
+        options.paths.clone()
+    };
+    let kind = if bench {
+        TestKind::Bench
+    } else if property_test {
+        TestKind::Property
+    } else {
+        TestKind::Test
+    };
+    let cases = test_runner::discover(&roots, kind, options.filter.as_deref());
+    if cases.is_empty() {
+        match (&options.filter, kind) {
+            (Some(filter), _) => println!("  No tests match '{}'.", filter),
+            (None, TestKind::Bench) => println!("  No #[bench] functions found."),
+            (None, TestKind::Property) => println!("  No #[property] functions found."),
+            (None, TestKind::Test) => println!("  No test files found in tests/ directory."),
         }
+        return Ok(());
+    }
 
-        let trimmed = line.trim_end_matches('\n').trim_end_matches('\r');
-
-        // ── REPL commands (only when not in continuation) ──────────────────
-        if !continuation {
-            match trimmed.trim() {
-                ":quit" | ":q" | ":exit" => {
-                    println!("{}", "Goodbye! 👋".bright_green());
-                    break;
-                }
-                ":help" | ":h" => {
-                    println!("{}", REPL_HELP.bright_cyan());
-                    continue;
-                }
-                ":vars" => {
-                    let vars = interp.repl_variables();
-                    if vars.is_empty() {
-                        println!("{}", "(no variables defined)".bright_black());
-                    } else {
-                        println!("{}", "Variables:".bright_yellow().bold());
-                        for (name, val) in &vars {
-                            // skip StructDef noise
-                            let display = format!("{}", val);
-                            if display.starts_with("<struct-def") { continue; }
-                            println!("  {} = {}", name.bright_cyan(), display.bright_white());
-                        }
-                    }
-                    continue;
-                }
-                ":fns" => {
-                    let fns = interp.repl_functions();
-                    if fns.is_empty() {
-                        println!("{}", "(no functions defined)".bright_black());
-                    } else {
-                        println!("{}", "Functions:".bright_yellow().bold());
-                        for name in &fns {
-                            println!("  {}", name.bright_cyan());
-                        }
-                    }
-                    continue;
-                }
-                ":reset" => {
-                    interp = crate::interpreter::Interpreter::new();
-                    println!("{}", "State reset.".bright_green());
-                    continue;
-                }
-                ":clear" => {
-                    print!("\x1B[2J\x1B[H");
-                    io::stdout().flush().ok();
-                    continue;
-                }
-                cmd if cmd.starts_with(":load ") => {
-                    let path_str = cmd[6..].trim();
-                    let path = PathBuf::from(path_str);
-                    match fs::read_to_string(&path) {
-                        Ok(src) => {
-                            match crate::parser::Parser::new(&src).parse() {
-                                Ok(ast) => match interp.execute(&ast) {
-                                    Ok(_) => println!("{} Loaded {}", "✓".green(), path.display()),
-                                    Err(e) => println!("{} {}", "error:".bright_red(), e),
-                                },
-                                Err(e) => println!("{} {}", "parse error:".bright_red(), e),
-                            }
-                        }
-                        Err(e) => println!("{} {}", "error:".bright_red(), e),
-                    }
-                    continue;
-                }
-                cmd if cmd.starts_with(":type ") => {
-                    let expr = cmd[6..].trim();
-                    match crate::interpreter::Interpreter::new().repl_exec(expr) {
-                        Ok(Some(val)) => {
-                            let type_name = value_type_name(&format!("{:?}", val));
-                            println!("{} : {}", expr.bright_white(), type_name.bright_cyan());
-                        }
-                        Ok(None) => println!("{}", "(no value)".bright_black()),
-                        Err(e) => println!("{} {}", "error:".bright_red(), e),
-                    }
-                    continue;
-                }
-                "" => {
-                    // blank line while not in continuation — skip
-                    continue;
-                }
-                _ => {}
+    let baseline = match &options.baseline {
+        Some(path) => Some(Baseline::load(path)?),
+        None => None,
+    };
+    // Benchmarks run one at a time so they do not disturb each other's timings
+    let jobs = match kind {
+        TestKind::Bench => 1,
+        TestKind::Test | TestKind::Property => options
+            .jobs
+            .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get())),
+    };
+    let started = std::time::Instant::now();
+    let results = test_runner::run_all(&cases, jobs, options, |result| {
+        let status = match &result.outcome {
+            Outcome::Passed => "ok".green().to_string(),
+            Outcome::Failed(_) => "FAIL".red().bold().to_string(),
+            Outcome::Skipped => "SKIP".yellow().to_string(),
+        };
+        match &result.stats {
+            Some(stats) => {
+                let change = baseline
+                    .as_ref()
+                    .and_then(|b| b.benchmarks.get(&result.id()))
+                    .map(|old| format_change(stats.change_from(old)))
+                    .unwrap_or_default();
+                println!(
+                    "  {} ... {} {}/iter (± {})  median {}  {}  {}{}",
+                    result.id(),
+                    status,
+                    bench::format_ns(stats.mean_ns).bright_white().bold(),
+                    bench::format_ns(stats.stddev_ns),
+                    bench::format_ns(stats.median_ns),
+                    bench::format_throughput(stats.throughput()),
+                    format!("[{} iters, {} outliers]", stats.iterations, stats.outliers).bright_black(),
+                    change
+                );
             }
+            None => println!("  {} ... {} {}", result.id(), status, format!("({:.2?})", result.duration).bright_black()),
         }
-
-        // ── accumulate input ────────────────────────────────────────────────
-        if !input_buf.is_empty() {
-            input_buf.push('\n');
-        }
-        input_buf.push_str(trimmed);
-
-        // Count brace balance to detect multi-line blocks
-        let open_braces: i32 = input_buf.chars().filter(|&c| c == '{').count() as i32;
-        let close_braces: i32 = input_buf.chars().filter(|&c| c == '}').count() as i32;
-        let depth = open_braces - close_braces;
-
-        if depth > 0 {
-            // Still inside a block — keep collecting
-            continuation = true;
-            continue;
+    });
+
+    let failures: Vec<_> = results
+        .iter()
+        .filter_map(|r| match &r.outcome {
+            Outcome::Failed(msg) => Some((r.id(), msg)),
+            _ => None,
+        })
+        .collect();
+    if !failures.is_empty() {
+        println!("\n{}", "Failures:".red().bold());
+        for (id, msg) in &failures {
+            println!("\n  {}\n{}", id.bright_white().bold(), msg.trim_end());
         }
+    }
 
-        // Empty buffer after a blank line in continuation? Submit anyway.
-        continuation = false;
-        let source = input_buf.trim().to_string();
-        input_buf.clear();
+    if let Some(path) = &options.junit {
+        fs::write(path, test_runner::to_junit(&results))
+            .map_err(|e| format!("Cannot write '{}': {}", path.display(), e))?;
+    }
+    if let Some(path) = &options.json {
+        let json = match kind {
+            TestKind::Bench => test_runner::to_baseline(&results).to_json(),
+            TestKind::Test | TestKind::Property => test_runner::to_json(&results),
+        };
+        fs::write(path, json).map_err(|e| format!("Cannot write '{}': {}", path.display(), e))?;
+    }
+    if let Some(path) = &options.save_baseline {
+        test_runner::to_baseline(&results).save(path)?;
+        println!("  {} Saved baseline to {}", "✓".green(), path.display());
+    }
 
-        if source.is_empty() { continue; }
+    let (passed, failed, skipped) = test_runner::counts(&results);
+    println!();
+    let summary = format!(
+        "{} passed  {}  {}",
+        passed.to_string().green().bold(),
+        if failed > 0 { format!("{} failed", failed).red().bold().to_string() }
+          else { format!("{} failed", failed).bright_black().to_string() },
+        format!("{} skipped", skipped).bright_black()
+    );
+    println!("Results: {}  {}", summary, format!("({:.2?})", started.elapsed()).bright_black());
 
-        // ── Execute ─────────────────────────────────────────────────────────
-        match interp.repl_exec(&source) {
-            Ok(Some(val)) => {
-                // Show result with type hint
-                let type_nm = value_type_name(&format!("{:?}", val));
-                println!("{} {}", format!("{}", val).bright_white().bold(), format!("// {}", type_nm).bright_black());
-            }
-            Ok(None) => {} // definition / statement / void call
-            Err(e) => {
-                println!("{} {}", "error:".bright_red().bold(), e);
-            }
-        }
+    if failed > 0 {
+        return Err(format!("{} test{} failed", failed, if failed == 1 { "" } else { "s" }));
     }
-
     Ok(())
 }
 
-/// Derive a user-friendly type name from a Debug string of a Value
-fn value_type_name(debug: &str) -> &'static str {
-    if debug.starts_with("Int(") || debug.starts_with("Float(") { "number" }
-    else if debug.starts_with("Str(") { "string" }
-    else if debug.starts_with("Bool(") { "bool" }
-    else if debug.starts_with("Array(") { "array" }
-    else if debug.starts_with("Map(") || debug.starts_with("HashMap(") { "map" }
-    else if debug.starts_with("Closure") || debug.starts_with("Function") { "fn" }
-    else if debug.starts_with("Struct {") || debug.starts_with("StructInstance") { "struct" }
-    else if debug.starts_with("Null") { "null" }
-    else { "value" }
+/// ` +12.3% slower` against a benchmark baseline, or ` no change` within the noise
+fn format_change(percent: f64) -> String {
+    if percent.abs() < bench::NOISE_PERCENT {
+        format!(" {}", format!("{:+.1}% no change", percent).bright_black())
+    } else if percent > 0.0 {
+        format!(" {}", format!("{:+.1}% slower", percent).red().bold())
+    } else {
+        format!(" {}", format!("{:+.1}% faster", percent).green().bold())
+    }
 }
 
 /// Show version information
@@ -661,7 +770,7 @@ pub fn show_help() {
     println!("  knull new my-project && cd my-project && knull run src/main.knull");
     println!();
     println!("{}", "REPL COMMANDS:".bright_yellow().bold());
-    println!("  :help  :vars  :fns  :reset  :load <file>  :type <expr>  :quit");
+    println!("  :help  :vars  :fns  :reset  :load <file>  :save <file>  :type <expr>  :time <code>  :ast <code>  :doc <builtin>  :quit");
     println!();
     println!("{}", "https://github.com/4fqr/knull".bright_black());
 }
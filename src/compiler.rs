@@ -84,7 +84,7 @@ pub fn compile(
     let mut lexer = Lexer::new(source);
     let _tokens = lexer.tokenize();
 
-    let mut parser = Parser::new(source);
+    let mut parser = Parser::new(source).without_spans();
     let ast = parser
         .parse()
         .map_err(|e| format!("Parse error: {:?}", e))?;
@@ -170,7 +170,7 @@ pub fn generate_assembly(
     // Parse
     let mut lexer = Lexer::new(source);
     let _tokens = lexer.tokenize();
-    let mut parser = Parser::new(source);
+    let mut parser = Parser::new(source).without_spans();
     let ast = parser
         .parse()
         .map_err(|e| format!("Parse error: {:?}", e))?;
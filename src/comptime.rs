@@ -180,9 +180,12 @@ fn value_to_embedded(val: &Value) -> EmbeddedValue {
         Value::Closure { params, .. } => EmbeddedValue::String(format!("<closure({})>", params.join(","))),
         Value::StructDef(_) => EmbeddedValue::String("<struct_def>".to_string()),
         Value::StructInstance(_) => EmbeddedValue::String("<struct_instance>".to_string()),
+        Value::EnumDef(_) => EmbeddedValue::String("<enum_def>".to_string()),
+        Value::Enum(e) => EmbeddedValue::String(e.variant.clone()),
         Value::Function(_) => EmbeddedValue::String("<function>".to_string()),
         Value::Trait(_) => EmbeddedValue::String("<trait>".to_string()),
         Value::Reference(_) => EmbeddedValue::String("<reference>".to_string()),
+        Value::Module { name, .. } => EmbeddedValue::String(format!("<module {}>", name)),
         Value::Null => EmbeddedValue::Unit,
         Value::Range { start, end, inclusive } => EmbeddedValue::String(if *inclusive { format!("{}..={}", start, end) } else { format!("{}..{}", start, end) }),
     }
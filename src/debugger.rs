@@ -1,1310 +1,925 @@
 //! Knull Debug Adapter Protocol Server
-//! Implements JSON-RPC based debugging for the Knull language
-
+//!
+//! `knull debug file.knull` speaks DAP over stdin/stdout. The program runs on
+//! the calling thread with a `DebugHook` attached; a reader thread forwards
+//! requests to it, and the hook answers them between statements and while the
+//! program is paused. The program's own stdout is turned into `output` events
+//! so it cannot corrupt the protocol stream.
+
+use crate::interpreter::{DebugHook, EnumPayload, ErrorKind, Interpreter, RuntimeError, Value};
+use crate::parser::{Parser, Span};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::fs::File;
 use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
 use std::sync::{Arc, Mutex};
 use std::thread;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DAPRequest {
-    pub id: Option<i32>,
-    pub method: String,
-    #[serde(default)]
-    pub params: Option<serde_json::Value>,
-}
+/// A Knull program has one thread as far as the client is concerned
+const THREAD_ID: i64 = 1;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DAPResponse {
-    pub id: Option<i32>,
-    #[serde(default)]
-    pub result: Option<serde_json::Value>,
+pub struct DAPRequest {
+    pub seq: i64,
+    pub command: String,
     #[serde(default)]
-    pub error: Option<DAPError>,
+    pub arguments: serde_json::Value,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DAPError {
-    pub code: i32,
-    pub message: String,
-    #[serde(default)]
-    pub data: Option<serde_json::Value>,
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    pub supports_configuration_done_request: bool,
+    pub supports_conditional_breakpoints: bool,
+    pub supports_evaluate_for_hovers: bool,
+    pub supports_terminate_request: bool,
+    pub exception_breakpoint_filters: Vec<ExceptionBreakpointsFilter>,
 }
 
-impl DAPError {
-    pub fn new(code: i32, message: &str) -> Self {
-        DAPError {
-            code,
-            message: message.to_string(),
-            data: None,
+impl Default for Capabilities {
+    fn default() -> Self {
+        Capabilities {
+            supports_configuration_done_request: true,
+            supports_conditional_breakpoints: true,
+            supports_evaluate_for_hovers: true,
+            supports_terminate_request: true,
+            exception_breakpoint_filters: vec![
+                ExceptionBreakpointsFilter { filter: "raised", label: "All Errors", default: false },
+                ExceptionBreakpointsFilter { filter: "uncaught", label: "Uncaught Errors", default: true },
+            ],
         }
     }
+}
 
-    pub fn with_data(code: i32, message: &str, data: serde_json::Value) -> Self {
-        DAPError {
-            code,
-            message: message.to_string(),
-            data: Some(data),
-        }
-    }
+#[derive(Debug, Clone, Serialize)]
+pub struct ExceptionBreakpointsFilter {
+    pub filter: &'static str,
+    pub label: &'static str,
+    pub default: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DAPEvent {
-    pub event: String,
-    #[serde(default)]
-    pub body: Option<serde_json::Value>,
+pub struct Source {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum DebugState {
-    #[serde(rename = "stopped")]
-    Stopped,
-    #[serde(rename = "running")]
-    Running,
-    #[serde(rename = "paused")]
-    Paused,
-    #[serde(rename = "exited")]
-    Exited,
+impl Source {
+    fn from_file(file: &str) -> Self {
+        Source {
+            name: Path::new(file).file_name().map(|n| n.to_string_lossy().into_owned()),
+            path: Some(file.to_string()),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct SourceBreakpoint {
-    pub line: u32,
-    #[serde(default)]
-    pub column: Option<u32>,
+    pub line: usize,
     #[serde(default)]
     pub condition: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Breakpoint {
-    pub id: Option<i32>,
+    pub id: i64,
     pub verified: bool,
-    pub line: u32,
-    #[serde(default)]
-    pub column: Option<u32>,
-    #[serde(default)]
-    pub source: Option<Source>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Source {
-    pub name: Option<String>,
-    pub path: Option<String>,
-    #[serde(default)]
-    pub source_reference: Option<i32>,
+    pub line: usize,
+    pub source: Source,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct StackFrame {
-    pub id: i32,
+    pub id: usize,
     pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub source: Option<Source>,
-    pub line: u32,
-    pub column: u32,
-    #[serde(default)]
-    pub end_line: Option<u32>,
-    #[serde(default)]
-    pub end_column: Option<u32>,
+    pub line: usize,
+    pub column: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<usize>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Scope {
     pub name: String,
-    pub variables_reference: i32,
-    #[serde(default)]
+    pub variables_reference: usize,
     pub expensive: bool,
-    #[serde(default)]
-    pub source: Option<Source>,
-    #[serde(default)]
-    pub line: Option<u32>,
-    #[serde(default)]
-    pub column: Option<u32>,
-    #[serde(default)]
-    pub end_line: Option<u32>,
-    #[serde(default)]
-    pub end_column: Option<u32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Variable {
     pub name: String,
     pub value: String,
     #[serde(rename = "type")]
-    #[serde(default)]
-    pub type_: Option<String>,
-    pub variables_reference: Option<i32>,
-    #[serde(default)]
-    pub indexed_variables: Option<i32>,
+    pub type_: String,
+    /// Non-zero when the value has children the client can expand
+    pub variables_reference: usize,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Thread {
-    pub id: i32,
-    pub name: String,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct StoppedEventBody {
     pub reason: String,
-    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    #[serde(default)]
-    pub thread_id: Option<i32>,
-    #[serde(default)]
-    pub all_threads_stopped: Option<bool>,
-    #[serde(default)]
-    pub hit_breakpoint_ids: Option<Vec<i32>>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ContinuedEventBody {
-    pub thread_id: i32,
-    #[serde(default)]
-    pub all_threads_continued: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    pub thread_id: i64,
+    pub all_threads_stopped: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub hit_breakpoint_ids: Vec<i64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ExitedEventBody {
-    pub exit_code: i32,
-    #[serde(default)]
-    pub description: Option<String>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TerminatedEventBody {
-    #[serde(default)]
-    pub restart: Option<String>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct OutputEventBody {
+    pub category: String,
     pub output: String,
-    #[serde(rename = "type")]
-    #[serde(default)]
-    pub output_type: Option<String>,
-    #[serde(default)]
-    pub group: Option<String>,
-    #[serde(default)]
-    pub variables_reference: Option<i32>,
-    #[serde(default)]
-    pub source: Option<Source>,
-    #[serde(default)]
-    pub line: Option<u32>,
-    #[serde(default)]
-    pub column: Option<u32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BreakpointEventBody {
-    pub reason: String,
-    pub breakpoint: Breakpoint,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct InitializeRequest {
-    #[serde(default)]
-    pub client_id: Option<String>,
-    #[serde(default)]
-    pub client_name: Option<String>,
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LaunchArguments {
     #[serde(default)]
-    pub adapter_id: Option<String>,
+    program: Option<String>,
     #[serde(default)]
-    pub locale: Option<String>,
-    #[serde(default)]
-    pub lines_start_at_1: Option<bool>,
-    #[serde(default)]
-    pub columns_start_at_1: Option<bool>,
-    #[serde(default)]
-    pub path_format: Option<String>,
-    #[serde(default)]
-    pub supports_variable_type: Option<bool>,
-    #[serde(default)]
-    pub supports_variable_paging: Option<bool>,
-    #[serde(default)]
-    pub supports_run_in_terminal_request: Option<bool>,
-    #[serde(default)]
-    pub supports_memory_references: Option<bool>,
-    #[serde(default)]
-    pub supports_progress_reporting: Option<bool>,
-    #[serde(default)]
-    pub supports_invalidated_event: Option<bool>,
-    #[serde(default)]
-    pub supports_args_can_be_interpreted_by_shell: Option<bool>,
-    #[serde(default)]
-    pub supports_start_debugging_request: Option<bool>,
+    stop_on_entry: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct InitializeResponse {
-    pub adapter_id: String,
-    pub locale: String,
-    pub lines_start_at_1: bool,
-    pub columns_start_at_1: bool,
-    pub path_format: String,
-    pub supports_variable_type: bool,
-    pub supports_variable_paging: bool,
-    pub supports_run_in_terminal_request: bool,
-    pub supports_memory_references: bool,
-    pub supports_progress_reporting: bool,
-    pub supports_invalidated_event: bool,
-    pub supports_args_can_be_interpreted_by_shell: bool,
-    pub supports_start_debugging_request: bool,
+#[derive(Debug, Deserialize)]
+struct SetBreakpointsArguments {
+    source: Source,
     #[serde(default)]
-    pub supports_stepping: Option<bool>,
-    pub supports_clipboard_context: bool,
-    pub supports_modules_request: bool,
-    pub supports_execute_command: bool,
-    pub supports_configuration_done_request: bool,
-    pub supports_function_breakpoints: bool,
-    pub supports_conditional_breakpoints: bool,
-    pub supports_hit_conditional_breakpoints: bool,
-    pub supports_set_variable: bool,
-    pub supports_restart_frame: bool,
-    pub supports_goto_targets_request: bool,
-    pub supports_step_in_targets_request: bool,
-    pub supports_completions_request: bool,
-    pub supports_delayed_stack_trace_loading: bool,
-    pub supports_loaded_sources_request: bool,
-    pub supports_log_points: bool,
-    pub supports_terminate_threads_request: bool,
-    pub supports_terminate_debuggee: bool,
-    pub supports_data_breakpoints: bool,
-    pub supports_read_memory_request: bool,
-    pub supports_write_memory_request: bool,
-    pub supports_disassemble_request: bool,
-    #[serde(default)]
-    pub additional_columns: Option<Vec<String>>,
-    #[serde(default)]
-    pub dependency_graphs: Option<Vec<serde_json::Value>>,
-}
-
-impl Default for InitializeResponse {
-    fn default() -> Self {
-        InitializeResponse {
-            adapter_id: "knull".to_string(),
-            locale: "en".to_string(),
-            lines_start_at_1: true,
-            columns_start_at_1: true,
-            path_format: "path".to_string(),
-            supports_variable_type: true,
-            supports_variable_paging: false,
-            supports_run_in_terminal_request: false,
-            supports_memory_references: false,
-            supports_progress_reporting: false,
-            supports_invalidated_event: false,
-            supports_args_can_be_interpreted_by_shell: false,
-            supports_start_debugging_request: false,
-            supports_stepping: Some(true),
-            supports_clipboard_context: false,
-            supports_modules_request: false,
-            supports_execute_command: false,
-            supports_configuration_done_request: true,
-            supports_function_breakpoints: false,
-            supports_conditional_breakpoints: true,
-            supports_hit_conditional_breakpoints: false,
-            supports_set_variable: false,
-            supports_restart_frame: false,
-            supports_goto_targets_request: false,
-            supports_step_in_targets_request: false,
-            supports_completions_request: false,
-            supports_delayed_stack_trace_loading: false,
-            supports_loaded_sources_request: false,
-            supports_log_points: false,
-            supports_terminate_threads_request: false,
-            supports_terminate_debuggee: false,
-            supports_data_breakpoints: false,
-            supports_read_memory_request: false,
-            supports_write_memory_request: false,
-            supports_disassemble_request: false,
-            additional_columns: None,
-            dependency_graphs: None,
-        }
-    }
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SetBreakpointsRequest {
-    pub source: Source,
-    #[serde(default)]
-    pub breakpoints: Option<Vec<SourceBreakpoint>>,
-    #[serde(default)]
-    pub source_modified: Option<bool>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SetBreakpointsResponse {
-    pub breakpoints: Vec<Breakpoint>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SetExceptionBreakpointsRequest {
-    #[serde(default)]
-    pub filters: Option<Vec<String>>,
-    #[serde(default)]
-    pub filter_options: Option<Vec<ExceptionBreakpointFilter>>,
-    #[serde(default)]
-    pub exception_options: Option<Vec<serde_json::Value>>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ExceptionBreakpointFilter {
-    pub filter: String,
-    pub label: String,
-    #[serde(default)]
-    pub description: Option<String>,
-    #[serde(default)]
-    pub default: Option<bool>,
-    #[serde(default)]
-    pub supports_conditions: Option<bool>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SetExceptionBreakpointsResponse {
-    #[serde(default)]
-    pub breakpoints: Option<Vec<Breakpoint>>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LaunchRequest {
-    #[serde(default)]
-    pub program: Option<String>,
-    #[serde(default)]
-    pub args: Option<Vec<String>>,
-    #[serde(default)]
-    pub cwd: Option<String>,
-    #[serde(default)]
-    pub env: Option<HashMap<String, String>>,
-    #[serde(default)]
-    pub name: Option<String>,
-    #[serde(default)]
-    pub debuggee: Option<serde_json::Value>,
-    #[serde(default)]
-    pub internal_console_options: Option<serde_json::Value>,
-    #[serde(default)]
-    pub runtime: Option<String>,
-    #[serde(default)]
-    pub runtime_version: Option<String>,
-    #[serde(default)]
-    pub console_name: Option<String>,
-    #[serde(default)]
-    pub suppress_refresh: Option<bool>,
-    #[serde(default)]
-    pub enable_automatic_environment_configuration: Option<bool>,
+    breakpoints: Vec<SourceBreakpoint>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ConfigurationDoneRequest {}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ThreadsResponse {
-    pub threads: Vec<Thread>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct StackTraceRequest {
-    pub thread_id: i32,
-    #[serde(default)]
-    pub start_frame: Option<i32>,
+#[derive(Debug, Deserialize)]
+struct SetExceptionBreakpointsArguments {
     #[serde(default)]
-    pub levels: Option<i32>,
-    #[serde(default)]
-    pub format: Option<StackTraceFormat>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct StackTraceFormat {
-    #[serde(default)]
-    pub thread_id: Option<bool>,
-    #[serde(default)]
-    pub module: Option<bool>,
-    #[serde(default)]
-    pub group: Option<bool>,
-    #[serde(default)]
-    pub inline_frames: Option<bool>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct StackTraceResponse {
-    pub stack_frames: Vec<StackFrame>,
-    #[serde(default)]
-    pub total_frames: Option<i32>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ScopesRequest {
-    pub frame_id: i32,
+    filters: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ScopesResponse {
-    pub scopes: Vec<Scope>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct VariablesRequest {
-    pub variables_reference: i32,
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StackTraceArguments {
     #[serde(default)]
-    pub filter: Option<String>,
+    start_frame: usize,
     #[serde(default)]
-    pub start: Option<i32>,
-    #[serde(default)]
-    pub count: Option<i32>,
-    #[serde(default)]
-    pub format: Option<serde_json::Value>,
+    levels: usize,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct VariablesResponse {
-    pub variables: Vec<Variable>,
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ScopesArguments {
+    frame_id: usize,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ContinueRequest {
-    pub thread_id: i32,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ContinueResponse {
-    pub all_threads_continued: bool,
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VariablesArguments {
+    variables_reference: usize,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct NextRequest {
-    pub thread_id: i32,
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EvaluateArguments {
+    expression: String,
     #[serde(default)]
-    pub granularity: Option<String>,
+    frame_id: Option<usize>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct StepInRequest {
-    pub thread_id: i32,
-    #[serde(default)]
-    pub target_id: Option<i32>,
-    #[serde(default)]
-    pub granularity: Option<String>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct StepOutRequest {
-    pub thread_id: i32,
-    #[serde(default)]
-    pub granularity: Option<String>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PauseRequest {
-    pub thread_id: i32,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SourceRequest {
-    pub source: Source,
-    #[serde(default)]
-    pub source_reference: Option<i32>,
+fn arguments<T: serde::de::DeserializeOwned>(request: &DAPRequest) -> Result<T, String> {
+    serde_json::from_value(request.arguments.clone())
+        .map_err(|e| format!("Invalid {} arguments: {}", request.command, e))
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LoadedSourcesRequest {}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct EvaluateRequest {
-    pub expression: String,
-    #[serde(default)]
-    pub frame_id: Option<i32>,
-    #[serde(default)]
-    pub context: Option<String>,
-    #[serde(default)]
-    pub format: Option<serde_json::Value>,
+/// Read one `Content-Length` framed request; `None` at the end of input
+pub fn read_message<R: BufRead>(reader: &mut R) -> Option<DAPRequest> {
+    loop {
+        let mut length = None;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).ok()? == 0 {
+                return None;
+            }
+            let line = line.trim();
+            if line.is_empty() {
+                if length.is_some() {
+                    break;
+                }
+            } else if let Some((name, value)) = line.split_once(':') {
+                if name.trim().eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse::<usize>().ok();
+                }
+            }
+        }
+        let mut body = vec![0; length?];
+        reader.read_exact(&mut body).ok()?;
+        // A malformed message is dropped rather than ending the session
+        if let Ok(request) = serde_json::from_slice(&body) {
+            return Some(request);
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct EvaluateResponse {
-    pub result: String,
-    #[serde(rename = "type")]
-    #[serde(default)]
-    pub type_: Option<String>,
-    pub variables_reference: Option<i32>,
-    #[serde(default)]
-    pub indexed_variables: Option<i32>,
-    #[serde(default)]
-    pub memory_reference: Option<String>,
+/// The client's end of the session, shared with the thread forwarding program output
+struct Outgoing {
+    writer: Box<dyn Write + Send>,
+    seq: i64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SetVariableRequest {
-    pub name: String,
-    pub value: String,
-    pub variables_reference: i32,
-    #[serde(default)]
-    pub format: Option<serde_json::Value>,
+impl Outgoing {
+    fn send(&mut self, mut message: serde_json::Value) {
+        self.seq += 1;
+        message["seq"] = json!(self.seq);
+        let body = message.to_string();
+        let frame = format!("Content-Length: {}\r\n\r\n{}", body.len(), body);
+        let _ = self.writer.write_all(frame.as_bytes());
+        let _ = self.writer.flush();
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SetVariableResponse {
-    pub value: String,
-    #[serde(rename = "type")]
-    #[serde(default)]
-    pub type_: Option<String>,
-    pub variables_reference: Option<i32>,
-    #[serde(default)]
-    pub indexed_variables: Option<i32>,
-}
+type Client = Arc<Mutex<Outgoing>>;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DisconnectRequest {
-    #[serde(default)]
-    pub terminate_debuggee: Option<bool>,
-    #[serde(default)]
-    pub restart: Option<bool>,
+fn send_event(client: &Client, event: &str, body: impl Serialize) {
+    client.lock().unwrap().send(json!({ "type": "event", "event": event, "body": body }));
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RestartRequest {
-    #[serde(default)]
-    pub arguments: Option<serde_json::Value>,
+/// What the program does once a request is answered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Flow {
+    Stay,
+    Resume,
+    End,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TerminateRequest {
-    #[serde(default)]
-    pub restart: Option<bool>,
+/// A step in progress, with the frame depth it started at
+#[derive(Debug, Clone, Copy)]
+enum Step {
+    In,
+    Over(usize),
+    Out(usize),
 }
 
-pub struct DebuggerState {
-    pub breakpoints: HashMap<String, Vec<SourceBreakpoint>>,
-    pub exception_breakpoints: Vec<String>,
-    pub state: DebugState,
-    pub current_thread_id: i32,
-    pub current_frame_id: i32,
-    pub program: Option<String>,
-    pub args: Vec<String>,
-    pub cwd: Option<String>,
-    pub env: HashMap<String, String>,
-    pub line: u32,
-    pub column: u32,
-    pub call_stack: Vec<StackFrame>,
-    pub variables: HashMap<i32, Vec<Variable>>,
-    pub next_var_ref: i32,
-    pub stopped_reason: Option<String>,
-    pub exit_code: Option<i32>,
-    pub initialized: bool,
-    pub configuration_done: bool,
-    pub line_breaks: Vec<u32>,
-    pub source_name: String,
+impl Step {
+    fn stops_at(self, depth: usize) -> bool {
+        match self {
+            Step::In => true,
+            Step::Over(from) => depth <= from,
+            Step::Out(from) => depth < from,
+        }
+    }
 }
 
-impl Default for DebuggerState {
-    fn default() -> Self {
-        DebuggerState {
+/// Something behind a `variablesReference`, valid until the program resumes
+enum Handle {
+    Locals(usize),
+    Globals,
+    Value(Value),
+}
+
+struct Session {
+    requests: Receiver<DAPRequest>,
+    client: Client,
+    program: PathBuf,
+    // Line breakpoints by canonical file path, each with the id reported for it
+    breakpoints: HashMap<String, Vec<(i64, SourceBreakpoint)>>,
+    breakpoint_ids: i64,
+    exception_filters: Vec<String>,
+    stop_on_entry: bool,
+    step: Option<Step>,
+    pause_requested: bool,
+    // Statement of the last stop, so the statements nested in it on the same line don't stop again
+    last_stop: Option<Span>,
+    handles: Vec<Handle>,
+    // The client disconnected or asked to terminate
+    ended: bool,
+}
+
+impl Session {
+    fn new(program: &Path, requests: Receiver<DAPRequest>, client: Client) -> Self {
+        Session {
+            requests,
+            client,
+            program: program.to_path_buf(),
             breakpoints: HashMap::new(),
-            exception_breakpoints: Vec::new(),
-            state: DebugState::Stopped,
-            current_thread_id: 1,
-            current_frame_id: 0,
-            program: None,
-            args: Vec::new(),
-            cwd: None,
-            env: HashMap::new(),
-            line: 1,
-            column: 1,
-            call_stack: Vec::new(),
-            variables: HashMap::new(),
-            next_var_ref: 1,
-            stopped_reason: None,
-            exit_code: None,
-            initialized: false,
-            configuration_done: false,
-            line_breaks: Vec::new(),
-            source_name: String::from("<unknown>"),
+            breakpoint_ids: 0,
+            exception_filters: vec!["uncaught".to_string()],
+            stop_on_entry: false,
+            step: None,
+            pause_requested: false,
+            last_stop: None,
+            handles: Vec::new(),
+            ended: false,
         }
     }
-}
-
-pub type DAPHandler = Arc<Mutex<DebuggerState>>;
-
-pub fn create_debugger_state() -> DAPHandler {
-    Arc::new(Mutex::new(DebuggerState::default()))
-}
-
-pub fn send_response<W: Write>(writer: &mut W, id: Option<i32>, result: serde_json::Value) {
-    let response = DAPResponse {
-        id,
-        result: Some(result),
-        error: None,
-    };
-    send_message(writer, response);
-}
-
-pub fn send_error_response<W: Write>(writer: &mut W, id: Option<i32>, code: i32, message: &str) {
-    let response = DAPResponse {
-        id,
-        result: None,
-        error: Some(DAPError::new(code, message)),
-    };
-    send_message(writer, response);
-}
 
-pub fn send_event<W: Write>(writer: &mut W, event: &str, body: Option<serde_json::Value>) {
-    let dap_event = DAPEvent {
-        event: event.to_string(),
-        body,
-    };
-    let json = serde_json::to_string(&dap_event).unwrap();
-    let header = format!("Content-Length: {}\r\n\r\n", json.len());
-    let _ = writer.write_all(header.as_bytes());
-    let _ = writer.write_all(json.as_bytes());
-    let _ = writer.flush();
-}
-
-fn send_message<W: Write, T: Serialize>(writer: &mut W, message: T) {
-    let json = serde_json::to_string(&message).unwrap();
-    let header = format!("Content-Length: {}\r\n\r\n", json.len());
-    let _ = writer.write_all(header.as_bytes());
-    let _ = writer.write_all(json.as_bytes());
-    let _ = writer.flush();
-}
-
-pub fn read_message<R: Read>(reader: &mut R) -> Option<DAPRequest> {
-    let mut reader = BufReader::new(reader);
-    let mut header = String::new();
-
-    loop {
-        header.clear();
-        match reader.read_line(&mut header) {
-            Ok(0) => return None,
-            Ok(_) => {
-                let trimmed = header.trim();
-                if trimmed.is_empty() {
-                    break;
-                }
+    /// Answer requests until the client finishes configuring; false if it left first
+    fn configure(&mut self) -> bool {
+        while let Ok(request) = self.requests.recv() {
+            match self.handle(&request, None) {
+                Flow::Resume if request.command == "configurationDone" => return true,
+                Flow::End => return false,
+                _ => {}
             }
-            Err(_) => return None,
         }
+        false
+    }
+
+    fn respond(&self, request: &DAPRequest, result: Result<serde_json::Value, String>) {
+        let mut response = json!({
+            "type": "response",
+            "request_seq": request.seq,
+            "command": request.command,
+            "success": result.is_ok(),
+        });
+        match result {
+            Ok(body) => response["body"] = body,
+            Err(message) => response["message"] = json!(message),
+        }
+        self.client.lock().unwrap().send(response);
     }
 
-    let mut content_length: usize = 0;
-    for line in header.lines() {
-        if line.to_lowercase().starts_with("content-length:") {
-            if let Some(len) = line.split(':').nth(1) {
-                content_length = len.trim().parse().unwrap_or(0);
+    /// Answer one request; `interp` is the running program, if it has started
+    fn handle(&mut self, request: &DAPRequest, mut interp: Option<&mut Interpreter>) -> Flow {
+        let depth = interp.as_ref().map_or(0, |i| i.debug_frames().len());
+        let mut flow = Flow::Stay;
+        let result = match request.command.as_str() {
+            "initialize" => Ok(json!(Capabilities::default())),
+            "launch" | "attach" => self.launch(request),
+            "setBreakpoints" => self.set_breakpoints(request),
+            "setExceptionBreakpoints" => arguments::<SetExceptionBreakpointsArguments>(request).map(|args| {
+                self.exception_filters = args.filters;
+                json!({})
+            }),
+            "configurationDone" => {
+                flow = Flow::Resume;
+                Ok(json!({}))
             }
+            "threads" => Ok(json!({ "threads": [{ "id": THREAD_ID, "name": "main" }] })),
+            "stackTrace" => arguments(request).map(|args| stack_trace(interp.as_deref(), &args)),
+            "scopes" => arguments(request).map(|args| self.scopes(&args)),
+            "variables" => match interp.as_deref() {
+                Some(interp) => arguments(request).map(|args| self.variables(interp, &args)),
+                None => Ok(json!({ "variables": [] })),
+            },
+            "evaluate" => match interp.as_mut() {
+                Some(interp) => arguments(request).and_then(|args| self.evaluate(interp, &args)),
+                None => Err("The program is not running".to_string()),
+            },
+            "continue" => {
+                flow = Flow::Resume;
+                Ok(json!({ "allThreadsContinued": true }))
+            }
+            "next" | "stepIn" | "stepOut" => {
+                self.step = Some(match request.command.as_str() {
+                    "next" => Step::Over(depth),
+                    "stepIn" => Step::In,
+                    _ => Step::Out(depth),
+                });
+                flow = Flow::Resume;
+                Ok(json!({}))
+            }
+            "pause" => {
+                self.pause_requested = true;
+                Ok(json!({}))
+            }
+            "disconnect" | "terminate" => {
+                flow = Flow::End;
+                Ok(json!({}))
+            }
+            other => Err(format!("Unsupported request: {}", other)),
+        };
+        self.respond(request, result);
+        if request.command == "initialize" {
+            send_event(&self.client, "initialized", json!({}));
         }
+        flow
     }
 
-    if content_length == 0 {
-        return None;
+    fn launch(&mut self, request: &DAPRequest) -> Result<serde_json::Value, String> {
+        let args: LaunchArguments = arguments(request)?;
+        if let Some(program) = args.program {
+            self.program = PathBuf::from(program);
+        }
+        self.stop_on_entry = args.stop_on_entry;
+        Ok(json!({}))
     }
 
-    let mut body = vec![0u8; content_length];
-    match reader.read_exact(&mut body) {
-        Ok(_) => {}
-        Err(_) => return None,
+    fn set_breakpoints(&mut self, request: &DAPRequest) -> Result<serde_json::Value, String> {
+        let args: SetBreakpointsArguments = arguments(request)?;
+        let path = args.source.path.clone().ok_or("setBreakpoints needs a source path")?;
+        let mut reported = Vec::new();
+        let mut lines = Vec::new();
+        for breakpoint in args.breakpoints {
+            self.breakpoint_ids += 1;
+            reported.push(Breakpoint {
+                id: self.breakpoint_ids,
+                verified: true,
+                line: breakpoint.line,
+                source: args.source.clone(),
+            });
+            lines.push((self.breakpoint_ids, breakpoint));
+        }
+        self.breakpoints.insert(source_key(Path::new(&path)), lines);
+        Ok(json!({ "breakpoints": reported }))
     }
 
-    let json_str = String::from_utf8_lossy(&body);
-    match serde_json::from_str(&json_str) {
-        Ok(request) => Some(request),
-        Err(_) => None,
+    fn scopes(&mut self, args: &ScopesArguments) -> serde_json::Value {
+        let locals = self.handle_for(Handle::Locals(args.frame_id));
+        let globals = self.handle_for(Handle::Globals);
+        json!({ "scopes": [
+            Scope { name: "Locals".to_string(), variables_reference: locals, expensive: false },
+            Scope { name: "Globals".to_string(), variables_reference: globals, expensive: false },
+        ] })
     }
-}
 
-pub fn handle_initialize(
-    state: &mut DebuggerState,
-    _params: Option<serde_json::Value>,
-) -> serde_json::Value {
-    state.initialized = true;
-    let response = InitializeResponse::default();
-    json!({
-        "capabilities": response
-    })
-}
+    fn variables(&mut self, interp: &Interpreter, args: &VariablesArguments) -> serde_json::Value {
+        let vars = match args.variables_reference.checked_sub(1).and_then(|i| self.handles.get(i)) {
+            Some(Handle::Locals(frame)) => interp.frame_locals(*frame),
+            Some(Handle::Globals) => interp
+                .repl_variables()
+                .into_iter()
+                .filter(|(_, v)| !matches!(v, Value::StructDef(_) | Value::EnumDef(_) | Value::Trait(_)))
+                .collect(),
+            Some(Handle::Value(value)) => children(value),
+            None => Vec::new(),
+        };
+        let variables: Vec<Variable> = vars.into_iter().map(|(name, value)| self.variable(name, value)).collect();
+        json!({ "variables": variables })
+    }
 
-pub fn handle_launch(
-    state: &mut DebuggerState,
-    params: Option<serde_json::Value>,
-) -> Result<serde_json::Value, String> {
-    if let Some(params) = params {
-        if let Ok(launch) = serde_json::from_value::<LaunchRequest>(params.clone()) {
-            state.program = launch.program;
-            state.args = launch.args.unwrap_or_default();
-            state.cwd = launch.cwd;
-            state.env = launch.env.unwrap_or_default();
-            state.state = DebugState::Stopped;
-
-            if let Some(name) = launch.name {
-                state.source_name = name;
-            }
+    fn evaluate(&mut self, interp: &mut Interpreter, args: &EvaluateArguments) -> Result<serde_json::Value, String> {
+        let frame = args.frame_id.unwrap_or_else(|| interp.debug_frames().len().saturating_sub(1));
+        let value = interp.evaluate_in_frame(frame, &args.expression).map_err(|e| e.message.clone())?;
+        let Variable { value, type_, variables_reference, .. } = self.variable(String::new(), value);
+        Ok(json!({ "result": value, "type": type_, "variablesReference": variables_reference }))
+    }
 
-            return Ok(serde_json::Value::Null);
-        }
+    fn variable(&mut self, name: String, value: Value) -> Variable {
+        let variables_reference = if children(&value).is_empty() { 0 } else { self.handle_for(Handle::Value(value.clone())) };
+        Variable { name, value: display(&value), type_: value.type_name(), variables_reference }
     }
-    Err("Invalid launch parameters".to_string())
-}
 
-pub fn handle_configuration_done(state: &mut DebuggerState) -> serde_json::Value {
-    state.configuration_done = true;
-    state.state = DebugState::Running;
-    serde_json::Value::Null
-}
+    fn handle_for(&mut self, handle: Handle) -> usize {
+        self.handles.push(handle);
+        self.handles.len()
+    }
 
-pub fn handle_set_breakpoints(
-    state: &mut DebuggerState,
-    params: Option<serde_json::Value>,
-) -> Result<serde_json::Value, String> {
-    if let Some(params) = params {
-        if let Ok(set_bp) = serde_json::from_value::<SetBreakpointsRequest>(params) {
-            let source_path = set_bp
-                .source
-                .path
-                .clone()
-                .unwrap_or_else(|| set_bp.source.name.clone().unwrap_or_default());
-
-            let breakpoints = set_bp.breakpoints.unwrap_or_default();
-            let mut bp_results: Vec<Breakpoint> = Vec::new();
-            let mut line_breaks: Vec<u32> = Vec::new();
-
-            for bp in &breakpoints {
-                line_breaks.push(bp.line);
-                bp_results.push(Breakpoint {
-                    id: Some(bp.line as i32),
-                    verified: true,
-                    line: bp.line,
-                    column: bp.column,
-                    source: Some(set_bp.source.clone()),
-                });
-            }
+    /// The ids of the breakpoints on the line `span` starts, whose conditions hold
+    fn breakpoints_hit(&self, interp: &mut Interpreter, span: &Span) -> Vec<i64> {
+        let Some(breakpoints) = span.file.as_ref().and_then(|file| self.breakpoints.get(file.as_ref())) else {
+            return Vec::new();
+        };
+        let frame = interp.debug_frames().len().saturating_sub(1);
+        breakpoints
+            .iter()
+            .filter(|(_, bp)| bp.line == span.line)
+            .filter(|(_, bp)| match &bp.condition {
+                // A condition that fails to evaluate stops, so the mistake is seen
+                Some(condition) => interp.evaluate_in_frame(frame, condition).map_or(true, |v| v.is_truthy()),
+                None => true,
+            })
+            .map(|(id, _)| *id)
+            .collect()
+    }
 
-            state.breakpoints.insert(source_path.clone(), breakpoints);
-            state.line_breaks = line_breaks;
+    /// Whether `span` is inside the statement of the last stop and starts on its line
+    fn repeats_stop(&self, span: &Span) -> bool {
+        self.last_stop.as_ref().is_some_and(|last| {
+            last != span
+                && last.file == span.file
+                && last.line == span.line
+                && (last.end_line, last.end_col) >= (span.end_line, span.end_col)
+                && last.col <= span.col
+        })
+    }
 
-            return Ok(json!({
-                "breakpoints": bp_results
-            }));
+    fn statement(&mut self, interp: &mut Interpreter, span: &Span) -> Result<(), RuntimeError> {
+        // Requests that came in while the program ran: breakpoint changes, pause
+        loop {
+            match self.requests.try_recv() {
+                Ok(request) => {
+                    if self.handle(&request, Some(&mut *interp)) == Flow::End {
+                        return Err(self.end());
+                    }
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return Err(self.end()),
+            }
+        }
+        let depth = interp.debug_frames().len();
+        if std::mem::take(&mut self.pause_requested) {
+            return self.stop(interp, span, "pause", None, Vec::new());
+        }
+        if std::mem::take(&mut self.stop_on_entry) {
+            return self.stop(interp, span, "entry", None, Vec::new());
+        }
+        if self.repeats_stop(span) {
+            return Ok(());
+        }
+        if self.step.is_some_and(|step| step.stops_at(depth)) {
+            return self.stop(interp, span, "step", None, Vec::new());
+        }
+        let hit = self.breakpoints_hit(interp, span);
+        if !hit.is_empty() {
+            return self.stop(interp, span, "breakpoint", None, hit);
         }
+        Ok(())
     }
-    Err("Invalid setBreakpoints parameters".to_string())
-}
 
-pub fn handle_set_exception_breakpoints(
-    state: &mut DebuggerState,
-    params: Option<serde_json::Value>,
-) -> Result<serde_json::Value, String> {
-    if let Some(params) = params {
-        if let Ok(set_ex) = serde_json::from_value::<SetExceptionBreakpointsRequest>(params) {
-            state.exception_breakpoints = set_ex.filters.unwrap_or_default();
-            return Ok(serde_json::Value::Null);
+    fn exception(&mut self, interp: &mut Interpreter, error: &RuntimeError, caught: bool) -> Result<(), RuntimeError> {
+        let wanted = |filter: &str| self.exception_filters.iter().any(|f| f == filter);
+        if !(wanted("raised") || (!caught && wanted("uncaught"))) {
+            return Ok(());
         }
+        let span = match (&error.span, interp.debug_frames().last()) {
+            (Some(span), _) => span.clone(),
+            (None, Some(frame)) => frame.span.clone().unwrap_or_default(),
+            (None, None) => Span::default(),
+        };
+        let description = format!("{}: {}", error.kind.name(), error.message);
+        self.stop(interp, &span, "exception", Some(description), Vec::new())
     }
-    Err("Invalid setExceptionBreakpoints parameters".to_string())
-}
 
-pub fn handle_threads(_state: &mut DebuggerState) -> serde_json::Value {
-    json!({
-        "threads": [
-            {
-                "id": 1,
-                "name": "main"
-            }
-        ]
-    })
-}
-
-pub fn handle_stack_trace(
-    state: &mut DebuggerState,
-    params: Option<serde_json::Value>,
-) -> Result<serde_json::Value, String> {
-    let start_frame = params
-        .as_ref()
-        .and_then(|p| p.get("startFrame"))
-        .and_then(|v| v.as_i64())
-        .unwrap_or(0) as usize;
-
-    let levels = params
-        .as_ref()
-        .and_then(|p| p.get("levels"))
-        .and_then(|v| v.as_i64())
-        .unwrap_or(100) as usize;
-
-    let frames: Vec<StackFrame> = state
-        .call_stack
-        .iter()
-        .skip(start_frame)
-        .take(levels)
-        .cloned()
-        .collect();
-
-    let total = state.call_stack.len() as i32;
-
-    Ok(json!({
-        "stackFrames": frames,
-        "totalFrames": total
-    }))
-}
-
-pub fn handle_scopes(
-    state: &mut DebuggerState,
-    params: Option<serde_json::Value>,
-) -> Result<serde_json::Value, String> {
-    let frame_id = params
-        .as_ref()
-        .and_then(|p| p.get("frameId"))
-        .and_then(|v| v.as_i64())
-        .unwrap_or(0) as i32;
-
-    let scopes = vec![
-        Scope {
-            name: "Local".to_string(),
-            variables_reference: frame_id,
-            expensive: false,
-            source: None,
-            line: Some(state.line),
-            column: Some(state.column),
-            end_line: None,
-            end_column: None,
-        },
-        Scope {
-            name: "Global".to_string(),
-            variables_reference: -1,
-            expensive: false,
-            source: None,
-            line: None,
-            column: None,
-            end_line: None,
-            end_column: None,
-        },
-    ];
-
-    Ok(json!({
-        "scopes": scopes
-    }))
-}
-
-pub fn handle_variables(
-    state: &mut DebuggerState,
-    params: Option<serde_json::Value>,
-) -> Result<serde_json::Value, String> {
-    let variables_ref = params
-        .as_ref()
-        .and_then(|p| p.get("variablesReference"))
-        .and_then(|v| v.as_i64())
-        .unwrap_or(0) as i32;
-
-    let vars = state
-        .variables
-        .get(&variables_ref)
-        .cloned()
-        .unwrap_or_else(|| {
-            if variables_ref == -1 {
-                vec![Variable {
-                    name: "env".to_string(),
-                    value: "{...}".to_string(),
-                    type_: Some("Object".to_string()),
-                    variables_reference: Some(-2),
-                    indexed_variables: None,
-                }]
-            } else {
-                vec![]
-            }
+    /// Tell the client the program stopped, then answer requests until it resumes
+    fn stop(
+        &mut self,
+        interp: &mut Interpreter,
+        span: &Span,
+        reason: &str,
+        description: Option<String>,
+        hit_breakpoint_ids: Vec<i64>,
+    ) -> Result<(), RuntimeError> {
+        self.step = None;
+        self.last_stop = Some(span.clone());
+        let _ = std::io::stdout().flush();
+        send_event(&self.client, "stopped", StoppedEventBody {
+            reason: reason.to_string(),
+            text: description.clone(),
+            description,
+            thread_id: THREAD_ID,
+            all_threads_stopped: true,
+            hit_breakpoint_ids,
         });
-
-    Ok(json!({
-        "variables": vars
-    }))
-}
-
-pub fn handle_continue(
-    state: &mut DebuggerState,
-    _params: Option<serde_json::Value>,
-) -> Result<serde_json::Value, String> {
-    state.state = DebugState::Running;
-    state.stopped_reason = None;
-    Ok(json!({
-        "allThreadsContinued": true
-    }))
-}
-
-pub fn handle_pause(
-    _state: &mut DebuggerState,
-    _params: Option<serde_json::Value>,
-) -> Result<serde_json::Value, String> {
-    Ok(serde_json::Value::Null)
-}
-
-pub fn handle_next(
-    state: &mut DebuggerState,
-    _params: Option<serde_json::Value>,
-) -> Result<serde_json::Value, String> {
-    state.state = DebugState::Running;
-    state.stopped_reason = None;
-    Ok(serde_json::Value::Null)
-}
-
-pub fn handle_step_in(
-    state: &mut DebuggerState,
-    _params: Option<serde_json::Value>,
-) -> Result<serde_json::Value, String> {
-    state.state = DebugState::Running;
-    state.stopped_reason = None;
-    Ok(serde_json::Value::Null)
-}
-
-pub fn handle_step_out(
-    state: &mut DebuggerState,
-    _params: Option<serde_json::Value>,
-) -> Result<serde_json::Value, String> {
-    state.state = DebugState::Running;
-    state.stopped_reason = None;
-    Ok(serde_json::Value::Null)
-}
-
-pub fn handle_evaluate(
-    state: &mut DebuggerState,
-    params: Option<serde_json::Value>,
-) -> Result<serde_json::Value, String> {
-    let expression = params
-        .as_ref()
-        .and_then(|p| p.get("expression"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-
-    let result = evaluate_expression(state, expression);
-
-    Ok(json!({
-        "result": result.0,
-        "type": result.1,
-        "variablesReference": result.2.unwrap_or(0)
-    }))
-}
-
-fn evaluate_expression(
-    state: &DebuggerState,
-    expression: &str,
-) -> (String, Option<String>, Option<i32>) {
-    let expr = expression.trim();
-
-    if expr.starts_with('$') {
-        let var_name = &expr[1..];
-        for vars in state.variables.values() {
-            for var in vars {
-                if var.name == var_name {
-                    return (
-                        var.value.clone(),
-                        var.type_.clone(),
-                        var.variables_reference,
-                    );
+        loop {
+            let Ok(request) = self.requests.recv() else {
+                return Err(self.end());
+            };
+            match self.handle(&request, Some(&mut *interp)) {
+                Flow::Stay => {}
+                Flow::Resume => {
+                    self.handles.clear();
+                    return Ok(());
                 }
+                Flow::End => return Err(self.end()),
             }
         }
     }
 
-    match expr {
-        "threads" | "thread" => (
-            format!("Thread {}", state.current_thread_id),
-            Some("Thread".to_string()),
-            None,
-        ),
-        "state" => (
-            format!("{:?}", state.state),
-            Some("String".to_string()),
-            None,
-        ),
-        "line" => (state.line.to_string(), Some("Int".to_string()), None),
-        "column" => (state.column.to_string(), Some("Int".to_string()), None),
-        "program" => (
-            state.program.clone().unwrap_or_default(),
-            Some("String".to_string()),
-            None,
-        ),
-        "args" => (format!("{:?}", state.args), Some("Array".to_string()), None),
-        "breakpoints" => (
-            format!(
-                "{} breakpoints set",
-                state.breakpoints.values().map(|v| v.len()).sum::<usize>()
-            ),
-            Some("Int".to_string()),
-            None,
-        ),
-        "exceptionBreakpoints" => (
-            format!("{:?}", state.exception_breakpoints),
-            Some("Array".to_string()),
-            None,
-        ),
-        _ => (
-            format!("Unknown: {}", expr),
-            Some("Unknown".to_string()),
-            None,
-        ),
+    fn end(&mut self) -> RuntimeError {
+        self.ended = true;
+        RuntimeError::new(ErrorKind::Error, "debug session ended")
     }
 }
 
-pub fn handle_disconnect(
-    state: &mut DebuggerState,
-    _params: Option<serde_json::Value>,
-) -> Result<serde_json::Value, String> {
-    state.state = DebugState::Exited;
-    state.breakpoints.clear();
-    state.call_stack.clear();
-    state.variables.clear();
-    Ok(serde_json::Value::Null)
+fn stack_trace(interp: Option<&Interpreter>, args: &StackTraceArguments) -> serde_json::Value {
+    let frames = interp.map_or(&[][..], |i| i.debug_frames());
+    let levels = if args.levels == 0 { frames.len() } else { args.levels };
+    let stack_frames: Vec<StackFrame> = frames
+        .iter()
+        .enumerate()
+        .rev()
+        .skip(args.start_frame)
+        .take(levels)
+        .map(|(id, frame)| {
+            let span = frame.span.clone().unwrap_or_default();
+            StackFrame {
+                id,
+                name: frame.function.clone(),
+                source: span.file.as_deref().map(Source::from_file),
+                line: span.line,
+                column: span.col,
+                end_line: Some(span.end_line).filter(|&l| l > 0),
+                end_column: Some(span.end_col).filter(|&c| c > 0),
+            }
+        })
+        .collect();
+    json!({ "stackFrames": stack_frames, "totalFrames": frames.len() })
+}
+
+/// The members the client can expand a value into
+fn children(value: &Value) -> Vec<(String, Value)> {
+    match value {
+        Value::Array(items) | Value::Tuple(items) => indexed(items),
+        Value::Map(fields) => named(fields),
+        Value::StructInstance(instance) => named(&instance.fields),
+        Value::Enum(e) => match &e.payload {
+            EnumPayload::Tuple(items) => indexed(items),
+            EnumPayload::Struct(fields) => fields.clone(),
+            EnumPayload::Unit => Vec::new(),
+        },
+        Value::Reference(inner) => children(inner),
+        _ => Vec::new(),
+    }
 }
 
-pub fn handle_terminate(_state: &mut DebuggerState) -> serde_json::Value {
-    serde_json::Value::Null
+fn indexed(items: &[Value]) -> Vec<(String, Value)> {
+    items.iter().enumerate().map(|(i, v)| (format!("[{}]", i), v.clone())).collect()
 }
 
-pub fn handle_restart(
-    _state: &mut DebuggerState,
-    _params: Option<serde_json::Value>,
-) -> Result<serde_json::Value, String> {
-    Ok(serde_json::Value::Null)
+fn named(fields: &HashMap<String, Value>) -> Vec<(String, Value)> {
+    let mut fields: Vec<(String, Value)> = fields.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    fields.sort_by(|a, b| a.0.cmp(&b.0));
+    fields
 }
 
-pub fn set_breakpoint_hit(state: &mut DebuggerState, line: u32, column: u32) {
-    state.line = line;
-    state.column = column;
-    state.state = DebugState::Paused;
-    state.stopped_reason = Some("breakpoint".to_string());
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(s) => format!("{:?}", s),
+        other => other.to_string(),
+    }
 }
 
-pub fn set_paused(state: &mut DebuggerState, line: u32, column: u32, reason: &str) {
-    state.line = line;
-    state.column = column;
-    state.state = DebugState::Paused;
-    state.stopped_reason = Some(reason.to_string());
+/// Breakpoints and spans name files by canonical path, however the client spelled it
+fn source_key(path: &Path) -> String {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf()).to_string_lossy().into_owned()
 }
 
-pub fn set_exited(state: &mut DebuggerState, exit_code: i32) {
-    state.state = DebugState::Exited;
-    state.exit_code = Some(exit_code);
-}
+/// The interpreter's handle on the session; `run_session` keeps one too, to
+/// finish the session after the program ends
+struct Attached(Rc<RefCell<Session>>);
 
-pub fn add_stack_frame(state: &mut DebuggerState, name: &str, line: u32, column: u32) {
-    let frame_id = state.call_stack.len() as i32;
-    state.call_stack.push(StackFrame {
-        id: frame_id,
-        name: name.to_string(),
-        source: Some(Source {
-            name: Some(state.source_name.clone()),
-            path: None,
-            source_reference: None,
-        }),
-        line,
-        column,
-        end_line: None,
-        end_column: None,
-    });
-}
-
-pub fn add_variable(
-    state: &mut DebuggerState,
-    reference: i32,
-    name: &str,
-    value: &str,
-    type_: &str,
-) {
-    let vars = state.variables.entry(reference).or_insert_with(Vec::new);
-    vars.push(Variable {
-        name: name.to_string(),
-        value: value.to_string(),
-        type_: Some(type_.to_string()),
-        variables_reference: None,
-        indexed_variables: None,
-    });
-}
+impl DebugHook for Attached {
+    fn statement(&mut self, interp: &mut Interpreter, span: &Span) -> Result<(), RuntimeError> {
+        self.0.borrow_mut().statement(interp, span)
+    }
 
-pub fn clear_variables(state: &mut DebuggerState) {
-    state.variables.clear();
-    state.next_var_ref = 1;
+    fn exception(&mut self, interp: &mut Interpreter, error: &RuntimeError, caught: bool) -> Result<(), RuntimeError> {
+        self.0.borrow_mut().exception(interp, error, caught)
+    }
 }
 
-pub fn create_variable_reference(state: &mut DebuggerState) -> i32 {
-    let ref_id = state.next_var_ref;
-    state.next_var_ref += 1;
-    ref_id
+fn run_program(session: &Rc<RefCell<Session>>) -> Result<(), String> {
+    let path = PathBuf::from(source_key(&session.borrow().program));
+    let file = path.to_string_lossy().into_owned();
+    let source = std::fs::read_to_string(&path).map_err(|e| format!("Cannot read '{}': {}", file, e))?;
+    let mut parser = Parser::new(&source).with_file(&file);
+    let ast = parser.parse().map_err(|e| match parser.error_span() {
+        Some(span) => format!("{}: {}", span, e),
+        None => format!("{}: {}", file, e),
+    })?;
+    let mut interp = Interpreter::new()
+        .with_file(&path)
+        .with_max_depth(crate::cli::resolve_max_depth(&path, None))
+        .with_debug_hook(Box::new(Attached(Rc::clone(session))));
+    interp.execute(&ast).map_err(|e| match &e.span {
+        Some(span) => format!("{}: {}: {}\n", span, e.kind.name(), e.message),
+        None => format!("{}: {}\n", e.kind.name(), e.message),
+    })
 }
 
-pub fn run_debugger_session<R: Read, W: Write>(reader: &mut R, writer: &mut W, state: DAPHandler) {
-    loop {
-        let request = match read_message(reader) {
-            Some(req) => req,
-            None => break,
-        };
+/// Debug `program`, reading requests from `requests` and writing responses and
+/// events to `writer`. `break_at` lines of the program are breakpoints from the
+/// start; `output` is read for the program's stdout, if it was redirected.
+pub fn run_session(
+    program: &Path,
+    break_at: &[u32],
+    requests: Receiver<DAPRequest>,
+    writer: Box<dyn Write + Send>,
+    output: Option<File>,
+) -> Result<(), String> {
+    let client: Client = Arc::new(Mutex::new(Outgoing { writer, seq: 0 }));
+    if let Some(mut output) = output {
+        let client = Arc::clone(&client);
+        thread::spawn(move || {
+            let mut buf = [0u8; 4096];
+            while let Ok(n @ 1..) = output.read(&mut buf) {
+                let output = String::from_utf8_lossy(&buf[..n]).into_owned();
+                send_event(&client, "output", OutputEventBody { category: "stdout".to_string(), output });
+            }
+        });
+    }
 
-        let method = request.method.clone();
-        let id = request.id.clone();
-        let params = request.params.clone();
-
-        let mut state_guard = state.lock().unwrap();
-
-        let result = match method.as_str() {
-            "initialize" => Ok(handle_initialize(&mut state_guard, params)),
-            "launch" => handle_launch(&mut state_guard, params),
-            "configurationDone" => Ok(handle_configuration_done(&mut state_guard)),
-            "setBreakpoints" => handle_set_breakpoints(&mut state_guard, params),
-            "setExceptionBreakpoints" => handle_set_exception_breakpoints(&mut state_guard, params),
-            "threads" => Ok(handle_threads(&mut state_guard)),
-            "stackTrace" => handle_stack_trace(&mut state_guard, params),
-            "scopes" => handle_scopes(&mut state_guard, params),
-            "variables" => handle_variables(&mut state_guard, params),
-            "continue" => handle_continue(&mut state_guard, params),
-            "pause" => handle_pause(&mut state_guard, params),
-            "next" => handle_next(&mut state_guard, params),
-            "stepIn" => handle_step_in(&mut state_guard, params),
-            "stepOut" => handle_step_out(&mut state_guard, params),
-            "evaluate" => handle_evaluate(&mut state_guard, params),
-            "disconnect" => handle_disconnect(&mut state_guard, params),
-            "terminate" => Ok(handle_terminate(&mut state_guard)),
-            "restart" => handle_restart(&mut state_guard, params),
-            "loadedSources" | "modules" | "source" => Ok(serde_json::Value::Null),
-            _ => Err(format!("Unknown method: {}", method)),
-        };
+    let mut session = Session::new(program, requests, Arc::clone(&client));
+    if !session.configure() {
+        return Ok(());
+    }
+    let key = source_key(&session.program);
+    for &line in break_at {
+        session.breakpoint_ids += 1;
+        let breakpoint = SourceBreakpoint { line: line as usize, condition: None };
+        session.breakpoints.entry(key.clone()).or_default().push((session.breakpoint_ids, breakpoint));
+    }
 
-        match result {
-            Ok(response) => {
-                send_response(writer, id, response);
-            }
-            Err(e) => {
-                send_error_response(writer, id, -32601, &e);
-            }
+    let session = Rc::new(RefCell::new(session));
+    let result = run_program(&session);
+    let _ = std::io::stdout().flush();
+    let mut session = session.borrow_mut();
+    if session.ended {
+        return Ok(());
+    }
+    if let Err(e) = &result {
+        send_event(&client, "output", OutputEventBody { category: "stderr".to_string(), output: e.clone() });
+    }
+    send_event(&client, "exited", json!({ "exitCode": i32::from(result.is_err()) }));
+    send_event(&client, "terminated", json!({}));
+    // The client may still ask for threads and the like before it disconnects
+    while let Ok(request) = session.requests.recv() {
+        if session.handle(&request, None) == Flow::End {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Point fd 1 at a pipe, returning the original stdout for protocol messages
+/// and the read end of the pipe for the program's output
+fn redirect_stdout() -> Result<(File, File), String> {
+    use std::os::unix::io::FromRawFd;
+    let mut fds = [0; 2];
+    unsafe {
+        let protocol = libc::dup(1);
+        if protocol < 0 || libc::pipe(fds.as_mut_ptr()) != 0 || libc::dup2(fds[1], 1) < 0 {
+            return Err(format!("Cannot redirect stdout: {}", std::io::Error::last_os_error()));
         }
+        libc::close(fds[1]);
+        Ok((File::from_raw_fd(protocol), File::from_raw_fd(fds[0])))
     }
 }
 
-pub fn start_debug_server() -> DAPHandler {
-    let state = create_debugger_state();
-    let state_clone = Arc::clone(&state);
-
+/// `knull debug`: debug `file` for a DAP client talking over stdin/stdout
+pub fn start_debug_session(file: &Path, break_at: Vec<u32>) -> Result<(), String> {
+    let (protocol, output) = redirect_stdout()?;
+    let (sender, requests) = mpsc::channel();
     thread::spawn(move || {
-        let stdin = std::io::stdin();
-        let stdout = std::io::stdout();
-
-        run_debugger_session(&mut stdin.lock(), &mut stdout.lock(), state_clone);
+        let mut stdin = BufReader::new(std::io::stdin());
+        while let Some(request) = read_message(&mut stdin) {
+            if sender.send(request).is_err() {
+                break;
+            }
+        }
     });
-
-    state
-}
-
-pub fn send_stopped_event<W: Write>(writer: &mut W, reason: &str, thread_id: i32) {
-    let body = StoppedEventBody {
-        reason: reason.to_string(),
-        description: None,
-        thread_id: Some(thread_id),
-        all_threads_stopped: Some(true),
-        hit_breakpoint_ids: None,
-    };
-
-    send_event(writer, "stopped", Some(serde_json::to_value(body).unwrap()));
-}
-
-pub fn send_continued_event<W: Write>(writer: &mut W, thread_id: i32) {
-    let body = ContinuedEventBody {
-        thread_id,
-        all_threads_continued: Some(true),
-    };
-
-    send_event(
-        writer,
-        "continued",
-        Some(serde_json::to_value(body).unwrap()),
-    );
+    run_session(file, &break_at, requests, Box::new(protocol), Some(output))
 }
 
-pub fn send_exited_event<W: Write>(writer: &mut W, exit_code: i32) {
-    let body = ExitedEventBody {
-        exit_code,
-        description: None,
-    };
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::Sender;
+    use std::time::Duration;
 
-    send_event(writer, "exited", Some(serde_json::to_value(body).unwrap()));
-}
+    // Hands each framed message `run_session` writes to the test as JSON
+    struct Recorder(Sender<serde_json::Value>);
 
-pub fn send_terminated_event<W: Write>(writer: &mut W) {
-    let body = TerminatedEventBody { restart: None };
+    impl Write for Recorder {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            let text = String::from_utf8_lossy(buf);
+            if let Some((_, body)) = text.split_once("\r\n\r\n") {
+                let _ = self.0.send(serde_json::from_str(body).unwrap());
+            }
+            Ok(buf.len())
+        }
 
-    send_event(
-        writer,
-        "terminated",
-        Some(serde_json::to_value(body).unwrap()),
-    );
-}
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
 
-pub fn send_output_event<W: Write>(writer: &mut W, output: &str, output_type: &str) {
-    let body = OutputEventBody {
-        output: output.to_string(),
-        output_type: Some(output_type.to_string()),
-        group: None,
-        variables_reference: None,
-        source: None,
-        line: None,
-        column: None,
-    };
-
-    send_event(writer, "output", Some(serde_json::to_value(body).unwrap()));
-}
+    struct TestClient {
+        requests: Sender<DAPRequest>,
+        messages: Receiver<serde_json::Value>,
+        seq: i64,
+    }
 
-pub fn send_breakpoint_event<W: Write>(writer: &mut W, reason: &str, breakpoint: Breakpoint) {
-    let body = BreakpointEventBody {
-        reason: reason.to_string(),
-        breakpoint,
-    };
-
-    send_event(
-        writer,
-        "breakpoint",
-        Some(serde_json::to_value(body).unwrap()),
-    );
-}
+    impl TestClient {
+        /// Start a session debugging `source`
+        fn launch(name: &str, source: &str) -> (Self, String, thread::JoinHandle<Result<(), String>>) {
+            let dir = std::env::temp_dir().join(format!("knull_debugger_{}", std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+            let path = dir.join(name);
+            std::fs::write(&path, source).unwrap();
+            let (requests, incoming) = mpsc::channel();
+            let (outgoing, messages) = mpsc::channel();
+            let program = path.clone();
+            let session = thread::spawn(move || run_session(&program, &[], incoming, Box::new(Recorder(outgoing)), None));
+            (TestClient { requests, messages, seq: 0 }, path.to_string_lossy().into_owned(), session)
+        }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        /// Send a request and return the body of its response
+        fn request(&mut self, command: &str, arguments: serde_json::Value) -> serde_json::Value {
+            self.seq += 1;
+            let seq = self.seq;
+            self.requests.send(DAPRequest { seq, command: command.to_string(), arguments }).unwrap();
+            let response = self.wait(|m| m["type"] == "response" && m["request_seq"] == seq);
+            assert_eq!(response["success"], true, "{} failed: {}", command, response);
+            response["body"].clone()
+        }
 
-    #[test]
-    fn test_debugger_state_default() {
-        let state = DebuggerState::default();
-        assert_eq!(state.state, DebugState::Stopped);
-        assert_eq!(state.current_thread_id, 1);
-        assert!(!state.initialized);
-    }
+        fn event(&mut self, event: &str) -> serde_json::Value {
+            self.wait(|m| m["type"] == "event" && m["event"] == event)["body"].clone()
+        }
 
-    #[test]
-    fn test_breakpoint_hit() {
-        let mut state = DebuggerState::default();
-        set_breakpoint_hit(&mut state, 10, 5);
-        assert_eq!(state.line, 10);
-        assert_eq!(state.column, 5);
-        assert_eq!(state.state, DebugState::Paused);
-    }
+        fn wait(&mut self, wanted: impl Fn(&serde_json::Value) -> bool) -> serde_json::Value {
+            loop {
+                let message = self.messages.recv_timeout(Duration::from_secs(10)).expect("no message from the session");
+                if wanted(&message) {
+                    return message;
+                }
+            }
+        }
 
-    #[test]
-    fn test_add_stack_frame() {
-        let mut state = DebuggerState::default();
-        add_variable(&mut state, 0, "x", "42", "Int");
-        add_variable(&mut state, 0, "y", "hello", "String");
+        /// The innermost frame's function and line
+        fn top_frame(&mut self) -> (String, u64, u64) {
+            let trace = self.request("stackTrace", json!({ "threadId": THREAD_ID }));
+            let frame = &trace["stackFrames"][0];
+            (frame["name"].as_str().unwrap().to_string(), frame["line"].as_u64().unwrap(), frame["id"].as_u64().unwrap())
+        }
 
-        let vars = state.variables.get(&0).unwrap();
-        assert_eq!(vars.len(), 2);
+        fn evaluate(&mut self, expression: &str, frame: u64) -> String {
+            let body = self.request("evaluate", json!({ "expression": expression, "frameId": frame }));
+            body["result"].as_str().unwrap().to_string()
+        }
     }
 
     #[test]
-    fn test_evaluate_expression() {
-        let state = DebuggerState::default();
-
-        let (result, type_, _) = evaluate_expression(&state, "line");
-        assert_eq!(result, "1");
-        assert_eq!(type_, Some("Int".to_string()));
-
-        let (result, _, _) = evaluate_expression(&state, "state");
-        assert_eq!(result, "Stopped");
+    fn test_breakpoints_and_stepping() {
+        let source = "fn add(a, b) {\n    let sum = a + b\n    return sum\n}\nlet x = 1\nlet y = add(x, 2)\nlet z = y * 2\n";
+        let (mut client, path, session) = TestClient::launch("stepping.knull", source);
+        let capabilities = client.request("initialize", json!({ "adapterID": "knull" }));
+        assert_eq!(capabilities["supportsConditionalBreakpoints"], true);
+        client.event("initialized");
+        let set = client.request("setBreakpoints", json!({ "source": { "path": path }, "breakpoints": [{ "line": 2 }] }));
+        assert_eq!(set["breakpoints"][0]["verified"], true);
+        client.request("configurationDone", json!({}));
+
+        assert_eq!(client.event("stopped")["reason"], "breakpoint");
+        let trace = client.request("stackTrace", json!({ "threadId": THREAD_ID }));
+        assert_eq!(trace["totalFrames"], 2);
+        assert_eq!(trace["stackFrames"][0]["name"], "add");
+        assert_eq!(trace["stackFrames"][0]["line"], 2);
+        assert_eq!(trace["stackFrames"][1]["line"], 6);
+        let frame = trace["stackFrames"][0]["id"].clone();
+        let scopes = client.request("scopes", json!({ "frameId": frame }));
+        let locals = client.request("variables", json!({ "variablesReference": scopes["scopes"][0]["variablesReference"] }));
+        let names: Vec<_> = locals["variables"].as_array().unwrap().iter().map(|v| v["name"].as_str().unwrap()).collect();
+        assert_eq!(names, ["a", "b"]);
+        assert_eq!(client.evaluate("a * 10", frame.as_u64().unwrap()), "10");
+
+        client.request("next", json!({ "threadId": THREAD_ID }));
+        assert_eq!(client.event("stopped")["reason"], "step");
+        let (name, line, frame) = client.top_frame();
+        assert_eq!((name.as_str(), line), ("add", 3));
+        assert_eq!(client.evaluate("sum", frame), "3");
+        // The caller's frame sees its own variables
+        assert_eq!(client.evaluate("x + 100", 0), "101");
+
+        client.request("stepOut", json!({ "threadId": THREAD_ID }));
+        client.event("stopped");
+        let (name, line, frame) = client.top_frame();
+        assert_eq!((name.as_str(), line), ("<program>", 7));
+        assert_eq!(client.evaluate("y", frame), "3");
+
+        client.request("continue", json!({ "threadId": THREAD_ID }));
+        assert_eq!(client.event("exited")["exitCode"], 0);
+        client.event("terminated");
+        client.request("disconnect", json!({}));
+        session.join().unwrap().unwrap();
     }
 
     #[test]
-    fn test_initialize_response() {
-        let response = InitializeResponse::default();
-        assert!(response.supports_conditional_breakpoints);
-        assert!(response.supports_configuration_done_request);
+    fn test_conditional_breakpoints_and_errors() {
+        let source = "let total = 0\nfor i in range(0, 5) {\n    total = total + i\n}\nlet items = [1, 2]\nlet bad = items[total]\n";
+        let (mut client, path, session) = TestClient::launch("errors.knull", source);
+        client.request("initialize", json!({}));
+        client.request("setBreakpoints", json!({ "source": { "path": path }, "breakpoints": [{ "line": 3, "condition": "i == 3" }] }));
+        client.request("configurationDone", json!({}));
+
+        assert_eq!(client.event("stopped")["reason"], "breakpoint");
+        assert_eq!(client.evaluate("i", 0), "3");
+        assert_eq!(client.evaluate("total", 0), "3");
+
+        client.request("continue", json!({ "threadId": THREAD_ID }));
+        let stopped = client.event("stopped");
+        assert_eq!(stopped["reason"], "exception");
+        assert!(stopped["description"].as_str().unwrap().starts_with("IndexError"), "{}", stopped);
+        assert_eq!(client.top_frame().1, 6);
+
+        client.request("continue", json!({ "threadId": THREAD_ID }));
+        assert_eq!(client.event("exited")["exitCode"], 1);
+        client.request("disconnect", json!({}));
+        session.join().unwrap().unwrap();
     }
 }
@@ -6,7 +6,7 @@
 //! - Effect inference
 //! - Effect handlers
 
-use crate::parser::ASTNode;
+use crate::parser::{ASTNode, Span};
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -129,7 +129,9 @@ impl Default for FunctionSignature {
 
 pub struct EffectChecker {
     scopes: Vec<HashMap<String, FunctionSignature>>,
-    errors: Vec<String>,
+    /// Span of the innermost node being checked that has one
+    span: Option<Span>,
+    errors: Vec<(String, Option<Span>)>,
     current_effects: EffectSet,
     effect_vars: HashMap<String, EffectSet>,
 }
@@ -147,6 +149,7 @@ impl EffectChecker {
 
         EffectChecker {
             scopes: vec![HashMap::new()],
+            span: None,
             errors: Vec::new(),
             current_effects: EffectSet::new(),
             effect_vars,
@@ -159,10 +162,16 @@ impl EffectChecker {
         if self.errors.is_empty() {
             Ok(effects)
         } else {
-            Err(self.errors.join("\n"))
+            let messages: Vec<_> = self.errors.iter().map(|(message, _)| message.as_str()).collect();
+            Err(messages.join("\n"))
         }
     }
 
+    /// What `check` found, each with the span of the node it is about
+    pub fn errors(&self) -> &[(String, Option<Span>)] {
+        &self.errors
+    }
+
     fn push_scope(&mut self) {
         self.scopes.push(HashMap::new());
     }
@@ -173,6 +182,12 @@ impl EffectChecker {
 
     fn check_node(&mut self, node: &ASTNode) -> Result<EffectSet, String> {
         match node {
+            ASTNode::Spanned { span, node } => {
+                let outer = self.span.replace(span.clone());
+                let effects = self.check_node(node);
+                self.span = outer;
+                effects
+            }
             ASTNode::Program(items) => {
                 let mut result = EffectSet::new();
                 for item in items {
@@ -196,9 +211,9 @@ impl EffectChecker {
                 let body_effects = self.check_node(body)?;
                 sig.return_effects = body_effects;
 
-                self.scopes.last_mut().unwrap().insert(name.clone(), sig);
-
+                // Callers see the signature once the function's own scope is gone
                 self.pop_scope();
+                self.scopes.last_mut().unwrap().insert(name.clone(), sig);
                 Ok(EffectSet::new())
             }
             ASTNode::Block(stmts) => {
@@ -269,7 +284,7 @@ impl EffectChecker {
                     result.union(&arg_effects);
                 }
 
-                if let ASTNode::Identifier(name) = func.as_ref() {
+                if let ASTNode::Identifier(name) = func.inner() {
                     if let Some(fn_effects) = self.effect_vars.get(name) {
                         result.union(fn_effects);
                     } else {
@@ -306,7 +321,7 @@ impl EffectChecker {
             ASTNode::Literal(_) => Ok(EffectSet::pure()),
             ASTNode::EffectAnnotation { expr, effects } => {
                 let mut result = EffectSet::new();
-                self.check_node(expr)?;
+                let performed = self.check_node(expr)?;
                 for effect in effects {
                     let eff = match effect {
                         crate::parser::Effect::IO => Effect::IO,
@@ -319,16 +334,39 @@ impl EffectChecker {
                     };
                     result.add(eff);
                 }
+                // The annotation bounds what the expression may do
+                let extra: Vec<_> = performed
+                    .effects()
+                    .iter()
+                    .filter(|effect| !result.contains(effect))
+                    .map(|effect| effect.name().to_lowercase())
+                    .collect();
+                if !extra.is_empty() {
+                    let allowed: Vec<_> = result.effects().iter().map(|e| e.name().to_lowercase()).collect();
+                    let message = format!(
+                        "expression performs {} but its effect annotation allows only {{{}}}",
+                        extra.join(", "),
+                        allowed.join(", ")
+                    );
+                    self.errors.push((message, self.span.clone()));
+                }
                 Ok(result)
             }
             ASTNode::LinearExpr(inner, _) => self.check_node(inner),
             ASTNode::Consume(expr) => self.check_node(expr),
-            _ => Ok(EffectSet::pure()),
+            other => {
+                let mut result = EffectSet::pure();
+                for child in other.children() {
+                    let effects = self.check_node(child)?;
+                    result.union(&effects);
+                }
+                Ok(result)
+            }
         }
     }
 
     pub fn infer_effects(&mut self, ast: &ASTNode) -> EffectSet {
-        self.check_node(ast).unwrap_or(EffectSet::new())
+        self.check_node(ast).unwrap_or_default()
     }
 }
 
@@ -0,0 +1,523 @@
+//! Knull Formatter
+//!
+//! `knull fmt` and the language server's formatting requests share
+//! `format_source`. It works on a lossless token stream: each token keeps its
+//! exact source text, so strings, f-strings and numbers are never rewritten,
+//! along with the comments and line breaks before it. Line breaks stay where
+//! they were written and runs of blank lines collapse to one; what changes is
+//! indentation, the spacing between tokens on a line, and that a `{ ... }`
+//! spanning several lines gets `{` at the end of a line and `}` on its own.
+//!
+//! As a safety net the result is lexed again and must give the same tokens as
+//! the input, otherwise formatting fails and the source is left alone.
+
+use crate::lexer::{Lexer, TokenKind};
+
+const INDENT: &str = "    ";
+
+/// What sits between two tokens
+#[derive(Debug, Clone, PartialEq)]
+enum Trivia {
+    Newlines(usize),
+    /// A `//` or `/* */` comment and the (0-based) line it starts on
+    Comment(String, usize),
+}
+
+/// A token with its exact source text and the trivia before it
+#[derive(Debug, Clone)]
+struct Piece {
+    kind: TokenKind,
+    text: String,
+    /// 0-based source lines the token starts and ends on
+    line: usize,
+    end_line: usize,
+    trivia: Vec<Trivia>,
+    /// Whether anything separated it from the previous token
+    spaced: bool,
+}
+
+/// How a token was printed, for deciding the space after it
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Role {
+    Normal,
+    /// Prefix `-x`, `!x`, `&x`, `*x`, `~x`
+    Unary,
+    /// The `|` opening a lambda's parameters, and the one closing them
+    LambdaOpen,
+    LambdaClose,
+    /// `<` and `>` around type arguments, as in `Vec<int>`
+    GenericOpen,
+    GenericClose,
+    /// `*` in `use a::*`
+    Glob,
+}
+
+/// Split `source` into pieces, plus the trivia after the last token
+fn pieces(source: &str) -> (Vec<Piece>, Vec<Trivia>) {
+    let chars: Vec<char> = source.chars().collect();
+    let mut line_starts = vec![0];
+    line_starts.extend(chars.iter().enumerate().filter(|(_, c)| **c == '\n').map(|(i, _)| i + 1));
+    let offset = |line: usize, col: usize| {
+        line_starts.get(line.saturating_sub(1)).map_or(chars.len(), |start| start + col.saturating_sub(1)).min(chars.len())
+    };
+
+    let mut out = Vec::new();
+    let mut pos = 0;
+    let mut pos_line = 0;
+    for tok in Lexer::new(source).tokenize() {
+        let start = offset(tok.line, tok.col).max(pos);
+        let gap: String = chars[pos..start].iter().collect();
+        let trivia = trivia(&gap, pos_line);
+        if tok.kind == TokenKind::Eof {
+            return (out, trivia);
+        }
+        let end = offset(tok.end_line, tok.end_col).max(start);
+        out.push(Piece {
+            kind: tok.kind,
+            text: chars[start..end].iter().collect(),
+            line: tok.line - 1,
+            end_line: tok.end_line - 1,
+            trivia,
+            spaced: start > pos,
+        });
+        pos = end;
+        pos_line = tok.end_line - 1;
+    }
+    (out, Vec::new())
+}
+
+/// The comments and line breaks in the text between two tokens, which starts on `line`
+fn trivia(gap: &str, mut line: usize) -> Vec<Trivia> {
+    let chars: Vec<char> = gap.chars().collect();
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let start = i;
+        match (chars[i], chars.get(i + 1)) {
+            ('\n', _) => {
+                line += 1;
+                i += 1;
+                match out.last_mut() {
+                    Some(Trivia::Newlines(n)) => *n += 1,
+                    _ => out.push(Trivia::Newlines(1)),
+                }
+                continue;
+            }
+            (c, _) if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            ('/', Some('/')) => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            ('/', Some('*')) => {
+                i += 2;
+                let mut depth = 1;
+                while i < chars.len() && depth > 0 {
+                    match (chars[i], chars.get(i + 1)) {
+                        ('/', Some('*')) => { depth += 1; i += 2; }
+                        ('*', Some('/')) => { depth -= 1; i += 2; }
+                        _ => i += 1,
+                    }
+                }
+            }
+            // Nothing else should come between tokens; keep whatever it is
+            _ => {
+                while i < chars.len() && !chars[i].is_whitespace() {
+                    i += 1;
+                }
+            }
+        }
+        let text: String = chars[start..i].iter().collect();
+        out.push(Trivia::Comment(text.trim_end().to_string(), line));
+        line += text.matches('\n').count();
+    }
+    out
+}
+
+/// A formatted line and the source lines it came from
+#[derive(Debug, Clone)]
+struct Line {
+    first: usize,
+    last: usize,
+    text: String,
+}
+
+fn is_opener(kind: &TokenKind) -> bool {
+    matches!(kind, TokenKind::LParen | TokenKind::LBracket | TokenKind::LBrace)
+}
+
+fn is_closer(kind: &TokenKind) -> bool {
+    matches!(kind, TokenKind::RParen | TokenKind::RBracket | TokenKind::RBrace)
+}
+
+/// Operators between two operands
+fn is_binary(kind: &TokenKind) -> bool {
+    use TokenKind::*;
+    matches!(
+        kind,
+        Plus | Minus | Star | Slash | Percent | Ampersand | Pipe | Caret | Shl | Shr | And | Or
+            | EqEq | Neq | Lt | Gt | Lte | Gte | Eq | PlusEq | MinusEq | StarEq | SlashEq
+            | PercentEq | AmpEq | BarEq | CaretEq | ShlEq | ShrEq | Pipeline | NullCoalesce
+            | FatArrow | Arrow
+    )
+}
+
+/// Whether an operand is expected after a token printed as `role`
+fn expects_operand(kind: Option<&TokenKind>, role: Role) -> bool {
+    use TokenKind::*;
+    if matches!(role, Role::Unary | Role::LambdaOpen | Role::LambdaClose) {
+        return true;
+    }
+    match kind {
+        None => true,
+        Some(kind) => {
+            is_binary(kind)
+                || matches!(
+                    kind,
+                    LParen | LBracket | LBrace | Comma | Semicolon | Colon | Return | In | If | While
+                        | Match | Else | Yield | Throw | Do | Defer | Spawn | Consume | Await
+                )
+        }
+    }
+}
+
+fn lex(source: &str) -> Vec<(TokenKind, String)> {
+    Lexer::new(source).tokenize().into_iter().map(|t| (t.kind, t.value)).collect()
+}
+
+/// Whether `a` and `b` printed with nothing between them still lex as `a` then `b`
+fn can_touch(a: &str, b: &str) -> bool {
+    let mut apart = lex(a);
+    apart.pop();
+    apart.extend(lex(b));
+    apart == lex(&format!("{}{}", a, b))
+}
+
+struct Printer<'a> {
+    pieces: &'a [Piece],
+    /// For each piece, whether a line break must follow (`{`) or precede (`}`) it
+    break_after: Vec<bool>,
+    break_before: Vec<bool>,
+    lines: Vec<Line>,
+    cur: Option<Line>,
+    /// Open brackets, with the indent of the line each is on
+    stack: Vec<(TokenKind, usize)>,
+    blank_pending: bool,
+    break_pending: bool,
+    /// The last token printed, and the last on the previous line
+    last: Option<(TokenKind, Role)>,
+    prev_line_last: Option<(TokenKind, Role)>,
+    in_lambda_params: bool,
+    generic_depth: usize,
+}
+
+impl<'a> Printer<'a> {
+    fn new(pieces: &'a [Piece]) -> Self {
+        let mut break_after = vec![false; pieces.len()];
+        let mut break_before = vec![false; pieces.len()];
+        let mut open = Vec::new();
+        for (i, piece) in pieces.iter().enumerate() {
+            match piece.kind {
+                TokenKind::LBrace => open.push(i),
+                TokenKind::RBrace => {
+                    if let Some(o) = open.pop() {
+                        if pieces[o].line != piece.line {
+                            break_after[o] = true;
+                            break_before[i] = true;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Printer {
+            pieces,
+            break_after,
+            break_before,
+            lines: Vec::new(),
+            cur: None,
+            stack: Vec::new(),
+            blank_pending: false,
+            break_pending: false,
+            last: None,
+            prev_line_last: None,
+            in_lambda_params: false,
+            generic_depth: 0,
+        }
+    }
+
+    fn newline(&mut self) {
+        if let Some(line) = self.cur.take() {
+            self.lines.push(line);
+            self.prev_line_last = self.last.clone();
+        }
+        self.break_pending = false;
+        self.generic_depth = 0;
+    }
+
+    /// Begin a line for a token of `kind` (`None` for a comment) from source `line`
+    fn start_line(&mut self, kind: Option<&TokenKind>, line: usize) {
+        let closes = kind.is_some_and(is_closer);
+        let after_opener = self.prev_line_last.as_ref().is_some_and(|(kind, _)| is_opener(kind));
+        if self.blank_pending && !self.lines.is_empty() && !closes && !after_opener {
+            let blank = line.saturating_sub(1);
+            self.lines.push(Line { first: blank, last: blank, text: String::new() });
+        }
+        self.blank_pending = false;
+
+        let mut indent = match self.stack.last() {
+            None => 0,
+            Some((_, base)) if closes => *base,
+            Some((_, base)) => base + 1,
+        };
+        let continues = kind.is_some_and(|k| {
+            matches!(k, TokenKind::Dot | TokenKind::SafeNav | TokenKind::Pipeline | TokenKind::And | TokenKind::Or | TokenKind::NullCoalesce)
+        }) || self.prev_line_last.as_ref().is_some_and(|(kind, role)| *role == Role::Normal && is_binary(kind));
+        if kind.is_some() && !closes && continues {
+            indent += 1;
+        }
+        self.cur = Some(Line { first: line, last: line, text: INDENT.repeat(indent) });
+    }
+
+    fn indent_of_current(&self) -> usize {
+        self.cur.as_ref().map_or(0, |l| (l.text.len() - l.text.trim_start_matches(' ').len()) / INDENT.len())
+    }
+
+    fn comment(&mut self, text: &str, line: usize) {
+        match &mut self.cur {
+            Some(cur) => {
+                cur.text.push(' ');
+                cur.text.push_str(text);
+                cur.last = line + text.matches('\n').count();
+            }
+            None => {
+                self.start_line(None, line);
+                let cur = self.cur.as_mut().unwrap();
+                cur.text.push_str(text);
+                cur.last = line + text.matches('\n').count();
+            }
+        }
+        if text.starts_with("//") {
+            self.newline();
+        }
+    }
+
+    fn token(&mut self, i: usize) {
+        let piece = &self.pieces[i];
+        for t in &piece.trivia {
+            match t {
+                Trivia::Newlines(n) => {
+                    self.newline();
+                    if *n >= 2 {
+                        self.blank_pending = true;
+                    }
+                }
+                Trivia::Comment(text, line) => self.comment(text, *line),
+            }
+        }
+        if self.break_pending || self.break_before[i] {
+            self.newline();
+        }
+
+        let role = self.role(i);
+        match &self.cur {
+            None => self.start_line(Some(&piece.kind), piece.line),
+            Some(_) => {
+                if self.space_before(i, role) {
+                    self.cur.as_mut().unwrap().text.push(' ');
+                }
+            }
+        }
+        let cur = self.cur.as_mut().unwrap();
+        cur.text.push_str(&piece.text);
+        cur.last = piece.end_line;
+
+        if is_opener(&piece.kind) {
+            let indent = self.indent_of_current();
+            self.stack.push((piece.kind.clone(), indent));
+        } else if is_closer(&piece.kind) {
+            self.stack.pop();
+        }
+        match role {
+            Role::LambdaOpen => self.in_lambda_params = true,
+            Role::LambdaClose => self.in_lambda_params = false,
+            Role::GenericOpen => self.generic_depth += 1,
+            Role::GenericClose => {
+                let closed = if piece.kind == TokenKind::Shr { 2 } else { 1 };
+                self.generic_depth = self.generic_depth.saturating_sub(closed);
+            }
+            _ => {}
+        }
+        if matches!(piece.kind, TokenKind::Semicolon | TokenKind::LBrace) {
+            self.generic_depth = 0;
+        }
+        if self.break_after[i] {
+            self.break_pending = true;
+        }
+        self.last = Some((piece.kind.clone(), role));
+    }
+
+    /// How piece `i` is used, judged from the tokens around it
+    fn role(&self, i: usize) -> Role {
+        use TokenKind::*;
+        let piece = &self.pieces[i];
+        let (last_kind, last_role) = match &self.last {
+            Some((kind, role)) => (Some(kind), *role),
+            None => (None, Role::Normal),
+        };
+        let operand_next = expects_operand(last_kind, last_role) || self.cur.is_none();
+        let next_spaced = self.pieces.get(i + 1).is_none_or(|p| p.spaced);
+        match piece.kind {
+            Pipe if self.in_lambda_params => Role::LambdaClose,
+            Pipe if operand_next => Role::LambdaOpen,
+            Minus | Bang | Ampersand | Star | Tilde if operand_next => Role::Unary,
+            Star if matches!(last_kind, Some(DoubleColon)) => Role::Glob,
+            Lt if !piece.spaced && !next_spaced && matches!(last_kind, Some(Identifier | DoubleColon)) => Role::GenericOpen,
+            Gt | Shr if self.generic_depth > 0 && !piece.spaced => Role::GenericClose,
+            _ => Role::Normal,
+        }
+    }
+
+    /// Whether to put a space between the last token printed and piece `i`
+    fn space_before(&self, i: usize, role: Role) -> bool {
+        use TokenKind::*;
+        let next = &self.pieces[i];
+        let prev = &self.pieces[i - 1];
+        let prev_role = self.last.as_ref().map_or(Role::Normal, |(_, role)| *role);
+        if prev.kind == Unknown || next.kind == Unknown {
+            return next.spaced;
+        }
+        let space = if matches!(prev_role, Role::Unary | Role::LambdaOpen | Role::GenericOpen)
+            || matches!(role, Role::LambdaClose | Role::GenericOpen | Role::GenericClose)
+        {
+            false
+        } else {
+            match (&prev.kind, &next.kind) {
+                (LParen | LBracket, _) | (_, RParen | RBracket) => false,
+                (_, Comma | Semicolon | Dot | SafeNav | DoubleColon | Question | Colon) => false,
+                (Dot | SafeNav | DoubleColon | Pound | At, _) => false,
+                (DotDot | DotDotEq, _) | (_, DotDot | DotDotEq) => false,
+                (LBrace, RBrace) => false,
+                (Identifier | RParen | RBracket | SelfValue | Question | Fn, LParen) => false,
+                (Gt | Shr, LParen) => prev_role != Role::GenericClose,
+                (Identifier | RParen | RBracket | String | SelfValue | Question, LBracket) => false,
+                _ => true,
+            }
+        };
+        space || !can_touch(&prev.text, &next.text)
+    }
+
+    /// Print the comments after the last token and hand back the lines
+    fn finish(mut self, trailing: &[Trivia]) -> Vec<Line> {
+        for t in trailing {
+            match t {
+                Trivia::Newlines(n) => {
+                    self.newline();
+                    if *n >= 2 {
+                        self.blank_pending = true;
+                    }
+                }
+                Trivia::Comment(text, line) => self.comment(text, *line),
+            }
+        }
+        self.newline();
+        self.lines
+    }
+}
+
+/// Lay out `source` line by line
+fn format_lines(source: &str) -> Result<Vec<Line>, String> {
+    let mut parser = crate::parser::Parser::new(source);
+    if let Err(e) = parser.parse() {
+        return Err(match parser.error_span() {
+            Some(span) => format!("cannot format: parse error at {}: {}", span, e),
+            None => format!("cannot format: parse error: {}", e),
+        });
+    }
+
+    let (pieces, trailing) = pieces(source);
+    let mut printer = Printer::new(&pieces);
+    for i in 0..pieces.len() {
+        printer.token(i);
+    }
+    let lines = printer.finish(&trailing);
+
+    if lex(source) != lex(&join(&lines)) {
+        return Err("cannot format: the result would not mean the same as the source".to_string());
+    }
+    Ok(lines)
+}
+
+fn join(lines: &[Line]) -> String {
+    let mut out = String::new();
+    for line in lines {
+        out.push_str(&line.text);
+        out.push('\n');
+    }
+    out
+}
+
+/// Format a whole file
+pub fn format_source(source: &str) -> Result<String, String> {
+    format_lines(source).map(|lines| join(&lines))
+}
+
+/// Format the source lines `first..=last` (0-based). Returns the lines actually
+/// replaced, widened so no construct is cut in half, and their new text.
+pub fn format_range(source: &str, first: usize, last: usize) -> Result<(usize, usize, String), String> {
+    let lines = format_lines(source)?;
+    let (mut first, mut last) = (first, last.max(first));
+    loop {
+        let straddles = |l: &&Line| (l.first < first && l.last >= first) || (l.first <= last && l.last > last);
+        match lines.iter().find(straddles) {
+            Some(l) => {
+                first = first.min(l.first);
+                last = last.max(l.last);
+            }
+            None => break,
+        }
+    }
+    let chosen: Vec<Line> = lines.into_iter().filter(|l| l.first >= first && l.last <= last).collect();
+    Ok((first, last, join(&chosen)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_keeps_comments_strings_and_blank_lines() {
+        let src = "// header\n\n\n\nfn   main( ) {\n  let x=1+2 // trailing\n\tlet s = \"a  { b }\\n\"\n  let f = f\"x = {x}\"   ;  println(s)\n\n\n  /* block\n     comment */\n  let add=|a,b|a+b\n  let m = {\"k\":1,\"j\": -2}\n  match x {\n    1=>println(\"one\"),\n    _ => {}\n  }\n}\n";
+        let expected = "// header\n\nfn main() {\n    let x = 1 + 2 // trailing\n    let s = \"a  { b }\\n\"\n    let f = f\"x = {x}\"; println(s)\n\n    /* block\n     comment */\n    let add = |a, b| a + b\n    let m = { \"k\": 1, \"j\": -2 }\n    match x {\n        1 => println(\"one\"),\n        _ => {}\n    }\n}\n";
+        let formatted = format_source(src).unwrap();
+        assert_eq!(formatted, expected);
+        assert_eq!(format_source(&formatted).unwrap(), formatted);
+    }
+
+    #[test]
+    fn test_format_layout() {
+        let src = "fn f<T>(xs: [T]) -> int { if !done && x < -1 { return -x }\nreturn xs.len() }\nlet total = a +\nb\nlet r = items\n.map(|| 0)\n.filter(|x| x>0)\nfor i in range(0, 10) { print([.. arr[i]]) }\n";
+        let expected = "fn f<T>(xs: [T]) -> int {\n    if !done && x < -1 { return -x }\n    return xs.len()\n}\nlet total = a +\n    b\nlet r = items\n    .map(|| 0)\n    .filter(|x| x > 0)\nfor i in range(0, 10) { print([..arr[i]]) }\n";
+        assert_eq!(format_source(src).unwrap(), expected);
+        // The output parses and formats to itself, spreads included
+        assert_eq!(format_source(expected).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_format_refuses_broken_source() {
+        assert!(format_source("fn main( {").is_err());
+    }
+
+    #[test]
+    fn test_format_range() {
+        let src = "let a=1\nfn f() {\n  let b=2\n  let c=3\n}\nlet d=4\n";
+        let (first, last, text) = format_range(src, 2, 2).unwrap();
+        assert_eq!((first, last), (2, 2));
+        assert_eq!(text, "    let b = 2\n");
+        let (first, last, text) = format_range(src, 5, 5).unwrap();
+        assert_eq!((first, last, text.as_str()), (5, 5, "let d = 4\n"));
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! Tree-walking interpreter for executing Knull code directly.
 
+use std::cell::Cell;
 use std::collections::HashMap;
 use std::fs;
 use std::io::{Read, Write};
@@ -10,7 +11,7 @@ use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
-use crate::parser::{ASTNode, Literal, Type};
+use crate::parser::{ASTNode, EnumVariant, Literal, Param, Span, Type, VariantData};
 use libc;
 use rusqlite;
 use flate2::Compression;
@@ -124,16 +125,16 @@ fn get_tcp_stream(handle: i64) -> Option<std::sync::MutexGuard<'static, HashMap<
     }
 }
 
-fn tcp_stream_send(handle: i64, data: &[u8]) -> Result<usize, String> {
+fn tcp_stream_send(handle: i64, data: &[u8]) -> Result<usize, RuntimeError> {
     let mut streams = TCP_STREAMS.lock().unwrap();
     if let Some(stream) = streams.get_mut(&handle) {
-        stream.write(data).map_err(|e| e.to_string())
+        stream.write(data).map_err(|e| raise(ErrorKind::IOError, e.to_string()))
     } else {
-        Err("Invalid stream handle".to_string())
+        Err(raise(ErrorKind::ValueError, "Invalid stream handle".to_string()))
     }
 }
 
-fn tcp_stream_recv(handle: i64, size: usize) -> Result<String, String> {
+fn tcp_stream_recv(handle: i64, size: usize) -> Result<String, RuntimeError> {
     let mut streams = TCP_STREAMS.lock().unwrap();
     if let Some(stream) = streams.get_mut(&handle) {
         let mut buf = vec![0u8; size];
@@ -142,10 +143,10 @@ fn tcp_stream_recv(handle: i64, size: usize) -> Result<String, String> {
                 buf.truncate(n);
                 Ok(String::from_utf8_lossy(&buf).to_string())
             }
-            Err(e) => Err(e.to_string()),
+            Err(e) => Err(raise(ErrorKind::IOError, e.to_string())),
         }
     } else {
-        Err("Invalid stream handle".to_string())
+        Err(raise(ErrorKind::ValueError, "Invalid stream handle".to_string()))
     }
 }
 
@@ -159,17 +160,17 @@ fn save_tcp_listener(listener: TcpListener) -> i64 {
     handle
 }
 
-fn tcp_listener_accept(handle: i64) -> Result<(i64, String), String> {
+fn tcp_listener_accept(handle: i64) -> Result<(i64, String), RuntimeError> {
     // Accept connection — we must release the listeners lock before acquiring streams lock
     let result = {
         let mut listeners = TCP_LISTENERS.lock().unwrap();
         if let Some(listener) = listeners.get_mut(&handle) {
             match listener.accept() {
                 Ok((stream, addr)) => Ok((stream, addr.to_string())),
-                Err(e) => Err(e.to_string()),
+                Err(e) => Err(raise(ErrorKind::IOError, e.to_string())),
             }
         } else {
-            Err("Invalid listener handle".to_string())
+            Err(raise(ErrorKind::ValueError, "Invalid listener handle".to_string()))
         }
     }; // listeners lock released here
     match result {
@@ -224,6 +225,48 @@ pub struct StructInstance {
     pub fields: HashMap<String, Value>,
 }
 
+/// Enum definition
+#[derive(Debug, Clone)]
+pub struct EnumDef {
+    pub name: String,
+    pub variants: Vec<EnumVariant>,
+}
+
+/// Enum instance: one variant of an enum and its payload
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumValue {
+    pub enum_name: String,
+    pub variant: String,
+    pub payload: EnumPayload,
+}
+
+/// Data carried by an enum instance
+#[derive(Debug, Clone, PartialEq)]
+pub enum EnumPayload {
+    Unit,
+    Tuple(Vec<Value>),
+    Struct(Vec<(String, Value)>),
+}
+
+impl EnumValue {
+    /// The value a payload pattern is matched against: a single tuple field
+    /// directly, several as a tuple
+    fn payload_value(&self) -> Value {
+        match &self.payload {
+            EnumPayload::Tuple(vals) if vals.len() == 1 => vals[0].clone(),
+            EnumPayload::Tuple(vals) => Value::Tuple(vals.clone()),
+            EnumPayload::Unit | EnumPayload::Struct(_) => Value::Null,
+        }
+    }
+
+    fn field(&self, name: &str) -> Option<&Value> {
+        match &self.payload {
+            EnumPayload::Struct(fields) => fields.iter().find(|(k, _)| k == name).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
 /// Function object (for closures)
 #[derive(Debug, Clone)]
 pub struct FunctionObj {
@@ -238,6 +281,87 @@ pub struct FunctionObj {
 pub struct TraitDef {
     pub name: String,
     pub methods: Vec<(String, FunctionObj)>,
+    pub required: Vec<String>,
+}
+
+/// Traits the interpreter itself consults, with the methods an impl must provide:
+/// `Display` for printing and string conversion, the rest for operators
+const BUILTIN_TRAITS: &[(&str, &[&str])] = &[
+    ("Display", &["to_string"]),
+    ("Add", &["add"]),
+    ("Sub", &["sub"]),
+    ("Mul", &["mul"]),
+    ("Div", &["div"]),
+    ("Rem", &["rem"]),
+    ("Neg", &["neg"]),
+    ("Eq", &["eq"]),
+    ("Ord", &["cmp"]),
+];
+
+/// Every name `call_builtin` handles, sorted; extracted from its match arms by `build.rs`
+pub const BUILTIN_NAMES: &[&str] = include!(concat!(env!("OUT_DIR"), "/builtin_names.rs"));
+
+/// Whether `name` is a builtin function
+pub fn is_builtin(name: &str) -> bool {
+    BUILTIN_NAMES.binary_search(&name).is_ok()
+}
+
+/// Nested calls allowed unless `--max-depth` or `knull.toml` says otherwise: 10,000,
+/// or as many as the stack holds when frames are larger (unoptimized builds)
+pub const DEFAULT_MAX_DEPTH: usize = {
+    let fits = (STACK_SIZE - STACK_RESERVE) / CALL_STACK;
+    if fits < 10_000 { fits } else { 10_000 }
+};
+
+/// Native stack of the thread programs run on. Pages are only committed as deep
+/// recursion reaches them.
+const STACK_SIZE: usize = 1 << 30;
+
+/// Stack kept free below the last call allowed in: enough for a builtin that
+/// calls back into Knull code to run to its next call
+const STACK_RESERVE: usize = 64 << 20;
+
+/// Native stack one Knull call can take at worst, a call made from deep inside a
+/// nested expression. Debug builds keep every local in its own slot, so
+/// `evaluate` alone is near 30 KB there.
+#[cfg(debug_assertions)]
+const CALL_STACK: usize = 512 << 10;
+#[cfg(not(debug_assertions))]
+const CALL_STACK: usize = 64 << 10;
+
+thread_local! {
+    // Lowest stack address a new call may start below; 0 when unknown
+    static STACK_FLOOR: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Approximate current stack pointer
+#[inline(never)]
+fn stack_address() -> usize {
+    let marker = 0u8;
+    std::hint::black_box(&marker) as *const u8 as usize
+}
+
+/// Run `f` on a thread with a large stack, turning deep recursion into a
+/// `RecursionError` long before the native stack overflows
+pub fn with_large_stack<T: Send>(f: impl FnOnce() -> T + Send) -> T {
+    thread::scope(|scope| {
+        let handle = thread::Builder::new()
+            .name("knull".to_string())
+            .stack_size(STACK_SIZE)
+            .spawn_scoped(scope, || {
+                STACK_FLOOR.with(|floor| floor.set(stack_address() - (STACK_SIZE - STACK_RESERVE)));
+                f()
+            })
+            .expect("failed to spawn the interpreter thread");
+        handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    })
+}
+
+/// CLOCK_MONOTONIC in nanoseconds, as the `monotonic_ns` builtin reports it
+pub fn monotonic_ns() -> i64 {
+    let mut ts = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut ts); }
+    ts.tv_sec * 1_000_000_000 + ts.tv_nsec
 }
 
 /// Runtime value
@@ -258,9 +382,13 @@ pub enum Value {
     },
     StructDef(Box<StructDef>),
     StructInstance(Box<StructInstance>),
+    EnumDef(Box<EnumDef>),
+    Enum(Box<EnumValue>),
     Function(Box<FunctionObj>),
     Trait(Box<TraitDef>),
     Reference(Box<Value>), // For Rc, Arc simulation
+    // Namespace bound by `import "..." as name`
+    Module { id: usize, name: String },
     Null,
 }
 
@@ -275,6 +403,7 @@ impl PartialEq for Value {
             (Value::Null, Value::Null) => true,
             (Value::Range { start: a, end: b, inclusive: c }, Value::Range { start: d, end: e, inclusive: f }) => a == d && b == e && c == f,
             (Value::Tuple(a), Value::Tuple(b)) => a == b,
+            (Value::Enum(a), Value::Enum(b)) => a == b,
             // Maps: compare key-value pairs
             (Value::Map(a), Value::Map(b)) => {
                 if a.len() != b.len() { return false; }
@@ -310,6 +439,9 @@ impl std::fmt::Display for Value {
                 if *inclusive { write!(f, "{}..={}", start, end) }
                 else { write!(f, "{}..{}", start, end) }
             }
+            Value::Map(map) if matches!(map.get("__type"), Some(Value::String(t)) if t == "Error") => {
+                write!(f, "{}", map.get("message").cloned().unwrap_or(Value::Null))
+            }
             Value::Map(map) => {
                 write!(f, "{{")?;
                 let mut first = true;
@@ -340,16 +472,40 @@ impl std::fmt::Display for Value {
                 }
                 write!(f, "}}")
             }
+            Value::EnumDef(def) => write!(f, "<enum {}>", def.name),
+            Value::Enum(e) => {
+                write!(f, "{}::{}", e.enum_name, e.variant)?;
+                match &e.payload {
+                    EnumPayload::Unit => Ok(()),
+                    EnumPayload::Tuple(vals) => {
+                        write!(f, "(")?;
+                        for (i, v) in vals.iter().enumerate() {
+                            if i > 0 { write!(f, ", ")?; }
+                            write!(f, "{}", v)?;
+                        }
+                        write!(f, ")")
+                    }
+                    EnumPayload::Struct(fields) => {
+                        write!(f, " {{")?;
+                        for (i, (k, v)) in fields.iter().enumerate() {
+                            if i > 0 { write!(f, ",")?; }
+                            write!(f, " {}: {}", k, v)?;
+                        }
+                        write!(f, " }}")
+                    }
+                }
+            }
             Value::Function(func) => write!(f, "<fn {}>", func.name),
             Value::Trait(tr) => write!(f, "<trait {}>", tr.name),
             Value::Reference(val) => write!(f, "<ref {}>", val),
+            Value::Module { name, .. } => write!(f, "<module {}>", name),
             Value::Null => write!(f, "null"),
         }
     }
 }
 
 impl Value {
-    fn is_truthy(&self) -> bool {
+    pub(crate) fn is_truthy(&self) -> bool {
         match self {
             Value::Bool(b) => *b,
             Value::Int(i) => *i != 0,
@@ -360,37 +516,73 @@ impl Value {
             Value::Tuple(t) => !t.is_empty(),
             Value::Range { start, end, .. } => start != end,
             Value::StructInstance(_) => true,
+            Value::Enum(_) | Value::EnumDef(_) => true,
             Value::Function(_) => true,
             Value::Closure { .. } => true,
             Value::Reference(val) => val.is_truthy(),
+            Value::Module { .. } => true,
             Value::Null => false,
             _ => false,
         }
     }
 
+    /// The type name the REPL and debugger show; instances give their struct or enum's name
+    pub fn type_name(&self) -> String {
+        let name = match self {
+            Value::Int(_) => "int",
+            Value::Float(_) => "float",
+            Value::Bool(_) => "bool",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Range { .. } => "range",
+            Value::Map(_) => "map",
+            Value::Tuple(_) => "tuple",
+            Value::Closure { .. } | Value::Function(_) => "function",
+            Value::StructInstance(inst) => return inst.def.name.clone(),
+            Value::Enum(e) => return e.enum_name.clone(),
+            Value::StructDef(_) | Value::EnumDef(_) | Value::Trait(_) => "type",
+            Value::Reference(val) => return val.type_name(),
+            Value::Module { .. } => "module",
+            Value::Null => "null",
+        };
+        name.to_string()
+    }
+
     fn is_int(&self) -> bool { matches!(self, Value::Int(_)) }
     fn is_float(&self) -> bool { matches!(self, Value::Float(_)) }
 
     fn get_field(&self, field: &str) -> Option<Value> {
         match self {
             Value::StructInstance(inst) => inst.fields.get(field).cloned(),
+            Value::Enum(e) => e.field(field).cloned(),
+            Value::Map(m) => m.get(field).cloned(),
             Value::Reference(val) => val.get_field(field),
             _ => None,
         }
     }
 
-    fn set_field(&mut self, field: &str, value: Value) -> bool {
+    pub(crate) fn set_field(&mut self, field: &str, value: Value) -> bool {
         match self {
             Value::StructInstance(inst) => {
                 inst.fields.insert(field.to_string(), value);
                 true
             }
+            Value::Enum(e) => match &mut e.payload {
+                EnumPayload::Struct(fields) => match fields.iter_mut().find(|(k, _)| k == field) {
+                    Some((_, v)) => {
+                        *v = value;
+                        true
+                    }
+                    None => false,
+                },
+                _ => false,
+            },
             Value::Reference(val) => val.set_field(field, value),
             _ => false,
         }
     }
 
-    fn as_int(&self) -> i64 {
+    pub(crate) fn as_int(&self) -> i64 {
         match self {
             Value::Int(i) => *i,
             Value::Float(f) => *f as i64,
@@ -413,7 +605,7 @@ impl Value {
         }
     }
 
-    fn as_string(&self) -> String {
+    pub(crate) fn as_string(&self) -> String {
         match self {
             Value::String(s) => s.clone(),
             _ => self.to_string(),
@@ -421,16 +613,212 @@ impl Value {
     }
 }
 
+/// Name of the struct or enum a value is an instance of, for impl and trait lookup
+fn user_type_name(value: &Value) -> Option<&str> {
+    match value {
+        Value::StructInstance(inst) => Some(&inst.def.name),
+        Value::Enum(e) => Some(&e.enum_name),
+        _ => None,
+    }
+}
+
+/// Category of a runtime error, exposed to `catch` blocks as `e.kind`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    TypeError,
+    NameError,
+    IndexError,
+    ValueError,
+    ArithmeticError,
+    IOError,
+    AssertionError,
+    RecursionError,
+    UserThrow,
+    Error,
+}
+
+impl ErrorKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ErrorKind::TypeError => "TypeError",
+            ErrorKind::NameError => "NameError",
+            ErrorKind::IndexError => "IndexError",
+            ErrorKind::ValueError => "ValueError",
+            ErrorKind::ArithmeticError => "ArithmeticError",
+            ErrorKind::IOError => "IOError",
+            ErrorKind::AssertionError => "AssertionError",
+            ErrorKind::RecursionError => "RecursionError",
+            ErrorKind::UserThrow => "UserThrow",
+            ErrorKind::Error => "Error",
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A Knull function frame on the stack of an unwinding error
+#[derive(Debug, Clone)]
+pub struct StackFrame {
+    pub function: String,
+    /// Where inside `function` execution was when the error passed through
+    pub span: Option<Span>,
+}
+
+impl std::fmt::Display for StackFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.span {
+            Some(span) => write!(f, "{} ({})", self.function, span),
+            None => write!(f, "{}", self.function),
+        }
+    }
+}
+
+/// A Knull frame as an attached debugger sees it
+#[derive(Debug, Clone)]
+pub struct DebugFrame {
+    pub function: String,
+    /// The statement the frame is running
+    pub span: Option<Span>,
+    // Index of the frame's first scope in `Interpreter::scopes`
+    scope_base: usize,
+}
+
+/// Receives control from the interpreter while a debugger is attached. Both
+/// callbacks run on the interpreter's thread and may block to pause the
+/// program; an error they return aborts it.
+pub trait DebugHook {
+    /// Called before each statement runs
+    fn statement(&mut self, interp: &mut Interpreter, span: &Span) -> Result<(), RuntimeError>;
+    /// Called when a statement raises `error`; `caught` when a `try` will handle it
+    fn exception(&mut self, interp: &mut Interpreter, error: &RuntimeError, caught: bool) -> Result<(), RuntimeError>;
+}
+
+/// Details of a runtime error; reached through `RuntimeError`'s `Deref`
+#[derive(Debug, Clone)]
+pub struct ErrorInfo {
+    pub kind: ErrorKind,
+    pub message: String,
+    /// Innermost source location that raised the error
+    pub span: Option<Span>,
+    /// Knull call frames the error unwound through, innermost first
+    pub stack: Vec<StackFrame>,
+    /// The value given to `throw`, handed to `catch` unchanged
+    pub value: Option<Value>,
+    // Innermost location in the frame currently unwinding; moved into its StackFrame on exit
+    frame_span: Option<Span>,
+}
+
+/// Error raised while interpreting Knull code.
+/// Boxed so the `Result`s threaded through every `evaluate` call stay small.
+#[derive(Debug, Clone)]
+pub struct RuntimeError(Box<ErrorInfo>);
+
+impl std::ops::Deref for RuntimeError {
+    type Target = ErrorInfo;
+    fn deref(&self) -> &ErrorInfo {
+        &self.0
+    }
+}
+
+impl std::ops::DerefMut for RuntimeError {
+    fn deref_mut(&mut self) -> &mut ErrorInfo {
+        &mut self.0
+    }
+}
+
+impl RuntimeError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        RuntimeError(Box::new(ErrorInfo {
+            kind,
+            message: message.into(),
+            span: None,
+            stack: Vec::new(),
+            value: None,
+            frame_span: None,
+        }))
+    }
+
+    /// Error carrying a user value from `throw expr`
+    pub fn thrown(value: Value) -> Self {
+        let mut err = RuntimeError::new(ErrorKind::UserThrow, value.as_string());
+        err.value = Some(value);
+        err
+    }
+
+    /// Record `span` as the error location unless an inner node already did
+    pub(crate) fn at(mut self, span: &Span) -> Self {
+        if self.span.is_none() {
+            self.span = Some(span.clone());
+        }
+        if self.frame_span.is_none() {
+            self.frame_span = Some(span.clone());
+        }
+        self
+    }
+
+    /// Push the frame of `function` as the error leaves it
+    pub(crate) fn in_frame(mut self, function: &str) -> Self {
+        let span = self.frame_span.take();
+        self.stack.push(StackFrame {
+            function: function.to_string(),
+            span,
+        });
+        self
+    }
+
+    /// The value bound by `catch`: the thrown value itself, or an error map
+    pub fn to_value(&self) -> Value {
+        if let Some(v) = &self.value {
+            return v.clone();
+        }
+        let mut m = HashMap::new();
+        m.insert("__type".to_string(), Value::String("Error".to_string()));
+        m.insert("kind".to_string(), Value::String(self.kind.name().to_string()));
+        m.insert("message".to_string(), Value::String(self.message.clone()));
+        m.insert(
+            "stack".to_string(),
+            Value::Array(self.stack.iter().map(|f| Value::String(f.to_string())).collect()),
+        );
+        Value::Map(m)
+    }
+}
+
+/// An error of `kind` raised by a builtin
+fn raise(kind: ErrorKind, message: impl Into<String>) -> RuntimeError {
+    RuntimeError::new(kind, message)
+}
+
+impl std::fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+
+
+impl From<RuntimeError> for String {
+    fn from(err: RuntimeError) -> Self {
+        err.0.message
+    }
+}
+
 /// Variable scope
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Scope {
     variables: HashMap<String, Value>,
+    // `defer` bodies registered in this scope, run in reverse when it is popped
+    deferred: Vec<ASTNode>,
 }
 
 impl Scope {
     fn new() -> Self {
         Scope {
             variables: HashMap::new(),
+            deferred: Vec::new(),
         }
     }
 
@@ -443,6 +831,18 @@ impl Scope {
     }
 }
 
+/// A storage location with its indices already evaluated: a variable, then the
+/// indices and fields leading into it
+struct Place {
+    root: String,
+    path: Vec<PlaceStep>,
+}
+
+enum PlaceStep {
+    Index(Value),
+    Field(String),
+}
+
 /// Interpreter state
 pub struct Interpreter {
     scopes: Vec<Scope>,
@@ -507,13 +907,74 @@ pub struct Interpreter {
     hbs: handlebars::Handlebars<'static>,
     // sysinfo System
     sysinfo_sys: Option<sysinfo::System>,
+    // Source file being run; imports resolve relative to it
+    file: Option<std::path::PathBuf>,
+    // Project the file belongs to; its dependencies are visible to the modules it imports
+    project: Option<std::path::PathBuf>,
+    // Imported modules, indexed by `FunctionDef::module`, and their cache keys
+    modules: Vec<Module>,
+    module_ids: HashMap<std::path::PathBuf, usize>,
+    // Module whose code is executing, if not the main program
+    current_module: Option<usize>,
+    // Declared traits, and the (type, trait) pairs with an `impl Trait for Type`
+    traits: HashMap<String, TraitDef>,
+    trait_impls: std::collections::HashSet<(String, String)>,
+    // Function and closure frames currently open, and how many may be
+    call_depth: usize,
+    max_depth: usize,
+    // Attached debugger and the frames it can inspect, outermost first
+    debug_hook: Option<Box<dyn DebugHook>>,
+    debug_frames: Vec<DebugFrame>,
+    // Open `try` bodies, and whether the error now unwinding was already reported
+    try_depth: usize,
+    error_traced: bool,
 }
 
 #[derive(Debug, Clone)]
-struct FunctionDef {
+pub(crate) struct FunctionDef {
     name: String,
     params: Vec<String>,
     body: ASTNode,
+    // Module the function was defined in; its helpers and globals resolve there first
+    module: Option<usize>,
+    // Parameters annotated with a named type, checked at call time when the name is a trait
+    pub(crate) bounds: Vec<(usize, String)>,
+}
+
+impl FunctionDef {
+    pub(crate) fn new(name: &str, params: &[Param], body: &ASTNode, module: Option<usize>) -> Self {
+        FunctionDef {
+            name: name.to_string(),
+            params: params.iter().map(|p| p.name.clone()).collect(),
+            body: body.clone(),
+            module,
+            bounds: params
+                .iter()
+                .enumerate()
+                .filter_map(|(i, p)| match &p.ty {
+                    Some(Type::Custom(ty)) => Some((i, ty.clone())),
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A file loaded through `import`, evaluated once and cached by path
+#[derive(Debug, Clone)]
+struct Module {
+    name: String,
+    path: std::path::PathBuf,
+    functions: HashMap<String, FunctionDef>,
+    globals: HashMap<String, Value>,
+    // Names declared `pub`; `None` when the file marks nothing `pub` and exports everything
+    exports: Option<std::collections::HashSet<String>>,
+}
+
+impl Module {
+    fn exports(&self, name: &str) -> bool {
+        self.exports.as_ref().is_none_or(|e| e.contains(name))
+    }
 }
 
 impl Interpreter {
@@ -563,9 +1024,44 @@ impl Interpreter {
             graph_counter: 0,
             hbs: handlebars::Handlebars::new(),
             sysinfo_sys: None,
+            file: None,
+            project: None,
+            modules: Vec::new(),
+            module_ids: HashMap::new(),
+            current_module: None,
+            traits: HashMap::new(),
+            trait_impls: std::collections::HashSet::new(),
+            call_depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+            debug_hook: None,
+            debug_frames: Vec::new(),
+            try_depth: 0,
+            error_traced: false,
         }
     }
 
+    /// Set the path of the program being run, used to resolve its imports
+    pub fn with_file(mut self, path: &std::path::Path) -> Self {
+        let dir = path.parent().map(|dir| if dir.as_os_str().is_empty() { std::path::Path::new(".") } else { dir });
+        let dir = dir.and_then(|dir| dir.canonicalize().ok());
+        self.project = dir.and_then(|dir| crate::modules::project_root(&dir));
+        self.file = Some(path.to_path_buf());
+        self
+    }
+
+    /// Limit how deeply function and closure calls may nest before a `RecursionError`
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Attach a debugger; it is called before every statement from now on
+    pub fn with_debug_hook(mut self, hook: Box<dyn DebugHook>) -> Self {
+        self.debug_hook = Some(hook);
+        self.debug_frames = vec![DebugFrame { function: "<program>".to_string(), span: None, scope_base: 1 }];
+        self
+    }
+
     fn current_scope(&mut self) -> &mut Scope {
         self.scopes.last_mut().unwrap()
     }
@@ -574,85 +1070,236 @@ impl Interpreter {
         self.scopes.push(Scope::new());
     }
 
-    fn pop_scope(&mut self) {
+    /// Pop the innermost scope after running its deferred expressions
+    fn pop_scope(&mut self) -> Result<(), RuntimeError> {
         if self.scopes.len() > 1 {
+            let deferred = std::mem::take(&mut self.current_scope().deferred);
+            let result = self.run_deferred(deferred);
             self.scopes.pop();
+            result
+        } else {
+            Ok(())
         }
     }
 
+    /// Pop every scope above `depth` while an error unwinds. Deferred expressions
+    /// still run; their own errors are dropped in favour of the one unwinding.
+    pub(crate) fn unwind_scopes(&mut self, depth: usize) {
+        while self.scopes.len() > depth.max(1) {
+            let _ = self.pop_scope();
+        }
+    }
+
+    /// Run deferred expressions last-registered first. Pending `return`/`break`/`continue`
+    /// state is set aside so it neither short-circuits them nor gets clobbered by them.
+    fn run_deferred(&mut self, deferred: Vec<ASTNode>) -> Result<(), RuntimeError> {
+        if deferred.is_empty() {
+            return Ok(());
+        }
+        let return_value = self.return_value.take();
+        let break_flag = std::mem::replace(&mut self.break_flag, false);
+        let continue_flag = std::mem::replace(&mut self.continue_flag, false);
+        let mut result = Ok(());
+        for node in deferred.iter().rev() {
+            if let Err(e) = self.evaluate(node) {
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+            self.return_value = None;
+            self.break_flag = false;
+            self.continue_flag = false;
+        }
+        self.return_value = return_value;
+        self.break_flag = break_flag;
+        self.continue_flag = continue_flag;
+        result
+    }
+
+    /// Count a function or closure frame about to open, refusing it past
+    /// `max_depth` or when the native stack is close to running out
+    pub(crate) fn enter_call(&mut self) -> Result<(), RuntimeError> {
+        if self.call_depth >= self.max_depth {
+            return Err(RuntimeError::new(
+                ErrorKind::RecursionError,
+                format!("maximum recursion depth exceeded ({} calls)", self.max_depth),
+            ));
+        }
+        let floor = STACK_FLOOR.with(Cell::get);
+        if floor != 0 && stack_address() < floor {
+            return Err(RuntimeError::new(
+                ErrorKind::RecursionError,
+                format!("stack overflow after {} nested calls", self.call_depth),
+            ));
+        }
+        self.call_depth += 1;
+        Ok(())
+    }
+
+    pub(crate) fn leave_call(&mut self) {
+        self.call_depth = self.call_depth.saturating_sub(1);
+    }
+
+    /// Show a debugger the frame of `function`, whose scope is pushed at `depth`
+    fn open_frame(&mut self, function: &str, depth: usize) {
+        if self.debug_hook.is_some() {
+            self.debug_frames.push(DebugFrame { function: function.to_string(), span: None, scope_base: depth });
+        }
+    }
+
+    /// Leave a function frame whose scope was pushed at `depth`
+    fn exit_frame(&mut self, depth: usize, function: &str, result: Result<Value, RuntimeError>) -> Result<Value, RuntimeError> {
+        if self.debug_hook.is_some() {
+            self.debug_frames.pop();
+        }
+        self.leave_call();
+        let result = match result {
+            Ok(v) => self.pop_scope().map(|_| v),
+            Err(e) => {
+                self.unwind_scopes(depth);
+                Err(e)
+            }
+        };
+        result.map_err(|e| e.in_frame(function))
+    }
+
     fn get_variable(&self, name: &str) -> Option<Value> {
-        for scope in self.scopes.iter().rev() {
+        for scope in self.scopes[1..].iter().rev() {
             if let Some(value) = scope.get(name) {
                 return Some(value);
             }
         }
-        None
+        // Module code sees its own globals before the program's
+        if let Some(m) = self.current_module {
+            if let Some(value) = self.modules[m].globals.get(name) {
+                return Some(value.clone());
+            }
+        }
+        self.scopes[0].get(name)
     }
 
     fn bind_parameter(&mut self, name: String, value: Value) {
         self.current_scope().set(name, value);
     }
 
-    fn set_variable(&mut self, name: String, value: Value) {
+    pub(crate) fn set_variable(&mut self, name: String, value: Value) {
         // First, check if the variable exists in any scope
-        for scope in self.scopes.iter_mut().rev() {
+        for scope in self.scopes[1..].iter_mut().rev() {
             if scope.variables.contains_key(&name) {
                 scope.set(name, value);
                 return;
             }
         }
+        if let Some(m) = self.current_module {
+            if let Some(slot) = self.modules[m].globals.get_mut(&name) {
+                *slot = value;
+                return;
+            }
+        }
+        if self.scopes[0].variables.contains_key(&name) {
+            self.scopes[0].set(name, value);
+            return;
+        }
         // If not found, create in current scope
         self.current_scope().set(name, value);
     }
 
     /// Assign to a target (identifier, index, field)
-    fn assign_target(&mut self, target: &ASTNode, value: Value) -> Result<(), String> {
-        match target {
+    fn assign_target(&mut self, target: &ASTNode, value: Value) -> Result<(), RuntimeError> {
+        match target.inner() {
             ASTNode::Identifier(name) => {
                 // update existing or create in current scope
                 self.set_variable(name.clone(), value);
                 Ok(())
             }
+            ASTNode::Index { obj, .. } | ASTNode::FieldAccess { obj, .. } => {
+                if !Self::is_place(obj) {
+                    return Err(match target.inner() {
+                        ASTNode::Index { .. } => RuntimeError::new(ErrorKind::Error, "Complex index assignment not supported"),
+                        _ => RuntimeError::new(ErrorKind::Error, "Complex field assignment not supported"),
+                    });
+                }
+                let place = self.resolve_place(target)?;
+                self.write_place(&place, value)
+            }
+            _ => Err(raise(ErrorKind::ValueError, "Invalid assignment target")),
+        }
+    }
+
+    /// `target op= rhs`, running the target's indices once
+    fn assign_op(&mut self, target: &ASTNode, op: &str, rhs: Value) -> Result<Value, RuntimeError> {
+        if !Self::is_place(target) {
+            return Err(raise(ErrorKind::ValueError, "Invalid assignment target"));
+        }
+        let place = self.resolve_place(target)?;
+        let lhs = self.read_place(&place)?;
+        let result = self.binary_op(op, lhs, rhs)?;
+        self.write_place(&place, result.clone())?;
+        Ok(result)
+    }
+
+    /// Evaluate the indices of a place expression, outermost container first
+    fn resolve_place(&mut self, node: &ASTNode) -> Result<Place, RuntimeError> {
+        match node.inner() {
+            ASTNode::Identifier(name) => Ok(Place { root: name.clone(), path: Vec::new() }),
             ASTNode::Index { obj, index } => {
-                let idx_val = self.evaluate(index)?;
-                // Walk the object to the deepest identifier, then update
-                let root_name = Self::extract_root_name(obj)
-                    .ok_or_else(|| "Complex index assignment not supported".to_string())?;
-                let mut root_val = self.get_variable(&root_name)
-                    .ok_or_else(|| format!("Undefined variable: {}", root_name))?;
-                Self::apply_index_assign(&mut root_val, obj, &root_name, idx_val, value)?;
-                self.set_variable(root_name, root_val);
-                Ok(())
+                let mut place = self.resolve_place(obj)?;
+                place.path.push(PlaceStep::Index(self.evaluate(index)?));
+                Ok(place)
             }
             ASTNode::FieldAccess { obj, field } => {
-                // Support both struct instances and maps
-                let root_name = Self::extract_root_name(obj)
-                    .ok_or_else(|| "Complex field assignment not supported".to_string())?;
-                let mut root_val = self.get_variable(&root_name)
-                    .ok_or_else(|| format!("Undefined variable: {}", root_name))?;
-                match &mut root_val {
-                    Value::Map(ref mut m) => { m.insert(field.clone(), value); }
-                    ref mut other => { other.set_field(field, value); }
-                }
-                self.set_variable(root_name, root_val);
-                Ok(())
+                let mut place = self.resolve_place(obj)?;
+                place.path.push(PlaceStep::Field(field.clone()));
+                Ok(place)
             }
-            _ => Err("Invalid assignment target".to_string()),
+            _ => Err(raise(ErrorKind::ValueError, "Invalid assignment target")),
         }
     }
 
-    /// Extract the root variable name from a potentially nested index/field expression
-    fn extract_root_name(node: &ASTNode) -> Option<String> {
-        match node {
-            ASTNode::Identifier(name) => Some(name.clone()),
-            ASTNode::Index { obj, .. } => Self::extract_root_name(obj),
-            ASTNode::FieldAccess { obj, .. } => Self::extract_root_name(obj),
-            _ => None,
+    fn read_place(&mut self, place: &Place) -> Result<Value, RuntimeError> {
+        let mut value = self.lookup(&place.root)?;
+        for step in &place.path {
+            value = match step {
+                PlaceStep::Index(idx) => Self::index_value(&value, idx)?,
+                PlaceStep::Field(field) => self.field_of(value, field)?,
+            };
+        }
+        Ok(value)
+    }
+
+    /// Store `value` in a place, then each updated container in the one holding it
+    fn write_place(&mut self, place: &Place, value: Value) -> Result<(), RuntimeError> {
+        let Some((_, outer)) = place.path.split_last() else {
+            self.set_variable(place.root.clone(), value);
+            return Ok(());
+        };
+        let mut containers = vec![self.lookup(&place.root)?];
+        for step in outer {
+            let container = containers.last().cloned().unwrap_or(Value::Null);
+            containers.push(match step {
+                PlaceStep::Index(idx) => Self::index_value(&container, idx)?,
+                PlaceStep::Field(field) => self.field_of(container, field)?,
+            });
+        }
+        let mut value = value;
+        for (step, mut container) in place.path.iter().zip(containers).rev() {
+            match (step, &mut container) {
+                (PlaceStep::Index(idx), container) => Self::apply_index_assign(container, idx.clone(), value)?,
+                (PlaceStep::Field(field), Value::Map(m)) => {
+                    m.insert(field.clone(), value);
+                }
+                (PlaceStep::Field(field), other) => {
+                    other.set_field(field, value);
+                }
+            }
+            value = container;
         }
+        self.set_variable(place.root.clone(), value);
+        Ok(())
     }
 
-    /// Apply a nested index assignment (e.g. a[0][1] = v, m["k"] = v)
-    fn apply_index_assign(root: &mut Value, _obj: &ASTNode, _root_name: &str, idx: Value, value: Value) -> Result<(), String> {
+    /// Store `value` at `idx` in an array or map
+    pub(crate) fn apply_index_assign(root: &mut Value, idx: Value, value: Value) -> Result<(), RuntimeError> {
         match root {
             Value::Array(arr) => {
                 let i = idx.as_int();
@@ -661,97 +1308,196 @@ impl Interpreter {
                     arr[i] = value;
                     Ok(())
                 } else {
-                    Err(format!("Index {} out of bounds (len={})", i, arr.len()))
+                    Err(RuntimeError::new(ErrorKind::IndexError, format!("Index {} out of bounds (len={})", i, arr.len())))
                 }
             }
             Value::Map(m) => {
                 m.insert(idx.as_string(), value);
                 Ok(())
             }
-            _ => Err("Cannot index-assign on non-array/map value".to_string()),
+            _ => Err(raise(ErrorKind::TypeError, "Cannot index-assign on non-array/map value")),
         }
     }
 
     /// Execute a program
-    pub fn execute(&mut self, ast: &ASTNode) -> Result<(), String> {
+    pub fn execute(&mut self, ast: &ASTNode) -> Result<(), RuntimeError> {
         match ast {
             ASTNode::Program(items) => {
-                // First pass: collect function and struct definitions
-                for item in items {
-                    match item {
-                        ASTNode::Function {
-                            name, params, body, ..
-                        } => {
-                            self.functions.insert(
-                                name.clone(),
-                                FunctionDef {
-                                    name: name.clone(),
-                                    params: params.iter().map(|p| p.name.clone()).collect(),
-                                    body: *body.clone(),
-                                },
-                            );
-                        }
-                        ASTNode::StructDef { name, fields } => {
-                            let struct_def = StructDef {
-                                name: name.clone(),
-                                fields: fields.clone(),
-                            };
-                            self.set_variable(name.clone(), Value::StructDef(Box::new(struct_def)));
-                        }
-                        ASTNode::Impl { ty, methods } => {
-                            // Collect impl methods
-                            for method in methods {
-                                if let ASTNode::Function {
-                                    name, params, body, ..
-                                } = method
-                                {
-                                    let method_name = format!("{}::{}", ty, name);
-                                    // Params already include self from parser
-                                    let method_params: Vec<String> =
-                                        params.iter().map(|p| p.name.clone()).collect();
-                                    self.functions.insert(
-                                        method_name,
-                                        FunctionDef {
-                                            name: name.clone(),
-                                            params: method_params,
-                                            body: *body.clone(),
-                                        },
-                                    );
-                                }
-                            }
-                        }
-                        _ => {}
-                    }
-                }
+                self.define_items(items)?;
+                let result = self.run_program(items);
+                self.finish_program(result)
+            }
+            _ => self.execute_node(ast),
+        }
+    }
 
-                // Second pass: execute non-definition statements
-                for item in items {
-                    match item {
-                        ASTNode::Function { .. }
-                        | ASTNode::StructDef { .. }
-                        | ASTNode::Impl { .. } => {}
-                        _ => self.execute_node(item)?,
-                    }
+    /// First pass over a program: register its traits, functions, structs, enums
+    /// and impls so code anywhere in the file can refer to them
+    pub(crate) fn define_items(&mut self, items: &[ASTNode]) -> Result<(), RuntimeError> {
+        self.collect_traits(items);
+        for item in items {
+            match item.inner() {
+                ASTNode::Function {
+                    name, params, body, ..
+                } => {
+                    self.functions.insert(
+                        name.clone(),
+                        FunctionDef::new(name, params, body, self.current_module),
+                    );
                 }
-
-                // Call main if it exists
-                if self.functions.contains_key("main") {
-                    self.call_function("main", vec![])?;
+                ASTNode::StructDef { name, fields } => {
+                    let struct_def = StructDef {
+                        name: name.clone(),
+                        fields: fields.clone(),
+                    };
+                    self.set_variable(name.clone(), Value::StructDef(Box::new(struct_def)));
+                }
+                ASTNode::EnumDef { name, variants } => {
+                    self.define_enum(name, variants);
                 }
+                ASTNode::Impl { ty, trait_name, methods } => {
+                    self.register_impl(ty, trait_name.as_deref(), methods, self.current_module)
+                        .map_err(|e| match item.span() {
+                            Some(span) => e.at(span),
+                            None => e,
+                        })?;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
 
-                Ok(())
+    /// Wrap up a program run: clear what an error left behind, then run the
+    /// top-level defers once everything (including main) is done
+    pub(crate) fn finish_program(&mut self, result: Result<(), RuntimeError>) -> Result<(), RuntimeError> {
+        if result.is_err() {
+            self.unwind_scopes(1);
+        }
+        let deferred = std::mem::take(&mut self.scopes[0].deferred);
+        let deferred_result = self.run_deferred(deferred);
+        result.and(deferred_result)
+    }
+
+    /// Run one test of a program in this interpreter: the program's top-level
+    /// statements run first, as setup, but `main` and the other tests do not
+    pub fn run_test(&mut self, ast: &ASTNode, test: &str) -> Result<(), RuntimeError> {
+        self.with_test(ast, test, |interp, node| interp.call_test(node, Vec::new()).map(|_| ()))
+    }
+
+    /// Set a program up as `run_test` does and hand its `ASTNode::Test` named
+    /// `test` to `run`, which can call it any number of times with `call_test`
+    pub fn with_test<T>(
+        &mut self,
+        ast: &ASTNode,
+        test: &str,
+        run: impl FnOnce(&mut Self, &ASTNode) -> Result<T, RuntimeError>,
+    ) -> Result<T, RuntimeError> {
+        let items = match ast {
+            ASTNode::Program(items) => items.as_slice(),
+            other => std::slice::from_ref(other),
+        };
+        let node = items
+            .iter()
+            .map(ASTNode::inner)
+            .find(|item| matches!(item, ASTNode::Test { name, .. } if name == test))
+            .ok_or_else(|| RuntimeError::new(ErrorKind::NameError, format!("No test named '{}'", test)))?;
+        self.define_items(items)?;
+        let (result, value) = match self.run_statements(items).and_then(|_| run(self, node)) {
+            Ok(value) => (Ok(()), Some(value)),
+            Err(e) => (Err(e), None),
+        };
+        self.finish_program(result)?;
+        Ok(value.expect("test finished without an error"))
+    }
+
+    /// Run a test once in a frame of its own, its params bound to `args`
+    pub fn call_test(&mut self, test: &ASTNode, args: Vec<Value>) -> Result<Value, RuntimeError> {
+        let ASTNode::Test { name, params, body, .. } = test.inner() else {
+            return Err(RuntimeError::new(ErrorKind::Error, "call_test needs a test"));
+        };
+        self.enter_call()?;
+        let depth = self.scopes.len();
+        let frame = format!("test {}", name);
+        self.open_frame(&frame, depth);
+        self.push_scope();
+        for (param, arg) in params.iter().zip(args) {
+            self.bind_parameter(param.name.clone(), arg);
+        }
+        let result = self.call_body(body);
+        self.exit_frame(depth, &frame, result)
+    }
+
+    /// Second pass over a program: execute non-definition statements, then call main
+    fn run_program(&mut self, items: &[ASTNode]) -> Result<(), RuntimeError> {
+        self.run_statements(items)?;
+
+        // Call main if it exists
+        if self.functions.contains_key("main") {
+            self.call_function("main", vec![])?;
+        }
+
+        Ok(())
+    }
+
+    fn run_statements(&mut self, items: &[ASTNode]) -> Result<(), RuntimeError> {
+        for item in items {
+            match item.inner() {
+                ASTNode::Function { .. }
+                | ASTNode::StructDef { .. }
+                | ASTNode::EnumDef { .. }
+                | ASTNode::Impl { .. }
+                | ASTNode::Test { .. } => {}
+                _ => self.traced(item, |interp| interp.execute_node(item))?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Run the statement `node` with `run`, letting an attached debugger see it
+    /// first and see the error if it raises one
+    fn traced<T>(&mut self, node: &ASTNode, run: impl FnOnce(&mut Self) -> Result<T, RuntimeError>) -> Result<T, RuntimeError> {
+        let Some(span) = self.debug_hook.as_ref().and(node.span()) else {
+            return run(self);
+        };
+        if let Some(frame) = self.debug_frames.last_mut() {
+            frame.span = Some(span.clone());
+        }
+        self.error_traced = false;
+        self.with_hook(|hook, interp| hook.statement(interp, span))?;
+        let result = run(self);
+        if let Err(e) = &result {
+            // Only the innermost statement reports it, while the raising frame is intact
+            if !self.error_traced {
+                self.error_traced = true;
+                let caught = self.try_depth > 0;
+                self.with_hook(|hook, interp| hook.exception(interp, e, caught))?;
             }
-            _ => self.execute_node(ast),
         }
+        result
+    }
+
+    /// Call the attached debugger, detached meanwhile so code it evaluates is not traced
+    fn with_hook(
+        &mut self,
+        call: impl FnOnce(&mut dyn DebugHook, &mut Self) -> Result<(), RuntimeError>,
+    ) -> Result<(), RuntimeError> {
+        let Some(mut hook) = self.debug_hook.take() else {
+            return Ok(());
+        };
+        let result = call(hook.as_mut(), self);
+        self.debug_hook = Some(hook);
+        result
     }
 
     /// Execute a single AST node
-    fn execute_node(&mut self, node: &ASTNode) -> Result<(), String> {
+    pub(crate) fn execute_node(&mut self, node: &ASTNode) -> Result<(), RuntimeError> {
         if self.return_value.is_some() || self.break_flag || self.continue_flag {
             return Ok(());
         }
 
         match node {
+            ASTNode::Spanned { span, node } => self.execute_node(node).map_err(|e| e.at(span)),
             ASTNode::Let { name, value, .. } => {
                 let val = self.evaluate(value)?;
                 // `let` always creates in current scope
@@ -765,9 +1511,7 @@ impl Interpreter {
             }
             ASTNode::AssignOp { target, op, value } => {
                 let rhs = self.evaluate(value)?;
-                let lhs = self.evaluate(target)?;
-                let result = self.eval_binary_op(op, lhs, rhs)?;
-                self.assign_target(target, result)?;
+                self.assign_op(target, op, rhs)?;
                 Ok(())
             }
             ASTNode::Break(_) => {
@@ -787,8 +1531,8 @@ impl Interpreter {
                 }
                 Ok(())
             }
-            ASTNode::Function { .. } => {
-                // Already handled in first pass
+            ASTNode::Function { .. } | ASTNode::Test { .. } => {
+                // Already handled in first pass; tests only run under `knull test`
                 Ok(())
             }
             ASTNode::Return(expr) => {
@@ -840,13 +1584,13 @@ impl Interpreter {
                         while i < limit { v.push(Value::Int(i)); i += 1; }
                         v
                     }
-                    _ => return Err(format!("Cannot iterate over {:?}", iter_val)),
+                    _ => return Err(raise(ErrorKind::TypeError, format!("Cannot iterate over {:?}", iter_val))),
                 };
                 for val in items {
                     self.push_scope();
                     self.bind_parameter(var.clone(), val);
                     self.execute_node(body)?;
-                    self.pop_scope();
+                    self.pop_scope()?;
                     if self.return_value.is_some() { break; }
                     if self.break_flag { self.break_flag = false; break; }
                     if self.continue_flag { self.continue_flag = false; }
@@ -856,16 +1600,16 @@ impl Interpreter {
             ASTNode::Block(nodes) => {
                 self.push_scope();
                 for node in nodes {
-                    self.execute_node(node)?;
+                    self.traced(node, |interp| interp.execute_node(node))?;
                     if self.return_value.is_some() || self.break_flag || self.continue_flag {
                         break;
                     }
                 }
-                self.pop_scope();
+                self.pop_scope()?;
                 Ok(())
             }
             ASTNode::Call { func, args } => {
-                let arg_values: Result<Vec<Value>, String> =
+                let arg_values: Result<Vec<Value>, RuntimeError> =
                     args.iter().map(|arg| self.evaluate(arg)).collect();
                 let arg_values = arg_values?;
                 match func.as_ref() {
@@ -888,24 +1632,17 @@ impl Interpreter {
                 self.set_variable(name.clone(), Value::StructDef(Box::new(struct_def)));
                 Ok(())
             }
-            ASTNode::Impl { ty, methods } => {
-                // Store methods for this type
-                for method in methods {
-                    if let ASTNode::Function {
-                        name, params, body, ..
-                    } = method
-                    {
-                        let method_name = format!("{}::{}", ty, name);
-                        self.functions.insert(
-                            method_name,
-                            FunctionDef {
-                                name: name.clone(),
-                                params: params.iter().map(|p| p.name.clone()).collect(),
-                                body: *body.clone(),
-                            },
-                        );
-                    }
-                }
+            ASTNode::EnumDef { name, variants } => {
+                self.define_enum(name, variants);
+                Ok(())
+            }
+            ASTNode::Impl { ty, trait_name, methods } => {
+                self.register_impl(ty, trait_name.as_deref(), methods, self.current_module)
+            }
+            ASTNode::TraitDef { name, required, methods } => {
+                self.define_trait(name, required, methods);
+                let def = self.traits[name].clone();
+                self.current_scope().set(name.clone(), Value::Trait(Box::new(def)));
                 Ok(())
             }
             other => {
@@ -918,43 +1655,22 @@ impl Interpreter {
 
     /// Evaluate an expression
     #[allow(unreachable_patterns)]
-    pub fn evaluate(&mut self, node: &ASTNode) -> Result<Value, String> {
+    pub fn evaluate(&mut self, node: &ASTNode) -> Result<Value, RuntimeError> {
         match node {
+            ASTNode::Spanned { span, node } => self.evaluate(node).map_err(|e| e.at(span)),
             ASTNode::Literal(lit) => Ok(self.literal_to_value(lit)),
-            ASTNode::Identifier(name) => {
-                if let Some(v) = self.get_variable(name) {
-                    return Ok(v);
-                }
-                // Also resolve named functions as first-class values (FunctionObj)
-                if let Some(func_def) = self.functions.get(name).cloned() {
-                    return Ok(Value::Function(Box::new(FunctionObj {
-                        name: func_def.name.clone(),
-                        params: func_def.params.iter().map(|p| (p.clone(), None)).collect(),
-                        body: Box::new(func_def.body.clone()),
-                        closure: HashMap::new(),
-                    })));
-                }
-                Err(format!("Undefined variable: {}", name))
-            }
+            ASTNode::Identifier(name) => self.lookup(name),
             ASTNode::Binary { op, left, right } => {
                 let left_val = self.evaluate(left)?;
                 let right_val = self.evaluate(right)?;
-                self.eval_binary_op(op, left_val, right_val)
+                self.binary_op(op, left_val, right_val)
             }
             ASTNode::Unary { op, operand } => {
                 let val = self.evaluate(operand)?;
-                match op.as_str() {
-                    "!" | "not" => Ok(Value::Bool(!val.is_truthy())),
-                    "-" => match val {
-                        Value::Int(i) => Ok(Value::Int(-i)),
-                        Value::Float(f) => Ok(Value::Float(-f)),
-                        _ => Err("Cannot negate non-numeric value".to_string()),
-                    },
-                    _ => Err(format!("Unknown unary operator: {}", op)),
-                }
+                self.unary_op(op, val)
             }
             ASTNode::Call { func, args } => {
-                let arg_values: Result<Vec<Value>, String> =
+                let arg_values: Result<Vec<Value>, RuntimeError> =
                     args.iter().map(|arg| self.evaluate(arg)).collect();
                 let arg_values = arg_values?;
                 match func.as_ref() {
@@ -968,34 +1684,7 @@ impl Interpreter {
             ASTNode::Index { obj, index } => {
                 let obj_val = self.evaluate(obj)?;
                 let idx_val = self.evaluate(index)?;
-                match (obj_val, idx_val) {
-                    (Value::Array(arr), Value::Int(i)) => {
-                        let idx = if i < 0 { arr.len() as i64 + i } else { i } as usize;
-                        arr.get(idx)
-                            .cloned()
-                            .ok_or_else(|| "Index out of bounds".to_string())
-                    }
-                    (Value::Tuple(arr), Value::Int(i)) => {
-                        let idx = if i < 0 { arr.len() as i64 + i } else { i } as usize;
-                        arr.get(idx)
-                            .cloned()
-                            .ok_or_else(|| "Tuple index out of bounds".to_string())
-                    }
-                    (Value::String(s), Value::Int(i)) => {
-                        let idx = if i < 0 { s.len() as i64 + i } else { i } as usize;
-                        s.chars()
-                            .nth(idx)
-                            .map(|c| Value::String(c.to_string()))
-                            .ok_or_else(|| "Index out of bounds".to_string())
-                    }
-                    (Value::Map(m), Value::String(key)) => {
-                        Ok(m.get(&key).cloned().unwrap_or(Value::Null))
-                    }
-                    (Value::Map(m), idx) => {
-                        Ok(m.get(&idx.as_string()).cloned().unwrap_or(Value::Null))
-                    }
-                    _ => Err("Cannot index non-array/string value".to_string()),
-                }
+                Self::index_value(&obj_val, &idx_val)
             }
             ASTNode::Range { start, end, inclusive } => {
                 let s = self.evaluate(start)?.as_int();
@@ -1009,19 +1698,16 @@ impl Interpreter {
             }
             ASTNode::AssignOp { target, op, value } => {
                 let rhs = self.evaluate(value)?;
-                let lhs = self.evaluate(target)?;
-                let result = self.eval_binary_op(op, lhs, rhs)?;
-                self.assign_target(target, result.clone())?;
-                Ok(result)
+                self.assign_op(target, op, rhs)
             }
             ASTNode::Block(nodes) => {
                 self.push_scope();
                 let mut last = Value::Null;
                 for node in nodes {
-                    last = self.evaluate(node)?;
+                    last = self.traced(node, |interp| interp.evaluate(node))?;
                     if self.return_value.is_some() || self.break_flag || self.continue_flag { break; }
                 }
-                self.pop_scope();
+                self.pop_scope()?;
                 Ok(last)
             }
             ASTNode::Array(elements) => {
@@ -1052,80 +1738,34 @@ impl Interpreter {
             }
             ASTNode::AssignOp { target, op, value } => {
                 let rhs = self.evaluate(value)?;
-                let lhs = self.evaluate(target)?;
-                let result = self.eval_binary_op(op, lhs, rhs)?;
-                self.assign_target(target, result.clone())?;
-                Ok(result)
+                self.assign_op(target, op, rhs)
             }
             ASTNode::StructLiteral { name, fields } => {
-                let struct_def = self
-                    .get_variable(name)
-                    .ok_or_else(|| format!("Unknown struct: {}", name))?;
-                if let Value::StructDef(def) = struct_def {
-                    let mut field_values = HashMap::new();
-                    for (field_name, field_expr) in fields {
-                        let val = self.evaluate(field_expr)?;
-                        field_values.insert(field_name.clone(), val);
-                    }
-                    Ok(Value::StructInstance(Box::new(StructInstance {
-                        def,
-                        fields: field_values,
-                    })))
-                } else {
-                    Err(format!("{} is not a struct", name))
+                let mut values = Vec::new();
+                for (field_name, field_expr) in fields {
+                    values.push((field_name.clone(), self.evaluate(field_expr)?));
                 }
+                self.construct_struct(name, values)
             }
             ASTNode::MethodCall { obj, method, args } => {
-                let obj_val = self.evaluate(obj)?;
-                let arg_values: Result<Vec<Value>, String> =
+                // Changes the method makes to `self` land back in the receiver's place,
+                // resolved once so an index with side effects runs once
+                let place = if Self::is_place(obj) { Some(self.resolve_place(obj)?) } else { None };
+                let obj_val = match &place {
+                    Some(place) => self.read_place(place)?,
+                    None => self.evaluate(obj)?,
+                };
+                let arg_values: Result<Vec<Value>, RuntimeError> =
                     args.iter().map(|arg| self.evaluate(arg)).collect();
-                let arg_values = arg_values?;
-
-                // Dispatch on built-in types first
-                match &obj_val {
-                    // ── String methods ────────────────────────────────────────
-                    Value::String(s) => {
-                        let s = s.clone();
-                        return self.call_string_method(&s, method, &arg_values);
-                    }
-                    // ── Array methods ─────────────────────────────────────────
-                    Value::Array(arr) => {
-                        let arr = arr.clone();
-                        return self.call_array_method(arr, method, &arg_values);
-                    }
-                    // ── Map methods ───────────────────────────────────────────
-                    Value::Map(map) => {
-                        let map = map.clone();
-                        return self.call_map_method(map, method, &arg_values);
-                    }
-                    // ── Int methods ───────────────────────────────────────────
-                    Value::Int(n) => {
-                        let n = *n;
-                        return self.call_int_method(n, method, &arg_values);
-                    }
-                    // ── Float methods ─────────────────────────────────────────
-                    Value::Float(f) => {
-                        let f = *f;
-                        return self.call_float_method(f, method, &arg_values);
-                    }
-                    // ── Struct instance: call impl method ─────────────────────
-                    Value::StructInstance(inst) => {
-                        let type_name = inst.def.name.clone();
-                        let method_name = format!("{}::{}", type_name, method);
-                        let mut full_args = vec![obj_val.clone()];
-                        full_args.extend(arg_values);
-                        return self.call_function(&method_name, full_args);
-                    }
-                    _ => {}
+                let (result, receiver) = self.call_method_on(obj_val, method, arg_values?)?;
+                if let (Some(receiver), Some(place)) = (receiver, &place) {
+                    self.write_place(place, receiver)?;
                 }
-
-                Err(format!("No method '{}' on value {:?}", method, obj_val))
+                Ok(result)
             }
             ASTNode::FieldAccess { obj, field } => {
                 let obj_val = self.evaluate(obj)?;
-                obj_val
-                    .get_field(field)
-                    .ok_or_else(|| format!("Field {} not found", field))
+                self.field_of(obj_val, field)
             }
             // ── Interpolated string: f"Hello {name}!" ────────────────────────
             ASTNode::InterpolatedString(segments) => {
@@ -1133,11 +1773,11 @@ impl Interpreter {
                 for (is_expr, content) in segments {
                     if *is_expr {
                         // Re-parse and evaluate the expression
-                        let mut sub_parser = crate::parser::Parser::new(content);
+                        let mut sub_parser = crate::parser::Parser::new(content).without_spans();
                         match sub_parser.parse_expression_pub() {
                             Ok(sub_ast) => {
                                 let val = self.evaluate(&sub_ast)?;
-                                result.push_str(&val.as_string());
+                                result.push_str(&self.display(&val)?);
                             }
                             Err(_) => result.push_str(content),
                         }
@@ -1174,7 +1814,7 @@ impl Interpreter {
             }
             // ── Tuple ─────────────────────────────────────────────────────────
             ASTNode::Tuple(elems) => {
-                let vals: Result<Vec<Value>, String> =
+                let vals: Result<Vec<Value>, RuntimeError> =
                     elems.iter().map(|e| self.evaluate(e)).collect();
                 Ok(Value::Tuple(vals?))
             }
@@ -1182,15 +1822,15 @@ impl Interpreter {
             ASTNode::Pipeline { left, right } => {
                 let lv = self.evaluate(left)?;
                 // right should be a function call or identifier
-                match right.as_ref() {
+                match right.inner() {
                     ASTNode::Call { func, args } => {
                         let mut full_args = vec![lv];
-                        let more: Result<Vec<Value>, String> =
+                        let more: Result<Vec<Value>, RuntimeError> =
                             args.iter().map(|a| self.evaluate(a)).collect();
                         full_args.extend(more?);
-                        let func_name = match func.as_ref() {
+                        let func_name = match func.inner() {
                             ASTNode::Identifier(n) => n.clone(),
-                            _ => return Err("Pipeline: right side must be a function call".to_string()),
+                            _ => return Err(raise(ErrorKind::TypeError, "Pipeline: right side must be a function call")),
                         };
                         self.call_function(&func_name, full_args)
                     }
@@ -1200,21 +1840,8 @@ impl Interpreter {
                     _ => {
                         let rv = self.evaluate(right)?;
                         // If right is a closure, call it with left as arg
-                        if let Value::Closure { params, body, env } = rv {
-                            self.push_scope();
-                            for (k, v) in &env { self.current_scope().set(k.clone(), v.clone()); }
-                            if let Some(p) = params.first() {
-                                self.bind_parameter(p.clone(), lv);
-                            }
-                            let result = match body.as_ref() {
-                                ASTNode::Block(_) | ASTNode::Program(_) => {
-                                    self.execute_node(&body)?;
-                                    self.return_value.take().unwrap_or(Value::Null)
-                                }
-                                expr => self.evaluate(expr)?,
-                            };
-                            self.pop_scope();
-                            Ok(result)
+                        if let Value::Closure { .. } = rv {
+                            self.call_value(rv, vec![lv])
                         } else {
                             Ok(lv)
                         }
@@ -1233,7 +1860,7 @@ impl Interpreter {
                     let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
                         let mut interp = Interpreter::new();
                         for (name, params, body) in funcs_clone {
-                            interp.functions.insert(name.clone(), FunctionDef { name, params, body });
+                            interp.functions.insert(name.clone(), FunctionDef { name, params, body, module: None, bounds: Vec::new() });
                         }
                         let _ = interp.execute_node(&body_clone);
                         interp.return_value.take().unwrap_or(Value::Null)
@@ -1257,17 +1884,17 @@ impl Interpreter {
                             self.pattern_bind(&arm.pattern, &val);
                             let guard_val = self.evaluate(guard)?;
                             if !guard_val.is_truthy() {
-                                self.pop_scope();
+                                self.pop_scope()?;
                                 continue;
                             }
                             let result = self.evaluate(&arm.body)?;
-                            self.pop_scope();
+                            self.pop_scope()?;
                             return Ok(result);
                         }
                         self.push_scope();
                         self.pattern_bind(&arm.pattern, &val);
                         let result = self.evaluate(&arm.body)?;
-                        self.pop_scope();
+                        self.pop_scope()?;
                         return Ok(result);
                     }
                 }
@@ -1277,11 +1904,7 @@ impl Interpreter {
             ASTNode::AsyncFunction { name, params, body, .. } => {
                 self.functions.insert(
                     name.clone(),
-                    FunctionDef {
-                        name: name.clone(),
-                        params: params.iter().map(|p| p.name.clone()).collect(),
-                        body: *body.clone(),
-                    },
+                    FunctionDef::new(name, params, body, self.current_module),
                 );
                 Ok(Value::Null)
             }
@@ -1314,11 +1937,11 @@ impl Interpreter {
                         // Propagate the error
                         let msg = m.get("message").map(|t| t.as_string()).unwrap_or_else(|| "error".to_string());
                         self.return_value = Some(v.clone());
-                        Err(format!("PropagatedError: {}", msg))
+                        Err(raise(ErrorKind::Error, format!("PropagatedError: {}", msg)))
                     }
                     Value::Null => {
                         self.return_value = Some(Value::Null);
-                        Err("PropagatedError: null".to_string())
+                        Err(raise(ErrorKind::Error, "PropagatedError: null"))
                     }
                     _ => Ok(v),
                 }
@@ -1347,12 +1970,12 @@ impl Interpreter {
                     if let Some(f) = filter {
                         let cond = self.evaluate(f)?;
                         if !cond.is_truthy() {
-                            self.pop_scope();
+                            self.pop_scope()?;
                             continue;
                         }
                     }
                     let v = self.evaluate(expr)?;
-                    self.pop_scope();
+                    self.pop_scope()?;
                     result.push(v);
                 }
                 Ok(Value::Array(result))
@@ -1363,10 +1986,10 @@ impl Interpreter {
                     self.push_scope();
                     let _ = self.execute_node(body);
                     let break_flag = self.break_flag;
-                    if self.return_value.is_some() { self.pop_scope(); break; }
-                    if break_flag { self.break_flag = false; self.pop_scope(); break; }
+                    if self.return_value.is_some() { self.pop_scope()?; break; }
+                    if break_flag { self.break_flag = false; self.pop_scope()?; break; }
                     self.continue_flag = false;
-                    self.pop_scope();
+                    self.pop_scope()?;
                     let c = self.evaluate(cond)?;
                     if !c.is_truthy() { break; }
                 }
@@ -1380,7 +2003,7 @@ impl Interpreter {
                     self.push_scope();
                     self.set_variable(pattern.clone(), v);
                     let result = self.evaluate(then_body)?;
-                    self.pop_scope();
+                    self.pop_scope()?;
                     Ok(result)
                 } else if let Some(eb) = else_body {
                     self.evaluate(eb)
@@ -1394,55 +2017,42 @@ impl Interpreter {
                 self.set_variable(name.clone(), v.clone());
                 Ok(v)
             }
-            // ── Type alias (no runtime effect) ────────────────────────────────
-            ASTNode::TypeAlias { .. } => Ok(Value::Null),
-            // ── Defer (run at end of scope — simplified: run immediately) ─────
+            // ── Type alias and mode declaration (no runtime effect) ───────────
+            ASTNode::TypeAlias { .. } | ASTNode::Mode(_) => Ok(Value::Null),
+            // ── Defer: queue on the current scope, run when it exits ──────────
             ASTNode::Defer(expr) => {
-                // In a full impl, deferred calls run at scope exit.
-                // Here we store and run at end of current block execution.
-                // Simplified: just evaluate the expression now.
-                self.evaluate(expr)
+                self.current_scope().deferred.push(*expr.clone());
+                Ok(Value::Null)
             }
             // ── Named arg (unwrap value, ignore name) ─────────────────────────
             ASTNode::NamedArg { value, .. } => self.evaluate(value),
             // ── Use / import: load and execute another .knull file ─────────────
-            ASTNode::Use(path) => {
-                let p = path.trim_matches('"');
-                match std::fs::read_to_string(p) {
-                    Ok(src) => {
-                        let mut parser = crate::parser::Parser::new(&src);
-                        match parser.parse() {
-                            Ok(ast) => {
-                                // Execute but share the current scope/functions
-                                for item in if let ASTNode::Program(items) = &ast { items.as_slice() } else { std::slice::from_ref(&ast) } {
-                                    match item {
-                                        ASTNode::Function { name, params, body, .. } => {
-                                            self.functions.insert(name.clone(), FunctionDef {
-                                                name: name.clone(),
-                                                params: params.iter().map(|p| p.name.clone()).collect(),
-                                                body: *body.clone(),
-                                            });
-                                        }
-                                        _ => { self.execute_node(item)?; }
-                                    }
-                                }
-                                Ok(Value::Null)
-                            }
-                            Err(e) => Err(format!("Import parse error in {}: {}", p, e)),
-                        }
+            ASTNode::Use { path, alias } => {
+                let id = self.load_module(path)?;
+                match alias {
+                    Some(alias) => {
+                        let module = Value::Module { id, name: alias.clone() };
+                        self.current_scope().set(alias.clone(), module);
                     }
-                    Err(e) => Err(format!("Cannot import '{}': {}", p, e)),
+                    None => self.import_exports(id),
                 }
+                Ok(Value::Null)
             }
             // ── try { } catch var { } — error handling ─────────────────────────
             ASTNode::TryCatch { try_body, catch_var, catch_body } => {
-                match self.evaluate(try_body) {
+                let depth = self.scopes.len();
+                self.try_depth += 1;
+                let result = self.evaluate(try_body);
+                self.try_depth -= 1;
+                match result {
                     Ok(v) => Ok(v),
                     Err(e) => {
+                        // Drop scopes left behind by the frames the error unwound through
+                        self.unwind_scopes(depth);
                         self.push_scope();
-                        self.set_variable(catch_var.clone(), Value::String(e));
+                        self.current_scope().set(catch_var.clone(), e.to_value());
                         let result = self.evaluate(catch_body)?;
-                        self.pop_scope();
+                        self.pop_scope()?;
                         Ok(result)
                     }
                 }
@@ -1450,7 +2060,7 @@ impl Interpreter {
             // ── throw expr ─────────────────────────────────────────────────────
             ASTNode::Throw(expr) => {
                 let v = self.evaluate(expr)?;
-                Err(v.as_string())
+                Err(RuntimeError::thrown(v))
             }
             // ── If as expression (handles both explicit return and implicit last value) ──
             ASTNode::If { cond, then_body, else_body } => {
@@ -1499,19 +2109,118 @@ impl Interpreter {
         }
     }
 
-    /// Convert a literal to a value
-    fn literal_to_value(&self, lit: &Literal) -> Value {
-        match lit {
-            Literal::Int(i) => Value::Int(*i),
-            Literal::Float(f) => Value::Float(*f),
-            Literal::String(s) => Value::String(s.clone()),
-            Literal::Bool(b) => Value::Bool(*b),
-            Literal::Null => Value::Null,
+    /// Convert a literal to a value
+    fn literal_to_value(&self, lit: &Literal) -> Value {
+        match lit {
+            Literal::Int(i) => Value::Int(*i),
+            Literal::Float(f) => Value::Float(*f),
+            Literal::String(s) => Value::String(s.clone()),
+            Literal::Bool(b) => Value::Bool(*b),
+            Literal::Null => Value::Null,
+        }
+    }
+
+    /// Value of a name: a variable, a named function, or a unit enum variant
+    pub(crate) fn lookup(&mut self, name: &str) -> Result<Value, RuntimeError> {
+        if let Some(v) = self.get_variable(name) {
+            return Ok(v);
+        }
+        // Also resolve named functions as first-class values (FunctionObj)
+        if let Some(func_def) = self.functions.get(name).cloned() {
+            return Ok(Value::Function(Box::new(FunctionObj {
+                name: func_def.name.clone(),
+                params: func_def.params.iter().map(|p| (p.clone(), None)).collect(),
+                body: Box::new(func_def.body.clone()),
+                closure: HashMap::new(),
+            })));
+        }
+        if let Some((def, variant)) = self.enum_variant(name) {
+            return self.construct_variant(&def, &variant, Vec::new());
+        }
+        Err(raise(ErrorKind::NameError, format!("Undefined variable: {}", name)))
+    }
+
+    /// Apply a prefix operator, dispatching `-` to a `Neg` impl
+    pub(crate) fn unary_op(&mut self, op: &str, val: Value) -> Result<Value, RuntimeError> {
+        if op == "-" && self.implements(&val, "Neg") {
+            if let Some(ty) = user_type_name(&val) {
+                let method = format!("{}::neg", ty);
+                return self.call_function(&method, vec![val]);
+            }
+        }
+        match op {
+            "!" | "not" => Ok(Value::Bool(!val.is_truthy())),
+            "-" => match val {
+                Value::Int(i) => Ok(Value::Int(-i)),
+                Value::Float(f) => Ok(Value::Float(-f)),
+                _ => Err(raise(ErrorKind::TypeError, "Cannot negate non-numeric value")),
+            },
+            _ => Err(raise(ErrorKind::ValueError, format!("Unknown unary operator: {}", op))),
+        }
+    }
+
+    /// `obj[idx]` on an array, tuple, string or map
+    pub(crate) fn index_value(obj_val: &Value, idx_val: &Value) -> Result<Value, RuntimeError> {
+        match (obj_val, idx_val) {
+            (Value::Array(arr), Value::Int(i)) => {
+                let idx = if *i < 0 { arr.len() as i64 + i } else { *i } as usize;
+                arr.get(idx)
+                    .cloned()
+                    .ok_or_else(|| RuntimeError::new(ErrorKind::IndexError, "Index out of bounds"))
+            }
+            (Value::Tuple(arr), Value::Int(i)) => {
+                let idx = if *i < 0 { arr.len() as i64 + i } else { *i } as usize;
+                arr.get(idx)
+                    .cloned()
+                    .ok_or_else(|| RuntimeError::new(ErrorKind::IndexError, "Tuple index out of bounds"))
+            }
+            (Value::String(s), Value::Int(i)) => {
+                let idx = if *i < 0 { s.len() as i64 + i } else { *i } as usize;
+                s.chars()
+                    .nth(idx)
+                    .map(|c| Value::String(c.to_string()))
+                    .ok_or_else(|| RuntimeError::new(ErrorKind::IndexError, "Index out of bounds"))
+            }
+            (Value::Map(m), Value::String(key)) => {
+                Ok(m.get(key).cloned().unwrap_or(Value::Null))
+            }
+            (Value::Map(m), idx) => {
+                Ok(m.get(&idx.as_string()).cloned().unwrap_or(Value::Null))
+            }
+            _ => Err(raise(ErrorKind::TypeError, "Cannot index non-array/string value")),
+        }
+    }
+
+    /// `obj.field` on a struct, map, enum or module
+    pub(crate) fn field_of(&self, obj_val: Value, field: &str) -> Result<Value, RuntimeError> {
+        if let Value::Module { id, .. } = obj_val {
+            return self.module_field(id, field);
+        }
+        obj_val
+            .get_field(field)
+            .ok_or_else(|| RuntimeError::new(ErrorKind::Error, format!("Field {} not found", field)))
+    }
+
+    /// Build `Name { field: value, .. }`: a struct instance or a struct-like enum variant
+    pub(crate) fn construct_struct(&mut self, name: &str, values: Vec<(String, Value)>) -> Result<Value, RuntimeError> {
+        if let Some((def, variant)) = self.enum_variant(name) {
+            return self.construct_struct_variant(&def, &variant, values);
+        }
+        let struct_def = self
+            .get_variable(name)
+            .ok_or_else(|| RuntimeError::new(ErrorKind::NameError, format!("Unknown struct: {}", name)))?;
+        if let Value::StructDef(def) = struct_def {
+            Ok(Value::StructInstance(Box::new(StructInstance {
+                def,
+                fields: values.into_iter().collect(),
+            })))
+        } else {
+            Err(raise(ErrorKind::Error, format!("{} is not a struct", name)))
         }
     }
 
     /// Evaluate a binary operation
-    fn eval_binary_op(&self, op: &str, left: Value, right: Value) -> Result<Value, String> {
+    fn eval_binary_op(&self, op: &str, left: Value, right: Value) -> Result<Value, RuntimeError> {
         match op {
             "+" => match (&left, &right) {
                 (Value::Int(l), Value::Int(r)) => Ok(Value::Int(l + r)),
@@ -1528,26 +2237,26 @@ impl Interpreter {
                     combined.extend(r.clone());
                     Ok(Value::Array(combined))
                 },
-                _ => Err("Cannot add incompatible types".to_string()),
+                _ => Err(raise(ErrorKind::TypeError, "Cannot add incompatible types")),
             },
             "-" => match (&left, &right) {
                 (Value::Int(l), Value::Int(r)) => Ok(Value::Int(l - r)),
                 (Value::Float(l), Value::Float(r)) => Ok(Value::Float(l - r)),
                 (Value::Int(l), Value::Float(r)) => Ok(Value::Float(*l as f64 - r)),
                 (Value::Float(l), Value::Int(r)) => Ok(Value::Float(l - *r as f64)),
-                _ => Err("Cannot subtract incompatible types".to_string()),
+                _ => Err(raise(ErrorKind::TypeError, "Cannot subtract incompatible types")),
             },
             "*" => match (&left, &right) {
                 (Value::Int(l), Value::Int(r)) => Ok(Value::Int(l * r)),
                 (Value::Float(l), Value::Float(r)) => Ok(Value::Float(l * r)),
                 (Value::Int(l), Value::Float(r)) => Ok(Value::Float(*l as f64 * r)),
                 (Value::Float(l), Value::Int(r)) => Ok(Value::Float(l * *r as f64)),
-                _ => Err("Cannot multiply incompatible types".to_string()),
+                _ => Err(raise(ErrorKind::TypeError, "Cannot multiply incompatible types")),
             },
             "/" => match (&left, &right) {
                 (Value::Int(l), Value::Int(r)) => {
                     if *r == 0 {
-                        Err("Division by zero".to_string())
+                        Err(RuntimeError::new(ErrorKind::ArithmeticError, "Division by zero"))
                     } else {
                         Ok(Value::Int(l / r))
                     }
@@ -1555,17 +2264,17 @@ impl Interpreter {
                 (Value::Float(l), Value::Float(r)) => Ok(Value::Float(l / r)),
                 (Value::Int(l), Value::Float(r)) => Ok(Value::Float(*l as f64 / r)),
                 (Value::Float(l), Value::Int(r)) => Ok(Value::Float(l / *r as f64)),
-                _ => Err("Cannot divide incompatible types".to_string()),
+                _ => Err(raise(ErrorKind::TypeError, "Cannot divide incompatible types")),
             },
             "%" => match (&left, &right) {
                 (Value::Int(l), Value::Int(r)) => {
                     if *r == 0 {
-                        Err("Modulo by zero".to_string())
+                        Err(RuntimeError::new(ErrorKind::ArithmeticError, "Modulo by zero"))
                     } else {
                         Ok(Value::Int(l % r))
                     }
                 }
-                _ => Err("Modulo only supported for integers".to_string()),
+                _ => Err(raise(ErrorKind::Error, "Modulo only supported for integers")),
             },
             "==" => Ok(Value::Bool(left == right)),
             "!=" => Ok(Value::Bool(left != right)),
@@ -1575,7 +2284,7 @@ impl Interpreter {
                 (Value::Int(l), Value::Float(r)) => Ok(Value::Bool((*l as f64) < *r)),
                 (Value::Float(l), Value::Int(r)) => Ok(Value::Bool(*l < *r as f64)),
                 (Value::String(l), Value::String(r)) => Ok(Value::Bool(l < r)),
-                _ => Err("Cannot compare incompatible types".to_string()),
+                _ => Err(raise(ErrorKind::TypeError, "Cannot compare incompatible types")),
             },
             ">" => match (&left, &right) {
                 (Value::Int(l), Value::Int(r)) => Ok(Value::Bool(l > r)),
@@ -1583,7 +2292,7 @@ impl Interpreter {
                 (Value::Int(l), Value::Float(r)) => Ok(Value::Bool((*l as f64) > *r)),
                 (Value::Float(l), Value::Int(r)) => Ok(Value::Bool(*l > *r as f64)),
                 (Value::String(l), Value::String(r)) => Ok(Value::Bool(l > r)),
-                _ => Err("Cannot compare incompatible types".to_string()),
+                _ => Err(raise(ErrorKind::TypeError, "Cannot compare incompatible types")),
             },
             "<=" => match (&left, &right) {
                 (Value::Int(l), Value::Int(r)) => Ok(Value::Bool(l <= r)),
@@ -1591,7 +2300,7 @@ impl Interpreter {
                 (Value::Int(l), Value::Float(r)) => Ok(Value::Bool((*l as f64) <= *r)),
                 (Value::Float(l), Value::Int(r)) => Ok(Value::Bool(*l <= *r as f64)),
                 (Value::String(l), Value::String(r)) => Ok(Value::Bool(l <= r)),
-                _ => Err("Cannot compare incompatible types".to_string()),
+                _ => Err(raise(ErrorKind::TypeError, "Cannot compare incompatible types")),
             },
             ">=" => match (&left, &right) {
                 (Value::Int(l), Value::Int(r)) => Ok(Value::Bool(l >= r)),
@@ -1599,39 +2308,39 @@ impl Interpreter {
                 (Value::Int(l), Value::Float(r)) => Ok(Value::Bool((*l as f64) >= *r)),
                 (Value::Float(l), Value::Int(r)) => Ok(Value::Bool(*l >= *r as f64)),
                 (Value::String(l), Value::String(r)) => Ok(Value::Bool(l >= r)),
-                _ => Err("Cannot compare incompatible types".to_string()),
+                _ => Err(raise(ErrorKind::TypeError, "Cannot compare incompatible types")),
             },
             "&&" | "and" => Ok(Value::Bool(left.is_truthy() && right.is_truthy())),
             "||" | "or" => Ok(Value::Bool(left.is_truthy() || right.is_truthy())),
             // Bitwise operators
             "&" => match (&left, &right) {
                 (Value::Int(l), Value::Int(r)) => Ok(Value::Int(l & r)),
-                _ => Err("Bitwise & requires integers".to_string()),
+                _ => Err(raise(ErrorKind::TypeError, "Bitwise & requires integers")),
             },
             "|" => match (&left, &right) {
                 (Value::Int(l), Value::Int(r)) => Ok(Value::Int(l | r)),
-                _ => Err("Bitwise | requires integers".to_string()),
+                _ => Err(raise(ErrorKind::TypeError, "Bitwise | requires integers")),
             },
             "^" => match (&left, &right) {
                 (Value::Int(l), Value::Int(r)) => Ok(Value::Int(l ^ r)),
-                _ => Err("Bitwise ^ requires integers".to_string()),
+                _ => Err(raise(ErrorKind::TypeError, "Bitwise ^ requires integers")),
             },
             "<<" => match (&left, &right) {
                 (Value::Int(l), Value::Int(r)) => Ok(Value::Int(l << (*r).clamp(0, 63))),
-                _ => Err("Bitwise << requires integers".to_string()),
+                _ => Err(raise(ErrorKind::TypeError, "Bitwise << requires integers")),
             },
             ">>" => match (&left, &right) {
                 (Value::Int(l), Value::Int(r)) => Ok(Value::Int(l >> (*r).clamp(0, 63))),
-                _ => Err("Bitwise >> requires integers".to_string()),
+                _ => Err(raise(ErrorKind::TypeError, "Bitwise >> requires integers")),
             },
-            _ => Err(format!("Unknown binary operator: {}", op)),
+            _ => Err(raise(ErrorKind::ValueError, format!("Unknown binary operator: {}", op))),
         }
     }
 
     /// Execute a function body node, handling both explicit `return` and
     /// implicit last-expression returns. The caller is responsible for
     /// pushing/popping scope and binding parameters.
-    fn call_body(&mut self, body: &ASTNode) -> Result<Value, String> {
+    fn call_body(&mut self, body: &ASTNode) -> Result<Value, RuntimeError> {
         match body {
             ASTNode::Block(nodes) => {
                 let mut last = Value::Null;
@@ -1641,9 +2350,9 @@ impl Interpreter {
                     }
                     if i + 1 == nodes.len() {
                         // Last node: evaluate as expression for implicit return
-                        last = self.evaluate(node)?;
+                        last = self.traced(node, |interp| interp.evaluate(node))?;
                     } else {
-                        self.execute_node(node)?;
+                        self.traced(node, |interp| interp.execute_node(node))?;
                     }
                 }
                 // Explicit return_value takes priority over implicit last
@@ -1658,14 +2367,21 @@ impl Interpreter {
     }
 
     /// Call a function
-    fn call_function(&mut self, name: &str, args: Vec<Value>) -> Result<Value, String> {
-        // Check for built-in functions first
-        if let Some(result) = self.call_builtin(name, &args) {
-            return result;
+    pub(crate) fn call_function(&mut self, name: &str, args: Vec<Value>) -> Result<Value, RuntimeError> {
+        // `builtin::name(..)` skips any user definition that shadows the builtin
+        if let Some(builtin) = name.strip_prefix("builtin::") {
+            return match self.call_builtin(builtin, &args) {
+                Some(result) => result,
+                None => Err(RuntimeError::new(ErrorKind::NameError, format!("Unknown builtin: {}", builtin))),
+            };
         }
 
-        // Check if the name resolves to a Closure in scope
-        if let Some(Value::Closure { params, body, env }) = self.get_variable(name) {
+        // Check if the name resolves to a Closure or Function value in scope
+        let in_scope = self.get_variable(name);
+        if let Some(Value::Closure { params, body, env }) = in_scope {
+            self.enter_call()?;
+            let depth = self.scopes.len();
+            self.open_frame(name, depth);
             self.push_scope();
             for (k, v) in &env {
                 self.current_scope().set(k.clone(), v.clone());
@@ -1675,51 +2391,300 @@ impl Interpreter {
             }
             let body_node = *body.clone();
             let result = match &body_node {
-                ASTNode::Block(_) | ASTNode::Program(_) => {
-                    self.execute_node(&body_node)?;
-                    self.return_value.take().unwrap_or(Value::Null)
-                }
-                expr => self.evaluate(expr)?,
+                ASTNode::Block(_) | ASTNode::Program(_) => self
+                    .execute_node(&body_node)
+                    .map(|_| self.return_value.take().unwrap_or(Value::Null)),
+                expr => self.evaluate(expr),
             };
-            self.pop_scope();
-            return Ok(result);
+            return self.exit_frame(depth, name, result);
         }
 
-        // Check if the name resolves to a Function value in scope
-        if let Some(Value::Function(func)) = self.get_variable(name) {
+        if let Some(Value::Function(func)) = in_scope {
             let fname = func.name.clone();
+            self.enter_call()?;
+            let depth = self.scopes.len();
+            self.open_frame(&fname, depth);
             self.push_scope();
             for ((param, _), arg) in func.params.iter().zip(args.iter()) {
                 self.bind_parameter(param.clone(), arg.clone());
             }
             let body_node = *func.body.clone();
-            let result = self.call_body(&body_node)?;
-            let _ = fname;
-            self.pop_scope();
-            return Ok(result);
+            let result = self.call_body(&body_node);
+            return self.exit_frame(depth, &fname, result);
         }
 
-        // Check for user-defined functions
-        if let Some(func_def) = self.functions.get(name).cloned() {
-            self.push_scope();
+        // Check for user-defined functions. Module code sees its own, then the builtins;
+        // the program's are only reached through `Type::method` paths
+        let module_fn = self.current_module.and_then(|m| self.modules[m].functions.get(name).cloned());
+        let program_fn = match self.current_module {
+            Some(_) if !name.contains("::") => None,
+            _ => self.functions.get(name).cloned(),
+        };
+        if let Some(func_def) = module_fn.or(program_fn) {
+            return self.call_user_function(name, func_def, args);
+        }
 
-            // Bind parameters to arguments (allow extra args to be silently ignored)
-            for (param, arg) in func_def.params.iter().zip(args.iter()) {
-                self.bind_parameter(param.clone(), arg.clone());
+        if let Some(result) = self.call_builtin(name, &args) {
+            return result;
+        }
+
+        if let Some((def, variant)) = self.enum_variant(name) {
+            return self.construct_variant(&def, &variant, args);
+        }
+
+        Err(raise(ErrorKind::NameError, format!("Unknown function: {}", name)))
+    }
+
+    /// Call an impl method, returning its result and, for `self` methods, the
+    /// final value of `self`
+    fn call_method(&mut self, name: &str, args: Vec<Value>) -> Result<(Value, Option<Value>), RuntimeError> {
+        match self.functions.get(name) {
+            Some(def) if def.params.first().is_some_and(|p| p == "self") => {
+                let def = def.clone();
+                self.invoke_user_function(name, def, args, true)
+            }
+            _ => Ok((self.call_function(name, args)?, None)),
+        }
+    }
+
+    /// Call `method` on a value: a builtin method of strings, arrays, maps and
+    /// numbers, a module function, or an impl method. Also returns the final
+    /// `self` of impl methods, for the caller to store back into the receiver.
+    pub(crate) fn call_method_on(&mut self, obj_val: Value, method: &str, arg_values: Vec<Value>) -> Result<(Value, Option<Value>), RuntimeError> {
+        // Dispatch on built-in types first
+        match &obj_val {
+            // ── String methods ────────────────────────────────────────
+            Value::String(s) => {
+                let s = s.clone();
+                return Ok((self.call_string_method(&s, method, &arg_values)?, None));
+            }
+            // ── Array methods ─────────────────────────────────────────
+            Value::Array(arr) => {
+                let arr = arr.clone();
+                return Ok((self.call_array_method(arr, method, &arg_values)?, None));
+            }
+            // ── Map methods ───────────────────────────────────────────
+            Value::Map(map) => {
+                let map = map.clone();
+                return Ok((self.call_map_method(map, method, &arg_values)?, None));
+            }
+            // ── Int methods ───────────────────────────────────────────
+            Value::Int(n) => {
+                let n = *n;
+                return Ok((self.call_int_method(n, method, &arg_values)?, None));
+            }
+            // ── Float methods ─────────────────────────────────────────
+            Value::Float(f) => {
+                let f = *f;
+                return Ok((self.call_float_method(f, method, &arg_values)?, None));
+            }
+            // ── Module namespace: call exported function ──────────────
+            Value::Module { id, .. } => {
+                return Ok((self.call_module_function(*id, method, arg_values)?, None));
+            }
+            // ── Struct / enum instance: call impl method ──────────────
+            Value::StructInstance(_) | Value::Enum(_) => {
+                let method_name = format!("{}::{}", user_type_name(&obj_val).unwrap_or_default(), method);
+                let mut full_args = vec![obj_val];
+                full_args.extend(arg_values);
+                return self.call_method(&method_name, full_args);
+            }
+            _ => {}
+        }
+
+        Err(raise(ErrorKind::TypeError, format!("No method '{}' on value {:?}", method, obj_val)))
+    }
+
+    /// Whether an expression names a storage location a value can be written back to
+    pub(crate) fn is_place(node: &ASTNode) -> bool {
+        match node.inner() {
+            ASTNode::Identifier(name) => !name.contains("::"),
+            ASTNode::FieldAccess { obj, .. } | ASTNode::Index { obj, .. } => Self::is_place(obj),
+            _ => false,
+        }
+    }
+
+    /// Run a `fn` definition in its own scope, inside the module it was defined in
+    fn call_user_function(&mut self, name: &str, func_def: FunctionDef, args: Vec<Value>) -> Result<Value, RuntimeError> {
+        Ok(self.invoke_user_function(name, func_def, args, false)?.0)
+    }
+
+    /// `call_user_function`, optionally also returning the first parameter's final value
+    fn invoke_user_function(&mut self, name: &str, func_def: FunctionDef, args: Vec<Value>, keep_first: bool) -> Result<(Value, Option<Value>), RuntimeError> {
+        if !func_def.bounds.is_empty() {
+            self.check_bounds(&func_def, &args)?;
+        }
+        self.enter_call()?;
+        let depth = self.scopes.len();
+        self.open_frame(name, depth);
+        let caller_module = std::mem::replace(&mut self.current_module, func_def.module);
+        self.push_scope();
+
+        // Bind parameters to arguments (allow extra args to be silently ignored)
+        for (param, arg) in func_def.params.iter().zip(args.iter()) {
+            self.bind_parameter(param.clone(), arg.clone());
+        }
+
+        // Execute function body; call_body handles implicit last-expression return
+        let result = self.call_body(&func_def.body);
+        let first = match (&result, func_def.params.first()) {
+            (Ok(_), Some(param)) if keep_first => self.scopes[depth].get(param),
+            _ => None,
+        };
+        let result = self.exit_frame(depth, name, result);
+        self.current_module = caller_module;
+        Ok((result?, first))
+    }
+
+    /// Load (or fetch from cache) the module an import path refers to
+    fn load_module(&mut self, spec: &str) -> Result<usize, RuntimeError> {
+        let importer = match self.current_module {
+            Some(m) => Some(self.modules[m].path.clone()),
+            None => self.file.clone(),
+        };
+        let path = crate::modules::resolve_import(spec, importer.as_deref(), self.project.as_deref())
+            .map_err(|e| RuntimeError::new(ErrorKind::IOError, e))?;
+        if let Some(&id) = self.module_ids.get(&path) {
+            return Ok(id);
+        }
+
+        let file = path.to_string_lossy().to_string();
+        let src = fs::read_to_string(&path)
+            .map_err(|e| RuntimeError::new(ErrorKind::IOError, format!("Cannot import '{}': {}", spec, e)))?;
+        let mut parser = crate::parser::Parser::new(&src).with_file(&file);
+        let ast = parser.parse().map_err(|e| {
+            let message = match parser.error_span() {
+                Some(span) => format!("Import parse error in {}: {}", span, e),
+                None => format!("Import parse error in {}: {}", file, e),
+            };
+            RuntimeError::new(ErrorKind::ValueError, message)
+        })?;
+        let public = parser.public_names();
+        let exports = (!public.is_empty()).then(|| public.iter().cloned().collect());
+
+        let id = self.modules.len();
+        let name = crate::modules::module_name(&path);
+        self.modules.push(Module {
+            name: name.clone(),
+            path: path.clone(),
+            functions: HashMap::new(),
+            globals: HashMap::new(),
+            exports,
+        });
+        // Cached before evaluation so an import cycle sees the partial module instead of recursing
+        self.module_ids.insert(path.clone(), id);
+
+        let items = match &ast {
+            ASTNode::Program(items) => items.as_slice(),
+            other => std::slice::from_ref(other),
+        };
+        self.collect_traits(items);
+        let mut registered = Ok(());
+        for item in items {
+            match item.inner() {
+                ASTNode::Function { name, params, body, .. } => {
+                    self.modules[id].functions.insert(name.clone(), FunctionDef::new(name, params, body, Some(id)));
+                }
+                ASTNode::Impl { ty, trait_name, methods } => {
+                    // Methods dispatch by type name, so they stay global
+                    let result = self.register_impl(ty, trait_name.as_deref(), methods, Some(id));
+                    registered = registered.and(result.map_err(|e| match item.span() {
+                        Some(span) => e.at(span),
+                        None => e,
+                    }));
+                }
+                _ => {}
+            }
+        }
+
+        // Top-level statements run once, in a scope that becomes the module's globals
+        let depth = self.scopes.len();
+        let importer_module = self.current_module.replace(id);
+        self.push_scope();
+        let mut result = registered;
+        for item in items {
+            if result.is_err() {
+                break;
+            }
+            if !matches!(item.inner(), ASTNode::Function { .. } | ASTNode::Impl { .. }) {
+                result = self.execute_node(item);
+            }
+        }
+        if result.is_ok() {
+            let deferred = std::mem::take(&mut self.current_scope().deferred);
+            result = self.run_deferred(deferred);
+        }
+        match result {
+            Ok(()) => {
+                let globals = std::mem::take(&mut self.current_scope().variables);
+                self.scopes.pop();
+                self.modules[id].globals = globals;
+                self.current_module = importer_module;
+                Ok(id)
+            }
+            Err(e) => {
+                self.unwind_scopes(depth);
+                self.current_module = importer_module;
+                self.module_ids.remove(&path);
+                Err(e.in_frame(&format!("<module {}>", name)))
             }
+        }
+    }
 
-            // Execute function body; call_body handles implicit last-expression return
-            let result = self.call_body(&func_def.body)?;
-            self.pop_scope();
+    /// `import "path"` without `as`: bring the module's exports into the importing scope
+    fn import_exports(&mut self, id: usize) {
+        let module = &self.modules[id];
+        let functions: Vec<(String, FunctionDef)> = module.functions.iter()
+            .filter(|(name, _)| module.exports(name))
+            .map(|(name, def)| (name.clone(), def.clone()))
+            .collect();
+        let globals: Vec<(String, Value)> = module.globals.iter()
+            .filter(|(name, _)| module.exports(name))
+            .map(|(name, v)| (name.clone(), v.clone()))
+            .collect();
+        for (name, def) in functions {
+            match self.current_module {
+                Some(m) => self.modules[m].functions.insert(name, def),
+                None => self.functions.insert(name, def),
+            };
+        }
+        for (name, value) in globals {
+            self.current_scope().set(name, value);
+        }
+    }
 
-            return Ok(result);
+    /// `ns.name(args)` on an imported module
+    fn call_module_function(&mut self, id: usize, name: &str, args: Vec<Value>) -> Result<Value, RuntimeError> {
+        let module = self.modules.get(id).ok_or_else(|| RuntimeError::new(ErrorKind::Error, "Module is not loaded in this interpreter"))?;
+        if !module.exports(name) {
+            return Err(raise(ErrorKind::Error, format!("'{}' is private to module {}", name, module.name)));
         }
+        match module.functions.get(name).cloned() {
+            Some(def) => self.call_user_function(name, def, args),
+            None => match module.globals.get(name).cloned() {
+                Some(callable) => self.call_value(callable, args),
+                None => Err(raise(ErrorKind::Error, format!("Module {} has no function '{}'", module.name, name))),
+            },
+        }
+    }
 
-        Err(format!("Unknown function: {}", name))
+    /// `ns.name` on an imported module
+    fn module_field(&self, id: usize, name: &str) -> Result<Value, RuntimeError> {
+        let module = self.modules.get(id).ok_or_else(|| RuntimeError::new(ErrorKind::Error, "Module is not loaded in this interpreter"))?;
+        if !module.exports(name) {
+            return Err(raise(ErrorKind::Error, format!("'{}' is private to module {}", name, module.name)));
+        }
+        if let Some(v) = module.globals.get(name) {
+            return Ok(v.clone());
+        }
+        if module.functions.contains_key(name) {
+            return Err(raise(ErrorKind::Error, format!("'{}' is a function; call it as {}.{}(...)", name, module.name, name)));
+        }
+        Err(raise(ErrorKind::Error, format!("Module {} has no member '{}'", module.name, name)))
     }
 
     /// Call a string method: str.method(args)
-    fn call_string_method(&mut self, s: &str, method: &str, args: &[Value]) -> Result<Value, String> {
+    fn call_string_method(&mut self, s: &str, method: &str, args: &[Value]) -> Result<Value, RuntimeError> {
         match method {
             "len" | "length" => Ok(Value::Int(s.chars().count() as i64)),
             "is_empty" => Ok(Value::Bool(s.is_empty())),
@@ -1755,13 +2720,13 @@ impl Interpreter {
             "replace" => {
                 if args.len() >= 2 {
                     Ok(Value::String(s.replace(&args[0].as_string() as &str, &args[1].as_string() as &str)))
-                } else { Err("str.replace(from, to)".to_string()) }
+                } else { Err(raise(ErrorKind::TypeError, "str.replace(from, to)".to_string())) }
             }
             "replace_n" => {
                 if args.len() >= 3 {
                     let n = args[2].as_int() as usize;
                     Ok(Value::String(s.replacen(&args[0].as_string() as &str, &args[1].as_string() as &str, n)))
-                } else { Err("str.replace_n(from, to, n)".to_string()) }
+                } else { Err(raise(ErrorKind::TypeError, "str.replace_n(from, to, n)".to_string())) }
             }
             "repeat" => {
                 let n = args.first().map(|v| v.as_int()).unwrap_or(1) as usize;
@@ -1827,12 +2792,12 @@ impl Interpreter {
                 }
                 Ok(Value::String(result))
             }
-            _ => Err(format!("String has no method '{}'", method)),
+            _ => Err(raise(ErrorKind::TypeError, format!("String has no method '{}'", method))),
         }
     }
 
     /// Call an array method: arr.method(args)
-    fn call_array_method(&mut self, arr: Vec<Value>, method: &str, args: &[Value]) -> Result<Value, String> {
+    fn call_array_method(&mut self, arr: Vec<Value>, method: &str, args: &[Value]) -> Result<Value, RuntimeError> {
         match method {
             "len" | "length" | "count" => Ok(Value::Int(arr.len() as i64)),
             "is_empty" => Ok(Value::Bool(arr.is_empty())),
@@ -1858,7 +2823,7 @@ impl Interpreter {
                     let mut new_arr = arr;
                     new_arr.insert(idx.min(new_arr.len()), args[1].clone());
                     Ok(Value::Array(new_arr))
-                } else { Err("arr.insert(idx, val)".to_string()) }
+                } else { Err(raise(ErrorKind::TypeError, "arr.insert(idx, val)")) }
             }
             "remove" => {
                 let idx = args.first().map(|v| v.as_int() as usize).unwrap_or(0);
@@ -1899,7 +2864,7 @@ impl Interpreter {
                 if let Some(cmp_fn) = args.first() {
                     let cmp_fn = cmp_fn.clone();
                     let mut new_arr = arr;
-                    let mut sort_err: Option<String> = None;
+                    let mut sort_err: Option<RuntimeError> = None;
                     new_arr.sort_by(|a, b| {
                         if sort_err.is_some() { return std::cmp::Ordering::Equal; }
                         match self.call_value(cmp_fn.clone(), vec![a.clone(), b.clone()]) {
@@ -1912,7 +2877,7 @@ impl Interpreter {
                     });
                     if let Some(e) = sort_err { return Err(e); }
                     Ok(Value::Array(new_arr))
-                } else { Err("arr.sort_by(|a,b| a < b)".to_string()) }
+                } else { Err(raise(ErrorKind::TypeError, "arr.sort_by(|a,b| a < b)")) }
             }
             "reverse" => {
                 let mut new_arr = arr;
@@ -1984,7 +2949,7 @@ impl Interpreter {
                     Ok(Value::Array(arr.iter().zip(other.iter())
                         .map(|(a, b)| Value::Array(vec![a.clone(), b.clone()]))
                         .collect()))
-                } else { Err("arr.zip(other_arr)".to_string()) }
+                } else { Err(raise(ErrorKind::TypeError, "arr.zip(other_arr)")) }
             }
             "any" => Ok(Value::Bool(arr.iter().any(|v| v.is_truthy()))),
             "all" => Ok(Value::Bool(arr.iter().all(|v| v.is_truthy()))),
@@ -2001,7 +2966,7 @@ impl Interpreter {
                         result.push(r);
                     }
                     Ok(Value::Array(result))
-                } else { Err("arr.map(fn)".to_string()) }
+                } else { Err(raise(ErrorKind::TypeError, "arr.map(fn)")) }
             }
             "filter" => {
                 if let Some(closure) = args.first() {
@@ -2011,7 +2976,7 @@ impl Interpreter {
                         if r.is_truthy() { result.push(item.clone()); }
                     }
                     Ok(Value::Array(result))
-                } else { Err("arr.filter(fn)".to_string()) }
+                } else { Err(raise(ErrorKind::TypeError, "arr.filter(fn)")) }
             }
             "reduce" | "fold" => {
                 if args.len() >= 2 {
@@ -2028,7 +2993,7 @@ impl Interpreter {
                         acc = self.call_value(closure.clone(), vec![acc, item.clone()])?;
                     }
                     Ok(acc)
-                } else { Err("arr.reduce(fn, [initial])".to_string()) }
+                } else { Err(raise(ErrorKind::TypeError, "arr.reduce(fn, [initial])")) }
             }
             "for_each" | "each" => {
                 if let Some(closure) = args.first() {
@@ -2036,7 +3001,7 @@ impl Interpreter {
                         self.call_value(closure.clone(), vec![item.clone()])?;
                     }
                     Ok(Value::Null)
-                } else { Err("arr.for_each(fn)".to_string()) }
+                } else { Err(raise(ErrorKind::TypeError, "arr.for_each(fn)")) }
             }
             "find" => {
                 if let Some(closure) = args.first() {
@@ -2045,7 +3010,7 @@ impl Interpreter {
                         if r.is_truthy() { return Ok(item.clone()); }
                     }
                     Ok(Value::Null)
-                } else { Err("arr.find(fn)".to_string()) }
+                } else { Err(raise(ErrorKind::TypeError, "arr.find(fn)")) }
             }
             "count_if" => {
                 if let Some(closure) = args.first() {
@@ -2066,14 +3031,14 @@ impl Interpreter {
                         else { result.push(r); }
                     }
                     Ok(Value::Array(result))
-                } else { Err("arr.flat_map(fn)".to_string()) }
+                } else { Err(raise(ErrorKind::TypeError, "arr.flat_map(fn)")) }
             }
-            _ => Err(format!("Array has no method '{}'", method)),
+            _ => Err(raise(ErrorKind::TypeError, format!("Array has no method '{}'", method))),
         }
     }
 
     /// Call a map method: map.method(args)
-    fn call_map_method(&mut self, mut map: HashMap<String, Value>, method: &str, args: &[Value]) -> Result<Value, String> {
+    fn call_map_method(&mut self, mut map: HashMap<String, Value>, method: &str, args: &[Value]) -> Result<Value, RuntimeError> {
         match method {
             "len" | "length" | "count" => Ok(Value::Int(map.len() as i64)),
             "is_empty" => Ok(Value::Bool(map.is_empty())),
@@ -2092,7 +3057,7 @@ impl Interpreter {
                     let val = args[1].clone();
                     map.insert(key, val);
                     Ok(Value::Map(map))
-                } else { Err("map.set(key, val)".to_string()) }
+                } else { Err(raise(ErrorKind::TypeError, "map.set(key, val)")) }
             }
             "remove" | "delete" => {
                 let key = args.first().map(|v| v.as_string()).unwrap_or_default();
@@ -2107,7 +3072,7 @@ impl Interpreter {
                 if let Some(Value::Map(other)) = args.first() {
                     for (k, v) in other { map.insert(k.clone(), v.clone()); }
                     Ok(Value::Map(map))
-                } else { Err("map.merge(other_map)".to_string()) }
+                } else { Err(raise(ErrorKind::TypeError, "map.merge(other_map)")) }
             }
             "to_array" | "to_list" => Ok(Value::Array(
                 map.iter().map(|(k, v)| Value::Array(vec![Value::String(k.clone()), v.clone()])).collect()
@@ -2119,14 +3084,14 @@ impl Interpreter {
                         self.call_value(closure.clone(), vec![Value::String(k.clone()), v.clone()])?;
                     }
                     Ok(Value::Null)
-                } else { Err("map.for_each(fn)".to_string()) }
+                } else { Err(raise(ErrorKind::TypeError, "map.for_each(fn)")) }
             }
-            _ => Err(format!("Map has no method '{}'", method)),
+            _ => Err(raise(ErrorKind::TypeError, format!("Map has no method '{}'", method))),
         }
     }
 
     /// Call an int method: n.method(args)
-    fn call_int_method(&self, n: i64, method: &str, args: &[Value]) -> Result<Value, String> {
+    fn call_int_method(&self, n: i64, method: &str, args: &[Value]) -> Result<Value, RuntimeError> {
         match method {
             "to_string" | "str" => Ok(Value::String(n.to_string())),
             "to_float" => Ok(Value::Float(n as f64)),
@@ -2149,7 +3114,7 @@ impl Interpreter {
                     let lo = args[0].as_int();
                     let hi = args[1].as_int();
                     Ok(Value::Int(n.clamp(lo, hi)))
-                } else { Err("n.clamp(lo, hi)".to_string()) }
+                } else { Err(raise(ErrorKind::TypeError, "n.clamp(lo, hi)".to_string())) }
             }
             "is_even" => Ok(Value::Bool(n % 2 == 0)),
             "is_odd" => Ok(Value::Bool(n % 2 != 0)),
@@ -2163,12 +3128,12 @@ impl Interpreter {
             "to_bits" => Ok(Value::String(format!("{:b}", n))),
             "to_hex" => Ok(Value::String(format!("{:x}", n))),
             "to_oct" => Ok(Value::String(format!("{:o}", n))),
-            _ => Err(format!("Int has no method '{}'", method)),
+            _ => Err(raise(ErrorKind::TypeError, format!("Int has no method '{}'", method))),
         }
     }
 
     /// Call a float method: f.method(args)
-    fn call_float_method(&self, f: f64, method: &str, _args: &[Value]) -> Result<Value, String> {
+    fn call_float_method(&self, f: f64, method: &str, _args: &[Value]) -> Result<Value, RuntimeError> {
         match method {
             "to_string" | "str" => Ok(Value::String(f.to_string())),
             "to_int" => Ok(Value::Int(f as i64)),
@@ -2197,14 +3162,17 @@ impl Interpreter {
             "is_zero" => Ok(Value::Bool(f == 0.0)),
             "is_positive" => Ok(Value::Bool(f > 0.0)),
             "is_negative" => Ok(Value::Bool(f < 0.0)),
-            _ => Err(format!("Float has no method '{}'", method)),
+            _ => Err(raise(ErrorKind::TypeError, format!("Float has no method '{}'", method))),
         }
     }
 
     /// Call a value as a function (used by higher-order builtins like map, filter)
-    fn call_value(&mut self, callable: Value, args: Vec<Value>) -> Result<Value, String> {
+    pub(crate) fn call_value(&mut self, callable: Value, args: Vec<Value>) -> Result<Value, RuntimeError> {
         match callable {
             Value::Closure { params, body, env } => {
+                self.enter_call()?;
+                let depth = self.scopes.len();
+                self.open_frame("<closure>", depth);
                 self.push_scope();
                 for (k, v) in &env {
                     self.current_scope().set(k.clone(), v.clone());
@@ -2213,29 +3181,285 @@ impl Interpreter {
                     self.bind_parameter(param.clone(), arg.clone());
                 }
                 let result = match body.as_ref() {
-                    ASTNode::Block(_) | ASTNode::Program(_) => {
-                        self.execute_node(&body)?;
-                        self.return_value.take().unwrap_or(Value::Null)
-                    }
-                    expr => self.evaluate(expr)?,
+                    ASTNode::Block(_) | ASTNode::Program(_) => self
+                        .execute_node(&body)
+                        .map(|_| self.return_value.take().unwrap_or(Value::Null)),
+                    expr => self.evaluate(expr),
                 };
-                self.pop_scope();
-                Ok(result)
+                self.exit_frame(depth, "<closure>", result)
             }
             Value::Function(func) => {
                 let name = func.name.clone();
                 self.call_function(&name, args)
             }
-            _ => Err(format!("Cannot call {:?} as a function", callable)),
+            _ => Err(raise(ErrorKind::TypeError, format!("Cannot call {:?} as a function", callable))),
+        }
+    }
+
+    /// Register the trait definitions among a program's items, so impls anywhere
+    /// in the file can refer to them
+    fn collect_traits(&mut self, items: &[ASTNode]) {
+        for item in items {
+            if let ASTNode::TraitDef { name, required, methods } = item.inner() {
+                self.define_trait(name, required, methods);
+            }
+        }
+    }
+
+    fn define_trait(&mut self, name: &str, required: &[String], methods: &[ASTNode]) {
+        let methods = methods
+            .iter()
+            .filter_map(|m| match m {
+                ASTNode::Function { name, params, body, .. } => Some((name.clone(), FunctionObj {
+                    name: name.clone(),
+                    params: params.iter().map(|p| (p.name.clone(), p.ty.clone())).collect(),
+                    body: body.clone(),
+                    closure: HashMap::new(),
+                })),
+                _ => None,
+            })
+            .collect();
+        let def = TraitDef { name: name.to_string(), methods, required: required.to_vec() };
+        self.traits.insert(name.to_string(), def);
+    }
+
+    /// Methods an implementation of `trait_name` must define, if it is a trait at all
+    fn trait_requirements(&self, trait_name: &str) -> Option<Vec<String>> {
+        if let Some(def) = self.traits.get(trait_name) {
+            return Some(def.required.clone());
+        }
+        BUILTIN_TRAITS
+            .iter()
+            .find(|(name, _)| *name == trait_name)
+            .map(|(_, methods)| methods.iter().map(|m| m.to_string()).collect())
+    }
+
+    /// Store an impl block's methods as `Type::method`; for `impl Trait for Type`
+    /// also fill in the trait's default methods and check the required ones exist
+    fn register_impl(&mut self, ty: &str, trait_name: Option<&str>, methods: &[ASTNode], module: Option<usize>) -> Result<(), RuntimeError> {
+        let mut defined = Vec::new();
+        for method in methods {
+            if let ASTNode::Function { name, params, body, .. } = method {
+                self.functions.insert(format!("{}::{}", ty, name), FunctionDef::new(name, params, body, module));
+                defined.push(name.as_str());
+            }
+        }
+        let Some(trait_name) = trait_name else {
+            return Ok(());
+        };
+        let required = self.trait_requirements(trait_name)
+            .ok_or_else(|| RuntimeError::new(ErrorKind::NameError, format!("Unknown trait: {}", trait_name)))?;
+        if let Some(missing) = required.iter().find(|m| !defined.contains(&m.as_str())) {
+            return Err(RuntimeError::new(
+                ErrorKind::TypeError,
+                format!("impl {} for {} is missing method '{}'", trait_name, ty, missing),
+            ));
+        }
+        let defaults = self.traits.get(trait_name).map(|t| t.methods.clone()).unwrap_or_default();
+        for (name, func) in defaults {
+            if !defined.contains(&name.as_str()) {
+                self.functions.insert(format!("{}::{}", ty, name), FunctionDef {
+                    name: name.clone(),
+                    params: func.params.iter().map(|(p, _)| p.clone()).collect(),
+                    body: *func.body,
+                    module,
+                    bounds: Vec::new(),
+                });
+            }
+        }
+        self.trait_impls.insert((ty.to_string(), trait_name.to_string()));
+        Ok(())
+    }
+
+    /// Whether a value's type implements a trait, through an `impl` or, for the
+    /// built-in traits, natively
+    fn implements(&self, value: &Value, trait_name: &str) -> bool {
+        if let Some(ty) = user_type_name(value) {
+            return self.trait_impls.contains(&(ty.to_string(), trait_name.to_string()));
+        }
+        match trait_name {
+            "Display" | "Eq" => true,
+            "Ord" => matches!(value, Value::Int(_) | Value::Float(_) | Value::String(_)),
+            "Add" => matches!(value, Value::Int(_) | Value::Float(_) | Value::String(_) | Value::Array(_)),
+            "Sub" | "Mul" | "Div" | "Rem" | "Neg" => matches!(value, Value::Int(_) | Value::Float(_)),
+            _ => false,
+        }
+    }
+
+    /// Reject arguments to trait-typed parameters (`fn show(x: Display)`) that
+    /// don't implement the trait
+    pub(crate) fn check_bounds(&self, func_def: &FunctionDef, args: &[Value]) -> Result<(), RuntimeError> {
+        for (i, ty) in &func_def.bounds {
+            if self.trait_requirements(ty).is_none() {
+                continue;
+            }
+            if let Some(arg) = args.get(*i) {
+                if !self.implements(arg, ty) {
+                    return Err(RuntimeError::new(
+                        ErrorKind::TypeError,
+                        format!("argument '{}' of {} does not implement {}", func_def.params[*i], func_def.name, ty),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply an operator through the operand's `Add`/`Eq`/`Ord`/... impl, or
+    /// `Display` when a user type is concatenated with a string
+    fn trait_binary_op(&mut self, op: &str, left: &Value, right: &Value) -> Result<Option<Value>, RuntimeError> {
+        let (trait_name, method) = match op {
+            "+" => ("Add", "add"),
+            "-" => ("Sub", "sub"),
+            "*" => ("Mul", "mul"),
+            "/" => ("Div", "div"),
+            "%" => ("Rem", "rem"),
+            "==" | "!=" => ("Eq", "eq"),
+            "<" | ">" | "<=" | ">=" => ("Ord", "cmp"),
+            _ => return Ok(None),
+        };
+        if let Some(ty) = user_type_name(left).filter(|_| self.implements(left, trait_name)) {
+            let result = self.call_function(&format!("{}::{}", ty, method), vec![left.clone(), right.clone()])?;
+            return Ok(Some(match op {
+                "!=" => Value::Bool(!result.is_truthy()),
+                "<" => Value::Bool(result.as_int() < 0),
+                ">" => Value::Bool(result.as_int() > 0),
+                "<=" => Value::Bool(result.as_int() <= 0),
+                ">=" => Value::Bool(result.as_int() >= 0),
+                "==" => Value::Bool(result.is_truthy()),
+                _ => result,
+            }));
+        }
+        if op == "+" && (matches!(left, Value::String(_)) || matches!(right, Value::String(_))) {
+            return Ok(Some(Value::String(self.display(left)? + &self.display(right)?)));
+        }
+        Ok(None)
+    }
+
+    /// Evaluate a binary operator, consulting operator traits for struct and enum operands
+    pub(crate) fn binary_op(&mut self, op: &str, left: Value, right: Value) -> Result<Value, RuntimeError> {
+        if user_type_name(&left).is_some() || user_type_name(&right).is_some() {
+            if let Some(v) = self.trait_binary_op(op, &left, &right)? {
+                return Ok(v);
+            }
+        }
+        self.eval_binary_op(op, left, right)
+    }
+
+    /// String form of a value for printing, using `Display` impls where they exist
+    pub(crate) fn display(&mut self, value: &Value) -> Result<String, RuntimeError> {
+        if !self.trait_impls.iter().any(|(_, t)| t == "Display") {
+            return Ok(value.as_string());
+        }
+        match value {
+            Value::StructInstance(_) | Value::Enum(_) if self.implements(value, "Display") => {
+                let ty = user_type_name(value).unwrap_or_default().to_string();
+                Ok(self.call_function(&format!("{}::to_string", ty), vec![value.clone()])?.as_string())
+            }
+            Value::Array(items) => Ok(format!("[{}]", self.display_all(items)?.join(", "))),
+            Value::Tuple(items) => Ok(format!("({})", self.display_all(items)?.join(", "))),
+            _ => Ok(value.as_string()),
+        }
+    }
+
+    fn display_all(&mut self, values: &[Value]) -> Result<Vec<String>, RuntimeError> {
+        values.iter().map(|v| self.display(v)).collect()
+    }
+
+    /// Register an `enum` definition under its name
+    fn define_enum(&mut self, name: &str, variants: &[EnumVariant]) {
+        let def = EnumDef { name: name.to_string(), variants: variants.to_vec() };
+        self.set_variable(name.to_string(), Value::EnumDef(Box::new(def)));
+    }
+
+    /// Resolve an `Enum::Variant` path to the enum and the named variant
+    fn enum_variant(&self, path: &str) -> Option<(Box<EnumDef>, EnumVariant)> {
+        let (enum_name, variant) = path.rsplit_once("::")?;
+        match self.get_variable(enum_name)? {
+            Value::EnumDef(def) => {
+                let v = def.variants.iter().find(|v| v.name == variant)?.clone();
+                Some((def, v))
+            }
+            _ => None,
+        }
+    }
+
+    /// Build a unit or tuple variant from positional arguments
+    fn construct_variant(&self, def: &EnumDef, variant: &EnumVariant, args: Vec<Value>) -> Result<Value, RuntimeError> {
+        let path = format!("{}::{}", def.name, variant.name);
+        let payload = match &variant.data {
+            VariantData::Unit if args.is_empty() => EnumPayload::Unit,
+            VariantData::Unit => return Err(RuntimeError::new(ErrorKind::TypeError, format!("{} takes no arguments", path))),
+            VariantData::Tuple(types) if types.len() == args.len() => EnumPayload::Tuple(args),
+            VariantData::Tuple(types) if args.is_empty() => {
+                return Err(RuntimeError::new(ErrorKind::TypeError, format!("{} expects {} argument(s); call it as {}(...)", path, types.len(), path)));
+            }
+            VariantData::Tuple(types) => {
+                return Err(RuntimeError::new(ErrorKind::TypeError, format!("{} expects {} argument(s), got {}", path, types.len(), args.len())));
+            }
+            VariantData::Struct(_) => {
+                return Err(RuntimeError::new(ErrorKind::TypeError, format!("{} has named fields; build it as {} {{ ... }}", path, path)));
+            }
+        };
+        Ok(Value::Enum(Box::new(EnumValue {
+            enum_name: def.name.clone(),
+            variant: variant.name.clone(),
+            payload,
+        })))
+    }
+
+    /// Build a struct-like variant, keeping the declared field order
+    fn construct_struct_variant(&self, def: &EnumDef, variant: &EnumVariant, mut values: Vec<(String, Value)>) -> Result<Value, RuntimeError> {
+        let path = format!("{}::{}", def.name, variant.name);
+        let declared = match &variant.data {
+            VariantData::Struct(fields) => fields,
+            _ => return Err(RuntimeError::new(ErrorKind::TypeError, format!("{} has no named fields", path))),
+        };
+        if let Some((extra, _)) = values.iter().find(|(k, _)| !declared.iter().any(|(d, _)| d == k)) {
+            return Err(RuntimeError::new(ErrorKind::TypeError, format!("{} has no field '{}'", path, extra)));
+        }
+        let mut fields = Vec::new();
+        for (name, _) in declared {
+            let pos = values.iter().position(|(k, _)| k == name)
+                .ok_or_else(|| RuntimeError::new(ErrorKind::TypeError, format!("missing field '{}' in {}", name, path)))?;
+            fields.push(values.swap_remove(pos));
+        }
+        Ok(Value::Enum(Box::new(EnumValue {
+            enum_name: def.name.clone(),
+            variant: variant.name.clone(),
+            payload: EnumPayload::Struct(fields),
+        })))
+    }
+
+    /// Whether a bare pattern name is a variant of the enum `value` belongs to,
+    /// in which case it compares against the variant instead of binding
+    fn names_variant(&self, name: &str, value: &Value) -> bool {
+        match value {
+            Value::Enum(e) => matches!(
+                self.get_variable(&e.enum_name),
+                Some(Value::EnumDef(def)) if def.variants.iter().any(|v| v.name == name)
+            ),
+            _ => false,
         }
     }
 
     /// Check if a value matches a pattern
-    fn pattern_matches(&self, pattern: &crate::parser::Pattern, value: &Value) -> bool {
+    pub(crate) fn pattern_matches(&self, pattern: &crate::parser::Pattern, value: &Value) -> bool {
         use crate::parser::{Pattern, Literal};
         match pattern {
             Pattern::Wildcard => true,
-            Pattern::Identifier(_) => true, // capture — always matches
+            Pattern::Identifier(name) => match value {
+                // A unit variant named bare (`Empty =>`) only matches itself
+                Value::Enum(e) if self.names_variant(name, value) => e.variant == *name,
+                _ => true, // capture — always matches
+            },
+            Pattern::Tuple(pats) => match value {
+                Value::Tuple(vals) | Value::Array(vals) => {
+                    pats.len() == vals.len()
+                        && pats.iter().zip(vals).all(|(p, v)| self.pattern_matches(p, v))
+                }
+                _ => false,
+            },
             Pattern::Literal(lit) => {
                 let lit_val = match lit {
                     Literal::Int(n) => Value::Int(*n),
@@ -2263,6 +3487,13 @@ impl Interpreter {
                         }
                         true
                     }
+                    Value::Enum(e) => {
+                        // Struct-like variant: `Rect { w, h }`
+                        e.variant == *name
+                            && fields.iter().all(|(field_name, field_pat)| {
+                                e.field(field_name).is_some_and(|v| self.pattern_matches(field_pat, v))
+                            })
+                    }
                     Value::Map(m) => {
                         // Also match maps with struct-like pattern
                         for (field_name, field_pat) in fields {
@@ -2284,6 +3515,16 @@ impl Interpreter {
             }
             Pattern::Enum { name, variant, data } => {
                 match value {
+                    Value::Enum(e) => {
+                        if (!name.is_empty() && e.enum_name != *name) || e.variant != *variant {
+                            return false;
+                        }
+                        match (data, &e.payload) {
+                            (None, _) => true,
+                            (Some(dp), EnumPayload::Struct(_)) => self.pattern_matches(dp, value),
+                            (Some(dp), _) => self.pattern_matches(dp, &e.payload_value()),
+                        }
+                    }
                     Value::StructInstance(inst) => {
                         // Enum variant as struct with "variant" field
                         let matches_name = inst.def.name == *name || inst.def.name == *variant;
@@ -2307,24 +3548,47 @@ impl Interpreter {
 
     /// Bind captured variables from a pattern match into current scope
     fn pattern_bind(&mut self, pattern: &crate::parser::Pattern, value: &Value) {
+        let mut bindings = Vec::new();
+        self.pattern_bindings(pattern, value, &mut bindings);
+        for (name, value) in bindings {
+            self.current_scope().set(name, value);
+        }
+    }
+
+    /// The variables a matching pattern binds, in pattern order
+    pub(crate) fn pattern_bindings(&self, pattern: &crate::parser::Pattern, value: &Value, out: &mut Vec<(String, Value)>) {
         use crate::parser::Pattern;
         match pattern {
-            Pattern::Identifier(name) => {
-                self.current_scope().set(name.clone(), value.clone());
+            Pattern::Identifier(name) if !self.names_variant(name, value) => {
+                out.push((name.clone(), value.clone()));
+            }
+            Pattern::Tuple(pats) => {
+                if let Value::Tuple(vals) | Value::Array(vals) = value {
+                    for (p, v) in pats.iter().zip(vals) {
+                        self.pattern_bindings(p, v, out);
+                    }
+                }
             }
             Pattern::Struct { fields, .. } => {
                 match value {
+                    Value::Enum(e) => {
+                        for (field_name, field_pat) in fields {
+                            if let Some(fv) = e.field(field_name) {
+                                self.pattern_bindings(field_pat, fv, out);
+                            }
+                        }
+                    }
                     Value::StructInstance(inst) => {
                         for (field_name, field_pat) in fields {
                             if let Some(fv) = inst.fields.get(field_name) {
-                                self.pattern_bind(field_pat, fv);
+                                self.pattern_bindings(field_pat, fv, out);
                             }
                         }
                     }
                     Value::Map(m) => {
                         for (field_name, field_pat) in fields {
                             if let Some(fv) = m.get(field_name) {
-                                self.pattern_bind(field_pat, fv);
+                                self.pattern_bindings(field_pat, fv, out);
                             }
                         }
                     }
@@ -2334,9 +3598,13 @@ impl Interpreter {
             Pattern::Enum { data, .. } => {
                 if let Some(dp) = data {
                     match value {
+                        Value::Enum(e) => match e.payload {
+                            EnumPayload::Struct(_) => self.pattern_bindings(dp, value, out),
+                            _ => self.pattern_bindings(dp, &e.payload_value(), out),
+                        },
                         Value::StructInstance(inst) => {
                             if let Some(inner) = inst.fields.get("value").or_else(|| inst.fields.get("data")) {
-                                self.pattern_bind(dp, inner);
+                                self.pattern_bindings(dp, inner, out);
                             }
                         }
                         _ => {}
@@ -2349,41 +3617,24 @@ impl Interpreter {
 
     /// Call a built-in function
     #[allow(unreachable_patterns)]
-    fn call_builtin(&mut self, name: &str, args: &[Value]) -> Option<Result<Value, String>> {
+    fn call_builtin(&mut self, name: &str, args: &[Value]) -> Option<Result<Value, RuntimeError>> {
         match name {
-            "print" => {
-                if args.is_empty() { print!(""); }
-                else {
-                    for (i, a) in args.iter().enumerate() {
-                        if i > 0 { print!(" "); }
-                        print!("{}", a);
-                    }
-                }
-                Some(Ok(Value::Null))
-            }
-            "println" => {
-                if args.is_empty() { println!(); }
-                else {
-                    for (i, a) in args.iter().enumerate() {
-                        if i > 0 { print!(" "); }
-                        print!("{}", a);
-                    }
-                    println!();
-                }
-                Some(Ok(Value::Null))
-            }
-            "eprint" => {
-                if let Some(arg) = args.first() {
-                    eprint!("{}", arg);
-                }
+            "print" | "println" => {
+                let parts = match self.display_all(args) {
+                    Ok(parts) => parts,
+                    Err(e) => return Some(Err(e)),
+                };
+                print!("{}", parts.join(" "));
+                if name == "println" { println!(); }
                 Some(Ok(Value::Null))
             }
-            "eprintln" => {
-                if let Some(arg) = args.first() {
-                    eprintln!("{}", arg);
-                } else {
-                    eprintln!();
-                }
+            "eprint" | "eprintln" => {
+                let text = match args.first().map(|a| self.display(a)).transpose() {
+                    Ok(text) => text.unwrap_or_default(),
+                    Err(e) => return Some(Err(e)),
+                };
+                eprint!("{}", text);
+                if name == "eprintln" { eprintln!(); }
                 Some(Ok(Value::Null))
             }
             "len" => {
@@ -2391,32 +3642,35 @@ impl Interpreter {
                     let len = match arg {
                         Value::String(s) => s.len() as i64,
                         Value::Array(arr) => arr.len() as i64,
-                        _ => return Some(Err("len() requires a string or array".to_string())),
+                        _ => return Some(Err(raise(ErrorKind::TypeError, "len() requires a string or array".to_string()))),
                     };
                     Some(Ok(Value::Int(len)))
                 } else {
-                    Some(Err("len() requires an argument".to_string()))
+                    Some(Err(raise(ErrorKind::TypeError, "len() requires an argument".to_string())))
                 }
             }
             "to_int" => {
                 if let Some(arg) = args.first() {
                     Some(Ok(Value::Int(arg.as_int())))
                 } else {
-                    Some(Err("to_int() requires an argument".to_string()))
+                    Some(Err(raise(ErrorKind::TypeError, "to_int() requires an argument".to_string())))
                 }
             }
             "to_float" => {
                 if let Some(arg) = args.first() {
                     Some(Ok(Value::Float(arg.as_float())))
                 } else {
-                    Some(Err("to_float() requires an argument".to_string()))
+                    Some(Err(raise(ErrorKind::TypeError, "to_float() requires an argument".to_string())))
                 }
             }
             "to_string" => {
                 if let Some(arg) = args.first() {
-                    Some(Ok(Value::String(arg.as_string())))
+                    match self.display(arg) {
+                        Ok(s) => Some(Ok(Value::String(s))),
+                        Err(e) => Some(Err(e)),
+                    }
                 } else {
-                    Some(Err("to_string() requires an argument".to_string()))
+                    Some(Err(raise(ErrorKind::TypeError, "to_string() requires an argument".to_string())))
                 }
             }
             "typeof" => {
@@ -2432,17 +3686,28 @@ impl Interpreter {
                         Value::Closure { .. } => "closure",
                         Value::StructDef(_) => "struct_def",
                         Value::StructInstance(_) => "struct_instance",
+                        Value::EnumDef(_) => "enum_def",
+                        Value::Enum(_) => "enum",
                         Value::Function(_) => "function",
                         Value::Trait(_) => "trait",
                         Value::Reference(_) => "reference",
+                        Value::Module { .. } => "module",
                         Value::Null => "null",
                         Value::Range { .. } => "range",
                     };
                     Some(Ok(Value::String(type_name.to_string())))
                 } else {
-                    Some(Err("typeof() requires an argument".to_string()))
+                    Some(Err(raise(ErrorKind::TypeError, "typeof() requires an argument".to_string())))
                 }
             }
+            "implements" => {
+                let trait_name = match args.get(1) {
+                    Some(Value::Trait(def)) => def.name.clone(),
+                    Some(Value::String(s)) => s.clone(),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "implements() requires a value and a trait".to_string()))),
+                };
+                Some(Ok(Value::Bool(self.implements(&args[0], &trait_name))))
+            }
             "exit" => {
                 let code = args.first().map(|v| v.as_int()).unwrap_or(0) as i32;
                 std::process::exit(code);
@@ -2453,10 +3718,10 @@ impl Interpreter {
                     let path = arg.as_string();
                     match fs::read_to_string(&path) {
                         Ok(contents) => Some(Ok(Value::String(contents))),
-                        Err(e) => Some(Err(format!("Failed to read file: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, format!("Failed to read file: {}", e)))),
                     }
                 } else {
-                    Some(Err("file_read() requires a path argument".to_string()))
+                    Some(Err(raise(ErrorKind::TypeError, "file_read() requires a path argument".to_string())))
                 }
             }
             "file_write" => {
@@ -2465,12 +3730,10 @@ impl Interpreter {
                     let contents = args[1].as_string();
                     match fs::write(&path, &contents) {
                         Ok(_) => Some(Ok(Value::Null)),
-                        Err(e) => Some(Err(format!("Failed to write file: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, format!("Failed to write file: {}", e)))),
                     }
                 } else {
-                    Some(Err(
-                        "file_write() requires path and content arguments".to_string()
-                    ))
+                    Some(Err(raise(ErrorKind::TypeError, "file_write() requires path and content arguments".to_string())))
                 }
             }
             "file_append" => {
@@ -2480,14 +3743,12 @@ impl Interpreter {
                     match fs::OpenOptions::new().create(true).append(true).open(&path) {
                         Ok(mut file) => match file.write_all(contents.as_bytes()) {
                             Ok(_) => Some(Ok(Value::Null)),
-                            Err(e) => Some(Err(format!("Failed to append: {}", e))),
+                            Err(e) => Some(Err(raise(ErrorKind::IOError, format!("Failed to append: {}", e)))),
                         },
-                        Err(e) => Some(Err(format!("Failed to open file: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, format!("Failed to open file: {}", e)))),
                     }
                 } else {
-                    Some(Err(
-                        "file_append() requires path and content arguments".to_string()
-                    ))
+                    Some(Err(raise(ErrorKind::TypeError, "file_append() requires path and content arguments".to_string())))
                 }
             }
             "file_exists" => {
@@ -2495,7 +3756,7 @@ impl Interpreter {
                     let path = arg.as_string();
                     Some(Ok(Value::Bool(std::path::Path::new(&path).exists())))
                 } else {
-                    Some(Err("file_exists() requires a path argument".to_string()))
+                    Some(Err(raise(ErrorKind::TypeError, "file_exists() requires a path argument".to_string())))
                 }
             }
             "file_remove" => {
@@ -2503,10 +3764,10 @@ impl Interpreter {
                     let path = arg.as_string();
                     match fs::remove_file(&path) {
                         Ok(_) => Some(Ok(Value::Null)),
-                        Err(e) => Some(Err(format!("Failed to remove: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, format!("Failed to remove: {}", e)))),
                     }
                 } else {
-                    Some(Err("file_remove() requires a path argument".to_string()))
+                    Some(Err(raise(ErrorKind::TypeError, "file_remove() requires a path argument".to_string())))
                 }
             }
             "mkdir" => {
@@ -2514,10 +3775,10 @@ impl Interpreter {
                     let path = arg.as_string();
                     match fs::create_dir_all(&path) {
                         Ok(_) => Some(Ok(Value::Null)),
-                        Err(e) => Some(Err(format!("Failed to create directory: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, format!("Failed to create directory: {}", e)))),
                     }
                 } else {
-                    Some(Err("mkdir() requires a path argument".to_string()))
+                    Some(Err(raise(ErrorKind::TypeError, "mkdir() requires a path argument".to_string())))
                 }
             }
             "dir_list" => {
@@ -2537,7 +3798,7 @@ impl Interpreter {
                         }
                         Some(Ok(Value::Array(result)))
                     }
-                    Err(e) => Some(Err(format!("Failed to list directory: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, format!("Failed to list directory: {}", e)))),
                 }
             }
             // Threading operations
@@ -2550,10 +3811,10 @@ impl Interpreter {
                         });
                         Some(Ok(Value::Null))
                     } else {
-                        Some(Err("spawn() requires a string argument".to_string()))
+                        Some(Err(raise(ErrorKind::TypeError, "spawn() requires a string argument".to_string())))
                     }
                 } else {
-                    Some(Err("spawn() requires an argument".to_string()))
+                    Some(Err(raise(ErrorKind::TypeError, "spawn() requires an argument".to_string())))
                 }
             }
             "sleep" => {
@@ -2570,10 +3831,10 @@ impl Interpreter {
                     let path = arg.as_string();
                     match crate::ffi::ffi_open(&path) {
                         Ok(handle) => Some(Ok(Value::Int(handle as i64))),
-                        Err(e) => Some(Err(e)),
+                        Err(e) => Some(Err(raise(ErrorKind::Error, e))),
                     }
                 } else {
-                    Some(Err("ffi_open() requires a path argument".to_string()))
+                    Some(Err(raise(ErrorKind::TypeError, "ffi_open() requires a path argument".to_string())))
                 }
             }
             "ffi_get_symbol" => {
@@ -2582,12 +3843,10 @@ impl Interpreter {
                     let name = args[1].as_string();
                     match crate::ffi::ffi_get_symbol(lib_handle, &name) {
                         Ok(symbol) => Some(Ok(Value::Int(symbol as i64))),
-                        Err(e) => Some(Err(e)),
+                        Err(e) => Some(Err(raise(ErrorKind::Error, e))),
                     }
                 } else {
-                    Some(Err(
-                        "ffi_get_symbol() requires library handle and symbol name".to_string(),
-                    ))
+                    Some(Err(raise(ErrorKind::TypeError, "ffi_get_symbol() requires library handle and symbol name")))
                 }
             }
             "ffi_malloc" => {
@@ -2601,7 +3860,7 @@ impl Interpreter {
                     crate::ffi::ffi_free(ptr);
                     Some(Ok(Value::Null))
                 } else {
-                    Some(Err("ffi_free() requires a pointer argument".to_string()))
+                    Some(Err(raise(ErrorKind::TypeError, "ffi_free() requires a pointer argument".to_string())))
                 }
             }
             // GC functions
@@ -2641,7 +3900,7 @@ impl Interpreter {
                         Err(_) => Some(Ok(Value::Null)),
                     }
                 } else {
-                    Some(Err("env_get() requires a key argument".to_string()))
+                    Some(Err(raise(ErrorKind::TypeError, "env_get() requires a key argument".to_string())))
                 }
             }
             "env_set" => {
@@ -2651,7 +3910,7 @@ impl Interpreter {
                     std::env::set_var(&key, &val);
                     Some(Ok(Value::Null))
                 } else {
-                    Some(Err("env_set() requires key and value arguments".to_string()))
+                    Some(Err(raise(ErrorKind::TypeError, "env_set() requires key and value arguments".to_string())))
                 }
             }
             // Command execution
@@ -2667,10 +3926,10 @@ impl Interpreter {
                             let stdout = String::from_utf8_lossy(&output.stdout).to_string();
                             Some(Ok(Value::String(stdout)))
                         }
-                        Err(e) => Some(Err(format!("Command failed: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::Error, format!("Command failed: {}", e)))),
                     }
                 } else {
-                    Some(Err("exec() requires a command argument".to_string()))
+                    Some(Err(raise(ErrorKind::TypeError, "exec() requires a command argument".to_string())))
                 }
             }
             // String utilities
@@ -2679,7 +3938,7 @@ impl Interpreter {
                     let s = arg.as_string();
                     Some(Ok(Value::Int(s.len() as i64)))
                 } else {
-                    Some(Err("strlen() requires a string argument".to_string()))
+                    Some(Err(raise(ErrorKind::TypeError, "strlen() requires a string argument".to_string())))
                 }
             }
             "substring" => {
@@ -2690,9 +3949,7 @@ impl Interpreter {
                     let result = s.chars().skip(start).take(end - start).collect::<String>();
                     Some(Ok(Value::String(result)))
                 } else {
-                    Some(Err(
-                        "substring() requires string, start, and end arguments".to_string()
-                    ))
+                    Some(Err(raise(ErrorKind::TypeError, "substring() requires string, start, and end arguments".to_string())))
                 }
             }
             // ── Array utilities ──────────────────────────────────────────────
@@ -2704,10 +3961,10 @@ impl Interpreter {
                             new_arr.push(args[1].clone());
                             Some(Ok(Value::Array(new_arr)))
                         }
-                        _ => Some(Err("push() requires an array as first argument".to_string())),
+                        _ => Some(Err(raise(ErrorKind::TypeError, "push() requires an array as first argument".to_string()))),
                     }
                 } else {
-                    Some(Err("push(arr, elem) requires 2 arguments".to_string()))
+                    Some(Err(raise(ErrorKind::TypeError, "push(arr, elem) requires 2 arguments".to_string())))
                 }
             }
             // ── String operations ────────────────────────────────────────────
@@ -2809,7 +4066,7 @@ impl Interpreter {
                         s.split(&sep as &str).map(|p| Value::String(p.to_string())).collect()
                     };
                     Some(Ok(Value::Array(parts)))
-                } else { Some(Err("split(string, sep) requires 2 args".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "split(string, sep) requires 2 args".to_string()))) }
             }
             "join" => {
                 if args.len() >= 2 {
@@ -2817,27 +4074,27 @@ impl Interpreter {
                         let sep = args[1].as_string();
                         let joined = arr.iter().map(|v| v.as_string()).collect::<Vec<_>>().join(&sep);
                         Some(Ok(Value::String(joined)))
-                    } else { Some(Err("join() first arg must be array".to_string())) }
-                } else { Some(Err("join(array, sep) requires 2 args".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "join() first arg must be array".to_string()))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "join(array, sep) requires 2 args".to_string()))) }
             }
             "contains" => {
                 if args.len() >= 2 {
                     match &args[0] {
                         Value::String(s) => Some(Ok(Value::Bool(s.contains(&args[1].as_string() as &str)))),
                         Value::Array(arr) => Some(Ok(Value::Bool(arr.contains(&args[1])))),
-                        _ => Some(Err("contains() requires string or array".to_string())),
+                        _ => Some(Err(raise(ErrorKind::TypeError, "contains() requires string or array".to_string()))),
                     }
-                } else { Some(Err("contains(haystack, needle) requires 2 args".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "contains(haystack, needle) requires 2 args".to_string()))) }
             }
             "starts_with" => {
                 if args.len() >= 2 {
                     Some(Ok(Value::Bool(args[0].as_string().starts_with(&args[1].as_string() as &str))))
-                } else { Some(Err("starts_with() requires 2 args".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "starts_with() requires 2 args".to_string()))) }
             }
             "ends_with" => {
                 if args.len() >= 2 {
                     Some(Ok(Value::Bool(args[0].as_string().ends_with(&args[1].as_string() as &str))))
-                } else { Some(Err("ends_with() requires 2 args".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "ends_with() requires 2 args".to_string()))) }
             }
             "replace" => {
                 if args.len() >= 3 {
@@ -2845,7 +4102,7 @@ impl Interpreter {
                     let from = args[1].as_string();
                     let to = args[2].as_string();
                     Some(Ok(Value::String(s.replace(&from as &str, &to as &str))))
-                } else { Some(Err("replace(s, from, to) requires 3 args".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "replace(s, from, to) requires 3 args".to_string()))) }
             }
             "trim" => {
                 Some(Ok(Value::String(args.first().map(|v| v.as_string().trim().to_string()).unwrap_or_default())))
@@ -2867,7 +4124,7 @@ impl Interpreter {
                     let s = args[0].as_string();
                     let n = args[1].as_int() as usize;
                     Some(Ok(Value::String(s.repeat(n))))
-                } else { Some(Err("repeat(s, n) requires 2 args".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "repeat(s, n) requires 2 args".to_string()))) }
             }
             "index_of" | "find" => {
                 if args.len() >= 2 {
@@ -2880,16 +4137,16 @@ impl Interpreter {
                             let needle = &args[1];
                             Some(Ok(Value::Int(arr.iter().position(|v| v == needle).map(|i| i as i64).unwrap_or(-1))))
                         }
-                        _ => Some(Err("index_of() requires string or array".to_string())),
+                        _ => Some(Err(raise(ErrorKind::TypeError, "index_of() requires string or array".to_string()))),
                     }
-                } else { Some(Err("index_of(haystack, needle) requires 2 args".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "index_of(haystack, needle) requires 2 args".to_string()))) }
             }
             "char_at" => {
                 if args.len() >= 2 {
                     let s = args[0].as_string();
                     let idx = args[1].as_int() as usize;
                     Some(Ok(s.chars().nth(idx).map(|c| Value::String(c.to_string())).unwrap_or(Value::Null)))
-                } else { Some(Err("char_at(s, i) requires 2 args".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "char_at(s, i) requires 2 args".to_string()))) }
             }
             "parse_int" | "int" => {
                 if let Some(v) = args.first() {
@@ -2907,25 +4164,25 @@ impl Interpreter {
                         s.trim().parse::<i64>().map_err(|e| e.to_string())
                     };
                     Some(Ok(n.map(Value::Int).unwrap_or(Value::Null)))
-                } else { Some(Err("parse_int() requires argument".to_string())) }
+                } else { Some(Err(raise(ErrorKind::ValueError, "parse_int() requires argument".to_string()))) }
             }
             "parse_float" | "float" => {
                 if let Some(v) = args.first() {
                     let s = v.as_string();
                     Some(Ok(s.trim().parse::<f64>().map(Value::Float).unwrap_or(Value::Null)))
-                } else { Some(Err("parse_float() requires argument".to_string())) }
+                } else { Some(Err(raise(ErrorKind::ValueError, "parse_float() requires argument".to_string()))) }
             }
             "chars" => {
                 if let Some(v) = args.first() {
                     let chars: Vec<Value> = v.as_string().chars().map(|c| Value::String(c.to_string())).collect();
                     Some(Ok(Value::Array(chars)))
-                } else { Some(Err("chars() requires argument".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "chars() requires argument".to_string()))) }
             }
             "bytes" => {
                 if let Some(v) = args.first() {
                     let bytes: Vec<Value> = v.as_string().bytes().map(|b| Value::Int(b as i64)).collect();
                     Some(Ok(Value::Array(bytes)))
-                } else { Some(Err("bytes() requires argument".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "bytes() requires argument".to_string()))) }
             }
             "pad_left" => {
                 if args.len() >= 2 {
@@ -2935,7 +4192,7 @@ impl Interpreter {
                     let pad_char = pad.chars().next().unwrap_or(' ');
                     let _padded = format!("{:>width$}", s, width = width).replace(' ', &pad_char.to_string());
                     Some(Ok(Value::String(format!("{}{}", pad_char.to_string().repeat(width.saturating_sub(s.len())), s))))
-                } else { Some(Err("pad_left(s, width [, char]) requires 2+ args".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "pad_left(s, width [, char]) requires 2+ args".to_string()))) }
             }
             "pad_right" => {
                 if args.len() >= 2 {
@@ -2943,13 +4200,13 @@ impl Interpreter {
                     let width = args[1].as_int() as usize;
                     let pad_char = args.get(2).and_then(|v| v.as_string().chars().next()).unwrap_or(' ');
                     Some(Ok(Value::String(format!("{:<width$}", s, width = width).replace(' ', &pad_char.to_string()))))
-                } else { Some(Err("pad_right(s, width [, char]) requires 2+ args".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "pad_right(s, width [, char]) requires 2+ args".to_string()))) }
             }
             // ── Math operations ──────────────────────────────────────────────
             "abs" => {
                 if let Some(v) = args.first() {
-                    Some(Ok(match v { Value::Int(i) => Value::Int(i.abs()), Value::Float(f) => Value::Float(f.abs()), _ => return Some(Err("abs() requires numeric argument".to_string())) }))
-                } else { Some(Err("abs() requires argument".to_string())) }
+                    Some(Ok(match v { Value::Int(i) => Value::Int(i.abs()), Value::Float(f) => Value::Float(f.abs()), _ => return Some(Err(raise(ErrorKind::TypeError, "abs() requires numeric argument".to_string()))) }))
+                } else { Some(Err(raise(ErrorKind::TypeError, "abs() requires argument".to_string()))) }
             }
             "min" => {
                 if args.len() == 1 {
@@ -2963,11 +4220,11 @@ impl Interpreter {
                             }
                         }
                         Some(Ok(m))
-                    } else { Some(Err("min(array) or min(a, b)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "min(array) or min(a, b)".to_string()))) }
                 } else if args.len() >= 2 {
                     let cmp = self.eval_binary_op("<", args[0].clone(), args[1].clone()).unwrap_or(Value::Bool(false));
                     Some(Ok(if cmp.is_truthy() { args[0].clone() } else { args[1].clone() }))
-                } else { Some(Err("min(a, b) or min(array)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "min(a, b) or min(array)".to_string()))) }
             }
             "max" => {
                 if args.len() == 1 {
@@ -2981,16 +4238,16 @@ impl Interpreter {
                             }
                         }
                         Some(Ok(m))
-                    } else { Some(Err("max(array) or max(a, b)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "max(array) or max(a, b)".to_string()))) }
                 } else if args.len() >= 2 {
                     let cmp = self.eval_binary_op(">", args[0].clone(), args[1].clone()).unwrap_or(Value::Bool(false));
                     Some(Ok(if cmp.is_truthy() { args[0].clone() } else { args[1].clone() }))
-                } else { Some(Err("max(a, b) or max(array)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "max(a, b) or max(array)".to_string()))) }
             }
             "sqrt" => {
                 if let Some(v) = args.first() {
                     Some(Ok(Value::Float(v.as_float().sqrt())))
-                } else { Some(Err("sqrt() requires argument".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "sqrt() requires argument".to_string()))) }
             }
             "pow" => {
                 if args.len() >= 2 {
@@ -3001,7 +4258,7 @@ impl Interpreter {
                     } else {
                         Some(Ok(Value::Float(base.powf(exp))))
                     }
-                } else { Some(Err("pow(base, exp) requires 2 args".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "pow(base, exp) requires 2 args".to_string()))) }
             }
             "floor" => { Some(Ok(Value::Float(args.first().map(|v| v.as_float().floor()).unwrap_or(0.0)))) }
             "ceil" => { Some(Ok(Value::Float(args.first().map(|v| v.as_float().ceil()).unwrap_or(0.0)))) }
@@ -3015,7 +4272,7 @@ impl Interpreter {
             "atan" => { Some(Ok(Value::Float(args.first().map(|v| v.as_float().atan()).unwrap_or(0.0)))) }
             "atan2" => {
                 if args.len() >= 2 { Some(Ok(Value::Float(args[0].as_float().atan2(args[1].as_float()))))
-                } else { Some(Err("atan2(y, x) requires 2 args".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "atan2(y, x) requires 2 args".to_string()))) }
             }
             "log" | "ln" => { Some(Ok(Value::Float(args.first().map(|v| v.as_float().ln()).unwrap_or(0.0)))) }
             "log2" => { Some(Ok(Value::Float(args.first().map(|v| v.as_float().log2()).unwrap_or(0.0)))) }
@@ -3041,14 +4298,14 @@ impl Interpreter {
                     let mut a = arr.clone();
                     let last = a.pop().unwrap_or(Value::Null);
                     Some(Ok(Value::Array(vec![Value::Array(a), last])))
-                } else { Some(Err("pop() requires array".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "pop() requires array".to_string()))) }
             }
             "shift" => {
                 if let Some(Value::Array(arr)) = args.first() {
                     if arr.is_empty() { return Some(Ok(Value::Null)); }
                     let first = arr[0].clone();
                     Some(Ok(first))
-                } else { Some(Err("shift() requires array".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "shift() requires array".to_string()))) }
             }
             "insert" => {
                 if args.len() >= 3 {
@@ -3057,8 +4314,8 @@ impl Interpreter {
                         let mut a = arr.clone();
                         a.insert(idx.min(a.len()), args[2].clone());
                         Some(Ok(Value::Array(a)))
-                    } else { Some(Err("insert() first arg must be array".to_string())) }
-                } else { Some(Err("insert(arr, idx, val) requires 3 args".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "insert() first arg must be array".to_string()))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "insert(arr, idx, val) requires 3 args".to_string()))) }
             }
             "remove" => {
                 if args.len() >= 2 {
@@ -3067,8 +4324,8 @@ impl Interpreter {
                         let mut a = arr.clone();
                         if idx < a.len() { a.remove(idx); }
                         Some(Ok(Value::Array(a)))
-                    } else { Some(Err("remove() first arg must be array".to_string())) }
-                } else { Some(Err("remove(arr, idx) requires 2 args".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "remove() first arg must be array".to_string()))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "remove(arr, idx) requires 2 args".to_string()))) }
             }
             "sort" => {
                 if let Some(Value::Array(arr)) = args.first() {
@@ -3082,7 +4339,7 @@ impl Interpreter {
                         }
                     });
                     Some(Ok(Value::Array(a)))
-                } else { Some(Err("sort() requires array".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "sort() requires array".to_string()))) }
             }
             "reverse" => {
                 if let Some(Value::Array(arr)) = args.first() {
@@ -3092,7 +4349,7 @@ impl Interpreter {
                 } else if let Some(v) = args.first() {
                     let s: String = v.as_string().chars().rev().collect();
                     Some(Ok(Value::String(s)))
-                } else { Some(Err("reverse() requires array or string".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "reverse() requires array or string".to_string()))) }
             }
             "concat" => {
                 let mut result = Vec::new();
@@ -3117,7 +4374,7 @@ impl Interpreter {
                         let end = (args[2].as_int() as usize).min(s.chars().count());
                         Some(Ok(Value::String(s.chars().skip(start).take(end - start).collect())))
                     }
-                } else { Some(Err("slice(arr, start, end) requires 3 args".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "slice(arr, start, end) requires 3 args".to_string()))) }
             }
             "flatten" => {
                 if let Some(Value::Array(arr)) = args.first() {
@@ -3127,7 +4384,7 @@ impl Interpreter {
                         else { flat.push(v.clone()); }
                     }
                     Some(Ok(Value::Array(flat)))
-                } else { Some(Err("flatten() requires array".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "flatten() requires array".to_string()))) }
             }
             "unique" | "dedup" => {
                 if let Some(Value::Array(arr)) = args.first() {
@@ -3137,7 +4394,7 @@ impl Interpreter {
                         if !seen.contains(v) { seen.push(v.clone()); result.push(v.clone()); }
                     }
                     Some(Ok(Value::Array(result)))
-                } else { Some(Err("unique() requires array".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "unique() requires array".to_string()))) }
             }
             "sum" => {
                 if let Some(Value::Array(arr)) = args.first() {
@@ -3148,7 +4405,7 @@ impl Interpreter {
                     } else {
                         Some(Ok(Value::Int(total)))
                     }
-                } else { Some(Err("sum() requires array".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "sum() requires array".to_string()))) }
             }
             "product" => {
                 if let Some(Value::Array(arr)) = args.first() {
@@ -3158,7 +4415,7 @@ impl Interpreter {
                     } else {
                         Some(Ok(Value::Int(arr.iter().fold(1i64, |acc, v| acc * v.as_int()))))
                     }
-                } else { Some(Err("product() requires array".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "product() requires array".to_string()))) }
             }
             "range" => {
                 // range(end) or range(start, end) or range(start, end, step)
@@ -3179,8 +4436,8 @@ impl Interpreter {
                             .map(|(x, y)| Value::Array(vec![x.clone(), y.clone()]))
                             .collect();
                         Some(Ok(Value::Array(zipped)))
-                    } else { Some(Err("zip() requires two arrays".to_string())) }
-                } else { Some(Err("zip(a, b) requires 2 args".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "zip() requires two arrays".to_string()))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "zip(a, b) requires 2 args".to_string()))) }
             }
             "count" => {
                 if args.len() >= 2 {
@@ -3194,10 +4451,10 @@ impl Interpreter {
                     }
                 } else if let Some(Value::Array(arr)) = args.first() {
                     Some(Ok(Value::Int(arr.len() as i64)))
-                } else { Some(Err("count() requires array".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "count() requires array".to_string()))) }
             }
             "any" => {
-                if args.is_empty() { return Some(Err("any(array[, fn])".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "any(array[, fn])".to_string()))); }
                 if let Value::Array(arr) = &args[0] {
                     if args.len() >= 2 {
                         // any(array, closure) — test each with closure
@@ -3210,10 +4467,10 @@ impl Interpreter {
                     } else {
                         Some(Ok(Value::Bool(arr.iter().any(|v| v.is_truthy()))))
                     }
-                } else { Some(Err("any: expected array".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "any: expected array".to_string()))) }
             }
             "all" => {
-                if args.is_empty() { return Some(Err("all(array[, fn])".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "all(array[, fn])".to_string()))); }
                 if let Value::Array(arr) = &args[0] {
                     if args.len() >= 2 {
                         // all(array, closure) — test each with closure
@@ -3226,17 +4483,17 @@ impl Interpreter {
                     } else {
                         Some(Ok(Value::Bool(arr.iter().all(|v| v.is_truthy()))))
                     }
-                } else { Some(Err("all: expected array".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "all: expected array".to_string()))) }
             }
             "first" | "head" => {
                 if let Some(Value::Array(arr)) = args.first() {
                     Some(Ok(arr.first().cloned().unwrap_or(Value::Null)))
-                } else { Some(Err("first() requires array".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "first() requires array".to_string()))) }
             }
             "last" => {
                 if let Some(Value::Array(arr)) = args.first() {
                     Some(Ok(arr.last().cloned().unwrap_or(Value::Null)))
-                } else { Some(Err("last() requires array".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "last() requires array".to_string()))) }
             }
             "is_empty" => {
                 if let Some(v) = args.first() {
@@ -3262,25 +4519,6 @@ impl Interpreter {
                 eprintln!("PANIC: {}", msg);
                 std::process::exit(101);
             }
-            "assert" => {
-                let cond = args.first().map(|v| v.is_truthy()).unwrap_or(false);
-                if !cond {
-                    let msg = args.get(1).map(|v| v.as_string()).unwrap_or_else(|| "assertion failed".to_string());
-                    eprintln!("ASSERTION FAILED: {}", msg);
-                    std::process::exit(1);
-                }
-                Some(Ok(Value::Null))
-            }
-            "assert_eq" => {
-                if args.len() >= 2 {
-                    if args[0] != args[1] {
-                        let msg = args.get(2).map(|v| v.as_string()).unwrap_or_else(|| format!("assert_eq failed: {:?} != {:?}", args[0], args[1]));
-                        eprintln!("ASSERTION FAILED: {}", msg);
-                        std::process::exit(1);
-                    }
-                    Some(Ok(Value::Null))
-                } else { Some(Err("assert_eq(a, b) requires 2 args".to_string())) }
-            }
             "dbg" => {
                 if let Some(v) = args.first() {
                     eprintln!("[dbg] {:?}", v);
@@ -3311,17 +4549,17 @@ impl Interpreter {
                     let path = arg.as_string();
                     match fs::read_to_string(&path) {
                         Ok(contents) => Some(Ok(Value::String(contents))),
-                        Err(e) => Some(Err(format!("read_file: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, format!("read_file: {}", e)))),
                     }
-                } else { Some(Err("read_file(path)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "read_file(path)".to_string()))) }
             }
             "write_file" => {
                 if args.len() >= 2 {
                     match fs::write(args[0].as_string(), args[1].as_string()) {
                         Ok(_) => Some(Ok(Value::Null)),
-                        Err(e) => Some(Err(format!("write_file: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, format!("write_file: {}", e)))),
                     }
-                } else { Some(Err("write_file(path, content)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "write_file(path, content)".to_string()))) }
             }
             "args" => {
                 let a: Vec<Value> = std::env::args().skip(1).map(Value::String).collect();
@@ -3336,9 +4574,9 @@ impl Interpreter {
                 if let Some(v) = args.first() {
                     match std::env::set_current_dir(v.as_string()) {
                         Ok(_) => Some(Ok(Value::Null)),
-                        Err(e) => Some(Err(format!("chdir: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::Error, format!("chdir: {}", e)))),
                     }
-                } else { Some(Err("chdir(path)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "chdir(path)".to_string()))) }
             }
             "getpid" => Some(Ok(Value::Int(std::process::id() as i64))),
             // ── Networking extras (full impls are in TCP/UDP section below) ──
@@ -3354,7 +4592,7 @@ impl Interpreter {
                             result.push(r);
                         }
                         Some(Ok(Value::Array(result)))
-                    } else { Some(Err("map(array, fn)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "map(array, fn)".to_string()))) }
                 } else { None }
             }
             "filter_fn" | "filter" => {
@@ -3368,7 +4606,7 @@ impl Interpreter {
                             if r.is_truthy() { result.push(item); }
                         }
                         Some(Ok(Value::Array(result)))
-                    } else { Some(Err("filter(array, fn)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "filter(array, fn)".to_string()))) }
                 } else { None }
             }
             "reduce" | "fold" => {
@@ -3380,7 +4618,7 @@ impl Interpreter {
                             acc = match self.call_value(closure.clone(), vec![acc, item]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
                         }
                         Some(Ok(acc))
-                    } else { Some(Err("reduce(array, fn, initial)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "reduce(array, fn, initial)".to_string()))) }
                 } else { None }
             }
             "for_each" => {
@@ -3391,7 +4629,7 @@ impl Interpreter {
                             match self.call_value(closure.clone(), vec![item]) { Ok(_) => {}, Err(e) => return Some(Err(e)) };
                         }
                         Some(Ok(Value::Null))
-                    } else { Some(Err("for_each(array, fn)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "for_each(array, fn)".to_string()))) }
                 } else { None }
             }
             "find_fn" => {
@@ -3403,7 +4641,7 @@ impl Interpreter {
                             if r.is_truthy() { return Some(Ok(item)); }
                         }
                         Some(Ok(Value::Null))
-                    } else { Some(Err("find_fn(array, fn)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "find_fn(array, fn)".to_string()))) }
                 } else { None }
             }
             "flat_map" => {
@@ -3417,7 +4655,7 @@ impl Interpreter {
                             else { result.push(r); }
                         }
                         Some(Ok(Value::Array(result)))
-                    } else { Some(Err("flat_map(array, fn)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "flat_map(array, fn)".to_string()))) }
                 } else { None }
             }
             "call" => {
@@ -3425,7 +4663,7 @@ impl Interpreter {
                 if let Some(callable) = args.first() {
                     let rest = args[1..].to_vec();
                     Some(self.call_value(callable.clone(), rest))
-                } else { Some(Err("call(fn, args...)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "call(fn, args...)".to_string()))) }
             }
             "sorted_by" | "sort_by" => {
                 // sorted_by(array, |a, b| a < b) — sort using comparator closure
@@ -3433,7 +4671,7 @@ impl Interpreter {
                     if let Value::Array(arr) = &args[0] {
                         let cmp_fn = args[1].clone();
                         let mut new_arr = arr.clone();
-                        let mut sort_err: Option<String> = None;
+                        let mut sort_err: Option<RuntimeError> = None;
                         new_arr.sort_by(|a, b| {
                             if sort_err.is_some() { return std::cmp::Ordering::Equal; }
                             match self.call_value(cmp_fn.clone(), vec![a.clone(), b.clone()]) {
@@ -3446,7 +4684,7 @@ impl Interpreter {
                         });
                         if let Some(e) = sort_err { return Some(Err(e)); }
                         Some(Ok(Value::Array(new_arr)))
-                    } else { Some(Err("sorted_by(array, comparator_fn)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "sorted_by(array, comparator_fn)".to_string()))) }
                 } else { None }
             }
             // ── Map/Dict operations ──────────────────────────────────────────
@@ -3454,7 +4692,7 @@ impl Interpreter {
             "keys" => {
                 if let Some(Value::Map(m)) = args.first() {
                     Some(Ok(Value::Array(m.keys().map(|k| Value::String(k.clone())).collect())))
-                } else { Some(Err("keys(map)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "keys(map)".to_string()))) }
             }
             "values" => {
                 if let Some(Value::Map(m)) = args.first() {
@@ -3462,7 +4700,7 @@ impl Interpreter {
                 } else if let Some(Value::Array(_)) = args.first() {
                     // values(array) → same as the array itself
                     Some(Ok(args[0].clone()))
-                } else { Some(Err("values(map)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "values(map)".to_string()))) }
             }
             "get" => {
                 if args.len() >= 2 {
@@ -3477,7 +4715,7 @@ impl Interpreter {
                             let idx = if idx < 0 { arr.len() as i64 + idx } else { idx } as usize;
                             Some(Ok(arr.get(idx).cloned().unwrap_or(Value::Null)))
                         }
-                        _ => Some(Err("get(dict, key) or get(array, idx)".to_string())),
+                        _ => Some(Err(raise(ErrorKind::TypeError, "get(dict, key) or get(array, idx)".to_string()))),
                     }
                 } else { None }
             }
@@ -3486,7 +4724,7 @@ impl Interpreter {
                     if let Value::Map(mut m) = args[0].clone() {
                         m.insert(args[1].as_string(), args[2].clone());
                         Some(Ok(Value::Map(m)))
-                    } else { Some(Err("set(map, key, val)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "set(map, key, val)".to_string()))) }
                 } else { None }
             }
             "del_key" | "delete" => {
@@ -3494,14 +4732,14 @@ impl Interpreter {
                     if let Value::Map(mut m) = args[0].clone() {
                         m.remove(&args[1].as_string());
                         Some(Ok(Value::Map(m)))
-                    } else { Some(Err("del_key(map, key)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "del_key(map, key)".to_string()))) }
                 } else { None }
             }
             "has_key" | "has" => {
                 if args.len() >= 2 {
                     if let Value::Map(m) = &args[0] {
                         Some(Ok(Value::Bool(m.contains_key(&args[1].as_string()))))
-                    } else { Some(Err("has_key(map, key)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "has_key(map, key)".to_string()))) }
                 } else { None }
             }
             "merge_maps" | "merge" => {
@@ -3509,7 +4747,7 @@ impl Interpreter {
                     if let (Value::Map(mut a), Value::Map(b)) = (args[0].clone(), args[1].clone()) {
                         for (k, v) in b { a.insert(k, v); }
                         Some(Ok(Value::Map(a)))
-                    } else { Some(Err("merge(map1, map2)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "merge(map1, map2)".to_string()))) }
                 } else { None }
             }
             "from_pairs" | "from_entries" => {
@@ -3523,7 +4761,7 @@ impl Interpreter {
                         }
                     }
                     Some(Ok(Value::Map(m)))
-                } else { Some(Err("from_pairs([[key,val], ...])".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "from_pairs([[key,val], ...])".to_string()))) }
             }
             "entries" => {
                 if let Some(Value::Map(m)) = args.first() {
@@ -3539,7 +4777,7 @@ impl Interpreter {
                     if let Value::Tuple(t) = &args[0] {
                         let idx = args[1].as_int() as usize;
                         Some(Ok(t.get(idx).cloned().unwrap_or(Value::Null)))
-                    } else { Some(Err("tuple_get(tuple, idx)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "tuple_get(tuple, idx)".to_string()))) }
                 } else { None }
             }
             // ── JSON serialization ────────────────────────────────────────────
@@ -3564,9 +4802,9 @@ impl Interpreter {
                         .output()
                     {
                         Ok(out) => Some(Ok(Value::String(String::from_utf8_lossy(&out.stdout).to_string()))),
-                        Err(e) => Some(Err(format!("http_post failed: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, format!("http_post failed: {}", e)))),
                     }
-                } else { Some(Err("http_post(url, body, [content_type])".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "http_post(url, body, [content_type])".to_string()))) }
             }
             "http_put" => {
                 if args.len() >= 2 {
@@ -3577,17 +4815,17 @@ impl Interpreter {
                         .output()
                     {
                         Ok(out) => Some(Ok(Value::String(String::from_utf8_lossy(&out.stdout).to_string()))),
-                        Err(e) => Some(Err(format!("http_put failed: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, format!("http_put failed: {}", e)))),
                     }
-                } else { Some(Err("http_put(url, body)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "http_put(url, body)".to_string()))) }
             }
             "http_delete" => {
                 if let Some(v) = args.first() {
                     match std::process::Command::new("curl").args(&["-s", "-X", "DELETE", &v.as_string()]).output() {
                         Ok(out) => Some(Ok(Value::String(String::from_utf8_lossy(&out.stdout).to_string()))),
-                        Err(e) => Some(Err(format!("http_delete failed: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, format!("http_delete failed: {}", e)))),
                     }
-                } else { Some(Err("http_delete(url)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "http_delete(url)".to_string()))) }
             }
             // ── Regex (via process) ───────────────────────────────────────────
             // regex_match(pattern, text) -> bool  [updated: uses regex crate, pattern first]
@@ -3597,13 +4835,16 @@ impl Interpreter {
                     let text = args[1].as_string();
                     match regex::Regex::new(&pat) {
                         Ok(re) => Some(Ok(Value::Bool(re.is_match(&text)))),
-                        Err(e) => Some(Err(format!("regex_match: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("regex_match: {}", e)))),
                     }
-                } else { Some(Err("regex_match(pattern, text)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "regex_match(pattern, text)".to_string()))) }
             }
             // ── Type conversions ──────────────────────────────────────────────
             "str" | "string" => {
-                Some(Ok(Value::String(args.first().map(|v| v.as_string()).unwrap_or_default())))
+                match args.first().map(|v| self.display(v)).transpose() {
+                    Ok(s) => Some(Ok(Value::String(s.unwrap_or_default()))),
+                    Err(e) => Some(Err(e)),
+                }
             }
             "num" | "number" => {
                 if let Some(v) = args.first() {
@@ -3697,7 +4938,7 @@ impl Interpreter {
                         m.insert("code".to_string(), Value::Int(code as i64));
                         Some(Ok(Value::Map(m)))
                     }
-                    Err(e) => Some(Err(format!("shell: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::Error, format!("shell: {}", e)))),
                 }
             }
             "pipe" => {
@@ -3770,14 +5011,14 @@ impl Interpreter {
                     let code = args[0].as_int();
                     let text = args[1].as_string();
                     Some(Ok(Value::String(format!("\x1b[38;5;{}m{}\x1b[0m", code, text))))
-                } else { Some(Err("color_256(code, text)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "color_256(code, text)".to_string()))) }
             }
             "bg_color_256" => {
                 if args.len() >= 2 {
                     let code = args[0].as_int();
                     let text = args[1].as_string();
                     Some(Ok(Value::String(format!("\x1b[48;5;{}m{}\x1b[0m", code, text))))
-                } else { Some(Err("bg_color_256(code, text)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "bg_color_256(code, text)".to_string()))) }
             }
             "rgb" => {
                 if args.len() >= 4 {
@@ -3786,7 +5027,7 @@ impl Interpreter {
                     let b = args[2].as_int();
                     let text = args[3].as_string();
                     Some(Ok(Value::String(format!("\x1b[38;2;{};{};{}m{}\x1b[0m", r, g, b, text))))
-                } else { Some(Err("rgb(r, g, b, text)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "rgb(r, g, b, text)".to_string()))) }
             }
             "bg_rgb" => {
                 if args.len() >= 4 {
@@ -3795,7 +5036,7 @@ impl Interpreter {
                     let b = args[2].as_int();
                     let text = args[3].as_string();
                     Some(Ok(Value::String(format!("\x1b[48;2;{};{};{}m{}\x1b[0m", r, g, b, text))))
-                } else { Some(Err("bg_rgb(r, g, b, text)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "bg_rgb(r, g, b, text)".to_string()))) }
             }
 
             // ── Terminal Cursor / Screen Control ──────────────────────────────
@@ -3889,11 +5130,11 @@ impl Interpreter {
             }
             "table_format" => {
                 // table_format(headers, rows) -> string table
-                if args.len() < 2 { return Some(Err("table_format(headers, rows)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "table_format(headers, rows)".to_string()))); }
                 let headers = if let Value::Array(h) = &args[0] {
                     h.iter().map(|v| v.as_string()).collect::<Vec<_>>()
-                } else { return Some(Err("headers must be array".to_string())); };
-                let rows_val = if let Value::Array(r) = &args[1] { r.clone() } else { return Some(Err("rows must be array".to_string())); };
+                } else { return Some(Err(raise(ErrorKind::TypeError, "headers must be array".to_string()))); };
+                let rows_val = if let Value::Array(r) = &args[1] { r.clone() } else { return Some(Err(raise(ErrorKind::TypeError, "rows must be array".to_string()))); };
                 // Calculate column widths
                 let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
                 let rows: Vec<Vec<String>> = rows_val.iter().map(|row| {
@@ -3926,7 +5167,7 @@ impl Interpreter {
             }
             "progress_bar" => {
                 // progress_bar(current, total, width?)
-                if args.len() < 2 { return Some(Err("progress_bar(current, total, width?)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "progress_bar(current, total, width?)".to_string()))); }
                 let current = args[0].as_float();
                 let total = args[1].as_float();
                 let width = args.get(2).map(|v| v.as_int() as usize).unwrap_or(40);
@@ -4008,14 +5249,14 @@ impl Interpreter {
             "cwd" | "getcwd" => {
                 match std::env::current_dir() {
                     Ok(p) => Some(Ok(Value::String(p.to_string_lossy().to_string()))),
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
             "chdir" | "set_cwd" => {
                 let p = args.first().map(|v| v.as_string()).unwrap_or_default();
                 match std::env::set_current_dir(&p) {
                     Ok(()) => Some(Ok(Value::Bool(true))),
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
             "ls" | "list_dir" | "read_dir" => {
@@ -4028,7 +5269,7 @@ impl Interpreter {
                             .collect();
                         Some(Ok(Value::Array(files)))
                     }
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
             "mkdir" | "make_dir" => {
@@ -4041,28 +5282,28 @@ impl Interpreter {
                 };
                 match result {
                     Ok(()) => Some(Ok(Value::Bool(true))),
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
             "rmdir" | "remove_dir" => {
                 let p = args.first().map(|v| v.as_string()).unwrap_or_default();
                 match std::fs::remove_dir_all(&p) {
                     Ok(()) => Some(Ok(Value::Bool(true))),
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
             "copy_file" | "file_copy" => {
-                if args.len() < 2 { return Some(Err("copy_file(src, dst)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "copy_file(src, dst)".to_string()))); }
                 match std::fs::copy(args[0].as_string(), args[1].as_string()) {
                     Ok(bytes) => Some(Ok(Value::Int(bytes as i64))),
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
             "move_file" | "rename" => {
-                if args.len() < 2 { return Some(Err("rename(src, dst)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "rename(src, dst)".to_string()))); }
                 match std::fs::rename(args[0].as_string(), args[1].as_string()) {
                     Ok(()) => Some(Ok(Value::Bool(true))),
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
             "file_meta" | "stat" => {
@@ -4081,7 +5322,7 @@ impl Interpreter {
                         }
                         Some(Ok(Value::Map(m)))
                     }
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
 
@@ -4224,28 +5465,28 @@ impl Interpreter {
             "atan2" => {
                 if args.len() >= 2 {
                     Some(Ok(Value::Float(args[0].as_float().atan2(args[1].as_float()))))
-                } else { Some(Err("atan2(y, x)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "atan2(y, x)".to_string()))) }
             }
             "hypot" => {
                 if args.len() >= 2 {
                     Some(Ok(Value::Float(args[0].as_float().hypot(args[1].as_float()))))
-                } else { Some(Err("hypot(x, y)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "hypot(x, y)".to_string()))) }
             }
             "factorial" => {
                 let n = args.first().map(|v| v.as_int()).unwrap_or(0);
-                if n < 0 { return Some(Err("factorial: negative input".to_string())); }
+                if n < 0 { return Some(Err(raise(ErrorKind::Error, "factorial: negative input".to_string()))); }
                 let result = (1i64..=n).fold(1i64, |a, b| a.saturating_mul(b));
                 Some(Ok(Value::Int(result)))
             }
             "gcd" => {
-                if args.len() < 2 { return Some(Err("gcd(a, b)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "gcd(a, b)".to_string()))); }
                 let mut a = args[0].as_int().abs();
                 let mut b = args[1].as_int().abs();
                 while b != 0 { let t = b; b = a % b; a = t; }
                 Some(Ok(Value::Int(a)))
             }
             "lcm" => {
-                if args.len() < 2 { return Some(Err("lcm(a, b)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "lcm(a, b)".to_string()))); }
                 let a = args[0].as_int().abs();
                 let b = args[1].as_int().abs();
                 let mut ga = a; let mut gb = b;
@@ -4282,7 +5523,7 @@ impl Interpreter {
                     let x = args[0].as_float();
                     let base = args[1].as_float();
                     Some(Ok(Value::Float(x.ln() / base.ln())))
-                } else { Some(Err("log_base(x, base)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "log_base(x, base)".to_string()))) }
             }
             "deg2rad" | "to_radians" => {
                 Some(Ok(Value::Float(args.first().map(|v| v.as_float() * std::f64::consts::PI / 180.0).unwrap_or(0.0))))
@@ -4304,7 +5545,7 @@ impl Interpreter {
                     let b = args[1].as_float();
                     let t = args[2].as_float();
                     Some(Ok(Value::Float(a + (b - a) * t)))
-                } else { Some(Err("lerp(a, b, t)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "lerp(a, b, t)".to_string()))) }
             }
             "map_range" | "remap" => {
                 // remap(val, in_min, in_max, out_min, out_max)
@@ -4316,7 +5557,7 @@ impl Interpreter {
                     let out_max = args[4].as_float();
                     let result = (v - in_min) / (in_max - in_min) * (out_max - out_min) + out_min;
                     Some(Ok(Value::Float(result)))
-                } else { Some(Err("remap(val, in_min, in_max, out_min, out_max)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "remap(val, in_min, in_max, out_min, out_max)".to_string()))) }
             }
             "sign" | "signum" => {
                 if let Some(v) = args.first() {
@@ -4333,7 +5574,7 @@ impl Interpreter {
                     let lo = args[1].as_float();
                     let hi = args[2].as_float();
                     Some(Ok(Value::Bool(v >= lo && v <= hi)))
-                } else { Some(Err("between(val, lo, hi)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "between(val, lo, hi)".to_string()))) }
             }
 
             // ── Sorting with comparator ────────────────────────────────────────
@@ -4354,7 +5595,7 @@ impl Interpreter {
                         });
                         let sorted: Vec<Value> = indices.iter().map(|&i| arr[i].clone()).collect();
                         Some(Ok(Value::Array(sorted)))
-                    } else { Some(Err("sort_by(array, key_fn)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "sort_by(array, key_fn)".to_string()))) }
                 } else { None }
             }
             "sort_by_desc" => {
@@ -4370,7 +5611,7 @@ impl Interpreter {
                                 .unwrap_or(std::cmp::Ordering::Equal)
                         });
                         Some(Ok(Value::Array(indices.iter().map(|&i| arr[i].clone()).collect())))
-                    } else { Some(Err("sort_by_desc(array, key_fn)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "sort_by_desc(array, key_fn)".to_string()))) }
                 } else { None }
             }
             "group_by" => {
@@ -4388,7 +5629,7 @@ impl Interpreter {
                             .map(|(k, v)| (k, Value::Array(v)))
                             .collect();
                         Some(Ok(Value::Map(result)))
-                    } else { Some(Err("group_by(array, key_fn)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "group_by(array, key_fn)".to_string()))) }
                 } else { None }
             }
             "zip" => {
@@ -4398,7 +5639,7 @@ impl Interpreter {
                             .map(|(x, y)| Value::Array(vec![x.clone(), y.clone()]))
                             .collect();
                         Some(Ok(Value::Array(result)))
-                    } else { Some(Err("zip(array, array)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "zip(array, array)".to_string()))) }
                 } else { None }
             }
             "zip_with" => {
@@ -4412,7 +5653,7 @@ impl Interpreter {
                             result.push(r);
                         }
                         Some(Ok(Value::Array(result)))
-                    } else { Some(Err("zip_with(a, b, fn)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "zip_with(a, b, fn)".to_string()))) }
                 } else { None }
             }
             "take" => {
@@ -4420,7 +5661,7 @@ impl Interpreter {
                     if let Value::Array(arr) = &args[0] {
                         let n = args[1].as_int() as usize;
                         Some(Ok(Value::Array(arr.iter().take(n).cloned().collect())))
-                    } else { Some(Err("take(array, n)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "take(array, n)".to_string()))) }
                 } else { None }
             }
             "drop" | "skip" => {
@@ -4428,7 +5669,7 @@ impl Interpreter {
                     if let Value::Array(arr) = &args[0] {
                         let n = args[1].as_int() as usize;
                         Some(Ok(Value::Array(arr.iter().skip(n).cloned().collect())))
-                    } else { Some(Err("drop(array, n)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "drop(array, n)".to_string()))) }
                 } else { None }
             }
             "take_while" => {
@@ -4441,7 +5682,7 @@ impl Interpreter {
                             if r.is_truthy() { result.push(item); } else { break; }
                         }
                         Some(Ok(Value::Array(result)))
-                    } else { Some(Err("take_while(array, fn)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "take_while(array, fn)".to_string()))) }
                 } else { None }
             }
             "drop_while" | "skip_while" => {
@@ -4458,7 +5699,7 @@ impl Interpreter {
                             result.push(item);
                         }
                         Some(Ok(Value::Array(result)))
-                    } else { Some(Err("drop_while(array, fn)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "drop_while(array, fn)".to_string()))) }
                 } else { None }
             }
             "partition" => {
@@ -4471,31 +5712,31 @@ impl Interpreter {
                             if r.is_truthy() { yes.push(item); } else { no.push(item); }
                         }
                         Some(Ok(Value::Tuple(vec![Value::Array(yes), Value::Array(no)])))
-                    } else { Some(Err("partition(array, fn)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "partition(array, fn)".to_string()))) }
                 } else { None }
             }
             "chunks" | "chunk" => {
                 if args.len() >= 2 {
                     if let Value::Array(arr) = &args[0] {
                         let n = args[1].as_int() as usize;
-                        if n == 0 { return Some(Err("chunk size must be > 0".to_string())); }
+                        if n == 0 { return Some(Err(raise(ErrorKind::TypeError, "chunk size must be > 0".to_string()))); }
                         let chunks = arr.chunks(n)
                             .map(|c| Value::Array(c.to_vec()))
                             .collect();
                         Some(Ok(Value::Array(chunks)))
-                    } else { Some(Err("chunks(array, size)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "chunks(array, size)".to_string()))) }
                 } else { None }
             }
             "windows" | "sliding" => {
                 if args.len() >= 2 {
                     if let Value::Array(arr) = &args[0] {
                         let n = args[1].as_int() as usize;
-                        if n == 0 { return Some(Err("window size must be > 0".to_string())); }
+                        if n == 0 { return Some(Err(raise(ErrorKind::TypeError, "window size must be > 0".to_string()))); }
                         let windows = arr.windows(n)
                             .map(|w| Value::Array(w.to_vec()))
                             .collect();
                         Some(Ok(Value::Array(windows)))
-                    } else { Some(Err("windows(array, size)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "windows(array, size)".to_string()))) }
                 } else { None }
             }
             "count_if" | "count_where" => {
@@ -4508,7 +5749,7 @@ impl Interpreter {
                             if r.is_truthy() { count += 1; }
                         }
                         Some(Ok(Value::Int(count)))
-                    } else { Some(Err("count_if(array, fn)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "count_if(array, fn)".to_string()))) }
                 } else { None }
             }
             "sum_by" | "sum_map" => {
@@ -4523,7 +5764,7 @@ impl Interpreter {
                             sum += r.as_float();
                         }
                         if is_int { Some(Ok(Value::Int(sum as i64))) } else { Some(Ok(Value::Float(sum))) }
-                    } else { Some(Err("sum_by(array, fn)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "sum_by(array, fn)".to_string()))) }
                 } else { None }
             }
             "max_by" => {
@@ -4538,7 +5779,7 @@ impl Interpreter {
                             if k.as_float() > best_key.as_float() { best = item; best_key = k; }
                         }
                         Some(Ok(best))
-                    } else { Some(Err("max_by(array, fn)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "max_by(array, fn)".to_string()))) }
                 } else { None }
             }
             "min_by" => {
@@ -4553,7 +5794,7 @@ impl Interpreter {
                             if k.as_float() < best_key.as_float() { best = item; best_key = k; }
                         }
                         Some(Ok(best))
-                    } else { Some(Err("min_by(array, fn)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "min_by(array, fn)".to_string()))) }
                 } else { None }
             }
 
@@ -4652,7 +5893,7 @@ impl Interpreter {
                         }
                     }
                     Some(Ok(Value::String(out)))
-                } else { Some(Err("sprintf(fmt, ...)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "sprintf(fmt, ...)".to_string()))) }
             }
             "pad_left" | "ljust" => {
                 if args.len() >= 2 {
@@ -4747,24 +5988,31 @@ impl Interpreter {
             "assert" => {
                 let cond = args.first().map(|v| v.is_truthy()).unwrap_or(false);
                 let msg = args.get(1).map(|v| v.as_string()).unwrap_or_else(|| "Assertion failed".to_string());
-                if !cond { Some(Err(format!("AssertionError: {}", msg))) }
+                if !cond { Some(Err(raise(ErrorKind::AssertionError, format!("AssertionError: {}", msg)))) }
                 else { Some(Ok(Value::Null)) }
             }
-            "assert_eq" => {
-                if args.len() >= 2 {
-                    if args[0] == args[1] { Some(Ok(Value::Null)) }
-                    else { Some(Err(format!("AssertionError: {} != {}", args[0], args[1]))) }
-                } else { Some(Err("assert_eq(a, b)".to_string())) }
-            }
-            "assert_ne" => {
-                if args.len() >= 2 {
-                    if args[0] != args[1] { Some(Ok(Value::Null)) }
-                    else { Some(Err(format!("AssertionError: expected values to differ, both are {}", args[0]))) }
-                } else { Some(Err("assert_ne(a, b)".to_string())) }
+            "assert_eq" | "assert_ne" => {
+                if args.len() < 2 {
+                    return Some(Err(raise(ErrorKind::TypeError, format!("{}(actual, expected) requires 2 args", name))));
+                }
+                // Quote strings so `"1"` and `1` read differently in the report
+                let show = |v: &Value| match v {
+                    Value::String(s) => format!("{:?}", s),
+                    other => other.to_string(),
+                };
+                let detail = match (name, args[0] == args[1]) {
+                    ("assert_eq", false) => format!("expected {}, got {}", show(&args[1]), show(&args[0])),
+                    ("assert_ne", true) => format!("expected values to differ, both are {}", show(&args[0])),
+                    _ => return Some(Ok(Value::Null)),
+                };
+                match args.get(2) {
+                    Some(msg) => Some(Err(raise(ErrorKind::AssertionError, format!("AssertionError: {} ({})", msg.as_string(), detail)))),
+                    None => Some(Err(raise(ErrorKind::AssertionError, format!("AssertionError: {}", detail)))),
+                }
             }
             "panic" | "raise" => {
                 let msg = args.first().map(|v| v.as_string()).unwrap_or_else(|| "panic called".to_string());
-                Some(Err(format!("Panic: {}", msg)))
+                Some(Err(raise(ErrorKind::Error, format!("Panic: {}", msg))))
             }
             "ok" => Some(Ok(args.first().cloned().unwrap_or(Value::Null))),
             "err" | "error" => {
@@ -4784,11 +6032,11 @@ impl Interpreter {
                     if let Value::Map(m) = val {
                         if m.get("__type").map(|v| v.as_string() == "Error").unwrap_or(false) {
                             let msg = m.get("message").map(|v| v.as_string()).unwrap_or_else(|| "unwrap on error".to_string());
-                            Some(Err(format!("Unwrap error: {}", msg)))
+                            Some(Err(raise(ErrorKind::Error, format!("Unwrap error: {}", msg))))
                         } else { Some(Ok(val.clone())) }
                     } else if matches!(val, Value::Null) {
                         let msg = args.get(1).map(|v| v.as_string()).unwrap_or_else(|| "unwrap on null".to_string());
-                        Some(Err(format!("Unwrap null: {}", msg)))
+                        Some(Err(raise(ErrorKind::Error, format!("Unwrap null: {}", msg))))
                     } else { Some(Ok(val.clone())) }
                 } else { Some(Ok(Value::Null)) }
             }
@@ -4853,7 +6101,7 @@ impl Interpreter {
                             .collect();
                         Some(Ok(Value::Array(ips)))
                     }
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
             "port_open" | "tcp_check" => {
@@ -4866,7 +6114,7 @@ impl Interpreter {
                         std::time::Duration::from_secs(3)
                     );
                     Some(Ok(Value::Bool(result.is_ok())))
-                } else { Some(Err("port_open(host, port)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "port_open(host, port)".to_string()))) }
             }
 
             // ── TCP Sockets ─────────────────────────────────────────────────────
@@ -4890,7 +6138,7 @@ impl Interpreter {
                         }
                         Err(_) => Some(Ok(Value::Null)),
                     }
-                } else { Some(Err("tcp_connect(host, port)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "tcp_connect(host, port)".to_string()))) }
             }
 
             // tcp_send(handle, data) -> bytes_written
@@ -4902,10 +6150,10 @@ impl Interpreter {
                     if let Some(stream) = self.tcp_streams.get_mut(&handle) {
                         match stream.write(data.as_bytes()) {
                             Ok(n) => Some(Ok(Value::Int(n as i64))),
-                            Err(e) => Some(Err(e.to_string())),
+                            Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                         }
-                    } else { Some(Err(format!("tcp_send: unknown handle {}", handle))) }
-                } else { Some(Err("tcp_send(handle, data)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::ValueError, format!("tcp_send: unknown handle {}", handle)))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "tcp_send(handle, data)".to_string()))) }
             }
 
             // tcp_recv(handle, max_bytes?) -> string
@@ -4921,10 +6169,10 @@ impl Interpreter {
                                 buf.truncate(n);
                                 Some(Ok(Value::String(String::from_utf8_lossy(&buf).to_string())))
                             }
-                            Err(e) => Some(Err(e.to_string())),
+                            Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                         }
-                    } else { Some(Err(format!("tcp_recv: unknown handle {}", handle))) }
-                } else { Some(Err("tcp_recv(handle)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::ValueError, format!("tcp_recv: unknown handle {}", handle)))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "tcp_recv(handle)".to_string()))) }
             }
 
             // tcp_recv_all(handle) -> string  (reads until connection closed)
@@ -4936,10 +6184,10 @@ impl Interpreter {
                         let mut buf = Vec::new();
                         match stream.read_to_end(&mut buf) {
                             Ok(_) => Some(Ok(Value::String(String::from_utf8_lossy(&buf).to_string()))),
-                            Err(e) => Some(Err(e.to_string())),
+                            Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                         }
-                    } else { Some(Err(format!("tcp_recv_all: unknown handle {}", handle))) }
-                } else { Some(Err("tcp_recv_all(handle)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::ValueError, format!("tcp_recv_all: unknown handle {}", handle)))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "tcp_recv_all(handle)".to_string()))) }
             }
 
             // tcp_close(handle)
@@ -4948,7 +6196,7 @@ impl Interpreter {
                     let handle = args[0].as_int();
                     self.tcp_streams.remove(&handle);
                     Some(Ok(Value::Null))
-                } else { Some(Err("tcp_close(handle)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "tcp_close(handle)".to_string()))) }
             }
 
             // tcp_listen(host, port) or tcp_listen("host:port") -> listener_handle
@@ -4966,9 +6214,9 @@ impl Interpreter {
                             self.tcp_listeners.insert(handle, listener);
                             Some(Ok(Value::Int(handle)))
                         }
-                        Err(e) => Some(Err(e.to_string())),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                     }
-                } else { Some(Err("tcp_listen(host, port)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "tcp_listen(host, port)".to_string()))) }
             }
 
             // tcp_accept(listener_handle) -> [stream_handle, peer_addr_str]
@@ -4990,10 +6238,10 @@ impl Interpreter {
                                 Value::String(peer_str),
                             ])))
                         }
-                        Some(Err(e)) => Some(Err(e.to_string())),
-                        None => Some(Err(format!("tcp_accept: unknown listener {}", handle))),
+                        Some(Err(e)) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
+                        None => Some(Err(raise(ErrorKind::ValueError, format!("tcp_accept: unknown listener {}", handle)))),
                     }
-                } else { Some(Err("tcp_accept(listener_handle)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "tcp_accept(listener_handle)".to_string()))) }
             }
 
             // tcp_listen_close(handle)
@@ -5002,7 +6250,7 @@ impl Interpreter {
                     let handle = args[0].as_int();
                     self.tcp_listeners.remove(&handle);
                     Some(Ok(Value::Null))
-                } else { Some(Err("tcp_listen_close(handle)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "tcp_listen_close(handle)".to_string()))) }
             }
 
             // tcp_set_timeout(handle, read_ms, write_ms)
@@ -5015,8 +6263,8 @@ impl Interpreter {
                         let _ = stream.set_read_timeout(Some(std::time::Duration::from_millis(read_ms)));
                         let _ = stream.set_write_timeout(Some(std::time::Duration::from_millis(write_ms)));
                         Some(Ok(Value::Null))
-                    } else { Some(Err(format!("tcp_set_timeout: unknown handle {}", handle))) }
-                } else { Some(Err("tcp_set_timeout(handle, read_ms, write_ms)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::ValueError, format!("tcp_set_timeout: unknown handle {}", handle)))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "tcp_set_timeout(handle, read_ms, write_ms)".to_string()))) }
             }
 
             // tcp_peer_addr(handle) -> string
@@ -5026,10 +6274,10 @@ impl Interpreter {
                     if let Some(stream) = self.tcp_streams.get(&handle) {
                         match stream.peer_addr() {
                             Ok(addr) => Some(Ok(Value::String(addr.to_string()))),
-                            Err(e) => Some(Err(e.to_string())),
+                            Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                         }
-                    } else { Some(Err(format!("tcp_peer_addr: unknown handle {}", handle))) }
-                } else { Some(Err("tcp_peer_addr(handle)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::ValueError, format!("tcp_peer_addr: unknown handle {}", handle)))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "tcp_peer_addr(handle)".to_string()))) }
             }
 
             // tcp_local_addr(handle) -> string
@@ -5039,10 +6287,10 @@ impl Interpreter {
                     if let Some(stream) = self.tcp_streams.get(&handle) {
                         match stream.local_addr() {
                             Ok(addr) => Some(Ok(Value::String(addr.to_string()))),
-                            Err(e) => Some(Err(e.to_string())),
+                            Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                         }
-                    } else { Some(Err(format!("tcp_local_addr: unknown handle {}", handle))) }
-                } else { Some(Err("tcp_local_addr(handle)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::ValueError, format!("tcp_local_addr: unknown handle {}", handle)))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "tcp_local_addr(handle)".to_string()))) }
             }
 
             // ── UDP Sockets ─────────────────────────────────────────────────────
@@ -5059,9 +6307,9 @@ impl Interpreter {
                             self.udp_sockets.insert(handle, sock);
                             Some(Ok(Value::Int(handle)))
                         }
-                        Err(e) => Some(Err(e.to_string())),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                     }
-                } else { Some(Err("udp_bind(host, port)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "udp_bind(host, port)".to_string()))) }
             }
 
             // udp_send(handle, dest_host, dest_port, data) -> bytes_sent
@@ -5075,10 +6323,10 @@ impl Interpreter {
                     if let Some(sock) = self.udp_sockets.get(&handle) {
                         match sock.send_to(data.as_bytes(), &dest_addr) {
                             Ok(n) => Some(Ok(Value::Int(n as i64))),
-                            Err(e) => Some(Err(e.to_string())),
+                            Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                         }
-                    } else { Some(Err(format!("udp_send: unknown handle {}", handle))) }
-                } else { Some(Err("udp_send(handle, host, port, data)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::ValueError, format!("udp_send: unknown handle {}", handle)))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "udp_send(handle, host, port, data)".to_string()))) }
             }
 
             // udp_recv(handle, max_bytes?) -> [data_str, sender_addr_str]
@@ -5096,10 +6344,10 @@ impl Interpreter {
                                     Value::String(sender.to_string()),
                                 ])))
                             }
-                            Err(e) => Some(Err(e.to_string())),
+                            Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                         }
-                    } else { Some(Err(format!("udp_recv: unknown handle {}", handle))) }
-                } else { Some(Err("udp_recv(handle)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::ValueError, format!("udp_recv: unknown handle {}", handle)))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "udp_recv(handle)".to_string()))) }
             }
 
             // udp_close(handle)
@@ -5108,7 +6356,7 @@ impl Interpreter {
                     let handle = args[0].as_int();
                     self.udp_sockets.remove(&handle);
                     Some(Ok(Value::Null))
-                } else { Some(Err("udp_close(handle)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "udp_close(handle)".to_string()))) }
             }
 
             // udp_set_timeout(handle, read_ms)
@@ -5119,8 +6367,8 @@ impl Interpreter {
                     if let Some(sock) = self.udp_sockets.get(&handle) {
                         let _ = sock.set_read_timeout(Some(std::time::Duration::from_millis(read_ms)));
                         Some(Ok(Value::Null))
-                    } else { Some(Err(format!("udp_set_timeout: unknown handle {}", handle))) }
-                } else { Some(Err("udp_set_timeout(handle, read_ms)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::ValueError, format!("udp_set_timeout: unknown handle {}", handle)))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "udp_set_timeout(handle, read_ms)".to_string()))) }
             }
 
             // udp_broadcast(handle, port, data) -> bytes_sent
@@ -5133,10 +6381,10 @@ impl Interpreter {
                         let _ = sock.set_broadcast(true);
                         match sock.send_to(data.as_bytes(), format!("255.255.255.255:{}", port)) {
                             Ok(n) => Some(Ok(Value::Int(n as i64))),
-                            Err(e) => Some(Err(e.to_string())),
+                            Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                         }
-                    } else { Some(Err(format!("udp_broadcast: unknown handle {}", handle))) }
-                } else { Some(Err("udp_broadcast(handle, port, data)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::ValueError, format!("udp_broadcast: unknown handle {}", handle)))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "udp_broadcast(handle, port, data)".to_string()))) }
             }
 
             // ── HTTP (builtin raw HTTP over TCP) ────────────────────────────────
@@ -5165,7 +6413,7 @@ impl Interpreter {
                         } else { s };
                         Some(Ok(Value::String(body)))
                     }
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
 
@@ -5193,9 +6441,9 @@ impl Interpreter {
                             let body_out = if let Some(pos) = s.find("\r\n\r\n") { s[pos+4..].to_string() } else { s };
                             Some(Ok(Value::String(body_out)))
                         }
-                        Err(e) => Some(Err(e.to_string())),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                     }
-                } else { Some(Err("http_post(url, body, content_type?)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "http_post(url, body, content_type?)".to_string()))) }
             }
 
             // http_response_code(url) -> status int
@@ -5300,7 +6548,7 @@ impl Interpreter {
                     let cols = args[1].as_int() as usize;
                     let data = vec![Value::Float(0.0); rows * cols];
                     Some(Ok(Value::Array(data)))
-                } else { Some(Err("mat_new(rows, cols)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mat_new(rows, cols)".to_string()))) }
             }
             // mat_get(mat, rows, cols, r, c) -> float
             "mat_get" => {
@@ -5310,8 +6558,8 @@ impl Interpreter {
                         let r = args[3].as_int() as usize;
                         let c = args[4].as_int() as usize;
                         Some(Ok(m.get(r * cols + c).cloned().unwrap_or(Value::Float(0.0))))
-                    } else { Some(Err("mat_get: first arg must be array".to_string())) }
-                } else { Some(Err("mat_get(mat, rows, cols, r, c)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "mat_get: first arg must be array".to_string()))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mat_get(mat, rows, cols, r, c)".to_string()))) }
             }
             // mat_set(mat, rows, cols, r, c, val) -> new mat
             "mat_set" => {
@@ -5324,8 +6572,8 @@ impl Interpreter {
                         let mut out = m.clone();
                         if r * cols + c < out.len() { out[r * cols + c] = val; }
                         Some(Ok(Value::Array(out)))
-                    } else { Some(Err("mat_set: first arg must be array".to_string())) }
-                } else { Some(Err("mat_set(mat, rows, cols, r, c, val)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "mat_set: first arg must be array".to_string()))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mat_set(mat, rows, cols, r, c, val)".to_string()))) }
             }
             // mat_mul(a, b, ar, ac_br, bc) -> flat result
             "flat_mat_mul" => {
@@ -5345,8 +6593,8 @@ impl Interpreter {
                             }
                         }
                         Some(Ok(Value::Array(out)))
-                    } else { Some(Err("mat_mul: args must be arrays".to_string())) }
-                } else { Some(Err("mat_mul(a, b, a_rows, shared, b_cols)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "mat_mul: args must be arrays".to_string()))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mat_mul(a, b, a_rows, shared, b_cols)".to_string()))) }
             }
             // mat_add(a, b) -> element-wise sum
             "flat_mat_add" => {
@@ -5356,8 +6604,8 @@ impl Interpreter {
                             .map(|(x, y)| Value::Float(x.as_float() + y.as_float()))
                             .collect();
                         Some(Ok(Value::Array(out)))
-                    } else { Some(Err("mat_add: args must be arrays".to_string())) }
-                } else { Some(Err("mat_add(a, b)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "mat_add: args must be arrays".to_string()))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mat_add(a, b)".to_string()))) }
             }
             // mat_transpose(mat, rows, cols) -> flat transposed
             "flat_mat_transpose" => {
@@ -5372,8 +6620,8 @@ impl Interpreter {
                             }
                         }
                         Some(Ok(Value::Array(out)))
-                    } else { Some(Err("mat_transpose: first arg must be array".to_string())) }
-                } else { Some(Err("mat_transpose(mat, rows, cols)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "mat_transpose: first arg must be array".to_string()))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mat_transpose(mat, rows, cols)".to_string()))) }
             }
             // dot(a, b) -> scalar
             "dot" | "flat_vec_dot" => {
@@ -5381,8 +6629,8 @@ impl Interpreter {
                     if let (Value::Array(ref a), Value::Array(ref b)) = (&args[0], &args[1]) {
                         let s: f64 = a.iter().zip(b.iter()).map(|(x,y)| x.as_float()*y.as_float()).sum();
                         Some(Ok(Value::Float(s)))
-                    } else { Some(Err("dot: args must be arrays".to_string())) }
-                } else { Some(Err("dot(a, b)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "dot: args must be arrays".to_string()))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "dot(a, b)".to_string()))) }
             }
             // cross(a, b) -> vec3
             "cross" | "flat_vec_cross" => {
@@ -5396,16 +6644,16 @@ impl Interpreter {
                                 Value::Float(az*bx - ax*bz),
                                 Value::Float(ax*by - ay*bx),
                             ])))
-                        } else { Some(Err("cross: need vec3".to_string())) }
-                    } else { Some(Err("cross: args must be arrays".to_string())) }
-                } else { Some(Err("cross(a, b)".to_string())) }
+                        } else { Some(Err(raise(ErrorKind::TypeError, "cross: need vec3".to_string()))) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "cross: args must be arrays".to_string()))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "cross(a, b)".to_string()))) }
             }
             // vec_norm(v) -> float magnitude
             "vec_norm" | "vec_magnitude" | "magnitude" => {
                 if let Some(Value::Array(ref v)) = args.first() {
                     let s: f64 = v.iter().map(|x| { let f = x.as_float(); f*f }).sum();
                     Some(Ok(Value::Float(s.sqrt())))
-                } else { Some(Err("vec_norm(v)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "vec_norm(v)".to_string()))) }
             }
             // vec_normalize(v) -> unit vector
             "vec_normalize" | "normalize" => {
@@ -5414,7 +6662,7 @@ impl Interpreter {
                     if mag == 0.0 { return Some(Ok(Value::Array(v.clone()))); }
                     let out: Vec<Value> = v.iter().map(|x| Value::Float(x.as_float()/mag)).collect();
                     Some(Ok(Value::Array(out)))
-                } else { Some(Err("vec_normalize(v)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "vec_normalize(v)".to_string()))) }
             }
             // vec_scale(v, scalar) -> scaled vector
             "vec_scale" => {
@@ -5423,8 +6671,8 @@ impl Interpreter {
                         let s = args[1].as_float();
                         let out: Vec<Value> = v.iter().map(|x| Value::Float(x.as_float()*s)).collect();
                         Some(Ok(Value::Array(out)))
-                    } else { Some(Err("vec_scale: first arg must be array".to_string())) }
-                } else { Some(Err("vec_scale(v, scalar)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "vec_scale: first arg must be array".to_string()))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "vec_scale(v, scalar)".to_string()))) }
             }
             // vec_add(a, b) / vec_sub(a, b)
             "vec_add" => {
@@ -5432,16 +6680,16 @@ impl Interpreter {
                     if let (Value::Array(ref a), Value::Array(ref b)) = (&args[0], &args[1]) {
                         let out: Vec<Value> = a.iter().zip(b.iter()).map(|(x,y)| Value::Float(x.as_float()+y.as_float())).collect();
                         Some(Ok(Value::Array(out)))
-                    } else { Some(Err("vec_add: args must be arrays".to_string())) }
-                } else { Some(Err("vec_add(a, b)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "vec_add: args must be arrays".to_string()))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "vec_add(a, b)".to_string()))) }
             }
             "vec_sub" => {
                 if args.len() >= 2 {
                     if let (Value::Array(ref a), Value::Array(ref b)) = (&args[0], &args[1]) {
                         let out: Vec<Value> = a.iter().zip(b.iter()).map(|(x,y)| Value::Float(x.as_float()-y.as_float())).collect();
                         Some(Ok(Value::Array(out)))
-                    } else { Some(Err("vec_sub: args must be arrays".to_string())) }
-                } else { Some(Err("vec_sub(a, b)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "vec_sub: args must be arrays".to_string()))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "vec_sub(a, b)".to_string()))) }
             }
 
             // ── Raw Memory / Pointers (OS, kernels, low-level, FFI) ─────────
@@ -5462,50 +6710,50 @@ impl Interpreter {
                         unsafe { std::alloc::dealloc(ptr, layout); }
                     }
                     Some(Ok(Value::Null))
-                } else { Some(Err("mem_free(ptr, size)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mem_free(ptr, size)".to_string()))) }
             }
             // mem_read_u8/u16/u32/u64/i8/i32/i64/f32/f64(ptr) -> int/float
             "mem_read_u8" => {
                 if let Some(a) = args.first() {
                     let ptr = a.as_int() as *const u8;
                     Some(Ok(Value::Int(unsafe { *ptr } as i64)))
-                } else { Some(Err("mem_read_u8(ptr)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mem_read_u8(ptr)".to_string()))) }
             }
             "mem_read_u16" => {
                 if let Some(a) = args.first() {
                     let ptr = a.as_int() as *const u16;
                     Some(Ok(Value::Int(unsafe { *ptr } as i64)))
-                } else { Some(Err("mem_read_u16(ptr)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mem_read_u16(ptr)".to_string()))) }
             }
             "mem_read_u32" => {
                 if let Some(a) = args.first() {
                     let ptr = a.as_int() as *const u32;
                     Some(Ok(Value::Int(unsafe { *ptr } as i64)))
-                } else { Some(Err("mem_read_u32(ptr)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mem_read_u32(ptr)".to_string()))) }
             }
             "mem_read_u64" | "mem_read_i64" => {
                 if let Some(a) = args.first() {
                     let ptr = a.as_int() as *const i64;
                     Some(Ok(Value::Int(unsafe { *ptr })))
-                } else { Some(Err("mem_read_u64(ptr)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mem_read_u64(ptr)".to_string()))) }
             }
             "mem_read_i32" => {
                 if let Some(a) = args.first() {
                     let ptr = a.as_int() as *const i32;
                     Some(Ok(Value::Int(unsafe { *ptr } as i64)))
-                } else { Some(Err("mem_read_i32(ptr)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mem_read_i32(ptr)".to_string()))) }
             }
             "mem_read_f32" => {
                 if let Some(a) = args.first() {
                     let ptr = a.as_int() as *const f32;
                     Some(Ok(Value::Float(unsafe { *ptr } as f64)))
-                } else { Some(Err("mem_read_f32(ptr)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mem_read_f32(ptr)".to_string()))) }
             }
             "mem_read_f64" => {
                 if let Some(a) = args.first() {
                     let ptr = a.as_int() as *const f64;
                     Some(Ok(Value::Float(unsafe { *ptr })))
-                } else { Some(Err("mem_read_f64(ptr)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mem_read_f64(ptr)".to_string()))) }
             }
             // mem_write_u8/u16/u32/u64/i32/f32/f64(ptr, val)
             "mem_write_u8" => {
@@ -5513,49 +6761,49 @@ impl Interpreter {
                     let ptr = args[0].as_int() as *mut u8;
                     unsafe { *ptr = args[1].as_int() as u8; }
                     Some(Ok(Value::Null))
-                } else { Some(Err("mem_write_u8(ptr, val)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mem_write_u8(ptr, val)".to_string()))) }
             }
             "mem_write_u16" => {
                 if args.len() >= 2 {
                     let ptr = args[0].as_int() as *mut u16;
                     unsafe { *ptr = args[1].as_int() as u16; }
                     Some(Ok(Value::Null))
-                } else { Some(Err("mem_write_u16(ptr, val)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mem_write_u16(ptr, val)".to_string()))) }
             }
             "mem_write_u32" => {
                 if args.len() >= 2 {
                     let ptr = args[0].as_int() as *mut u32;
                     unsafe { *ptr = args[1].as_int() as u32; }
                     Some(Ok(Value::Null))
-                } else { Some(Err("mem_write_u32(ptr, val)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mem_write_u32(ptr, val)".to_string()))) }
             }
             "mem_write_u64" | "mem_write_i64" => {
                 if args.len() >= 2 {
                     let ptr = args[0].as_int() as *mut i64;
                     unsafe { *ptr = args[1].as_int(); }
                     Some(Ok(Value::Null))
-                } else { Some(Err("mem_write_u64(ptr, val)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mem_write_u64(ptr, val)".to_string()))) }
             }
             "mem_write_i32" => {
                 if args.len() >= 2 {
                     let ptr = args[0].as_int() as *mut i32;
                     unsafe { *ptr = args[1].as_int() as i32; }
                     Some(Ok(Value::Null))
-                } else { Some(Err("mem_write_i32(ptr, val)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mem_write_i32(ptr, val)".to_string()))) }
             }
             "mem_write_f32" => {
                 if args.len() >= 2 {
                     let ptr = args[0].as_int() as *mut f32;
                     unsafe { *ptr = args[1].as_float() as f32; }
                     Some(Ok(Value::Null))
-                } else { Some(Err("mem_write_f32(ptr, val)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mem_write_f32(ptr, val)".to_string()))) }
             }
             "mem_write_f64" => {
                 if args.len() >= 2 {
                     let ptr = args[0].as_int() as *mut f64;
                     unsafe { *ptr = args[1].as_float(); }
                     Some(Ok(Value::Null))
-                } else { Some(Err("mem_write_f64(ptr, val)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mem_write_f64(ptr, val)".to_string()))) }
             }
             // mem_copy(dst, src, bytes)
             "mem_copy" | "memcpy" => {
@@ -5565,7 +6813,7 @@ impl Interpreter {
                     let bytes = args[2].as_int() as usize;
                     unsafe { std::ptr::copy_nonoverlapping(src, dst, bytes); }
                     Some(Ok(Value::Null))
-                } else { Some(Err("mem_copy(dst, src, bytes)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mem_copy(dst, src, bytes)".to_string()))) }
             }
             // mem_set(ptr, byte_val, count)
             "mem_set" | "memset" => {
@@ -5575,7 +6823,7 @@ impl Interpreter {
                     let count = args[2].as_int() as usize;
                     unsafe { std::ptr::write_bytes(ptr, val, count); }
                     Some(Ok(Value::Null))
-                } else { Some(Err("mem_set(ptr, byte_val, count)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mem_set(ptr, byte_val, count)".to_string()))) }
             }
             // mem_cmp(a, b, bytes) -> int  (like memcmp)
             "mem_cmp" | "memcmp" => {
@@ -5589,7 +6837,7 @@ impl Interpreter {
                         sa.cmp(sb) as i8 as i64
                     };
                     Some(Ok(Value::Int(result)))
-                } else { Some(Err("mem_cmp(a, b, bytes)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mem_cmp(a, b, bytes)".to_string()))) }
             }
             // ptr_add(ptr, offset) -> ptr
             "ptr_add" => {
@@ -5597,13 +6845,13 @@ impl Interpreter {
                     let ptr    = args[0].as_int();
                     let offset = args[1].as_int();
                     Some(Ok(Value::Int(ptr + offset)))
-                } else { Some(Err("ptr_add(ptr, offset)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "ptr_add(ptr, offset)".to_string()))) }
             }
             // ptr_diff(a, b) -> int (a - b)
             "ptr_diff" => {
                 if args.len() >= 2 {
                     Some(Ok(Value::Int(args[0].as_int() - args[1].as_int())))
-                } else { Some(Err("ptr_diff(a, b)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "ptr_diff(a, b)".to_string()))) }
             }
             // ptr_null() -> 0
             "ptr_null" | "null_ptr" => Some(Ok(Value::Int(0))),
@@ -5617,7 +6865,7 @@ impl Interpreter {
                     let ptr = boxed.as_ptr() as i64;
                     std::mem::forget(boxed);
                     Some(Ok(Value::Int(ptr)))
-                } else { Some(Err("str_to_ptr(s)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "str_to_ptr(s)".to_string()))) }
             }
             // ptr_to_str(ptr, len) -> string
             "ptr_to_str" => {
@@ -5629,7 +6877,7 @@ impl Interpreter {
                         String::from_utf8_lossy(slice).to_string()
                     };
                     Some(Ok(Value::String(s)))
-                } else { Some(Err("ptr_to_str(ptr, len)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "ptr_to_str(ptr, len)".to_string()))) }
             }
             // syscall(nr, a0, a1, a2, a3, a4, a5) -> int  [Linux x86-64 only]
             "syscall" => {
@@ -5657,10 +6905,10 @@ impl Interpreter {
                             );
                         }
                         Some(Ok(Value::Int(ret)))
-                    } else { Some(Err("syscall(nr, ...)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "syscall(nr, ...)".to_string()))) }
                 }
                 #[cfg(not(target_arch = "x86_64"))]
-                { Some(Err("syscall() only supported on x86_64".to_string())) }
+                { Some(Err(raise(ErrorKind::Error, "syscall() only supported on x86_64".to_string()))) }
             }
 
             // ── OS Threads ───────────────────────────────────────────────────
@@ -5682,7 +6930,7 @@ impl Interpreter {
                         let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
                             let mut interp = Interpreter::new();
                             for (name, params, body) in funcs_clone {
-                                interp.functions.insert(name.clone(), FunctionDef { name, params, body });
+                                interp.functions.insert(name.clone(), FunctionDef { name, params, body, module: None, bounds: Vec::new() });
                             }
                             if let Some(name) = func_name {
                                 interp.call_function(&name, fargs).unwrap_or(Value::Null)
@@ -5697,7 +6945,7 @@ impl Interpreter {
                     let id = self.thread_counter;
                     self.thread_results.insert(id, rx);
                     Some(Ok(Value::Int(id)))
-                } else { Some(Err("thread_spawn(fn, args...)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "thread_spawn(fn, args...)".to_string()))) }
             }
             // thread_join(handle) -> return_value
             "thread_join" => {
@@ -5708,8 +6956,8 @@ impl Interpreter {
                             Ok(v)  => Some(Ok(v)),
                             Err(_) => Some(Ok(Value::Null)),
                         }
-                    } else { Some(Err(format!("thread_join: unknown handle {}", id))) }
-                } else { Some(Err("thread_join(handle)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::ValueError, format!("thread_join: unknown handle {}", id)))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "thread_join(handle)".to_string()))) }
             }
             // thread_try_recv(handle) -> value or null (non-blocking)
             "thread_try_recv" | "thread_poll" => {
@@ -5721,7 +6969,7 @@ impl Interpreter {
                             Err(_) => Some(Ok(Value::Null)),
                         }
                     } else { Some(Ok(Value::Null)) }
-                } else { Some(Err("thread_poll(handle)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "thread_poll(handle)".to_string()))) }
             }
             // thread_sleep(ms)
             "thread_sleep" | "sleep_ms" => {
@@ -5758,10 +7006,10 @@ impl Interpreter {
                     if let Some(m) = self.mutexes.get(&id) {
                         match m.lock() {
                             Ok(v)  => Some(Ok(v.clone())),
-                            Err(_) => Some(Err("mutex_lock: poisoned".to_string())),
+                            Err(_) => Some(Err(raise(ErrorKind::Error, "mutex_lock: poisoned".to_string()))),
                         }
-                    } else { Some(Err(format!("mutex_lock: unknown {}", id))) }
-                } else { Some(Err("mutex_lock(handle)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::ValueError, format!("mutex_lock: unknown {}", id)))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mutex_lock(handle)".to_string()))) }
             }
             // mutex_set(handle, value) — set value under lock
             "mutex_set" => {
@@ -5771,10 +7019,10 @@ impl Interpreter {
                     if let Some(m) = self.mutexes.get(&id) {
                         match m.lock() {
                             Ok(mut v) => { *v = val; Some(Ok(Value::Null)) }
-                            Err(_) => Some(Err("mutex_set: poisoned".to_string())),
+                            Err(_) => Some(Err(raise(ErrorKind::Error, "mutex_set: poisoned".to_string()))),
                         }
-                    } else { Some(Err(format!("mutex_set: unknown {}", id))) }
-                } else { Some(Err("mutex_set(handle, value)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::ValueError, format!("mutex_set: unknown {}", id)))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mutex_set(handle, value)".to_string()))) }
             }
             // mutex_clone(handle) -> new handle sharing same Arc
             "mutex_clone" => {
@@ -5785,8 +7033,8 @@ impl Interpreter {
                         let new_id = self.mutex_counter;
                         self.mutexes.insert(new_id, m);
                         Some(Ok(Value::Int(new_id)))
-                    } else { Some(Err(format!("mutex_clone: unknown {}", id))) }
-                } else { Some(Err("mutex_clone(handle)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::ValueError, format!("mutex_clone: unknown {}", id)))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mutex_clone(handle)".to_string()))) }
             }
 
             // ── AI / ML Math ─────────────────────────────────────────────────
@@ -5812,7 +7060,7 @@ impl Interpreter {
                     let x     = args[0].as_float();
                     let alpha = args[1].as_float();
                     Some(Ok(Value::Float(if x > 0.0 { x } else { alpha * x })))
-                } else { Some(Err("leaky_relu(x, alpha)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "leaky_relu(x, alpha)".to_string()))) }
             }
             "tanh_act" | "tanh_activation" => {
                 let x = args.first().map(|v| v.as_float()).unwrap_or(0.0);
@@ -5825,7 +7073,7 @@ impl Interpreter {
                     let sum: f64 = exps.iter().sum();
                     let out: Vec<Value> = exps.iter().map(|e| Value::Float(e / sum)).collect();
                     Some(Ok(Value::Array(out)))
-                } else { Some(Err("softmax(array)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "softmax(array)".to_string()))) }
             }
             // mse(predicted_arr, target_arr) -> float
             "mse" | "mean_squared_error" => {
@@ -5836,8 +7084,8 @@ impl Interpreter {
                             .map(|(pi, ti)| { let d = pi.as_float() - ti.as_float(); d*d })
                             .sum::<f64>() / n;
                         Some(Ok(Value::Float(loss)))
-                    } else { Some(Err("mse: args must be arrays".to_string())) }
-                } else { Some(Err("mse(predicted, target)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "mse: args must be arrays".to_string()))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mse(predicted, target)".to_string()))) }
             }
             // cross_entropy(predicted_arr, target_arr) -> float
             "cross_entropy" => {
@@ -5848,8 +7096,8 @@ impl Interpreter {
                             .map(|(pi, ti)| -ti.as_float() * (pi.as_float().max(eps)).ln())
                             .sum();
                         Some(Ok(Value::Float(loss)))
-                    } else { Some(Err("cross_entropy: args must be arrays".to_string())) }
-                } else { Some(Err("cross_entropy(predicted, target)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "cross_entropy: args must be arrays".to_string()))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "cross_entropy(predicted, target)".to_string()))) }
             }
             // mat_scalar_mul(mat, scalar) -> scaled mat
             "mat_scalar_mul" => {
@@ -5858,8 +7106,8 @@ impl Interpreter {
                         let s = args[1].as_float();
                         let out: Vec<Value> = m.iter().map(|x| Value::Float(x.as_float()*s)).collect();
                         Some(Ok(Value::Array(out)))
-                    } else { Some(Err("mat_scalar_mul: first arg must be array".to_string())) }
-                } else { Some(Err("mat_scalar_mul(mat, scalar)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "mat_scalar_mul: first arg must be array".to_string()))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mat_scalar_mul(mat, scalar)".to_string()))) }
             }
             // mat_apply(mat, fn) -> mat with fn applied to each element
             "mat_apply" => {
@@ -5876,8 +7124,8 @@ impl Interpreter {
                             out.push(v);
                         }
                         Some(Ok(Value::Array(out)))
-                    } else { Some(Err("mat_apply: first arg must be array".to_string())) }
-                } else { Some(Err("mat_apply(mat, fn)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "mat_apply: first arg must be array".to_string()))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mat_apply(mat, fn)".to_string()))) }
             }
             // argmax(arr) -> index of max element
             "argmax" => {
@@ -5887,7 +7135,7 @@ impl Interpreter {
                         .map(|(i, _)| i)
                         .unwrap_or(0);
                     Some(Ok(Value::Int(idx as i64)))
-                } else { Some(Err("argmax(array)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "argmax(array)".to_string()))) }
             }
             // argmin(arr) -> index of min element
             "argmin" => {
@@ -5897,7 +7145,7 @@ impl Interpreter {
                         .map(|(i, _)| i)
                         .unwrap_or(0);
                     Some(Ok(Value::Int(idx as i64)))
-                } else { Some(Err("argmin(array)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "argmin(array)".to_string()))) }
             }
             // linspace(start, end, n) -> array of n evenly spaced floats
             "linspace" => {
@@ -5910,7 +7158,7 @@ impl Interpreter {
                     let step = (end - start) / (n - 1) as f64;
                     let out: Vec<Value> = (0..n).map(|i| Value::Float(start + step * i as f64)).collect();
                     Some(Ok(Value::Array(out)))
-                } else { Some(Err("linspace(start, end, n)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "linspace(start, end, n)".to_string()))) }
             }
             // arange(start, end, step) -> array
             "arange" => {
@@ -5918,7 +7166,7 @@ impl Interpreter {
                     let start = args[0].as_float();
                     let end   = args[1].as_float();
                     let step  = args[2].as_float();
-                    if step == 0.0 { return Some(Err("arange: step cannot be 0".to_string())); }
+                    if step == 0.0 { return Some(Err(raise(ErrorKind::TypeError, "arange: step cannot be 0".to_string()))); }
                     let mut v = Vec::new();
                     let mut cur = start;
                     while (step > 0.0 && cur < end) || (step < 0.0 && cur > end) {
@@ -5926,7 +7174,7 @@ impl Interpreter {
                         cur += step;
                     }
                     Some(Ok(Value::Array(v)))
-                } else { Some(Err("arange(start, end, step)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "arange(start, end, step)".to_string()))) }
             }
             // mean(arr) -> float
             "mean" | "average" => {
@@ -5934,7 +7182,7 @@ impl Interpreter {
                     if v.is_empty() { return Some(Ok(Value::Float(0.0))); }
                     let s: f64 = v.iter().map(|x| x.as_float()).sum();
                     Some(Ok(Value::Float(s / v.len() as f64)))
-                } else { Some(Err("mean(array)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mean(array)".to_string()))) }
             }
             // std_dev(arr) -> float
             "std_dev" | "stddev" => {
@@ -5944,7 +7192,7 @@ impl Interpreter {
                     let m: f64 = v.iter().map(|x| x.as_float()).sum::<f64>() / n;
                     let variance: f64 = v.iter().map(|x| { let d = x.as_float() - m; d*d }).sum::<f64>() / n;
                     Some(Ok(Value::Float(variance.sqrt())))
-                } else { Some(Err("std_dev(array)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "std_dev(array)".to_string()))) }
             }
 
             // ── Regex ───────────────────────────────────────────────────────
@@ -5955,9 +7203,9 @@ impl Interpreter {
                     let text = args[1].as_string();
                     match regex::Regex::new(&pat) {
                         Ok(re) => Some(Ok(Value::Bool(re.is_match(&text)))),
-                        Err(e) => Some(Err(format!("regex_match: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("regex_match: {}", e)))),
                     }
-                } else { Some(Err("regex_match(pattern, text)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "regex_match(pattern, text)".to_string()))) }
             }
             // regex_find(pattern, text) -> first match string or null
             "re_find" => {
@@ -5969,9 +7217,9 @@ impl Interpreter {
                             Some(m) => Some(Ok(Value::String(m.as_str().to_string()))),
                             None    => Some(Ok(Value::Null)),
                         },
-                        Err(e) => Some(Err(format!("regex_find: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("regex_find: {}", e)))),
                     }
-                } else { Some(Err("regex_find(pattern, text)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "regex_find(pattern, text)".to_string()))) }
             }
             // regex_findall(pattern, text) -> array of match strings
             "re_findall" => {
@@ -5985,9 +7233,9 @@ impl Interpreter {
                                 .collect();
                             Some(Ok(Value::Array(matches)))
                         },
-                        Err(e) => Some(Err(format!("regex_findall: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("regex_findall: {}", e)))),
                     }
-                } else { Some(Err("regex_findall(pattern, text)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "regex_findall(pattern, text)".to_string()))) }
             }
             // regex_replace(pattern, replacement, text) -> string
             "re_replace" => {
@@ -5997,9 +7245,9 @@ impl Interpreter {
                     let text = args[2].as_string();
                     match regex::Regex::new(&pat) {
                         Ok(re) => Some(Ok(Value::String(re.replace_all(&text, rep.as_str()).to_string()))),
-                        Err(e) => Some(Err(format!("regex_replace: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("regex_replace: {}", e)))),
                     }
-                } else { Some(Err("regex_replace(pattern, replacement, text)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "regex_replace(pattern, replacement, text)".to_string()))) }
             }
             // regex_split(pattern, text) -> array of parts
             "re_split" => {
@@ -6013,9 +7261,9 @@ impl Interpreter {
                                 .collect();
                             Some(Ok(Value::Array(parts)))
                         },
-                        Err(e) => Some(Err(format!("regex_split: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("regex_split: {}", e)))),
                     }
-                } else { Some(Err("regex_split(pattern, text)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "regex_split(pattern, text)".to_string()))) }
             }
             // regex_captures(pattern, text) -> array of captured groups (or null)
             "re_captures" => {
@@ -6033,9 +7281,9 @@ impl Interpreter {
                             }
                             None => Some(Ok(Value::Null)),
                         },
-                        Err(e) => Some(Err(format!("regex_captures: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("regex_captures: {}", e)))),
                     }
-                } else { Some(Err("regex_captures(pattern, text)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "regex_captures(pattern, text)".to_string()))) }
             }
 
             // ── ANSI Terminal ────────────────────────────────────────────────
@@ -6069,7 +7317,7 @@ impl Interpreter {
             "cursor_move" => {
                 if args.len() >= 2 {
                     Some(Ok(Value::String(format!("\x1b[{};{}H", args[0].as_int(), args[1].as_int()))))
-                } else { Some(Err("cursor_move(row, col)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "cursor_move(row, col)".to_string()))) }
             }
             "clear_screen" => Some(Ok(Value::String("\x1b[2J\x1b[H".to_string()))),
             "clear_line"   => Some(Ok(Value::String("\x1b[2K\r".to_string()))),
@@ -6108,9 +7356,9 @@ impl Interpreter {
                             let arr: Vec<Value> = bytes.iter().map(|&b| Value::Int(b as i64)).collect();
                             Some(Ok(Value::Array(arr)))
                         }
-                        Err(e) => Some(Err(format!("file_read_bytes: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, format!("file_read_bytes: {}", e)))),
                     }
-                } else { Some(Err("file_read_bytes(path)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "file_read_bytes(path)".to_string()))) }
             }
             // file_write_bytes(path, array_of_ints)
             "file_write_bytes" | "write_bytes" => {
@@ -6120,10 +7368,10 @@ impl Interpreter {
                         let bytes: Vec<u8> = arr.iter().map(|v| v.as_int() as u8).collect();
                         match std::fs::write(&path, &bytes) {
                             Ok(_)  => Some(Ok(Value::Null)),
-                            Err(e) => Some(Err(format!("file_write_bytes: {}", e))),
+                            Err(e) => Some(Err(raise(ErrorKind::IOError, format!("file_write_bytes: {}", e)))),
                         }
-                    } else { Some(Err("file_write_bytes: second arg must be array".to_string())) }
-                } else { Some(Err("file_write_bytes(path, bytes_array)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "file_write_bytes: second arg must be array".to_string()))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "file_write_bytes(path, bytes_array)".to_string()))) }
             }
             // file_append_bytes(path, bytes_array)
             "file_append_bytes" => {
@@ -6135,12 +7383,12 @@ impl Interpreter {
                         match std::fs::OpenOptions::new().append(true).create(true).open(&path) {
                             Ok(mut f) => match f.write_all(&bytes) {
                                 Ok(_) => Some(Ok(Value::Null)),
-                                Err(e) => Some(Err(e.to_string())),
+                                Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                             },
-                            Err(e) => Some(Err(e.to_string())),
+                            Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                         }
-                    } else { Some(Err("file_append_bytes: second arg must be array".to_string())) }
-                } else { Some(Err("file_append_bytes(path, bytes_array)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "file_append_bytes: second arg must be array".to_string()))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "file_append_bytes(path, bytes_array)".to_string()))) }
             }
 
             // ── Encoding: hex / base64 ───────────────────────────────────────
@@ -6157,7 +7405,7 @@ impl Interpreter {
                         }
                     };
                     Some(Ok(Value::String(hex)))
-                } else { Some(Err("hex_encode(bytes_or_string)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "hex_encode(bytes_or_string)".to_string()))) }
             }
             // hex_decode(hex_string) -> array of ints
             "hex_decode" | "from_hex" | "hex_to_bytes" => {
@@ -6172,7 +7420,7 @@ impl Interpreter {
                         }
                     }
                     Some(Ok(Value::Array(bytes)))
-                } else { Some(Err("hex_decode(hex_string)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "hex_decode(hex_string)".to_string()))) }
             }
             // hex_decode_str(hex_string) -> utf8 string
             "hex_decode_str" => {
@@ -6186,7 +7434,7 @@ impl Interpreter {
                         }
                     }
                     Some(Ok(Value::String(String::from_utf8_lossy(&bytes).to_string())))
-                } else { Some(Err("hex_decode_str(hex_string)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "hex_decode_str(hex_string)".to_string()))) }
             }
             // base64_encode(string or bytes_array) -> base64 string
             "base64_encode" | "b64_encode" => {
@@ -6209,7 +7457,7 @@ impl Interpreter {
                         if chunk.len() > 2 { out.push(ALPHA[(n & 63) as usize] as char); } else { out.push('='); }
                     }
                     Some(Ok(Value::String(out)))
-                } else { Some(Err("base64_encode(string_or_bytes)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "base64_encode(string_or_bytes)".to_string()))) }
             }
             // base64_decode(b64_string) -> string
             "base64_decode" | "b64_decode" => {
@@ -6254,7 +7502,7 @@ impl Interpreter {
                         }
                     }
                     Some(Ok(Value::String(String::from_utf8_lossy(&out).to_string())))
-                } else { Some(Err("base64_decode(b64_string)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "base64_decode(b64_string)".to_string()))) }
             }
             // base64_decode_bytes(b64_string) -> array of ints
             "base64_decode_bytes" | "b64_decode_bytes" => {
@@ -6268,7 +7516,7 @@ impl Interpreter {
                         }
                     };
                     Some(Ok(Value::Array(decoded)))
-                } else { Some(Err("base64_decode_bytes(b64_string)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "base64_decode_bytes(b64_string)".to_string()))) }
             }
 
             // ── Process Control (OS, red-team, automation) ───────────────────
@@ -6284,7 +7532,7 @@ impl Interpreter {
                     let pid = args[0].as_int();
                     let result = unsafe { libc::kill(pid as libc::pid_t, libc::SIGTERM) };
                     Some(Ok(Value::Bool(result == 0)))
-                } else { Some(Err("kill(pid, signal?)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "kill(pid, signal?)".to_string()))) }
             }
             // getppid() -> parent pid
             "getppid" => Some(Ok(Value::Int(unsafe { libc::getppid() } as i64))),
@@ -6293,7 +7541,7 @@ impl Interpreter {
                 if let Some(v) = args.first() {
                     std::env::remove_var(v.as_string());
                     Some(Ok(Value::Null))
-                } else { Some(Err("unsetenv(key)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "unsetenv(key)".to_string()))) }
             }
             // fork() -> pid (0 = child, >0 = parent with child pid)
             "fork" => {
@@ -6383,7 +7631,7 @@ impl Interpreter {
                     let hash = sha256_round(&bytes);
                     let hex: String = hash.iter().map(|b| format!("{:02x}", b)).collect();
                     Some(Ok(Value::String(hex)))
-                } else { Some(Err("sha256_native(data)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "sha256_native(data)".to_string()))) }
             }
 
             // hmac_sha256(key, data) -> hex
@@ -6441,7 +7689,7 @@ impl Interpreter {
                     let mac = sha256_bytes(&outer);
                     let hex: String = mac.iter().map(|b| format!("{:02x}", b)).collect();
                     Some(Ok(Value::String(hex)))
-                } else { Some(Err("hmac_sha256(key, data)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "hmac_sha256(key, data)".to_string()))) }
             }
 
             // xor_bytes(bytes_array, key_byte) -> bytes_array  (XOR cipher)
@@ -6451,20 +7699,20 @@ impl Interpreter {
                         let key = args[1].as_int() as u8;
                         let out: Vec<Value> = arr.iter().map(|b| Value::Int((b.as_int() as u8 ^ key) as i64)).collect();
                         Some(Ok(Value::Array(out)))
-                    } else { Some(Err("xor_bytes: first arg must be array".to_string())) }
-                } else { Some(Err("xor_bytes(bytes, key_byte)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "xor_bytes: first arg must be array".to_string()))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "xor_bytes(bytes, key_byte)".to_string()))) }
             }
             // xor_key(bytes_array, key_bytes_array) -> bytes_array  (repeating XOR)
             "xor_key" | "xor_encrypt_key" => {
                 if args.len() >= 2 {
                     if let (Value::Array(ref data), Value::Array(ref key)) = (&args[0], &args[1]) {
-                        if key.is_empty() { return Some(Err("xor_key: empty key".to_string())); }
+                        if key.is_empty() { return Some(Err(raise(ErrorKind::ValueError, "xor_key: empty key".to_string()))); }
                         let out: Vec<Value> = data.iter().enumerate()
                             .map(|(i, b)| Value::Int((b.as_int() as u8 ^ key[i % key.len()].as_int() as u8) as i64))
                             .collect();
                         Some(Ok(Value::Array(out)))
-                    } else { Some(Err("xor_key: args must be arrays".to_string())) }
-                } else { Some(Err("xor_key(data_bytes, key_bytes)".to_string())) }
+                    } else { Some(Err(raise(ErrorKind::TypeError, "xor_key: args must be arrays".to_string()))) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "xor_key(data_bytes, key_bytes)".to_string()))) }
             }
             // crc32(bytes_array or string) -> int
             "crc32" => {
@@ -6481,7 +7729,7 @@ impl Interpreter {
                         }
                     }
                     Some(Ok(Value::Int((crc ^ 0xFFFFFFFF) as i64)))
-                } else { Some(Err("crc32(data)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "crc32(data)".to_string()))) }
             }
 
             // ── File System Ops ──────────────────────────────────────────────
@@ -6502,9 +7750,9 @@ impl Interpreter {
                             map.insert("is_link".to_string(),Value::Bool(m.file_type().is_symlink()));
                             Some(Ok(Value::Map(map)))
                         }
-                        Err(e) => Some(Err(format!("stat: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::Error, format!("stat: {}", e)))),
                     }
-                } else { Some(Err("file_stat(path)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "file_stat(path)".to_string()))) }
             }
             // file_lstat(path) — like stat but doesn't follow symlinks
             "file_lstat" | "lstat" => {
@@ -6523,9 +7771,9 @@ impl Interpreter {
                             map.insert("is_link".to_string(),Value::Bool(m.file_type().is_symlink()));
                             Some(Ok(Value::Map(map)))
                         }
-                        Err(e) => Some(Err(format!("lstat: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::Error, format!("lstat: {}", e)))),
                     }
-                } else { Some(Err("file_lstat(path)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "file_lstat(path)".to_string()))) }
             }
             // chmod(path, mode_oct)
             "chmod" | "file_chmod" => {
@@ -6535,9 +7783,9 @@ impl Interpreter {
                     let mode = args[1].as_int() as u32;
                     match std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)) {
                         Ok(_)  => Some(Ok(Value::Null)),
-                        Err(e) => Some(Err(format!("chmod: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, format!("chmod: {}", e)))),
                     }
-                } else { Some(Err("chmod(path, mode)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "chmod(path, mode)".to_string()))) }
             }
             // symlink(target, link_path)
             "symlink" | "file_symlink" => {
@@ -6546,27 +7794,27 @@ impl Interpreter {
                     let link   = args[1].as_string();
                     match std::os::unix::fs::symlink(&target, &link) {
                         Ok(_)  => Some(Ok(Value::Null)),
-                        Err(e) => Some(Err(format!("symlink: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, format!("symlink: {}", e)))),
                     }
-                } else { Some(Err("symlink(target, link_path)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "symlink(target, link_path)".to_string()))) }
             }
             // readlink(path) -> target string
             "readlink" => {
                 if let Some(v) = args.first() {
                     match std::fs::read_link(v.as_string()) {
                         Ok(p)  => Some(Ok(Value::String(p.to_string_lossy().to_string()))),
-                        Err(e) => Some(Err(format!("readlink: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, format!("readlink: {}", e)))),
                     }
-                } else { Some(Err("readlink(path)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "readlink(path)".to_string()))) }
             }
             // hardlink(src, dst)
             "hardlink" | "file_hardlink" => {
                 if args.len() >= 2 {
                     match std::fs::hard_link(args[0].as_string(), args[1].as_string()) {
                         Ok(_)  => Some(Ok(Value::Null)),
-                        Err(e) => Some(Err(format!("hardlink: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, format!("hardlink: {}", e)))),
                     }
-                } else { Some(Err("hardlink(src, dst)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "hardlink(src, dst)".to_string()))) }
             }
             // truncate(path, size)
             "truncate" | "file_truncate" => {
@@ -6575,12 +7823,12 @@ impl Interpreter {
                         Ok(f) => {
                             match f.set_len(args[1].as_int() as u64) {
                                 Ok(_)  => Some(Ok(Value::Null)),
-                                Err(e) => Some(Err(format!("truncate: {}", e))),
+                                Err(e) => Some(Err(raise(ErrorKind::Error, format!("truncate: {}", e)))),
                             }
                         }
-                        Err(e) => Some(Err(format!("truncate: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::Error, format!("truncate: {}", e)))),
                     }
-                } else { Some(Err("truncate(path, size)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "truncate(path, size)".to_string()))) }
             }
             // glob(pattern) -> array of paths
             "glob" | "file_glob" => {
@@ -6601,9 +7849,9 @@ impl Interpreter {
                                 .collect();
                             Some(Ok(Value::Array(paths)))
                         }
-                        Err(e) => Some(Err(format!("glob: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::Error, format!("glob: {}", e)))),
                     }
-                } else { Some(Err("glob(pattern)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "glob(pattern)".to_string()))) }
             }
 
             // ── mmap (memory-mapped files) ───────────────────────────────────
@@ -6620,14 +7868,14 @@ impl Interpreter {
                                 libc::mmap(std::ptr::null_mut(), size, libc::PROT_READ, libc::MAP_PRIVATE, f.as_raw_fd(), 0)
                             };
                             if ptr == libc::MAP_FAILED {
-                                Some(Err("mmap_file: mmap failed".to_string()))
+                                Some(Err(raise(ErrorKind::Error, "mmap_file: mmap failed".to_string())))
                             } else {
                                 Some(Ok(Value::Array(vec![Value::Int(ptr as i64), Value::Int(size as i64)])))
                             }
                         }
-                        Err(e) => Some(Err(format!("mmap_file: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::Error, format!("mmap_file: {}", e)))),
                     }
-                } else { Some(Err("mmap_file(path)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mmap_file(path)".to_string()))) }
             }
             // mmap_anon(size) -> [ptr_int, size_int]  (anonymous RW mapping)
             "mmap_anon" => {
@@ -6638,11 +7886,11 @@ impl Interpreter {
                                    libc::MAP_PRIVATE | libc::MAP_ANONYMOUS, -1, 0)
                     };
                     if ptr == libc::MAP_FAILED {
-                        Some(Err("mmap_anon: mmap failed".to_string()))
+                        Some(Err(raise(ErrorKind::Error, "mmap_anon: mmap failed".to_string())))
                     } else {
                         Some(Ok(Value::Array(vec![Value::Int(ptr as i64), Value::Int(size as i64)])))
                     }
-                } else { Some(Err("mmap_anon(size)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mmap_anon(size)".to_string()))) }
             }
             // munmap(ptr, size)
             "munmap" => {
@@ -6651,7 +7899,7 @@ impl Interpreter {
                     let size = args[1].as_int() as usize;
                     unsafe { libc::munmap(ptr, size); }
                     Some(Ok(Value::Null))
-                } else { Some(Err("munmap(ptr, size)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "munmap(ptr, size)".to_string()))) }
             }
             // mprotect(ptr, size, prot_flags)  e.g. mprotect(ptr, sz, 7) = RWX
             "mprotect" => {
@@ -6661,7 +7909,7 @@ impl Interpreter {
                     let prot  = args[2].as_int() as libc::c_int;
                     let r = unsafe { libc::mprotect(ptr, size, prot) };
                     Some(Ok(Value::Bool(r == 0)))
-                } else { Some(Err("mprotect(ptr, size, prot)".to_string())) }
+                } else { Some(Err(raise(ErrorKind::TypeError, "mprotect(ptr, size, prot)".to_string()))) }
             }
             // mmap protection constants
             "PROT_READ"  => Some(Ok(Value::Int(libc::PROT_READ  as i64))),
@@ -6675,7 +7923,7 @@ impl Interpreter {
 
             // db_open(path) -> int handle
             "db_open" => {
-                if args.is_empty() { return Some(Err("db_open(path)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "db_open(path)".to_string()))); }
                 let path = args[0].as_string();
                 match rusqlite::Connection::open(&path) {
                     Ok(conn) => {
@@ -6684,7 +7932,7 @@ impl Interpreter {
                         self.db_connections.insert(id, conn);
                         Some(Ok(Value::Int(id)))
                     }
-                    Err(e) => Some(Err(format!("db_open: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::Error, format!("db_open: {}", e)))),
                 }
             }
             // db_open_memory() -> int handle  (in-memory DB)
@@ -6696,27 +7944,27 @@ impl Interpreter {
                         self.db_connections.insert(id, conn);
                         Some(Ok(Value::Int(id)))
                     }
-                    Err(e) => Some(Err(format!("db_open_memory: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::Error, format!("db_open_memory: {}", e)))),
                 }
             }
             // db_exec(handle, sql) -> bool
             "db_exec" => {
-                if args.len() < 2 { return Some(Err("db_exec(handle, sql)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "db_exec(handle, sql)".to_string()))); }
                 let id = args[0].as_int();
                 let sql = args[1].as_string();
                 match self.db_connections.get(&id) {
-                    None => Some(Err(format!("db_exec: invalid handle {}", id))),
+                    None => Some(Err(raise(ErrorKind::ValueError, format!("db_exec: invalid handle {}", id)))),
                     Some(conn) => {
                         match conn.execute_batch(&sql) {
                             Ok(_)  => Some(Ok(Value::Bool(true))),
-                            Err(e) => Some(Err(format!("db_exec: {}", e))),
+                            Err(e) => Some(Err(raise(ErrorKind::Error, format!("db_exec: {}", e)))),
                         }
                     }
                 }
             }
             // db_query(handle, sql) -> array of maps
             "db_query" => {
-                if args.len() < 2 { return Some(Err("db_query(handle, sql)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "db_query(handle, sql)".to_string()))); }
                 let id = args[0].as_int();
                 let sql = args[1].as_string();
                 // bind params (optional 3rd arg: array of values)
@@ -6732,7 +7980,7 @@ impl Interpreter {
                     }
                 } else { vec![] };
                 match self.db_connections.get(&id) {
-                    None => Some(Err(format!("db_query: invalid handle {}", id))),
+                    None => Some(Err(raise(ErrorKind::ValueError, format!("db_query: invalid handle {}", id)))),
                     Some(conn) => {
                         let result = (|| -> Result<Value, rusqlite::Error> {
                             let mut stmt = conn.prepare(&sql)?;
@@ -6760,18 +8008,18 @@ impl Interpreter {
                         })();
                         match result {
                             Ok(v) => Some(Ok(v)),
-                            Err(e) => Some(Err(format!("db_query: {}", e))),
+                            Err(e) => Some(Err(raise(ErrorKind::Error, format!("db_query: {}", e)))),
                         }
                     }
                 }
             }
             // db_query_one(handle, sql) -> map or null
             "db_query_one" => {
-                if args.len() < 2 { return Some(Err("db_query_one(handle, sql)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "db_query_one(handle, sql)".to_string()))); }
                 let id = args[0].as_int();
                 let sql = args[1].as_string();
                 match self.db_connections.get(&id) {
-                    None => Some(Err(format!("db_query_one: invalid handle {}", id))),
+                    None => Some(Err(raise(ErrorKind::ValueError, format!("db_query_one: invalid handle {}", id)))),
                     Some(conn) => {
                         let result = (|| -> Result<Value, rusqlite::Error> {
                             let mut stmt = conn.prepare(&sql)?;
@@ -6797,23 +8045,23 @@ impl Interpreter {
                         })();
                         match result {
                             Ok(v) => Some(Ok(v)),
-                            Err(e) => Some(Err(format!("db_query_one: {}", e))),
+                            Err(e) => Some(Err(raise(ErrorKind::Error, format!("db_query_one: {}", e)))),
                         }
                     }
                 }
             }
             // db_last_insert_id(handle) -> int
             "db_last_insert_id" => {
-                if args.is_empty() { return Some(Err("db_last_insert_id(handle)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "db_last_insert_id(handle)".to_string()))); }
                 let id = args[0].as_int();
                 match self.db_connections.get(&id) {
-                    None => Some(Err(format!("db_last_insert_id: invalid handle {}", id))),
+                    None => Some(Err(raise(ErrorKind::ValueError, format!("db_last_insert_id: invalid handle {}", id)))),
                     Some(conn) => Some(Ok(Value::Int(conn.last_insert_rowid()))),
                 }
             }
             // db_close(handle) -> bool
             "db_close" => {
-                if args.is_empty() { return Some(Err("db_close(handle)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "db_close(handle)".to_string()))); }
                 let id = args[0].as_int();
                 let removed = self.db_connections.remove(&id);
                 Some(Ok(Value::Bool(removed.is_some())))
@@ -6825,111 +8073,111 @@ impl Interpreter {
 
             // gzip_compress(bytes_array) -> bytes_array
             "gzip_compress" => {
-                if args.is_empty() { return Some(Err("gzip_compress(bytes)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "gzip_compress(bytes)".to_string()))); }
                 let bytes = match &args[0] {
                     Value::Array(a) => a.iter().map(|v| v.as_int() as u8).collect::<Vec<u8>>(),
                     Value::String(s) => s.as_bytes().to_vec(),
-                    _ => return Some(Err("gzip_compress: expected array or string".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "gzip_compress: expected array or string".to_string()))),
                 };
                 let mut enc = GzEncoder::new(Vec::new(), Compression::default());
-                if let Err(e) = enc.write_all(&bytes) { return Some(Err(format!("gzip_compress: {}", e))); }
+                if let Err(e) = enc.write_all(&bytes) { return Some(Err(raise(ErrorKind::Error, format!("gzip_compress: {}", e)))); }
                 match enc.finish() {
                     Ok(out) => Some(Ok(Value::Array(out.iter().map(|b| Value::Int(*b as i64)).collect()))),
-                    Err(e)  => Some(Err(format!("gzip_compress: {}", e))),
+                    Err(e)  => Some(Err(raise(ErrorKind::Error, format!("gzip_compress: {}", e)))),
                 }
             }
             // gzip_decompress(bytes_array) -> bytes_array
             "gzip_decompress" => {
-                if args.is_empty() { return Some(Err("gzip_decompress(bytes)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "gzip_decompress(bytes)".to_string()))); }
                 let bytes = match &args[0] {
                     Value::Array(a) => a.iter().map(|v| v.as_int() as u8).collect::<Vec<u8>>(),
-                    _ => return Some(Err("gzip_decompress: expected array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "gzip_decompress: expected array".to_string()))),
                 };
                 let mut dec = GzDecoder::new(bytes.as_slice());
                 let mut out = Vec::new();
-                if let Err(e) = dec.read_to_end(&mut out) { return Some(Err(format!("gzip_decompress: {}", e))); }
+                if let Err(e) = dec.read_to_end(&mut out) { return Some(Err(raise(ErrorKind::Error, format!("gzip_decompress: {}", e)))); }
                 Some(Ok(Value::Array(out.iter().map(|b| Value::Int(*b as i64)).collect())))
             }
             // zlib_compress(bytes_array) -> bytes_array
             "zlib_compress" => {
-                if args.is_empty() { return Some(Err("zlib_compress(bytes)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "zlib_compress(bytes)".to_string()))); }
                 let bytes = match &args[0] {
                     Value::Array(a) => a.iter().map(|v| v.as_int() as u8).collect::<Vec<u8>>(),
                     Value::String(s) => s.as_bytes().to_vec(),
-                    _ => return Some(Err("zlib_compress: expected array or string".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "zlib_compress: expected array or string".to_string()))),
                 };
                 let mut enc = ZlibEncoder::new(Vec::new(), Compression::default());
-                if let Err(e) = enc.write_all(&bytes) { return Some(Err(format!("zlib_compress: {}", e))); }
+                if let Err(e) = enc.write_all(&bytes) { return Some(Err(raise(ErrorKind::Error, format!("zlib_compress: {}", e)))); }
                 match enc.finish() {
                     Ok(out) => Some(Ok(Value::Array(out.iter().map(|b| Value::Int(*b as i64)).collect()))),
-                    Err(e)  => Some(Err(format!("zlib_compress: {}", e))),
+                    Err(e)  => Some(Err(raise(ErrorKind::Error, format!("zlib_compress: {}", e)))),
                 }
             }
             // zlib_decompress(bytes_array) -> bytes_array
             "zlib_decompress" => {
-                if args.is_empty() { return Some(Err("zlib_decompress(bytes)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "zlib_decompress(bytes)".to_string()))); }
                 let bytes = match &args[0] {
                     Value::Array(a) => a.iter().map(|v| v.as_int() as u8).collect::<Vec<u8>>(),
-                    _ => return Some(Err("zlib_decompress: expected array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "zlib_decompress: expected array".to_string()))),
                 };
                 let mut dec = ZlibDecoder::new(bytes.as_slice());
                 let mut out = Vec::new();
-                if let Err(e) = dec.read_to_end(&mut out) { return Some(Err(format!("zlib_decompress: {}", e))); }
+                if let Err(e) = dec.read_to_end(&mut out) { return Some(Err(raise(ErrorKind::Error, format!("zlib_decompress: {}", e)))); }
                 Some(Ok(Value::Array(out.iter().map(|b| Value::Int(*b as i64)).collect())))
             }
             // deflate_compress(bytes_array) -> bytes_array
             "deflate_compress" => {
-                if args.is_empty() { return Some(Err("deflate_compress(bytes)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "deflate_compress(bytes)".to_string()))); }
                 let bytes = match &args[0] {
                     Value::Array(a) => a.iter().map(|v| v.as_int() as u8).collect::<Vec<u8>>(),
                     Value::String(s) => s.as_bytes().to_vec(),
-                    _ => return Some(Err("deflate_compress: expected array or string".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "deflate_compress: expected array or string".to_string()))),
                 };
                 let mut enc = DeflateEncoder::new(Vec::new(), Compression::default());
-                if let Err(e) = enc.write_all(&bytes) { return Some(Err(format!("deflate_compress: {}", e))); }
+                if let Err(e) = enc.write_all(&bytes) { return Some(Err(raise(ErrorKind::Error, format!("deflate_compress: {}", e)))); }
                 match enc.finish() {
                     Ok(out) => Some(Ok(Value::Array(out.iter().map(|b| Value::Int(*b as i64)).collect()))),
-                    Err(e)  => Some(Err(format!("deflate_compress: {}", e))),
+                    Err(e)  => Some(Err(raise(ErrorKind::Error, format!("deflate_compress: {}", e)))),
                 }
             }
             // deflate_decompress(bytes_array) -> bytes_array
             "deflate_decompress" => {
-                if args.is_empty() { return Some(Err("deflate_decompress(bytes)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "deflate_decompress(bytes)".to_string()))); }
                 let bytes = match &args[0] {
                     Value::Array(a) => a.iter().map(|v| v.as_int() as u8).collect::<Vec<u8>>(),
-                    _ => return Some(Err("deflate_decompress: expected array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "deflate_decompress: expected array".to_string()))),
                 };
                 let mut dec = DeflateDecoder::new(bytes.as_slice());
                 let mut out = Vec::new();
-                if let Err(e) = dec.read_to_end(&mut out) { return Some(Err(format!("deflate_decompress: {}", e))); }
+                if let Err(e) = dec.read_to_end(&mut out) { return Some(Err(raise(ErrorKind::Error, format!("deflate_decompress: {}", e)))); }
                 Some(Ok(Value::Array(out.iter().map(|b| Value::Int(*b as i64)).collect())))
             }
             // compress_level(bytes, level) -> bytes  (gzip with custom level 0-9)
             "gzip_compress_level" => {
-                if args.len() < 2 { return Some(Err("gzip_compress_level(bytes, level)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "gzip_compress_level(bytes, level)".to_string()))); }
                 let bytes = match &args[0] {
                     Value::Array(a) => a.iter().map(|v| v.as_int() as u8).collect::<Vec<u8>>(),
                     Value::String(s) => s.as_bytes().to_vec(),
-                    _ => return Some(Err("gzip_compress_level: expected array or string".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "gzip_compress_level: expected array or string".to_string()))),
                 };
                 let level = (args[1].as_int() as u32).min(9);
                 let mut enc = GzEncoder::new(Vec::new(), Compression::new(level));
-                if let Err(e) = enc.write_all(&bytes) { return Some(Err(format!("gzip_compress_level: {}", e))); }
+                if let Err(e) = enc.write_all(&bytes) { return Some(Err(raise(ErrorKind::Error, format!("gzip_compress_level: {}", e)))); }
                 match enc.finish() {
                     Ok(out) => Some(Ok(Value::Array(out.iter().map(|b| Value::Int(*b as i64)).collect()))),
-                    Err(e)  => Some(Err(format!("gzip_compress_level: {}", e))),
+                    Err(e)  => Some(Err(raise(ErrorKind::Error, format!("gzip_compress_level: {}", e)))),
                 }
             }
             // gzip_decompress_str(bytes_array) -> string
             "gzip_decompress_str" => {
-                if args.is_empty() { return Some(Err("gzip_decompress_str(bytes)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "gzip_decompress_str(bytes)".to_string()))); }
                 let bytes = match &args[0] {
                     Value::Array(a) => a.iter().map(|v| v.as_int() as u8).collect::<Vec<u8>>(),
-                    _ => return Some(Err("gzip_decompress_str: expected array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "gzip_decompress_str: expected array".to_string()))),
                 };
                 let mut dec = GzDecoder::new(bytes.as_slice());
                 let mut out = String::new();
-                if let Err(e) = dec.read_to_string(&mut out) { return Some(Err(format!("gzip_decompress_str: {}", e))); }
+                if let Err(e) = dec.read_to_string(&mut out) { return Some(Err(raise(ErrorKind::Error, format!("gzip_decompress_str: {}", e)))); }
                 Some(Ok(Value::String(out)))
             }
 
@@ -6939,31 +8187,31 @@ impl Interpreter {
 
             // chr(n) -> single-char string  (e.g. chr(65) == "A")
             "chr" => {
-                if args.is_empty() { return Some(Err("chr(int)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "chr(int)".to_string()))); }
                 let n = args[0].as_int() as u32;
                 match char::from_u32(n) {
                     Some(c) => Some(Ok(Value::String(c.to_string()))),
-                    None    => Some(Err(format!("chr: {} is not a valid Unicode code point", n))),
+                    None    => Some(Err(raise(ErrorKind::Error, format!("chr: {} is not a valid Unicode code point", n)))),
                 }
             }
             // ord(s) -> int (code point of first character)
             "ord" => {
-                if args.is_empty() { return Some(Err("ord(str)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "ord(str)".to_string()))); }
                 let s = args[0].as_string();
                 match s.chars().next() {
                     Some(c) => Some(Ok(Value::Int(c as i64))),
-                    None    => Some(Err("ord: empty string".to_string())),
+                    None    => Some(Err(raise(ErrorKind::ValueError, "ord: empty string".to_string()))),
                 }
             }
             // str_bytes(s) -> array of ints (UTF-8 bytes)
             "str_bytes" => {
-                if args.is_empty() { return Some(Err("str_bytes(str)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "str_bytes(str)".to_string()))); }
                 let s = args[0].as_string();
                 Some(Ok(Value::Array(s.as_bytes().iter().map(|b| Value::Int(*b as i64)).collect())))
             }
             // bytes_str(array) -> string (from UTF-8 byte array)
             "bytes_str" => {
-                if args.is_empty() { return Some(Err("bytes_str(array)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "bytes_str(array)".to_string()))); }
                 match &args[0] {
                     Value::Array(a) => {
                         let bytes: Vec<u8> = a.iter().map(|v| v.as_int() as u8).collect();
@@ -6976,31 +8224,31 @@ impl Interpreter {
                             }
                         }
                     }
-                    _ => Some(Err("bytes_str: expected array".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "bytes_str: expected array".to_string()))),
                 }
             }
             // str_chars(s) -> array of single-char strings
             "str_chars" => {
-                if args.is_empty() { return Some(Err("str_chars(str)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "str_chars(str)".to_string()))); }
                 let s = args[0].as_string();
                 Some(Ok(Value::Array(s.chars().map(|c| Value::String(c.to_string())).collect())))
             }
             // str_codepoints(s) -> array of ints (Unicode code points)
             "str_codepoints" => {
-                if args.is_empty() { return Some(Err("str_codepoints(str)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "str_codepoints(str)".to_string()))); }
                 let s = args[0].as_string();
                 Some(Ok(Value::Array(s.chars().map(|c| Value::Int(c as i64)).collect())))
             }
             // str_repeat(s, n) -> string
             "str_repeat" => {
-                if args.len() < 2 { return Some(Err("str_repeat(str, n)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "str_repeat(str, n)".to_string()))); }
                 let s = args[0].as_string();
                 let n = args[1].as_int().max(0) as usize;
                 Some(Ok(Value::String(s.repeat(n))))
             }
             // str_pad_left(s, width, pad_char) -> string
             "str_pad_left" => {
-                if args.len() < 3 { return Some(Err("str_pad_left(str, width, pad_char)".to_string())); }
+                if args.len() < 3 { return Some(Err(raise(ErrorKind::TypeError, "str_pad_left(str, width, pad_char)".to_string()))); }
                 let s = args[0].as_string();
                 let width = args[1].as_int().max(0) as usize;
                 let pad = args[2].as_string();
@@ -7014,7 +8262,7 @@ impl Interpreter {
             }
             // str_pad_right(s, width, pad_char) -> string
             "str_pad_right" => {
-                if args.len() < 3 { return Some(Err("str_pad_right(str, width, pad_char)".to_string())); }
+                if args.len() < 3 { return Some(Err(raise(ErrorKind::TypeError, "str_pad_right(str, width, pad_char)".to_string()))); }
                 let s = args[0].as_string();
                 let width = args[1].as_int().max(0) as usize;
                 let pad = args[2].as_string();
@@ -7028,7 +8276,7 @@ impl Interpreter {
             }
             // sprintf(fmt, ...args) -> string  — simple %d %f %s %x %o %b support
             "sprintf" => {
-                if args.is_empty() { return Some(Err("sprintf(fmt, ...)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "sprintf(fmt, ...)".to_string()))); }
                 let fmt = args[0].as_string();
                 let mut result = String::new();
                 let mut arg_idx = 1usize;
@@ -7132,12 +8380,12 @@ impl Interpreter {
             }
             // printf(fmt, ...args) — print formatted string with newline
             "printf" => {
-                if args.is_empty() { return Some(Err("printf(fmt, ...)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "printf(fmt, ...)".to_string()))); }
                 // reuse sprintf logic via recursive call
                 let s = match self.call_builtin("sprintf", args) {
                     Some(Ok(v)) => v.as_string(),
                     Some(Err(e)) => return Some(Err(e)),
-                    None => return Some(Err("printf: sprintf failed".to_string())),
+                    None => return Some(Err(raise(ErrorKind::Error, "printf: sprintf failed".to_string()))),
                 };
                 print!("{}", s);
                 Some(Ok(Value::Null))
@@ -7149,10 +8397,10 @@ impl Interpreter {
 
             // aes128_ecb_encrypt(plaintext_bytes, key_bytes) -> bytes
             "aes128_ecb_encrypt" => {
-                if args.len() < 2 { return Some(Err("aes128_ecb_encrypt(plaintext, key)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "aes128_ecb_encrypt(plaintext, key)".to_string()))); }
                 let data = val_to_bytes(&args[0]);
                 let key  = val_to_bytes(&args[1]);
-                if key.len() != 16 { return Some(Err("aes128_ecb_encrypt: key must be 16 bytes".to_string())); }
+                if key.len() != 16 { return Some(Err(raise(ErrorKind::TypeError, "aes128_ecb_encrypt: key must be 16 bytes".to_string()))); }
                 let key16: [u8; 16] = key[..16].try_into().unwrap();
                 let padded = pkcs7_pad(&data, 16);
                 let mut out = Vec::with_capacity(padded.len());
@@ -7165,11 +8413,11 @@ impl Interpreter {
             }
             // aes128_ecb_decrypt(ciphertext_bytes, key_bytes) -> bytes
             "aes128_ecb_decrypt" => {
-                if args.len() < 2 { return Some(Err("aes128_ecb_decrypt(ciphertext, key)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "aes128_ecb_decrypt(ciphertext, key)".to_string()))); }
                 let data = val_to_bytes(&args[0]);
                 let key  = val_to_bytes(&args[1]);
-                if key.len() != 16 { return Some(Err("aes128_ecb_decrypt: key must be 16 bytes".to_string())); }
-                if data.len() % 16 != 0 { return Some(Err("aes128_ecb_decrypt: ciphertext length not a multiple of 16".to_string())); }
+                if key.len() != 16 { return Some(Err(raise(ErrorKind::TypeError, "aes128_ecb_decrypt: key must be 16 bytes".to_string()))); }
+                if data.len() % 16 != 0 { return Some(Err(raise(ErrorKind::Error, "aes128_ecb_decrypt: ciphertext length not a multiple of 16".to_string()))); }
                 let key16: [u8; 16] = key[..16].try_into().unwrap();
                 let mut out = Vec::with_capacity(data.len());
                 for chunk in data.chunks(16) {
@@ -7179,17 +8427,17 @@ impl Interpreter {
                 }
                 match pkcs7_unpad(&out) {
                     Ok(v)  => Some(Ok(bytes_to_val(&v))),
-                    Err(e) => Some(Err(format!("aes128_ecb_decrypt: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("aes128_ecb_decrypt: {}", e)))),
                 }
             }
             // aes128_cbc_encrypt(plaintext_bytes, key_bytes, iv_bytes) -> bytes
             "aes128_cbc_encrypt" => {
-                if args.len() < 3 { return Some(Err("aes128_cbc_encrypt(plaintext, key, iv)".to_string())); }
+                if args.len() < 3 { return Some(Err(raise(ErrorKind::TypeError, "aes128_cbc_encrypt(plaintext, key, iv)".to_string()))); }
                 let data = val_to_bytes(&args[0]);
                 let key  = val_to_bytes(&args[1]);
                 let iv   = val_to_bytes(&args[2]);
-                if key.len() != 16 { return Some(Err("aes128_cbc_encrypt: key must be 16 bytes".to_string())); }
-                if iv.len() != 16  { return Some(Err("aes128_cbc_encrypt: iv must be 16 bytes".to_string())); }
+                if key.len() != 16 { return Some(Err(raise(ErrorKind::TypeError, "aes128_cbc_encrypt: key must be 16 bytes".to_string()))); }
+                if iv.len() != 16  { return Some(Err(raise(ErrorKind::TypeError, "aes128_cbc_encrypt: iv must be 16 bytes".to_string()))); }
                 let key16: [u8; 16] = key[..16].try_into().unwrap();
                 let mut prev: [u8; 16] = iv[..16].try_into().unwrap();
                 let padded = pkcs7_pad(&data, 16);
@@ -7205,13 +8453,13 @@ impl Interpreter {
             }
             // aes128_cbc_decrypt(ciphertext_bytes, key_bytes, iv_bytes) -> bytes
             "aes128_cbc_decrypt" => {
-                if args.len() < 3 { return Some(Err("aes128_cbc_decrypt(ciphertext, key, iv)".to_string())); }
+                if args.len() < 3 { return Some(Err(raise(ErrorKind::TypeError, "aes128_cbc_decrypt(ciphertext, key, iv)".to_string()))); }
                 let data = val_to_bytes(&args[0]);
                 let key  = val_to_bytes(&args[1]);
                 let iv   = val_to_bytes(&args[2]);
-                if key.len() != 16 { return Some(Err("aes128_cbc_decrypt: key must be 16 bytes".to_string())); }
-                if iv.len() != 16  { return Some(Err("aes128_cbc_decrypt: iv must be 16 bytes".to_string())); }
-                if data.len() % 16 != 0 { return Some(Err("aes128_cbc_decrypt: ciphertext length not a multiple of 16".to_string())); }
+                if key.len() != 16 { return Some(Err(raise(ErrorKind::TypeError, "aes128_cbc_decrypt: key must be 16 bytes".to_string()))); }
+                if iv.len() != 16  { return Some(Err(raise(ErrorKind::TypeError, "aes128_cbc_decrypt: iv must be 16 bytes".to_string()))); }
+                if data.len() % 16 != 0 { return Some(Err(raise(ErrorKind::Error, "aes128_cbc_decrypt: ciphertext length not a multiple of 16".to_string()))); }
                 let key16: [u8; 16] = key[..16].try_into().unwrap();
                 let mut prev: [u8; 16] = iv[..16].try_into().unwrap();
                 let mut out = Vec::with_capacity(data.len());
@@ -7225,12 +8473,12 @@ impl Interpreter {
                 }
                 match pkcs7_unpad(&out) {
                     Ok(v)  => Some(Ok(bytes_to_val(&v))),
-                    Err(e) => Some(Err(format!("aes128_cbc_decrypt: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("aes128_cbc_decrypt: {}", e)))),
                 }
             }
             // aes128_key_from_str(s) -> bytes (takes first 16 bytes, zero-pads)
             "aes128_key_from_str" => {
-                if args.is_empty() { return Some(Err("aes128_key_from_str(str)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "aes128_key_from_str(str)".to_string()))); }
                 let s = args[0].as_string();
                 let mut key = [0u8; 16];
                 let b = s.as_bytes();
@@ -7248,19 +8496,19 @@ impl Interpreter {
             // (intentionally left blank — map handles this via call_value at lines above)
             // foreach(array, closure) — call fn(item) for side effects
             "foreach" => {
-                if args.len() < 2 { return Some(Err("foreach(array, fn)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "foreach(array, fn)".to_string()))); }
                 let callable = args[1].clone();
                 match &args[0].clone() {
                     Value::Array(arr) => {
                         for item in arr.clone() { match self.call_value(callable.clone(), vec![item]) { Ok(_) => {} Err(e) => return Some(Err(e)) }; }
                         Some(Ok(Value::Null))
                     }
-                    _ => Some(Err("foreach: first arg must be array".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "foreach: first arg must be array".to_string()))),
                 }
             }
             // flat_map(array, closure) -> array
             "flat_map" => {
-                if args.len() < 2 { return Some(Err("flat_map(array, fn)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "flat_map(array, fn)".to_string()))); }
                 let callable = args[1].clone();
                 match &args[0].clone() {
                     Value::Array(arr) => {
@@ -7271,30 +8519,30 @@ impl Interpreter {
                         }
                         Some(Ok(Value::Array(out)))
                     }
-                    _ => Some(Err("flat_map: first arg must be array".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "flat_map: first arg must be array".to_string()))),
                 }
             }
             // zip(a, b) -> array of [a[i], b[i]] pairs
             "zip" => {
-                if args.len() < 2 { return Some(Err("zip(a, b)".to_string())); }
-                let a = match &args[0] { Value::Array(x) => x.clone(), _ => return Some(Err("zip: both args must be arrays".to_string())), };
-                let b = match &args[1] { Value::Array(x) => x.clone(), _ => return Some(Err("zip: both args must be arrays".to_string())), };
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "zip(a, b)".to_string()))); }
+                let a = match &args[0] { Value::Array(x) => x.clone(), _ => return Some(Err(raise(ErrorKind::TypeError, "zip: both args must be arrays".to_string()))), };
+                let b = match &args[1] { Value::Array(x) => x.clone(), _ => return Some(Err(raise(ErrorKind::TypeError, "zip: both args must be arrays".to_string()))), };
                 let len = a.len().min(b.len());
                 Some(Ok(Value::Array((0..len).map(|i| Value::Array(vec![a[i].clone(), b[i].clone()])).collect())))
             }
             // enumerate(array) -> array of [index, value] pairs
             "enumerate" => {
-                if args.is_empty() { return Some(Err("enumerate(array)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "enumerate(array)".to_string()))); }
                 match &args[0] {
                     Value::Array(arr) => {
                         Some(Ok(Value::Array(arr.iter().enumerate().map(|(i, v)| Value::Array(vec![Value::Int(i as i64), v.clone()])).collect())))
                     }
-                    _ => Some(Err("enumerate: expected array".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "enumerate: expected array".to_string()))),
                 }
             }
             // all(array, closure) -> bool
             "all" => {
-                if args.len() < 2 { return Some(Err("all(array, fn)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "all(array, fn)".to_string()))); }
                 let callable = args[1].clone();
                 match &args[0].clone() {
                     Value::Array(arr) => {
@@ -7303,12 +8551,12 @@ impl Interpreter {
                         }
                         Some(Ok(Value::Bool(true)))
                     }
-                    _ => Some(Err("all: first arg must be array".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "all: first arg must be array".to_string()))),
                 }
             }
             // any(array, closure) -> bool
             "any" => {
-                if args.len() < 2 { return Some(Err("any(array, fn)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "any(array, fn)".to_string()))); }
                 let callable = args[1].clone();
                 match &args[0].clone() {
                     Value::Array(arr) => {
@@ -7317,12 +8565,12 @@ impl Interpreter {
                         }
                         Some(Ok(Value::Bool(false)))
                     }
-                    _ => Some(Err("any: first arg must be array".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "any: first arg must be array".to_string()))),
                 }
             }
             // count_if(array, closure) -> int
             "count_if" => {
-                if args.len() < 2 { return Some(Err("count_if(array, fn)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "count_if(array, fn)".to_string()))); }
                 let callable = args[1].clone();
                 match &args[0].clone() {
                     Value::Array(arr) => {
@@ -7330,45 +8578,45 @@ impl Interpreter {
                         for item in arr.clone() { let r = match self.call_value(callable.clone(), vec![item]) { Ok(v) => v, Err(e) => return Some(Err(e)) }; if r.is_truthy() { n += 1; } }
                         Some(Ok(Value::Int(n)))
                     }
-                    _ => Some(Err("count_if: first arg must be array".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "count_if: first arg must be array".to_string()))),
                 }
             }
             // unique(array) -> array (removes duplicates, preserves order)
             "unique" | "distinct" => {
-                if args.is_empty() { return Some(Err("unique(array)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "unique(array)".to_string()))); }
                 match &args[0] {
                     Value::Array(arr) => {
                         let mut seen: Vec<Value> = Vec::new();
                         for v in arr { if !seen.contains(v) { seen.push(v.clone()); } }
                         Some(Ok(Value::Array(seen)))
                     }
-                    _ => Some(Err("unique: expected array".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "unique: expected array".to_string()))),
                 }
             }
             // flatten(array) -> array (one level deep)
             "flatten" => {
-                if args.is_empty() { return Some(Err("flatten(array)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "flatten(array)".to_string()))); }
                 match &args[0] {
                     Value::Array(arr) => {
                         let mut out = Vec::new();
                         for v in arr { match v { Value::Array(inner) => out.extend(inner.clone()), other => out.push(other.clone()), } }
                         Some(Ok(Value::Array(out)))
                     }
-                    _ => Some(Err("flatten: expected array".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "flatten: expected array".to_string()))),
                 }
             }
             // chunk(array, size) -> array of arrays
             "chunk" => {
-                if args.len() < 2 { return Some(Err("chunk(array, size)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "chunk(array, size)".to_string()))); }
                 let size = args[1].as_int().max(1) as usize;
                 match &args[0] {
                     Value::Array(arr) => Some(Ok(Value::Array(arr.chunks(size).map(|c| Value::Array(c.to_vec())).collect()))),
-                    _ => Some(Err("chunk: expected array".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "chunk: expected array".to_string()))),
                 }
             }
             // partition(array, closure) -> [matching_array, not_matching_array]
             "partition" => {
-                if args.len() < 2 { return Some(Err("partition(array, fn)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "partition(array, fn)".to_string()))); }
                 let callable = args[1].clone();
                 match &args[0].clone() {
                     Value::Array(arr) => {
@@ -7378,30 +8626,30 @@ impl Interpreter {
                         }
                         Some(Ok(Value::Array(vec![Value::Array(yes), Value::Array(no)])))
                     }
-                    _ => Some(Err("partition: first arg must be array".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "partition: first arg must be array".to_string()))),
                 }
             }
             // take(array, n) -> first n elements
             "take" => {
-                if args.len() < 2 { return Some(Err("take(array, n)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "take(array, n)".to_string()))); }
                 let n = args[1].as_int().max(0) as usize;
                 match &args[0] {
                     Value::Array(arr) => Some(Ok(Value::Array(arr[..n.min(arr.len())].to_vec()))),
-                    _ => Some(Err("take: expected array".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "take: expected array".to_string()))),
                 }
             }
             // drop(array, n) -> skip first n elements
             "drop" => {
-                if args.len() < 2 { return Some(Err("drop(array, n)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "drop(array, n)".to_string()))); }
                 let n = args[1].as_int().max(0) as usize;
                 match &args[0] {
                     Value::Array(arr) => Some(Ok(Value::Array(arr[n.min(arr.len())..].to_vec()))),
-                    _ => Some(Err("drop: expected array".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "drop: expected array".to_string()))),
                 }
             }
             // sum(array) -> number
             "array_sum" | "sum" => {
-                if args.is_empty() { return Some(Err("sum(array)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "sum(array)".to_string()))); }
                 match &args[0] {
                     Value::Array(arr) => {
                         let mut is_float = false;
@@ -7412,12 +8660,12 @@ impl Interpreter {
                         }
                         if is_float { Some(Ok(Value::Float(sf))) } else { Some(Ok(Value::Int(si))) }
                     }
-                    _ => Some(Err("sum: expected array".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "sum: expected array".to_string()))),
                 }
             }
             // array_min(array) -> value
             "array_min" => {
-                if args.is_empty() { return Some(Err("array_min(array)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "array_min(array)".to_string()))); }
                 match &args[0] {
                     Value::Array(arr) if !arr.is_empty() => {
                         let mut best = arr[0].clone();
@@ -7425,12 +8673,12 @@ impl Interpreter {
                         Some(Ok(best))
                     }
                     Value::Array(_) => Some(Ok(Value::Null)),
-                    _ => Some(Err("array_min: expected array".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "array_min: expected array".to_string()))),
                 }
             }
             // array_max(array) -> value
             "array_max" => {
-                if args.is_empty() { return Some(Err("array_max(array)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "array_max(array)".to_string()))); }
                 match &args[0] {
                     Value::Array(arr) if !arr.is_empty() => {
                         let mut best = arr[0].clone();
@@ -7438,47 +8686,47 @@ impl Interpreter {
                         Some(Ok(best))
                     }
                     Value::Array(_) => Some(Ok(Value::Null)),
-                    _ => Some(Err("array_max: expected array".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "array_max: expected array".to_string()))),
                 }
             }
             // range(start?, end, step?) -> array of integers
             "range" => {
-                if args.is_empty() { return Some(Err("range(end) or range(start, end) or range(start, end, step)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "range(end) or range(start, end) or range(start, end, step)".to_string()))); }
                 let (start, end, step) = match args.len() {
                     1 => (0i64, args[0].as_int(), 1i64),
                     2 => (args[0].as_int(), args[1].as_int(), 1i64),
                     _ => (args[0].as_int(), args[1].as_int(), args[2].as_int()),
                 };
-                if step == 0 { return Some(Err("range: step cannot be zero".to_string())); }
+                if step == 0 { return Some(Err(raise(ErrorKind::ValueError, "range: step cannot be zero".to_string()))); }
                 let mut out = Vec::new();
                 let mut i = start;
                 while (step > 0 && i < end) || (step < 0 && i > end) {
                     out.push(Value::Int(i));
                     i += step;
-                    if out.len() > 10_000_000 { return Some(Err("range: too many elements".to_string())); }
+                    if out.len() > 10_000_000 { return Some(Err(raise(ErrorKind::Error, "range: too many elements".to_string()))); }
                 }
                 Some(Ok(Value::Array(out)))
             }
             // contains(array_or_string, value) -> bool
             "array_contains" | "contains" => {
-                if args.len() < 2 { return Some(Err("contains(array, value)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "contains(array, value)".to_string()))); }
                 match &args[0] {
                     Value::Array(arr) => Some(Ok(Value::Bool(arr.contains(&args[1])))),
                     Value::String(s)  => Some(Ok(Value::Bool(s.contains(&args[1].as_string() as &str)))),
-                    _ => Some(Err("contains: expected array or string".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "contains: expected array or string".to_string()))),
                 }
             }
             // index_of(array, value) -> int (-1 if not found)
             "array_index" | "index_of" => {
-                if args.len() < 2 { return Some(Err("index_of(array, value)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "index_of(array, value)".to_string()))); }
                 match &args[0] {
                     Value::Array(arr) => Some(Ok(Value::Int(arr.iter().position(|v| v == &args[1]).map(|p| p as i64).unwrap_or(-1)))),
-                    _ => Some(Err("index_of: expected array".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "index_of: expected array".to_string()))),
                 }
             }
             // sort_cmp(array, closure) -> sorted array (fn(a,b) returns negative/0/positive)
             "sort_cmp" => {
-                if args.len() < 2 { return Some(Err("sort_cmp(array, fn)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "sort_cmp(array, fn)".to_string()))); }
                 let callable = args[1].clone();
                 match &args[0].clone() {
                     Value::Array(arr) => {
@@ -7493,72 +8741,72 @@ impl Interpreter {
                         }
                         Some(Ok(Value::Array(sorted)))
                     }
-                    _ => Some(Err("sort_cmp: first arg must be array".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "sort_cmp: first arg must be array".to_string()))),
                 }
             }
             // keys(map) -> sorted array of keys
             "map_keys" | "keys" => {
-                if args.is_empty() { return Some(Err("keys(map)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "keys(map)".to_string()))); }
                 match &args[0] {
                     Value::Map(m) => {
                         let mut out: Vec<Value> = m.keys().map(|k| Value::String(k.clone())).collect();
                         out.sort_by(|a, b| a.as_string().cmp(&b.as_string()));
                         Some(Ok(Value::Array(out)))
                     }
-                    _ => Some(Err("keys: expected map".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "keys: expected map".to_string()))),
                 }
             }
             // values(map) -> array of values
             "map_values" | "values" => {
-                if args.is_empty() { return Some(Err("values(map)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "values(map)".to_string()))); }
                 match &args[0] {
                     Value::Map(m) => {
                         let mut kvs: Vec<(&String, &Value)> = m.iter().collect();
                         kvs.sort_by_key(|(k, _)| k.as_str());
                         Some(Ok(Value::Array(kvs.into_iter().map(|(_, v)| v.clone()).collect())))
                     }
-                    _ => Some(Err("values: expected map".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "values: expected map".to_string()))),
                 }
             }
             // entries(map) -> array of [key, value] pairs
             "map_entries" | "entries" => {
-                if args.is_empty() { return Some(Err("entries(map)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "entries(map)".to_string()))); }
                 match &args[0] {
                     Value::Map(m) => {
                         let mut kvs: Vec<(&String, &Value)> = m.iter().collect();
                         kvs.sort_by_key(|(k, _)| k.as_str());
                         Some(Ok(Value::Array(kvs.into_iter().map(|(k, v)| Value::Array(vec![Value::String(k.clone()), v.clone()])).collect())))
                     }
-                    _ => Some(Err("entries: expected map".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "entries: expected map".to_string()))),
                 }
             }
             // merge(m1, m2) -> merged map (m2 overwrites m1)
             "map_merge" | "merge" => {
-                if args.len() < 2 { return Some(Err("merge(map1, map2)".to_string())); }
-                let mut out = match &args[0] { Value::Map(m) => m.clone(), _ => return Some(Err("merge: args must be maps".to_string())), };
-                match &args[1] { Value::Map(m) => { for (k, v) in m { out.insert(k.clone(), v.clone()); } } _ => return Some(Err("merge: args must be maps".to_string())), }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "merge(map1, map2)".to_string()))); }
+                let mut out = match &args[0] { Value::Map(m) => m.clone(), _ => return Some(Err(raise(ErrorKind::TypeError, "merge: args must be maps".to_string()))), };
+                match &args[1] { Value::Map(m) => { for (k, v) in m { out.insert(k.clone(), v.clone()); } } _ => return Some(Err(raise(ErrorKind::TypeError, "merge: args must be maps".to_string()))), }
                 Some(Ok(Value::Map(out)))
             }
             // has_key(map, key) -> bool
             "map_has" | "has_key" => {
-                if args.len() < 2 { return Some(Err("has_key(map, key)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "has_key(map, key)".to_string()))); }
                 match &args[0] {
                     Value::Map(m) => Some(Ok(Value::Bool(m.contains_key(&args[1].as_string())))),
-                    _ => Some(Err("has_key: expected map".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "has_key: expected map".to_string()))),
                 }
             }
             // map_remove(map, key) -> new map without key
             "map_remove" => {
-                if args.len() < 2 { return Some(Err("map_remove(map, key)".to_string())); }
-                let mut m = match &args[0] { Value::Map(m) => m.clone(), _ => return Some(Err("map_remove: expected map".to_string())), };
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "map_remove(map, key)".to_string()))); }
+                let mut m = match &args[0] { Value::Map(m) => m.clone(), _ => return Some(Err(raise(ErrorKind::TypeError, "map_remove: expected map".to_string()))), };
                 m.remove(&args[1].as_string());
                 Some(Ok(Value::Map(m)))
             }
             // map_from_arrays(keys, values) -> map
             "map_from_arrays" => {
-                if args.len() < 2 { return Some(Err("map_from_arrays(keys, values)".to_string())); }
-                let ks = match &args[0] { Value::Array(a) => a.clone(), _ => return Some(Err("map_from_arrays: keys must be array".to_string())), };
-                let vs = match &args[1] { Value::Array(a) => a.clone(), _ => return Some(Err("map_from_arrays: values must be array".to_string())), };
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "map_from_arrays(keys, values)".to_string()))); }
+                let ks = match &args[0] { Value::Array(a) => a.clone(), _ => return Some(Err(raise(ErrorKind::TypeError, "map_from_arrays: keys must be array".to_string()))), };
+                let vs = match &args[1] { Value::Array(a) => a.clone(), _ => return Some(Err(raise(ErrorKind::TypeError, "map_from_arrays: values must be array".to_string()))), };
                 let mut m = HashMap::new();
                 for (k, v) in ks.iter().zip(vs.iter()) { m.insert(k.as_string(), v.clone()); }
                 Some(Ok(Value::Map(m)))
@@ -7567,19 +8815,19 @@ impl Interpreter {
             // ── HTTPS (TLS) ────────────────────────────────────────────────────
             // https_get(url) -> string
             "https_get" => {
-                if args.is_empty() { return Some(Err("https_get(url)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "https_get(url)".to_string()))); }
                 let url = args[0].as_string();
                 match reqwest::blocking::get(&url) {
                     Ok(resp) => match resp.text() {
                         Ok(t) => Some(Ok(Value::String(t))),
-                        Err(e) => Some(Err(e.to_string())),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                     },
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
             // https_post(url, body, content_type?) -> string
             "https_post" => {
-                if args.len() < 2 { return Some(Err("https_post(url, body)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "https_post(url, body)".to_string()))); }
                 let url = args[0].as_string();
                 let body = args[1].as_string();
                 let ct = args.get(2).map(|v| v.as_string()).unwrap_or_else(|| "application/json".to_string());
@@ -7587,14 +8835,14 @@ impl Interpreter {
                 match client.post(&url).header("Content-Type", ct).body(body).send() {
                     Ok(resp) => match resp.text() {
                         Ok(t) => Some(Ok(Value::String(t))),
-                        Err(e) => Some(Err(e.to_string())),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                     },
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
             // https_request(method, url, headers_map, body) -> {status, body, headers}
             "https_request" => {
-                if args.len() < 2 { return Some(Err("https_request(method, url, headers?, body?)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "https_request(method, url, headers?, body?)".to_string()))); }
                 let method = args[0].as_string().to_uppercase();
                 let url = args[1].as_string();
                 let client = reqwest::blocking::Client::new();
@@ -7605,7 +8853,7 @@ impl Interpreter {
                     "DELETE" => client.delete(&url),
                     "PATCH"  => client.patch(&url),
                     "HEAD"   => client.head(&url),
-                    other    => return Some(Err(format!("Unknown HTTP method: {}", other))),
+                    other    => return Some(Err(raise(ErrorKind::ValueError, format!("Unknown HTTP method: {}", other)))),
                 };
                 if let Some(Value::Map(hdrs)) = args.get(2) {
                     for (k, v) in hdrs { req = req.header(k.as_str(), v.as_string()); }
@@ -7625,14 +8873,14 @@ impl Interpreter {
                         out.insert("headers".to_string(), Value::Map(hdrs));
                         Some(Ok(Value::Map(out)))
                     }
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
 
             // ── WebSocket ──────────────────────────────────────────────────────
             // ws_connect(url) -> handle
             "ws_connect" => {
-                if args.is_empty() { return Some(Err("ws_connect(url)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "ws_connect(url)".to_string()))); }
                 let url = args[0].as_string();
                 match tungstenite::connect(&url) {
                     Ok((ws, _)) => {
@@ -7641,25 +8889,25 @@ impl Interpreter {
                         self.ws_connections.insert(id, ws);
                         Some(Ok(Value::Int(id)))
                     }
-                    Err(e) => Some(Err(format!("ws_connect: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, format!("ws_connect: {}", e)))),
                 }
             }
             // ws_send(handle, message)
             "ws_send" => {
-                if args.len() < 2 { return Some(Err("ws_send(handle, msg)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "ws_send(handle, msg)".to_string()))); }
                 let id = args[0].as_int();
                 let msg = args[1].as_string();
                 match self.ws_connections.get_mut(&id) {
                     Some(ws) => match ws.send(tungstenite::Message::Text(msg)) {
                         Ok(_) => Some(Ok(Value::Null)),
-                        Err(e) => Some(Err(e.to_string())),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                     },
-                    None => Some(Err(format!("ws_send: no connection {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("ws_send: no connection {}", id)))),
                 }
             }
             // ws_recv(handle) -> string
             "ws_recv" => {
-                if args.is_empty() { return Some(Err("ws_recv(handle)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "ws_recv(handle)".to_string()))); }
                 let id = args[0].as_int();
                 match self.ws_connections.get_mut(&id) {
                     Some(ws) => match ws.read() {
@@ -7667,14 +8915,14 @@ impl Interpreter {
                         Ok(tungstenite::Message::Binary(b)) => Some(Ok(Value::Array(b.iter().map(|x| Value::Int(*x as i64)).collect()))),
                         Ok(tungstenite::Message::Close(_)) => Some(Ok(Value::Null)),
                         Ok(_) => Some(Ok(Value::String(String::new()))),
-                        Err(e) => Some(Err(e.to_string())),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                     },
-                    None => Some(Err(format!("ws_recv: no connection {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("ws_recv: no connection {}", id)))),
                 }
             }
             // ws_close(handle)
             "ws_close" => {
-                if args.is_empty() { return Some(Err("ws_close(handle)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "ws_close(handle)".to_string()))); }
                 let id = args[0].as_int();
                 if let Some(mut ws) = self.ws_connections.remove(&id) {
                     let _ = ws.close(None);
@@ -7685,7 +8933,7 @@ impl Interpreter {
             // ── Dynamic Libraries (dlopen/dlsym) ───────────────────────────────
             // dlopen(path) -> handle
             "dlopen" => {
-                if args.is_empty() { return Some(Err("dlopen(path)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "dlopen(path)".to_string()))); }
                 let path = args[0].as_string();
                 match unsafe { libloading::Library::new(&path) } {
                     Ok(lib) => {
@@ -7694,12 +8942,12 @@ impl Interpreter {
                         self.dl_libs.insert(id, lib);
                         Some(Ok(Value::Int(id)))
                     }
-                    Err(e) => Some(Err(format!("dlopen: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::Error, format!("dlopen: {}", e)))),
                 }
             }
             // dlsym(handle, symbol) -> fn_handle
             "dlsym" => {
-                if args.len() < 2 { return Some(Err("dlsym(handle, symbol)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "dlsym(handle, symbol)".to_string()))); }
                 let lib_id = args[0].as_int();
                 let sym = args[1].as_string();
                 match self.dl_libs.get(&lib_id) {
@@ -7708,7 +8956,7 @@ impl Interpreter {
                         let raw_ptr: usize = unsafe {
                             match lib.get::<unsafe extern "C" fn()>(sym_bytes.as_bytes()) {
                                 Ok(sym) => *sym as usize,
-                                Err(e) => return Some(Err(format!("dlsym: {}", e))),
+                                Err(e) => return Some(Err(raise(ErrorKind::Error, format!("dlsym: {}", e)))),
                             }
                         };
                         let id = self.dl_counter;
@@ -7716,12 +8964,12 @@ impl Interpreter {
                         self.dl_syms.insert(id, raw_ptr);
                         Some(Ok(Value::Int(id)))
                     }
-                    None => Some(Err(format!("dlsym: no library {}", lib_id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("dlsym: no library {}", lib_id)))),
                 }
             }
             // dlcall_i(sym_handle, args...) -> int  [call fn returning i64]
             "dlcall_i" => {
-                if args.is_empty() { return Some(Err("dlcall_i(sym_handle, args...)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "dlcall_i(sym_handle, args...)".to_string()))); }
                 let sym_id = args[0].as_int();
                 match self.dl_syms.get(&sym_id) {
                     Some(&ptr) => {
@@ -7733,12 +8981,12 @@ impl Interpreter {
                         };
                         Some(Ok(Value::Int(result)))
                     }
-                    None => Some(Err(format!("dlcall_i: no symbol {}", sym_id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("dlcall_i: no symbol {}", sym_id)))),
                 }
             }
             // dlclose(handle)
             "dlclose" => {
-                if args.is_empty() { return Some(Err("dlclose(handle)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "dlclose(handle)".to_string()))); }
                 let id = args[0].as_int();
                 self.dl_libs.remove(&id);
                 Some(Ok(Value::Null))
@@ -7749,7 +8997,7 @@ impl Interpreter {
             "raw_socket" => {
                 let proto = args.first().map(|v| v.as_int()).unwrap_or(255) as libc::c_int;
                 let fd = unsafe { libc::socket(libc::AF_INET, libc::SOCK_RAW, proto) };
-                if fd < 0 { return Some(Err(format!("raw_socket: errno {}", unsafe { *libc::__errno_location() }))); }
+                if fd < 0 { return Some(Err(raise(ErrorKind::IOError, format!("raw_socket: errno {}", unsafe { *libc::__errno_location() })))); }
                 let id = self.raw_sock_counter;
                 self.raw_sock_counter += 1;
                 self.raw_sockets.insert(id, fd);
@@ -7757,19 +9005,19 @@ impl Interpreter {
             }
             // raw_send(fd_handle, dest_ip, payload_bytes)
             "raw_send" => {
-                if args.len() < 3 { return Some(Err("raw_send(fd, ip, bytes)".to_string())); }
+                if args.len() < 3 { return Some(Err(raise(ErrorKind::TypeError, "raw_send(fd, ip, bytes)".to_string()))); }
                 let fd = match self.raw_sockets.get(&args[0].as_int()) {
                     Some(&f) => f,
-                    None => return Some(Err("raw_send: bad fd".to_string())),
+                    None => return Some(Err(raise(ErrorKind::Error, "raw_send: bad fd".to_string()))),
                 };
                 let ip_str = args[1].as_string();
                 let payload: Vec<u8> = match &args[2] {
                     Value::Array(a) => a.iter().map(|v| v.as_int() as u8).collect(),
                     Value::String(s) => s.bytes().collect(),
-                    _ => return Some(Err("raw_send: payload must be array or string".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "raw_send: payload must be array or string".to_string()))),
                 };
                 let ip_bytes: Vec<u8> = ip_str.split('.').map(|p| p.parse::<u8>().unwrap_or(0)).collect();
-                if ip_bytes.len() < 4 { return Some(Err("raw_send: invalid IP".to_string())); }
+                if ip_bytes.len() < 4 { return Some(Err(raise(ErrorKind::ValueError, "raw_send: invalid IP".to_string()))); }
                 let addr = libc::sockaddr_in {
                     sin_family: libc::AF_INET as u16,
                     sin_port: 0,
@@ -7785,21 +9033,21 @@ impl Interpreter {
             }
             // raw_recv(fd_handle, max_bytes?) -> array of bytes
             "raw_recv" => {
-                if args.is_empty() { return Some(Err("raw_recv(fd)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "raw_recv(fd)".to_string()))); }
                 let fd = match self.raw_sockets.get(&args[0].as_int()) {
                     Some(&f) => f,
-                    None => return Some(Err("raw_recv: bad fd".to_string())),
+                    None => return Some(Err(raise(ErrorKind::Error, "raw_recv: bad fd".to_string()))),
                 };
                 let max = args.get(1).map(|v| v.as_int() as usize).unwrap_or(65535);
                 let mut buf = vec![0u8; max];
                 let n = unsafe { libc::recv(fd, buf.as_mut_ptr() as *mut _, max, 0) };
-                if n < 0 { return Some(Err(format!("raw_recv: errno {}", unsafe { *libc::__errno_location() }))); }
+                if n < 0 { return Some(Err(raise(ErrorKind::IOError, format!("raw_recv: errno {}", unsafe { *libc::__errno_location() })))); }
                 let out: Vec<Value> = buf[..n as usize].iter().map(|b| Value::Int(*b as i64)).collect();
                 Some(Ok(Value::Array(out)))
             }
             // raw_close(fd_handle)
             "raw_close" => {
-                if args.is_empty() { return Some(Err("raw_close(fd)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "raw_close(fd)".to_string()))); }
                 if let Some(fd) = self.raw_sockets.remove(&args[0].as_int()) {
                     unsafe { libc::close(fd); }
                 }
@@ -7809,7 +9057,7 @@ impl Interpreter {
             // ── Signal handling ────────────────────────────────────────────────
             // signal_send(pid, signum) -> 0 on success
             "signal_send" | "kill" => {
-                if args.len() < 2 { return Some(Err("signal_send(pid, signum)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "signal_send(pid, signum)".to_string()))); }
                 let pid = args[0].as_int() as libc::pid_t;
                 let sig = args[1].as_int() as libc::c_int;
                 let r = unsafe { libc::kill(pid, sig) };
@@ -7842,7 +9090,7 @@ impl Interpreter {
             // ── GUI window (minifb) ────────────────────────────────────────────
             // gui_window(title, width, height) -> handle
             "gui_window" => {
-                if args.len() < 3 { return Some(Err("gui_window(title, width, height)".to_string())); }
+                if args.len() < 3 { return Some(Err(raise(ErrorKind::TypeError, "gui_window(title, width, height)".to_string()))); }
                 let title  = args[0].as_string();
                 let width  = args[1].as_int() as usize;
                 let height = args[2].as_int() as usize;
@@ -7854,12 +9102,12 @@ impl Interpreter {
                         self.gui_windows.insert(id, (win, buf));
                         Some(Ok(Value::Int(id)))
                     }
-                    Err(e) => Some(Err(format!("gui_window: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::Error, format!("gui_window: {}", e)))),
                 }
             }
             // gui_set_pixel(handle, x, y, rgb) — set pixel (rgb = 0xRRGGBB)
             "gui_set_pixel" => {
-                if args.len() < 4 { return Some(Err("gui_set_pixel(h, x, y, rgb)".to_string())); }
+                if args.len() < 4 { return Some(Err(raise(ErrorKind::TypeError, "gui_set_pixel(h, x, y, rgb)".to_string()))); }
                 let id = args[0].as_int();
                 let x = args[1].as_int() as usize;
                 let y = args[2].as_int() as usize;
@@ -7870,56 +9118,56 @@ impl Interpreter {
                         if x < w && y < h { buf[y * w + x] = rgb; }
                         Some(Ok(Value::Null))
                     }
-                    None => Some(Err(format!("gui_set_pixel: no window {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("gui_set_pixel: no window {}", id)))),
                 }
             }
             // gui_fill(handle, rgb) — fill whole buffer
             "gui_fill" => {
-                if args.len() < 2 { return Some(Err("gui_fill(h, rgb)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "gui_fill(h, rgb)".to_string()))); }
                 let id = args[0].as_int();
                 let rgb = args[1].as_int() as u32;
                 match self.gui_windows.get_mut(&id) {
                     Some((_, buf)) => { buf.iter_mut().for_each(|p| *p = rgb); Some(Ok(Value::Null)) }
-                    None => Some(Err(format!("gui_fill: no window {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("gui_fill: no window {}", id)))),
                 }
             }
             // gui_present(handle) — flush buffer to window
             "gui_present" => {
-                if args.is_empty() { return Some(Err("gui_present(handle)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "gui_present(handle)".to_string()))); }
                 let id = args[0].as_int();
                 match self.gui_windows.get_mut(&id) {
                     Some((win, buf)) => {
                         let (w, h) = win.get_size();
                         match win.update_with_buffer(buf, w, h) {
                             Ok(_) => Some(Ok(Value::Null)),
-                            Err(e) => Some(Err(e.to_string())),
+                            Err(e) => Some(Err(raise(ErrorKind::Error, e.to_string()))),
                         }
                     }
-                    None => Some(Err(format!("gui_present: no window {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("gui_present: no window {}", id)))),
                 }
             }
             // gui_is_open(handle) -> bool
             "gui_is_open" => {
-                if args.is_empty() { return Some(Err("gui_is_open(handle)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "gui_is_open(handle)".to_string()))); }
                 let id = args[0].as_int();
                 let open = self.gui_windows.get(&id).map(|(w, _)| w.is_open()).unwrap_or(false);
                 Some(Ok(Value::Bool(open)))
             }
             // gui_get_keys(handle) -> array of key names
             "gui_get_keys" => {
-                if args.is_empty() { return Some(Err("gui_get_keys(handle)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "gui_get_keys(handle)".to_string()))); }
                 let id = args[0].as_int();
                 match self.gui_windows.get(&id) {
                     Some((win, _)) => {
                         let keys: Vec<Value> = win.get_keys().iter().map(|k| Value::String(format!("{:?}", k))).collect();
                         Some(Ok(Value::Array(keys)))
                     }
-                    None => Some(Err(format!("gui_get_keys: no window {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("gui_get_keys: no window {}", id)))),
                 }
             }
             // gui_get_mouse(handle) -> {x, y, left, right, middle}
             "gui_get_mouse" => {
-                if args.is_empty() { return Some(Err("gui_get_mouse(handle)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "gui_get_mouse(handle)".to_string()))); }
                 let id = args[0].as_int();
                 match self.gui_windows.get(&id) {
                     Some((win, _)) => {
@@ -7935,18 +9183,18 @@ impl Interpreter {
                         m.insert("middle".to_string(), Value::Bool(middle));
                         Some(Ok(Value::Map(m)))
                     }
-                    None => Some(Err(format!("gui_get_mouse: no window {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("gui_get_mouse: no window {}", id)))),
                 }
             }
             // gui_close(handle)
             "gui_close" => {
-                if args.is_empty() { return Some(Err("gui_close(handle)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "gui_close(handle)".to_string()))); }
                 self.gui_windows.remove(&args[0].as_int());
                 Some(Ok(Value::Null))
             }
             // gui_rgb(r, g, b) -> packed 0xRRGGBB color
             "gui_rgb" => {
-                if args.len() < 3 { return Some(Err("gui_rgb(r, g, b)".to_string())); }
+                if args.len() < 3 { return Some(Err(raise(ErrorKind::TypeError, "gui_rgb(r, g, b)".to_string()))); }
                 let r = (args[0].as_int() as u32) & 0xFF;
                 let g = (args[1].as_int() as u32) & 0xFF;
                 let b = (args[2].as_int() as u32) & 0xFF;
@@ -7954,7 +9202,7 @@ impl Interpreter {
             }
             // gui_size(handle) -> {w, h}
             "gui_size" => {
-                if args.is_empty() { return Some(Err("gui_size(handle)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "gui_size(handle)".to_string()))); }
                 let id = args[0].as_int();
                 match self.gui_windows.get(&id) {
                     Some((win, _)) => {
@@ -7964,12 +9212,12 @@ impl Interpreter {
                         m.insert("h".to_string(), Value::Int(h as i64));
                         Some(Ok(Value::Map(m)))
                     }
-                    None => Some(Err(format!("gui_size: no window {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("gui_size: no window {}", id)))),
                 }
             }
             // gui_rect(handle, x, y, w, h, rgb) — filled rectangle
             "gui_rect" => {
-                if args.len() < 6 { return Some(Err("gui_rect(h, x, y, w, h, rgb)".to_string())); }
+                if args.len() < 6 { return Some(Err(raise(ErrorKind::TypeError, "gui_rect(h, x, y, w, h, rgb)".to_string()))); }
                 let id  = args[0].as_int();
                 let rx  = args[1].as_int() as i64;
                 let ry  = args[2].as_int() as i64;
@@ -7989,12 +9237,12 @@ impl Interpreter {
                         }
                         Some(Ok(Value::Null))
                     }
-                    None => Some(Err(format!("gui_rect: no window {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("gui_rect: no window {}", id)))),
                 }
             }
             // gui_rect_outline(handle, x, y, w, h, rgb) — rectangle outline only
             "gui_rect_outline" => {
-                if args.len() < 6 { return Some(Err("gui_rect_outline(h, x, y, w, h, rgb)".to_string())); }
+                if args.len() < 6 { return Some(Err(raise(ErrorKind::TypeError, "gui_rect_outline(h, x, y, w, h, rgb)".to_string()))); }
                 let id  = args[0].as_int();
                 let rx  = args[1].as_int() as i64;
                 let ry  = args[2].as_int() as i64;
@@ -8014,12 +9262,12 @@ impl Interpreter {
                         for py in ry..(ry + rh) { set(buf, rx, py); set(buf, rx + rw - 1, py); }
                         Some(Ok(Value::Null))
                     }
-                    None => Some(Err(format!("gui_rect_outline: no window {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("gui_rect_outline: no window {}", id)))),
                 }
             }
             // gui_line(handle, x0, y0, x1, y1, rgb) — Bresenham anti-clipped line
             "gui_line" => {
-                if args.len() < 6 { return Some(Err("gui_line(h, x0, y0, x1, y1, rgb)".to_string())); }
+                if args.len() < 6 { return Some(Err(raise(ErrorKind::TypeError, "gui_line(h, x0, y0, x1, y1, rgb)".to_string()))); }
                 let id  = args[0].as_int();
                 let mut x0 = args[1].as_int() as i64;
                 let mut y0 = args[2].as_int() as i64;
@@ -8046,12 +9294,12 @@ impl Interpreter {
                         }
                         Some(Ok(Value::Null))
                     }
-                    None => Some(Err(format!("gui_line: no window {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("gui_line: no window {}", id)))),
                 }
             }
             // gui_circle(handle, cx, cy, r, rgb) — filled circle
             "gui_circle" => {
-                if args.len() < 5 { return Some(Err("gui_circle(h, cx, cy, r, rgb)".to_string())); }
+                if args.len() < 5 { return Some(Err(raise(ErrorKind::TypeError, "gui_circle(h, cx, cy, r, rgb)".to_string()))); }
                 let id  = args[0].as_int();
                 let cx  = args[1].as_int() as i64;
                 let cy  = args[2].as_int() as i64;
@@ -8072,12 +9320,12 @@ impl Interpreter {
                         }
                         Some(Ok(Value::Null))
                     }
-                    None => Some(Err(format!("gui_circle: no window {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("gui_circle: no window {}", id)))),
                 }
             }
             // gui_circle_outline(handle, cx, cy, r, rgb) — circle outline (midpoint)
             "gui_circle_outline" => {
-                if args.len() < 5 { return Some(Err("gui_circle_outline(h, cx, cy, r, rgb)".to_string())); }
+                if args.len() < 5 { return Some(Err(raise(ErrorKind::TypeError, "gui_circle_outline(h, cx, cy, r, rgb)".to_string()))); }
                 let id  = args[0].as_int();
                 let cx  = args[1].as_int() as i64;
                 let cy  = args[2].as_int() as i64;
@@ -8104,17 +9352,17 @@ impl Interpreter {
                         }
                         Some(Ok(Value::Null))
                     }
-                    None => Some(Err(format!("gui_circle_outline: no window {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("gui_circle_outline: no window {}", id)))),
                 }
             }
             // gui_set_title(handle, title) — update window title at runtime
             "gui_set_title" => {
-                if args.len() < 2 { return Some(Err("gui_set_title(h, title)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "gui_set_title(h, title)".to_string()))); }
                 let id    = args[0].as_int();
                 let title = args[1].as_string();
                 match self.gui_windows.get_mut(&id) {
                     Some((win, _)) => { win.set_title(&title); Some(Ok(Value::Null)) }
-                    None => Some(Err(format!("gui_set_title: no window {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("gui_set_title: no window {}", id)))),
                 }
             }
 
@@ -8126,7 +9374,7 @@ impl Interpreter {
             }
             // execve(path, args_array, env_map?) -> never returns on success
             "execve" => {
-                if args.is_empty() { return Some(Err("execve(path, args, env?)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "execve(path, args, env?)".to_string()))); }
                 let path = args[0].as_string();
                 let a_list: Vec<String> = match args.get(1) {
                     Some(Value::Array(a)) => a.iter().map(|v| v.as_string()).collect(),
@@ -8144,7 +9392,7 @@ impl Interpreter {
                 let mut p_env:  Vec<*const libc::c_char> = c_env.iter().map(|s| s.as_ptr()).collect();
                 p_args.push(std::ptr::null()); p_env.push(std::ptr::null());
                 unsafe { libc::execve(c_path.as_ptr(), p_args.as_ptr(), p_env.as_ptr()) };
-                Some(Err(format!("execve failed: errno {}", unsafe { *libc::__errno_location() })))
+                Some(Err(raise(ErrorKind::IOError, format!("execve failed: errno {}", unsafe { *libc::__errno_location() }))))
             }
             // waitpid(pid, options?) -> {pid, status, exited, code}
             "waitpid" => {
@@ -8174,7 +9422,7 @@ impl Interpreter {
                 Some(Ok(Value::Int(unsafe { libc::setgid(gid) } as i64)))
             }
             "chroot" => {
-                if args.is_empty() { return Some(Err("chroot(path)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "chroot(path)".to_string()))); }
                 use std::ffi::CString;
                 let p = CString::new(args[0].as_string().as_bytes()).unwrap();
                 Some(Ok(Value::Int(unsafe { libc::chroot(p.as_ptr()) } as i64)))
@@ -8187,7 +9435,7 @@ impl Interpreter {
             // ── ioctl ──────────────────────────────────────────────────────────
             // ioctl(fd, request, arg?) -> int
             "ioctl" => {
-                if args.len() < 2 { return Some(Err("ioctl(fd, request, arg?)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "ioctl(fd, request, arg?)".to_string()))); }
                 let fd  = args[0].as_int() as libc::c_int;
                 let req = args[1].as_int() as libc::c_ulong;
                 let arg = args.get(2).map(|v| v.as_int()).unwrap_or(0) as libc::c_long;
@@ -8198,7 +9446,7 @@ impl Interpreter {
             // ── mprotect ──────────────────────────────────────────────────────
             // mprotect(addr, size, prot) -> 0 on success
             "mprotect" => {
-                if args.len() < 3 { return Some(Err("mprotect(addr, size, prot)".to_string())); }
+                if args.len() < 3 { return Some(Err(raise(ErrorKind::TypeError, "mprotect(addr, size, prot)".to_string()))); }
                 let addr = args[0].as_int() as *mut libc::c_void;
                 let size = args[1].as_int() as libc::size_t;
                 let prot = args[2].as_int() as libc::c_int;
@@ -8208,16 +9456,16 @@ impl Interpreter {
             // ── Shared memory (SysV) ───────────────────────────────────────────
             // shm_create(key_or_size, size?) -> {id, addr}
             "shm_create" => {
-                if args.is_empty() { return Some(Err("shm_create(size) or shm_create(key, size)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "shm_create(size) or shm_create(key, size)".to_string()))); }
                 let (key, size) = if args.len() >= 2 {
                     (args[0].as_int() as libc::key_t, args[1].as_int() as libc::size_t)
                 } else {
                     (libc::IPC_PRIVATE, args[0].as_int() as libc::size_t)
                 };
                 let id = unsafe { libc::shmget(key, size, 0o600 | libc::IPC_CREAT) };
-                if id < 0 { return Some(Err(format!("shm_create failed: errno {}", unsafe { *libc::__errno_location() }))); }
+                if id < 0 { return Some(Err(raise(ErrorKind::IOError, format!("shm_create failed: errno {}", unsafe { *libc::__errno_location() })))); }
                 let addr = unsafe { libc::shmat(id, std::ptr::null(), 0) } as usize;
-                if addr == usize::MAX { return Some(Err(format!("shmat failed: errno {}", unsafe { *libc::__errno_location() }))); }
+                if addr == usize::MAX { return Some(Err(raise(ErrorKind::IOError, format!("shmat failed: errno {}", unsafe { *libc::__errno_location() })))); }
                 let mut m = HashMap::new();
                 m.insert("id".to_string(), Value::Int(id as i64));
                 m.insert("addr".to_string(), Value::Int(addr as i64));
@@ -8225,29 +9473,29 @@ impl Interpreter {
             }
             // shm_write(addr, bytes_or_string)
             "shm_write" => {
-                if args.len() < 2 { return Some(Err("shm_write(addr, bytes)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "shm_write(addr, bytes)".to_string()))); }
                 let addr_val = match &args[0] {
                     Value::Map(m) => m.get("addr").map(|v| v.as_int()).unwrap_or(0),
                     other => other.as_int(),
                 };
-                if addr_val == 0 { return Some(Err("shm_write: null address".to_string())); }
+                if addr_val == 0 { return Some(Err(raise(ErrorKind::Error, "shm_write: null address".to_string()))); }
                 let addr = addr_val as *mut u8;
                 let bytes: Vec<u8> = match &args[1] {
                     Value::Array(a) => a.iter().map(|v| v.as_int() as u8).collect(),
                     Value::String(s) => s.bytes().collect(),
-                    _ => return Some(Err("shm_write: expected array or string".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "shm_write: expected array or string".to_string()))),
                 };
                 unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), addr, bytes.len()); }
                 Some(Ok(Value::Int(bytes.len() as i64)))
             }
             // shm_read(addr, size) -> string
             "shm_read" => {
-                if args.len() < 2 { return Some(Err("shm_read(addr, size)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "shm_read(addr, size)".to_string()))); }
                 let addr_val = match &args[0] {
                     Value::Map(m) => m.get("addr").map(|v| v.as_int()).unwrap_or(0),
                     other => other.as_int(),
                 };
-                if addr_val == 0 { return Some(Err("shm_read: null address".to_string())); }
+                if addr_val == 0 { return Some(Err(raise(ErrorKind::Error, "shm_read: null address".to_string()))); }
                 let addr = addr_val as *const u8;
                 let size = args[1].as_int() as usize;
                 let bytes = unsafe { std::slice::from_raw_parts(addr, size) };
@@ -8255,7 +9503,7 @@ impl Interpreter {
             }
             // shm_remove(id_or_map)
             "shm_remove" => {
-                if args.is_empty() { return Some(Err("shm_remove(id)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "shm_remove(id)".to_string()))); }
                 let id = match &args[0] {
                     Value::Map(m) => m.get("id").map(|v| v.as_int()).unwrap_or(0),
                     other => other.as_int(),
@@ -8268,11 +9516,11 @@ impl Interpreter {
             // tar_create(output_path, files_array) -> bool
             "tar_create" => {
                 use tar::Builder;
-                if args.len() < 2 { return Some(Err("tar_create(output, [files])".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "tar_create(output, [files])".to_string()))); }
                 let out_path = args[0].as_string();
                 let files: Vec<String> = match &args[1] {
                     Value::Array(a) => a.iter().map(|v| v.as_string()).collect(),
-                    _ => return Some(Err("tar_create: second arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "tar_create: second arg must be array".to_string()))),
                 };
                 match std::fs::File::create(&out_path) {
                     Ok(f) => {
@@ -8284,24 +9532,24 @@ impl Interpreter {
                             match std::fs::File::open(p) {
                                 Ok(mut fh) => {
                                     if let Err(e) = ar.append_file(&name, &mut fh) {
-                                        return Some(Err(e.to_string()));
+                                        return Some(Err(raise(ErrorKind::IOError, e.to_string())));
                                     }
                                 }
-                                Err(e) => return Some(Err(e.to_string())),
+                                Err(e) => return Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                             }
                         }
                         match ar.finish() {
                             Ok(_) => Some(Ok(Value::Bool(true))),
-                            Err(e) => Some(Err(e.to_string())),
+                            Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                         }
                     }
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
             // tar_list(path) -> array of filenames
             "tar_list" => {
                 use tar::Archive;
-                if args.is_empty() { return Some(Err("tar_list(path)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "tar_list(path)".to_string()))); }
                 match std::fs::File::open(args[0].as_string()) {
                     Ok(f) => {
                         let mut ar = Archive::new(f);
@@ -8314,47 +9562,47 @@ impl Interpreter {
                                     }
                                 }
                             }
-                            Err(e) => return Some(Err(e.to_string())),
+                            Err(e) => return Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                         }
                         Some(Ok(Value::Array(names)))
                     }
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
             // tar_extract(path, dest_dir) -> bool
             "tar_extract" => {
                 use tar::Archive;
-                if args.len() < 2 { return Some(Err("tar_extract(path, dest)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "tar_extract(path, dest)".to_string()))); }
                 match std::fs::File::open(args[0].as_string()) {
                     Ok(f) => {
                         let mut ar = Archive::new(f);
                         match ar.unpack(args[1].as_string()) {
                             Ok(_) => Some(Ok(Value::Bool(true))),
-                            Err(e) => Some(Err(e.to_string())),
+                            Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                         }
                     }
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
 
             // ── ptrace ─────────────────────────────────────────────────────────
             // ptrace_attach(pid) -> 0 on success
             "ptrace_attach" => {
-                if args.is_empty() { return Some(Err("ptrace_attach(pid)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "ptrace_attach(pid)".to_string()))); }
                 let pid = args[0].as_int() as libc::pid_t;
                 let r = unsafe { libc::ptrace(libc::PTRACE_ATTACH, pid, 0, 0) };
                 Some(Ok(Value::Int(r as i64)))
             }
             // ptrace_detach(pid)
             "ptrace_detach" => {
-                if args.is_empty() { return Some(Err("ptrace_detach(pid)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "ptrace_detach(pid)".to_string()))); }
                 let pid = args[0].as_int() as libc::pid_t;
                 let r = unsafe { libc::ptrace(libc::PTRACE_DETACH, pid, 0, 0) };
                 Some(Ok(Value::Int(r as i64)))
             }
             // ptrace_peekdata(pid, addr) -> i64
             "ptrace_peekdata" => {
-                if args.len() < 2 { return Some(Err("ptrace_peekdata(pid, addr)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "ptrace_peekdata(pid, addr)".to_string()))); }
                 let pid  = args[0].as_int() as libc::pid_t;
                 let addr = args[1].as_int() as *mut libc::c_void;
                 let val = unsafe { libc::ptrace(libc::PTRACE_PEEKDATA, pid, addr, 0) };
@@ -8362,7 +9610,7 @@ impl Interpreter {
             }
             // ptrace_pokedata(pid, addr, value)
             "ptrace_pokedata" => {
-                if args.len() < 3 { return Some(Err("ptrace_pokedata(pid, addr, val)".to_string())); }
+                if args.len() < 3 { return Some(Err(raise(ErrorKind::TypeError, "ptrace_pokedata(pid, addr, val)".to_string()))); }
                 let pid  = args[0].as_int() as libc::pid_t;
                 let addr = args[1].as_int() as *mut libc::c_void;
                 let val  = args[2].as_int() as *mut libc::c_void;
@@ -8371,7 +9619,7 @@ impl Interpreter {
             }
             // ptrace_cont(pid, sig?)
             "ptrace_cont" => {
-                if args.is_empty() { return Some(Err("ptrace_cont(pid, sig?)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "ptrace_cont(pid, sig?)".to_string()))); }
                 let pid = args[0].as_int() as libc::pid_t;
                 let sig = args.get(1).map(|v| v.as_int()).unwrap_or(0) as *mut libc::c_void;
                 let r = unsafe { libc::ptrace(libc::PTRACE_CONT, pid, 0, sig) };
@@ -8381,28 +9629,28 @@ impl Interpreter {
             // ── Regex (extended) ───────────────────────────────────────────────
             // regex_replace(pattern, replacement, text) -> string
             "old_regex_replace" => {
-                if args.len() < 3 { return Some(Err("regex_replace(pattern, replacement, text)".to_string())); }
+                if args.len() < 3 { return Some(Err(raise(ErrorKind::TypeError, "regex_replace(pattern, replacement, text)".to_string()))); }
                 let pat  = args[0].as_string();
                 let rep  = args[1].as_string();
                 let text = args[2].as_string();
                 match regex::Regex::new(&pat) {
                     Ok(re) => Some(Ok(Value::String(re.replace_all(&text, rep.as_str()).to_string()))),
-                    Err(e) => Some(Err(format!("regex_replace: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("regex_replace: {}", e)))),
                 }
             }
             // regex_find_all(pattern, text) -> array of strings
             "regex_find_all" => {
-                if args.len() < 2 { return Some(Err("regex_find_all(pattern, text)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "regex_find_all(pattern, text)".to_string()))); }
                 let pat  = args[0].as_string();
                 let text = args[1].as_string();
                 match regex::Regex::new(&pat) {
                     Ok(re) => Some(Ok(Value::Array(re.find_iter(&text).map(|m| Value::String(m.as_str().to_string())).collect()))),
-                    Err(e) => Some(Err(format!("regex_find_all: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("regex_find_all: {}", e)))),
                 }
             }
             // regex_captures(pattern, text) -> array of [full, group1, group2, ...]  or null
             "old_regex_captures" => {
-                if args.len() < 2 { return Some(Err("regex_captures(pattern, text)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "regex_captures(pattern, text)".to_string()))); }
                 let pat  = args[0].as_string();
                 let text = args[1].as_string();
                 match regex::Regex::new(&pat) {
@@ -8412,12 +9660,12 @@ impl Interpreter {
                         ))),
                         None => Some(Ok(Value::Null)),
                     },
-                    Err(e) => Some(Err(format!("regex_captures: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("regex_captures: {}", e)))),
                 }
             }
             // regex_captures_all(pattern, text) -> array of arrays
             "regex_captures_all" => {
-                if args.len() < 2 { return Some(Err("regex_captures_all(pattern, text)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "regex_captures_all(pattern, text)".to_string()))); }
                 let pat  = args[0].as_string();
                 let text = args[1].as_string();
                 match regex::Regex::new(&pat) {
@@ -8426,24 +9674,24 @@ impl Interpreter {
                             Value::Array(caps.iter().map(|m| m.map(|s| Value::String(s.as_str().to_string())).unwrap_or(Value::Null)).collect())
                         ).collect()
                     ))),
-                    Err(e) => Some(Err(format!("regex_captures_all: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("regex_captures_all: {}", e)))),
                 }
             }
             // regex_split(pattern, text) -> array of strings
             "old_regex_split" => {
-                if args.len() < 2 { return Some(Err("regex_split(pattern, text)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "regex_split(pattern, text)".to_string()))); }
                 let pat  = args[0].as_string();
                 let text = args[1].as_string();
                 match regex::Regex::new(&pat) {
                     Ok(re) => Some(Ok(Value::Array(re.split(&text).map(|s| Value::String(s.to_string())).collect()))),
-                    Err(e) => Some(Err(format!("regex_split: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("regex_split: {}", e)))),
                 }
             }
 
             // ── Named pipes (mkfifo) ───────────────────────────────────────────
             // mkfifo(path, mode?) -> 0 on success
             "mkfifo" => {
-                if args.is_empty() { return Some(Err("mkfifo(path, mode?)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "mkfifo(path, mode?)".to_string()))); }
                 use std::ffi::CString;
                 let path = CString::new(args[0].as_string().as_bytes()).unwrap();
                 let mode = args.get(1).map(|v| v.as_int()).unwrap_or(0o644) as libc::mode_t;
@@ -8453,7 +9701,7 @@ impl Interpreter {
             "pipe_create" => {
                 let mut fds = [0i32; 2];
                 let r = unsafe { libc::pipe(fds.as_mut_ptr()) };
-                if r < 0 { return Some(Err(format!("pipe_create: errno {}", unsafe { *libc::__errno_location() }))); }
+                if r < 0 { return Some(Err(raise(ErrorKind::IOError, format!("pipe_create: errno {}", unsafe { *libc::__errno_location() })))); }
                 let mut m = HashMap::new();
                 m.insert("read_fd".to_string(), Value::Int(fds[0] as i64));
                 m.insert("write_fd".to_string(), Value::Int(fds[1] as i64));
@@ -8461,17 +9709,17 @@ impl Interpreter {
             }
             // fd_read(fd, size?) -> string
             "fd_read" => {
-                if args.is_empty() { return Some(Err("fd_read(fd, size?)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "fd_read(fd, size?)".to_string()))); }
                 let fd = args[0].as_int() as libc::c_int;
                 let size = args.get(1).map(|v| v.as_int() as usize).unwrap_or(4096);
                 let mut buf = vec![0u8; size];
                 let n = unsafe { libc::read(fd, buf.as_mut_ptr() as *mut _, size) };
-                if n < 0 { return Some(Err(format!("fd_read: errno {}", unsafe { *libc::__errno_location() }))); }
+                if n < 0 { return Some(Err(raise(ErrorKind::IOError, format!("fd_read: errno {}", unsafe { *libc::__errno_location() })))); }
                 Some(Ok(Value::String(String::from_utf8_lossy(&buf[..n as usize]).to_string())))
             }
             // fd_write(fd, data) -> bytes_written
             "fd_write" => {
-                if args.len() < 2 { return Some(Err("fd_write(fd, data)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "fd_write(fd, data)".to_string()))); }
                 let fd = args[0].as_int() as libc::c_int;
                 let data = args[1].as_string();
                 let n = unsafe { libc::write(fd, data.as_ptr() as *const _, data.len()) };
@@ -8479,7 +9727,7 @@ impl Interpreter {
             }
             // fd_close(fd)
             "fd_close" => {
-                if args.is_empty() { return Some(Err("fd_close(fd)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "fd_close(fd)".to_string()))); }
                 unsafe { libc::close(args[0].as_int() as libc::c_int); }
                 Some(Ok(Value::Null))
             }
@@ -8492,13 +9740,13 @@ impl Interpreter {
             }
             // env_set(key, value)
             "env_set" | "setenv" => {
-                if args.len() < 2 { return Some(Err("env_set(key, val)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "env_set(key, val)".to_string()))); }
                 std::env::set_var(args[0].as_string(), args[1].as_string());
                 Some(Ok(Value::Null))
             }
             // env_del(key)
             "env_del" | "unsetenv" => {
-                if args.is_empty() { return Some(Err("env_del(key)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "env_del(key)".to_string()))); }
                 std::env::remove_var(args[0].as_string());
                 Some(Ok(Value::Null))
             }
@@ -8506,7 +9754,7 @@ impl Interpreter {
             // ── sendfile (fast file copy to socket/fd) ─────────────────────────
             // sendfile(out_fd, in_fd, offset?, count) -> bytes_sent
             "sendfile" => {
-                if args.len() < 3 { return Some(Err("sendfile(out_fd, in_fd, count)".to_string())); }
+                if args.len() < 3 { return Some(Err(raise(ErrorKind::TypeError, "sendfile(out_fd, in_fd, count)".to_string()))); }
                 let out_fd = args[0].as_int() as libc::c_int;
                 let in_fd  = args[1].as_int() as libc::c_int;
                 let count  = args[2].as_int() as libc::size_t;
@@ -8518,12 +9766,12 @@ impl Interpreter {
             // epoll_create() -> epoll_fd
             "epoll_create" => {
                 let fd = unsafe { libc::epoll_create1(0) };
-                if fd < 0 { return Some(Err(format!("epoll_create: errno {}", unsafe { *libc::__errno_location() }))); }
+                if fd < 0 { return Some(Err(raise(ErrorKind::IOError, format!("epoll_create: errno {}", unsafe { *libc::__errno_location() })))); }
                 Some(Ok(Value::Int(fd as i64)))
             }
             // epoll_add(epoll_fd, watch_fd, events?) — events: 1=EPOLLIN 4=EPOLLOUT
             "epoll_add" => {
-                if args.len() < 2 { return Some(Err("epoll_add(epoll_fd, fd, events?)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "epoll_add(epoll_fd, fd, events?)".to_string()))); }
                 let epfd = args[0].as_int() as libc::c_int;
                 let fd   = args[1].as_int() as libc::c_int;
                 let events = args.get(2).map(|v| v.as_int() as u32).unwrap_or(libc::EPOLLIN as u32 | libc::EPOLLOUT as u32);
@@ -8533,13 +9781,13 @@ impl Interpreter {
             }
             // epoll_wait(epoll_fd, max_events?, timeout_ms?) -> array of ready fds
             "epoll_wait" => {
-                if args.is_empty() { return Some(Err("epoll_wait(epoll_fd)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "epoll_wait(epoll_fd)".to_string()))); }
                 let epfd   = args[0].as_int() as libc::c_int;
                 let maxevs = args.get(1).map(|v| v.as_int() as usize).unwrap_or(64);
                 let timeout = args.get(2).map(|v| v.as_int() as libc::c_int).unwrap_or(-1);
                 let mut events = vec![libc::epoll_event { events: 0, u64: 0 }; maxevs];
                 let n = unsafe { libc::epoll_wait(epfd, events.as_mut_ptr(), maxevs as libc::c_int, timeout) };
-                if n < 0 { return Some(Err(format!("epoll_wait: errno {}", unsafe { *libc::__errno_location() }))); }
+                if n < 0 { return Some(Err(raise(ErrorKind::IOError, format!("epoll_wait: errno {}", unsafe { *libc::__errno_location() })))); }
                 let ready: Vec<Value> = events[..n as usize].iter().map(|e| Value::Int(e.u64 as i64)).collect();
                 Some(Ok(Value::Array(ready)))
             }
@@ -8547,7 +9795,7 @@ impl Interpreter {
             // ── fd-level control ───────────────────────────────────────────────
             // fcntl(fd, cmd, arg?) -> int
             "fcntl" => {
-                if args.len() < 2 { return Some(Err("fcntl(fd, cmd, arg?)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "fcntl(fd, cmd, arg?)".to_string()))); }
                 let fd  = args[0].as_int() as libc::c_int;
                 let cmd = args[1].as_int() as libc::c_int;
                 let arg = args.get(2).map(|v| v.as_int()).unwrap_or(0) as libc::c_int;
@@ -8561,17 +9809,17 @@ impl Interpreter {
             "F_DUPFD"    => Some(Ok(Value::Int(libc::F_DUPFD as i64))),
             // dup(fd) -> new_fd
             "dup" => {
-                if args.is_empty() { return Some(Err("dup(fd)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "dup(fd)".to_string()))); }
                 Some(Ok(Value::Int(unsafe { libc::dup(args[0].as_int() as libc::c_int) } as i64)))
             }
             // dup2(old_fd, new_fd) -> new_fd
             "dup2" => {
-                if args.len() < 2 { return Some(Err("dup2(old_fd, new_fd)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "dup2(old_fd, new_fd)".to_string()))); }
                 Some(Ok(Value::Int(unsafe { libc::dup2(args[0].as_int() as libc::c_int, args[1].as_int() as libc::c_int) } as i64)))
             }
             // lseek(fd, offset, whence) -> new_pos   whence: 0=SET 1=CUR 2=END
             "lseek" => {
-                if args.len() < 2 { return Some(Err("lseek(fd, offset, whence?)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "lseek(fd, offset, whence?)".to_string()))); }
                 let fd     = args[0].as_int() as libc::c_int;
                 let offset = args[1].as_int() as libc::off_t;
                 let whence = args.get(2).map(|v| v.as_int()).unwrap_or(0) as libc::c_int;
@@ -8579,7 +9827,7 @@ impl Interpreter {
             }
             // flock(fd, op) -> 0  op: 1=LOCK_SH 2=LOCK_EX 4=LOCK_NB 8=LOCK_UN
             "flock" => {
-                if args.len() < 2 { return Some(Err("flock(fd, op)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "flock(fd, op)".to_string()))); }
                 let fd = args[0].as_int() as libc::c_int;
                 let op = args[1].as_int() as libc::c_int;
                 Some(Ok(Value::Int(unsafe { libc::flock(fd, op) } as i64)))
@@ -8590,14 +9838,14 @@ impl Interpreter {
             "LOCK_NB" => Some(Ok(Value::Int(libc::LOCK_NB as i64))),
             // ftruncate(fd, length) -> 0
             "ftruncate" => {
-                if args.len() < 2 { return Some(Err("ftruncate(fd, len)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "ftruncate(fd, len)".to_string()))); }
                 let fd  = args[0].as_int() as libc::c_int;
                 let len = args[1].as_int() as libc::off_t;
                 Some(Ok(Value::Int(unsafe { libc::ftruncate(fd, len) } as i64)))
             }
             // open(path, flags, mode?) -> fd
             "open" | "fd_open" => {
-                if args.is_empty() { return Some(Err("open(path, flags?, mode?)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "open(path, flags?, mode?)".to_string()))); }
                 use std::ffi::CString;
                 let path  = CString::new(args[0].as_string().as_bytes()).unwrap();
                 let flags = args.get(1).map(|v| v.as_int()).unwrap_or(libc::O_RDONLY as i64) as libc::c_int;
@@ -8612,7 +9860,7 @@ impl Interpreter {
             "O_APPEND" => Some(Ok(Value::Int(libc::O_APPEND as i64))),
             // setsockopt(fd, level, name, value) -> int
             "setsockopt" => {
-                if args.len() < 4 { return Some(Err("setsockopt(fd, level, optname, value)".to_string())); }
+                if args.len() < 4 { return Some(Err(raise(ErrorKind::TypeError, "setsockopt(fd, level, optname, value)".to_string()))); }
                 let fd       = args[0].as_int() as libc::c_int;
                 let level    = args[1].as_int() as libc::c_int;
                 let optname  = args[2].as_int() as libc::c_int;
@@ -8622,7 +9870,7 @@ impl Interpreter {
             }
             // getsockopt(fd, level, name) -> int
             "getsockopt" => {
-                if args.len() < 3 { return Some(Err("getsockopt(fd, level, optname)".to_string())); }
+                if args.len() < 3 { return Some(Err(raise(ErrorKind::TypeError, "getsockopt(fd, level, optname)".to_string()))); }
                 let fd      = args[0].as_int() as libc::c_int;
                 let level   = args[1].as_int() as libc::c_int;
                 let optname = args[2].as_int() as libc::c_int;
@@ -8659,23 +9907,23 @@ impl Interpreter {
             // inotify_init() -> fd
             "inotify_init" => {
                 let fd = unsafe { libc::inotify_init1(libc::IN_CLOEXEC) };
-                if fd < 0 { Some(Err(format!("inotify_init: errno {}", unsafe { *libc::__errno_location() }))) }
+                if fd < 0 { Some(Err(raise(ErrorKind::IOError, format!("inotify_init: errno {}", unsafe { *libc::__errno_location() })))) }
                 else { Some(Ok(Value::Int(fd as i64))) }
             }
             // inotify_add_watch(fd, path, mask) -> wd
             "inotify_add_watch" => {
-                if args.len() < 3 { return Some(Err("inotify_add_watch(fd, path, mask)".to_string())); }
+                if args.len() < 3 { return Some(Err(raise(ErrorKind::TypeError, "inotify_add_watch(fd, path, mask)".to_string()))); }
                 let fd   = args[0].as_int() as libc::c_int;
                 let path = args[1].as_string();
                 let mask = args[2].as_int() as u32;
                 let cs = std::ffi::CString::new(path).unwrap();
                 let wd = unsafe { libc::inotify_add_watch(fd, cs.as_ptr(), mask) };
-                if wd < 0 { Some(Err(format!("inotify_add_watch: errno {}", unsafe { *libc::__errno_location() }))) }
+                if wd < 0 { Some(Err(raise(ErrorKind::IOError, format!("inotify_add_watch: errno {}", unsafe { *libc::__errno_location() })))) }
                 else { Some(Ok(Value::Int(wd as i64))) }
             }
             // inotify_rm_watch(fd, wd)
             "inotify_rm_watch" => {
-                if args.len() < 2 { return Some(Err("inotify_rm_watch(fd, wd)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "inotify_rm_watch(fd, wd)".to_string()))); }
                 let fd = args[0].as_int() as libc::c_int;
                 let wd = args[1].as_int() as libc::c_int;
                 unsafe { libc::inotify_rm_watch(fd, wd); }
@@ -8683,7 +9931,7 @@ impl Interpreter {
             }
             // inotify_read(fd) -> array of {wd, mask, cookie, name}
             "inotify_read" => {
-                if args.is_empty() { return Some(Err("inotify_read(fd)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "inotify_read(fd)".to_string()))); }
                 let fd = args[0].as_int() as libc::c_int;
                 let mut buf = [0u8; 4096];
                 let n = unsafe { libc::read(fd, buf.as_mut_ptr() as *mut _, buf.len()) };
@@ -8751,7 +9999,7 @@ impl Interpreter {
             }
             // shuffle(array) -> shuffled copy
             "shuffle" => {
-                if args.is_empty() { return Some(Err("shuffle(array)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "shuffle(array)".to_string()))); }
                 match &args[0] {
                     Value::Array(arr) => {
                         let mut out = arr.clone();
@@ -8763,7 +10011,7 @@ impl Interpreter {
                         }
                         Some(Ok(Value::Array(out)))
                     }
-                    _ => Some(Err("shuffle: expected array".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "shuffle: expected array".to_string()))),
                 }
             }
 
@@ -8782,7 +10030,7 @@ impl Interpreter {
                         }).collect();
                         Some(Ok(Value::Array(entries)))
                     }
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
             // proc_status(pid?) -> map of key=value from /proc/PID/status
@@ -8802,7 +10050,7 @@ impl Interpreter {
                         }
                         Some(Ok(Value::Map(m)))
                     }
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
             // proc_fds(pid?) -> array of open fd numbers
@@ -8819,7 +10067,7 @@ impl Interpreter {
                             .collect();
                         Some(Ok(Value::Array(fds)))
                     }
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
             // proc_cmdline(pid?) -> array of argv strings
@@ -8834,7 +10082,7 @@ impl Interpreter {
                             .collect();
                         Some(Ok(Value::Array(args_vec)))
                     }
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
             // proc_list() -> array of {pid, name} for all processes
@@ -8857,7 +10105,7 @@ impl Interpreter {
             }
             // mem_read(pid, addr, size) -> bytes (reads /proc/PID/mem)
             "mem_read" => {
-                if args.len() < 3 { return Some(Err("mem_read(pid, addr, size)".to_string())); }
+                if args.len() < 3 { return Some(Err(raise(ErrorKind::TypeError, "mem_read(pid, addr, size)".to_string()))); }
                 let pid  = args[0].as_int();
                 let addr = args[1].as_int() as u64;
                 let size = args[2].as_int() as usize;
@@ -8866,16 +10114,16 @@ impl Interpreter {
                     Ok(mut f) => {
                         use std::io::Seek;
                         if f.seek(std::io::SeekFrom::Start(addr)).is_err() {
-                            return Some(Err("mem_read: seek failed".to_string()));
+                            return Some(Err(raise(ErrorKind::Error, "mem_read: seek failed".to_string())));
                         }
                         let mut buf = vec![0u8; size];
                         use std::io::Read;
                         match f.read_exact(&mut buf) {
                             Ok(_) => Some(Ok(Value::Array(buf.iter().map(|b| Value::Int(*b as i64)).collect()))),
-                            Err(e) => Some(Err(e.to_string())),
+                            Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                         }
                     }
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
 
@@ -8889,13 +10137,13 @@ impl Interpreter {
             "str_is_hex"     => { let s = args.first().map(|v| v.as_string()).unwrap_or_default(); Some(Ok(Value::Bool(!s.is_empty() && s.chars().all(|c| c.is_ascii_hexdigit())))) }
             "str_reverse"    => { let s = args.first().map(|v| v.as_string()).unwrap_or_default(); Some(Ok(Value::String(s.chars().rev().collect()))) }
             "str_count"      => {
-                if args.len() < 2 { return Some(Err("str_count(s, sub)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "str_count(s, sub)".to_string()))); }
                 let s   = args[0].as_string();
                 let sub = args[1].as_string();
                 Some(Ok(Value::Int(s.matches(sub.as_str()).count() as i64)))
             }
             "str_center" => {
-                if args.len() < 2 { return Some(Err("str_center(s, width, fill?)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "str_center(s, width, fill?)".to_string()))); }
                 let s = args[0].as_string();
                 let w = args[1].as_int() as usize;
                 let fill = args.get(2).map(|v| v.as_string()).unwrap_or_else(|| " ".to_string());
@@ -8907,20 +10155,20 @@ impl Interpreter {
                 Some(Ok(Value::String(format!("{}{}{}", fill_ch.to_string().repeat(left), s, fill_ch.to_string().repeat(right)))))
             }
             "str_zfill" => {
-                if args.len() < 2 { return Some(Err("str_zfill(s, width)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "str_zfill(s, width)".to_string()))); }
                 let s = args[0].as_string();
                 let w = args[1].as_int() as usize;
                 if s.len() >= w { return Some(Ok(Value::String(s))); }
                 Some(Ok(Value::String(format!("{:0>width$}", s, width = w))))
             }
             "str_indent" => {
-                if args.len() < 2 { return Some(Err("str_indent(s, prefix)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "str_indent(s, prefix)".to_string()))); }
                 let s      = args[0].as_string();
                 let prefix = args[1].as_string();
                 Some(Ok(Value::String(s.lines().map(|l| format!("{}{}", prefix, l)).collect::<Vec<_>>().join("\n"))))
             }
             "str_dedent" => {
-                if args.is_empty() { return Some(Err("str_dedent(s)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "str_dedent(s)".to_string()))); }
                 let s = args[0].as_string();
                 let min_indent = s.lines().filter(|l| !l.trim().is_empty())
                     .map(|l| l.len() - l.trim_start().len()).min().unwrap_or(0);
@@ -8929,11 +10177,7 @@ impl Interpreter {
 
             // ── High-resolution time ───────────────────────────────────────────
             // monotonic_ns() -> i64 nanoseconds (CLOCK_MONOTONIC)
-            "monotonic_ns" | "clock_mono" => {
-                let mut ts = libc::timespec { tv_sec: 0, tv_nsec: 0 };
-                unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut ts); }
-                Some(Ok(Value::Int(ts.tv_sec as i64 * 1_000_000_000 + ts.tv_nsec as i64)))
-            }
+            "monotonic_ns" | "clock_mono" => Some(Ok(Value::Int(monotonic_ns()))),
             // realtime_ns() -> i64 nanoseconds since epoch (CLOCK_REALTIME)
             "realtime_ns" | "clock_real" => {
                 let mut ts = libc::timespec { tv_sec: 0, tv_nsec: 0 };
@@ -9019,7 +10263,7 @@ impl Interpreter {
             // ── DNS / networking ──────────────────────────────────────────────
             // dns_lookup(host) -> array of IP strings
             "dns_lookup" | "getaddrinfo_ips" => {
-                if args.is_empty() { return Some(Err("dns_lookup(host)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "dns_lookup(host)".to_string()))); }
                 let host = args[0].as_string();
                 use std::net::ToSocketAddrs;
                 match (host.as_str(), 0u16).to_socket_addrs() {
@@ -9027,21 +10271,21 @@ impl Interpreter {
                         let ips: Vec<Value> = iter.map(|a| Value::String(a.ip().to_string())).collect();
                         Some(Ok(Value::Array(ips)))
                     }
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
             // ip_to_int(ip_str) -> i64 (IPv4 only)
             "ip_to_int" | "inet_aton" => {
-                if args.is_empty() { return Some(Err("ip_to_int(str)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "ip_to_int(str)".to_string()))); }
                 let s = args[0].as_string();
                 match s.parse::<std::net::Ipv4Addr>() {
                     Ok(ip) => Some(Ok(Value::Int(u32::from(ip) as i64))),
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::ValueError, e.to_string()))),
                 }
             }
             // int_to_ip(n) -> string
             "int_to_ip" | "inet_ntoa" => {
-                if args.is_empty() { return Some(Err("int_to_ip(n)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "int_to_ip(n)".to_string()))); }
                 let n = args[0].as_int() as u32;
                 Some(Ok(Value::String(std::net::Ipv4Addr::from(n).to_string())))
             }
@@ -9053,7 +10297,7 @@ impl Interpreter {
             // ── waitpid ───────────────────────────────────────────────────────
             // waitpid(pid, flags?) -> {pid, status, exited, exit_code}
             "waitpid" => {
-                if args.is_empty() { return Some(Err("waitpid(pid, flags?)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "waitpid(pid, flags?)".to_string()))); }
                 let pid   = args[0].as_int() as libc::pid_t;
                 let flags = args.get(1).map(|v| v.as_int() as libc::c_int).unwrap_or(0);
                 let mut status: libc::c_int = 0;
@@ -9077,7 +10321,7 @@ impl Interpreter {
             // ── select() I/O multiplexer ──────────────────────────────────────
             // fd_select(read_fds, write_fds, except_fds, timeout_us?) -> {readable, writable}
             "fd_select" | "select" => {
-                if args.is_empty() { return Some(Err("fd_select(read_fds, write_fds?, except_fds?, timeout_us?)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "fd_select(read_fds, write_fds?, except_fds?, timeout_us?)".to_string()))); }
                 let to_fd_list = |v: &Value| -> Vec<i32> {
                     match v { Value::Array(a) => a.iter().map(|x| x.as_int() as i32).collect(), _ => vec![] }
                 };
@@ -9109,7 +10353,7 @@ impl Interpreter {
 
             // ── Base64 (pure Rust, no dependency) ────────────────────────────
             "base64_encode" | "b64enc" => {
-                if args.is_empty() { return Some(Err("base64_encode(str_or_bytes)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "base64_encode(str_or_bytes)".to_string()))); }
                 let bytes: Vec<u8> = match &args[0] {
                     Value::String(s) => s.bytes().collect(),
                     Value::Array(a)  => a.iter().map(|v| v.as_int() as u8).collect(),
@@ -9130,7 +10374,7 @@ impl Interpreter {
                 Some(Ok(Value::String(out)))
             }
             "base64_decode" | "b64dec" => {
-                if args.is_empty() { return Some(Err("base64_decode(str)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "base64_decode(str)".to_string()))); }
                 let s = args[0].as_string();
                 let decode_char = |c: u8| -> u8 {
                     match c {
@@ -9155,7 +10399,7 @@ impl Interpreter {
                 Some(Ok(Value::String(String::from_utf8_lossy(&out).to_string())))
             }
             "base64_decode_bytes" => {
-                if args.is_empty() { return Some(Err("base64_decode_bytes(str)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "base64_decode_bytes(str)".to_string()))); }
                 let s = args[0].as_string();
                 let decode_char = |c: u8| -> u8 {
                     match c {
@@ -9183,25 +10427,25 @@ impl Interpreter {
             // ── bytes <-> string helpers ──────────────────────────────────────
             // bytes_to_str(array) -> utf8 string
             "bytes_to_str" | "bytes_to_string" => {
-                if args.is_empty() { return Some(Err("bytes_to_str(array)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "bytes_to_str(array)".to_string()))); }
                 match &args[0] {
                     Value::Array(a) => {
                         let bytes: Vec<u8> = a.iter().map(|v| v.as_int() as u8).collect();
                         Some(Ok(Value::String(String::from_utf8_lossy(&bytes).to_string())))
                     }
                     Value::String(s) => Some(Ok(Value::String(s.clone()))),
-                    _ => Some(Err("bytes_to_str: expected array".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "bytes_to_str: expected array".to_string()))),
                 }
             }
             // str_to_bytes(str) -> array of ints
             "str_to_bytes" | "string_to_bytes" => {
-                if args.is_empty() { return Some(Err("str_to_bytes(str)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "str_to_bytes(str)".to_string()))); }
                 let bytes: Vec<Value> = args[0].as_string().bytes().map(|b| Value::Int(b as i64)).collect();
                 Some(Ok(Value::Array(bytes)))
             }
             // hex_to_bytes(hex_str) -> array
             "hex_to_bytes" | "from_hex" => {
-                if args.is_empty() { return Some(Err("hex_to_bytes(hex_str)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "hex_to_bytes(hex_str)".to_string()))); }
                 let s = args[0].as_string();
                 let s = s.trim_start_matches("0x").trim_start_matches("0X");
                 let bytes: Result<Vec<u8>, _> = (0..s.len()).step_by(2)
@@ -9209,12 +10453,12 @@ impl Interpreter {
                     .collect();
                 match bytes {
                     Ok(b) => Some(Ok(Value::Array(b.iter().map(|v| Value::Int(*v as i64)).collect()))),
-                    Err(_) => Some(Err("hex_to_bytes: invalid hex string".to_string())),
+                    Err(_) => Some(Err(raise(ErrorKind::ValueError, "hex_to_bytes: invalid hex string".to_string()))),
                 }
             }
             // bytes_to_hex(array) -> hex string
             "bytes_to_hex" | "to_hex" => {
-                if args.is_empty() { return Some(Err("bytes_to_hex(array)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "bytes_to_hex(array)".to_string()))); }
                 match &args[0] {
                     Value::Array(a) => {
                         let s: String = a.iter().map(|v| format!("{:02x}", v.as_int() as u8)).collect();
@@ -9224,39 +10468,39 @@ impl Interpreter {
                         let s: String = s.bytes().map(|b| format!("{:02x}", b)).collect();
                         Some(Ok(Value::String(s)))
                     }
-                    _ => Some(Err("bytes_to_hex: expected array or string".to_string())),
+                    _ => Some(Err(raise(ErrorKind::TypeError, "bytes_to_hex: expected array or string".to_string()))),
                 }
             }
 
             // ── File metadata ─────────────────────────────────────────────────
             "file_size" => {
-                if args.is_empty() { return Some(Err("file_size(path)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "file_size(path)".to_string()))); }
                 match std::fs::metadata(args[0].as_string()) {
                     Ok(m) => Some(Ok(Value::Int(m.len() as i64))),
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
             "file_mtime" => {
-                if args.is_empty() { return Some(Err("file_mtime(path)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "file_mtime(path)".to_string()))); }
                 match std::fs::metadata(args[0].as_string()) {
                     Ok(m) => match m.modified() {
                         Ok(t) => Some(Ok(Value::Int(t.duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs() as i64))),
-                        Err(e) => Some(Err(e.to_string())),
+                        Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                     },
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
             "file_is_dir" => {
-                if args.is_empty() { return Some(Err("file_is_dir(path)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "file_is_dir(path)".to_string()))); }
                 Some(Ok(Value::Bool(std::fs::metadata(args[0].as_string()).map(|m| m.is_dir()).unwrap_or(false))))
             }
             "file_is_file" => {
-                if args.is_empty() { return Some(Err("file_is_file(path)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "file_is_file(path)".to_string()))); }
                 Some(Ok(Value::Bool(std::fs::metadata(args[0].as_string()).map(|m| m.is_file()).unwrap_or(false))))
             }
             // dir_files(path) -> array of {name, size, is_dir} entries
             "dir_files" | "dir_entries" => {
-                if args.is_empty() { return Some(Err("dir_files(path)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "dir_files(path)".to_string()))); }
                 match std::fs::read_dir(args[0].as_string()) {
                     Ok(entries) => {
                         let mut out = Vec::new();
@@ -9271,12 +10515,12 @@ impl Interpreter {
                         }
                         Some(Ok(Value::Array(out)))
                     }
-                    Err(e) => Some(Err(e.to_string())),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 }
             }
             // glob(pattern) -> array of matching paths
             "glob" => {
-                if args.is_empty() { return Some(Err("glob(pattern)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "glob(pattern)".to_string()))); }
                 let pattern = args[0].as_string();
                 // Simple glob: supports * in filename, ** for recursive
                 fn glob_match(pattern: &str, s: &str) -> bool {
@@ -9356,13 +10600,13 @@ impl Interpreter {
             "trailing_zeros" => { let n = args.first().map(|v| v.as_int() as u64).unwrap_or(0); Some(Ok(Value::Int(n.trailing_zeros() as i64))) }
             "bit_reverse"    => { let n = args.first().map(|v| v.as_int() as u64).unwrap_or(0); Some(Ok(Value::Int(n.reverse_bits() as i64))) }
             "rotate_left"    => {
-                if args.len() < 2 { return Some(Err("rotate_left(n, bits)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "rotate_left(n, bits)".to_string()))); }
                 let n = args[0].as_int() as u64;
                 let b = (args[1].as_int() as u32) & 63;
                 Some(Ok(Value::Int(n.rotate_left(b) as i64)))
             }
             "rotate_right" => {
-                if args.len() < 2 { return Some(Err("rotate_right(n, bits)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "rotate_right(n, bits)".to_string()))); }
                 let n = args[0].as_int() as u64;
                 let b = (args[1].as_int() as u32) & 63;
                 Some(Ok(Value::Int(n.rotate_right(b) as i64)))
@@ -9371,11 +10615,11 @@ impl Interpreter {
             // ── Raw TCP server fd ─────────────────────────────────────────────
             // tcp_server_fd(port, backlog?) -> fd  (non-blocking capable)
             "tcp_server_fd" => {
-                if args.is_empty() { return Some(Err("tcp_server_fd(port, backlog?)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "tcp_server_fd(port, backlog?)".to_string()))); }
                 let port    = args[0].as_int() as u16;
                 let backlog = args.get(1).map(|v| v.as_int() as libc::c_int).unwrap_or(128);
                 let fd = unsafe { libc::socket(libc::AF_INET, libc::SOCK_STREAM, 0) };
-                if fd < 0 { return Some(Err(format!("tcp_server_fd: socket errno {}", unsafe { *libc::__errno_location() }))); }
+                if fd < 0 { return Some(Err(raise(ErrorKind::IOError, format!("tcp_server_fd: socket errno {}", unsafe { *libc::__errno_location() })))); }
                 let opt: libc::c_int = 1;
                 unsafe { libc::setsockopt(fd, libc::SOL_SOCKET, libc::SO_REUSEADDR, &opt as *const _ as *const _, std::mem::size_of::<libc::c_int>() as libc::socklen_t); }
                 let addr = libc::sockaddr_in {
@@ -9386,22 +10630,22 @@ impl Interpreter {
                 };
                 if unsafe { libc::bind(fd, &addr as *const _ as *const libc::sockaddr, std::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t) } < 0 {
                     unsafe { libc::close(fd); }
-                    return Some(Err(format!("tcp_server_fd: bind errno {}", unsafe { *libc::__errno_location() })));
+                    return Some(Err(raise(ErrorKind::IOError, format!("tcp_server_fd: bind errno {}", unsafe { *libc::__errno_location() }))));
                 }
                 if unsafe { libc::listen(fd, backlog) } < 0 {
                     unsafe { libc::close(fd); }
-                    return Some(Err(format!("tcp_server_fd: listen errno {}", unsafe { *libc::__errno_location() })));
+                    return Some(Err(raise(ErrorKind::IOError, format!("tcp_server_fd: listen errno {}", unsafe { *libc::__errno_location() }))));
                 }
                 Some(Ok(Value::Int(fd as i64)))
             }
             // tcp_accept_fd(server_fd) -> {fd, addr, port}
             "tcp_accept_fd" => {
-                if args.is_empty() { return Some(Err("tcp_accept_fd(server_fd)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "tcp_accept_fd(server_fd)".to_string()))); }
                 let server_fd = args[0].as_int() as libc::c_int;
                 let mut addr: libc::sockaddr_in = unsafe { std::mem::zeroed() };
                 let mut addrlen = std::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t;
                 let fd = unsafe { libc::accept(server_fd, &mut addr as *mut _ as *mut libc::sockaddr, &mut addrlen) };
-                if fd < 0 { return Some(Err(format!("tcp_accept_fd: errno {}", unsafe { *libc::__errno_location() }))); }
+                if fd < 0 { return Some(Err(raise(ErrorKind::IOError, format!("tcp_accept_fd: errno {}", unsafe { *libc::__errno_location() })))); }
                 let ip = std::net::Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr)).to_string();
                 let port = u16::from_be(addr.sin_port);
                 let mut m = HashMap::new();
@@ -9412,19 +10656,19 @@ impl Interpreter {
             }
             // tcp_connect_fd(host, port) -> fd
             "tcp_connect_fd" => {
-                if args.len() < 2 { return Some(Err("tcp_connect_fd(host, port)".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "tcp_connect_fd(host, port)".to_string()))); }
                 let host = args[0].as_string();
                 let port = args[1].as_int() as u16;
                 use std::net::ToSocketAddrs;
                 let addr = match format!("{}:{}", host, port).to_socket_addrs() {
                     Ok(mut it) => match it.next() {
                         Some(a) => a,
-                        None => return Some(Err("tcp_connect_fd: no addr".to_string())),
+                        None => return Some(Err(raise(ErrorKind::Error, "tcp_connect_fd: no addr".to_string()))),
                     },
-                    Err(e) => return Some(Err(e.to_string())),
+                    Err(e) => return Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 };
                 let fd = unsafe { libc::socket(libc::AF_INET, libc::SOCK_STREAM, 0) };
-                if fd < 0 { return Some(Err(format!("tcp_connect_fd: socket errno {}", unsafe { *libc::__errno_location() }))); }
+                if fd < 0 { return Some(Err(raise(ErrorKind::IOError, format!("tcp_connect_fd: socket errno {}", unsafe { *libc::__errno_location() })))); }
                 let sin = libc::sockaddr_in {
                     sin_family: libc::AF_INET as libc::sa_family_t,
                     sin_port: port.to_be(),
@@ -9436,7 +10680,7 @@ impl Interpreter {
                 };
                 if unsafe { libc::connect(fd, &sin as *const _ as *const libc::sockaddr, std::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t) } < 0 {
                     unsafe { libc::close(fd); }
-                    return Some(Err(format!("tcp_connect_fd: connect errno {}", unsafe { *libc::__errno_location() })));
+                    return Some(Err(raise(ErrorKind::IOError, format!("tcp_connect_fd: connect errno {}", unsafe { *libc::__errno_location() }))));
                 }
                 Some(Ok(Value::Int(fd as i64)))
             }
@@ -9445,7 +10689,7 @@ impl Interpreter {
             // udp_socket(port?) -> fd
             "udp_socket" => {
                 let fd = unsafe { libc::socket(libc::AF_INET, libc::SOCK_DGRAM, 0) };
-                if fd < 0 { return Some(Err(format!("udp_socket: errno {}", unsafe { *libc::__errno_location() }))); }
+                if fd < 0 { return Some(Err(raise(ErrorKind::IOError, format!("udp_socket: errno {}", unsafe { *libc::__errno_location() })))); }
                 if let Some(port_val) = args.first() {
                     let port = port_val.as_int() as u16;
                     let addr = libc::sockaddr_in {
@@ -9460,7 +10704,7 @@ impl Interpreter {
             }
             // udp_sendto(fd, data, host, port) -> bytes_sent
             "udp_sendto" => {
-                if args.len() < 4 { return Some(Err("udp_sendto(fd, data, host, port)".to_string())); }
+                if args.len() < 4 { return Some(Err(raise(ErrorKind::TypeError, "udp_sendto(fd, data, host, port)".to_string()))); }
                 let fd   = args[0].as_int() as libc::c_int;
                 let data = args[1].as_string();
                 let host = args[2].as_string();
@@ -9468,7 +10712,7 @@ impl Interpreter {
                 use std::net::ToSocketAddrs;
                 let addr = match format!("{}:{}", host, port).to_socket_addrs().ok().and_then(|mut it| it.next()) {
                     Some(a) => a,
-                    None => return Some(Err("udp_sendto: resolve failed".to_string())),
+                    None => return Some(Err(raise(ErrorKind::Error, "udp_sendto: resolve failed".to_string()))),
                 };
                 let sin = libc::sockaddr_in {
                     sin_family: libc::AF_INET as libc::sa_family_t,
@@ -9486,7 +10730,7 @@ impl Interpreter {
             }
             // udp_recvfrom(fd, size?) -> {data, addr, port}
             "udp_recvfrom" => {
-                if args.is_empty() { return Some(Err("udp_recvfrom(fd, size?)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "udp_recvfrom(fd, size?)".to_string()))); }
                 let fd   = args[0].as_int() as libc::c_int;
                 let size = args.get(1).map(|v| v.as_int() as usize).unwrap_or(4096);
                 let mut buf = vec![0u8; size];
@@ -9494,7 +10738,7 @@ impl Interpreter {
                 let mut addrlen = std::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t;
                 let n = unsafe { libc::recvfrom(fd, buf.as_mut_ptr() as *mut _, size, 0,
                     &mut addr as *mut _ as *mut libc::sockaddr, &mut addrlen) };
-                if n < 0 { return Some(Err(format!("udp_recvfrom: errno {}", unsafe { *libc::__errno_location() }))); }
+                if n < 0 { return Some(Err(raise(ErrorKind::IOError, format!("udp_recvfrom: errno {}", unsafe { *libc::__errno_location() })))); }
                 let ip   = std::net::Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr)).to_string();
                 let port = u16::from_be(addr.sin_port);
                 let mut m = HashMap::new();
@@ -9525,7 +10769,7 @@ impl Interpreter {
             }
             // int_to_str(n, base) -> string (e.g. int_to_str(255, 16) -> "ff")
             "int_to_str" | "to_base" => {
-                if args.is_empty() { return Some(Err("int_to_str(n, base?)".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "int_to_str(n, base?)".to_string()))); }
                 let n = args[0].as_int() as u64;
                 let base = args.get(1).map(|v| v.as_int()).unwrap_or(10);
                 let s = match base {
@@ -9568,13 +10812,13 @@ impl Interpreter {
 
             "aes_encrypt" => {
                 if args.len() < 3 {
-                    return Some(Err("aes_encrypt: requires key, nonce, plaintext".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "aes_encrypt: requires key, nonce, plaintext".to_string())));
                 }
                 let mut key_bytes = vbytes(&args[0]);
                 key_bytes.resize(32, 0);
                 let nonce_bytes = vbytes(&args[1]);
                 if nonce_bytes.len() < 12 {
-                    return Some(Err("aes_encrypt: nonce must be 12 bytes".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "aes_encrypt: nonce must be 12 bytes".to_string())));
                 }
                 let plaintext = vbytes(&args[2]);
                 let key_arr = aes_gcm::aead::generic_array::GenericArray::clone_from_slice(&key_bytes[..32]);
@@ -9582,18 +10826,18 @@ impl Interpreter {
                 let cipher = <Aes256Gcm as AeadKeyInit>::new(&key_arr);
                 match cipher.encrypt(&nonce_arr, plaintext.as_slice()) {
                     Ok(ct) => Some(Ok(Value::String(b2he(&ct)))),
-                    Err(e) => Some(Err(format!("aes_encrypt error: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::Error, format!("aes_encrypt error: {}", e)))),
                 }
             }
             "aes_decrypt" => {
                 if args.len() < 3 {
-                    return Some(Err("aes_decrypt: requires key, nonce, ciphertext".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "aes_decrypt: requires key, nonce, ciphertext".to_string())));
                 }
                 let mut key_bytes = vbytes(&args[0]);
                 key_bytes.resize(32, 0);
                 let nonce_bytes = vbytes(&args[1]);
                 if nonce_bytes.len() < 12 {
-                    return Some(Err("aes_decrypt: nonce must be 12 bytes".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "aes_decrypt: nonce must be 12 bytes".to_string())));
                 }
                 let ct_bytes = vbytes(&args[2]);
                 let key_arr = aes_gcm::aead::generic_array::GenericArray::clone_from_slice(&key_bytes[..32]);
@@ -9601,18 +10845,18 @@ impl Interpreter {
                 let cipher = <Aes256Gcm as AeadKeyInit>::new(&key_arr);
                 match cipher.decrypt(&nonce_arr, ct_bytes.as_slice()) {
                     Ok(pt) => Some(Ok(Value::String(String::from_utf8_lossy(&pt).into_owned()))),
-                    Err(e) => Some(Err(format!("aes_decrypt error: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::Error, format!("aes_decrypt error: {}", e)))),
                 }
             }
             "chacha_encrypt" | "chacha20_encrypt" => {
                 if args.len() < 3 {
-                    return Some(Err("chacha_encrypt: requires key, nonce, plaintext".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "chacha_encrypt: requires key, nonce, plaintext".to_string())));
                 }
                 let mut key_bytes = vbytes(&args[0]);
                 key_bytes.resize(32, 0);
                 let nonce_bytes = vbytes(&args[1]);
                 if nonce_bytes.len() < 12 {
-                    return Some(Err("chacha_encrypt: nonce must be 12 bytes".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "chacha_encrypt: nonce must be 12 bytes".to_string())));
                 }
                 let plaintext = vbytes(&args[2]);
                 let key_arr = aes_gcm::aead::generic_array::GenericArray::clone_from_slice(&key_bytes[..32]);
@@ -9620,18 +10864,18 @@ impl Interpreter {
                 let cipher = <ChaCha20Poly1305 as ChaChaKeyInit>::new(&key_arr);
                 match cipher.encrypt(&nonce_arr, plaintext.as_slice()) {
                     Ok(ct) => Some(Ok(Value::String(b2he(&ct)))),
-                    Err(e) => Some(Err(format!("chacha_encrypt error: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::Error, format!("chacha_encrypt error: {}", e)))),
                 }
             }
             "chacha_decrypt" | "chacha20_decrypt" => {
                 if args.len() < 3 {
-                    return Some(Err("chacha_decrypt: requires key, nonce, ciphertext".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "chacha_decrypt: requires key, nonce, ciphertext".to_string())));
                 }
                 let mut key_bytes = vbytes(&args[0]);
                 key_bytes.resize(32, 0);
                 let nonce_bytes = vbytes(&args[1]);
                 if nonce_bytes.len() < 12 {
-                    return Some(Err("chacha_decrypt: nonce must be 12 bytes".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "chacha_decrypt: nonce must be 12 bytes".to_string())));
                 }
                 let ct_bytes = vbytes(&args[2]);
                 let key_arr = aes_gcm::aead::generic_array::GenericArray::clone_from_slice(&key_bytes[..32]);
@@ -9639,7 +10883,7 @@ impl Interpreter {
                 let cipher = <ChaCha20Poly1305 as ChaChaKeyInit>::new(&key_arr);
                 match cipher.decrypt(&nonce_arr, ct_bytes.as_slice()) {
                     Ok(pt) => Some(Ok(Value::String(String::from_utf8_lossy(&pt).into_owned()))),
-                    Err(e) => Some(Err(format!("chacha_decrypt error: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::Error, format!("chacha_decrypt error: {}", e)))),
                 }
             }
             "ed25519_keygen" => {
@@ -9655,11 +10899,11 @@ impl Interpreter {
             }
             "ed25519_sign" => {
                 if args.len() < 2 {
-                    return Some(Err("ed25519_sign: requires secret_hex, message".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "ed25519_sign: requires secret_hex, message".to_string())));
                 }
                 let secret_bytes = vbytes(&args[0]);
                 if secret_bytes.len() < 32 {
-                    return Some(Err("ed25519_sign: secret must be 32 bytes".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "ed25519_sign: secret must be 32 bytes".to_string())));
                 }
                 let mut secret_arr = [0u8; 32];
                 secret_arr.copy_from_slice(&secret_bytes[..32]);
@@ -9672,22 +10916,22 @@ impl Interpreter {
             "ed25519_verify" => {
                 use ed25519_dalek::Signature as Ed25519Signature;
                 if args.len() < 3 {
-                    return Some(Err("ed25519_verify: requires public_hex, message, signature_hex".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "ed25519_verify: requires public_hex, message, signature_hex".to_string())));
                 }
                 let pub_bytes = vbytes(&args[0]);
                 if pub_bytes.len() < 32 {
-                    return Some(Err("ed25519_verify: public key must be 32 bytes".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "ed25519_verify: public key must be 32 bytes".to_string())));
                 }
                 let mut pub_arr = [0u8; 32];
                 pub_arr.copy_from_slice(&pub_bytes[..32]);
                 let verifying_key = match VerifyingKey::from_bytes(&pub_arr) {
                     Ok(k) => k,
-                    Err(e) => return Some(Err(format!("ed25519_verify: invalid public key: {}", e))),
+                    Err(e) => return Some(Err(raise(ErrorKind::ValueError, format!("ed25519_verify: invalid public key: {}", e)))),
                 };
                 let message = vbytes(&args[1]);
                 let sig_bytes = vbytes(&args[2]);
                 if sig_bytes.len() < 64 {
-                    return Some(Err("ed25519_verify: signature must be 64 bytes".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "ed25519_verify: signature must be 64 bytes".to_string())));
                 }
                 let mut sig_arr = [0u8; 64];
                 sig_arr.copy_from_slice(&sig_bytes[..64]);
@@ -9707,15 +10951,15 @@ impl Interpreter {
             }
             "x25519_dh" | "x25519_exchange" => {
                 if args.len() < 2 {
-                    return Some(Err("x25519_dh: requires secret_hex, their_public_hex".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "x25519_dh: requires secret_hex, their_public_hex".to_string())));
                 }
                 let sec_bytes = vbytes(&args[0]);
                 let pub_bytes = vbytes(&args[1]);
                 if sec_bytes.len() < 32 {
-                    return Some(Err("x25519_dh: secret must be 32 bytes".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "x25519_dh: secret must be 32 bytes".to_string())));
                 }
                 if pub_bytes.len() < 32 {
-                    return Some(Err("x25519_dh: their public must be 32 bytes".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "x25519_dh: their public must be 32 bytes".to_string())));
                 }
                 let mut sec_arr = [0u8; 32];
                 sec_arr.copy_from_slice(&sec_bytes[..32]);
@@ -9733,7 +10977,7 @@ impl Interpreter {
                 let bits = if bits < 512 { 512 } else { bits };
                 let priv_key = match RsaPrivateKey::new(&mut OsRng, bits) {
                     Ok(k) => k,
-                    Err(e) => return Some(Err(format!("rsa_keygen error: {}", e))),
+                    Err(e) => return Some(Err(raise(ErrorKind::Error, format!("rsa_keygen error: {}", e)))),
                 };
                 let n_bytes = priv_key.n().to_bytes_be();
                 let e_bytes = priv_key.e().to_bytes_be();
@@ -9747,7 +10991,7 @@ impl Interpreter {
             "rsa_encrypt" => {
                 use rsa::BigUint;
                 if args.len() < 3 {
-                    return Some(Err("rsa_encrypt: requires n_hex, e_hex, plaintext".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "rsa_encrypt: requires n_hex, e_hex, plaintext".to_string())));
                 }
                 let n_bytes = vbytes(&args[0]);
                 let e_bytes = vbytes(&args[1]);
@@ -9756,11 +11000,11 @@ impl Interpreter {
                 let e = BigUint::from_bytes_be(&e_bytes);
                 let pub_key = match RsaPublicKey::new(n, e) {
                     Ok(k) => k,
-                    Err(err) => return Some(Err(format!("rsa_encrypt: invalid key: {}", err))),
+                    Err(err) => return Some(Err(raise(ErrorKind::ValueError, format!("rsa_encrypt: invalid key: {}", err)))),
                 };
                 match pub_key.encrypt(&mut OsRng, Pkcs1v15Encrypt, &plaintext) {
                     Ok(ct) => Some(Ok(Value::String(b2he(&ct)))),
-                    Err(err) => Some(Err(format!("rsa_encrypt error: {}", err))),
+                    Err(err) => Some(Err(raise(ErrorKind::Error, format!("rsa_encrypt error: {}", err)))),
                 }
             }
             "rsa_decrypt" => {
@@ -9790,11 +11034,11 @@ impl Interpreter {
             }
             "blake3_keyed" => {
                 if args.len() < 2 {
-                    return Some(Err("blake3_keyed: requires key (32 bytes), data".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "blake3_keyed: requires key (32 bytes), data".to_string())));
                 }
                 let key_bytes = vbytes(&args[0]);
                 if key_bytes.len() < 32 {
-                    return Some(Err("blake3_keyed: key must be 32 bytes".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "blake3_keyed: key must be 32 bytes".to_string())));
                 }
                 let mut key_arr = [0u8; 32];
                 key_arr.copy_from_slice(&key_bytes[..32]);
@@ -9804,14 +11048,14 @@ impl Interpreter {
             }
             "hmac_sha512" => {
                 if args.len() < 2 {
-                    return Some(Err("hmac_sha512: requires key, data".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "hmac_sha512: requires key, data".to_string())));
                 }
                 type HmacSha512 = Hmac<Sha512>;
                 let key = vbytes(&args[0]);
                 let data = vbytes(&args[1]);
                 let mut mac = match <HmacSha512 as hmac::Mac>::new_from_slice(&key) {
                     Ok(m) => m,
-                    Err(e) => return Some(Err(format!("hmac_sha512: invalid key length: {}", e))),
+                    Err(e) => return Some(Err(raise(ErrorKind::ValueError, format!("hmac_sha512: invalid key length: {}", e)))),
                 };
                 hmac::Mac::update(&mut mac, &data);
                 let result = hmac::Mac::finalize(mac);
@@ -9819,25 +11063,25 @@ impl Interpreter {
             }
             "argon2_hash" => {
                 if args.is_empty() {
-                    return Some(Err("argon2_hash: requires password".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "argon2_hash: requires password".to_string())));
                 }
                 let pwd = args[0].as_string();
                 let salt = SaltString::generate(&mut OsRng);
                 match Argon2::default().hash_password(pwd.as_bytes(), &salt) {
                     Ok(hash) => Some(Ok(Value::String(hash.to_string()))),
-                    Err(e) => Some(Err(format!("argon2_hash error: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::Error, format!("argon2_hash error: {}", e)))),
                 }
             }
             "argon2_verify" => {
                 use argon2::password_hash::PasswordHash;
                 if args.len() < 2 {
-                    return Some(Err("argon2_verify: requires hash_str, password".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "argon2_verify: requires hash_str, password".to_string())));
                 }
                 let hash_str = args[0].as_string();
                 let pwd = args[1].as_string();
                 let parsed_hash = match PasswordHash::new(&hash_str) {
                     Ok(h) => h,
-                    Err(e) => return Some(Err(format!("argon2_verify: invalid hash: {}", e))),
+                    Err(e) => return Some(Err(raise(ErrorKind::ValueError, format!("argon2_verify: invalid hash: {}", e)))),
                 };
                 let ok = Argon2::default()
                     .verify_password(pwd.as_bytes(), &parsed_hash)
@@ -9846,7 +11090,7 @@ impl Interpreter {
             }
             "pbkdf2_sha256" => {
                 if args.is_empty() {
-                    return Some(Err("pbkdf2_sha256: requires password".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "pbkdf2_sha256: requires password".to_string())));
                 }
                 let pwd = vbytes(&args[0]);
                 let salt = if args.len() > 1 { vbytes(&args[1]) } else { b"knull-salt".to_vec() };
@@ -9929,77 +11173,77 @@ impl Interpreter {
             }
 
             "bignum" | "big_int" => {
-                if args.is_empty() { return Some(Err("bignum requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "bignum requires 1 arg".to_string()))); }
                 let s = args[0].as_string();
                 let s2 = s.trim_start_matches("bignum:");
                 match s2.parse::<BigInt>() {
                     Ok(n) => Some(Ok(Value::String(format!("bignum:{}", n)))),
-                    Err(e) => Some(Err(format!("bignum parse error: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("bignum parse error: {}", e)))),
                 }
             }
             "big_add" => {
-                if args.len() < 2 { return Some(Err("big_add requires 2 args".to_string())); }
-                let parse_big = |v: &Value| -> Result<BigInt, String> {
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "big_add requires 2 args".to_string()))); }
+                let parse_big = |v: &Value| -> Result<BigInt, RuntimeError> {
                     let s = v.as_string();
                     let s2 = s.trim_start_matches("bignum:").to_string();
-                    s2.parse::<BigInt>().map_err(|e| e.to_string())
+                    s2.parse::<BigInt>().map_err(|e| raise(ErrorKind::ValueError, e.to_string()))
                 };
                 let a = match parse_big(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
                 let b = match parse_big(&args[1]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
                 Some(Ok(Value::String(format!("bignum:{}", a + b))))
             }
             "big_sub" => {
-                if args.len() < 2 { return Some(Err("big_sub requires 2 args".to_string())); }
-                let parse_big = |v: &Value| -> Result<BigInt, String> {
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "big_sub requires 2 args".to_string()))); }
+                let parse_big = |v: &Value| -> Result<BigInt, RuntimeError> {
                     let s = v.as_string();
                     let s2 = s.trim_start_matches("bignum:").to_string();
-                    s2.parse::<BigInt>().map_err(|e| e.to_string())
+                    s2.parse::<BigInt>().map_err(|e| raise(ErrorKind::ValueError, e.to_string()))
                 };
                 let a = match parse_big(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
                 let b = match parse_big(&args[1]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
                 Some(Ok(Value::String(format!("bignum:{}", a - b))))
             }
             "big_mul" => {
-                if args.len() < 2 { return Some(Err("big_mul requires 2 args".to_string())); }
-                let parse_big = |v: &Value| -> Result<BigInt, String> {
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "big_mul requires 2 args".to_string()))); }
+                let parse_big = |v: &Value| -> Result<BigInt, RuntimeError> {
                     let s = v.as_string();
                     let s2 = s.trim_start_matches("bignum:").to_string();
-                    s2.parse::<BigInt>().map_err(|e| e.to_string())
+                    s2.parse::<BigInt>().map_err(|e| raise(ErrorKind::ValueError, e.to_string()))
                 };
                 let a = match parse_big(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
                 let b = match parse_big(&args[1]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
                 Some(Ok(Value::String(format!("bignum:{}", a * b))))
             }
             "big_div" => {
-                if args.len() < 2 { return Some(Err("big_div requires 2 args".to_string())); }
-                let parse_big = |v: &Value| -> Result<BigInt, String> {
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "big_div requires 2 args".to_string()))); }
+                let parse_big = |v: &Value| -> Result<BigInt, RuntimeError> {
                     let s = v.as_string();
                     let s2 = s.trim_start_matches("bignum:").to_string();
-                    s2.parse::<BigInt>().map_err(|e| e.to_string())
+                    s2.parse::<BigInt>().map_err(|e| raise(ErrorKind::ValueError, e.to_string()))
                 };
                 let a = match parse_big(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
                 let b = match parse_big(&args[1]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
-                if b.is_zero() { return Some(Err("big_div: division by zero".to_string())); }
+                if b.is_zero() { return Some(Err(raise(ErrorKind::ArithmeticError, "big_div: division by zero"))); }
                 Some(Ok(Value::String(format!("bignum:{}", a / b))))
             }
             "big_mod" => {
-                if args.len() < 2 { return Some(Err("big_mod requires 2 args".to_string())); }
-                let parse_big = |v: &Value| -> Result<BigInt, String> {
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "big_mod requires 2 args".to_string()))); }
+                let parse_big = |v: &Value| -> Result<BigInt, RuntimeError> {
                     let s = v.as_string();
                     let s2 = s.trim_start_matches("bignum:").to_string();
-                    s2.parse::<BigInt>().map_err(|e| e.to_string())
+                    s2.parse::<BigInt>().map_err(|e| raise(ErrorKind::ValueError, e.to_string()))
                 };
                 let a = match parse_big(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
                 let b = match parse_big(&args[1]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
-                if b.is_zero() { return Some(Err("big_mod: modulo by zero".to_string())); }
+                if b.is_zero() { return Some(Err(raise(ErrorKind::ArithmeticError, "big_mod: modulo by zero"))); }
                 Some(Ok(Value::String(format!("bignum:{}", a % b))))
             }
             "big_pow" => {
-                if args.len() < 2 { return Some(Err("big_pow requires 2 args".to_string())); }
-                let parse_big = |v: &Value| -> Result<BigInt, String> {
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "big_pow requires 2 args".to_string()))); }
+                let parse_big = |v: &Value| -> Result<BigInt, RuntimeError> {
                     let s = v.as_string();
                     let s2 = s.trim_start_matches("bignum:").to_string();
-                    s2.parse::<BigInt>().map_err(|e| e.to_string())
+                    s2.parse::<BigInt>().map_err(|e| raise(ErrorKind::ValueError, e.to_string()))
                 };
                 let base = match parse_big(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
                 let exp_val = args[1].as_int() as u64;
@@ -10016,11 +11260,11 @@ impl Interpreter {
                 Some(Ok(Value::String(format!("bignum:{}", result))))
             }
             "big_cmp" => {
-                if args.len() < 2 { return Some(Err("big_cmp requires 2 args".to_string())); }
-                let parse_big = |v: &Value| -> Result<BigInt, String> {
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "big_cmp requires 2 args".to_string()))); }
+                let parse_big = |v: &Value| -> Result<BigInt, RuntimeError> {
                     let s = v.as_string();
                     let s2 = s.trim_start_matches("bignum:").to_string();
-                    s2.parse::<BigInt>().map_err(|e| e.to_string())
+                    s2.parse::<BigInt>().map_err(|e| raise(ErrorKind::ValueError, e.to_string()))
                 };
                 let a = match parse_big(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
                 let b = match parse_big(&args[1]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
@@ -10032,12 +11276,12 @@ impl Interpreter {
                 Some(Ok(Value::Int(cmp_result)))
             }
             "big_to_hex" => {
-                if args.is_empty() { return Some(Err("big_to_hex requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "big_to_hex requires 1 arg".to_string()))); }
                 let s = args[0].as_string();
                 let s2 = s.trim_start_matches("bignum:");
                 let n = match s2.parse::<BigInt>() {
                     Ok(v) => v,
-                    Err(e) => return Some(Err(e.to_string())),
+                    Err(e) => return Some(Err(raise(ErrorKind::ValueError, e.to_string()))),
                 };
                 let (sign, bytes) = n.to_bytes_be();
                 let hex_str = b2he(&bytes);
@@ -10049,7 +11293,7 @@ impl Interpreter {
                 Some(Ok(Value::String(result)))
             }
             "big_from_hex" => {
-                if args.is_empty() { return Some(Err("big_from_hex requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "big_from_hex requires 1 arg".to_string()))); }
                 let hex_str = args[0].as_string();
                 let (negative, hex_clean) = if hex_str.starts_with('-') {
                     (true, &hex_str[1..])
@@ -10058,18 +11302,18 @@ impl Interpreter {
                 };
                 let bytes = match he2b(hex_clean) {
                     Some(b) => b,
-                    None => return Some(Err("big_from_hex: invalid hex string".to_string())),
+                    None => return Some(Err(raise(ErrorKind::ValueError, "big_from_hex: invalid hex string".to_string()))),
                 };
                 let sign = if negative { Sign::Minus } else { Sign::Plus };
                 let n = BigInt::from_bytes_be(sign, &bytes);
                 Some(Ok(Value::String(format!("bignum:{}", n))))
             }
             "big_gcd" => {
-                if args.len() < 2 { return Some(Err("big_gcd requires 2 args".to_string())); }
-                let parse_big = |v: &Value| -> Result<BigInt, String> {
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "big_gcd requires 2 args".to_string()))); }
+                let parse_big = |v: &Value| -> Result<BigInt, RuntimeError> {
                     let s = v.as_string();
                     let s2 = s.trim_start_matches("bignum:").to_string();
-                    s2.parse::<BigInt>().map_err(|e| e.to_string())
+                    s2.parse::<BigInt>().map_err(|e| raise(ErrorKind::ValueError, e.to_string()))
                 };
                 let a = match parse_big(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
                 let b = match parse_big(&args[1]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
@@ -10077,11 +11321,11 @@ impl Interpreter {
                 Some(Ok(Value::String(format!("bignum:{}", g))))
             }
             "big_lcm" => {
-                if args.len() < 2 { return Some(Err("big_lcm requires 2 args".to_string())); }
-                let parse_big = |v: &Value| -> Result<BigInt, String> {
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "big_lcm requires 2 args".to_string()))); }
+                let parse_big = |v: &Value| -> Result<BigInt, RuntimeError> {
                     let s = v.as_string();
                     let s2 = s.trim_start_matches("bignum:").to_string();
-                    s2.parse::<BigInt>().map_err(|e| e.to_string())
+                    s2.parse::<BigInt>().map_err(|e| raise(ErrorKind::ValueError, e.to_string()))
                 };
                 let a = match parse_big(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
                 let b = match parse_big(&args[1]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
@@ -10093,12 +11337,12 @@ impl Interpreter {
                 Some(Ok(Value::String(format!("bignum:{}", lcm))))
             }
             "big_is_prime" => {
-                if args.is_empty() { return Some(Err("big_is_prime requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "big_is_prime requires 1 arg".to_string()))); }
                 let s = args[0].as_string();
                 let s2 = s.trim_start_matches("bignum:");
                 let n = match s2.parse::<BigInt>() {
                     Ok(v) => v,
-                    Err(e) => return Some(Err(e.to_string())),
+                    Err(e) => return Some(Err(raise(ErrorKind::ValueError, e.to_string()))),
                 };
                 if n < BigInt::from(2i64) {
                     return Some(Ok(Value::Bool(false)));
@@ -10149,15 +11393,15 @@ impl Interpreter {
                 Some(Ok(Value::Bool(is_prime)))
             }
             "big_sqrt" => {
-                if args.is_empty() { return Some(Err("big_sqrt requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "big_sqrt requires 1 arg".to_string()))); }
                 let s = args[0].as_string();
                 let s2 = s.trim_start_matches("bignum:");
                 let n = match s2.parse::<BigInt>() {
                     Ok(v) => v,
-                    Err(e) => return Some(Err(e.to_string())),
+                    Err(e) => return Some(Err(raise(ErrorKind::ValueError, e.to_string()))),
                 };
                 if n < BigInt::zero() {
-                    return Some(Err("big_sqrt: cannot take sqrt of negative number".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "big_sqrt: cannot take sqrt of negative number".to_string())));
                 }
                 if n.is_zero() {
                     return Some(Ok(Value::String("bignum:0".to_string())));
@@ -10172,13 +11416,13 @@ impl Interpreter {
                 Some(Ok(Value::String(format!("bignum:{}", x))))
             }
             "big_factorial" => {
-                if args.is_empty() { return Some(Err("big_factorial requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "big_factorial requires 1 arg".to_string()))); }
                 let n = args[0].as_int();
                 if n < 0 {
-                    return Some(Err("big_factorial: negative input".to_string()));
+                    return Some(Err(raise(ErrorKind::Error, "big_factorial: negative input".to_string())));
                 }
                 if n > 10000 {
-                    return Some(Err("big_factorial: input too large (max 10000)".to_string()));
+                    return Some(Err(raise(ErrorKind::Error, "big_factorial: input too large (max 10000)".to_string())));
                 }
                 let mut result = BigInt::one();
                 for i in 2..=(n as u64) {
@@ -10187,12 +11431,12 @@ impl Interpreter {
                 Some(Ok(Value::String(format!("bignum:{}", result))))
             }
             "big_to_int" => {
-                if args.is_empty() { return Some(Err("big_to_int requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "big_to_int requires 1 arg".to_string()))); }
                 let s = args[0].as_string();
                 let s2 = s.trim_start_matches("bignum:");
                 let n = match s2.parse::<BigInt>() {
                     Ok(v) => v,
-                    Err(e) => return Some(Err(e.to_string())),
+                    Err(e) => return Some(Err(raise(ErrorKind::ValueError, e.to_string()))),
                 };
                 match n.to_i64() {
                     Some(i) => Some(Ok(Value::Int(i))),
@@ -10206,11 +11450,11 @@ impl Interpreter {
                 Some(Ok(Value::String(Uuid::now_v7().to_string())))
             }
             "uuid_parse" => {
-                if args.is_empty() { return Some(Err("uuid_parse requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::ValueError, "uuid_parse requires 1 arg".to_string()))); }
                 let s = args[0].as_string();
                 let id = match Uuid::parse_str(&s) {
                     Ok(v) => v,
-                    Err(e) => return Some(Err(format!("uuid_parse: {}", e))),
+                    Err(e) => return Some(Err(raise(ErrorKind::ValueError, format!("uuid_parse: {}", e)))),
                 };
                 let version = id.get_version_num() as i64;
                 let variant_str = format!("{:?}", id.get_variant());
@@ -10253,37 +11497,37 @@ impl Interpreter {
                 Some(Ok(Value::Map(m)))
             }
             "date_format" => {
-                if args.len() < 2 { return Some(Err("date_format requires 2 args: ts, fmt".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "date_format requires 2 args: ts, fmt".to_string()))); }
                 let ts = args[0].as_int();
                 let fmt = args[1].as_string();
                 match Local.timestamp_opt(ts, 0).single() {
                     Some(dt) => Some(Ok(Value::String(dt.format(&fmt).to_string()))),
-                    None => Some(Err(format!("date_format: invalid timestamp {}", ts))),
+                    None => Some(Err(raise(ErrorKind::ValueError, format!("date_format: invalid timestamp {}", ts)))),
                 }
             }
             "date_parse" => {
-                if args.len() < 2 { return Some(Err("date_parse requires 2 args: date_str, fmt".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::ValueError, "date_parse requires 2 args: date_str, fmt".to_string()))); }
                 let date_str = args[0].as_string();
                 let fmt_str = args[1].as_string();
                 match chrono::NaiveDateTime::parse_from_str(&date_str, &fmt_str) {
                     Ok(ndt) => Some(Ok(Value::Int(ndt.and_utc().timestamp()))),
-                    Err(e) => Some(Err(format!("date_parse: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("date_parse: {}", e)))),
                 }
             }
             "date_add" => {
-                if args.len() < 2 { return Some(Err("date_add requires 2 args: ts, seconds".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "date_add requires 2 args: ts, seconds".to_string()))); }
                 let ts = args[0].as_int();
                 let secs = args[1].as_int();
                 Some(Ok(Value::Int(ts + secs)))
             }
             "date_diff" => {
-                if args.len() < 2 { return Some(Err("date_diff requires 2 args: ts1, ts2".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "date_diff requires 2 args: ts1, ts2".to_string()))); }
                 let ts1 = args[0].as_int();
                 let ts2 = args[1].as_int();
                 Some(Ok(Value::Int(ts2 - ts1)))
             }
             "date_weekday" => {
-                if args.is_empty() { return Some(Err("date_weekday requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "date_weekday requires 1 arg".to_string()))); }
                 let ts = args[0].as_int();
                 match Local.timestamp_opt(ts, 0).single() {
                     Some(dt) => {
@@ -10299,11 +11543,11 @@ impl Interpreter {
                         };
                         Some(Ok(Value::String(wd.to_string())))
                     }
-                    None => Some(Err(format!("date_weekday: invalid timestamp {}", ts))),
+                    None => Some(Err(raise(ErrorKind::ValueError, format!("date_weekday: invalid timestamp {}", ts)))),
                 }
             }
             "date_components" => {
-                if args.is_empty() { return Some(Err("date_components requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "date_components requires 1 arg".to_string()))); }
                 let ts = args[0].as_int();
                 match Local.timestamp_opt(ts, 0).single() {
                     Some(dt) => {
@@ -10329,14 +11573,14 @@ impl Interpreter {
                         m.insert("week_of_year".to_string(), Value::Int(dt.iso_week().week() as i64));
                         Some(Ok(Value::Map(m)))
                     }
-                    None => Some(Err(format!("date_components: invalid timestamp {}", ts))),
+                    None => Some(Err(raise(ErrorKind::ValueError, format!("date_components: invalid timestamp {}", ts)))),
                 }
             }
             "arr_unique" | "unique" => {
-                if args.is_empty() { return Some(Err("arr_unique requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "arr_unique requires 1 arg".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(a) => a.clone(),
-                    _ => return Some(Err("arr_unique: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_unique: first arg must be array".to_string()))),
                 };
                 let mut seen = std::collections::HashSet::new();
                 let mut result = Vec::new();
@@ -10349,10 +11593,10 @@ impl Interpreter {
                 Some(Ok(Value::Array(result)))
             }
             "arr_flatten" | "flatten" => {
-                if args.is_empty() { return Some(Err("arr_flatten requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "arr_flatten requires 1 arg".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(a) => a.clone(),
-                    _ => return Some(Err("arr_flatten: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_flatten: first arg must be array".to_string()))),
                 };
                 let mut result = Vec::new();
                 for item in arr {
@@ -10364,7 +11608,7 @@ impl Interpreter {
                 Some(Ok(Value::Array(result)))
             }
             "arr_flatten_deep" => {
-                if args.is_empty() { return Some(Err("arr_flatten_deep requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "arr_flatten_deep requires 1 arg".to_string()))); }
                 fn flatten_deep(v: &Value) -> Vec<Value> {
                     match v {
                         Value::Array(arr) => arr.iter().flat_map(|x| flatten_deep(x)).collect(),
@@ -10375,14 +11619,14 @@ impl Interpreter {
                 Some(Ok(Value::Array(result)))
             }
             "arr_zip" | "zip" => {
-                if args.len() < 2 { return Some(Err("arr_zip requires 2 args".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "arr_zip requires 2 args".to_string()))); }
                 let a = match &args[0] {
                     Value::Array(v) => v.clone(),
-                    _ => return Some(Err("arr_zip: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_zip: first arg must be array".to_string()))),
                 };
                 let b = match &args[1] {
                     Value::Array(v) => v.clone(),
-                    _ => return Some(Err("arr_zip: second arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_zip: second arg must be array".to_string()))),
                 };
                 let result: Vec<Value> = a.into_iter().zip(b.into_iter())
                     .map(|(x, y)| Value::Array(vec![x, y]))
@@ -10390,10 +11634,10 @@ impl Interpreter {
                 Some(Ok(Value::Array(result)))
             }
             "arr_unzip" => {
-                if args.is_empty() { return Some(Err("arr_unzip requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "arr_unzip requires 1 arg".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(v) => v.clone(),
-                    _ => return Some(Err("arr_unzip: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_unzip: first arg must be array".to_string()))),
                 };
                 let mut first = Vec::new();
                 let mut second = Vec::new();
@@ -10403,7 +11647,7 @@ impl Interpreter {
                             first.push(pair[0].clone());
                             second.push(pair[1].clone());
                         }
-                        _ => return Some(Err("arr_unzip: each element must be a pair array".to_string())),
+                        _ => return Some(Err(raise(ErrorKind::TypeError, "arr_unzip: each element must be a pair array".to_string()))),
                     }
                 }
                 let mut m = std::collections::HashMap::new();
@@ -10412,26 +11656,26 @@ impl Interpreter {
                 Some(Ok(Value::Map(m)))
             }
             "arr_chunk" | "chunk" => {
-                if args.len() < 2 { return Some(Err("arr_chunk requires 2 args".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "arr_chunk requires 2 args".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(v) => v.clone(),
-                    _ => return Some(Err("arr_chunk: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_chunk: first arg must be array".to_string()))),
                 };
                 let size = args[1].as_int() as usize;
-                if size == 0 { return Some(Err("arr_chunk: chunk size must be > 0".to_string())); }
+                if size == 0 { return Some(Err(raise(ErrorKind::TypeError, "arr_chunk: chunk size must be > 0".to_string()))); }
                 let result: Vec<Value> = arr.chunks(size)
                     .map(|c| Value::Array(c.to_vec()))
                     .collect();
                 Some(Ok(Value::Array(result)))
             }
             "arr_window" | "windows" => {
-                if args.len() < 2 { return Some(Err("arr_window requires 2 args".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "arr_window requires 2 args".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(v) => v.clone(),
-                    _ => return Some(Err("arr_window: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_window: first arg must be array".to_string()))),
                 };
                 let size = args[1].as_int() as usize;
-                if size == 0 { return Some(Err("arr_window: window size must be > 0".to_string())); }
+                if size == 0 { return Some(Err(raise(ErrorKind::TypeError, "arr_window: window size must be > 0".to_string()))); }
                 if size > arr.len() {
                     return Some(Ok(Value::Array(vec![])));
                 }
@@ -10441,30 +11685,30 @@ impl Interpreter {
                 Some(Ok(Value::Array(result)))
             }
             "arr_take" | "take" => {
-                if args.len() < 2 { return Some(Err("arr_take requires 2 args".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "arr_take requires 2 args".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(v) => v.clone(),
-                    _ => return Some(Err("arr_take: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_take: first arg must be array".to_string()))),
                 };
                 let n = args[1].as_int() as usize;
                 let result = arr.into_iter().take(n).collect();
                 Some(Ok(Value::Array(result)))
             }
             "arr_drop" | "drop" => {
-                if args.len() < 2 { return Some(Err("arr_drop requires 2 args".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "arr_drop requires 2 args".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(v) => v.clone(),
-                    _ => return Some(Err("arr_drop: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_drop: first arg must be array".to_string()))),
                 };
                 let n = args[1].as_int() as usize;
                 let result = arr.into_iter().skip(n).collect();
                 Some(Ok(Value::Array(result)))
             }
             "arr_count_by" => {
-                if args.is_empty() { return Some(Err("arr_count_by requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "arr_count_by requires 1 arg".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(v) => v.clone(),
-                    _ => return Some(Err("arr_count_by: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_count_by: first arg must be array".to_string()))),
                 };
                 let mut counts: std::collections::HashMap<String, i64> = std::collections::HashMap::new();
                 for item in &arr {
@@ -10477,22 +11721,22 @@ impl Interpreter {
                 Some(Ok(Value::Map(m)))
             }
             "arr_mean" | "mean" => {
-                if args.is_empty() { return Some(Err("arr_mean requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "arr_mean requires 1 arg".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(v) => v.clone(),
-                    _ => return Some(Err("arr_mean: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_mean: first arg must be array".to_string()))),
                 };
-                if arr.is_empty() { return Some(Err("arr_mean: empty array".to_string())); }
+                if arr.is_empty() { return Some(Err(raise(ErrorKind::ValueError, "arr_mean: empty array".to_string()))); }
                 let sum: f64 = arr.iter().map(|v| v.as_float()).sum();
                 Some(Ok(Value::Float(sum / arr.len() as f64)))
             }
             "arr_median" | "median" => {
-                if args.is_empty() { return Some(Err("arr_median requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "arr_median requires 1 arg".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(v) => v.clone(),
-                    _ => return Some(Err("arr_median: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_median: first arg must be array".to_string()))),
                 };
-                if arr.is_empty() { return Some(Err("arr_median: empty array".to_string())); }
+                if arr.is_empty() { return Some(Err(raise(ErrorKind::ValueError, "arr_median: empty array".to_string()))); }
                 let mut sorted: Vec<f64> = arr.iter().map(|v| v.as_float()).collect();
                 sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
                 let n = sorted.len();
@@ -10504,12 +11748,12 @@ impl Interpreter {
                 Some(Ok(Value::Float(median)))
             }
             "arr_mode" => {
-                if args.is_empty() { return Some(Err("arr_mode requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "arr_mode requires 1 arg".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(v) => v.clone(),
-                    _ => return Some(Err("arr_mode: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_mode: first arg must be array".to_string()))),
                 };
-                if arr.is_empty() { return Some(Err("arr_mode: empty array".to_string())); }
+                if arr.is_empty() { return Some(Err(raise(ErrorKind::ValueError, "arr_mode: empty array".to_string()))); }
                 let mut counts: std::collections::HashMap<String, (usize, Value)> = std::collections::HashMap::new();
                 for item in &arr {
                     let key = item.as_string();
@@ -10523,34 +11767,34 @@ impl Interpreter {
                 Some(Ok(mode_val))
             }
             "arr_variance" => {
-                if args.is_empty() { return Some(Err("arr_variance requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "arr_variance requires 1 arg".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(v) => v.clone(),
-                    _ => return Some(Err("arr_variance: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_variance: first arg must be array".to_string()))),
                 };
-                if arr.is_empty() { return Some(Err("arr_variance: empty array".to_string())); }
+                if arr.is_empty() { return Some(Err(raise(ErrorKind::ValueError, "arr_variance: empty array".to_string()))); }
                 let floats: Vec<f64> = arr.iter().map(|v| v.as_float()).collect();
                 let mean = floats.iter().sum::<f64>() / floats.len() as f64;
                 let variance = floats.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / floats.len() as f64;
                 Some(Ok(Value::Float(variance)))
             }
             "arr_stdev" | "stdev" => {
-                if args.is_empty() { return Some(Err("arr_stdev requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "arr_stdev requires 1 arg".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(v) => v.clone(),
-                    _ => return Some(Err("arr_stdev: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_stdev: first arg must be array".to_string()))),
                 };
-                if arr.is_empty() { return Some(Err("arr_stdev: empty array".to_string())); }
+                if arr.is_empty() { return Some(Err(raise(ErrorKind::ValueError, "arr_stdev: empty array".to_string()))); }
                 let floats: Vec<f64> = arr.iter().map(|v| v.as_float()).collect();
                 let mean = floats.iter().sum::<f64>() / floats.len() as f64;
                 let variance = floats.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / floats.len() as f64;
                 Some(Ok(Value::Float(variance.sqrt())))
             }
             "arr_product" => {
-                if args.is_empty() { return Some(Err("arr_product requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "arr_product requires 1 arg".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(v) => v.clone(),
-                    _ => return Some(Err("arr_product: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_product: first arg must be array".to_string()))),
                 };
                 if arr.is_empty() { return Some(Ok(Value::Float(1.0))); }
                 let has_float = arr.iter().any(|v| matches!(v, Value::Float(_)));
@@ -10563,12 +11807,12 @@ impl Interpreter {
                 }
             }
             "arr_percentile" => {
-                if args.len() < 2 { return Some(Err("arr_percentile requires 2 args: array, p".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "arr_percentile requires 2 args: array, p".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(v) => v.clone(),
-                    _ => return Some(Err("arr_percentile: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_percentile: first arg must be array".to_string()))),
                 };
-                if arr.is_empty() { return Some(Err("arr_percentile: empty array".to_string())); }
+                if arr.is_empty() { return Some(Err(raise(ErrorKind::ValueError, "arr_percentile: empty array".to_string()))); }
                 let p = args[1].as_float().clamp(0.0, 100.0);
                 let mut sorted: Vec<f64> = arr.iter().map(|v| v.as_float()).collect();
                 sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
@@ -10582,10 +11826,10 @@ impl Interpreter {
                 Some(Ok(Value::Float(result)))
             }
             "arr_rotate" => {
-                if args.len() < 2 { return Some(Err("arr_rotate requires 2 args".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "arr_rotate requires 2 args".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(v) => v.clone(),
-                    _ => return Some(Err("arr_rotate: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_rotate: first arg must be array".to_string()))),
                 };
                 if arr.is_empty() { return Some(Ok(Value::Array(vec![]))); }
                 let n = arr.len();
@@ -10595,22 +11839,22 @@ impl Interpreter {
                 Some(Ok(Value::Array(result)))
             }
             "arr_repeat" | "repeat_arr" => {
-                if args.len() < 2 { return Some(Err("arr_repeat requires 2 args: value, n".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "arr_repeat requires 2 args: value, n".to_string()))); }
                 let val = args[0].clone();
                 let n = args[1].as_int();
-                if n < 0 { return Some(Err("arr_repeat: count must be non-negative".to_string())); }
+                if n < 0 { return Some(Err(raise(ErrorKind::TypeError, "arr_repeat: count must be non-negative".to_string()))); }
                 let result = vec![val; n as usize];
                 Some(Ok(Value::Array(result)))
             }
             "arr_interleave" => {
-                if args.len() < 2 { return Some(Err("arr_interleave requires 2 args".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "arr_interleave requires 2 args".to_string()))); }
                 let a = match &args[0] {
                     Value::Array(v) => v.clone(),
-                    _ => return Some(Err("arr_interleave: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_interleave: first arg must be array".to_string()))),
                 };
                 let b = match &args[1] {
                     Value::Array(v) => v.clone(),
-                    _ => return Some(Err("arr_interleave: second arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_interleave: second arg must be array".to_string()))),
                 };
                 let max_len = a.len().max(b.len());
                 let mut result = Vec::new();
@@ -10621,10 +11865,10 @@ impl Interpreter {
                 Some(Ok(Value::Array(result)))
             }
             "arr_partition" => {
-                if args.len() < 2 { return Some(Err("arr_partition requires 2 args: array, n".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "arr_partition requires 2 args: array, n".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(v) => v.clone(),
-                    _ => return Some(Err("arr_partition: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_partition: first arg must be array".to_string()))),
                 };
                 let n = args[1].as_int() as usize;
                 let pass = arr[..n.min(arr.len())].to_vec();
@@ -10635,10 +11879,10 @@ impl Interpreter {
                 Some(Ok(Value::Map(m)))
             }
             "arr_tally" => {
-                if args.is_empty() { return Some(Err("arr_tally requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "arr_tally requires 1 arg".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(v) => v.clone(),
-                    _ => return Some(Err("arr_tally: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_tally: first arg must be array".to_string()))),
                 };
                 let mut counts: std::collections::HashMap<String, i64> = std::collections::HashMap::new();
                 for item in &arr {
@@ -10651,14 +11895,14 @@ impl Interpreter {
                 Some(Ok(Value::Map(m)))
             }
             "arr_combinations" => {
-                if args.len() < 2 { return Some(Err("arr_combinations requires 2 args: array, r".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "arr_combinations requires 2 args: array, r".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(v) => v.clone(),
-                    _ => return Some(Err("arr_combinations: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_combinations: first arg must be array".to_string()))),
                 };
                 let r = args[1].as_int() as usize;
-                if arr.len() > 20 { return Some(Err("arr_combinations: array too large (max 20)".to_string())); }
-                if r > 4 { return Some(Err("arr_combinations: r too large (max 4)".to_string())); }
+                if arr.len() > 20 { return Some(Err(raise(ErrorKind::Error, "arr_combinations: array too large (max 20)".to_string()))); }
+                if r > 4 { return Some(Err(raise(ErrorKind::Error, "arr_combinations: r too large (max 4)".to_string()))); }
                 fn combos(arr: &[Value], r: usize) -> Vec<Value> {
                     if r == 0 { return vec![Value::Array(vec![])]; }
                     if arr.len() < r { return vec![]; }
@@ -10677,12 +11921,12 @@ impl Interpreter {
                 Some(Ok(Value::Array(combos(&arr, r))))
             }
             "arr_permutations" => {
-                if args.is_empty() { return Some(Err("arr_permutations requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "arr_permutations requires 1 arg".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(v) => v.clone(),
-                    _ => return Some(Err("arr_permutations: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "arr_permutations: first arg must be array".to_string()))),
                 };
-                if arr.len() > 8 { return Some(Err("arr_permutations: array too large (max 8)".to_string())); }
+                if arr.len() > 8 { return Some(Err(raise(ErrorKind::Error, "arr_permutations: array too large (max 8)".to_string()))); }
                 fn perms(arr: &[Value]) -> Vec<Value> {
                     if arr.is_empty() { return vec![Value::Array(vec![])]; }
                     let mut result = Vec::new();
@@ -10702,10 +11946,10 @@ impl Interpreter {
                 Some(Ok(Value::Array(perms(&arr))))
             }
             "map_invert" => {
-                if args.is_empty() { return Some(Err("map_invert requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "map_invert requires 1 arg".to_string()))); }
                 let map = match &args[0] {
                     Value::Map(m) => m.clone(),
-                    _ => return Some(Err("map_invert: first arg must be map".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "map_invert: first arg must be map".to_string()))),
                 };
                 let result: std::collections::HashMap<String, Value> = map.into_iter()
                     .map(|(k, v)| (v.as_string(), Value::String(k)))
@@ -10713,10 +11957,10 @@ impl Interpreter {
                 Some(Ok(Value::Map(result)))
             }
             "map_filter" => {
-                if args.is_empty() { return Some(Err("map_filter requires at least 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "map_filter requires at least 1 arg".to_string()))); }
                 let map = match &args[0] {
                     Value::Map(m) => m.clone(),
-                    _ => return Some(Err("map_filter: first arg must be map".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "map_filter: first arg must be map".to_string()))),
                 };
                 let prefix = if args.len() > 1 { args[1].as_string() } else { String::new() };
                 let result: std::collections::HashMap<String, Value> = map.into_iter()
@@ -10725,18 +11969,18 @@ impl Interpreter {
                 Some(Ok(Value::Map(result)))
             }
             "map_count" => {
-                if args.is_empty() { return Some(Err("map_count requires 1 arg".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "map_count requires 1 arg".to_string()))); }
                 let count = match &args[0] {
                     Value::Map(m) => m.len() as i64,
-                    _ => return Some(Err("map_count: first arg must be map".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "map_count: first arg must be map".to_string()))),
                 };
                 Some(Ok(Value::Int(count)))
             }
             "map_update" | "map_set" => {
-                if args.len() < 3 { return Some(Err("map_update requires 3 args: map, key, value".to_string())); }
+                if args.len() < 3 { return Some(Err(raise(ErrorKind::TypeError, "map_update requires 3 args: map, key, value".to_string()))); }
                 let mut map = match &args[0] {
                     Value::Map(m) => m.clone(),
-                    _ => return Some(Err("map_update: first arg must be map".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "map_update: first arg must be map".to_string()))),
                 };
                 let key = args[1].as_string();
                 let val = args[2].clone();
@@ -10744,10 +11988,10 @@ impl Interpreter {
                 Some(Ok(Value::Map(map)))
             }
             "map_get_or" => {
-                if args.len() < 3 { return Some(Err("map_get_or requires 3 args: map, key, default".to_string())); }
+                if args.len() < 3 { return Some(Err(raise(ErrorKind::TypeError, "map_get_or requires 3 args: map, key, default".to_string()))); }
                 let map = match &args[0] {
                     Value::Map(m) => m.clone(),
-                    _ => return Some(Err("map_get_or: first arg must be map".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "map_get_or: first arg must be map".to_string()))),
                 };
                 let key = args[1].as_string();
                 let default = args[2].clone();
@@ -10769,13 +12013,13 @@ impl Interpreter {
                 let bytes: Vec<u8> = if let Value::Array(arr) = &args[0] {
                     arr.iter().map(|v| v.as_int() as u8).collect()
                 } else {
-                    return Some(Err("lz4_decompress: expected array of ints".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "lz4_decompress: expected array of ints".to_string())));
                 };
                 match lz4_flex::decompress_size_prepended(&bytes) {
                     Ok(decompressed) => Some(Ok(Value::String(
                         String::from_utf8_lossy(&decompressed).to_string(),
                     ))),
-                    Err(e) => Some(Err(format!("lz4_decompress error: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::Error, format!("lz4_decompress error: {}", e)))),
                 }
             }
             "lz4_compress_hex" => {
@@ -10787,13 +12031,13 @@ impl Interpreter {
                 let s = args[0].as_string();
                 let bytes = match he2b(&s) {
                     Some(b) => b,
-                    None => return Some(Err("lz4_decompress_hex: invalid hex string".to_string())),
+                    None => return Some(Err(raise(ErrorKind::ValueError, "lz4_decompress_hex: invalid hex string".to_string()))),
                 };
                 match lz4_flex::decompress_size_prepended(&bytes) {
                     Ok(decompressed) => Some(Ok(Value::String(
                         String::from_utf8_lossy(&decompressed).to_string(),
                     ))),
-                    Err(e) => Some(Err(format!("lz4_decompress_hex error: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::Error, format!("lz4_decompress_hex error: {}", e)))),
                 }
             }
             "zstd_compress" => {
@@ -10803,20 +12047,20 @@ impl Interpreter {
                     Ok(compressed) => Some(Ok(Value::Array(
                         compressed.into_iter().map(|b| Value::Int(b as i64)).collect(),
                     ))),
-                    Err(e) => Some(Err(format!("zstd_compress error: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, format!("zstd_compress error: {}", e)))),
                 }
             }
             "zstd_decompress" => {
                 let bytes: Vec<u8> = if let Value::Array(arr) = &args[0] {
                     arr.iter().map(|v| v.as_int() as u8).collect()
                 } else {
-                    return Some(Err("zstd_decompress: expected array of ints".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "zstd_decompress: expected array of ints".to_string())));
                 };
                 match zstd::decode_all(std::io::Cursor::new(bytes)) {
                     Ok(decompressed) => Some(Ok(Value::String(
                         String::from_utf8_lossy(&decompressed).to_string(),
                     ))),
-                    Err(e) => Some(Err(format!("zstd_decompress error: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("zstd_decompress error: {}", e)))),
                 }
             }
             "zstd_compress_hex" => {
@@ -10824,20 +12068,20 @@ impl Interpreter {
                 let level = if args.len() > 1 { args[1].as_int() } else { 3 };
                 match zstd::encode_all(std::io::Cursor::new(data), level as i32) {
                     Ok(compressed) => Some(Ok(Value::String(b2he(&compressed)))),
-                    Err(e) => Some(Err(format!("zstd_compress_hex error: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, format!("zstd_compress_hex error: {}", e)))),
                 }
             }
             "zstd_decompress_hex" => {
                 let s = args[0].as_string();
                 let bytes = match he2b(&s) {
                     Some(b) => b,
-                    None => return Some(Err("zstd_decompress_hex: invalid hex string".to_string())),
+                    None => return Some(Err(raise(ErrorKind::ValueError, "zstd_decompress_hex: invalid hex string".to_string()))),
                 };
                 match zstd::decode_all(std::io::Cursor::new(bytes)) {
                     Ok(decompressed) => Some(Ok(Value::String(
                         String::from_utf8_lossy(&decompressed).to_string(),
                     ))),
-                    Err(e) => Some(Err(format!("zstd_decompress_hex error: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("zstd_decompress_hex error: {}", e)))),
                 }
             }
             "csv_parse" => {
@@ -10855,7 +12099,7 @@ impl Interpreter {
                                 .collect();
                             rows.push(Value::Array(row));
                         }
-                        Err(e) => return Some(Err(format!("csv_parse error: {}", e))),
+                        Err(e) => return Some(Err(raise(ErrorKind::ValueError, format!("csv_parse error: {}", e)))),
                     }
                 }
                 Some(Ok(Value::Array(rows)))
@@ -10867,7 +12111,7 @@ impl Interpreter {
                     .from_reader(s.as_bytes());
                 let headers: Vec<String> = match rdr.headers() {
                     Ok(h) => h.iter().map(|f| f.to_string()).collect(),
-                    Err(e) => return Some(Err(format!("csv_parse_maps header error: {}", e))),
+                    Err(e) => return Some(Err(raise(ErrorKind::ValueError, format!("csv_parse_maps header error: {}", e)))),
                 };
                 let mut rows = Vec::new();
                 for result in rdr.records() {
@@ -10883,7 +12127,7 @@ impl Interpreter {
                             }
                             rows.push(Value::Map(map));
                         }
-                        Err(e) => return Some(Err(format!("csv_parse_maps error: {}", e))),
+                        Err(e) => return Some(Err(raise(ErrorKind::ValueError, format!("csv_parse_maps error: {}", e)))),
                     }
                 }
                 Some(Ok(Value::Array(rows)))
@@ -10892,7 +12136,7 @@ impl Interpreter {
                 let rows = if let Value::Array(arr) = &args[0] {
                     arr
                 } else {
-                    return Some(Err("csv_stringify: expected array of arrays".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "csv_stringify: expected array of arrays".to_string())));
                 };
                 let mut wtr = csv_crate::WriterBuilder::new().from_writer(vec![]);
                 for row in rows {
@@ -10900,7 +12144,7 @@ impl Interpreter {
                         let record: Vec<String> =
                             fields.iter().map(|f| f.as_string()).collect();
                         if let Err(e) = wtr.write_record(&record) {
-                            return Some(Err(format!("csv_stringify write error: {}", e)));
+                            return Some(Err(raise(ErrorKind::Error, format!("csv_stringify write error: {}", e))));
                         }
                     }
                 }
@@ -10908,14 +12152,14 @@ impl Interpreter {
                     Ok(bytes) => Some(Ok(Value::String(
                         String::from_utf8_lossy(&bytes).to_string(),
                     ))),
-                    Err(e) => Some(Err(format!("csv_stringify flush error: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::Error, format!("csv_stringify flush error: {}", e)))),
                 }
             }
             "csv_from_maps" => {
                 let rows = if let Value::Array(arr) = &args[0] {
                     arr
                 } else {
-                    return Some(Err("csv_from_maps: expected array of maps".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "csv_from_maps: expected array of maps".to_string())));
                 };
                 let headers: Vec<String> = if let Some(Value::Map(first)) = rows.first() {
                     let mut keys: Vec<String> = first.keys().cloned().collect();
@@ -10926,7 +12170,7 @@ impl Interpreter {
                 };
                 let mut wtr = csv_crate::WriterBuilder::new().from_writer(vec![]);
                 if let Err(e) = wtr.write_record(&headers) {
-                    return Some(Err(format!("csv_from_maps header write error: {}", e)));
+                    return Some(Err(raise(ErrorKind::Error, format!("csv_from_maps header write error: {}", e))));
                 }
                 for row in rows {
                     if let Value::Map(map) = row {
@@ -10935,7 +12179,7 @@ impl Interpreter {
                             .map(|k| map.get(k).map(|v| v.as_string()).unwrap_or_default())
                             .collect();
                         if let Err(e) = wtr.write_record(&record) {
-                            return Some(Err(format!("csv_from_maps write error: {}", e)));
+                            return Some(Err(raise(ErrorKind::Error, format!("csv_from_maps write error: {}", e))));
                         }
                     }
                 }
@@ -10943,7 +12187,7 @@ impl Interpreter {
                     Ok(bytes) => Some(Ok(Value::String(
                         String::from_utf8_lossy(&bytes).to_string(),
                     ))),
-                    Err(e) => Some(Err(format!("csv_from_maps flush error: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::Error, format!("csv_from_maps flush error: {}", e)))),
                 }
             }
             "xml_parse" => {
@@ -11027,7 +12271,7 @@ impl Interpreter {
                             }
                         }
                         Ok(Event::Eof) => break,
-                        Err(e) => return Some(Err(format!("xml_parse error: {}", e))),
+                        Err(e) => return Some(Err(raise(ErrorKind::ValueError, format!("xml_parse error: {}", e)))),
                         _ => {}
                     }
                     buf.clear();
@@ -11119,7 +12363,7 @@ impl Interpreter {
                 }
                 match serde_yaml::from_str::<serde_json::Value>(&s) {
                     Ok(v) => Some(Ok(sjv_to_val(v))),
-                    Err(e) => Some(Err(format!("yaml_parse error: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("yaml_parse error: {}", e)))),
                 }
             }
             "yaml_stringify" => {
@@ -11148,7 +12392,7 @@ impl Interpreter {
                 let jv = val_to_sjv(&args[0]);
                 match serde_yaml::to_string(&jv) {
                     Ok(s) => Some(Ok(Value::String(s))),
-                    Err(e) => Some(Err(format!("yaml_stringify error: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("yaml_stringify error: {}", e)))),
                 }
             }
             "yaml_to_json" => {
@@ -11156,9 +12400,9 @@ impl Interpreter {
                 match serde_yaml::from_str::<serde_json::Value>(&s) {
                     Ok(v) => match serde_json::to_string(&v) {
                         Ok(j) => Some(Ok(Value::String(j))),
-                        Err(e) => Some(Err(format!("yaml_to_json serialize error: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("yaml_to_json serialize error: {}", e)))),
                     },
-                    Err(e) => Some(Err(format!("yaml_to_json parse error: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("yaml_to_json parse error: {}", e)))),
                 }
             }
             "json_to_yaml" => {
@@ -11166,9 +12410,9 @@ impl Interpreter {
                 match serde_json::from_str::<serde_json::Value>(&s) {
                     Ok(v) => match serde_yaml::to_string(&v) {
                         Ok(y) => Some(Ok(Value::String(y))),
-                        Err(e) => Some(Err(format!("json_to_yaml serialize error: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("json_to_yaml serialize error: {}", e)))),
                     },
-                    Err(e) => Some(Err(format!("json_to_yaml parse error: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("json_to_yaml parse error: {}", e)))),
                 }
             }
             "chan_create" => {
@@ -11192,10 +12436,10 @@ impl Interpreter {
                 if let Some(s) = self.chan_senders.get(&id) {
                     match s.send(val) {
                         Ok(_) => Some(Ok(Value::Bool(true))),
-                        Err(e) => Some(Err(format!("chan_send error: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::Error, format!("chan_send error: {}", e)))),
                     }
                 } else {
-                    Some(Err(format!("chan_send: unknown channel id {}", id)))
+                    Some(Err(raise(ErrorKind::ValueError, format!("chan_send: unknown channel id {}", id))))
                 }
             }
             "chan_recv" => {
@@ -11206,7 +12450,7 @@ impl Interpreter {
                         Err(_) => Some(Ok(Value::Null)),
                     }
                 } else {
-                    Some(Err(format!("chan_recv: unknown channel id {}", id)))
+                    Some(Err(raise(ErrorKind::ValueError, format!("chan_recv: unknown channel id {}", id))))
                 }
             }
             "chan_try_recv" => {
@@ -11217,7 +12461,7 @@ impl Interpreter {
                         Err(_) => Some(Ok(Value::Null)),
                     }
                 } else {
-                    Some(Err(format!("chan_try_recv: unknown channel id {}", id)))
+                    Some(Err(raise(ErrorKind::ValueError, format!("chan_try_recv: unknown channel id {}", id))))
                 }
             }
             "chan_close" => {
@@ -11230,7 +12474,7 @@ impl Interpreter {
                 if let Some(s) = self.chan_senders.get(&id) {
                     Some(Ok(Value::Int(s.len() as i64)))
                 } else {
-                    Some(Err(format!("chan_len: unknown channel id {}", id)))
+                    Some(Err(raise(ErrorKind::ValueError, format!("chan_len: unknown channel id {}", id))))
                 }
             }
             "atomic_new" => {
@@ -11245,7 +12489,7 @@ impl Interpreter {
                 if let Some(a) = self.atomics.get(&id) {
                     Some(Ok(Value::Int(a.load(Ordering::SeqCst))))
                 } else {
-                    Some(Err(format!("atomic_load: unknown atomic id {}", id)))
+                    Some(Err(raise(ErrorKind::ValueError, format!("atomic_load: unknown atomic id {}", id))))
                 }
             }
             "atomic_store" => {
@@ -11255,7 +12499,7 @@ impl Interpreter {
                     a.store(val, Ordering::SeqCst);
                     Some(Ok(Value::Bool(true)))
                 } else {
-                    Some(Err(format!("atomic_store: unknown atomic id {}", id)))
+                    Some(Err(raise(ErrorKind::ValueError, format!("atomic_store: unknown atomic id {}", id))))
                 }
             }
             "atomic_add" => {
@@ -11264,7 +12508,7 @@ impl Interpreter {
                 if let Some(a) = self.atomics.get(&id) {
                     Some(Ok(Value::Int(a.fetch_add(delta, Ordering::SeqCst))))
                 } else {
-                    Some(Err(format!("atomic_add: unknown atomic id {}", id)))
+                    Some(Err(raise(ErrorKind::ValueError, format!("atomic_add: unknown atomic id {}", id))))
                 }
             }
             "atomic_sub" => {
@@ -11273,7 +12517,7 @@ impl Interpreter {
                 if let Some(a) = self.atomics.get(&id) {
                     Some(Ok(Value::Int(a.fetch_sub(delta, Ordering::SeqCst))))
                 } else {
-                    Some(Err(format!("atomic_sub: unknown atomic id {}", id)))
+                    Some(Err(raise(ErrorKind::ValueError, format!("atomic_sub: unknown atomic id {}", id))))
                 }
             }
             "atomic_cas" | "atomic_compare_exchange" => {
@@ -11291,7 +12535,7 @@ impl Interpreter {
                         .is_ok();
                     Some(Ok(Value::Bool(ok)))
                 } else {
-                    Some(Err(format!("atomic_cas: unknown atomic id {}", id)))
+                    Some(Err(raise(ErrorKind::ValueError, format!("atomic_cas: unknown atomic id {}", id))))
                 }
             }
             "atomic_swap" => {
@@ -11300,12 +12544,12 @@ impl Interpreter {
                 if let Some(a) = self.atomics.get(&id) {
                     Some(Ok(Value::Int(a.swap(new_val, Ordering::SeqCst))))
                 } else {
-                    Some(Err(format!("atomic_swap: unknown atomic id {}", id)))
+                    Some(Err(raise(ErrorKind::ValueError, format!("atomic_swap: unknown atomic id {}", id))))
                 }
             }
 
             "img_load" => {
-                if args.is_empty() { return Some(Err("img_load: expected path".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "img_load: expected path".to_string()))); }
                 let path = args[0].as_string();
                 match img_crate::open(&path) {
                     Ok(img) => {
@@ -11314,23 +12558,23 @@ impl Interpreter {
                         self.img_counter += 1;
                         Some(Ok(Value::Int(id)))
                     }
-                    Err(e) => Some(Err(format!("img_load: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::Error, format!("img_load: {}", e)))),
                 }
             }
             "img_save" => {
-                if args.len() < 2 { return Some(Err("img_save: expected id, path".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "img_save: expected id, path".to_string()))); }
                 let id = args[0].as_int();
                 let path = args[1].as_string();
                 match self.images.get(&id) {
                     Some(img) => match img.save(&path) {
                         Ok(_) => Some(Ok(Value::Bool(true))),
-                        Err(e) => Some(Err(format!("img_save: {}", e))),
+                        Err(e) => Some(Err(raise(ErrorKind::Error, format!("img_save: {}", e)))),
                     },
-                    None => Some(Err(format!("img_save: no image with id {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("img_save: no image with id {}", id)))),
                 }
             }
             "img_new" => {
-                if args.len() < 5 { return Some(Err("img_new: expected w,h,r,g,b".to_string())); }
+                if args.len() < 5 { return Some(Err(raise(ErrorKind::TypeError, "img_new: expected w,h,r,g,b".to_string()))); }
                 let w = args[0].as_int() as u32;
                 let h = args[1].as_int() as u32;
                 let r = args[2].as_int() as u8;
@@ -11345,23 +12589,23 @@ impl Interpreter {
                 Some(Ok(Value::Int(id)))
             }
             "img_width" => {
-                if args.is_empty() { return Some(Err("img_width: expected id".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "img_width: expected id".to_string()))); }
                 let id = args[0].as_int();
                 match self.images.get(&id) {
                     Some(img) => Some(Ok(Value::Int(img.width() as i64))),
-                    None => Some(Err(format!("img_width: no image with id {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("img_width: no image with id {}", id)))),
                 }
             }
             "img_height" => {
-                if args.is_empty() { return Some(Err("img_height: expected id".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "img_height: expected id".to_string()))); }
                 let id = args[0].as_int();
                 match self.images.get(&id) {
                     Some(img) => Some(Ok(Value::Int(img.height() as i64))),
-                    None => Some(Err(format!("img_height: no image with id {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("img_height: no image with id {}", id)))),
                 }
             }
             "img_resize" => {
-                if args.len() < 3 { return Some(Err("img_resize: expected id,w,h".to_string())); }
+                if args.len() < 3 { return Some(Err(raise(ErrorKind::TypeError, "img_resize: expected id,w,h".to_string()))); }
                 let id = args[0].as_int();
                 let w = args[1].as_int() as u32;
                 let h = args[2].as_int() as u32;
@@ -11373,11 +12617,11 @@ impl Interpreter {
                         self.img_counter += 1;
                         Some(Ok(Value::Int(new_id)))
                     }
-                    None => Some(Err(format!("img_resize: no image with id {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("img_resize: no image with id {}", id)))),
                 }
             }
             "img_crop" => {
-                if args.len() < 5 { return Some(Err("img_crop: expected id,x,y,w,h".to_string())); }
+                if args.len() < 5 { return Some(Err(raise(ErrorKind::TypeError, "img_crop: expected id,x,y,w,h".to_string()))); }
                 let id = args[0].as_int();
                 let x = args[1].as_int() as u32;
                 let y = args[2].as_int() as u32;
@@ -11391,11 +12635,11 @@ impl Interpreter {
                         self.img_counter += 1;
                         Some(Ok(Value::Int(new_id)))
                     }
-                    None => Some(Err(format!("img_crop: no image with id {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("img_crop: no image with id {}", id)))),
                 }
             }
             "img_rotate90" => {
-                if args.is_empty() { return Some(Err("img_rotate90: expected id".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "img_rotate90: expected id".to_string()))); }
                 let id = args[0].as_int();
                 match self.images.get(&id).cloned() {
                     Some(img) => {
@@ -11405,11 +12649,11 @@ impl Interpreter {
                         self.img_counter += 1;
                         Some(Ok(Value::Int(new_id)))
                     }
-                    None => Some(Err(format!("img_rotate90: no image with id {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("img_rotate90: no image with id {}", id)))),
                 }
             }
             "img_rotate180" => {
-                if args.is_empty() { return Some(Err("img_rotate180: expected id".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "img_rotate180: expected id".to_string()))); }
                 let id = args[0].as_int();
                 match self.images.get(&id).cloned() {
                     Some(img) => {
@@ -11419,11 +12663,11 @@ impl Interpreter {
                         self.img_counter += 1;
                         Some(Ok(Value::Int(new_id)))
                     }
-                    None => Some(Err(format!("img_rotate180: no image with id {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("img_rotate180: no image with id {}", id)))),
                 }
             }
             "img_rotate270" => {
-                if args.is_empty() { return Some(Err("img_rotate270: expected id".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "img_rotate270: expected id".to_string()))); }
                 let id = args[0].as_int();
                 match self.images.get(&id).cloned() {
                     Some(img) => {
@@ -11433,11 +12677,11 @@ impl Interpreter {
                         self.img_counter += 1;
                         Some(Ok(Value::Int(new_id)))
                     }
-                    None => Some(Err(format!("img_rotate270: no image with id {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("img_rotate270: no image with id {}", id)))),
                 }
             }
             "img_fliph" => {
-                if args.is_empty() { return Some(Err("img_fliph: expected id".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "img_fliph: expected id".to_string()))); }
                 let id = args[0].as_int();
                 match self.images.get(&id).cloned() {
                     Some(img) => {
@@ -11447,11 +12691,11 @@ impl Interpreter {
                         self.img_counter += 1;
                         Some(Ok(Value::Int(new_id)))
                     }
-                    None => Some(Err(format!("img_fliph: no image with id {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("img_fliph: no image with id {}", id)))),
                 }
             }
             "img_flipv" => {
-                if args.is_empty() { return Some(Err("img_flipv: expected id".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "img_flipv: expected id".to_string()))); }
                 let id = args[0].as_int();
                 match self.images.get(&id).cloned() {
                     Some(img) => {
@@ -11461,11 +12705,11 @@ impl Interpreter {
                         self.img_counter += 1;
                         Some(Ok(Value::Int(new_id)))
                     }
-                    None => Some(Err(format!("img_flipv: no image with id {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("img_flipv: no image with id {}", id)))),
                 }
             }
             "img_grayscale" => {
-                if args.is_empty() { return Some(Err("img_grayscale: expected id".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "img_grayscale: expected id".to_string()))); }
                 let id = args[0].as_int();
                 match self.images.get(&id).cloned() {
                     Some(img) => {
@@ -11475,11 +12719,11 @@ impl Interpreter {
                         self.img_counter += 1;
                         Some(Ok(Value::Int(new_id)))
                     }
-                    None => Some(Err(format!("img_grayscale: no image with id {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("img_grayscale: no image with id {}", id)))),
                 }
             }
             "img_blur" | "img_gaussian_blur" => {
-                if args.len() < 2 { return Some(Err("img_blur: expected id, sigma".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "img_blur: expected id, sigma".to_string()))); }
                 let id = args[0].as_int();
                 let sigma = args[1].as_float() as f32;
                 match self.images.get(&id).cloned() {
@@ -11490,11 +12734,11 @@ impl Interpreter {
                         self.img_counter += 1;
                         Some(Ok(Value::Int(new_id)))
                     }
-                    None => Some(Err(format!("img_blur: no image with id {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("img_blur: no image with id {}", id)))),
                 }
             }
             "img_brighten" => {
-                if args.len() < 2 { return Some(Err("img_brighten: expected id, val".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "img_brighten: expected id, val".to_string()))); }
                 let id = args[0].as_int();
                 let val = args[1].as_int() as i32;
                 match self.images.get(&id).cloned() {
@@ -11505,11 +12749,11 @@ impl Interpreter {
                         self.img_counter += 1;
                         Some(Ok(Value::Int(new_id)))
                     }
-                    None => Some(Err(format!("img_brighten: no image with id {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("img_brighten: no image with id {}", id)))),
                 }
             }
             "img_contrast" => {
-                if args.len() < 2 { return Some(Err("img_contrast: expected id, c".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "img_contrast: expected id, c".to_string()))); }
                 let id = args[0].as_int();
                 let c = args[1].as_float() as f32;
                 match self.images.get(&id).cloned() {
@@ -11520,11 +12764,11 @@ impl Interpreter {
                         self.img_counter += 1;
                         Some(Ok(Value::Int(new_id)))
                     }
-                    None => Some(Err(format!("img_contrast: no image with id {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("img_contrast: no image with id {}", id)))),
                 }
             }
             "img_get_pixel" => {
-                if args.len() < 3 { return Some(Err("img_get_pixel: expected id,x,y".to_string())); }
+                if args.len() < 3 { return Some(Err(raise(ErrorKind::TypeError, "img_get_pixel: expected id,x,y".to_string()))); }
                 let id = args[0].as_int();
                 let x = args[1].as_int() as u32;
                 let y = args[2].as_int() as u32;
@@ -11540,11 +12784,11 @@ impl Interpreter {
                         map.insert("a".to_string(), Value::Int(a as i64));
                         Some(Ok(Value::Map(map)))
                     }
-                    None => Some(Err(format!("img_get_pixel: no image with id {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("img_get_pixel: no image with id {}", id)))),
                 }
             }
             "img_set_pixel" => {
-                if args.len() < 6 { return Some(Err("img_set_pixel: expected id,x,y,r,g,b".to_string())); }
+                if args.len() < 6 { return Some(Err(raise(ErrorKind::TypeError, "img_set_pixel: expected id,x,y,r,g,b".to_string()))); }
                 let id = args[0].as_int();
                 let x = args[1].as_int() as u32;
                 let y = args[2].as_int() as u32;
@@ -11557,11 +12801,11 @@ impl Interpreter {
                         img.put_pixel(x, y, img_crate::Rgba([r, g, b, 255u8]));
                         Some(Ok(Value::Bool(true)))
                     }
-                    None => Some(Err(format!("img_set_pixel: no image with id {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("img_set_pixel: no image with id {}", id)))),
                 }
             }
             "img_to_bytes" => {
-                if args.is_empty() { return Some(Err("img_to_bytes: expected id".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "img_to_bytes: expected id".to_string()))); }
                 let id = args[0].as_int();
                 match self.images.get(&id) {
                     Some(img) => {
@@ -11569,11 +12813,11 @@ impl Interpreter {
                         let arr = bytes.into_iter().map(|b| Value::Int(b as i64)).collect();
                         Some(Ok(Value::Array(arr)))
                     }
-                    None => Some(Err(format!("img_to_bytes: no image with id {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("img_to_bytes: no image with id {}", id)))),
                 }
             }
             "img_info" => {
-                if args.is_empty() { return Some(Err("img_info: expected id".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "img_info: expected id".to_string()))); }
                 let id = args[0].as_int();
                 match self.images.get(&id) {
                     Some(img) => {
@@ -11583,11 +12827,11 @@ impl Interpreter {
                         map.insert("format".to_string(), Value::String("rgba8".to_string()));
                         Some(Ok(Value::Map(map)))
                     }
-                    None => Some(Err(format!("img_info: no image with id {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("img_info: no image with id {}", id)))),
                 }
             }
             "img_thumbnail" => {
-                if args.len() < 3 { return Some(Err("img_thumbnail: expected id,max_w,max_h".to_string())); }
+                if args.len() < 3 { return Some(Err(raise(ErrorKind::TypeError, "img_thumbnail: expected id,max_w,max_h".to_string()))); }
                 let id = args[0].as_int();
                 let max_w = args[1].as_int() as u32;
                 let max_h = args[2].as_int() as u32;
@@ -11599,22 +12843,22 @@ impl Interpreter {
                         self.img_counter += 1;
                         Some(Ok(Value::Int(new_id)))
                     }
-                    None => Some(Err(format!("img_thumbnail: no image with id {}", id))),
+                    None => Some(Err(raise(ErrorKind::Error, format!("img_thumbnail: no image with id {}", id)))),
                 }
             }
             "img_overlay" => {
-                if args.len() < 4 { return Some(Err("img_overlay: expected base_id,overlay_id,x,y".to_string())); }
+                if args.len() < 4 { return Some(Err(raise(ErrorKind::TypeError, "img_overlay: expected base_id,overlay_id,x,y".to_string()))); }
                 let base_id = args[0].as_int();
                 let overlay_id = args[1].as_int();
                 let x = args[2].as_int();
                 let y = args[3].as_int();
                 let base_clone = match self.images.get(&base_id) {
                     Some(img) => img.clone(),
-                    None => return Some(Err(format!("img_overlay: no base image with id {}", base_id))),
+                    None => return Some(Err(raise(ErrorKind::Error, format!("img_overlay: no base image with id {}", base_id)))),
                 };
                 let overlay_clone = match self.images.get(&overlay_id) {
                     Some(img) => img.clone(),
-                    None => return Some(Err(format!("img_overlay: no overlay image with id {}", overlay_id))),
+                    None => return Some(Err(raise(ErrorKind::Error, format!("img_overlay: no overlay image with id {}", overlay_id)))),
                 };
                 let mut base = base_clone;
                 img_crate::imageops::overlay(&mut base, &overlay_clone, x as i64, y as i64);
@@ -11624,7 +12868,7 @@ impl Interpreter {
                 Some(Ok(Value::Int(new_id)))
             }
             "img_free" => {
-                if args.is_empty() { return Some(Err("img_free: expected id".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "img_free: expected id".to_string()))); }
                 let id = args[0].as_int();
                 self.images.remove(&id);
                 Some(Ok(Value::Bool(true)))
@@ -11647,7 +12891,7 @@ impl Interpreter {
                 Some(Ok(Value::Bool(true)))
             }
             "tui_move" | "term_goto" => {
-                if args.len() < 2 { return Some(Err("tui_move: expected col,row".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "tui_move: expected col,row".to_string()))); }
                 let col = args[0].as_int() as u16;
                 let row = args[1].as_int() as u16;
                 let mut stdout = std::io::stdout();
@@ -11655,7 +12899,7 @@ impl Interpreter {
                 Some(Ok(Value::Bool(true)))
             }
             "tui_print" | "term_print" => {
-                if args.is_empty() { return Some(Err("tui_print: expected str".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "tui_print: expected str".to_string()))); }
                 let s = args[0].as_string();
                 use std::io::Write;
                 print!("{}", s);
@@ -11663,7 +12907,7 @@ impl Interpreter {
                 Some(Ok(Value::Bool(true)))
             }
             "tui_color" | "term_color" => {
-                if args.len() < 4 { return Some(Err("tui_color: expected r,g,b,str".to_string())); }
+                if args.len() < 4 { return Some(Err(raise(ErrorKind::TypeError, "tui_color: expected r,g,b,str".to_string()))); }
                 let r = args[0].as_int() as u8;
                 let g = args[1].as_int() as u8;
                 let b = args[2].as_int() as u8;
@@ -11716,7 +12960,7 @@ impl Interpreter {
                         map.insert("shift".to_string(), Value::Bool(false));
                         Some(Ok(Value::Map(map)))
                     }
-                    Err(e) => Some(Err(format!("tui_read_key: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::Error, format!("tui_read_key: {}", e)))),
                 }
             }
             "tui_poll_key" => {
@@ -11767,7 +13011,7 @@ impl Interpreter {
                 Some(Ok(Value::Bool(true)))
             }
             "tui_bold" => {
-                if args.is_empty() { return Some(Err("tui_bold: expected str".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "tui_bold: expected str".to_string()))); }
                 let s = args[0].as_string();
                 use std::io::Write;
                 let mut stdout = std::io::stdout();
@@ -11778,7 +13022,7 @@ impl Interpreter {
                 Some(Ok(Value::Bool(true)))
             }
             "tui_dim" => {
-                if args.is_empty() { return Some(Err("tui_dim: expected str".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "tui_dim: expected str".to_string()))); }
                 let s = args[0].as_string();
                 use std::io::Write;
                 let mut stdout = std::io::stdout();
@@ -11789,7 +13033,7 @@ impl Interpreter {
                 Some(Ok(Value::Bool(true)))
             }
             "tui_italic" => {
-                if args.is_empty() { return Some(Err("tui_italic: expected str".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "tui_italic: expected str".to_string()))); }
                 let s = args[0].as_string();
                 use std::io::Write;
                 let mut stdout = std::io::stdout();
@@ -11800,7 +13044,7 @@ impl Interpreter {
                 Some(Ok(Value::Bool(true)))
             }
             "tui_underline" => {
-                if args.is_empty() { return Some(Err("tui_underline: expected str".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "tui_underline: expected str".to_string()))); }
                 let s = args[0].as_string();
                 use std::io::Write;
                 let mut stdout = std::io::stdout();
@@ -11811,7 +13055,7 @@ impl Interpreter {
                 Some(Ok(Value::Bool(true)))
             }
             "tui_blink" => {
-                if args.is_empty() { return Some(Err("tui_blink: expected str".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "tui_blink: expected str".to_string()))); }
                 let s = args[0].as_string();
                 use std::io::Write;
                 let mut stdout = std::io::stdout();
@@ -11822,7 +13066,7 @@ impl Interpreter {
                 Some(Ok(Value::Bool(true)))
             }
             "tui_bg_color" => {
-                if args.len() < 4 { return Some(Err("tui_bg_color: expected r,g,b,str".to_string())); }
+                if args.len() < 4 { return Some(Err(raise(ErrorKind::TypeError, "tui_bg_color: expected r,g,b,str".to_string()))); }
                 let r = args[0].as_int() as u8;
                 let g = args[1].as_int() as u8;
                 let b = args[2].as_int() as u8;
@@ -11836,7 +13080,7 @@ impl Interpreter {
                 Some(Ok(Value::Bool(true)))
             }
             "tui_box" => {
-                if args.len() < 4 { return Some(Err("tui_box: expected col,row,width,height".to_string())); }
+                if args.len() < 4 { return Some(Err(raise(ErrorKind::TypeError, "tui_box: expected col,row,width,height".to_string()))); }
                 let col = args[0].as_int() as u16;
                 let row = args[1].as_int() as u16;
                 let width = args[2].as_int() as u16;
@@ -11844,7 +13088,7 @@ impl Interpreter {
                 use std::io::Write;
                 let mut stdout = std::io::stdout();
                 if width < 2 || height < 2 {
-                    return Some(Err("tui_box: width and height must be at least 2".to_string()));
+                    return Some(Err(raise(ErrorKind::TypeError, "tui_box: width and height must be at least 2".to_string())));
                 }
                 // Top row
                 execute!(stdout, cursor::MoveTo(col, row)).ok();
@@ -11873,10 +13117,10 @@ impl Interpreter {
                 Some(Ok(Value::Bool(true)))
             }
             "par_map" => {
-                if args.len() < 2 { return Some(Err("par_map: expected array, fname".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "par_map: expected array, fname".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(a) => a.clone(),
-                    _ => return Some(Err("par_map: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "par_map: first arg must be array".to_string()))),
                 };
                 let fname = args[1].as_string();
                 let result: Vec<Value> = arr.into_iter().map(|item| {
@@ -11888,10 +13132,10 @@ impl Interpreter {
                 Some(Ok(Value::Array(result)))
             }
             "par_filter" => {
-                if args.len() < 2 { return Some(Err("par_filter: expected array, fname".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "par_filter: expected array, fname".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(a) => a.clone(),
-                    _ => return Some(Err("par_filter: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "par_filter: first arg must be array".to_string()))),
                 };
                 let fname = args[1].as_string();
                 let result: Vec<Value> = arr.into_iter().filter(|item| {
@@ -11904,10 +13148,10 @@ impl Interpreter {
                 Some(Ok(Value::Array(result)))
             }
             "par_reduce" => {
-                if args.len() < 3 { return Some(Err("par_reduce: expected array, fname, init".to_string())); }
+                if args.len() < 3 { return Some(Err(raise(ErrorKind::TypeError, "par_reduce: expected array, fname, init".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(a) => a.clone(),
-                    _ => return Some(Err("par_reduce: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "par_reduce: first arg must be array".to_string()))),
                 };
                 let fname = args[1].as_string();
                 let init = args[2].clone();
@@ -11920,10 +13164,10 @@ impl Interpreter {
                 Some(Ok(result))
             }
             "par_sum" => {
-                if args.is_empty() { return Some(Err("par_sum: expected array".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "par_sum: expected array".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(a) => a.clone(),
-                    _ => return Some(Err("par_sum: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "par_sum: first arg must be array".to_string()))),
                 };
                 let mut has_float = false;
                 for v in &arr {
@@ -11938,10 +13182,10 @@ impl Interpreter {
                 }
             }
             "par_any" => {
-                if args.len() < 2 { return Some(Err("par_any: expected array, fname".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "par_any: expected array, fname".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(a) => a.clone(),
-                    _ => return Some(Err("par_any: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "par_any: first arg must be array".to_string()))),
                 };
                 let fname = args[1].as_string();
                 let result = arr.into_iter().any(|item| {
@@ -11954,10 +13198,10 @@ impl Interpreter {
                 Some(Ok(Value::Bool(result)))
             }
             "par_all" => {
-                if args.len() < 2 { return Some(Err("par_all: expected array, fname".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "par_all: expected array, fname".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(a) => a.clone(),
-                    _ => return Some(Err("par_all: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "par_all: first arg must be array".to_string()))),
                 };
                 let fname = args[1].as_string();
                 let result = arr.into_iter().all(|item| {
@@ -11970,10 +13214,10 @@ impl Interpreter {
                 Some(Ok(Value::Bool(result)))
             }
             "par_for_each" => {
-                if args.len() < 2 { return Some(Err("par_for_each: expected array, fname".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "par_for_each: expected array, fname".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(a) => a.clone(),
-                    _ => return Some(Err("par_for_each: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "par_for_each: first arg must be array".to_string()))),
                 };
                 let fname = args[1].as_string();
                 for item in arr {
@@ -11982,10 +13226,10 @@ impl Interpreter {
                 Some(Ok(Value::Bool(true)))
             }
             "par_find" => {
-                if args.len() < 2 { return Some(Err("par_find: expected array, fname".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "par_find: expected array, fname".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(a) => a.clone(),
-                    _ => return Some(Err("par_find: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "par_find: first arg must be array".to_string()))),
                 };
                 let fname = args[1].as_string();
                 let result = arr.into_iter().find(|item| {
@@ -11998,10 +13242,10 @@ impl Interpreter {
                 Some(Ok(result.unwrap_or(Value::Null)))
             }
             "par_count" => {
-                if args.len() < 2 { return Some(Err("par_count: expected array, fname".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "par_count: expected array, fname".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(a) => a.clone(),
-                    _ => return Some(Err("par_count: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "par_count: first arg must be array".to_string()))),
                 };
                 let fname = args[1].as_string();
                 let count = arr.into_iter().filter(|item| {
@@ -12014,10 +13258,10 @@ impl Interpreter {
                 Some(Ok(Value::Int(count)))
             }
             "par_flat_map" => {
-                if args.len() < 2 { return Some(Err("par_flat_map: expected array, fname".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "par_flat_map: expected array, fname".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(a) => a.clone(),
-                    _ => return Some(Err("par_flat_map: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "par_flat_map: first arg must be array".to_string()))),
                 };
                 let fname = args[1].as_string();
                 let mut result: Vec<Value> = Vec::new();
@@ -12031,14 +13275,14 @@ impl Interpreter {
                 Some(Ok(Value::Array(result)))
             }
             "par_zip_with" => {
-                if args.len() < 3 { return Some(Err("par_zip_with: expected array, array, fname".to_string())); }
+                if args.len() < 3 { return Some(Err(raise(ErrorKind::TypeError, "par_zip_with: expected array, array, fname".to_string()))); }
                 let arr_a = match &args[0] {
                     Value::Array(a) => a.clone(),
-                    _ => return Some(Err("par_zip_with: first arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "par_zip_with: first arg must be array".to_string()))),
                 };
                 let arr_b = match &args[1] {
                     Value::Array(b) => b.clone(),
-                    _ => return Some(Err("par_zip_with: second arg must be array".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "par_zip_with: second arg must be array".to_string()))),
                 };
                 let fname = args[2].as_string();
                 let result: Vec<Value> = arr_a.into_iter().zip(arr_b.into_iter()).map(|(a, b)| {
@@ -12050,46 +13294,46 @@ impl Interpreter {
                 Some(Ok(Value::Array(result)))
             }
             "str_levenshtein" => {
-                if args.len() < 2 { return Some(Err("str_levenshtein: expected a, b".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "str_levenshtein: expected a, b".to_string()))); }
                 let a = args[0].as_string();
                 let b = args[1].as_string();
                 Some(Ok(Value::Int(strsim::levenshtein(&a, &b) as i64)))
             }
             "str_jaro" => {
-                if args.len() < 2 { return Some(Err("str_jaro: expected a, b".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "str_jaro: expected a, b".to_string()))); }
                 let a = args[0].as_string();
                 let b = args[1].as_string();
                 Some(Ok(Value::Float(strsim::jaro(&a, &b))))
             }
             "str_jaro_winkler" => {
-                if args.len() < 2 { return Some(Err("str_jaro_winkler: expected a, b".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "str_jaro_winkler: expected a, b".to_string()))); }
                 let a = args[0].as_string();
                 let b = args[1].as_string();
                 Some(Ok(Value::Float(strsim::jaro_winkler(&a, &b))))
             }
             "str_hamming" => {
-                if args.len() < 2 { return Some(Err("str_hamming: expected a, b".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "str_hamming: expected a, b".to_string()))); }
                 let a = args[0].as_string();
                 let b = args[1].as_string();
                 match strsim::hamming(&a, &b) {
                     Ok(d) => Some(Ok(Value::Int(d as i64))),
-                    Err(e) => Some(Err(format!("str_hamming: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::Error, format!("str_hamming: {}", e)))),
                 }
             }
             "str_damerau_levenshtein" | "str_osa" => {
-                if args.len() < 2 { return Some(Err("str_damerau_levenshtein: expected a, b".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "str_damerau_levenshtein: expected a, b".to_string()))); }
                 let a = args[0].as_string();
                 let b = args[1].as_string();
                 Some(Ok(Value::Int(strsim::damerau_levenshtein(&a, &b) as i64)))
             }
             "str_sorensen_dice" => {
-                if args.len() < 2 { return Some(Err("str_sorensen_dice: expected a, b".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "str_sorensen_dice: expected a, b".to_string()))); }
                 let a = args[0].as_string();
                 let b = args[1].as_string();
                 Some(Ok(Value::Float(strsim::sorensen_dice(&a, &b))))
             }
             "str_normalize" => {
-                if args.is_empty() { return Some(Err("str_normalize: expected str".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "str_normalize: expected str".to_string()))); }
                 let s = args[0].as_string();
                 let lowered = s.to_lowercase();
                 let trimmed = lowered.trim().to_string();
@@ -12109,34 +13353,34 @@ impl Interpreter {
                 Some(Ok(Value::String(result)))
             }
             "str_word_count" => {
-                if args.is_empty() { return Some(Err("str_word_count: expected str".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "str_word_count: expected str".to_string()))); }
                 let s = args[0].as_string();
                 Some(Ok(Value::Int(s.split_whitespace().count() as i64)))
             }
             "str_line_count" => {
-                if args.is_empty() { return Some(Err("str_line_count: expected str".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "str_line_count: expected str".to_string()))); }
                 let s = args[0].as_string();
                 Some(Ok(Value::Int(s.lines().count() as i64)))
             }
             "str_char_count" => {
-                if args.is_empty() { return Some(Err("str_char_count: expected str".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "str_char_count: expected str".to_string()))); }
                 let s = args[0].as_string();
                 Some(Ok(Value::Int(s.chars().count() as i64)))
             }
             "str_bytes_len" => {
-                if args.is_empty() { return Some(Err("str_bytes_len: expected str".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "str_bytes_len: expected str".to_string()))); }
                 let s = args[0].as_string();
                 Some(Ok(Value::Int(s.len() as i64)))
             }
             "str_is_palindrome" => {
-                if args.is_empty() { return Some(Err("str_is_palindrome: expected str".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "str_is_palindrome: expected str".to_string()))); }
                 let s = args[0].as_string();
                 let chars: Vec<char> = s.chars().collect();
                 let rev: Vec<char> = chars.iter().cloned().rev().collect();
                 Some(Ok(Value::Bool(chars == rev)))
             }
             "str_camel_to_snake" => {
-                if args.is_empty() { return Some(Err("str_camel_to_snake: expected str".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "str_camel_to_snake: expected str".to_string()))); }
                 let s = args[0].as_string();
                 let mut result = String::new();
                 for (i, c) in s.chars().enumerate() {
@@ -12148,7 +13392,7 @@ impl Interpreter {
                 Some(Ok(Value::String(result)))
             }
             "str_snake_to_camel" => {
-                if args.is_empty() { return Some(Err("str_snake_to_camel: expected str".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "str_snake_to_camel: expected str".to_string()))); }
                 let s = args[0].as_string();
                 let mut result = String::new();
                 let mut capitalize_next = false;
@@ -12165,12 +13409,12 @@ impl Interpreter {
                 Some(Ok(Value::String(result)))
             }
             "str_kebab_to_snake" => {
-                if args.is_empty() { return Some(Err("str_kebab_to_snake: expected str".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "str_kebab_to_snake: expected str".to_string()))); }
                 let s = args[0].as_string();
                 Some(Ok(Value::String(s.replace('-', "_"))))
             }
             "str_title_case" => {
-                if args.is_empty() { return Some(Err("str_title_case: expected str".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "str_title_case: expected str".to_string()))); }
                 let s = args[0].as_string();
                 let result = s.split_whitespace().map(|word| {
                     let mut chars = word.chars();
@@ -12182,7 +13426,7 @@ impl Interpreter {
                 Some(Ok(Value::String(result)))
             }
             "str_wrap" => {
-                if args.len() < 2 { return Some(Err("str_wrap: expected str, width".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "str_wrap: expected str, width".to_string()))); }
                 let s = args[0].as_string();
                 let width = args[1].as_int() as usize;
                 if width == 0 { return Some(Ok(Value::String(s))); }
@@ -12210,9 +13454,9 @@ impl Interpreter {
                 Some(Ok(Value::String(lines.join("\n"))))
             }
             "str_truncate" => {
-                if args.is_empty() { return Some(Err("str_truncate: expected str, max_len".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "str_truncate: expected str, max_len".to_string()))); }
                 let s = args[0].as_string();
-                let max_len = if args.len() > 1 { args[1].as_int() as usize } else { return Some(Err("str_truncate: expected max_len".to_string())); };
+                let max_len = if args.len() > 1 { args[1].as_int() as usize } else { return Some(Err(raise(ErrorKind::TypeError, "str_truncate: expected max_len".to_string()))); };
                 let suffix = if args.len() > 2 { args[2].as_string() } else { "...".to_string() };
                 let char_count = s.chars().count();
                 if char_count <= max_len {
@@ -12225,21 +13469,21 @@ impl Interpreter {
                 }
             }
             "str_common_prefix" => {
-                if args.len() < 2 { return Some(Err("str_common_prefix: expected a, b".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "str_common_prefix: expected a, b".to_string()))); }
                 let a = args[0].as_string();
                 let b = args[1].as_string();
                 let prefix: String = a.chars().zip(b.chars()).take_while(|(ca, cb)| ca == cb).map(|(c, _)| c).collect();
                 Some(Ok(Value::String(prefix)))
             }
             "str_common_suffix" => {
-                if args.len() < 2 { return Some(Err("str_common_suffix: expected a, b".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "str_common_suffix: expected a, b".to_string()))); }
                 let a = args[0].as_string();
                 let b = args[1].as_string();
                 let suffix: String = a.chars().rev().zip(b.chars().rev()).take_while(|(ca, cb)| ca == cb).map(|(c, _)| c).collect::<String>().chars().rev().collect();
                 Some(Ok(Value::String(suffix)))
             }
             "str_rot13" => {
-                if args.is_empty() { return Some(Err("str_rot13: expected str".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "str_rot13: expected str".to_string()))); }
                 let s = args[0].as_string();
                 let result: String = s.chars().map(|c| match c {
                     'a'..='m' | 'A'..='M' => (c as u8 + 13) as char,
@@ -12249,7 +13493,7 @@ impl Interpreter {
                 Some(Ok(Value::String(result)))
             }
             "str_caesar" => {
-                if args.len() < 2 { return Some(Err("str_caesar: expected str, shift".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "str_caesar: expected str, shift".to_string()))); }
                 let s = args[0].as_string();
                 let shift = args[1].as_int();
                 let result: String = s.chars().map(|c| {
@@ -12264,16 +13508,16 @@ impl Interpreter {
                 Some(Ok(Value::String(result)))
             }
             "str_expand_tabs" => {
-                if args.is_empty() { return Some(Err("str_expand_tabs: expected str".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "str_expand_tabs: expected str".to_string()))); }
                 let s = args[0].as_string();
                 let n = if args.len() > 1 { args[1].as_int() as usize } else { 4 };
                 Some(Ok(Value::String(s.replace('\t', &" ".repeat(n)))))
             }
             "str_from_chars" => {
-                if args.is_empty() { return Some(Err("str_from_chars: expected array".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "str_from_chars: expected array".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(a) => a.clone(),
-                    _ => return Some(Err("str_from_chars: expected array of codepoints".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "str_from_chars: expected array of codepoints".to_string()))),
                 };
                 let s: String = arr.iter().map(|v| {
                     let cp = v.as_int() as u32;
@@ -12282,43 +13526,43 @@ impl Interpreter {
                 Some(Ok(Value::String(s)))
             }
             "str_to_chars" => {
-                if args.is_empty() { return Some(Err("str_to_chars: expected str".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "str_to_chars: expected str".to_string()))); }
                 let s = args[0].as_string();
                 let arr: Vec<Value> = s.chars().map(|c| Value::Int(c as i64)).collect();
                 Some(Ok(Value::Array(arr)))
             }
             "str_each_line" => {
-                if args.is_empty() { return Some(Err("str_each_line: expected str".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "str_each_line: expected str".to_string()))); }
                 let s = args[0].as_string();
                 let arr: Vec<Value> = s.lines().map(|l| Value::String(l.to_string())).collect();
                 Some(Ok(Value::Array(arr)))
             }
             "str_join_lines" => {
-                if args.is_empty() { return Some(Err("str_join_lines: expected array".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "str_join_lines: expected array".to_string()))); }
                 let arr = match &args[0] {
                     Value::Array(a) => a.clone(),
-                    _ => return Some(Err("str_join_lines: expected array of strings".to_string())),
+                    _ => return Some(Err(raise(ErrorKind::TypeError, "str_join_lines: expected array of strings".to_string()))),
                 };
                 let joined = arr.iter().map(|v| v.as_string()).collect::<Vec<_>>().join("\n");
                 Some(Ok(Value::String(joined)))
             }
             "whois" => {
-                if args.is_empty() { return Some(Err("whois: expected query".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "whois: expected query".to_string()))); }
                 let query = args[0].as_string();
                 use std::io::{Read, Write};
                 match std::net::TcpStream::connect("whois.iana.org:43") {
                     Ok(mut stream) => {
                         let req = format!("{}\r\n", query);
                         if let Err(e) = stream.write_all(req.as_bytes()) {
-                            return Some(Err(format!("whois write: {}", e)));
+                            return Some(Err(raise(ErrorKind::Error, format!("whois write: {}", e))));
                         }
                         let mut response = String::new();
                         match stream.read_to_string(&mut response) {
                             Ok(_) => Some(Ok(Value::String(response))),
-                            Err(e) => Some(Err(format!("whois read: {}", e))),
+                            Err(e) => Some(Err(raise(ErrorKind::IOError, format!("whois read: {}", e)))),
                         }
                     }
-                    Err(e) => Some(Err(format!("whois connect: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::IOError, format!("whois connect: {}", e)))),
                 }
             }
             "net_interfaces" => {
@@ -12345,11 +13589,11 @@ impl Interpreter {
                         }
                         Some(Ok(Value::Array(interfaces)))
                     }
-                    Err(e) => Some(Err(format!("net_interfaces: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::Error, format!("net_interfaces: {}", e)))),
                 }
             }
             "tcp_port_open" | "port_open" => {
-                if args.len() < 2 { return Some(Err("tcp_port_open: expected host, port".to_string())); }
+                if args.len() < 2 { return Some(Err(raise(ErrorKind::TypeError, "tcp_port_open: expected host, port".to_string()))); }
                 let host = args[0].as_string();
                 let port = args[1].as_int();
                 let timeout_ms = if args.len() > 2 { args[2].as_int() as u64 } else { 1000u64 };
@@ -12388,7 +13632,7 @@ impl Interpreter {
                         }
                         Some(Ok(Value::Null))
                     }
-                    Err(e) => Some(Err(format!("net_gateway: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::Error, format!("net_gateway: {}", e)))),
                 }
             }
             "net_arp_table" => {
@@ -12406,7 +13650,7 @@ impl Interpreter {
                         }
                         Some(Ok(Value::Array(entries)))
                     }
-                    Err(e) => Some(Err(format!("net_arp_table: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::Error, format!("net_arp_table: {}", e)))),
                 }
             }
             "net_connections" => {
@@ -12465,11 +13709,11 @@ impl Interpreter {
                         }
                         Some(Ok(Value::Array(connections)))
                     }
-                    Err(e) => Some(Err(format!("net_connections: {}", e))),
+                    Err(e) => Some(Err(raise(ErrorKind::Error, format!("net_connections: {}", e)))),
                 }
             }
             "net_ping" => {
-                if args.is_empty() { return Some(Err("net_ping: expected host".to_string())); }
+                if args.is_empty() { return Some(Err(raise(ErrorKind::TypeError, "net_ping: expected host".to_string()))); }
                 let host = args[0].as_string();
                 use std::net::ToSocketAddrs;
                 let resolvable = format!("{}:0", host).to_socket_addrs().is_ok();
@@ -12480,11 +13724,11 @@ impl Interpreter {
 
 
             // ── KNULL_B9_LINALG ──
-"mat_new" => Some((|| -> Result<Value, String> {
+"mat_new" => Some((|| -> Result<Value, RuntimeError> {
     // Two calling conventions:
     // mat_new([[row0], [row1], ...])      — from nested array
     // mat_new(rows, cols, fill_value)     — fill form
-    if args.is_empty() { return Err("mat_new: need arguments".into()); }
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "mat_new: need arguments")); }
     if let Value::Array(_) = &args[0] {
         // Nested array form — just return as-is (already an array of arrays)
         // Ensure all rows are arrays of floats
@@ -12497,7 +13741,7 @@ impl Interpreter {
         }).collect();
         Ok(Value::Array(rows))
     } else {
-        if args.len() < 3 { return Err("mat_new(rows, cols, fill) requires 3 args".into()); }
+        if args.len() < 3 { return Err(raise(ErrorKind::TypeError, "mat_new(rows, cols, fill) requires 3 args")); }
         let rows = args[0].as_int() as usize;
         let cols = args[1].as_int() as usize;
         let val = args[2].as_float();
@@ -12507,8 +13751,8 @@ impl Interpreter {
     }
 })()),
 
-"mat_identity" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("mat_identity requires 1 arg".into()); }
+"mat_identity" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "mat_identity requires 1 arg")); }
     let n = args[0].as_int() as usize;
     let m = DMatrix::<f64>::identity(n, n);
     let rows: Vec<Value> = (0..n)
@@ -12517,24 +13761,24 @@ impl Interpreter {
     Ok(Value::Array(rows))
 })()),
 
-"mat_add" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("mat_add requires 2 args".into()); }
-    let to_dm = |v: &Value| -> Result<DMatrix<f64>, String> {
+"mat_add" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "mat_add requires 2 args")); }
+    let to_dm = |v: &Value| -> Result<DMatrix<f64>, RuntimeError> {
         if let Value::Array(rows) = v {
             let nrows = rows.len();
-            if nrows == 0 { return Err("Empty matrix".into()); }
-            let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err("Not array of arrays".into()); };
+            if nrows == 0 { return Err(raise(ErrorKind::ValueError, "Empty matrix")); }
+            let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err(raise(ErrorKind::Error, "Not array of arrays")); };
             let mut data = Vec::with_capacity(nrows * ncols);
             for row in rows {
                 if let Value::Array(r) = row { for val in r { data.push(val.as_float()); } }
-                else { return Err("Expected row array".into()); }
+                else { return Err(raise(ErrorKind::TypeError, "Expected row array")); }
             }
             Ok(DMatrix::from_row_slice(nrows, ncols, &data))
-        } else { Err("Expected matrix array".into()) }
+        } else { Err(raise(ErrorKind::TypeError, "Expected matrix array")) }
     };
     let a = to_dm(&args[0])?;
     let b = to_dm(&args[1])?;
-    if a.nrows() != b.nrows() || a.ncols() != b.ncols() { return Err("Matrix dimension mismatch".into()); }
+    if a.nrows() != b.nrows() || a.ncols() != b.ncols() { return Err(raise(ErrorKind::ValueError, "Matrix dimension mismatch")); }
     let c = a + b;
     let rows: Vec<Value> = (0..c.nrows())
         .map(|i| Value::Array((0..c.ncols()).map(|j| Value::Float(c[(i, j)])).collect()))
@@ -12542,24 +13786,24 @@ impl Interpreter {
     Ok(Value::Array(rows))
 })()),
 
-"mat_sub" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("mat_sub requires 2 args".into()); }
-    let to_dm = |v: &Value| -> Result<DMatrix<f64>, String> {
+"mat_sub" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "mat_sub requires 2 args")); }
+    let to_dm = |v: &Value| -> Result<DMatrix<f64>, RuntimeError> {
         if let Value::Array(rows) = v {
             let nrows = rows.len();
-            if nrows == 0 { return Err("Empty matrix".into()); }
-            let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err("Not array of arrays".into()); };
+            if nrows == 0 { return Err(raise(ErrorKind::ValueError, "Empty matrix")); }
+            let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err(raise(ErrorKind::Error, "Not array of arrays")); };
             let mut data = Vec::with_capacity(nrows * ncols);
             for row in rows {
                 if let Value::Array(r) = row { for val in r { data.push(val.as_float()); } }
-                else { return Err("Expected row array".into()); }
+                else { return Err(raise(ErrorKind::TypeError, "Expected row array")); }
             }
             Ok(DMatrix::from_row_slice(nrows, ncols, &data))
-        } else { Err("Expected matrix array".into()) }
+        } else { Err(raise(ErrorKind::TypeError, "Expected matrix array")) }
     };
     let a = to_dm(&args[0])?;
     let b = to_dm(&args[1])?;
-    if a.nrows() != b.nrows() || a.ncols() != b.ncols() { return Err("Matrix dimension mismatch".into()); }
+    if a.nrows() != b.nrows() || a.ncols() != b.ncols() { return Err(raise(ErrorKind::ValueError, "Matrix dimension mismatch")); }
     let c = a - b;
     let rows: Vec<Value> = (0..c.nrows())
         .map(|i| Value::Array((0..c.ncols()).map(|j| Value::Float(c[(i, j)])).collect()))
@@ -12567,24 +13811,24 @@ impl Interpreter {
     Ok(Value::Array(rows))
 })()),
 
-"mat_mul" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("mat_mul requires 2 args".into()); }
-    let to_dm = |v: &Value| -> Result<DMatrix<f64>, String> {
+"mat_mul" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "mat_mul requires 2 args")); }
+    let to_dm = |v: &Value| -> Result<DMatrix<f64>, RuntimeError> {
         if let Value::Array(rows) = v {
             let nrows = rows.len();
-            if nrows == 0 { return Err("Empty matrix".into()); }
-            let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err("Not array of arrays".into()); };
+            if nrows == 0 { return Err(raise(ErrorKind::ValueError, "Empty matrix")); }
+            let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err(raise(ErrorKind::Error, "Not array of arrays")); };
             let mut data = Vec::with_capacity(nrows * ncols);
             for row in rows {
                 if let Value::Array(r) = row { for val in r { data.push(val.as_float()); } }
-                else { return Err("Expected row array".into()); }
+                else { return Err(raise(ErrorKind::TypeError, "Expected row array")); }
             }
             Ok(DMatrix::from_row_slice(nrows, ncols, &data))
-        } else { Err("Expected matrix array".into()) }
+        } else { Err(raise(ErrorKind::TypeError, "Expected matrix array")) }
     };
     let a = to_dm(&args[0])?;
     let b = to_dm(&args[1])?;
-    if a.ncols() != b.nrows() { return Err(format!("Incompatible dimensions {}x{} * {}x{}", a.nrows(), a.ncols(), b.nrows(), b.ncols())); }
+    if a.ncols() != b.nrows() { return Err(raise(ErrorKind::Error, format!("Incompatible dimensions {}x{} * {}x{}", a.nrows(), a.ncols(), b.nrows(), b.ncols()))); }
     let c = a * b;
     let rows: Vec<Value> = (0..c.nrows())
         .map(|i| Value::Array((0..c.ncols()).map(|j| Value::Float(c[(i, j)])).collect()))
@@ -12592,16 +13836,16 @@ impl Interpreter {
     Ok(Value::Array(rows))
 })()),
 
-"mat_transpose" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("mat_transpose requires 1 arg".into()); }
+"mat_transpose" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "mat_transpose requires 1 arg")); }
     if let Value::Array(rows) = &args[0] {
         let nrows = rows.len();
         if nrows == 0 { return Ok(Value::Array(vec![])); }
-        let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err("Not array of arrays".into()); };
+        let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err(raise(ErrorKind::Error, "Not array of arrays")); };
         let mut data = Vec::with_capacity(nrows * ncols);
         for row in rows {
             if let Value::Array(r) = row { for val in r { data.push(val.as_float()); } }
-            else { return Err("Expected row array".into()); }
+            else { return Err(raise(ErrorKind::TypeError, "Expected row array")); }
         }
         let m = DMatrix::from_row_slice(nrows, ncols, &data);
         let t = m.transpose();
@@ -12609,37 +13853,37 @@ impl Interpreter {
             .map(|i| Value::Array((0..t.ncols()).map(|j| Value::Float(t[(i, j)])).collect()))
             .collect();
         Ok(Value::Array(out))
-    } else { Err("Expected matrix array".into()) }
+    } else { Err(raise(ErrorKind::TypeError, "Expected matrix array")) }
 })()),
 
-"mat_det" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("mat_det requires 1 arg".into()); }
+"mat_det" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "mat_det requires 1 arg")); }
     if let Value::Array(rows) = &args[0] {
         let nrows = rows.len();
         if nrows == 0 { return Ok(Value::Float(1.0)); }
-        let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err("Not array of arrays".into()); };
-        if nrows != ncols { return Err("Determinant requires square matrix".into()); }
+        let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err(raise(ErrorKind::Error, "Not array of arrays")); };
+        if nrows != ncols { return Err(raise(ErrorKind::TypeError, "Determinant requires square matrix")); }
         let mut data = Vec::with_capacity(nrows * ncols);
         for row in rows {
             if let Value::Array(r) = row { for val in r { data.push(val.as_float()); } }
-            else { return Err("Expected row array".into()); }
+            else { return Err(raise(ErrorKind::TypeError, "Expected row array")); }
         }
         let m = DMatrix::from_row_slice(nrows, ncols, &data);
         Ok(Value::Float(m.determinant()))
-    } else { Err("Expected matrix array".into()) }
+    } else { Err(raise(ErrorKind::TypeError, "Expected matrix array")) }
 })()),
 
-"mat_inv" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("mat_inv requires 1 arg".into()); }
+"mat_inv" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "mat_inv requires 1 arg")); }
     if let Value::Array(rows) = &args[0] {
         let nrows = rows.len();
-        if nrows == 0 { return Err("Empty matrix".into()); }
-        let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err("Not array of arrays".into()); };
-        if nrows != ncols { return Err("Inverse requires square matrix".into()); }
+        if nrows == 0 { return Err(raise(ErrorKind::ValueError, "Empty matrix")); }
+        let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err(raise(ErrorKind::Error, "Not array of arrays")); };
+        if nrows != ncols { return Err(raise(ErrorKind::TypeError, "Inverse requires square matrix")); }
         let mut data = Vec::with_capacity(nrows * ncols);
         for row in rows {
             if let Value::Array(r) = row { for val in r { data.push(val.as_float()); } }
-            else { return Err("Expected row array".into()); }
+            else { return Err(raise(ErrorKind::TypeError, "Expected row array")); }
         }
         let m = DMatrix::from_row_slice(nrows, ncols, &data);
         match m.try_inverse() {
@@ -12649,17 +13893,17 @@ impl Interpreter {
                     .collect();
                 Ok(Value::Array(out))
             }
-            None => Err("Matrix is singular".into()),
+            None => Err(raise(ErrorKind::Error, "Matrix is singular")),
         }
-    } else { Err("Expected matrix array".into()) }
+    } else { Err(raise(ErrorKind::TypeError, "Expected matrix array")) }
 })()),
 
-"mat_trace" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("mat_trace requires 1 arg".into()); }
+"mat_trace" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "mat_trace requires 1 arg")); }
     if let Value::Array(rows) = &args[0] {
         let nrows = rows.len();
         if nrows == 0 { return Ok(Value::Float(0.0)); }
-        let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err("Not array of arrays".into()); };
+        let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err(raise(ErrorKind::Error, "Not array of arrays")); };
         let n = nrows.min(ncols);
         let mut trace = 0.0f64;
         for i in 0..n {
@@ -12668,58 +13912,58 @@ impl Interpreter {
             }
         }
         Ok(Value::Float(trace))
-    } else { Err("Expected matrix array".into()) }
+    } else { Err(raise(ErrorKind::TypeError, "Expected matrix array")) }
 })()),
 
-"mat_norm" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("mat_norm requires 1 arg".into()); }
+"mat_norm" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "mat_norm requires 1 arg")); }
     if let Value::Array(rows) = &args[0] {
         let nrows = rows.len();
         if nrows == 0 { return Ok(Value::Float(0.0)); }
-        let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err("Not array of arrays".into()); };
+        let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err(raise(ErrorKind::Error, "Not array of arrays")); };
         let mut data = Vec::with_capacity(nrows * ncols);
         for row in rows {
             if let Value::Array(r) = row { for val in r { data.push(val.as_float()); } }
-            else { return Err("Expected row array".into()); }
+            else { return Err(raise(ErrorKind::TypeError, "Expected row array")); }
         }
         let m = DMatrix::from_row_slice(nrows, ncols, &data);
         Ok(Value::Float(m.norm()))
-    } else { Err("Expected matrix array".into()) }
+    } else { Err(raise(ErrorKind::TypeError, "Expected matrix array")) }
 })()),
 
-"mat_rank" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("mat_rank requires 1 arg".into()); }
+"mat_rank" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "mat_rank requires 1 arg")); }
     if let Value::Array(rows) = &args[0] {
         let nrows = rows.len();
         if nrows == 0 { return Ok(Value::Int(0)); }
-        let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err("Not array of arrays".into()); };
+        let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err(raise(ErrorKind::Error, "Not array of arrays")); };
         let mut data = Vec::with_capacity(nrows * ncols);
         for row in rows {
             if let Value::Array(r) = row { for val in r { data.push(val.as_float()); } }
-            else { return Err("Expected row array".into()); }
+            else { return Err(raise(ErrorKind::TypeError, "Expected row array")); }
         }
         let m = DMatrix::from_row_slice(nrows, ncols, &data);
         let svd = nalgebra::linalg::SVD::new(m, false, false);
         let rank = svd.singular_values.iter().filter(|&&s| s > 1e-10).count();
         Ok(Value::Int(rank as i64))
-    } else { Err("Expected matrix array".into()) }
+    } else { Err(raise(ErrorKind::TypeError, "Expected matrix array")) }
 })()),
 
-"mat_svd" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("mat_svd requires 1 arg".into()); }
+"mat_svd" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "mat_svd requires 1 arg")); }
     if let Value::Array(rows) = &args[0] {
         let nrows = rows.len();
-        if nrows == 0 { return Err("Empty matrix".into()); }
-        let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err("Not array of arrays".into()); };
+        if nrows == 0 { return Err(raise(ErrorKind::ValueError, "Empty matrix")); }
+        let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err(raise(ErrorKind::Error, "Not array of arrays")); };
         let mut data = Vec::with_capacity(nrows * ncols);
         for row in rows {
             if let Value::Array(r) = row { for val in r { data.push(val.as_float()); } }
-            else { return Err("Expected row array".into()); }
+            else { return Err(raise(ErrorKind::TypeError, "Expected row array")); }
         }
         let m = DMatrix::from_row_slice(nrows, ncols, &data);
         let svd = nalgebra::linalg::SVD::new(m, true, true);
-        let u_mat = svd.u.ok_or("SVD failed: no U")?;
-        let v_t_mat = svd.v_t.ok_or("SVD failed: no V_t")?;
+        let u_mat = svd.u.ok_or_else(|| raise(ErrorKind::Error, "SVD failed: no U"))?;
+        let v_t_mat = svd.v_t.ok_or_else(|| raise(ErrorKind::Error, "SVD failed: no V_t"))?;
         let s_vec = svd.singular_values;
         let dm_to_val = |m: &DMatrix<f64>| -> Value {
             Value::Array((0..m.nrows())
@@ -12731,19 +13975,19 @@ impl Interpreter {
         result.insert("S".into(), Value::Array(s_vec.iter().map(|&s| Value::Float(s)).collect()));
         result.insert("V".into(), dm_to_val(&v_t_mat.transpose()));
         Ok(Value::Map(result))
-    } else { Err("Expected matrix array".into()) }
+    } else { Err(raise(ErrorKind::TypeError, "Expected matrix array")) }
 })()),
 
-"mat_lu" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("mat_lu requires 1 arg".into()); }
+"mat_lu" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "mat_lu requires 1 arg")); }
     if let Value::Array(rows) = &args[0] {
         let nrows = rows.len();
-        if nrows == 0 { return Err("Empty matrix".into()); }
-        let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err("Not array of arrays".into()); };
+        if nrows == 0 { return Err(raise(ErrorKind::ValueError, "Empty matrix")); }
+        let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err(raise(ErrorKind::Error, "Not array of arrays")); };
         let mut data = Vec::with_capacity(nrows * ncols);
         for row in rows {
             if let Value::Array(r) = row { for val in r { data.push(val.as_float()); } }
-            else { return Err("Expected row array".into()); }
+            else { return Err(raise(ErrorKind::TypeError, "Expected row array")); }
         }
         let m = DMatrix::from_row_slice(nrows, ncols, &data);
         let lu = nalgebra::linalg::LU::new(m);
@@ -12760,19 +14004,19 @@ impl Interpreter {
         result.insert("U".into(), dm_to_val(&u));
         result.insert("P".into(), Value::Null); // P permutation omitted (nalgebra API limitation)
         Ok(Value::Map(result))
-    } else { Err("Expected matrix array".into()) }
+    } else { Err(raise(ErrorKind::TypeError, "Expected matrix array")) }
 })()),
 
-"mat_qr" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("mat_qr requires 1 arg".into()); }
+"mat_qr" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "mat_qr requires 1 arg")); }
     if let Value::Array(rows) = &args[0] {
         let nrows = rows.len();
-        if nrows == 0 { return Err("Empty matrix".into()); }
-        let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err("Not array of arrays".into()); };
+        if nrows == 0 { return Err(raise(ErrorKind::ValueError, "Empty matrix")); }
+        let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err(raise(ErrorKind::Error, "Not array of arrays")); };
         let mut data = Vec::with_capacity(nrows * ncols);
         for row in rows {
             if let Value::Array(r) = row { for val in r { data.push(val.as_float()); } }
-            else { return Err("Expected row array".into()); }
+            else { return Err(raise(ErrorKind::TypeError, "Expected row array")); }
         }
         let m = DMatrix::from_row_slice(nrows, ncols, &data);
         let qr = nalgebra::linalg::QR::new(m);
@@ -12787,20 +14031,20 @@ impl Interpreter {
         result.insert("Q".into(), dm_to_val(&q));
         result.insert("R".into(), dm_to_val(&r));
         Ok(Value::Map(result))
-    } else { Err("Expected matrix array".into()) }
+    } else { Err(raise(ErrorKind::TypeError, "Expected matrix array")) }
 })()),
 
-"mat_eigenvalues" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("mat_eigenvalues requires 1 arg".into()); }
+"mat_eigenvalues" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "mat_eigenvalues requires 1 arg")); }
     if let Value::Array(rows) = &args[0] {
         let nrows = rows.len();
         if nrows == 0 { return Ok(Value::Array(vec![])); }
-        let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err("Not array of arrays".into()); };
-        if nrows != ncols { return Err("Eigenvalues require square matrix".into()); }
+        let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err(raise(ErrorKind::Error, "Not array of arrays")); };
+        if nrows != ncols { return Err(raise(ErrorKind::Error, "Eigenvalues require square matrix")); }
         let mut data = Vec::with_capacity(nrows * ncols);
         for row in rows {
             if let Value::Array(r) = row { for val in r { data.push(val.as_float()); } }
-            else { return Err("Expected row array".into()); }
+            else { return Err(raise(ErrorKind::TypeError, "Expected row array")); }
         }
         let m = DMatrix::from_row_slice(nrows, ncols, &data);
         // Symmetrize then use SymmetricEigen for real eigenvalues
@@ -12809,167 +14053,167 @@ impl Interpreter {
         let mut evals: Vec<f64> = eigen.eigenvalues.iter().copied().collect();
         evals.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
         Ok(Value::Array(evals.into_iter().map(Value::Float).collect()))
-    } else { Err("Expected matrix array".into()) }
+    } else { Err(raise(ErrorKind::TypeError, "Expected matrix array")) }
 })()),
 
-"mat_solve" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("mat_solve requires 2 args (A, b)".into()); }
-    let to_dm = |v: &Value| -> Result<DMatrix<f64>, String> {
+"mat_solve" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "mat_solve requires 2 args (A, b)")); }
+    let to_dm = |v: &Value| -> Result<DMatrix<f64>, RuntimeError> {
         if let Value::Array(rows) = v {
             let nrows = rows.len();
-            if nrows == 0 { return Err("Empty matrix".into()); }
-            let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err("Not array of arrays".into()); };
+            if nrows == 0 { return Err(raise(ErrorKind::ValueError, "Empty matrix")); }
+            let ncols = if let Value::Array(r) = &rows[0] { r.len() } else { return Err(raise(ErrorKind::Error, "Not array of arrays")); };
             let mut data = Vec::with_capacity(nrows * ncols);
             for row in rows {
                 if let Value::Array(r) = row { for val in r { data.push(val.as_float()); } }
-                else { return Err("Expected row array".into()); }
+                else { return Err(raise(ErrorKind::TypeError, "Expected row array")); }
             }
             Ok(DMatrix::from_row_slice(nrows, ncols, &data))
-        } else { Err("Expected matrix".into()) }
+        } else { Err(raise(ErrorKind::TypeError, "Expected matrix")) }
     };
     let a = to_dm(&args[0])?;
     let b_vec: Vec<f64> = if let Value::Array(arr) = &args[1] {
         arr.iter().map(|x| x.as_float()).collect()
-    } else { return Err("Expected vector array for b".into()); };
+    } else { return Err(raise(ErrorKind::TypeError, "Expected vector array for b")); };
     let b = DMatrix::from_column_slice(b_vec.len(), 1, &b_vec);
     let lu = nalgebra::linalg::LU::new(a);
     match lu.solve(&b) {
         Some(x) => Ok(Value::Array((0..x.nrows()).map(|i| Value::Float(x[(i, 0)])).collect())),
-        None => Err("System is singular or inconsistent".into()),
+        None => Err(raise(ErrorKind::Error, "System is singular or inconsistent")),
     }
 })()),
 
-"mat_dot" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("mat_dot requires 2 args".into()); }
-    let a: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
-    let b: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
-    if a.len() != b.len() { return Err("Vector length mismatch".into()); }
+"mat_dot" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "mat_dot requires 2 args")); }
+    let a: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
+    let b: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
+    if a.len() != b.len() { return Err(raise(ErrorKind::ValueError, "Vector length mismatch")); }
     let dot: f64 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
     Ok(Value::Float(dot))
 })()),
 
-"mat_cross" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("mat_cross requires 2 args".into()); }
-    let a: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
-    let b: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
-    if a.len() != 3 || b.len() != 3 { return Err("Cross product requires 3D vectors".into()); }
+"mat_cross" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "mat_cross requires 2 args")); }
+    let a: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
+    let b: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
+    if a.len() != 3 || b.len() != 3 { return Err(raise(ErrorKind::TypeError, "Cross product requires 3D vectors")); }
     let av = Vector3::new(a[0], a[1], a[2]);
     let bv = Vector3::new(b[0], b[1], b[2]);
     let c = av.cross(&bv);
     Ok(Value::Array(vec![Value::Float(c[0]), Value::Float(c[1]), Value::Float(c[2])]))
 })()),
 
-"mat_normalize" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("mat_normalize requires 1 arg".into()); }
-    let v: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
+"mat_normalize" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "mat_normalize requires 1 arg")); }
+    let v: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
     let norm: f64 = v.iter().map(|x| x * x).sum::<f64>().sqrt();
-    if norm == 0.0 { return Err("Cannot normalize zero vector".into()); }
+    if norm == 0.0 { return Err(raise(ErrorKind::ValueError, "Cannot normalize zero vector")); }
     Ok(Value::Array(v.into_iter().map(|x| Value::Float(x / norm)).collect()))
 })()),
 
-"mat_outer" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("mat_outer requires 2 args".into()); }
-    let a: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
-    let b: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
+"mat_outer" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "mat_outer requires 2 args")); }
+    let a: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
+    let b: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
     let rows: Vec<Value> = a.iter()
         .map(|&ai| Value::Array(b.iter().map(|&bi| Value::Float(ai * bi)).collect()))
         .collect();
     Ok(Value::Array(rows))
 })()),
 
-"vec_add" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("vec_add requires 2 args".into()); }
-    let a: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
-    let b: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
-    if a.len() != b.len() { return Err("Vector length mismatch".into()); }
+"vec_add" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "vec_add requires 2 args")); }
+    let a: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
+    let b: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
+    if a.len() != b.len() { return Err(raise(ErrorKind::ValueError, "Vector length mismatch")); }
     Ok(Value::Array(a.iter().zip(b.iter()).map(|(x, y)| Value::Float(x + y)).collect()))
 })()),
 
-"vec_sub" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("vec_sub requires 2 args".into()); }
-    let a: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
-    let b: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
-    if a.len() != b.len() { return Err("Vector length mismatch".into()); }
+"vec_sub" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "vec_sub requires 2 args")); }
+    let a: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
+    let b: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
+    if a.len() != b.len() { return Err(raise(ErrorKind::ValueError, "Vector length mismatch")); }
     Ok(Value::Array(a.iter().zip(b.iter()).map(|(x, y)| Value::Float(x - y)).collect()))
 })()),
 
-"vec_scale" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("vec_scale requires 2 args".into()); }
-    let v: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
+"vec_scale" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "vec_scale requires 2 args")); }
+    let v: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
     let s = args[1].as_float();
     Ok(Value::Array(v.into_iter().map(|x| Value::Float(x * s)).collect()))
 })()),
 
-"vec_dot" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("vec_dot requires 2 args".into()); }
-    let a: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
-    let b: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
-    if a.len() != b.len() { return Err("Vector length mismatch".into()); }
+"vec_dot" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "vec_dot requires 2 args")); }
+    let a: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
+    let b: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
+    if a.len() != b.len() { return Err(raise(ErrorKind::ValueError, "Vector length mismatch")); }
     Ok(Value::Float(a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()))
 })()),
 
-"vec_norm" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("vec_norm requires 1 arg".into()); }
-    let v: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
+"vec_norm" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "vec_norm requires 1 arg")); }
+    let v: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
     Ok(Value::Float(v.iter().map(|x| x * x).sum::<f64>().sqrt()))
 })()),
 
-"vec_cross" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("vec_cross requires 2 args".into()); }
-    let a: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
-    let b: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
-    if a.len() != 3 || b.len() != 3 { return Err("vec_cross requires 3D vectors".into()); }
+"vec_cross" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "vec_cross requires 2 args")); }
+    let a: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
+    let b: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
+    if a.len() != 3 || b.len() != 3 { return Err(raise(ErrorKind::TypeError, "vec_cross requires 3D vectors")); }
     let av = Vector3::new(a[0], a[1], a[2]);
     let bv = Vector3::new(b[0], b[1], b[2]);
     let c = av.cross(&bv);
     Ok(Value::Array(vec![Value::Float(c[0]), Value::Float(c[1]), Value::Float(c[2])]))
 })()),
 
-"vec_normalize" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("vec_normalize requires 1 arg".into()); }
-    let v: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
+"vec_normalize" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "vec_normalize requires 1 arg")); }
+    let v: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
     let norm: f64 = v.iter().map(|x| x * x).sum::<f64>().sqrt();
-    if norm == 0.0 { return Err("Cannot normalize zero vector".into()); }
+    if norm == 0.0 { return Err(raise(ErrorKind::ValueError, "Cannot normalize zero vector")); }
     Ok(Value::Array(v.into_iter().map(|x| Value::Float(x / norm)).collect()))
 })()),
 
-"vec_angle" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("vec_angle requires 2 args".into()); }
-    let a: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
-    let b: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
-    if a.len() != b.len() { return Err("Vector length mismatch".into()); }
+"vec_angle" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "vec_angle requires 2 args")); }
+    let a: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
+    let b: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
+    if a.len() != b.len() { return Err(raise(ErrorKind::ValueError, "Vector length mismatch")); }
     let dot: f64 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
     let na: f64 = a.iter().map(|x| x * x).sum::<f64>().sqrt();
     let nb: f64 = b.iter().map(|x| x * x).sum::<f64>().sqrt();
-    if na == 0.0 || nb == 0.0 { return Err("Cannot compute angle with zero vector".into()); }
+    if na == 0.0 || nb == 0.0 { return Err(raise(ErrorKind::ValueError, "Cannot compute angle with zero vector")); }
     let cos_theta = (dot / (na * nb)).clamp(-1.0, 1.0);
     Ok(Value::Float(cos_theta.acos()))
 })()),
 
-"vec_lerp" => Some((|| -> Result<Value, String> {
-    if args.len() < 3 { return Err("vec_lerp requires 3 args (a, b, t)".into()); }
-    let a: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
-    let b: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
+"vec_lerp" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 3 { return Err(raise(ErrorKind::TypeError, "vec_lerp requires 3 args (a, b, t)")); }
+    let a: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
+    let b: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
     let t = args[2].as_float();
-    if a.len() != b.len() { return Err("Vector length mismatch".into()); }
+    if a.len() != b.len() { return Err(raise(ErrorKind::ValueError, "Vector length mismatch")); }
     Ok(Value::Array(a.iter().zip(b.iter()).map(|(ai, bi)| Value::Float(ai + t * (bi - ai))).collect()))
 })()),
 
-"vec_project" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("vec_project requires 2 args (a, b)".into()); }
-    let a: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
-    let b: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
-    if a.len() != b.len() { return Err("Vector length mismatch".into()); }
+"vec_project" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "vec_project requires 2 args (a, b)")); }
+    let a: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
+    let b: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
+    if a.len() != b.len() { return Err(raise(ErrorKind::ValueError, "Vector length mismatch")); }
     let dot_ab: f64 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
     let dot_bb: f64 = b.iter().map(|x| x * x).sum();
-    if dot_bb == 0.0 { return Err("Cannot project onto zero vector".into()); }
+    if dot_bb == 0.0 { return Err(raise(ErrorKind::ValueError, "Cannot project onto zero vector")); }
     let scale = dot_ab / dot_bb;
     Ok(Value::Array(b.iter().map(|&bi| Value::Float(bi * scale)).collect()))
 })()),
 
-"fft" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("fft requires 1 arg".into()); }
-    let vals: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
+"fft" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "fft requires 1 arg")); }
+    let vals: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
     let n = vals.len();
     if n == 0 { return Ok(Value::Array(vec![])); }
     let mut buffer: Vec<Complex<f64>> = vals.iter().map(|&r| Complex { re: r, im: 0.0 }).collect();
@@ -12985,8 +14229,8 @@ impl Interpreter {
     Ok(Value::Array(result))
 })()),
 
-"ifft" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("ifft requires 1 arg".into()); }
+"ifft" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "ifft requires 1 arg")); }
     let vals: Vec<Complex<f64>> = if let Value::Array(arr) = &args[0] {
         arr.iter().map(|x| {
             if let Value::Map(m) = x {
@@ -12997,7 +14241,7 @@ impl Interpreter {
                 Complex { re: x.as_float(), im: 0.0 }
             }
         }).collect()
-    } else { return Err("Expected array of {re, im} maps".into()); };
+    } else { return Err(raise(ErrorKind::TypeError, "Expected array of {re, im} maps")); };
     let n = vals.len();
     if n == 0 { return Ok(Value::Array(vec![])); }
     let mut buffer = vals;
@@ -13008,8 +14252,8 @@ impl Interpreter {
     Ok(Value::Array(buffer.iter().map(|c| Value::Float(c.re * scale)).collect()))
 })()),
 
-"fft_magnitude" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("fft_magnitude requires 1 arg".into()); }
+"fft_magnitude" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "fft_magnitude requires 1 arg")); }
     match &args[0] {
         Value::Array(arr) => {
             // Check if it's output from fft() — array of {re, im} maps
@@ -13039,13 +14283,13 @@ impl Interpreter {
             fft_plan.process(&mut buffer);
             Ok(Value::Array(buffer.iter().map(|c| Value::Float(c.norm())).collect()))
         }
-        _ => Err("fft_magnitude: expected array".into()),
+        _ => Err(raise(ErrorKind::TypeError, "fft_magnitude: expected array")),
     }
 })()),
 
-"fft_phase" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("fft_phase requires 1 arg".into()); }
-    let vals: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
+"fft_phase" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "fft_phase requires 1 arg")); }
+    let vals: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
     let n = vals.len();
     if n == 0 { return Ok(Value::Array(vec![])); }
     let mut buffer: Vec<Complex<f64>> = vals.iter().map(|&r| Complex { re: r, im: 0.0 }).collect();
@@ -13055,9 +14299,9 @@ impl Interpreter {
     Ok(Value::Array(buffer.iter().map(|c| Value::Float(c.arg())).collect()))
 })()),
 
-"fft_power_spectrum" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("fft_power_spectrum requires 1 arg".into()); }
-    let vals: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
+"fft_power_spectrum" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "fft_power_spectrum requires 1 arg")); }
+    let vals: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
     let n = vals.len();
     if n == 0 { return Ok(Value::Array(vec![])); }
     let mut buffer: Vec<Complex<f64>> = vals.iter().map(|&r| Complex { re: r, im: 0.0 }).collect();
@@ -13067,8 +14311,8 @@ impl Interpreter {
     Ok(Value::Array(buffer.iter().map(|c| Value::Float(c.norm_sqr())).collect()))
 })()),
 
-"fft_frequencies" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("fft_frequencies requires 2 args (n, sample_rate)".into()); }
+"fft_frequencies" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "fft_frequencies requires 2 args (n, sample_rate)")); }
     let n = args[0].as_int() as usize;
     let sample_rate = args[1].as_float();
     if n == 0 { return Ok(Value::Array(vec![])); }
@@ -13079,9 +14323,9 @@ impl Interpreter {
     Ok(Value::Array(freqs))
 })()),
 
-"integrate_simpson" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("integrate_simpson requires 2 args (arr, dx)".into()); }
-    let y: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
+"integrate_simpson" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "integrate_simpson requires 2 args (arr, dx)")); }
+    let y: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
     let dx = args[1].as_float();
     let n = y.len();
     if n < 2 { return Ok(Value::Float(0.0)); }
@@ -13104,8 +14348,8 @@ impl Interpreter {
     }
 })()),
 
-"integrate_trapezoidal" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("integrate_trapezoidal requires 2 args".into()); }
+"integrate_trapezoidal" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "integrate_trapezoidal requires 2 args")); }
     // Two forms:
     // (y_array, dx)            — uniform step
     // (x_array, y_array)       — non-uniform x values
@@ -13124,7 +14368,7 @@ impl Interpreter {
         }
         _ => {
             // (y_array, dx) uniform
-            let y: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err("Expected array".into()); };
+            let y: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|x| x.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
             let dx = args[1].as_float();
             let n = y.len();
             if n < 2 { return Ok(Value::Float(0.0)); }
@@ -13134,16 +14378,16 @@ impl Interpreter {
     }
 })()),
 
-"ode_rk4" => Some((|| -> Result<Value, String> {
+"ode_rk4" => Some((|| -> Result<Value, RuntimeError> {
     // Solves dy/dt = k*y (exponential growth/decay)
     // args: y0, t0, t1, dt, k
-    if args.len() < 5 { return Err("ode_rk4 requires 5 args (y0, t0, t1, dt, k)".into()); }
+    if args.len() < 5 { return Err(raise(ErrorKind::TypeError, "ode_rk4 requires 5 args (y0, t0, t1, dt, k)")); }
     let mut y = args[0].as_float();
     let t0 = args[1].as_float();
     let t1 = args[2].as_float();
     let dt = args[3].as_float();
     let k = args[4].as_float();
-    if dt <= 0.0 { return Err("dt must be positive".into()); }
+    if dt <= 0.0 { return Err(raise(ErrorKind::TypeError, "dt must be positive")); }
     let f = |_t: f64, y: f64| k * y;
     let mut t = t0;
     let mut result = vec![Value::Array(vec![Value::Float(t), Value::Float(y)])];
@@ -13160,12 +14404,12 @@ impl Interpreter {
     Ok(Value::Array(result))
 })()),
 
-"interpolate_linear" => Some((|| -> Result<Value, String> {
-    if args.len() < 3 { return Err("interpolate_linear requires 3 args (x_arr, y_arr, x)".into()); }
-    let xs: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err("Expected x array".into()); };
-    let ys: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|v| v.as_float()).collect() } else { return Err("Expected y array".into()); };
+"interpolate_linear" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 3 { return Err(raise(ErrorKind::TypeError, "interpolate_linear requires 3 args (x_arr, y_arr, x)")); }
+    let xs: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected x array")); };
+    let ys: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|v| v.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected y array")); };
     let x = args[2].as_float();
-    if xs.len() != ys.len() || xs.is_empty() { return Err("x and y arrays must be non-empty and same length".into()); }
+    if xs.len() != ys.len() || xs.is_empty() { return Err(raise(ErrorKind::TypeError, "x and y arrays must be non-empty and same length")); }
     if x <= xs[0] { return Ok(Value::Float(ys[0])); }
     if x >= xs[xs.len() - 1] { return Ok(Value::Float(ys[ys.len() - 1])); }
     for i in 0..xs.len() - 1 {
@@ -13177,14 +14421,14 @@ impl Interpreter {
     Ok(Value::Float(ys[ys.len() - 1]))
 })()),
 
-"interpolate_cubic" => Some((|| -> Result<Value, String> {
+"interpolate_cubic" => Some((|| -> Result<Value, RuntimeError> {
     // Catmull-Rom spline interpolation
-    if args.len() < 3 { return Err("interpolate_cubic requires 3 args (x_arr, y_arr, x)".into()); }
-    let xs: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err("Expected x array".into()); };
-    let ys: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|v| v.as_float()).collect() } else { return Err("Expected y array".into()); };
+    if args.len() < 3 { return Err(raise(ErrorKind::TypeError, "interpolate_cubic requires 3 args (x_arr, y_arr, x)")); }
+    let xs: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected x array")); };
+    let ys: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|v| v.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected y array")); };
     let x = args[2].as_float();
     let n = xs.len();
-    if n != ys.len() || n == 0 { return Err("x and y arrays must be non-empty and same length".into()); }
+    if n != ys.len() || n == 0 { return Err(raise(ErrorKind::TypeError, "x and y arrays must be non-empty and same length")); }
     if n == 1 { return Ok(Value::Float(ys[0])); }
     if n == 2 {
         let t = (x - xs[0]) / (xs[1] - xs[0]);
@@ -13212,12 +14456,12 @@ impl Interpreter {
     Ok(Value::Float(v))
 })()),
 
-"numerical_diff" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("numerical_diff requires 2 args (x_arr, y_arr)".into()); }
-    let xs: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err("Expected x array".into()); };
-    let ys: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|v| v.as_float()).collect() } else { return Err("Expected y array".into()); };
+"numerical_diff" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "numerical_diff requires 2 args (x_arr, y_arr)")); }
+    let xs: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected x array")); };
+    let ys: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|v| v.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected y array")); };
     let n = xs.len();
-    if n != ys.len() || n == 0 { return Err("x and y arrays must be non-empty and same length".into()); }
+    if n != ys.len() || n == 0 { return Err(raise(ErrorKind::TypeError, "x and y arrays must be non-empty and same length")); }
     if n == 1 { return Ok(Value::Array(vec![Value::Float(0.0)])); }
     let mut dy = Vec::with_capacity(n);
     // Forward difference at start
@@ -13231,8 +14475,8 @@ impl Interpreter {
     Ok(Value::Array(dy.into_iter().map(Value::Float).collect()))
 })()),
 
-"linspace" => Some((|| -> Result<Value, String> {
-    if args.len() < 3 { return Err("linspace requires 3 args (start, end, n)".into()); }
+"linspace" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 3 { return Err(raise(ErrorKind::TypeError, "linspace requires 3 args (start, end, n)")); }
     let start = args[0].as_float();
     let end = args[1].as_float();
     let n = args[2].as_int() as usize;
@@ -13242,8 +14486,8 @@ impl Interpreter {
     Ok(Value::Array((0..n).map(|i| Value::Float(start + i as f64 * step)).collect()))
 })()),
 
-"logspace" => Some((|| -> Result<Value, String> {
-    if args.len() < 3 { return Err("logspace requires 3 args (start, end, n)".into()); }
+"logspace" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 3 { return Err(raise(ErrorKind::TypeError, "logspace requires 3 args (start, end, n)")); }
     let start = args[0].as_float();
     let end = args[1].as_float();
     let n = args[2].as_int() as usize;
@@ -13253,12 +14497,12 @@ impl Interpreter {
     Ok(Value::Array((0..n).map(|i| Value::Float(10_f64.powf(start + i as f64 * step))).collect()))
 })()),
 
-"arange" => Some((|| -> Result<Value, String> {
-    if args.len() < 3 { return Err("arange requires 3 args (start, end, step)".into()); }
+"arange" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 3 { return Err(raise(ErrorKind::TypeError, "arange requires 3 args (start, end, step)")); }
     let start = args[0].as_float();
     let end = args[1].as_float();
     let step = args[2].as_float();
-    if step == 0.0 { return Err("step cannot be zero".into()); }
+    if step == 0.0 { return Err(raise(ErrorKind::ValueError, "step cannot be zero")); }
     let mut result = Vec::new();
     let mut v = start;
     if step > 0.0 {
@@ -13269,10 +14513,10 @@ impl Interpreter {
     Ok(Value::Array(result))
 })()),
 
-"meshgrid" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("meshgrid requires 2 args (x_arr, y_arr)".into()); }
-    let xs: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err("Expected x array".into()); };
-    let ys: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|v| v.as_float()).collect() } else { return Err("Expected y array".into()); };
+"meshgrid" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "meshgrid requires 2 args (x_arr, y_arr)")); }
+    let xs: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected x array")); };
+    let ys: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|v| v.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected y array")); };
     let ny = ys.len();
     let nx = xs.len();
     // X grid: each row is xs repeated for each y
@@ -13285,12 +14529,12 @@ impl Interpreter {
     Ok(Value::Map(result))
 })()),
 
-"stat_correlation" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("stat_correlation requires 2 args".into()); }
-    let x: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err("Expected array".into()); };
-    let y: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|v| v.as_float()).collect() } else { return Err("Expected array".into()); };
+"stat_correlation" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "stat_correlation requires 2 args")); }
+    let x: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
+    let y: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|v| v.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
     let n = x.len();
-    if n != y.len() || n == 0 { return Err("Arrays must be non-empty and same length".into()); }
+    if n != y.len() || n == 0 { return Err(raise(ErrorKind::TypeError, "Arrays must be non-empty and same length")); }
     let mx = x.iter().sum::<f64>() / n as f64;
     let my = y.iter().sum::<f64>() / n as f64;
     let cov: f64 = x.iter().zip(y.iter()).map(|(xi, yi)| (xi - mx) * (yi - my)).sum::<f64>() / n as f64;
@@ -13300,31 +14544,31 @@ impl Interpreter {
     Ok(Value::Float(cov / (sx * sy)))
 })()),
 
-"stat_covariance" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("stat_covariance requires 2 args".into()); }
-    let x: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err("Expected array".into()); };
-    let y: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|v| v.as_float()).collect() } else { return Err("Expected array".into()); };
+"stat_covariance" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "stat_covariance requires 2 args")); }
+    let x: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
+    let y: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|v| v.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
     let n = x.len();
-    if n != y.len() || n < 2 { return Err("Arrays must have at least 2 elements and same length".into()); }
+    if n != y.len() || n < 2 { return Err(raise(ErrorKind::Error, "Arrays must have at least 2 elements and same length")); }
     let mx = x.iter().sum::<f64>() / n as f64;
     let my = y.iter().sum::<f64>() / n as f64;
     let cov: f64 = x.iter().zip(y.iter()).map(|(xi, yi)| (xi - mx) * (yi - my)).sum::<f64>() / (n - 1) as f64;
     Ok(Value::Float(cov))
 })()),
 
-"stat_regression_linear" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("stat_regression_linear requires 2 args (x, y)".into()); }
-    let x: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err("Expected array".into()); };
-    let y: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|v| v.as_float()).collect() } else { return Err("Expected array".into()); };
+"stat_regression_linear" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "stat_regression_linear requires 2 args (x, y)")); }
+    let x: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
+    let y: Vec<f64> = if let Value::Array(arr) = &args[1] { arr.iter().map(|v| v.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
     let n = x.len();
-    if n != y.len() || n < 2 { return Err("Arrays must have at least 2 elements and same length".into()); }
+    if n != y.len() || n < 2 { return Err(raise(ErrorKind::Error, "Arrays must have at least 2 elements and same length")); }
     let nf = n as f64;
     let sum_x: f64 = x.iter().sum();
     let sum_y: f64 = y.iter().sum();
     let sum_xx: f64 = x.iter().map(|xi| xi * xi).sum();
     let sum_xy: f64 = x.iter().zip(y.iter()).map(|(xi, yi)| xi * yi).sum();
     let denom = nf * sum_xx - sum_x * sum_x;
-    if denom == 0.0 { return Err("Cannot fit: all x values are identical".into()); }
+    if denom == 0.0 { return Err(raise(ErrorKind::TypeError, "Cannot fit: all x values are identical")); }
     let slope = (nf * sum_xy - sum_x * sum_y) / denom;
     let intercept = (sum_y - slope * sum_x) / nf;
     let y_mean = sum_y / nf;
@@ -13341,9 +14585,9 @@ impl Interpreter {
     Ok(Value::Map(result))
 })()),
 
-"stat_histogram" => Some((|| -> Result<Value, String> {
-    if args.len() < 2 { return Err("stat_histogram requires 2 args (arr, bins)".into()); }
-    let vals: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err("Expected array".into()); };
+"stat_histogram" => Some((|| -> Result<Value, RuntimeError> {
+    if args.len() < 2 { return Err(raise(ErrorKind::TypeError, "stat_histogram requires 2 args (arr, bins)")); }
+    let vals: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
     let bins = args[1].as_int() as usize;
     if vals.is_empty() || bins == 0 { return Ok(Value::Array(vec![])); }
     let min = vals.iter().cloned().fold(f64::INFINITY, f64::min);
@@ -13370,12 +14614,12 @@ impl Interpreter {
     Ok(Value::Array(result))
 })()),
 
-"stat_entropy" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("stat_entropy requires 1 arg".into()); }
-    let vals: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err("Expected array".into()); };
+"stat_entropy" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "stat_entropy requires 1 arg")); }
+    let vals: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
     if vals.is_empty() { return Ok(Value::Float(0.0)); }
     let total: f64 = vals.iter().sum();
-    if total == 0.0 { return Err("Cannot compute entropy: all values are zero".into()); }
+    if total == 0.0 { return Err(raise(ErrorKind::ValueError, "Cannot compute entropy: all values are zero")); }
     let entropy: f64 = vals.iter()
         .filter(|&&v| v > 0.0)
         .map(|&v| { let p = v / total; -p * p.ln() })
@@ -13383,9 +14627,9 @@ impl Interpreter {
     Ok(Value::Float(entropy))
 })()),
 
-"stat_zscore" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("stat_zscore requires 1 arg".into()); }
-    let vals: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err("Expected array".into()); };
+"stat_zscore" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "stat_zscore requires 1 arg")); }
+    let vals: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
     let n = vals.len();
     if n == 0 { return Ok(Value::Array(vec![])); }
     let mean = vals.iter().sum::<f64>() / n as f64;
@@ -13395,9 +14639,9 @@ impl Interpreter {
     Ok(Value::Array(vals.iter().map(|&x| Value::Float((x - mean) / std_dev)).collect()))
 })()),
 
-"stat_normalize_minmax" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("stat_normalize_minmax requires 1 arg".into()); }
-    let vals: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err("Expected array".into()); };
+"stat_normalize_minmax" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "stat_normalize_minmax requires 1 arg")); }
+    let vals: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
     if vals.is_empty() { return Ok(Value::Array(vec![])); }
     let min = vals.iter().cloned().fold(f64::INFINITY, f64::min);
     let max = vals.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
@@ -13406,9 +14650,9 @@ impl Interpreter {
     Ok(Value::Array(vals.iter().map(|&x| Value::Float((x - min) / range)).collect()))
 })()),
 
-"stat_iqr" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("stat_iqr requires 1 arg".into()); }
-    let vals: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err("Expected array".into()); };
+"stat_iqr" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "stat_iqr requires 1 arg")); }
+    let vals: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
     if vals.is_empty() { return Ok(Value::Float(0.0)); }
     let mut sorted = vals.clone();
     sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
@@ -13420,11 +14664,11 @@ impl Interpreter {
     Ok(Value::Float(q3 - q1))
 })()),
 
-"stat_skewness" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("stat_skewness requires 1 arg".into()); }
-    let vals: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err("Expected array".into()); };
+"stat_skewness" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "stat_skewness requires 1 arg")); }
+    let vals: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
     let n = vals.len();
-    if n < 3 { return Err("stat_skewness requires at least 3 elements".into()); }
+    if n < 3 { return Err(raise(ErrorKind::TypeError, "stat_skewness requires at least 3 elements")); }
     let nf = n as f64;
     let mean = vals.iter().sum::<f64>() / nf;
     let variance = vals.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / nf;
@@ -13434,11 +14678,11 @@ impl Interpreter {
     Ok(Value::Float(m3))
 })()),
 
-"stat_kurtosis" => Some((|| -> Result<Value, String> {
-    if args.is_empty() { return Err("stat_kurtosis requires 1 arg".into()); }
-    let vals: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err("Expected array".into()); };
+"stat_kurtosis" => Some((|| -> Result<Value, RuntimeError> {
+    if args.is_empty() { return Err(raise(ErrorKind::TypeError, "stat_kurtosis requires 1 arg")); }
+    let vals: Vec<f64> = if let Value::Array(arr) = &args[0] { arr.iter().map(|v| v.as_float()).collect() } else { return Err(raise(ErrorKind::TypeError, "Expected array")); };
     let n = vals.len();
-    if n < 4 { return Err("stat_kurtosis requires at least 4 elements".into()); }
+    if n < 4 { return Err(raise(ErrorKind::TypeError, "stat_kurtosis requires at least 4 elements")); }
     let nf = n as f64;
     let mean = vals.iter().sum::<f64>() / nf;
     let variance = vals.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / nf;
@@ -13475,7 +14719,7 @@ impl Interpreter {
 
 "graph_add_node" => {
     if args.len() < 2 {
-        return Some(Err("graph_add_node requires (id, label)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_add_node requires (id, label)".to_string())));
     }
     let id = args[0].as_int();
     let label = args[1].as_string();
@@ -13487,12 +14731,12 @@ impl Interpreter {
         let idx = g.add_node(label);
         return Some(Ok(Value::Int(idx.index() as i64)));
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 "graph_add_edge" => {
     if args.len() < 3 {
-        return Some(Err("graph_add_edge requires (id, from_idx, to_idx[, weight])".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_add_edge requires (id, from_idx, to_idx[, weight])".to_string())));
     }
     let id = args[0].as_int();
     let from = args[1].as_int() as usize;
@@ -13506,12 +14750,12 @@ impl Interpreter {
         let ei = g.add_edge(NodeIndex::new(from), NodeIndex::new(to), weight);
         return Some(Ok(Value::Int(ei.index() as i64)));
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 "graph_remove_node" => {
     if args.len() < 2 {
-        return Some(Err("graph_remove_node requires (id, node_idx)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_remove_node requires (id, node_idx)".to_string())));
     }
     let id = args[0].as_int();
     let node = NodeIndex::new(args[1].as_int() as usize);
@@ -13519,20 +14763,20 @@ impl Interpreter {
         if g.remove_node(node).is_some() {
             return Some(Ok(Value::Bool(true)));
         }
-        return Some(Err(format!("Node index {} not found in graph {}", node.index(), id)));
+        return Some(Err(raise(ErrorKind::ValueError, format!("Node index {} not found in graph {}", node.index(), id))));
     }
     if let Some(g) = self.graphs_undirected.get_mut(&id) {
         if g.remove_node(node).is_some() {
             return Some(Ok(Value::Bool(true)));
         }
-        return Some(Err(format!("Node index {} not found in graph {}", node.index(), id)));
+        return Some(Err(raise(ErrorKind::ValueError, format!("Node index {} not found in graph {}", node.index(), id))));
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 "graph_remove_edge" => {
     if args.len() < 3 {
-        return Some(Err("graph_remove_edge requires (id, from_idx, to_idx)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_remove_edge requires (id, from_idx, to_idx)".to_string())));
     }
     let id = args[0].as_int();
     let from = NodeIndex::new(args[1].as_int() as usize);
@@ -13542,21 +14786,21 @@ impl Interpreter {
             g.remove_edge(ei);
             return Some(Ok(Value::Bool(true)));
         }
-        return Some(Err(format!("Edge {}->{} not found in graph {}", from.index(), to.index(), id)));
+        return Some(Err(raise(ErrorKind::ValueError, format!("Edge {}->{} not found in graph {}", from.index(), to.index(), id))));
     }
     if let Some(g) = self.graphs_undirected.get_mut(&id) {
         if let Some(ei) = g.find_edge(from, to) {
             g.remove_edge(ei);
             return Some(Ok(Value::Bool(true)));
         }
-        return Some(Err(format!("Edge {}--{} not found in graph {}", from.index(), to.index(), id)));
+        return Some(Err(raise(ErrorKind::ValueError, format!("Edge {}--{} not found in graph {}", from.index(), to.index(), id))));
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 "graph_node_count" => {
     if args.is_empty() {
-        return Some(Err("graph_node_count requires (id)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_node_count requires (id)".to_string())));
     }
     let id = args[0].as_int();
     if let Some(g) = self.graphs_directed.get(&id) {
@@ -13565,12 +14809,12 @@ impl Interpreter {
     if let Some(g) = self.graphs_undirected.get(&id) {
         return Some(Ok(Value::Int(g.node_count() as i64)));
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 "graph_edge_count" => {
     if args.is_empty() {
-        return Some(Err("graph_edge_count requires (id)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_edge_count requires (id)".to_string())));
     }
     let id = args[0].as_int();
     if let Some(g) = self.graphs_directed.get(&id) {
@@ -13579,12 +14823,12 @@ impl Interpreter {
     if let Some(g) = self.graphs_undirected.get(&id) {
         return Some(Ok(Value::Int(g.edge_count() as i64)));
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 "graph_neighbors" => {
     if args.len() < 2 {
-        return Some(Err("graph_neighbors requires (id, node_idx)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_neighbors requires (id, node_idx)".to_string())));
     }
     let id = args[0].as_int();
     let node = NodeIndex::new(args[1].as_int() as usize);
@@ -13600,33 +14844,33 @@ impl Interpreter {
             .collect();
         return Some(Ok(Value::Array(neighbors)));
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 "graph_node_label" => {
     if args.len() < 2 {
-        return Some(Err("graph_node_label requires (id, node_idx)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_node_label requires (id, node_idx)".to_string())));
     }
     let id = args[0].as_int();
     let node = NodeIndex::new(args[1].as_int() as usize);
     if let Some(g) = self.graphs_directed.get(&id) {
         match g.node_weight(node) {
             Some(label) => return Some(Ok(Value::String(label.clone()))),
-            None => return Some(Err(format!("Node index {} not found in graph {}", node.index(), id))),
+            None => return Some(Err(raise(ErrorKind::ValueError, format!("Node index {} not found in graph {}", node.index(), id)))),
         }
     }
     if let Some(g) = self.graphs_undirected.get(&id) {
         match g.node_weight(node) {
             Some(label) => return Some(Ok(Value::String(label.clone()))),
-            None => return Some(Err(format!("Node index {} not found in graph {}", node.index(), id))),
+            None => return Some(Err(raise(ErrorKind::ValueError, format!("Node index {} not found in graph {}", node.index(), id)))),
         }
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 "graph_bfs" => {
     if args.len() < 2 {
-        return Some(Err("graph_bfs requires (id, start_idx)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_bfs requires (id, start_idx)".to_string())));
     }
     let id = args[0].as_int();
     let start = NodeIndex::new(args[1].as_int() as usize);
@@ -13646,12 +14890,12 @@ impl Interpreter {
         }
         return Some(Ok(Value::Array(result)));
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 "graph_dfs" => {
     if args.len() < 2 {
-        return Some(Err("graph_dfs requires (id, start_idx)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_dfs requires (id, start_idx)".to_string())));
     }
     let id = args[0].as_int();
     let start = NodeIndex::new(args[1].as_int() as usize);
@@ -13671,12 +14915,12 @@ impl Interpreter {
         }
         return Some(Ok(Value::Array(result)));
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 "graph_dijkstra" => {
     if args.len() < 2 {
-        return Some(Err("graph_dijkstra requires (id, start_idx)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_dijkstra requires (id, start_idx)".to_string())));
     }
     let id = args[0].as_int();
     let start = NodeIndex::new(args[1].as_int() as usize);
@@ -13696,12 +14940,12 @@ impl Interpreter {
         }
         return Some(Ok(Value::Map(map)));
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 "graph_shortest_path" => {
     if args.len() < 3 {
-        return Some(Err("graph_shortest_path requires (id, start_idx, end_idx)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_shortest_path requires (id, start_idx, end_idx)".to_string())));
     }
     let id = args[0].as_int();
     let start = NodeIndex::new(args[1].as_int() as usize);
@@ -13717,9 +14961,9 @@ impl Interpreter {
                 map.insert("cost".to_string(), Value::Float(cost));
                 return Some(Ok(Value::Map(map)));
             }
-            None => return Some(Err(format!(
+            None => return Some(Err(raise(ErrorKind::Error, format!(
                 "No path from {} to {} in graph {}", start.index(), end.index(), id
-            ))),
+            )))),
         }
     }
     if let Some(g) = self.graphs_undirected.get(&id) {
@@ -13733,17 +14977,17 @@ impl Interpreter {
                 map.insert("cost".to_string(), Value::Float(cost));
                 return Some(Ok(Value::Map(map)));
             }
-            None => return Some(Err(format!(
+            None => return Some(Err(raise(ErrorKind::Error, format!(
                 "No path from {} to {} in graph {}", start.index(), end.index(), id
-            ))),
+            )))),
         }
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 "graph_bellman_ford" => {
     if args.len() < 2 {
-        return Some(Err("graph_bellman_ford requires (id, start_idx)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_bellman_ford requires (id, start_idx)".to_string())));
     }
     let id = args[0].as_int();
     let start = NodeIndex::new(args[1].as_int() as usize);
@@ -13759,18 +15003,18 @@ impl Interpreter {
                 }
                 return Some(Ok(Value::Map(map)));
             }
-            Err(_) => return Some(Err("Negative cycle detected in graph".to_string())),
+            Err(_) => return Some(Err(raise(ErrorKind::Error, "Negative cycle detected in graph".to_string()))),
         }
     }
     if self.graphs_undirected.contains_key(&id) {
-        return Some(Err("graph_bellman_ford is only supported for directed graphs".to_string()));
+        return Some(Err(raise(ErrorKind::Error, "graph_bellman_ford is only supported for directed graphs".to_string())));
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 "graph_topo_sort" => {
     if args.is_empty() {
-        return Some(Err("graph_topo_sort requires (id)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_topo_sort requires (id)".to_string())));
     }
     let id = args[0].as_int();
     if let Some(g) = self.graphs_directed.get(&id) {
@@ -13781,20 +15025,18 @@ impl Interpreter {
                     .collect();
                 return Some(Ok(Value::Array(result)));
             }
-            Err(_) => return Some(Err(
-                "Graph contains a cycle; topological sort not possible".to_string()
-            )),
+            Err(_) => return Some(Err(raise(ErrorKind::Error, "Graph contains a cycle; topological sort not possible".to_string()))),
         }
     }
     if self.graphs_undirected.contains_key(&id) {
-        return Some(Err("graph_topo_sort is only supported for directed graphs".to_string()));
+        return Some(Err(raise(ErrorKind::Error, "graph_topo_sort is only supported for directed graphs".to_string())));
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 "graph_scc" => {
     if args.is_empty() {
-        return Some(Err("graph_scc requires (id)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_scc requires (id)".to_string())));
     }
     let id = args[0].as_int();
     if let Some(g) = self.graphs_directed.get(&id) {
@@ -13810,14 +15052,14 @@ impl Interpreter {
         return Some(Ok(Value::Array(result)));
     }
     if self.graphs_undirected.contains_key(&id) {
-        return Some(Err("graph_scc is only supported for directed graphs".to_string()));
+        return Some(Err(raise(ErrorKind::Error, "graph_scc is only supported for directed graphs".to_string())));
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 "graph_connected_components" => {
     if args.is_empty() {
-        return Some(Err("graph_connected_components requires (id)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_connected_components requires (id)".to_string())));
     }
     let id = args[0].as_int();
     if let Some(g) = self.graphs_undirected.get(&id) {
@@ -13825,16 +15067,14 @@ impl Interpreter {
         return Some(Ok(Value::Int(count as i64)));
     }
     if self.graphs_directed.contains_key(&id) {
-        return Some(Err(
-            "graph_connected_components is only supported for undirected graphs".to_string()
-        ));
+        return Some(Err(raise(ErrorKind::Error, "graph_connected_components is only supported for undirected graphs".to_string())));
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 "graph_is_cyclic" => {
     if args.is_empty() {
-        return Some(Err("graph_is_cyclic requires (id)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_is_cyclic requires (id)".to_string())));
     }
     let id = args[0].as_int();
     if let Some(g) = self.graphs_directed.get(&id) {
@@ -13843,12 +15083,12 @@ impl Interpreter {
     if let Some(g) = self.graphs_undirected.get(&id) {
         return Some(Ok(Value::Bool(petgraph::algo::is_cyclic_undirected(g))));
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 "graph_min_spanning_tree" => {
     if args.is_empty() {
-        return Some(Err("graph_min_spanning_tree requires (id)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_min_spanning_tree requires (id)".to_string())));
     }
     use petgraph::data::Element;
     let id = args[0].as_int();
@@ -13878,12 +15118,12 @@ impl Interpreter {
         }
         return Some(Ok(Value::Array(edges)));
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 "graph_node_degree" => {
     if args.len() < 2 {
-        return Some(Err("graph_node_degree requires (id, node_idx)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_node_degree requires (id, node_idx)".to_string())));
     }
     let id = args[0].as_int();
     let node = NodeIndex::new(args[1].as_int() as usize);
@@ -13896,12 +15136,12 @@ impl Interpreter {
         let deg = g.neighbors(node).count();
         return Some(Ok(Value::Int(deg as i64)));
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 "graph_all_nodes" => {
     if args.is_empty() {
-        return Some(Err("graph_all_nodes requires (id)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_all_nodes requires (id)".to_string())));
     }
     let id = args[0].as_int();
     if let Some(g) = self.graphs_directed.get(&id) {
@@ -13932,12 +15172,12 @@ impl Interpreter {
             .collect();
         return Some(Ok(Value::Array(result)));
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 "graph_all_edges" => {
     if args.is_empty() {
-        return Some(Err("graph_all_edges requires (id)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_all_edges requires (id)".to_string())));
     }
     let id = args[0].as_int();
     if let Some(g) = self.graphs_directed.get(&id) {
@@ -13964,12 +15204,12 @@ impl Interpreter {
             .collect();
         return Some(Ok(Value::Array(result)));
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 "graph_has_edge" => {
     if args.len() < 3 {
-        return Some(Err("graph_has_edge requires (id, from_idx, to_idx)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_has_edge requires (id, from_idx, to_idx)".to_string())));
     }
     let id = args[0].as_int();
     let from = NodeIndex::new(args[1].as_int() as usize);
@@ -13980,12 +15220,12 @@ impl Interpreter {
     if let Some(g) = self.graphs_undirected.get(&id) {
         return Some(Ok(Value::Bool(g.find_edge(from, to).is_some())));
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 "graph_free" => {
     if args.is_empty() {
-        return Some(Err("graph_free requires (id)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_free requires (id)".to_string())));
     }
     let id = args[0].as_int();
     let removed_d = self.graphs_directed.remove(&id).is_some();
@@ -13993,13 +15233,13 @@ impl Interpreter {
     if removed_d || removed_u {
         Some(Ok(Value::Bool(true)))
     } else {
-        Some(Err(format!("Graph {} not found", id)))
+        Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
     }
 }
 
 "graph_to_dot" => {
     if args.is_empty() {
-        return Some(Err("graph_to_dot requires (id)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "graph_to_dot requires (id)".to_string())));
     }
     let id = args[0].as_int();
     if let Some(g) = self.graphs_directed.get(&id) {
@@ -14036,7 +15276,7 @@ impl Interpreter {
         dot.push('}');
         return Some(Ok(Value::String(dot)));
     }
-    Some(Err(format!("Graph {} not found", id)))
+    Some(Err(raise(ErrorKind::ValueError, format!("Graph {} not found", id))))
 }
 
 
@@ -14048,7 +15288,7 @@ impl Interpreter {
 
 "xxhash64" => {
     if args.is_empty() {
-        return Some(Err("xxhash64 requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "xxhash64 requires 1 argument".to_string())));
     }
     let bytes = vbytes(&args[0]);
     let hash = xxh3_64(&bytes);
@@ -14057,7 +15297,7 @@ impl Interpreter {
 
 "xxhash32" => {
     if args.is_empty() {
-        return Some(Err("xxhash32 requires at least 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "xxhash32 requires at least 1 argument".to_string())));
     }
     let bytes = vbytes(&args[0]);
     let seed = if args.len() > 1 { args[1].as_int() as u32 } else { 0u32 };
@@ -14211,7 +15451,7 @@ impl Interpreter {
 
 "base32_decode" => {
     if args.is_empty() {
-        return Some(Err("base32_decode requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "base32_decode requires 1 argument".to_string())));
     }
     let s = args[0].as_string();
     match base32::decode(base32::Alphabet::RFC4648 { padding: true }, &s) {
@@ -14220,7 +15460,7 @@ impl Interpreter {
                 .unwrap_or_else(|_| b2he(&bytes));
             Some(Ok(Value::String(result)))
         }
-        None => Some(Err("base32_decode: invalid base32 input".to_string())),
+        None => Some(Err(raise(ErrorKind::ValueError, "base32_decode: invalid base32 input".to_string()))),
     }
 }
 
@@ -14232,12 +15472,12 @@ impl Interpreter {
 
 "base58_decode" => {
     if args.is_empty() {
-        return Some(Err("base58_decode requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "base58_decode requires 1 argument".to_string())));
     }
     let s = args[0].as_string();
     match bs58::decode(s).into_vec() {
         Ok(bytes) => Some(Ok(Value::String(b2he(&bytes)))),
-        Err(e) => Some(Err(format!("base58_decode error: {}", e))),
+        Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("base58_decode error: {}", e)))),
     }
 }
 
@@ -14249,7 +15489,7 @@ impl Interpreter {
 
 "base16_decode" => {
     if args.is_empty() {
-        return Some(Err("base16_decode requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "base16_decode requires 1 argument".to_string())));
     }
     let s = args[0].as_string();
     match he2b(&s) {
@@ -14258,13 +15498,13 @@ impl Interpreter {
                 .unwrap_or_else(|_| b2he(&bytes));
             Some(Ok(Value::String(result)))
         }
-        None => Some(Err("base16_decode: invalid hex input".to_string())),
+        None => Some(Err(raise(ErrorKind::ValueError, "base16_decode: invalid hex input".to_string()))),
     }
 }
 
 "hex_to_bytes" => {
     if args.is_empty() {
-        return Some(Err("hex_to_bytes requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "hex_to_bytes requires 1 argument".to_string())));
     }
     let s = args[0].as_string();
     match he2b(&s) {
@@ -14272,17 +15512,17 @@ impl Interpreter {
             let arr: Vec<Value> = bytes.iter().map(|&b| Value::Int(b as i64)).collect();
             Some(Ok(Value::Array(arr)))
         }
-        None => Some(Err("hex_to_bytes: invalid hex string".to_string())),
+        None => Some(Err(raise(ErrorKind::ValueError, "hex_to_bytes: invalid hex string".to_string()))),
     }
 }
 
 "bytes_to_hex" => {
     if args.is_empty() {
-        return Some(Err("bytes_to_hex requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "bytes_to_hex requires 1 argument".to_string())));
     }
     let arr = match &args[0] {
         Value::Array(v) => v.clone(),
-        _ => return Some(Err("bytes_to_hex: expected Array".to_string())),
+        _ => return Some(Err(raise(ErrorKind::TypeError, "bytes_to_hex: expected Array".to_string()))),
     };
     let bytes: Vec<u8> = arr.iter().map(|v| v.as_int() as u8).collect();
     Some(Ok(Value::String(b2he(&bytes))))
@@ -14290,7 +15530,7 @@ impl Interpreter {
 
 "url_encode" => {
     if args.is_empty() {
-        return Some(Err("url_encode requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "url_encode requires 1 argument".to_string())));
     }
     let s = args[0].as_string();
     let mut encoded = String::with_capacity(s.len() * 3);
@@ -14312,7 +15552,7 @@ impl Interpreter {
 
 "url_decode" => {
     if args.is_empty() {
-        return Some(Err("url_decode requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "url_decode requires 1 argument".to_string())));
     }
     let s = args[0].as_string();
     let bytes_in = s.as_bytes();
@@ -14343,7 +15583,7 @@ impl Interpreter {
 
 "html_encode" => {
     if args.is_empty() {
-        return Some(Err("html_encode requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "html_encode requires 1 argument".to_string())));
     }
     let s = args[0].as_string();
     let mut out = String::with_capacity(s.len() * 2);
@@ -14362,7 +15602,7 @@ impl Interpreter {
 
 "html_decode" => {
     if args.is_empty() {
-        return Some(Err("html_decode requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "html_decode requires 1 argument".to_string())));
     }
     let s = args[0].as_string();
     let out = s
@@ -14377,7 +15617,7 @@ impl Interpreter {
 
 "punycode_encode" => {
     if args.is_empty() {
-        return Some(Err("punycode_encode requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "punycode_encode requires 1 argument".to_string())));
     }
     let s = args[0].as_string();
     // Simplified: per-label encoding for hostnames
@@ -14406,7 +15646,7 @@ impl Interpreter {
 
 "morse_encode" => {
     if args.is_empty() {
-        return Some(Err("morse_encode requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "morse_encode requires 1 argument".to_string())));
     }
     const MORSE: &[(&str, &str)] = &[
         ("A",".-"),("B","-..."),("C","-.-."),("D","-.."),("E","."),
@@ -14439,7 +15679,7 @@ impl Interpreter {
 
 "morse_decode" => {
     if args.is_empty() {
-        return Some(Err("morse_decode requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "morse_decode requires 1 argument".to_string())));
     }
     const MORSE: &[(&str, char)] = &[
         (".-",'A'),("-...",'B'),("-.-.",'C'),("-..",'D'),(".",'E'),
@@ -14491,12 +15731,12 @@ impl Interpreter {
         }
     }
     if args.is_empty() {
-        return Some(Err("msgpack_encode requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "msgpack_encode requires 1 argument".to_string())));
     }
     let json_val = val_to_json_mp(&args[0]);
     match rmp_serde::to_vec(&json_val) {
         Ok(bytes) => Some(Ok(Value::String(b2he(&bytes)))),
-        Err(e)    => Some(Err(format!("msgpack_encode error: {}", e))),
+        Err(e)    => Some(Err(raise(ErrorKind::Error, format!("msgpack_encode error: {}", e)))),
     }
 }
 
@@ -14518,16 +15758,16 @@ impl Interpreter {
         }
     }
     if args.is_empty() {
-        return Some(Err("msgpack_decode requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "msgpack_decode requires 1 argument".to_string())));
     }
     let hex = args[0].as_string();
     let bytes = match he2b(&hex) {
         Some(b) => b,
-        None    => return Some(Err("msgpack_decode: invalid hex input".to_string())),
+        None    => return Some(Err(raise(ErrorKind::ValueError, "msgpack_decode: invalid hex input".to_string()))),
     };
     match rmp_serde::from_slice::<serde_json::Value>(&bytes) {
         Ok(json_val) => Some(Ok(json_to_val_mp(json_val))),
-        Err(e)       => Some(Err(format!("msgpack_decode error: {}", e))),
+        Err(e)       => Some(Err(raise(ErrorKind::ValueError, format!("msgpack_decode error: {}", e)))),
     }
 }
 
@@ -14554,14 +15794,14 @@ impl Interpreter {
         }
     }
     if args.is_empty() {
-        return Some(Err("bincode_encode requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "bincode_encode requires 1 argument".to_string())));
     }
     let json_val  = val_to_json_bc(&args[0]);
     let json_str  = json_val.to_string();
     let json_bytes: Vec<u8> = json_str.into_bytes();
     match bincode::serialize(&json_bytes) {
         Ok(encoded) => Some(Ok(Value::String(b2he(&encoded)))),
-        Err(e)      => Some(Err(format!("bincode_encode error: {}", e))),
+        Err(e)      => Some(Err(raise(ErrorKind::Error, format!("bincode_encode error: {}", e)))),
     }
 }
 
@@ -14583,24 +15823,24 @@ impl Interpreter {
         }
     }
     if args.is_empty() {
-        return Some(Err("bincode_decode requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "bincode_decode requires 1 argument".to_string())));
     }
     let hex   = args[0].as_string();
     let bytes = match he2b(&hex) {
         Some(b) => b,
-        None    => return Some(Err("bincode_decode: invalid hex input".to_string())),
+        None    => return Some(Err(raise(ErrorKind::ValueError, "bincode_decode: invalid hex input".to_string()))),
     };
     let json_bytes: Vec<u8> = match bincode::deserialize(&bytes) {
         Ok(v)  => v,
-        Err(e) => return Some(Err(format!("bincode_decode error: {}", e))),
+        Err(e) => return Some(Err(raise(ErrorKind::Error, format!("bincode_decode error: {}", e)))),
     };
     let json_str = match std::str::from_utf8(&json_bytes) {
         Ok(s)  => s,
-        Err(e) => return Some(Err(format!("bincode_decode utf8 error: {}", e))),
+        Err(e) => return Some(Err(raise(ErrorKind::ValueError, format!("bincode_decode utf8 error: {}", e)))),
     };
     match serde_json::from_str::<serde_json::Value>(json_str) {
         Ok(json_val) => Some(Ok(json_to_val_bc(json_val))),
-        Err(e)       => Some(Err(format!("bincode_decode json error: {}", e))),
+        Err(e)       => Some(Err(raise(ErrorKind::ValueError, format!("bincode_decode json error: {}", e)))),
     }
 }
 
@@ -14627,7 +15867,7 @@ impl Interpreter {
         }
     }
     if args.len() < 3 {
-        return Some(Err("jwt_sign requires 3 arguments: payload_map, secret, algorithm".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "jwt_sign requires 3 arguments: payload_map, secret, algorithm".to_string())));
     }
     let secret_str = args[1].as_string();
     let alg_str    = args[2].as_string();
@@ -14635,20 +15875,20 @@ impl Interpreter {
         "HS256" => JwtAlgorithm::HS256,
         "HS384" => JwtAlgorithm::HS384,
         "HS512" => JwtAlgorithm::HS512,
-        other   => return Some(Err(format!("jwt_sign: unsupported algorithm '{}'", other))),
+        other   => return Some(Err(raise(ErrorKind::Error, format!("jwt_sign: unsupported algorithm '{}'", other)))),
     };
     let claims = val_to_json_jwt(&args[0]);
     let header = JwtHeader::new(algorithm);
     let key    = EncodingKey::from_secret(secret_str.as_bytes());
     match jwt_encode(&header, &claims, &key) {
         Ok(token) => Some(Ok(Value::String(token))),
-        Err(e)    => Some(Err(format!("jwt_sign error: {}", e))),
+        Err(e)    => Some(Err(raise(ErrorKind::Error, format!("jwt_sign error: {}", e)))),
     }
 }
 
 "jwt_verify" => {
     if args.len() < 3 {
-        return Some(Err("jwt_verify requires 3 arguments: token, secret, algorithm".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "jwt_verify requires 3 arguments: token, secret, algorithm".to_string())));
     }
     let token      = args[0].as_string();
     let secret_str = args[1].as_string();
@@ -14657,7 +15897,7 @@ impl Interpreter {
         "HS256" => JwtAlgorithm::HS256,
         "HS384" => JwtAlgorithm::HS384,
         "HS512" => JwtAlgorithm::HS512,
-        other   => return Some(Err(format!("jwt_verify: unsupported algorithm '{}'", other))),
+        other   => return Some(Err(raise(ErrorKind::Error, format!("jwt_verify: unsupported algorithm '{}'", other)))),
     };
     let key        = DecodingKey::from_secret(secret_str.as_bytes());
     let mut validation = Validation::new(algorithm);
@@ -14684,7 +15924,7 @@ impl Interpreter {
         }
     }
     if args.is_empty() {
-        return Some(Err("jwt_decode requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "jwt_decode requires 1 argument".to_string())));
     }
     let token = args[0].as_string();
     let mut validation = Validation::new(JwtAlgorithm::HS256);
@@ -14736,25 +15976,25 @@ impl Interpreter {
 }
     }
     if args.len() < 2 {
-        return Some(Err("template_render requires 2 arguments: template_str, data_map".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "template_render requires 2 arguments: template_str, data_map".to_string())));
     }
     let template_str = args[0].as_string();
     let json_data    = val_to_json_hb(&args[1]);
     match self.hbs.render_template(&template_str, &json_data) {
         Ok(rendered) => Some(Ok(Value::String(rendered))),
-        Err(e)       => Some(Err(format!("template_render error: {}", e))),
+        Err(e)       => Some(Err(raise(ErrorKind::Error, format!("template_render error: {}", e)))),
     }
 }
 
 "template_register" => {
     if args.len() < 2 {
-        return Some(Err("template_register requires 2 arguments: name, template_str".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "template_register requires 2 arguments: name, template_str".to_string())));
     }
     let name         = args[0].as_string();
     let template_str = args[1].as_string();
     match self.hbs.register_template_string(&name, &template_str) {
         Ok(())  => Some(Ok(Value::Bool(true))),
-        Err(e)  => Some(Err(format!("template_register error: {}", e))),
+        Err(e)  => Some(Err(raise(ErrorKind::Error, format!("template_register error: {}", e)))),
     }
 }
 
@@ -14779,19 +16019,19 @@ impl Interpreter {
 }
     }
     if args.len() < 2 {
-        return Some(Err("template_render_named requires 2 arguments: name, data_map".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "template_render_named requires 2 arguments: name, data_map".to_string())));
     }
     let name      = args[0].as_string();
     let json_data = val_to_json_hrn(&args[1]);
     match self.hbs.render(&name, &json_data) {
         Ok(rendered) => Some(Ok(Value::String(rendered))),
-        Err(e)       => Some(Err(format!("template_render_named error: {}", e))),
+        Err(e)       => Some(Err(raise(ErrorKind::Error, format!("template_render_named error: {}", e)))),
     }
 }
 
 "template_escape" => {
     if args.is_empty() {
-        return Some(Err("template_escape requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "template_escape requires 1 argument".to_string())));
     }
     let s = args[0].as_string();
     let escaped = handlebars::html_escape(&s);
@@ -14802,7 +16042,7 @@ impl Interpreter {
 
 "bit_and" => {
     if args.len() < 2 {
-        return Some(Err("bit_and requires 2 arguments".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "bit_and requires 2 arguments".to_string())));
     }
     let a = args[0].as_int() as u64;
     let b = args[1].as_int() as u64;
@@ -14811,7 +16051,7 @@ impl Interpreter {
 
 "bit_or" => {
     if args.len() < 2 {
-        return Some(Err("bit_or requires 2 arguments".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "bit_or requires 2 arguments".to_string())));
     }
     let a = args[0].as_int() as u64;
     let b = args[1].as_int() as u64;
@@ -14820,7 +16060,7 @@ impl Interpreter {
 
 "bit_xor" => {
     if args.len() < 2 {
-        return Some(Err("bit_xor requires 2 arguments".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "bit_xor requires 2 arguments".to_string())));
     }
     let a = args[0].as_int() as u64;
     let b = args[1].as_int() as u64;
@@ -14829,7 +16069,7 @@ impl Interpreter {
 
 "bit_not" => {
     if args.is_empty() {
-        return Some(Err("bit_not requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "bit_not requires 1 argument".to_string())));
     }
     let a = args[0].as_int() as u64;
     Some(Ok(Value::Int((!a) as i64)))
@@ -14837,7 +16077,7 @@ impl Interpreter {
 
 "bit_shl" => {
     if args.len() < 2 {
-        return Some(Err("bit_shl requires 2 arguments".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "bit_shl requires 2 arguments".to_string())));
     }
     let a = args[0].as_int() as u64;
     let n = (args[1].as_int() as u32) & 63;
@@ -14846,7 +16086,7 @@ impl Interpreter {
 
 "bit_shr" => {
     if args.len() < 2 {
-        return Some(Err("bit_shr requires 2 arguments".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "bit_shr requires 2 arguments".to_string())));
     }
     let a = args[0].as_int() as u64;
     let n = (args[1].as_int() as u32) & 63;
@@ -14855,7 +16095,7 @@ impl Interpreter {
 
 "bit_rotl" => {
     if args.len() < 2 {
-        return Some(Err("bit_rotl requires 2 arguments".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "bit_rotl requires 2 arguments".to_string())));
     }
     let a = args[0].as_int() as u64;
     let n = (args[1].as_int() as u32) & 63;
@@ -14864,7 +16104,7 @@ impl Interpreter {
 
 "bit_rotr" => {
     if args.len() < 2 {
-        return Some(Err("bit_rotr requires 2 arguments".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "bit_rotr requires 2 arguments".to_string())));
     }
     let a = args[0].as_int() as u64;
     let n = (args[1].as_int() as u32) & 63;
@@ -14873,7 +16113,7 @@ impl Interpreter {
 
 "bit_popcnt" => {
     if args.is_empty() {
-        return Some(Err("bit_popcnt requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "bit_popcnt requires 1 argument".to_string())));
     }
     let a = args[0].as_int() as u64;
     Some(Ok(Value::Int(a.count_ones() as i64)))
@@ -14881,7 +16121,7 @@ impl Interpreter {
 
 "bit_clz" => {
     if args.is_empty() {
-        return Some(Err("bit_clz requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "bit_clz requires 1 argument".to_string())));
     }
     let a = args[0].as_int() as u64;
     Some(Ok(Value::Int(a.leading_zeros() as i64)))
@@ -14889,7 +16129,7 @@ impl Interpreter {
 
 "bit_ctz" => {
     if args.is_empty() {
-        return Some(Err("bit_ctz requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "bit_ctz requires 1 argument".to_string())));
     }
     let a = args[0].as_int() as u64;
     Some(Ok(Value::Int(a.trailing_zeros() as i64)))
@@ -14897,7 +16137,7 @@ impl Interpreter {
 
 "bit_reverse" => {
     if args.is_empty() {
-        return Some(Err("bit_reverse requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "bit_reverse requires 1 argument".to_string())));
     }
     let a = args[0].as_int() as u64;
     Some(Ok(Value::Int(a.reverse_bits() as i64)))
@@ -14905,7 +16145,7 @@ impl Interpreter {
 
 "bit_get" => {
     if args.len() < 2 {
-        return Some(Err("bit_get requires 2 arguments".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "bit_get requires 2 arguments".to_string())));
     }
     let a   = args[0].as_int() as u64;
     let pos = (args[1].as_int() as u32) & 63;
@@ -14914,7 +16154,7 @@ impl Interpreter {
 
 "bit_set" => {
     if args.len() < 2 {
-        return Some(Err("bit_set requires 2 arguments".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "bit_set requires 2 arguments".to_string())));
     }
     let a   = args[0].as_int() as u64;
     let pos = (args[1].as_int() as u32) & 63;
@@ -14923,7 +16163,7 @@ impl Interpreter {
 
 "bit_clear" => {
     if args.len() < 2 {
-        return Some(Err("bit_clear requires 2 arguments".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "bit_clear requires 2 arguments".to_string())));
     }
     let a   = args[0].as_int() as u64;
     let pos = (args[1].as_int() as u32) & 63;
@@ -14932,7 +16172,7 @@ impl Interpreter {
 
 "bit_toggle" => {
     if args.len() < 2 {
-        return Some(Err("bit_toggle requires 2 arguments".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "bit_toggle requires 2 arguments".to_string())));
     }
     let a   = args[0].as_int() as u64;
     let pos = (args[1].as_int() as u32) & 63;
@@ -14941,14 +16181,14 @@ impl Interpreter {
 
 "bytes_to_int_be" => {
     if args.is_empty() {
-        return Some(Err("bytes_to_int_be requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "bytes_to_int_be requires 1 argument".to_string())));
     }
     let arr = match &args[0] {
         Value::Array(v) => v.clone(),
-        _ => return Some(Err("bytes_to_int_be: expected Array".to_string())),
+        _ => return Some(Err(raise(ErrorKind::TypeError, "bytes_to_int_be: expected Array".to_string()))),
     };
     if arr.len() > 8 {
-        return Some(Err("bytes_to_int_be: array too large (max 8 bytes)".to_string()));
+        return Some(Err(raise(ErrorKind::Error, "bytes_to_int_be: array too large (max 8 bytes)".to_string())));
     }
     let mut result: i64 = 0;
     for val in &arr {
@@ -14959,14 +16199,14 @@ impl Interpreter {
 
 "bytes_to_int_le" => {
     if args.is_empty() {
-        return Some(Err("bytes_to_int_le requires 1 argument".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "bytes_to_int_le requires 1 argument".to_string())));
     }
     let arr = match &args[0] {
         Value::Array(v) => v.clone(),
-        _ => return Some(Err("bytes_to_int_le: expected Array".to_string())),
+        _ => return Some(Err(raise(ErrorKind::TypeError, "bytes_to_int_le: expected Array".to_string()))),
     };
     if arr.len() > 8 {
-        return Some(Err("bytes_to_int_le: array too large (max 8 bytes)".to_string()));
+        return Some(Err(raise(ErrorKind::Error, "bytes_to_int_le: array too large (max 8 bytes)".to_string())));
     }
     let mut result: i64 = 0;
     for (i, val) in arr.iter().enumerate() {
@@ -14977,12 +16217,12 @@ impl Interpreter {
 
 "int_to_bytes_be" => {
     if args.len() < 2 {
-        return Some(Err("int_to_bytes_be requires 2 arguments: n, size".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "int_to_bytes_be requires 2 arguments: n, size".to_string())));
     }
     let n    = args[0].as_int();
     let size = args[1].as_int() as usize;
     if size == 0 || size > 8 {
-        return Some(Err("int_to_bytes_be: size must be 1..=8".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "int_to_bytes_be: size must be 1..=8".to_string())));
     }
     let n_u64 = n as u64;
     let mut bytes: Vec<Value> = Vec::with_capacity(size);
@@ -14994,12 +16234,12 @@ impl Interpreter {
 
 "int_to_bytes_le" => {
     if args.len() < 2 {
-        return Some(Err("int_to_bytes_le requires 2 arguments: n, size".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "int_to_bytes_le requires 2 arguments: n, size".to_string())));
     }
     let n    = args[0].as_int();
     let size = args[1].as_int() as usize;
     if size == 0 || size > 8 {
-        return Some(Err("int_to_bytes_le: size must be 1..=8".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "int_to_bytes_le: size must be 1..=8".to_string())));
     }
     let n_u64 = n as u64;
     let bytes: Vec<Value> = (0..size)
@@ -15156,7 +16396,7 @@ impl Interpreter {
 
         "sys_process_by_name" => {
             if args.is_empty() {
-                return Some(Err("sys_process_by_name requires 1 argument".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "sys_process_by_name requires 1 argument".to_string())));
             }
             let target_name = args[0].as_string();
             let sys = self.sysinfo_sys.get_or_insert_with(|| {
@@ -15182,7 +16422,7 @@ impl Interpreter {
         "sys_process_kill" => {
             use sysinfo::Pid;
             if args.is_empty() {
-                return Some(Err("sys_process_kill requires 1 argument".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "sys_process_kill requires 1 argument".to_string())));
             }
             let pid_val = args[0].as_int();
             let sys = self.sysinfo_sys.get_or_insert_with(|| {
@@ -15251,7 +16491,7 @@ impl Interpreter {
 
         "proc_run" => {
             if args.len() < 2 {
-                return Some(Err("proc_run requires 2 arguments".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "proc_run requires 2 arguments".to_string())));
             }
             let cmd = args[0].as_string();
             let cmd_args: Vec<String> = match &args[1] {
@@ -15259,7 +16499,7 @@ impl Interpreter {
                 _ => vec![],
             };
             match Command::new(&cmd).args(&cmd_args).output() {
-                Err(e) => Some(Err(e.to_string())),
+                Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 Ok(out) => {
                     let mut m: HashMap<String, Value> = HashMap::new();
                     m.insert("stdout".to_string(), Value::String(String::from_utf8_lossy(&out.stdout).to_string()));
@@ -15272,11 +16512,11 @@ impl Interpreter {
 
         "proc_run_shell" => {
             if args.is_empty() {
-                return Some(Err("proc_run_shell requires 1 argument".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "proc_run_shell requires 1 argument".to_string())));
             }
             let shell_cmd = args[0].as_string();
             match Command::new("sh").arg("-c").arg(&shell_cmd).output() {
-                Err(e) => Some(Err(e.to_string())),
+                Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 Ok(out) => {
                     let mut m: HashMap<String, Value> = HashMap::new();
                     m.insert("stdout".to_string(), Value::String(String::from_utf8_lossy(&out.stdout).to_string()));
@@ -15289,7 +16529,7 @@ impl Interpreter {
 
         "proc_spawn_bg" => {
             if args.len() < 2 {
-                return Some(Err("proc_spawn_bg requires 2 arguments".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "proc_spawn_bg requires 2 arguments".to_string())));
             }
             let cmd = args[0].as_string();
             let cmd_args: Vec<String> = match &args[1] {
@@ -15303,18 +16543,18 @@ impl Interpreter {
                 .stderr(Stdio::null())
                 .spawn()
             {
-                Err(e) => Some(Err(e.to_string())),
+                Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 Ok(child) => Some(Ok(Value::Int(child.id() as i64))),
             }
         }
 
         "proc_which" => {
             if args.is_empty() {
-                return Some(Err("proc_which requires 1 argument".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "proc_which requires 1 argument".to_string())));
             }
             let prog_name = args[0].as_string();
             match Command::new("which").arg(&prog_name).output() {
-                Err(e) => Some(Err(e.to_string())),
+                Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 Ok(out) => {
                     if out.status.success() {
                         let path = String::from_utf8_lossy(&out.stdout).trim().to_string();
@@ -15341,7 +16581,7 @@ impl Interpreter {
 
         "env_get" => {
             if args.is_empty() {
-                return Some(Err("env_get requires 1 argument".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "env_get requires 1 argument".to_string())));
             }
             let key = args[0].as_string();
             match std::env::var(&key) {
@@ -15352,7 +16592,7 @@ impl Interpreter {
 
         "env_set" => {
             if args.len() < 2 {
-                return Some(Err("env_set requires 2 arguments".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "env_set requires 2 arguments".to_string())));
             }
             let key = args[0].as_string();
             let val = args[1].as_string();
@@ -15362,7 +16602,7 @@ impl Interpreter {
 
         "env_unset" => {
             if args.is_empty() {
-                return Some(Err("env_unset requires 1 argument".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "env_unset requires 1 argument".to_string())));
             }
             let key = args[0].as_string();
             std::env::remove_var(&key);
@@ -15379,18 +16619,18 @@ impl Interpreter {
         "env_cwd" => {
             match std::env::current_dir() {
                 Ok(path) => Some(Ok(Value::String(path.to_string_lossy().to_string()))),
-                Err(e) => Some(Err(e.to_string())),
+                Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
             }
         }
 
         "env_set_cwd" => {
             if args.is_empty() {
-                return Some(Err("env_set_cwd requires 1 argument".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "env_set_cwd requires 1 argument".to_string())));
             }
             let path = args[0].as_string();
             match std::env::set_current_dir(&path) {
                 Ok(_) => Some(Ok(Value::Bool(true))),
-                Err(e) => Some(Err(e.to_string())),
+                Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
             }
         }
 
@@ -15402,7 +16642,7 @@ impl Interpreter {
         "env_exe" => {
             match std::env::current_exe() {
                 Ok(path) => Some(Ok(Value::String(path.to_string_lossy().to_string()))),
-                Err(e) => Some(Err(e.to_string())),
+                Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
             }
         }
 
@@ -15410,11 +16650,11 @@ impl Interpreter {
 
         "fs_glob" => {
             if args.is_empty() {
-                return Some(Err("fs_glob requires 1 argument".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "fs_glob requires 1 argument".to_string())));
             }
             let pattern = args[0].as_string();
             match glob::glob(&pattern) {
-                Err(e) => Some(Err(e.to_string())),
+                Err(e) => Some(Err(raise(ErrorKind::ValueError, e.to_string()))),
                 Ok(paths) => {
                     let results: Vec<Value> = paths
                         .filter_map(|p| p.ok())
@@ -15427,7 +16667,7 @@ impl Interpreter {
 
         "fs_walk" => {
             if args.is_empty() {
-                return Some(Err("fs_walk requires 1 argument".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "fs_walk requires 1 argument".to_string())));
             }
             let dir = args[0].as_string();
             let files: Vec<Value> = WalkDir::new(&dir)
@@ -15441,7 +16681,7 @@ impl Interpreter {
 
         "fs_walk_dirs" => {
             if args.is_empty() {
-                return Some(Err("fs_walk_dirs requires 1 argument".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "fs_walk_dirs requires 1 argument".to_string())));
             }
             let dir = args[0].as_string();
             let dirs: Vec<Value> = WalkDir::new(&dir)
@@ -15455,13 +16695,13 @@ impl Interpreter {
 
         "fs_find" => {
             if args.len() < 2 {
-                return Some(Err("fs_find requires 2 arguments".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "fs_find requires 2 arguments".to_string())));
             }
             let dir = args[0].as_string();
             let pattern = args[1].as_string();
             let full_pattern = format!("{}/{}", dir.trim_end_matches('/'), pattern);
             match glob::glob(&full_pattern) {
-                Err(e) => Some(Err(e.to_string())),
+                Err(e) => Some(Err(raise(ErrorKind::ValueError, e.to_string()))),
                 Ok(paths) => {
                     let results: Vec<Value> = paths
                         .filter_map(|p| p.ok())
@@ -15475,48 +16715,48 @@ impl Interpreter {
         "fs_symlink" => {
             use std::os::unix::fs::symlink;
             if args.len() < 2 {
-                return Some(Err("fs_symlink requires 2 arguments".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "fs_symlink requires 2 arguments".to_string())));
             }
             let target = args[0].as_string();
             let link = args[1].as_string();
             match symlink(&target, &link) {
                 Ok(_) => Some(Ok(Value::Bool(true))),
-                Err(e) => Some(Err(e.to_string())),
+                Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
             }
         }
 
         "fs_hardlink" => {
             if args.len() < 2 {
-                return Some(Err("fs_hardlink requires 2 arguments".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "fs_hardlink requires 2 arguments".to_string())));
             }
             let src = args[0].as_string();
             let dst = args[1].as_string();
             match fs::hard_link(&src, &dst) {
                 Ok(_) => Some(Ok(Value::Bool(true))),
-                Err(e) => Some(Err(e.to_string())),
+                Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
             }
         }
 
         "fs_chmod" => {
             use std::os::unix::fs::PermissionsExt;
             if args.len() < 2 {
-                return Some(Err("fs_chmod requires 2 arguments".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "fs_chmod requires 2 arguments".to_string())));
             }
             let path = args[0].as_string();
             let mode = args[1].as_int();
             match fs::set_permissions(&path, fs::Permissions::from_mode(mode as u32)) {
                 Ok(_) => Some(Ok(Value::Bool(true))),
-                Err(e) => Some(Err(e.to_string())),
+                Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
             }
         }
 
         "fs_stat" => {
             if args.is_empty() {
-                return Some(Err("fs_stat requires 1 argument".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "fs_stat requires 1 argument".to_string())));
             }
             let path = args[0].as_string();
             match fs::metadata(&path) {
-                Err(e) => Some(Err(e.to_string())),
+                Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 Ok(meta) => {
                     let ts_secs = |t: std::io::Result<SystemTime>| -> i64 {
                         t.ok()
@@ -15540,7 +16780,7 @@ impl Interpreter {
 
         "fs_disk_usage" => {
             if args.is_empty() {
-                return Some(Err("fs_disk_usage requires 1 argument".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "fs_disk_usage requires 1 argument".to_string())));
             }
             let dir = args[0].as_string();
             let total: u64 = WalkDir::new(&dir)
@@ -15562,7 +16802,7 @@ impl Interpreter {
             let dir = base.join(format!("knull_tmp_{}_{}", std::process::id(), nanos));
             match fs::create_dir_all(&dir) {
                 Ok(_) => Some(Ok(Value::String(dir.to_string_lossy().to_string()))),
-                Err(e) => Some(Err(e.to_string())),
+                Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
             }
         }
 
@@ -15575,13 +16815,13 @@ impl Interpreter {
             let file = base.join(format!("knull_tmp_{}_{}.tmp", std::process::id(), nanos));
             match fs::write(&file, b"") {
                 Ok(_) => Some(Ok(Value::String(file.to_string_lossy().to_string()))),
-                Err(e) => Some(Err(e.to_string())),
+                Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
             }
         }
 
         "fs_mime_type" => {
             if args.is_empty() {
-                return Some(Err("fs_mime_type requires 1 argument".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "fs_mime_type requires 1 argument".to_string())));
             }
             let path = args[0].as_string();
             let ext = Path::new(&path)
@@ -15646,12 +16886,12 @@ impl Interpreter {
         "fs_hash_file" => {
             use sha2::{Sha256, Sha512, Digest};
             if args.len() < 2 {
-                return Some(Err("fs_hash_file requires 2 arguments".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "fs_hash_file requires 2 arguments".to_string())));
             }
             let path = args[0].as_string();
             let algorithm = args[1].as_string();
             match fs::read(&path) {
-                Err(e) => Some(Err(e.to_string())),
+                Err(e) => Some(Err(raise(ErrorKind::IOError, e.to_string()))),
                 Ok(bytes) => {
                     let hex = match algorithm.as_str() {
                         "sha256" => {
@@ -15677,7 +16917,7 @@ impl Interpreter {
 
         "fs_watch_once" => {
             if args.len() < 2 {
-                return Some(Err("fs_watch_once requires 2 arguments".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "fs_watch_once requires 2 arguments".to_string())));
             }
             let watch_path = args[0].as_string();
             let timeout_ms = args[1].as_int() as u64;
@@ -15722,7 +16962,7 @@ impl Interpreter {
 
         "sleep_us" => {
             if args.is_empty() {
-                return Some(Err("sleep_us requires 1 argument".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "sleep_us requires 1 argument".to_string())));
             }
             let micros = args[0].as_int() as u64;
             std::thread::sleep(std::time::Duration::from_micros(micros));
@@ -15731,7 +16971,7 @@ impl Interpreter {
 
         "sleep_ns" => {
             if args.is_empty() {
-                return Some(Err("sleep_ns requires 1 argument".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "sleep_ns requires 1 argument".to_string())));
             }
             let nanos = args[0].as_int() as u64;
             std::thread::sleep(std::time::Duration::from_nanos(nanos));
@@ -15742,7 +16982,7 @@ impl Interpreter {
 
         "rand_seed" => {
             if args.is_empty() {
-                return Some(Err("rand_seed requires 1 argument".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "rand_seed requires 1 argument".to_string())));
             }
             let seed = args[0].as_int();
             std::env::set_var("KNULL_RAND_SEED", seed.to_string());
@@ -15753,7 +16993,7 @@ impl Interpreter {
             use rand::RngCore;
             use rand::rngs::OsRng;
             if args.len() < 2 {
-                return Some(Err("rand_float_range requires 2 arguments".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "rand_float_range requires 2 arguments".to_string())));
             }
             let min = args[0].as_float();
             let max = args[1].as_float();
@@ -15766,12 +17006,12 @@ impl Interpreter {
             use rand::RngCore;
             use rand::rngs::OsRng;
             if args.len() < 2 {
-                return Some(Err("rand_int_range requires 2 arguments".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "rand_int_range requires 2 arguments".to_string())));
             }
             let min = args[0].as_int();
             let max = args[1].as_int();
             if min >= max {
-                return Some(Err("rand_int_range: min must be less than max".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "rand_int_range: min must be less than max".to_string())));
             }
             let mut rng = OsRng;
             let range = (max - min) as u64;
@@ -15783,7 +17023,7 @@ impl Interpreter {
             use rand::RngCore;
             use rand::rngs::OsRng;
             if args.is_empty() {
-                return Some(Err("rand_bytes requires 1 argument".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "rand_bytes requires 1 argument".to_string())));
             }
             let n = args[0].as_int() as usize;
             let mut rng = OsRng;
@@ -15797,7 +17037,7 @@ impl Interpreter {
             use rand::RngCore;
             use rand::rngs::OsRng;
             if args.is_empty() {
-                return Some(Err("rand_choice requires 1 argument".to_string()));
+                return Some(Err(raise(ErrorKind::TypeError, "rand_choice requires 1 argument".to_string())));
             }
             match &args[0] {
                 Value::Array(arr) => {
@@ -15808,7 +17048,7 @@ impl Interpreter {
                     let idx = (rng.next_u64() as usize) % arr.len();
                     Some(Ok(arr[idx].clone()))
                 }
-                _ => Some(Err("rand_choice: argument must be an Array".to_string())),
+                _ => Some(Err(raise(ErrorKind::TypeError, "rand_choice: argument must be an Array".to_string()))),
             }
         }
 
@@ -15825,7 +17065,7 @@ impl Interpreter {
 
 "diff_lines" => {
     if args.len() < 2 {
-        return Some(Err("diff_lines: need 2 args".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "diff_lines: need 2 args".to_string())));
     }
     let old = args[0].as_string();
     let new_s = args[1].as_string();
@@ -15849,7 +17089,7 @@ impl Interpreter {
 
 "diff_chars" => {
     if args.len() < 2 {
-        return Some(Err("diff_chars: need 2 args".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "diff_chars: need 2 args".to_string())));
     }
     let old = args[0].as_string();
     let new_s = args[1].as_string();
@@ -15873,7 +17113,7 @@ impl Interpreter {
 
 "diff_words" => {
     if args.len() < 2 {
-        return Some(Err("diff_words: need 2 args".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "diff_words: need 2 args".to_string())));
     }
     let old = args[0].as_string();
     let new_s = args[1].as_string();
@@ -15897,7 +17137,7 @@ impl Interpreter {
 
 "diff_unified" => {
     if args.len() < 3 {
-        return Some(Err("diff_unified: need 3 args (old, new, context)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "diff_unified: need 3 args (old, new, context)".to_string())));
     }
     let old = args[0].as_string();
     let new_s = args[1].as_string();
@@ -15912,7 +17152,7 @@ impl Interpreter {
 
 "diff_ratio" => {
     if args.len() < 2 {
-        return Some(Err("diff_ratio: need 2 args".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "diff_ratio: need 2 args".to_string())));
     }
     let old = args[0].as_string();
     let new_s = args[1].as_string();
@@ -15922,11 +17162,11 @@ impl Interpreter {
 
 "diff_patch" => {
     if args.len() < 2 {
-        return Some(Err("diff_patch: need (original, diff_arr)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "diff_patch: need (original, diff_arr)".to_string())));
     }
     let arr = match &args[1] {
         Value::Array(v) => v.clone(),
-        _ => return Some(Err("diff_patch: second arg must be Array".to_string())),
+        _ => return Some(Err(raise(ErrorKind::TypeError, "diff_patch: second arg must be Array".to_string()))),
     };
     let mut out = String::new();
     for item in &arr {
@@ -15950,7 +17190,7 @@ impl Interpreter {
 
 "diff_is_same" => {
     if args.len() < 2 {
-        return Some(Err("diff_is_same: need 2 args".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "diff_is_same: need 2 args".to_string())));
     }
     Some(Ok(Value::Bool(args[0].as_string() == args[1].as_string())))
 }
@@ -15959,14 +17199,14 @@ impl Interpreter {
 
 "regex_match" => {
     if args.len() < 2 {
-        return Some(Err("regex_match: need (pattern, text)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "regex_match: need (pattern, text)".to_string())));
     }
     let pat = args[0].as_string();
     let txt = args[1].as_string();
     match FancyRegex::new(&pat) {
-        Err(e) => Some(Err(format!("regex_match: bad pattern: {}", e))),
+        Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("regex_match: bad pattern: {}", e)))),
         Ok(re) => match re.is_match(&txt) {
-            Err(e) => Some(Err(format!("regex_match: match error: {}", e))),
+            Err(e) => Some(Err(raise(ErrorKind::Error, format!("regex_match: match error: {}", e)))),
             Ok(b) => Some(Ok(Value::Bool(b))),
         },
     }
@@ -15974,14 +17214,14 @@ impl Interpreter {
 
 "regex_find" => {
     if args.len() < 2 {
-        return Some(Err("regex_find: need (pattern, text)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "regex_find: need (pattern, text)".to_string())));
     }
     let pat = args[0].as_string();
     let txt = args[1].as_string();
     match FancyRegex::new(&pat) {
-        Err(e) => Some(Err(format!("regex_find: bad pattern: {}", e))),
+        Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("regex_find: bad pattern: {}", e)))),
         Ok(re) => match re.find(&txt) {
-            Err(e) => Some(Err(format!("regex_find: error: {}", e))),
+            Err(e) => Some(Err(raise(ErrorKind::Error, format!("regex_find: error: {}", e)))),
             Ok(None) => Some(Ok(Value::Null)),
             Ok(Some(m)) => Some(Ok(Value::String(m.as_str().to_string()))),
         },
@@ -15990,18 +17230,18 @@ impl Interpreter {
 
 "regex_find_all" => {
     if args.len() < 2 {
-        return Some(Err("regex_find_all: need (pattern, text)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "regex_find_all: need (pattern, text)".to_string())));
     }
     let pat = args[0].as_string();
     let txt = args[1].as_string();
     match FancyRegex::new(&pat) {
-        Err(e) => Some(Err(format!("regex_find_all: bad pattern: {}", e))),
+        Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("regex_find_all: bad pattern: {}", e)))),
         Ok(re) => {
             let mut results = Vec::new();
             let mut start = 0usize;
             loop {
                 match re.find_from_pos(&txt, start) {
-                    Err(e) => return Some(Err(format!("regex_find_all: error: {}", e))),
+                    Err(e) => return Some(Err(raise(ErrorKind::Error, format!("regex_find_all: error: {}", e)))),
                     Ok(None) => break,
                     Ok(Some(m)) => {
                         results.push(Value::String(m.as_str().to_string()));
@@ -16024,14 +17264,14 @@ impl Interpreter {
 
 "regex_captures" => {
     if args.len() < 2 {
-        return Some(Err("regex_captures: need (pattern, text)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "regex_captures: need (pattern, text)".to_string())));
     }
     let pat = args[0].as_string();
     let txt = args[1].as_string();
     match FancyRegex::new(&pat) {
-        Err(e) => Some(Err(format!("regex_captures: bad pattern: {}", e))),
+        Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("regex_captures: bad pattern: {}", e)))),
         Ok(re) => match re.captures(&txt) {
-            Err(e) => Some(Err(format!("regex_captures: error: {}", e))),
+            Err(e) => Some(Err(raise(ErrorKind::Error, format!("regex_captures: error: {}", e)))),
             Ok(None) => Some(Ok(Value::Array(vec![]))),
             Ok(Some(caps)) => {
                 let groups: Vec<Value> = (0..caps.len())
@@ -16049,13 +17289,13 @@ impl Interpreter {
 
 "regex_replace" => {
     if args.len() < 3 {
-        return Some(Err("regex_replace: need (pattern, text, replacement)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "regex_replace: need (pattern, text, replacement)".to_string())));
     }
     let pat = args[0].as_string();
     let txt = args[1].as_string();
     let rep = args[2].as_string();
     match FancyRegex::new(&pat) {
-        Err(e) => Some(Err(format!("regex_replace: bad pattern: {}", e))),
+        Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("regex_replace: bad pattern: {}", e)))),
         Ok(re) => {
             let out = re.replace(&txt, rep.as_str());
             Some(Ok(Value::String(out.to_string())))
@@ -16065,13 +17305,13 @@ impl Interpreter {
 
 "regex_replace_all" => {
     if args.len() < 3 {
-        return Some(Err("regex_replace_all: need (pattern, text, replacement)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "regex_replace_all: need (pattern, text, replacement)".to_string())));
     }
     let pat = args[0].as_string();
     let txt = args[1].as_string();
     let rep = args[2].as_string();
     match FancyRegex::new(&pat) {
-        Err(e) => Some(Err(format!("regex_replace_all: bad pattern: {}", e))),
+        Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("regex_replace_all: bad pattern: {}", e)))),
         Ok(re) => {
             let out = re.replace_all(&txt, rep.as_str());
             Some(Ok(Value::String(out.to_string())))
@@ -16081,19 +17321,19 @@ impl Interpreter {
 
 "regex_split" => {
     if args.len() < 2 {
-        return Some(Err("regex_split: need (pattern, text)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "regex_split: need (pattern, text)".to_string())));
     }
     let pat = args[0].as_string();
     let txt = args[1].as_string();
     match FancyRegex::new(&pat) {
-        Err(e) => Some(Err(format!("regex_split: bad pattern: {}", e))),
+        Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("regex_split: bad pattern: {}", e)))),
         Ok(re) => {
             let mut parts: Vec<Value> = Vec::new();
             let mut last = 0usize;
             let mut start = 0usize;
             loop {
                 match re.find_from_pos(&txt, start) {
-                    Err(e) => return Some(Err(format!("regex_split: error: {}", e))),
+                    Err(e) => return Some(Err(raise(ErrorKind::Error, format!("regex_split: error: {}", e)))),
                     Ok(None) => {
                         parts.push(Value::String(txt[last..].to_string()));
                         break;
@@ -16121,7 +17361,7 @@ impl Interpreter {
 
 "regex_test" => {
     if args.is_empty() {
-        return Some(Err("regex_test: need (pattern)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "regex_test: need (pattern)".to_string())));
     }
     let pat = args[0].as_string();
     Some(Ok(Value::Bool(FancyRegex::new(&pat).is_ok())))
@@ -16129,14 +17369,14 @@ impl Interpreter {
 
 "regex_named_captures" => {
     if args.len() < 2 {
-        return Some(Err("regex_named_captures: need (pattern, text)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "regex_named_captures: need (pattern, text)".to_string())));
     }
     let pat = args[0].as_string();
     let txt = args[1].as_string();
     match FancyRegex::new(&pat) {
-        Err(e) => Some(Err(format!("regex_named_captures: bad pattern: {}", e))),
+        Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("regex_named_captures: bad pattern: {}", e)))),
         Ok(re) => match re.captures(&txt) {
-            Err(e) => Some(Err(format!("regex_named_captures: error: {}", e))),
+            Err(e) => Some(Err(raise(ErrorKind::Error, format!("regex_named_captures: error: {}", e)))),
             Ok(None) => Some(Ok(Value::Map(HashMap::new()))),
             Ok(Some(caps)) => {
                 // Extract named group names from the pattern using a simple scan
@@ -16192,7 +17432,7 @@ impl Interpreter {
 
 "vec2" => {
     if args.len() < 2 {
-        return Some(Err("vec2: need (x, y)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "vec2: need (x, y)".to_string())));
     }
     let x = args[0].as_float();
     let y = args[1].as_float();
@@ -16205,7 +17445,7 @@ impl Interpreter {
 
 "vec3" => {
     if args.len() < 3 {
-        return Some(Err("vec3: need (x, y, z)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "vec3: need (x, y, z)".to_string())));
     }
     let x = args[0].as_float();
     let y = args[1].as_float();
@@ -16220,7 +17460,7 @@ impl Interpreter {
 
 "vec4" => {
     if args.len() < 4 {
-        return Some(Err("vec4: need (x, y, z, w)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "vec4: need (x, y, z, w)".to_string())));
     }
     let x = args[0].as_float();
     let y = args[1].as_float();
@@ -16237,16 +17477,16 @@ impl Interpreter {
 
 "vec3_add" => {
     if args.len() < 2 {
-        return Some(Err("vec3_add: need (a, b)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "vec3_add: need (a, b)".to_string())));
     }
-    let get_v3 = |v: &Value| -> Result<glam::Vec3, String> {
+    let get_v3 = |v: &Value| -> Result<glam::Vec3, RuntimeError> {
         if let Value::Map(m) = v {
             let x = m.get("x").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let y = m.get("y").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let z = m.get("z").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             Ok(glam::Vec3::new(x, y, z))
         } else {
-            Err("expected vec3 map".to_string())
+            Err(raise(ErrorKind::TypeError, "expected vec3 map".to_string()))
         }
     };
     let a = match get_v3(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
@@ -16262,16 +17502,16 @@ impl Interpreter {
 
 "vec3_sub" => {
     if args.len() < 2 {
-        return Some(Err("vec3_sub: need (a, b)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "vec3_sub: need (a, b)".to_string())));
     }
-    let get_v3 = |v: &Value| -> Result<glam::Vec3, String> {
+    let get_v3 = |v: &Value| -> Result<glam::Vec3, RuntimeError> {
         if let Value::Map(m) = v {
             let x = m.get("x").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let y = m.get("y").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let z = m.get("z").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             Ok(glam::Vec3::new(x, y, z))
         } else {
-            Err("expected vec3 map".to_string())
+            Err(raise(ErrorKind::TypeError, "expected vec3 map".to_string()))
         }
     };
     let a = match get_v3(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
@@ -16287,16 +17527,16 @@ impl Interpreter {
 
 "vec3_mul" => {
     if args.len() < 2 {
-        return Some(Err("vec3_mul: need (v, scalar)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "vec3_mul: need (v, scalar)".to_string())));
     }
-    let get_v3 = |v: &Value| -> Result<glam::Vec3, String> {
+    let get_v3 = |v: &Value| -> Result<glam::Vec3, RuntimeError> {
         if let Value::Map(m) = v {
             let x = m.get("x").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let y = m.get("y").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let z = m.get("z").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             Ok(glam::Vec3::new(x, y, z))
         } else {
-            Err("expected vec3 map".to_string())
+            Err(raise(ErrorKind::TypeError, "expected vec3 map".to_string()))
         }
     };
     let v = match get_v3(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
@@ -16312,16 +17552,16 @@ impl Interpreter {
 
 "vec3_dot" => {
     if args.len() < 2 {
-        return Some(Err("vec3_dot: need (a, b)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "vec3_dot: need (a, b)".to_string())));
     }
-    let get_v3 = |v: &Value| -> Result<glam::Vec3, String> {
+    let get_v3 = |v: &Value| -> Result<glam::Vec3, RuntimeError> {
         if let Value::Map(m) = v {
             let x = m.get("x").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let y = m.get("y").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let z = m.get("z").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             Ok(glam::Vec3::new(x, y, z))
         } else {
-            Err("expected vec3 map".to_string())
+            Err(raise(ErrorKind::TypeError, "expected vec3 map".to_string()))
         }
     };
     let a = match get_v3(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
@@ -16331,16 +17571,16 @@ impl Interpreter {
 
 "vec3_cross" => {
     if args.len() < 2 {
-        return Some(Err("vec3_cross: need (a, b)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "vec3_cross: need (a, b)".to_string())));
     }
-    let get_v3 = |v: &Value| -> Result<glam::Vec3, String> {
+    let get_v3 = |v: &Value| -> Result<glam::Vec3, RuntimeError> {
         if let Value::Map(m) = v {
             let x = m.get("x").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let y = m.get("y").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let z = m.get("z").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             Ok(glam::Vec3::new(x, y, z))
         } else {
-            Err("expected vec3 map".to_string())
+            Err(raise(ErrorKind::TypeError, "expected vec3 map".to_string()))
         }
     };
     let a = match get_v3(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
@@ -16356,16 +17596,16 @@ impl Interpreter {
 
 "vec3_len" => {
     if args.is_empty() {
-        return Some(Err("vec3_len: need (v)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "vec3_len: need (v)".to_string())));
     }
-    let get_v3 = |v: &Value| -> Result<glam::Vec3, String> {
+    let get_v3 = |v: &Value| -> Result<glam::Vec3, RuntimeError> {
         if let Value::Map(m) = v {
             let x = m.get("x").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let y = m.get("y").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let z = m.get("z").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             Ok(glam::Vec3::new(x, y, z))
         } else {
-            Err("expected vec3 map".to_string())
+            Err(raise(ErrorKind::TypeError, "expected vec3 map".to_string()))
         }
     };
     let v = match get_v3(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
@@ -16374,16 +17614,16 @@ impl Interpreter {
 
 "vec3_normalize" => {
     if args.is_empty() {
-        return Some(Err("vec3_normalize: need (v)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "vec3_normalize: need (v)".to_string())));
     }
-    let get_v3 = |v: &Value| -> Result<glam::Vec3, String> {
+    let get_v3 = |v: &Value| -> Result<glam::Vec3, RuntimeError> {
         if let Value::Map(m) = v {
             let x = m.get("x").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let y = m.get("y").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let z = m.get("z").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             Ok(glam::Vec3::new(x, y, z))
         } else {
-            Err("expected vec3 map".to_string())
+            Err(raise(ErrorKind::TypeError, "expected vec3 map".to_string()))
         }
     };
     let v = match get_v3(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
@@ -16398,16 +17638,16 @@ impl Interpreter {
 
 "vec3_lerp" => {
     if args.len() < 3 {
-        return Some(Err("vec3_lerp: need (a, b, t)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "vec3_lerp: need (a, b, t)".to_string())));
     }
-    let get_v3 = |v: &Value| -> Result<glam::Vec3, String> {
+    let get_v3 = |v: &Value| -> Result<glam::Vec3, RuntimeError> {
         if let Value::Map(m) = v {
             let x = m.get("x").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let y = m.get("y").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let z = m.get("z").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             Ok(glam::Vec3::new(x, y, z))
         } else {
-            Err("expected vec3 map".to_string())
+            Err(raise(ErrorKind::TypeError, "expected vec3 map".to_string()))
         }
     };
     let a = match get_v3(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
@@ -16424,16 +17664,16 @@ impl Interpreter {
 
 "vec3_distance" => {
     if args.len() < 2 {
-        return Some(Err("vec3_distance: need (a, b)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "vec3_distance: need (a, b)".to_string())));
     }
-    let get_v3 = |v: &Value| -> Result<glam::Vec3, String> {
+    let get_v3 = |v: &Value| -> Result<glam::Vec3, RuntimeError> {
         if let Value::Map(m) = v {
             let x = m.get("x").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let y = m.get("y").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let z = m.get("z").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             Ok(glam::Vec3::new(x, y, z))
         } else {
-            Err("expected vec3 map".to_string())
+            Err(raise(ErrorKind::TypeError, "expected vec3 map".to_string()))
         }
     };
     let a = match get_v3(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
@@ -16443,16 +17683,16 @@ impl Interpreter {
 
 "vec3_reflect" => {
     if args.len() < 2 {
-        return Some(Err("vec3_reflect: need (v, normal)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "vec3_reflect: need (v, normal)".to_string())));
     }
-    let get_v3 = |v: &Value| -> Result<glam::Vec3, String> {
+    let get_v3 = |v: &Value| -> Result<glam::Vec3, RuntimeError> {
         if let Value::Map(m) = v {
             let x = m.get("x").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let y = m.get("y").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let z = m.get("z").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             Ok(glam::Vec3::new(x, y, z))
         } else {
-            Err("expected vec3 map".to_string())
+            Err(raise(ErrorKind::TypeError, "expected vec3 map".to_string()))
         }
     };
     let v = match get_v3(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
@@ -16469,16 +17709,16 @@ impl Interpreter {
 
 "vec3_angle" => {
     if args.len() < 2 {
-        return Some(Err("vec3_angle: need (a, b)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "vec3_angle: need (a, b)".to_string())));
     }
-    let get_v3 = |v: &Value| -> Result<glam::Vec3, String> {
+    let get_v3 = |v: &Value| -> Result<glam::Vec3, RuntimeError> {
         if let Value::Map(m) = v {
             let x = m.get("x").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let y = m.get("y").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let z = m.get("z").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             Ok(glam::Vec3::new(x, y, z))
         } else {
-            Err("expected vec3 map".to_string())
+            Err(raise(ErrorKind::TypeError, "expected vec3 map".to_string()))
         }
     };
     let a = match get_v3(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
@@ -16495,12 +17735,12 @@ impl Interpreter {
 
 "mat4_mul" => {
     if args.len() < 2 {
-        return Some(Err("mat4_mul: need (a, b) each Array of 16 floats".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "mat4_mul: need (a, b) each Array of 16 floats".to_string())));
     }
-    let arr_to_mat4 = |v: &Value| -> Result<glam::Mat4, String> {
+    let arr_to_mat4 = |v: &Value| -> Result<glam::Mat4, RuntimeError> {
         if let Value::Array(a) = v {
             if a.len() != 16 {
-                return Err("mat4 must be 16 elements".to_string());
+                return Err(raise(ErrorKind::TypeError, "mat4 must be 16 elements".to_string()));
             }
             let mut f = [0f32; 16];
             for (i, val) in a.iter().enumerate() {
@@ -16508,7 +17748,7 @@ impl Interpreter {
             }
             Ok(glam::Mat4::from_cols_array(&f))
         } else {
-            Err("expected Array of 16 floats for mat4".to_string())
+            Err(raise(ErrorKind::TypeError, "expected Array of 16 floats for mat4".to_string()))
         }
     };
     let ma = match arr_to_mat4(&args[0]) { Ok(m) => m, Err(e) => return Some(Err(e)) };
@@ -16521,7 +17761,7 @@ impl Interpreter {
 
 "mat4_translate" => {
     if args.len() < 3 {
-        return Some(Err("mat4_translate: need (x, y, z)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "mat4_translate: need (x, y, z)".to_string())));
     }
     let x = args[0].as_float() as f32;
     let y = args[1].as_float() as f32;
@@ -16534,7 +17774,7 @@ impl Interpreter {
 
 "mat4_scale" => {
     if args.len() < 3 {
-        return Some(Err("mat4_scale: need (x, y, z)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "mat4_scale: need (x, y, z)".to_string())));
     }
     let x = args[0].as_float() as f32;
     let y = args[1].as_float() as f32;
@@ -16547,7 +17787,7 @@ impl Interpreter {
 
 "mat4_rotate_x" => {
     if args.is_empty() {
-        return Some(Err("mat4_rotate_x: need (angle)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "mat4_rotate_x: need (angle)".to_string())));
     }
     let angle = args[0].as_float() as f32;
     let mat = glam::Mat4::from_rotation_x(angle);
@@ -16558,7 +17798,7 @@ impl Interpreter {
 
 "mat4_rotate_y" => {
     if args.is_empty() {
-        return Some(Err("mat4_rotate_y: need (angle)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "mat4_rotate_y: need (angle)".to_string())));
     }
     let angle = args[0].as_float() as f32;
     let mat = glam::Mat4::from_rotation_y(angle);
@@ -16569,7 +17809,7 @@ impl Interpreter {
 
 "mat4_rotate_z" => {
     if args.is_empty() {
-        return Some(Err("mat4_rotate_z: need (angle)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "mat4_rotate_z: need (angle)".to_string())));
     }
     let angle = args[0].as_float() as f32;
     let mat = glam::Mat4::from_rotation_z(angle);
@@ -16580,7 +17820,7 @@ impl Interpreter {
 
 "mat4_perspective" => {
     if args.len() < 4 {
-        return Some(Err("mat4_perspective: need (fov, aspect, near, far)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "mat4_perspective: need (fov, aspect, near, far)".to_string())));
     }
     let fov = args[0].as_float() as f32;
     let aspect = args[1].as_float() as f32;
@@ -16594,9 +17834,9 @@ impl Interpreter {
 
 "mat4_look_at" => {
     if args.len() < 3 {
-        return Some(Err("mat4_look_at: need (eye_arr, target_arr, up_arr)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "mat4_look_at: need (eye_arr, target_arr, up_arr)".to_string())));
     }
-    let arr_to_v3 = |v: &Value| -> Result<glam::Vec3, String> {
+    let arr_to_v3 = |v: &Value| -> Result<glam::Vec3, RuntimeError> {
         match v {
             Value::Array(a) if a.len() >= 3 => Ok(glam::Vec3::new(
                 a[0].as_float() as f32,
@@ -16608,7 +17848,7 @@ impl Interpreter {
                 m.get("y").map(|v| v.as_float()).unwrap_or(0.0) as f32,
                 m.get("z").map(|v| v.as_float()).unwrap_or(0.0) as f32,
             )),
-            _ => Err("expected Array[3] or vec3 map".to_string()),
+            _ => Err(raise(ErrorKind::TypeError, "expected Array[3] or vec3 map".to_string())),
         }
     };
     let eye = match arr_to_v3(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
@@ -16622,17 +17862,17 @@ impl Interpreter {
 
 "mat4_transform_point" => {
     if args.len() < 2 {
-        return Some(Err("mat4_transform_point: need (mat16, point3)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "mat4_transform_point: need (mat16, point3)".to_string())));
     }
     let mat = if let Value::Array(a) = &args[0] {
         if a.len() != 16 {
-            return Some(Err("mat4_transform_point: mat must be 16 elements".to_string()));
+            return Some(Err(raise(ErrorKind::TypeError, "mat4_transform_point: mat must be 16 elements".to_string())));
         }
         let mut f = [0f32; 16];
         for (i, val) in a.iter().enumerate() { f[i] = val.as_float() as f32; }
         glam::Mat4::from_cols_array(&f)
     } else {
-        return Some(Err("mat4_transform_point: first arg must be Array of 16".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "mat4_transform_point: first arg must be Array of 16".to_string())));
     };
     let pt = match &args[1] {
         Value::Array(a) if a.len() >= 3 => glam::Vec3::new(
@@ -16643,7 +17883,7 @@ impl Interpreter {
             m.get("y").map(|v| v.as_float()).unwrap_or(0.0) as f32,
             m.get("z").map(|v| v.as_float()).unwrap_or(0.0) as f32,
         ),
-        _ => return Some(Err("mat4_transform_point: invalid point".to_string())),
+        _ => return Some(Err(raise(ErrorKind::ValueError, "mat4_transform_point: invalid point".to_string()))),
     };
     let r = mat.transform_point3(pt);
     let mut m = HashMap::new();
@@ -16666,7 +17906,7 @@ impl Interpreter {
 
 "quat_from_axis_angle" => {
     if args.len() < 2 {
-        return Some(Err("quat_from_axis_angle: need (axis_arr, angle)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "quat_from_axis_angle: need (axis_arr, angle)".to_string())));
     }
     let axis = match &args[0] {
         Value::Array(a) if a.len() >= 3 => glam::Vec3::new(
@@ -16677,7 +17917,7 @@ impl Interpreter {
             m.get("y").map(|v| v.as_float()).unwrap_or(0.0) as f32,
             m.get("z").map(|v| v.as_float()).unwrap_or(0.0) as f32,
         ),
-        _ => return Some(Err("quat_from_axis_angle: invalid axis".to_string())),
+        _ => return Some(Err(raise(ErrorKind::ValueError, "quat_from_axis_angle: invalid axis".to_string()))),
     };
     let angle = args[1].as_float() as f32;
     let q = glam::Quat::from_axis_angle(axis.normalize_or_zero(), angle);
@@ -16692,9 +17932,9 @@ impl Interpreter {
 
 "quat_mul" => {
     if args.len() < 2 {
-        return Some(Err("quat_mul: need (a, b)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "quat_mul: need (a, b)".to_string())));
     }
-    let get_q = |v: &Value| -> Result<glam::Quat, String> {
+    let get_q = |v: &Value| -> Result<glam::Quat, RuntimeError> {
         if let Value::Map(m) = v {
             let x = m.get("x").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let y = m.get("y").map(|v| v.as_float()).unwrap_or(0.0) as f32;
@@ -16702,7 +17942,7 @@ impl Interpreter {
             let w = m.get("w").map(|v| v.as_float()).unwrap_or(1.0) as f32;
             Ok(glam::Quat::from_xyzw(x, y, z, w))
         } else {
-            Err("expected quat map".to_string())
+            Err(raise(ErrorKind::TypeError, "expected quat map".to_string()))
         }
     };
     let qa = match get_q(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
@@ -16719,9 +17959,9 @@ impl Interpreter {
 
 "quat_slerp" => {
     if args.len() < 3 {
-        return Some(Err("quat_slerp: need (a, b, t)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "quat_slerp: need (a, b, t)".to_string())));
     }
-    let get_q = |v: &Value| -> Result<glam::Quat, String> {
+    let get_q = |v: &Value| -> Result<glam::Quat, RuntimeError> {
         if let Value::Map(m) = v {
             let x = m.get("x").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let y = m.get("y").map(|v| v.as_float()).unwrap_or(0.0) as f32;
@@ -16729,7 +17969,7 @@ impl Interpreter {
             let w = m.get("w").map(|v| v.as_float()).unwrap_or(1.0) as f32;
             Ok(glam::Quat::from_xyzw(x, y, z, w))
         } else {
-            Err("expected quat map".to_string())
+            Err(raise(ErrorKind::TypeError, "expected quat map".to_string()))
         }
     };
     let qa = match get_q(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
@@ -16747,7 +17987,7 @@ impl Interpreter {
 
 "quat_to_euler" => {
     if args.is_empty() {
-        return Some(Err("quat_to_euler: need (q)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "quat_to_euler: need (q)".to_string())));
     }
     let q = if let Value::Map(map) = &args[0] {
         let x = map.get("x").map(|v| v.as_float()).unwrap_or(0.0) as f32;
@@ -16756,7 +17996,7 @@ impl Interpreter {
         let w = map.get("w").map(|v| v.as_float()).unwrap_or(1.0) as f32;
         glam::Quat::from_xyzw(x, y, z, w)
     } else {
-        return Some(Err("quat_to_euler: expected quat map".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "quat_to_euler: expected quat map".to_string())));
     };
     let (roll, pitch, yaw) = q.to_euler(glam::EulerRot::XYZ);
     let mut m = HashMap::new();
@@ -16768,7 +18008,7 @@ impl Interpreter {
 
 "quat_from_euler" => {
     if args.len() < 3 {
-        return Some(Err("quat_from_euler: need (x, y, z)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "quat_from_euler: need (x, y, z)".to_string())));
     }
     let ex = args[0].as_float() as f32;
     let ey = args[1].as_float() as f32;
@@ -16785,7 +18025,7 @@ impl Interpreter {
 
 "quat_rotate_vec3" => {
     if args.len() < 2 {
-        return Some(Err("quat_rotate_vec3: need (q, v)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "quat_rotate_vec3: need (q, v)".to_string())));
     }
     let q = if let Value::Map(map) = &args[0] {
         let x = map.get("x").map(|v| v.as_float()).unwrap_or(0.0) as f32;
@@ -16794,7 +18034,7 @@ impl Interpreter {
         let w = map.get("w").map(|v| v.as_float()).unwrap_or(1.0) as f32;
         glam::Quat::from_xyzw(x, y, z, w)
     } else {
-        return Some(Err("quat_rotate_vec3: expected quat map".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "quat_rotate_vec3: expected quat map".to_string())));
     };
     let v = match &args[1] {
         Value::Map(m) => glam::Vec3::new(
@@ -16805,7 +18045,7 @@ impl Interpreter {
         Value::Array(a) if a.len() >= 3 => glam::Vec3::new(
             a[0].as_float() as f32, a[1].as_float() as f32, a[2].as_float() as f32
         ),
-        _ => return Some(Err("quat_rotate_vec3: invalid vec3".to_string())),
+        _ => return Some(Err(raise(ErrorKind::ValueError, "quat_rotate_vec3: invalid vec3".to_string()))),
     };
     let r = q * v;
     let mut m = HashMap::new();
@@ -16818,7 +18058,7 @@ impl Interpreter {
 
 "quat_conjugate" => {
     if args.is_empty() {
-        return Some(Err("quat_conjugate: need (q)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "quat_conjugate: need (q)".to_string())));
     }
     let q = if let Value::Map(map) = &args[0] {
         let x = map.get("x").map(|v| v.as_float()).unwrap_or(0.0) as f32;
@@ -16827,7 +18067,7 @@ impl Interpreter {
         let w = map.get("w").map(|v| v.as_float()).unwrap_or(1.0) as f32;
         glam::Quat::from_xyzw(x, y, z, w)
     } else {
-        return Some(Err("quat_conjugate: expected quat map".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "quat_conjugate: expected quat map".to_string())));
     };
     let r = q.conjugate();
     let mut m = HashMap::new();
@@ -16841,7 +18081,7 @@ impl Interpreter {
 
 "quat_normalize" => {
     if args.is_empty() {
-        return Some(Err("quat_normalize: need (q)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "quat_normalize: need (q)".to_string())));
     }
     let q = if let Value::Map(map) = &args[0] {
         let x = map.get("x").map(|v| v.as_float()).unwrap_or(0.0) as f32;
@@ -16850,7 +18090,7 @@ impl Interpreter {
         let w = map.get("w").map(|v| v.as_float()).unwrap_or(1.0) as f32;
         glam::Quat::from_xyzw(x, y, z, w)
     } else {
-        return Some(Err("quat_normalize: expected quat map".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "quat_normalize: expected quat map".to_string())));
     };
     let r = q.normalize();
     let mut m = HashMap::new();
@@ -16864,9 +18104,9 @@ impl Interpreter {
 
 "quat_dot" => {
     if args.len() < 2 {
-        return Some(Err("quat_dot: need (a, b)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "quat_dot: need (a, b)".to_string())));
     }
-    let get_q = |v: &Value| -> Result<glam::Quat, String> {
+    let get_q = |v: &Value| -> Result<glam::Quat, RuntimeError> {
         if let Value::Map(m) = v {
             let x = m.get("x").map(|v| v.as_float()).unwrap_or(0.0) as f32;
             let y = m.get("y").map(|v| v.as_float()).unwrap_or(0.0) as f32;
@@ -16874,7 +18114,7 @@ impl Interpreter {
             let w = m.get("w").map(|v| v.as_float()).unwrap_or(1.0) as f32;
             Ok(glam::Quat::from_xyzw(x, y, z, w))
         } else {
-            Err("expected quat map".to_string())
+            Err(raise(ErrorKind::TypeError, "expected quat map".to_string()))
         }
     };
     let qa = match get_q(&args[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
@@ -16886,12 +18126,12 @@ impl Interpreter {
 
 "wav_read" => {
     if args.is_empty() {
-        return Some(Err("wav_read: need (path)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "wav_read: need (path)".to_string())));
     }
     let path = args[0].as_string();
     let mut reader = match hound::WavReader::open(&path) {
         Ok(r) => r,
-        Err(e) => return Some(Err(format!("wav_read: {}", e))),
+        Err(e) => return Some(Err(raise(ErrorKind::Error, format!("wav_read: {}", e)))),
     };
     let spec = reader.spec();
     let samples: Vec<Value> = match spec.sample_format {
@@ -16923,7 +18163,7 @@ impl Interpreter {
 
 "wav_write" => {
     if args.len() < 4 {
-        return Some(Err("wav_write: need (path, samples, sample_rate, channels)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "wav_write: need (path, samples, sample_rate, channels)".to_string())));
     }
     let path = args[0].as_string();
     let sample_rate = args[2].as_int() as u32;
@@ -16936,33 +18176,33 @@ impl Interpreter {
     };
     let mut writer = match hound::WavWriter::create(&path, spec) {
         Ok(w) => w,
-        Err(e) => return Some(Err(format!("wav_write: {}", e))),
+        Err(e) => return Some(Err(raise(ErrorKind::Error, format!("wav_write: {}", e)))),
     };
     let samples = match &args[1] {
         Value::Array(a) => a.clone(),
-        _ => return Some(Err("wav_write: samples must be Array".to_string())),
+        _ => return Some(Err(raise(ErrorKind::TypeError, "wav_write: samples must be Array".to_string()))),
     };
     for s in &samples {
         let fv = s.as_float().clamp(-1.0, 1.0);
         let iv = (fv * 32767.0) as i16;
         if let Err(e) = writer.write_sample(iv) {
-            return Some(Err(format!("wav_write: write error: {}", e)));
+            return Some(Err(raise(ErrorKind::Error, format!("wav_write: write error: {}", e))));
         }
     }
     if let Err(e) = writer.finalize() {
-        return Some(Err(format!("wav_write: finalize error: {}", e)));
+        return Some(Err(raise(ErrorKind::Error, format!("wav_write: finalize error: {}", e))));
     }
     Some(Ok(Value::Null))
 }
 
 "wav_info" => {
     if args.is_empty() {
-        return Some(Err("wav_info: need (path)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "wav_info: need (path)".to_string())));
     }
     let path = args[0].as_string();
     let reader = match hound::WavReader::open(&path) {
         Ok(r) => r,
-        Err(e) => return Some(Err(format!("wav_info: {}", e))),
+        Err(e) => return Some(Err(raise(ErrorKind::Error, format!("wav_info: {}", e)))),
     };
     let spec = reader.spec();
     let duration_samples = reader.duration();
@@ -16990,7 +18230,7 @@ impl Interpreter {
 
 "wav_generate_sine" => {
     if args.len() < 3 {
-        return Some(Err("wav_generate_sine: need (freq, duration_sec, sample_rate)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "wav_generate_sine: need (freq, duration_sec, sample_rate)".to_string())));
     }
     let freq = args[0].as_float();
     let dur = args[1].as_float();
@@ -17007,7 +18247,7 @@ impl Interpreter {
 
 "wav_generate_square" => {
     if args.len() < 3 {
-        return Some(Err("wav_generate_square: need (freq, duration_sec, sample_rate)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "wav_generate_square: need (freq, duration_sec, sample_rate)".to_string())));
     }
     let freq = args[0].as_float();
     let dur = args[1].as_float();
@@ -17025,7 +18265,7 @@ impl Interpreter {
 
 "wav_generate_sawtooth" => {
     if args.len() < 3 {
-        return Some(Err("wav_generate_sawtooth: need (freq, duration_sec, sample_rate)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "wav_generate_sawtooth: need (freq, duration_sec, sample_rate)".to_string())));
     }
     let freq = args[0].as_float();
     let dur = args[1].as_float();
@@ -17045,15 +18285,15 @@ impl Interpreter {
 
 "wav_mix" => {
     if args.len() < 3 {
-        return Some(Err("wav_mix: need (samples_a, samples_b, ratio)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "wav_mix: need (samples_a, samples_b, ratio)".to_string())));
     }
     let sa = match &args[0] {
         Value::Array(a) => a.clone(),
-        _ => return Some(Err("wav_mix: first arg must be Array".to_string())),
+        _ => return Some(Err(raise(ErrorKind::TypeError, "wav_mix: first arg must be Array".to_string()))),
     };
     let sb = match &args[1] {
         Value::Array(a) => a.clone(),
-        _ => return Some(Err("wav_mix: second arg must be Array".to_string())),
+        _ => return Some(Err(raise(ErrorKind::TypeError, "wav_mix: second arg must be Array".to_string()))),
     };
     let ratio = args[2].as_float().clamp(0.0, 1.0);
     let len = sa.len().max(sb.len());
@@ -17069,11 +18309,11 @@ impl Interpreter {
 
 "wav_volume" => {
     if args.len() < 2 {
-        return Some(Err("wav_volume: need (samples, factor)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "wav_volume: need (samples, factor)".to_string())));
     }
     let samples = match &args[0] {
         Value::Array(a) => a.clone(),
-        _ => return Some(Err("wav_volume: first arg must be Array".to_string())),
+        _ => return Some(Err(raise(ErrorKind::TypeError, "wav_volume: first arg must be Array".to_string()))),
     };
     let factor = args[1].as_float();
     let result: Vec<Value> = samples
@@ -17085,11 +18325,11 @@ impl Interpreter {
 
 "wav_reverse" => {
     if args.is_empty() {
-        return Some(Err("wav_reverse: need (samples)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "wav_reverse: need (samples)".to_string())));
     }
     let mut samples = match &args[0] {
         Value::Array(a) => a.clone(),
-        _ => return Some(Err("wav_reverse: arg must be Array".to_string())),
+        _ => return Some(Err(raise(ErrorKind::TypeError, "wav_reverse: arg must be Array".to_string()))),
     };
     samples.reverse();
     Some(Ok(Value::Array(samples)))
@@ -17097,11 +18337,11 @@ impl Interpreter {
 
 "wav_to_pcm16" => {
     if args.is_empty() {
-        return Some(Err("wav_to_pcm16: need (samples)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "wav_to_pcm16: need (samples)".to_string())));
     }
     let samples = match &args[0] {
         Value::Array(a) => a.clone(),
-        _ => return Some(Err("wav_to_pcm16: arg must be Array".to_string())),
+        _ => return Some(Err(raise(ErrorKind::TypeError, "wav_to_pcm16: arg must be Array".to_string()))),
     };
     let pcm: Vec<Value> = samples
         .iter()
@@ -17117,7 +18357,7 @@ impl Interpreter {
 
 "finance_pv" => {
     if args.len() < 4 {
-        return Some(Err("finance_pv: need (rate, nper, pmt, fv)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "finance_pv: need (rate, nper, pmt, fv)".to_string())));
     }
     let r = args[0].as_float();
     let n = args[1].as_float();
@@ -17134,7 +18374,7 @@ impl Interpreter {
 
 "finance_fv" => {
     if args.len() < 4 {
-        return Some(Err("finance_fv: need (rate, nper, pmt, pv)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "finance_fv: need (rate, nper, pmt, pv)".to_string())));
     }
     let r = args[0].as_float();
     let n = args[1].as_float();
@@ -17151,7 +18391,7 @@ impl Interpreter {
 
 "finance_nper" => {
     if args.len() < 4 {
-        return Some(Err("finance_nper: need (rate, pmt, pv, fv)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "finance_nper: need (rate, pmt, pv, fv)".to_string())));
     }
     let r = args[0].as_float();
     let pmt = args[1].as_float();
@@ -17159,14 +18399,14 @@ impl Interpreter {
     let fv = args[3].as_float();
     if r.abs() < 1e-12 {
         if pmt.abs() < 1e-12 {
-            return Some(Err("finance_nper: rate and pmt both zero".to_string()));
+            return Some(Err(raise(ErrorKind::ValueError, "finance_nper: rate and pmt both zero".to_string())));
         }
         return Some(Ok(Value::Float(-(pv + fv) / pmt)));
     }
     let num = pmt / r - fv;
     let den = pmt / r + pv;
     if den == 0.0 || (num / den) <= 0.0 {
-        return Some(Err("finance_nper: invalid inputs (log of non-positive)".to_string()));
+        return Some(Err(raise(ErrorKind::ValueError, "finance_nper: invalid inputs (log of non-positive)".to_string())));
     }
     let nper = (num / den).ln() / (1.0 + r).ln();
     Some(Ok(Value::Float(nper)))
@@ -17174,7 +18414,7 @@ impl Interpreter {
 
 "finance_pmt" => {
     if args.len() < 4 {
-        return Some(Err("finance_pmt: need (rate, nper, pv, fv)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "finance_pmt: need (rate, nper, pv, fv)".to_string())));
     }
     let r = args[0].as_float();
     let n = args[1].as_float();
@@ -17191,12 +18431,12 @@ impl Interpreter {
 
 "finance_npv" => {
     if args.len() < 2 {
-        return Some(Err("finance_npv: need (rate, cashflows)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "finance_npv: need (rate, cashflows)".to_string())));
     }
     let r = args[0].as_float();
     let cfs = match &args[1] {
         Value::Array(a) => a.clone(),
-        _ => return Some(Err("finance_npv: cashflows must be Array".to_string())),
+        _ => return Some(Err(raise(ErrorKind::TypeError, "finance_npv: cashflows must be Array".to_string()))),
     };
     let npv: f64 = cfs
         .iter()
@@ -17208,14 +18448,14 @@ impl Interpreter {
 
 "finance_irr" => {
     if args.is_empty() {
-        return Some(Err("finance_irr: need (cashflows)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "finance_irr: need (cashflows)".to_string())));
     }
     let cfs: Vec<f64> = match &args[0] {
         Value::Array(a) => a.iter().map(|v| v.as_float()).collect(),
-        _ => return Some(Err("finance_irr: cashflows must be Array".to_string())),
+        _ => return Some(Err(raise(ErrorKind::TypeError, "finance_irr: cashflows must be Array".to_string()))),
     };
     if cfs.is_empty() {
-        return Some(Err("finance_irr: cashflows empty".to_string()));
+        return Some(Err(raise(ErrorKind::ValueError, "finance_irr: cashflows empty".to_string())));
     }
     let npv_fn = |r: f64| -> f64 {
         cfs.iter()
@@ -17252,7 +18492,7 @@ impl Interpreter {
 
 "finance_compound" => {
     if args.len() < 4 {
-        return Some(Err("finance_compound: need (principal, rate, n_per_year, years)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "finance_compound: need (principal, rate, n_per_year, years)".to_string())));
     }
     let p = args[0].as_float();
     let r = args[1].as_float();
@@ -17264,7 +18504,7 @@ impl Interpreter {
 
 "finance_simple_interest" => {
     if args.len() < 3 {
-        return Some(Err("finance_simple_interest: need (principal, rate, years)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "finance_simple_interest: need (principal, rate, years)".to_string())));
     }
     let p = args[0].as_float();
     let r = args[1].as_float();
@@ -17274,7 +18514,7 @@ impl Interpreter {
 
 "finance_black_scholes_call" => {
     if args.len() < 5 {
-        return Some(Err("finance_black_scholes_call: need (S, K, T, r, sigma)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "finance_black_scholes_call: need (S, K, T, r, sigma)".to_string())));
     }
     let s = args[0].as_float();
     let k = args[1].as_float();
@@ -17282,7 +18522,7 @@ impl Interpreter {
     let r = args[3].as_float();
     let sigma = args[4].as_float();
     if t <= 0.0 || sigma <= 0.0 || s <= 0.0 || k <= 0.0 {
-        return Some(Err("finance_black_scholes_call: S, K, T, sigma must be positive".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "finance_black_scholes_call: S, K, T, sigma must be positive".to_string())));
     }
     let sqrt_t = t.sqrt();
     let d1 = ((s / k).ln() + (r + sigma * sigma / 2.0) * t) / (sigma * sqrt_t);
@@ -17304,7 +18544,7 @@ impl Interpreter {
 
 "finance_black_scholes_put" => {
     if args.len() < 5 {
-        return Some(Err("finance_black_scholes_put: need (S, K, T, r, sigma)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "finance_black_scholes_put: need (S, K, T, r, sigma)".to_string())));
     }
     let s = args[0].as_float();
     let k = args[1].as_float();
@@ -17312,7 +18552,7 @@ impl Interpreter {
     let r = args[3].as_float();
     let sigma = args[4].as_float();
     if t <= 0.0 || sigma <= 0.0 || s <= 0.0 || k <= 0.0 {
-        return Some(Err("finance_black_scholes_put: S, K, T, sigma must be positive".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "finance_black_scholes_put: S, K, T, sigma must be positive".to_string())));
     }
     let sqrt_t = t.sqrt();
     let d1 = ((s / k).ln() + (r + sigma * sigma / 2.0) * t) / (sigma * sqrt_t);
@@ -17332,58 +18572,58 @@ impl Interpreter {
 
 "finance_sharpe" => {
     if args.len() < 2 {
-        return Some(Err("finance_sharpe: need (returns_arr, risk_free_rate)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "finance_sharpe: need (returns_arr, risk_free_rate)".to_string())));
     }
     let returns: Vec<f64> = match &args[0] {
         Value::Array(a) => a.iter().map(|v| v.as_float()).collect(),
-        _ => return Some(Err("finance_sharpe: returns must be Array".to_string())),
+        _ => return Some(Err(raise(ErrorKind::TypeError, "finance_sharpe: returns must be Array".to_string()))),
     };
     let rf = args[1].as_float();
     let n = returns.len() as f64;
     if n == 0.0 {
-        return Some(Err("finance_sharpe: empty returns".to_string()));
+        return Some(Err(raise(ErrorKind::ValueError, "finance_sharpe: empty returns".to_string())));
     }
     let excess: Vec<f64> = returns.iter().map(|&r| r - rf).collect();
     let mean = excess.iter().sum::<f64>() / n;
     let variance = excess.iter().map(|&x| (x - mean).powi(2)).sum::<f64>() / n;
     let std_dev = variance.sqrt();
     if std_dev.abs() < 1e-15 {
-        return Some(Err("finance_sharpe: zero std deviation".to_string()));
+        return Some(Err(raise(ErrorKind::ValueError, "finance_sharpe: zero std deviation".to_string())));
     }
     Some(Ok(Value::Float(mean / std_dev)))
 }
 
 "finance_roi" => {
     if args.len() < 2 {
-        return Some(Err("finance_roi: need (gain, cost)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "finance_roi: need (gain, cost)".to_string())));
     }
     let gain = args[0].as_float();
     let cost = args[1].as_float();
     if cost.abs() < 1e-15 {
-        return Some(Err("finance_roi: cost cannot be zero".to_string()));
+        return Some(Err(raise(ErrorKind::ValueError, "finance_roi: cost cannot be zero".to_string())));
     }
     Some(Ok(Value::Float((gain - cost) / cost)))
 }
 
 "finance_cagr" => {
     if args.len() < 3 {
-        return Some(Err("finance_cagr: need (begin, end, years)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "finance_cagr: need (begin, end, years)".to_string())));
     }
     let begin = args[0].as_float();
     let end = args[1].as_float();
     let years = args[2].as_float();
     if begin <= 0.0 {
-        return Some(Err("finance_cagr: begin value must be positive".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "finance_cagr: begin value must be positive".to_string())));
     }
     if years <= 0.0 {
-        return Some(Err("finance_cagr: years must be positive".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "finance_cagr: years must be positive".to_string())));
     }
     Some(Ok(Value::Float((end / begin).powf(1.0 / years) - 1.0)))
 }
 
 "finance_mortgage" => {
     if args.len() < 3 {
-        return Some(Err("finance_mortgage: need (principal, annual_rate, years)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "finance_mortgage: need (principal, annual_rate, years)".to_string())));
     }
     let p = args[0].as_float();
     let annual_rate = args[1].as_float();
@@ -17403,7 +18643,7 @@ impl Interpreter {
 
 "str_format_number" => {
     if args.len() < 4 {
-        return Some(Err("str_format_number: need (n, decimals, thousands_sep, decimal_sep)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "str_format_number: need (n, decimals, thousands_sep, decimal_sep)".to_string())));
     }
     let n = args[0].as_float();
     let decimals = args[1].as_int() as usize;
@@ -17439,19 +18679,19 @@ impl Interpreter {
 
 "str_parse_number" => {
     if args.is_empty() {
-        return Some(Err("str_parse_number: need (s)".to_string()));
+        return Some(Err(raise(ErrorKind::ValueError, "str_parse_number: need (s)".to_string())));
     }
     let s = args[0].as_string();
     let cleaned: String = s.chars().filter(|&c| c != ',').collect();
     match cleaned.trim().parse::<f64>() {
         Ok(v) => Some(Ok(Value::Float(v))),
-        Err(e) => Some(Err(format!("str_parse_number: {}", e))),
+        Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("str_parse_number: {}", e)))),
     }
 }
 
 "str_lorem_ipsum" => {
     if args.is_empty() {
-        return Some(Err("str_lorem_ipsum: need (words)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "str_lorem_ipsum: need (words)".to_string())));
     }
     let n = args[0].as_int() as usize;
     let bank = [
@@ -17465,7 +18705,7 @@ impl Interpreter {
 
 "str_uuid_like" => {
     if args.is_empty() {
-        return Some(Err("str_uuid_like: need (s)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "str_uuid_like: need (s)".to_string())));
     }
     let s = args[0].as_string();
     // xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx
@@ -17482,7 +18722,7 @@ impl Interpreter {
 
 "str_email_valid" => {
     if args.is_empty() {
-        return Some(Err("str_email_valid: need (s)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "str_email_valid: need (s)".to_string())));
     }
     let s = args[0].as_string();
     let at_pos = s.find('@');
@@ -17497,7 +18737,7 @@ impl Interpreter {
 
 "str_ip_valid" => {
     if args.is_empty() {
-        return Some(Err("str_ip_valid: need (s)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "str_ip_valid: need (s)".to_string())));
     }
     let s = args[0].as_string();
     let parts: Vec<&str> = s.split('.').collect();
@@ -17512,7 +18752,7 @@ impl Interpreter {
 
 "str_mask" => {
     if args.len() < 4 {
-        return Some(Err("str_mask: need (s, start, end, char)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "str_mask: need (s, start, end, char)".to_string())));
     }
     let s = args[0].as_string();
     let start = args[1].as_int() as usize;
@@ -17530,12 +18770,12 @@ impl Interpreter {
 
 "str_contains_all" => {
     if args.len() < 2 {
-        return Some(Err("str_contains_all: need (s, arr)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "str_contains_all: need (s, arr)".to_string())));
     }
     let s = args[0].as_string();
     let needles = match &args[1] {
         Value::Array(a) => a.clone(),
-        _ => return Some(Err("str_contains_all: second arg must be Array".to_string())),
+        _ => return Some(Err(raise(ErrorKind::TypeError, "str_contains_all: second arg must be Array".to_string()))),
     };
     let all = needles.iter().all(|n| s.contains(&n.as_string() as &str));
     Some(Ok(Value::Bool(all)))
@@ -17543,12 +18783,12 @@ impl Interpreter {
 
 "str_contains_any" => {
     if args.len() < 2 {
-        return Some(Err("str_contains_any: need (s, arr)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "str_contains_any: need (s, arr)".to_string())));
     }
     let s = args[0].as_string();
     let needles = match &args[1] {
         Value::Array(a) => a.clone(),
-        _ => return Some(Err("str_contains_any: second arg must be Array".to_string())),
+        _ => return Some(Err(raise(ErrorKind::TypeError, "str_contains_any: second arg must be Array".to_string()))),
     };
     let any = needles.iter().any(|n| s.contains(&n.as_string() as &str));
     Some(Ok(Value::Bool(any)))
@@ -17556,7 +18796,7 @@ impl Interpreter {
 
 "str_count_occurrences" => {
     if args.len() < 2 {
-        return Some(Err("str_count_occurrences: need (s, substr)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "str_count_occurrences: need (s, substr)".to_string())));
     }
     let s = args[0].as_string();
     let sub = args[1].as_string();
@@ -17569,11 +18809,11 @@ impl Interpreter {
 
 "str_extract_numbers" => {
     if args.is_empty() {
-        return Some(Err("str_extract_numbers: need (s)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "str_extract_numbers: need (s)".to_string())));
     }
     let s = args[0].as_string();
     match FancyRegex::new(r"-?\d+(?:\.\d+)?") {
-        Err(e) => Some(Err(format!("str_extract_numbers: regex error: {}", e))),
+        Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("str_extract_numbers: regex error: {}", e)))),
         Ok(re) => {
             let mut results = Vec::new();
             let mut pos = 0usize;
@@ -17597,11 +18837,11 @@ impl Interpreter {
 
 "str_extract_urls" => {
     if args.is_empty() {
-        return Some(Err("str_extract_urls: need (s)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "str_extract_urls: need (s)".to_string())));
     }
     let s = args[0].as_string();
     match FancyRegex::new(r#"https?://[^\s<>"'{}|\\^\[\]`]+"#) {
-        Err(e) => Some(Err(format!("str_extract_urls: regex error: {}", e))),
+        Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("str_extract_urls: regex error: {}", e)))),
         Ok(re) => {
             let mut results = Vec::new();
             let mut pos = 0usize;
@@ -17623,11 +18863,11 @@ impl Interpreter {
 
 "str_extract_emails" => {
     if args.is_empty() {
-        return Some(Err("str_extract_emails: need (s)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "str_extract_emails: need (s)".to_string())));
     }
     let s = args[0].as_string();
     match FancyRegex::new(r"[a-zA-Z0-9._%+\-]+@[a-zA-Z0-9.\-]+\.[a-zA-Z]{2,}") {
-        Err(e) => Some(Err(format!("str_extract_emails: regex error: {}", e))),
+        Err(e) => Some(Err(raise(ErrorKind::ValueError, format!("str_extract_emails: regex error: {}", e)))),
         Ok(re) => {
             let mut results = Vec::new();
             let mut pos = 0usize;
@@ -17653,7 +18893,7 @@ impl Interpreter {
     // │  HELLO   │
     // └──────────┘
     if args.is_empty() {
-        return Some(Err("str_banner: need (s)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "str_banner: need (s)".to_string())));
     }
     let s = args[0].as_string();
     let inner = format!("  {}  ", s);
@@ -17666,7 +18906,7 @@ impl Interpreter {
 
 "str_soundex" => {
     if args.is_empty() {
-        return Some(Err("str_soundex: need (s)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "str_soundex: need (s)".to_string())));
     }
     let s = args[0].as_string().to_uppercase();
     let chars: Vec<char> = s.chars().filter(|c| c.is_ascii_alphabetic()).collect();
@@ -17707,7 +18947,7 @@ impl Interpreter {
 "str_metaphone" => {
     // Simplified metaphone: basic consonant reduction
     if args.is_empty() {
-        return Some(Err("str_metaphone: need (s)".to_string()));
+        return Some(Err(raise(ErrorKind::TypeError, "str_metaphone: need (s)".to_string())));
     }
     let s = args[0].as_string().to_uppercase();
     let chars: Vec<char> = s.chars().filter(|c| c.is_ascii_alphabetic()).collect();
@@ -18195,7 +19435,7 @@ pub fn execute_source(source: &str) -> Result<(), String> {
     let ast = parser.parse().map_err(|e| format!("Parse error: {}", e))?;
 
     let mut interpreter = Interpreter::new();
-    interpreter.execute(&ast)
+    Ok(interpreter.execute(&ast)?)
 }
 
 // ── REPL helpers ──────────────────────────────────────────────────────────────
@@ -18210,10 +19450,11 @@ impl Interpreter {
         match &ast {
             ASTNode::Program(items) if items.len() == 1 => {
                 let is_def = matches!(
-                    &items[0],
+                    items[0].inner(),
                     ASTNode::Function { .. }
                         | ASTNode::StructDef { .. }
                         | ASTNode::Impl { .. }
+                        | ASTNode::Test { .. }
                 );
                 if is_def {
                     self.execute(&ast)?;
@@ -18237,6 +19478,25 @@ impl Interpreter {
         }
     }
 
+    /// Like `repl_exec`, but on a copy of the session: variables, functions, types and
+    /// imports are left as they were, whatever the code assigns or defines.
+    pub fn repl_peek(&mut self, source: &str) -> Result<Option<Value>, String> {
+        let scopes = self.scopes.clone();
+        let functions = self.functions.clone();
+        let traits = self.traits.clone();
+        let trait_impls = self.trait_impls.clone();
+        let modules = self.modules.clone();
+        let module_ids = self.module_ids.clone();
+        let result = self.repl_exec(source);
+        self.scopes = scopes;
+        self.functions = functions;
+        self.traits = traits;
+        self.trait_impls = trait_impls;
+        self.modules = modules;
+        self.module_ids = module_ids;
+        result
+    }
+
     /// Return all variables visible in the current (global) scope.
     pub fn repl_variables(&self) -> Vec<(String, Value)> {
         let mut vars: Vec<(String, Value)> = Vec::new();
@@ -18256,4 +19516,285 @@ impl Interpreter {
         names.sort();
         names
     }
+
+    /// Frames open under an attached debugger, outermost first
+    pub fn debug_frames(&self) -> &[DebugFrame] {
+        &self.debug_frames
+    }
+
+    /// Variables declared in `frame` (an index into `debug_frames`), sorted by
+    /// name; globals are not included
+    pub fn frame_locals(&self, frame: usize) -> Vec<(String, Value)> {
+        let mut vars = HashMap::new();
+        for scope in &self.scopes[self.frame_scopes(frame)] {
+            for (name, value) in &scope.variables {
+                vars.insert(name.clone(), value.clone());
+            }
+        }
+        let mut vars: Vec<_> = vars.into_iter().collect();
+        vars.sort_by(|a, b| a.0.cmp(&b.0));
+        vars
+    }
+
+    // The slice of `scopes` that belongs to `frame`
+    fn frame_scopes(&self, frame: usize) -> std::ops::Range<usize> {
+        let start = self.debug_frames.get(frame).map_or(1, |f| f.scope_base).min(self.scopes.len());
+        let end = self.debug_frames.get(frame + 1).map_or(self.scopes.len(), |f| f.scope_base);
+        start..end.max(start)
+    }
+
+    /// Evaluate `source` as though it were the next statement of `frame`; the
+    /// scopes of the frames it called are hidden until it finishes
+    pub fn evaluate_in_frame(&mut self, frame: usize, source: &str) -> Result<Value, RuntimeError> {
+        let ast = crate::parser::Parser::new(source).parse().map_err(|e| RuntimeError::new(ErrorKind::ValueError, e))?;
+        let items = match ast {
+            ASTNode::Program(items) => items,
+            other => vec![other],
+        };
+        let top = self.frame_scopes(frame).end.max(1);
+        let hidden = self.scopes.split_off(top);
+        let return_value = self.return_value.take();
+        let break_flag = std::mem::replace(&mut self.break_flag, false);
+        let continue_flag = std::mem::replace(&mut self.continue_flag, false);
+        let mut result = Ok(Value::Null);
+        for item in &items {
+            result = self.evaluate(item);
+            if result.is_err() {
+                break;
+            }
+        }
+        self.unwind_scopes(top);
+        self.scopes.extend(hidden);
+        self.return_value = return_value;
+        self.break_flag = break_flag;
+        self.continue_flag = continue_flag;
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn run(source: &str) -> (Interpreter, Result<(), RuntimeError>) {
+        let ast = Parser::new(source).with_file("t.knull").parse().unwrap();
+        let mut interp = Interpreter::new();
+        let result = interp.execute(&ast);
+        (interp, result)
+    }
+
+    fn global(interp: &Interpreter, name: &str) -> Value {
+        interp.scopes[0].get(name).unwrap_or(Value::Null)
+    }
+
+    #[test]
+    fn test_error_kind_and_stack() {
+        let src = "fn inner(a) {\n    return a / 0\n}\nfn outer() {\n    inner(1)\n}\nfn main() {\n    outer()\n}";
+        let err = run(src).1.unwrap_err();
+        assert_eq!(err.kind, ErrorKind::ArithmeticError);
+        assert_eq!(err.message, "Division by zero");
+        assert_eq!(err.span.as_ref().map(|s| (s.line, s.col)), Some((2, 12)));
+        let frames: Vec<_> = err.stack.iter()
+            .map(|f| (f.function.as_str(), f.span.as_ref().map(|s| s.line)))
+            .collect();
+        assert_eq!(frames, vec![("inner", Some(2)), ("outer", Some(5)), ("main", Some(8))]);
+    }
+
+    #[test]
+    fn test_recursion_limit_raises_catchable_error() {
+        let src = "fn down(n) { if n == 0 { return 0 }\n down(n - 1) + 1 }\nlet ok = down(40)\nlet kind = try { down(1000) } catch e { e.kind }\nlet after = down(40)";
+        let globals = with_large_stack(|| {
+            let ast = Parser::new(src).parse().unwrap();
+            let mut interp = Interpreter::new().with_max_depth(50);
+            interp.execute(&ast).unwrap();
+            ["ok", "kind", "after"].map(|name| global(&interp, name))
+        });
+        assert_eq!(globals[0], Value::Int(40));
+        assert_eq!(globals[1], Value::String("RecursionError".to_string()));
+        assert_eq!(globals[2], Value::Int(40));
+    }
+
+    #[test]
+    fn test_default_depth_fits_the_stack() {
+        let src = format!(
+            "fn down(n) {{ if n == 0 {{ return 0 }}\n [(down(n - 1) + 1) * 1][0] }}\nlet ok = down({})\nlet err = try {{ down({}) }} catch e {{ \"\" + e }}",
+            DEFAULT_MAX_DEPTH - 1,
+            DEFAULT_MAX_DEPTH + 1
+        );
+        let globals = with_large_stack(|| {
+            let ast = Parser::new(&src).parse().unwrap();
+            let mut interp = Interpreter::new();
+            interp.execute(&ast).unwrap();
+            ["ok", "err"].map(|name| global(&interp, name))
+        });
+        assert_eq!(globals[0], Value::Int(DEFAULT_MAX_DEPTH as i64 - 1));
+        let expected = format!("maximum recursion depth exceeded ({} calls)", DEFAULT_MAX_DEPTH);
+        assert_eq!(globals[1], Value::String(expected));
+    }
+
+    #[test]
+    fn test_catch_binds_error_map() {
+        let src = "let kind = null\nlet msg = null\ntry {\n    [1][3]\n} catch e {\n    kind = e.kind\n    msg = \"\" + e\n}";
+        let (interp, result) = run(src);
+        result.unwrap();
+        assert_eq!(global(&interp, "kind"), Value::String("IndexError".to_string()));
+        assert_eq!(global(&interp, "msg"), Value::String("Index out of bounds".to_string()));
+    }
+
+    #[test]
+    fn test_error_kinds_are_explicit() {
+        let src = "fn kind(f) { try { f() } catch e { return e.kind } }\nlet kinds = [kind(fn() { file_read(\"/no/such/file.knull\") }), kind(fn() { 1 % 0 }), kind(fn() { let a = [1]\n a[5] = 2 }), kind(fn() { big_div(\"1\", \"0\") }), kind(fn() { regex_match(\"(\", \"a\") }), kind(fn() { assert(false, \"no\") }), kind(fn() { tcp_recv() }), kind(fn() { nope() })]";
+        let (interp, result) = run(src);
+        result.unwrap();
+        let names = |v: &[&str]| Value::Array(v.iter().map(|n| Value::String(n.to_string())).collect());
+        assert_eq!(global(&interp, "kinds"), names(&["IOError", "ArithmeticError", "IndexError", "ArithmeticError", "ValueError", "AssertionError", "TypeError", "NameError"]));
+    }
+
+    #[test]
+    fn test_thrown_value_caught_intact() {
+        let src = "let code = 0\ntry {\n    map([1, 2], fn(x) { throw {code: x * 10} })\n} catch e {\n    code = e.code\n}";
+        let (interp, result) = run(src);
+        result.unwrap();
+        assert_eq!(global(&interp, "code"), Value::Int(10));
+    }
+
+    #[test]
+    fn test_defer_runs_lifo_on_return_and_error() {
+        let src = "let log = \"\"\nfn f(n) {\n    defer log = log + \"a\"\n    defer { log = log + \"b\" }\n    if n == 0 { return 1 / n }\n    return n\n}\nfn main() {\n    f(1)\n    log = log + \"|\"\n    try { f(0) } catch e { log = log + \"!\" }\n}";
+        let (interp, result) = run(src);
+        result.unwrap();
+        assert_eq!(global(&interp, "log"), Value::String("ba|ba!".to_string()));
+    }
+
+    #[test]
+    fn test_uncaught_throw_is_user_throw() {
+        let err = run("throw \"boom\"").1.unwrap_err();
+        assert_eq!(err.kind, ErrorKind::UserThrow);
+        assert_eq!(err.message, "boom");
+        assert!(matches!(err.value, Some(Value::String(_))));
+    }
+
+    #[test]
+    fn test_module_namespace_and_privacy() {
+        let dir = std::env::temp_dir().join(format!("knull_interp_mod_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("counter.knull"),
+            "let loads = 1\npub let label = \"ctr\"\npub fn bump() { return helper() + 1 }\nfn helper() { return 41 }",
+        ).unwrap();
+        let main = dir.join("main.knull");
+        let src = "use counter as c\nimport \"counter\" as again\nlet n = c.bump()\nlet l = again.label\nlet err = null\ntry { c.helper() } catch e { err = e.message }";
+        let ast = Parser::new(src).parse().unwrap();
+        let mut interp = Interpreter::new().with_file(&main);
+        let result = interp.execute(&ast);
+        std::fs::remove_dir_all(&dir).unwrap();
+        result.unwrap();
+        assert_eq!(interp.modules.len(), 1);
+        assert_eq!(global(&interp, "n"), Value::Int(42));
+        assert_eq!(global(&interp, "l"), Value::String("ctr".to_string()));
+        assert_eq!(global(&interp, "err"), Value::String("'helper' is private to module counter".to_string()));
+        assert_eq!(global(&interp, "helper"), Value::Null);
+    }
+
+    #[test]
+    fn test_enum_construct_and_match() {
+        let src = "enum Shape { Circle(f64), Rect(f64, f64), Point { x: f64, y: f64 }, Empty }\nimpl Shape {\n    fn area(self) {\n        match self {\n            Shape::Circle(r) => r * r,\n            Shape::Rect(w, h) => w * h,\n            Shape::Point { x, .. } => x,\n            Empty => 0,\n        }\n    }\n}\nlet areas = [Shape::Circle(3).area(), Shape::Rect(2, 5).area(), Shape::Point { x: 7, y: 1 }.area(), Shape::Empty.area()]\nlet shown = \"\" + Shape::Rect(2, 5)\nlet same = Shape::Circle(1) == Shape::Circle(1)";
+        let (interp, result) = run(src);
+        result.unwrap();
+        let ints = |v: &[i64]| Value::Array(v.iter().map(|n| Value::Int(*n)).collect());
+        assert_eq!(global(&interp, "areas"), ints(&[9, 10, 7, 0]));
+        assert_eq!(global(&interp, "shown"), Value::String("Shape::Rect(2, 5)".to_string()));
+        assert_eq!(global(&interp, "same"), Value::Bool(true));
+
+        let err = run("enum Shape { Rect(f64, f64) }\nShape::Rect(1)").1.unwrap_err();
+        assert_eq!(err.kind, ErrorKind::TypeError);
+    }
+
+    #[test]
+    fn test_traits_defaults_operators_and_display() {
+        let src = "trait Shape {\n    fn area(self);\n    fn twice(self) { self.area() * 2 }\n}\nstruct Sq { s }\nimpl Shape for Sq {\n    fn area(self) { self.s * self.s }\n}\nstruct V { x }\nimpl Add for V {\n    fn add(self, o) { V { x: self.x + o.x } }\n}\nimpl Display for V {\n    fn to_string(self) { \"V\" + str(self.x) }\n}\nlet twice = Sq { s: 3 }.twice()\nlet sum = to_string(V { x: 1 } + V { x: 2 })\nlet shown = f\"{V { x: 5 }}\"\nlet checks = [implements(Sq { s: 1 }, \"Shape\"), implements(V { x: 1 }, \"Shape\"), implements(1, \"Add\")]";
+        let (interp, result) = run(src);
+        result.unwrap();
+        assert_eq!(global(&interp, "twice"), Value::Int(18));
+        assert_eq!(global(&interp, "sum"), Value::String("V3".to_string()));
+        assert_eq!(global(&interp, "shown"), Value::String("V5".to_string()));
+        let bools = |v: &[bool]| Value::Array(v.iter().map(|b| Value::Bool(*b)).collect());
+        assert_eq!(global(&interp, "checks"), bools(&[true, false, true]));
+
+        let err = run("trait T { fn need(self); }\nstruct S { }\nimpl T for S { }").1.unwrap_err();
+        assert_eq!(err.kind, ErrorKind::TypeError);
+        let err = run("fn show(x: Display) { x }\nstruct S { }\nshow(S { })").1.unwrap_err();
+        assert_eq!(err.message, "argument 'x' of show does not implement Display");
+    }
+
+    #[test]
+    fn test_method_mutations_write_back_to_receiver() {
+        let src = "struct C { n }\nimpl C {\n    fn inc(self) { self.n += 1 }\n    fn inc2(self) { self.inc()\n self.inc() }\n}\nlet c = C { n: 0 }\nc.inc2()\nlet holder = { list: [C { n: 10 }], one: C { n: 20 } }\nholder.list[0].inc()\nholder.one.inc()\nlet grid = [[0, 0], [0, 0]]\ngrid[1][0] = 5";
+        let (interp, result) = run(src);
+        result.unwrap();
+        assert_eq!(global(&interp, "c").get_field("n"), Some(Value::Int(2)));
+        let holder = global(&interp, "holder");
+        let list = holder.get_field("list").unwrap();
+        assert!(matches!(&list, Value::Array(a) if a[0].get_field("n") == Some(Value::Int(11))));
+        assert_eq!(holder.get_field("one").unwrap().get_field("n"), Some(Value::Int(21)));
+        let ints = |v: &[i64]| Value::Array(v.iter().map(|n| Value::Int(*n)).collect());
+        assert_eq!(global(&interp, "grid"), Value::Array(vec![ints(&[0, 0]), ints(&[5, 0])]));
+    }
+
+    #[test]
+    fn test_program_shadowing_stays_out_of_modules() {
+        let dir = std::env::temp_dir().join(format!("knull_interp_shadow_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("util.knull"), "pub fn count(xs) { return len(xs) }").unwrap();
+        let src = "fn len(x) { return 999 }\nuse util as u\nlet inside = u.count([1, 2, 3])\nlet outside = len([1])";
+        let ast = Parser::new(src).parse().unwrap();
+        let mut interp = Interpreter::new().with_file(&dir.join("main.knull"));
+        let result = interp.execute(&ast);
+        std::fs::remove_dir_all(&dir).unwrap();
+        result.unwrap();
+        assert_eq!(global(&interp, "inside"), Value::Int(3));
+        assert_eq!(global(&interp, "outside"), Value::Int(999));
+    }
+
+    #[test]
+    fn test_places_evaluate_their_indices_once() {
+        let src = "struct C { n }\nimpl C {\n    fn inc(self) { self.n += 1 }\n}\nlet calls = 0\nfn next() {\n    calls += 1\n    return calls - 1\n}\nlet cs = [C { n: 0 }, C { n: 10 }, C { n: 20 }]\ncs[next()].inc()\nlet xs = [0, 0, 0, 0]\nxs[next()] += 5\nxs[next()] = 7";
+        let (interp, result) = run(src);
+        result.unwrap();
+        assert_eq!(global(&interp, "calls"), Value::Int(3));
+        let ns: Vec<_> = match global(&interp, "cs") {
+            Value::Array(cs) => cs.iter().map(|c| c.get_field("n")).collect(),
+            other => panic!("{:?}", other),
+        };
+        assert_eq!(ns, [Some(Value::Int(1)), Some(Value::Int(10)), Some(Value::Int(20))]);
+        let ints = |v: &[i64]| Value::Array(v.iter().map(|n| Value::Int(*n)).collect());
+        assert_eq!(global(&interp, "xs"), ints(&[0, 5, 7, 0]));
+    }
+
+    #[test]
+    fn test_user_functions_shadow_builtins() {
+        let src = "fn len(x) { builtin::len(x) * 10 }\nfn ok(v) { \"ok:\" + v }\nlet a = len([1, 2])\nlet b = ok(\"x\")\nlet c = builtin::len(\"abc\")";
+        let (interp, result) = run(src);
+        result.unwrap();
+        assert_eq!(global(&interp, "a"), Value::Int(20));
+        assert_eq!(global(&interp, "b"), Value::String("ok:x".to_string()));
+        assert_eq!(global(&interp, "c"), Value::Int(3));
+        assert!(is_builtin("len") && !is_builtin("no_such_builtin"));
+
+        let (_, result) = run("builtin::no_such_builtin()");
+        assert_eq!(result.unwrap_err().kind, ErrorKind::NameError);
+    }
+
+    #[test]
+    fn test_repl_peek_leaves_the_session_alone() {
+        let mut interp = Interpreter::new();
+        interp.repl_exec("let n = 1").unwrap();
+        interp.repl_exec("fn bump() { n = n + 1\n return n }").unwrap();
+        let peeked = interp.repl_peek("{ fn extra() { 0 }\n bump() }").unwrap();
+        assert_eq!(peeked, Some(Value::Int(2)));
+        assert_eq!(global(&interp, "n"), Value::Int(1));
+        assert!(!interp.repl_functions().contains(&"extra".to_string()));
+        assert_eq!(interp.repl_exec("bump()").unwrap(), Some(Value::Int(2)));
+    }
 }
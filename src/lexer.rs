@@ -251,6 +251,9 @@ pub struct Token {
     pub value: String,
     pub line: usize,
     pub col: usize,
+    /// Position just past the last character of the token
+    pub end_line: usize,
+    pub end_col: usize,
 }
 
 pub struct Lexer {
@@ -298,13 +301,23 @@ impl Lexer {
             value: value.to_string(),
             line,
             col,
+            end_line: line,
+            end_col: col,
         });
     }
 
     pub fn tokenize(&mut self) -> Vec<Token> {
-        let mut tokens = Vec::new();
+        let mut tokens: Vec<Token> = Vec::new();
+        let mut ended = 0;
 
         while let Some(c) = self.peek() {
+            // Tokens pushed by the previous iteration end exactly here
+            for tok in &mut tokens[ended..] {
+                tok.end_line = self.line;
+                tok.end_col = self.col;
+            }
+            ended = tokens.len();
+
             // ── Whitespace ────────────────────────────────────────────────
             if c.is_whitespace() {
                 self.advance();
@@ -402,6 +415,8 @@ impl Lexer {
                     value: ch_val.to_string(),
                     line,
                     col,
+                    end_line: line,
+                    end_col: col,
                 });
                 continue;
             }
@@ -429,7 +444,7 @@ impl Lexer {
                         Some(ch) => { raw.push(ch); self.advance(); }
                     }
                 }
-                tokens.push(Token { kind: TokenKind::FString, value: raw, line, col });
+                tokens.push(Token { kind: TokenKind::FString, value: raw, line, col, end_line: line, end_col: col });
                 continue;
             }
 
@@ -460,7 +475,7 @@ impl Lexer {
                         Some(ch) => { raw.push(ch); self.advance(); }
                     }
                 }
-                tokens.push(Token { kind: TokenKind::FString, value: raw, line, col });
+                tokens.push(Token { kind: TokenKind::FString, value: raw, line, col, end_line: line, end_col: col });
                 continue;
             }
 
@@ -491,7 +506,7 @@ impl Lexer {
                             Some(ch) => { value.push(ch); self.advance(); }
                         }
                     }
-                    tokens.push(Token { kind: TokenKind::String, value, line, col });
+                    tokens.push(Token { kind: TokenKind::String, value, line, col, end_line: line, end_col: col });
                     continue;
                 } else {
                     // Normal double-quoted string with escape sequences
@@ -531,7 +546,7 @@ impl Lexer {
                             Some(ch) => { value.push(ch); self.advance(); }
                         }
                     }
-                    tokens.push(Token { kind: TokenKind::String, value, line, col });
+                    tokens.push(Token { kind: TokenKind::String, value, line, col, end_line: line, end_col: col });
                     continue;
                 }
             }
@@ -556,7 +571,7 @@ impl Lexer {
                     }
                 }
                 let kind = TokenKind::from_keyword(&value).unwrap_or(TokenKind::Identifier);
-                tokens.push(Token { kind, value, line, col });
+                tokens.push(Token { kind, value, line, col, end_line: line, end_col: col });
                 continue;
             }
 
@@ -573,7 +588,7 @@ impl Lexer {
                         let ch = self.advance().unwrap();
                         if ch != '_' { value.push(ch); }
                     }
-                    tokens.push(Token { kind: TokenKind::Int, value, line, col });
+                    tokens.push(Token { kind: TokenKind::Int, value, line, col, end_line: line, end_col: col });
                     continue;
                 }
                 // Binary: 0b…
@@ -583,7 +598,7 @@ impl Lexer {
                         let ch = self.advance().unwrap();
                         if ch != '_' { value.push(ch); }
                     }
-                    tokens.push(Token { kind: TokenKind::Int, value, line, col });
+                    tokens.push(Token { kind: TokenKind::Int, value, line, col, end_line: line, end_col: col });
                     continue;
                 }
                 // Octal: 0o…
@@ -593,7 +608,7 @@ impl Lexer {
                         let ch = self.advance().unwrap();
                         if ch != '_' { value.push(ch); }
                     }
-                    tokens.push(Token { kind: TokenKind::Int, value, line, col });
+                    tokens.push(Token { kind: TokenKind::Int, value, line, col, end_line: line, end_col: col });
                     continue;
                 }
 
@@ -631,7 +646,7 @@ impl Lexer {
                     }
                 }
                 let kind = if has_dot || has_exp { TokenKind::Float } else { TokenKind::Int };
-                tokens.push(Token { kind, value, line, col });
+                tokens.push(Token { kind, value, line, col, end_line: line, end_col: col });
                 continue;
             }
 
@@ -762,11 +777,18 @@ impl Lexer {
             }
         }
 
+        for tok in &mut tokens[ended..] {
+            tok.end_line = self.line;
+            tok.end_col = self.col;
+        }
+
         tokens.push(Token {
             kind: TokenKind::Eof,
             value: String::new(),
             line: self.line,
             col: self.col,
+            end_line: self.line,
+            end_col: self.col,
         });
         tokens
     }
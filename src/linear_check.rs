@@ -4,7 +4,7 @@
 //! Linear types ensure resources are used exactly once, preventing leaks
 //! and use-after-free bugs.
 
-use crate::parser::ASTNode;
+use crate::parser::{ASTNode, Span};
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -20,11 +20,15 @@ pub struct LinearVar {
     pub usage_count: usize,
     pub consumed: bool,
     pub drop_fn: Option<String>,
+    /// Where the value was bound
+    pub span: Option<Span>,
 }
 
 pub struct LinearChecker {
     scopes: Vec<HashMap<String, LinearVar>>,
-    errors: Vec<String>,
+    /// Span of the innermost node being checked that has one
+    span: Option<Span>,
+    errors: Vec<(String, Option<Span>)>,
     drop_code: Vec<DropInstruction>,
 }
 
@@ -39,6 +43,7 @@ impl LinearChecker {
     pub fn new() -> Self {
         LinearChecker {
             scopes: vec![HashMap::new()],
+            span: None,
             errors: Vec::new(),
             drop_code: Vec::new(),
         }
@@ -46,27 +51,36 @@ impl LinearChecker {
 
     pub fn check(&mut self, ast: &ASTNode) -> Result<(), String> {
         self.check_node(ast)?;
-        self.check_unused_values()?;
+        self.check_unused_values();
 
         if self.errors.is_empty() {
             Ok(())
         } else {
-            Err(self.errors.join("\n"))
+            let messages: Vec<_> = self.errors.iter().map(|(message, _)| message.as_str()).collect();
+            Err(messages.join("\n"))
         }
     }
 
+    /// What `check` found, each with the span of the node it is about
+    pub fn errors(&self) -> &[(String, Option<Span>)] {
+        &self.errors
+    }
+
+    fn error(&mut self, message: String, span: Option<Span>) {
+        self.errors.push((message, span));
+    }
+
     fn push_scope(&mut self) {
         self.scopes.push(HashMap::new());
     }
 
     fn pop_scope(&mut self) {
         let scope = self.scopes.pop().expect("No scope available");
-        for (_, var) in scope {
-            if var.linear_kind == LinearKind::Linear && !var.consumed && var.usage_count > 0 {
-                self.errors.push(format!(
-                    "Linear value '{}' was not consumed (used {} time(s))",
-                    var.name, var.usage_count
-                ));
+        let mut vars: Vec<_> = scope.into_values().collect();
+        vars.sort_by_key(|var| var.span.as_ref().map(|s| (s.line, s.col)));
+        for var in vars {
+            if var.linear_kind == LinearKind::Linear && !var.consumed {
+                self.unused(&var);
             }
         }
     }
@@ -100,6 +114,7 @@ impl LinearChecker {
             usage_count: 0,
             consumed: false,
             drop_fn,
+            span: self.span.clone(),
         };
         self.current_scope().insert(name, var);
     }
@@ -112,8 +127,7 @@ impl LinearChecker {
             if let Some(var) = self.find_variable_mut(name) {
                 var.usage_count += 1;
                 if var.linear_kind == LinearKind::Linear && var.usage_count > 1 {
-                    self.errors
-                        .push(format!("Linear value '{}' used more than once", name));
+                    self.error(format!("linear value `{}` used more than once", name), self.span.clone());
                     return false;
                 }
                 should_generate_drop = var.usage_count == 1;
@@ -153,22 +167,32 @@ impl LinearChecker {
         }
     }
 
-    fn check_unused_values(&self) -> Result<(), String> {
-        for scope in &self.scopes {
-            for (name, var) in scope {
-                if var.linear_kind == LinearKind::Linear && var.usage_count == 0 && !var.consumed {
-                    return Err(format!(
-                        "Linear variable '{}' declared but never used",
-                        name
-                    ));
-                }
-            }
+    fn check_unused_values(&mut self) {
+        let mut unused: Vec<_> = self
+            .scopes
+            .iter()
+            .flat_map(|scope| scope.values())
+            .filter(|var| var.linear_kind == LinearKind::Linear && var.usage_count == 0 && !var.consumed)
+            .cloned()
+            .collect();
+        unused.sort_by_key(|var| var.span.as_ref().map(|s| (s.line, s.col)));
+        for var in unused {
+            self.unused(&var);
         }
-        Ok(())
+    }
+
+    fn unused(&mut self, var: &LinearVar) {
+        self.error(format!("linear value `{}` is never used", var.name), var.span.clone());
     }
 
     fn check_node(&mut self, node: &ASTNode) -> Result<(), String> {
         match node {
+            ASTNode::Spanned { span, node } => {
+                let outer = self.span.replace(span.clone());
+                let result = self.check_node(node);
+                self.span = outer;
+                result
+            }
             ASTNode::Program(items) => {
                 for item in items {
                     self.check_node(item)?;
@@ -183,7 +207,12 @@ impl LinearChecker {
             } => {
                 self.push_scope();
                 for param in params {
-                    self.declare_linear(param.name.clone(), LinearKind::Linear, None);
+                    let kind = if self.is_linear_type(param.ty.as_ref()) {
+                        LinearKind::Linear
+                    } else {
+                        LinearKind::Unrestricted
+                    };
+                    self.declare_linear(param.name.clone(), kind, None);
                 }
                 self.check_node(body)?;
                 self.pop_scope();
@@ -269,11 +298,8 @@ impl LinearChecker {
                 Ok(())
             }
             ASTNode::Identifier(name) => {
-                if self.use_variable(name) {
-                    Ok(())
-                } else {
-                    Ok(())
-                }
+                self.use_variable(name);
+                Ok(())
             }
             ASTNode::Literal(_) => Ok(()),
             ASTNode::Array(items) => {
@@ -283,22 +309,20 @@ impl LinearChecker {
                 Ok(())
             }
             ASTNode::Consume(expr) => {
-                self.check_node(expr)?;
-                if let ASTNode::Identifier(name) = expr.as_ref() {
-                    if let Some(var) = self.find_variable_mut(name) {
-                        if var.usage_count > 0 {
-                            self.errors
-                                .push(format!("Cannot consume '{}': value already used", name));
-                        } else {
-                            let drop_fn = var.drop_fn.clone();
-                            let var_name = var.name.clone();
-                            var.consumed = true;
-                            drop_fn.map(|f| {
-                                self.drop_code.push(DropInstruction::Custom(format!(
-                                    "call {} for {}",
-                                    f, var_name
-                                )))
-                            });
+                let ASTNode::Identifier(name) = expr.inner() else {
+                    return self.check_node(expr);
+                };
+                let span = expr.span().cloned().or_else(|| self.span.clone());
+                if let Some(var) = self.find_variable_mut(name) {
+                    if var.usage_count > 0 {
+                        self.error(format!("cannot consume `{}`: value already used", name), span);
+                    } else {
+                        let drop_fn = var.drop_fn.clone();
+                        let var_name = var.name.clone();
+                        var.usage_count += 1;
+                        var.consumed = true;
+                        if let Some(f) = drop_fn {
+                            self.drop_code.push(DropInstruction::Custom(format!("call {} for {}", f, var_name)));
                         }
                     }
                 }
@@ -315,7 +339,12 @@ impl LinearChecker {
                 self.check_node(expr)?;
                 Ok(())
             }
-            _ => Ok(()),
+            other => {
+                for child in other.children() {
+                    self.check_node(child)?;
+                }
+                Ok(())
+            }
         }
     }
 
@@ -2,38 +2,101 @@
 //! 
 //! This module provides IDE integration through the Language Server Protocol.
 
-use anyhow::Result;
 use tower_lsp::jsonrpc::Result as JsonResult;
 use tower_lsp::lsp_types::*;
-use tower_lsp::{Client, LanguageServer, LspService, Printer};
+use tower_lsp::{Client, LanguageServer, LspService, Server};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-
-use crate::lexer::Lexer;
-use crate::parser::Parser;
-use crate::compiler::Compiler;
+use std::time::Duration;
+
+use crate::builtins::{self, Builtin};
+use crate::checker::{self, FindingKind};
+use crate::parser::{Parser, Span};
+use crate::symbols::{self, Symbol, Workspace};
+
+/// Documents larger than this are only re-checked once edits pause for `DEBOUNCE`
+const DEBOUNCE_BYTES: usize = 32 * 1024;
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Keywords and type names offered by completion
+const KEYWORDS: &[(&str, &str)] = &[
+    ("fn", "Function declaration"),
+    ("let", "Immutable binding"),
+    ("var", "Mutable binding"),
+    ("own", "Ownership transfer"),
+    ("mut", "Mutable reference"),
+    ("ref", "Borrowed reference"),
+    ("const", "Constant"),
+    ("static", "Static variable"),
+    ("struct", "Struct definition"),
+    ("enum", "Enum definition"),
+    ("union", "Union definition"),
+    ("trait", "Trait definition"),
+    ("impl", "Implementation block"),
+    ("type", "Type alias"),
+    ("if", "Conditional"),
+    ("else", "Else branch"),
+    ("match", "Pattern matching"),
+    ("loop", "Infinite loop"),
+    ("while", "While loop"),
+    ("for", "For loop"),
+    ("in", "Iterator binding"),
+    ("return", "Return value"),
+    ("break", "Break loop"),
+    ("continue", "Continue loop"),
+    ("defer", "Deferred cleanup"),
+    ("unsafe", "Unsafe block"),
+    ("comptime", "Compile-time block"),
+    ("import", "Import module"),
+    ("pub", "Public visibility"),
+    ("i8", "8-bit signed integer"),
+    ("i16", "16-bit signed integer"),
+    ("i32", "32-bit signed integer"),
+    ("i64", "64-bit signed integer"),
+    ("u8", "8-bit unsigned integer"),
+    ("u16", "16-bit unsigned integer"),
+    ("u32", "32-bit unsigned integer"),
+    ("u64", "64-bit unsigned integer"),
+    ("f32", "32-bit float"),
+    ("f64", "64-bit float"),
+    ("bool", "Boolean"),
+    ("char", "Character"),
+    ("String", "String type"),
+    ("Vec", "Vector type"),
+    ("HashMap", "Hash map type"),
+    ("Option", "Option type"),
+    ("Result", "Result type"),
+];
 
 pub struct KnullLanguageServer {
     client: Arc<Client>,
     documents: std::sync::Mutex<DocumentStore>,
+    workspace: std::sync::Mutex<Workspace>,
 }
 
 pub struct DocumentStore {
-    documents: std::collections::HashMap<Url, String>,
+    documents: HashMap<Url, (String, i32)>,
 }
 
 impl DocumentStore {
     pub fn new() -> Self {
         DocumentStore {
-            documents: std::collections::HashMap::new(),
+            documents: HashMap::new(),
         }
     }
 
     pub fn get(&self, uri: &Url) -> Option<String> {
-        self.documents.get(uri).cloned()
+        self.documents.get(uri).map(|(text, _)| text.clone())
+    }
+
+    /// The text of a document, if it is still at `version`
+    pub fn at_version(&self, uri: &Url, version: i32) -> Option<String> {
+        self.documents.get(uri).filter(|(_, v)| *v == version).map(|(text, _)| text.clone())
     }
 
-    pub fn insert(&mut self, uri: Url, text: String) {
-        self.documents.insert(uri, text);
+    pub fn insert(&mut self, uri: Url, text: String, version: i32) {
+        self.documents.insert(uri, (text, version));
     }
 
     pub fn remove(&mut self, uri: &Url) {
@@ -46,53 +109,40 @@ impl KnullLanguageServer {
         KnullLanguageServer {
             client: Arc::new(client),
             documents: std::sync::Mutex::new(DocumentStore::new()),
+            workspace: std::sync::Mutex::new(Workspace::new()),
         }
     }
 
-    fn compile_document(&self, source: &str) -> Vec<Diagnostic> {
-        let mut diagnostics = Vec::new();
-
-        // Lex
-        match Lexer::new(source).lex() {
-            Ok(tokens) => {
-                // Parse
-                match Parser::new(tokens).parse() {
-                    Ok(ast) => {
-                        // Type check
-                        let mut compiler = Compiler::new();
-                        if let Err(e) = compiler.compile(source) {
-                            diagnostics.push(Diagnostic {
-                                range: Range::default(),
-                                severity: Some(DiagnosticSeverity::ERROR),
-                                message: e.to_string(),
-                                source: Some("knull".to_string()),
-                                ..Default::default()
-                            });
-                        }
-                    }
-                    Err(e) => {
-                        diagnostics.push(Diagnostic {
-                            range: Range::default(),
-                            severity: Some(DiagnosticSeverity::ERROR),
-                            message: format!("Parse error: {}", e),
-                            source: Some("knull".to_string()),
-                            ..Default::default()
-                        });
-                    }
-                }
-            }
-            Err(e) => {
-                diagnostics.push(Diagnostic {
-                    range: Range::default(),
-                    severity: Some(DiagnosticSeverity::ERROR),
-                    message: format!("Lex error: {}", e),
-                    source: Some("knull".to_string()),
-                    ..Default::default()
-                });
-            }
+    /// Re-index a document after it was opened or edited
+    fn reindex(&self, uri: &Url, text: &str) {
+        if let Some(path) = file_path(uri) {
+            self.workspace.lock().unwrap().update(&path, text);
         }
+    }
 
-        diagnostics
+    /// The file and 1-based line and column of an LSP position
+    fn locate(&self, uri: &Url, position: Position) -> Option<(PathBuf, usize, usize)> {
+        let path = file_path(uri)?;
+        let workspace = self.workspace.lock().unwrap();
+        let (line, col) = from_position(&workspace.file(&path)?.source, position);
+        Some((path, line, col))
+    }
+
+    fn location(&self, span: &Span) -> Option<Location> {
+        let path = PathBuf::from(span.file.as_deref()?);
+        let workspace = self.workspace.lock().unwrap();
+        let source = workspace.file(&path).map_or("", |file| file.source.as_str());
+        Some(Location { uri: Url::from_file_path(&path).ok()?, range: to_range(source, span) })
+    }
+
+    /// Re-index a document and publish its diagnostics, unless a later edit
+    /// has already replaced `version`
+    async fn refresh(&self, uri: Url, version: i32) {
+        let text = self.documents.lock().unwrap().at_version(&uri, version);
+        let Some(text) = text else { return };
+        self.reindex(&uri, &text);
+        let diagnostics = diagnostics(file_path(&uri).as_deref(), &text);
+        self.client.publish_diagnostics(uri, diagnostics, Some(version)).await;
     }
 }
 
@@ -101,6 +151,11 @@ impl LanguageServer for KnullLanguageServer {
     async fn initialize(&self, params: InitializeParams) -> JsonResult<InitializeResult> {
         log::info!("Client initialized: {:?}", params.client_info);
 
+        // Index the workspace so references and symbol search reach unopened files
+        if let Some(root) = params.root_uri.as_ref().and_then(file_path) {
+            self.workspace.lock().unwrap().load_dir(&root);
+        }
+
         Ok(InitializeResult {
             server_info: Some(ServerInfo {
                 name: "knull".to_string(),
@@ -113,6 +168,9 @@ impl LanguageServer for KnullLanguageServer {
                 definition_provider: Some(OneOf::Left(true)),
                 type_definition_provider: Some(TypeDefinitionProviderCapability::Simple(true)),
                 references_provider: Some(OneOf::Left(true)),
+                rename_provider: Some(OneOf::Left(true)),
+                document_symbol_provider: Some(OneOf::Left(true)),
+                workspace_symbol_provider: Some(OneOf::Left(true)),
                 hover_provider: Some(HoverProviderCapability::Simple(true)),
                 completion_provider: Some(CompletionOptions {
                     resolve_provider: Some(false),
@@ -123,6 +181,7 @@ impl LanguageServer for KnullLanguageServer {
                     ]),
                     work_done_progress_options: WorkDoneProgressOptions::default(),
                     all_commit_characters: None,
+                    completion_item: None,
                 }),
                 signature_help_provider: Some(SignatureHelpOptions {
                     trigger_characters: Some(vec!["(".to_string(), ",".to_string()]),
@@ -145,21 +204,39 @@ impl LanguageServer for KnullLanguageServer {
     }
 
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
-        self.documents.lock().unwrap().insert(
-            params.text_document.uri,
-            params.text_document.text,
-        );
+        let document = params.text_document;
+        self.documents.lock().unwrap().insert(document.uri.clone(), document.text, document.version);
+        self.refresh(document.uri, document.version).await;
     }
 
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
-        self.documents.lock().unwrap().insert(
-            params.text_document.uri,
-            params.content_changes.into_iter().last().unwrap().text,
-        );
+        let uri = params.text_document.uri;
+        let version = params.text_document.version;
+        let len = {
+            let mut documents = self.documents.lock().unwrap();
+            let mut text = documents.get(&uri).unwrap_or_default();
+            for change in params.content_changes {
+                apply_change(&mut text, change);
+            }
+            let len = text.len();
+            documents.insert(uri.clone(), text, version);
+            len
+        };
+        // Skip the work for a large file while it is still being typed in
+        if len > DEBOUNCE_BYTES {
+            tokio::time::sleep(DEBOUNCE).await;
+        }
+        self.refresh(uri, version).await;
     }
 
     async fn did_close(&self, params: DidCloseTextDocumentParams) {
-        self.documents.lock().unwrap().remove(&params.text_document.uri);
+        let uri = params.text_document.uri;
+        self.documents.lock().unwrap().remove(&uri);
+        // Unsaved edits are gone; the file on disk is what other files see
+        if let Some(path) = file_path(&uri) {
+            self.workspace.lock().unwrap().load(&path);
+        }
+        self.client.publish_diagnostics(uri, Vec::new(), None).await;
     }
 
     async fn did_change_workspace_folders(&self, params: DidChangeWorkspaceFoldersParams) {
@@ -171,256 +248,576 @@ impl LanguageServer for KnullLanguageServer {
     }
 
     async fn hover(&self, params: HoverParams) -> JsonResult<Option<Hover>> {
-        let uri = params.text_document_position_params.text_document.uri;
-        
-        if let Some(source) = self.documents.lock().unwrap().get(&uri) {
-            let position = params.text_document_position_params.position;
-            
-            // Simple hover - show keyword info
-            let word = extract_word_at_position(source, position);
-            
-            let content = match word.as_str() {
-                "fn" => "**fn** - Function declaration\n\nDeclares a new function.",
-                "let" => "**let** - Immutable binding\n\nDeclares a variable that cannot be reassigned.",
-                "var" => "**var** - Mutable binding\n\nDeclares a variable that can be reassigned.",
-                "struct" => "**struct** - Structure definition\n\nDefines a new struct type.",
-                "enum" => "**enum** - Enumeration definition\n\nDefines a new enum type.",
-                "if" => "**if** - Conditional\n\nConditional branching.",
-                "else" => "**else** - Alternative branch\n\nAlternative branch for if statements.",
-                "match" => "**match** - Pattern matching\n\nPattern matching with exhaustiveness checking.",
-                "loop" => "**loop** - Infinite loop\n\nInfinite loop that must be broken explicitly.",
-                "while" => "**while** - Conditional loop\n\nLoop that continues while condition is true.",
-                "for" => "**for** - Iterator loop\n\nLoop over an iterator or range.",
-                "return" => "**return** - Return value\n\nReturn from a function.",
-                "unsafe" => "**unsafe** - Unsafe block\n\nDisables safety checks for low-level operations.",
-                "comptime" => "**comptime** - Compile-time execution\n\nExecutes code at compile time.",
-                "import" => "**import** - Import module\n\nImports items from another module.",
-                "pub" => "**pub** - Public visibility\n\nMakes an item publicly accessible.",
-                "i32" => "**i32** - 32-bit signed integer\n\nSigned 32-bit integer type.",
-                "i64" => "**i64** - 64-bit signed integer\n\nSigned 64-bit integer type.",
-                "u32" => "**u32** - 32-bit unsigned integer\n\nUnsigned 32-bit integer type.",
-                "u64" => "**u64** - 64-bit unsigned integer\n\nUnsigned 64-bit integer type.",
-                "f64" => "**f64** - 64-bit floating point\n\nDouble-precision floating point type.",
-                "bool" => "**bool** - Boolean\n\nBoolean type (true or false).",
-                "String" => "**String** - String type\n\nDynamically allocated UTF-8 string.",
-                "Vec" => "**Vec<T>** - Vector type\n\nDynamically growing array.",
-                _ => "**Knull** - The God Language\n\nHover for more info",
-            };
-
-            let range = Range::new(position, Position::new(position.line, position.character + word.len() as u32));
-            
-            return Ok(Some(Hover {
-                contents: HoverContents::Scalar(MarkupContent {
-                    kind: MarkupKind::Markdown,
-                    value: content.to_string(),
-                }),
-                range: Some(range),
-            }));
-        }
-        
-        Ok(None)
-    }
-
-    async fn definition(&self, params: DefinitionParams) -> JsonResult<Option<Location>> {
-        let uri = params.text_document_position_params.text_document.uri;
-        
-        if let Some(source) = self.documents.lock().unwrap().get(&uri) {
-            let position = params.text_document_position_params.position;
-            
-            // Simple definition - return the same location
-            return Ok(Some(Location {
-                uri,
-                range: Range::new(position, Position::new(position.line, position.character + 1)),
-            }));
+        let position = params.text_document_position_params;
+        let uri = position.text_document.uri;
+        let Some(source) = self.documents.lock().unwrap().get(&uri) else {
+            return Ok(None);
+        };
+        let Some((range, word, member)) = word_at(&source, position.position) else {
+            return Ok(None);
+        };
+
+        // What the program declares comes first, since it may shadow a builtin
+        let declared: Vec<String> = match self.locate(&uri, position.position) {
+            Some((path, line, col)) => {
+                let workspace = self.workspace.lock().unwrap();
+                workspace.symbols_at(&path, line, col).into_iter().map(|symbol| code_block(&declaration(symbol))).collect()
+            }
+            None => Vec::new(),
+        };
+        let content = if !declared.is_empty() {
+            declared.join("\n\n")
+        } else if member {
+            builtins::methods(&word).map(builtin_doc).collect::<Vec<_>>().join("\n\n---\n\n")
+        } else if let Some(builtin) = builtins::lookup(&word) {
+            builtin_doc(builtin)
+        } else {
+            keyword_doc(&word).unwrap_or_default().to_string()
+        };
+        if content.is_empty() {
+            return Ok(None);
         }
-        
-        Ok(None)
+        Ok(Some(Hover {
+            contents: HoverContents::Markup(MarkupContent { kind: MarkupKind::Markdown, value: content }),
+            range: Some(range),
+        }))
+    }
+
+    async fn goto_definition(&self, params: GotoDefinitionParams) -> JsonResult<Option<GotoDefinitionResponse>> {
+        let position = params.text_document_position_params;
+        let Some((path, line, col)) = self.locate(&position.text_document.uri, position.position) else {
+            return Ok(None);
+        };
+        let spans = self.workspace.lock().unwrap().definition(&path, line, col);
+        let locations: Vec<Location> = spans.iter().filter_map(|span| self.location(span)).collect();
+        Ok((!locations.is_empty()).then_some(GotoDefinitionResponse::Array(locations)))
     }
 
     async fn references(&self, params: ReferenceParams) -> JsonResult<Option<Vec<Location>>> {
-        let uri = params.text_document_position_params.text_document.uri;
-        
-        if let Some(_source) = self.documents.lock().unwrap().get(&uri) {
-            // Return empty references for now
-            return Ok(Some(Vec::new()));
+        let position = params.text_document_position;
+        let Some((path, line, col)) = self.locate(&position.text_document.uri, position.position) else {
+            return Ok(None);
+        };
+        let include_declaration = params.context.include_declaration;
+        let spans = self.workspace.lock().unwrap().references(&path, line, col, include_declaration);
+        Ok(Some(spans.iter().filter_map(|span| self.location(span)).collect()))
+    }
+
+    async fn rename(&self, params: RenameParams) -> JsonResult<Option<WorkspaceEdit>> {
+        let position = params.text_document_position;
+        let Some((path, line, col)) = self.locate(&position.text_document.uri, position.position) else {
+            return Ok(None);
+        };
+        let spans = self
+            .workspace
+            .lock()
+            .unwrap()
+            .rename(&path, line, col, &params.new_name)
+            .map_err(tower_lsp::jsonrpc::Error::invalid_params)?;
+
+        let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+        for location in spans.iter().filter_map(|span| self.location(span)) {
+            changes
+                .entry(location.uri)
+                .or_default()
+                .push(TextEdit { range: location.range, new_text: params.new_name.clone() });
         }
-        
-        Ok(None)
-    }
-
-    async fn completion(&self, _params: CompletionParams) -> JsonResult<Option<CompletionList>> {
-        let items = vec![
-            CompletionItem::new_simple("fn".to_string(), "Function declaration".to_string()),
-            CompletionItem::new_simple("let".to_string(), "Immutable binding".to_string()),
-            CompletionItem::new_simple("var".to_string(), "Mutable binding".to_string()),
-            CompletionItem::new_simple("own".to_string(), "Ownership transfer".to_string()),
-            CompletionItem::new_simple("mut".to_string(), "Mutable reference".to_string()),
-            CompletionItem::new_simple("ref".to_string(), "Borrowed reference".to_string()),
-            CompletionItem::new_simple("const".to_string(), "Constant".to_string()),
-            CompletionItem::new_simple("static".to_string(), "Static variable".to_string()),
-            CompletionItem::new_simple("struct".to_string(), "Struct definition".to_string()),
-            CompletionItem::new_simple("enum".to_string(), "Enum definition".to_string()),
-            CompletionItem::new_simple("union".to_string(), "Union definition".to_string()),
-            CompletionItem::new_simple("trait".to_string(), "Trait definition".to_string()),
-            CompletionItem::new_simple("impl".to_string(), "Implementation block".to_string()),
-            CompletionItem::new_simple("type".to_string(), "Type alias".to_string()),
-            CompletionItem::new_simple("if".to_string(), "Conditional".to_string()),
-            CompletionItem::new_simple("else".to_string(), "Else branch".to_string()),
-            CompletionItem::new_simple("match".to_string(), "Pattern matching".to_string()),
-            CompletionItem::new_simple("loop".to_string(), "Infinite loop".to_string()),
-            CompletionItem::new_simple("while".to_string(), "While loop".to_string()),
-            CompletionItem::new_simple("for".to_string(), "For loop".to_string()),
-            CompletionItem::new_simple("in".to_string(), "Iterator binding".to_string()),
-            CompletionItem::new_simple("return".to_string(), "Return value".to_string()),
-            CompletionItem::new_simple("break".to_string(), "Break loop".to_string()),
-            CompletionItem::new_simple("continue".to_string(), "Continue loop".to_string()),
-            CompletionItem::new_simple("defer".to_string(), "Deferred cleanup".to_string()),
-            CompletionItem::new_simple("unsafe".to_string(), "Unsafe block".to_string()),
-            CompletionItem::new_simple("comptime".to_string(), "Compile-time block".to_string()),
-            CompletionItem::new_simple("import".to_string(), "Import module".to_string()),
-            CompletionItem::new_simple("pub".to_string(), "Public visibility".to_string()),
-            CompletionItem::new_simple("i8".to_string(), "8-bit signed integer".to_string()),
-            CompletionItem::new_simple("i16".to_string(), "16-bit signed integer".to_string()),
-            CompletionItem::new_simple("i32".to_string(), "32-bit signed integer".to_string()),
-            CompletionItem::new_simple("i64".to_string(), "64-bit signed integer".to_string()),
-            CompletionItem::new_simple("u8".to_string(), "8-bit unsigned integer".to_string()),
-            CompletionItem::new_simple("u16".to_string(), "16-bit unsigned integer".to_string()),
-            CompletionItem::new_simple("u32".to_string(), "32-bit unsigned integer".to_string()),
-            CompletionItem::new_simple("u64".to_string(), "64-bit unsigned integer".to_string()),
-            CompletionItem::new_simple("f32".to_string(), "32-bit float".to_string()),
-            CompletionItem::new_simple("f64".to_string(), "64-bit float".to_string()),
-            CompletionItem::new_simple("bool".to_string(), "Boolean".to_string()),
-            CompletionItem::new_simple("char".to_string(), "Character".to_string()),
-            CompletionItem::new_simple("String".to_string(), "String type".to_string()),
-            CompletionItem::new_simple("Vec".to_string(), "Vector type".to_string()),
-            CompletionItem::new_simple("HashMap".to_string(), "Hash map type".to_string()),
-            CompletionItem::new_simple("Option".to_string(), "Option type".to_string()),
-            CompletionItem::new_simple("Result".to_string(), "Result type".to_string()),
-        ];
-
-        Ok(Some(CompletionList {
-            is_incomplete: false,
-            items,
-        }))
+        Ok(Some(WorkspaceEdit { changes: Some(changes), ..WorkspaceEdit::default() }))
     }
 
-    async fn signature_help(&self, _params: SignatureHelpParams) -> JsonResult<Option<SignatureHelp>> {
-        Ok(Some(SignatureHelp {
-            signatures: vec![],
-            active_signature: None,
-            active_parameter: None,
-        }))
+    #[allow(deprecated)]
+    async fn document_symbol(&self, params: DocumentSymbolParams) -> JsonResult<Option<DocumentSymbolResponse>> {
+        let Some(path) = file_path(&params.text_document.uri) else {
+            return Ok(None);
+        };
+        let workspace = self.workspace.lock().unwrap();
+        let Some(file) = workspace.file(&path) else {
+            return Ok(None);
+        };
+        let symbol = |s: &symbols::Symbol, children: Option<Vec<DocumentSymbol>>| DocumentSymbol {
+            name: s.name.clone(),
+            detail: s.container.clone(),
+            kind: symbol_kind(s.kind),
+            tags: None,
+            deprecated: None,
+            range: to_range(&file.source, &s.extent),
+            selection_range: to_range(&file.source, &s.span),
+            children,
+        };
+        let outline = file
+            .outline()
+            .into_iter()
+            .map(|(item, members)| {
+                let children = members.into_iter().map(|member| symbol(member, None)).collect::<Vec<_>>();
+                symbol(item, (!children.is_empty()).then_some(children))
+            })
+            .collect();
+        Ok(Some(DocumentSymbolResponse::Nested(outline)))
+    }
+
+    #[allow(deprecated)]
+    async fn symbol(&self, params: WorkspaceSymbolParams) -> JsonResult<Option<Vec<SymbolInformation>>> {
+        let found: Vec<(String, SymbolKind, Option<String>, Span)> = self
+            .workspace
+            .lock()
+            .unwrap()
+            .search(&params.query)
+            .into_iter()
+            .map(|s| (s.name.clone(), symbol_kind(s.kind), s.container.clone(), s.span.clone()))
+            .collect();
+        let symbols = found
+            .into_iter()
+            .filter_map(|(name, kind, container_name, span)| {
+                Some(SymbolInformation {
+                    name,
+                    kind,
+                    tags: None,
+                    deprecated: None,
+                    location: self.location(&span)?,
+                    container_name,
+                })
+            })
+            .collect();
+        Ok(Some(symbols))
+    }
+
+    async fn completion(&self, params: CompletionParams) -> JsonResult<Option<CompletionResponse>> {
+        let position = params.text_document_position;
+        let uri = position.text_document.uri;
+        let Some(source) = self.documents.lock().unwrap().get(&uri) else {
+            return Ok(None);
+        };
+        let before = text_before(&source, position.position);
+        let member = before.trim_end_matches(is_word_char).ends_with('.');
+
+        let workspace = self.workspace.lock().unwrap();
+        let mut items: Vec<CompletionItem> = Vec::new();
+        if member {
+            // The receiver's type is unknown, so offer every field and method
+            let members = workspace.member_names().into_iter().filter(|s| s.kind != symbols::SymbolKind::Variant);
+            items.extend(members.map(symbol_item));
+            for method in builtins::METHODS {
+                if !items.iter().any(|item| item.label == method.name) {
+                    items.push(builtin_item(method));
+                }
+            }
+        } else {
+            items.extend(KEYWORDS.iter().map(|&(keyword, detail)| CompletionItem {
+                label: keyword.to_string(),
+                kind: Some(CompletionItemKind::KEYWORD),
+                detail: Some(detail.to_string()),
+                ..CompletionItem::default()
+            }));
+            if let Some(path) = file_path(&uri) {
+                let (line, col) = from_position(&source, position.position);
+                items.extend(workspace.visible(&path, line, col).into_iter().map(symbol_item));
+            }
+            items.extend(builtins::BUILTINS.iter().map(builtin_item));
+        }
+        Ok(Some(CompletionResponse::Array(items)))
+    }
+
+    async fn signature_help(&self, params: SignatureHelpParams) -> JsonResult<Option<SignatureHelp>> {
+        let position = params.text_document_position_params;
+        let uri = position.text_document.uri;
+        let Some(source) = self.documents.lock().unwrap().get(&uri) else {
+            return Ok(None);
+        };
+        let Some((callee, member, argument)) = open_call(text_before(&source, position.position)) else {
+            return Ok(None);
+        };
+
+        let mut signatures = Vec::new();
+        {
+            let workspace = self.workspace.lock().unwrap();
+            let user: Vec<&Symbol> = if member {
+                workspace.member_names().into_iter().filter(|s| s.kind == symbols::SymbolKind::Method).collect()
+            } else {
+                let (line, col) = from_position(&source, position.position);
+                file_path(&uri).map_or_else(Vec::new, |path| workspace.visible(&path, line, col))
+            };
+            for symbol in user.into_iter().filter(|s| s.name == callee) {
+                if matches!(symbol.kind, symbols::SymbolKind::Function | symbols::SymbolKind::Method) {
+                    signatures.push(signature_info(declaration(symbol), &symbol.params, None, argument));
+                }
+            }
+        }
+        // Builtins only when the program declares nothing by that name
+        if signatures.is_empty() {
+            let found: Vec<&Builtin> =
+                if member { builtins::methods(&callee).collect() } else { builtins::lookup(&callee).into_iter().collect() };
+            for builtin in found {
+                let doc = (!builtin.doc.is_empty()).then(|| builtin.doc.to_string());
+                signatures.push(signature_info(builtin.signature(), builtin.params, doc, argument));
+            }
+        }
+        if signatures.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(SignatureHelp { signatures, active_signature: Some(0), active_parameter: None }))
     }
 
     async fn formatting(&self, params: DocumentFormattingParams) -> JsonResult<Option<Vec<TextEdit>>> {
         let uri = params.text_document.uri;
-        
-        if let Some(source) = self.documents.lock().unwrap().get(&uri) {
-            let formatted = simple_format(source);
-            
-            return Ok(Some(vec![TextEdit {
-                range: Range::new(
-                    Position::new(0, 0),
-                    Position::new(usize::MAX, usize::MAX),
-                ),
-                new_text: formatted,
-            }]));
+        let Some(source) = self.documents.lock().unwrap().get(&uri) else {
+            return Ok(None);
+        };
+        // Source that does not parse is left alone
+        let Ok(formatted) = crate::formatter::format_source(&source) else {
+            return Ok(None);
+        };
+        if formatted == source {
+            return Ok(Some(Vec::new()));
         }
-        
-        Ok(None)
+        Ok(Some(vec![TextEdit {
+            range: Range::new(Position::new(0, 0), end_position(&source)),
+            new_text: formatted,
+        }]))
     }
 
     async fn range_formatting(&self, params: DocumentRangeFormattingParams) -> JsonResult<Option<Vec<TextEdit>>> {
         let uri = params.text_document.uri;
-        
-        if let Some(source) = self.documents.lock().unwrap().get(&uri) {
-            return Ok(Some(vec![TextEdit {
-                range: params.range,
-                new_text: source.clone(),
-            }]));
-        }
-        
-        Ok(None)
+        let Some(source) = self.documents.lock().unwrap().get(&uri) else {
+            return Ok(None);
+        };
+        let range = params.range;
+        // A range ending at the start of a line does not include that line
+        let last = if range.end.character == 0 && range.end.line > range.start.line {
+            range.end.line - 1
+        } else {
+            range.end.line
+        };
+        let Ok((first, last, new_text)) =
+            crate::formatter::format_range(&source, range.start.line as usize, last as usize)
+        else {
+            return Ok(None);
+        };
+        Ok(Some(vec![TextEdit {
+            range: Range::new(Position::new(first as u32, 0), Position::new(last as u32 + 1, 0)),
+            new_text,
+        }]))
     }
+}
+
+/// Serve the protocol over stdin and stdout, as editors launch it
+pub fn run_stdio() -> Result<(), String> {
+    runtime()?.block_on(async {
+        let (service, socket) = LspService::new(KnullLanguageServer::new);
+        Server::new(tokio::io::stdin(), tokio::io::stdout(), socket).serve(service).await;
+    });
+    Ok(())
+}
 
-    async fn publish_diagnostics(&self, params: PublishDiagnosticsParams) {
-        log::info!("Diagnostics for {}: {:?}", params.uri, params.diagnostics);
+/// Serve a single client that connects over TCP on `port`
+pub fn run_server(port: u16) -> Result<(), String> {
+    runtime()?.block_on(async {
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+            .await
+            .map_err(|e| format!("Cannot listen on port {}: {}", port, e))?;
+        log::info!("Knull LSP listening on 127.0.0.1:{}", port);
+        let (stream, _) = listener.accept().await.map_err(|e| format!("Connection failed: {}", e))?;
+        let (read, write) = tokio::io::split(stream);
+        let (service, socket) = LspService::new(KnullLanguageServer::new);
+        Server::new(read, write, socket).serve(service).await;
+        Ok(())
+    })
+}
+
+fn runtime() -> Result<tokio::runtime::Runtime, String> {
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("Cannot start the LSP runtime: {}", e))
+}
+
+/// A document's parse error, or what `knull check` finds in it
+fn diagnostics(path: Option<&Path>, source: &str) -> Vec<Diagnostic> {
+    let mut parser = Parser::new(source);
+    let ast = match parser.parse() {
+        Ok(ast) => ast,
+        Err(message) => {
+            let range = parser.error_span().map_or_else(Range::default, |span| to_range(source, span));
+            return vec![Diagnostic {
+                range,
+                severity: Some(DiagnosticSeverity::ERROR),
+                source: Some("knull".to_string()),
+                message,
+                ..Default::default()
+            }];
+        }
+    };
+    checker::check_program(&ast, path)
+        .into_iter()
+        .map(|finding| Diagnostic {
+            range: finding.span.as_ref().map_or_else(Range::default, |span| to_range(source, span)),
+            severity: Some(if finding.kind.is_error() { DiagnosticSeverity::ERROR } else { DiagnosticSeverity::WARNING }),
+            source: Some("knull".to_string()),
+            message: finding.message,
+            // Editors fade these out
+            tags: matches!(finding.kind, FindingKind::Unused | FindingKind::Unreachable)
+                .then(|| vec![DiagnosticTag::UNNECESSARY]),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// The word under an LSP position, its range, and whether a `.` comes before it
+fn word_at(source: &str, position: Position) -> Option<(Range, String, bool)> {
+    let line: Vec<char> = source.split('\n').nth(position.line as usize)?.chars().collect();
+    let (_, col) = from_position(source, position);
+    let at = (col - 1).min(line.len());
+    let start = line[..at].iter().rposition(|&c| !is_word_char(c)).map_or(0, |i| i + 1);
+    let end = line[at..].iter().position(|&c| !is_word_char(c)).map_or(line.len(), |i| at + i);
+    if start == end {
+        return None;
     }
+    let units = |chars: &[char]| chars.iter().map(|c| c.len_utf16()).sum::<usize>() as u32;
+    let range = Range::new(
+        Position::new(position.line, units(&line[..start])),
+        Position::new(position.line, units(&line[..end])),
+    );
+    let member = start > 0 && line[start - 1] == '.';
+    Some((range, line[start..end].iter().collect(), member))
 }
 
-fn extract_word_at_position(source: &str, position: Position) -> String {
-    let mut start = 0;
-    let mut end = 0;
-    let mut current_pos = 0;
-    let mut in_word = false;
-    
-    for (i, c) in source.char_indices() {
-        if current_pos == position.line as usize {
-            if c.is_alphanumeric() || c == '_' {
-                if !in_word {
-                    start = i;
-                    in_word = true;
+/// The text of a document up to an LSP position
+fn text_before(source: &str, position: Position) -> &str {
+    &source[..byte_offset(source, position)]
+}
+
+/// The call whose argument list `before` ends inside: the callee, whether it
+/// is a method, and which argument the end is in, counting from 0
+fn open_call(before: &str) -> Option<(String, bool, usize)> {
+    let mut depth = 0;
+    let mut argument = 0;
+    for (i, c) in before.char_indices().rev() {
+        match c {
+            ')' | ']' | '}' => depth += 1,
+            '(' | '[' | '{' if depth > 0 => depth -= 1,
+            '(' => {
+                let callee = before[..i].trim_end();
+                let name = &callee[callee.trim_end_matches(is_word_char).len()..];
+                if name.is_empty() {
+                    return None;
                 }
-                end = i + c.len_utf8();
-            } else if in_word {
-                break;
-            }
-            
-            if current_pos == position.line as usize && i >= position.character as usize {
-                break;
+                let member = callee[..callee.len() - name.len()].ends_with('.');
+                return Some((name.to_string(), member, argument));
             }
+            // Inside a block or an array literal rather than a call
+            '[' | '{' | ';' => return None,
+            ',' if depth == 0 => argument += 1,
+            _ => {}
         }
-        
-        if c == '\n' {
-            current_pos += 1;
+    }
+    None
+}
+
+/// A symbol as it would be declared: `fn area(w, h)`, `struct Point`
+fn declaration(symbol: &Symbol) -> String {
+    use symbols::SymbolKind as Kind;
+    let owner = symbol.container.as_deref().map_or(String::new(), |owner| format!("{}::", owner));
+    match symbol.kind {
+        Kind::Function => format!("fn {}({})", symbol.name, symbol.params.join(", ")),
+        Kind::Method => format!("fn {}{}({})", owner, symbol.name, symbol.params.join(", ")),
+        Kind::Struct => format!("struct {}", symbol.name),
+        Kind::Field => format!("{}.{}", symbol.container.as_deref().unwrap_or("_"), symbol.name),
+        Kind::Enum => format!("enum {}", symbol.name),
+        Kind::Variant => format!("{}{}", owner, symbol.name),
+        Kind::Trait => format!("trait {}", symbol.name),
+        Kind::Constant => format!("const {}", symbol.name),
+        Kind::TypeAlias => format!("type {}", symbol.name),
+        Kind::Variable => format!("let {}", symbol.name),
+        Kind::Parameter => format!("{} (parameter)", symbol.name),
+        Kind::Module => format!("use {}", symbol.name),
+    }
+}
+
+fn code_block(code: &str) -> String {
+    format!("```knull\n{}\n```", code)
+}
+
+/// Hover text for a builtin: its signature, doc and category
+fn builtin_doc(builtin: &Builtin) -> String {
+    let mut doc = code_block(&builtin.signature());
+    if !builtin.doc.is_empty() {
+        doc.push_str("\n\n");
+        doc.push_str(builtin.doc);
+    }
+    if !builtin.category.is_empty() {
+        doc.push_str(&format!("\n\n*{}*", builtin.category));
+    }
+    doc
+}
+
+fn keyword_doc(word: &str) -> Option<&'static str> {
+    Some(match word {
+        "fn" => "**fn** - Function declaration\n\nDeclares a new function.",
+        "let" => "**let** - Immutable binding\n\nDeclares a variable that cannot be reassigned.",
+        "var" => "**var** - Mutable binding\n\nDeclares a variable that can be reassigned.",
+        "struct" => "**struct** - Structure definition\n\nDefines a new struct type.",
+        "enum" => "**enum** - Enumeration definition\n\nDefines a new enum type.",
+        "if" => "**if** - Conditional\n\nConditional branching.",
+        "else" => "**else** - Alternative branch\n\nAlternative branch for if statements.",
+        "match" => "**match** - Pattern matching\n\nPattern matching with exhaustiveness checking.",
+        "loop" => "**loop** - Infinite loop\n\nInfinite loop that must be broken explicitly.",
+        "while" => "**while** - Conditional loop\n\nLoop that continues while condition is true.",
+        "for" => "**for** - Iterator loop\n\nLoop over an iterator or range.",
+        "return" => "**return** - Return value\n\nReturn from a function.",
+        "unsafe" => "**unsafe** - Unsafe block\n\nDisables safety checks for low-level operations.",
+        "comptime" => "**comptime** - Compile-time execution\n\nExecutes code at compile time.",
+        "import" => "**import** - Import module\n\nImports items from another module.",
+        "pub" => "**pub** - Public visibility\n\nMakes an item publicly accessible.",
+        "i32" => "**i32** - 32-bit signed integer\n\nSigned 32-bit integer type.",
+        "i64" => "**i64** - 64-bit signed integer\n\nSigned 64-bit integer type.",
+        "u32" => "**u32** - 32-bit unsigned integer\n\nUnsigned 32-bit integer type.",
+        "u64" => "**u64** - 64-bit unsigned integer\n\nUnsigned 64-bit integer type.",
+        "f64" => "**f64** - 64-bit floating point\n\nDouble-precision floating point type.",
+        "bool" => "**bool** - Boolean\n\nBoolean type (true or false).",
+        "String" => "**String** - String type\n\nDynamically allocated UTF-8 string.",
+        "Vec" => "**Vec<T>** - Vector type\n\nDynamically growing array.",
+        _ => return None,
+    })
+}
+
+fn symbol_item(symbol: &Symbol) -> CompletionItem {
+    use symbols::SymbolKind as Kind;
+    let kind = match symbol.kind {
+        Kind::Function => CompletionItemKind::FUNCTION,
+        Kind::Method => CompletionItemKind::METHOD,
+        Kind::Struct => CompletionItemKind::STRUCT,
+        Kind::Field => CompletionItemKind::FIELD,
+        Kind::Enum => CompletionItemKind::ENUM,
+        Kind::Variant => CompletionItemKind::ENUM_MEMBER,
+        Kind::Trait => CompletionItemKind::INTERFACE,
+        Kind::Constant => CompletionItemKind::CONSTANT,
+        Kind::TypeAlias => CompletionItemKind::TYPE_PARAMETER,
+        Kind::Variable | Kind::Parameter => CompletionItemKind::VARIABLE,
+        Kind::Module => CompletionItemKind::MODULE,
+    };
+    CompletionItem {
+        label: symbol.name.clone(),
+        kind: Some(kind),
+        detail: Some(declaration(symbol)),
+        ..CompletionItem::default()
+    }
+}
+
+fn builtin_item(builtin: &Builtin) -> CompletionItem {
+    CompletionItem {
+        label: builtin.name.to_string(),
+        kind: Some(if builtin.receiver.is_some() { CompletionItemKind::METHOD } else { CompletionItemKind::FUNCTION }),
+        detail: Some(builtin.signature()),
+        documentation: (!builtin.doc.is_empty()).then(|| Documentation::String(builtin.doc.to_string())),
+        ..CompletionItem::default()
+    }
+}
+
+/// `argument` is the one the cursor is in; past the last parameter, it is
+/// still the last when that takes the rest
+fn signature_info(label: String, params: &[impl AsRef<str>], doc: Option<String>, argument: usize) -> SignatureInformation {
+    let rest = params.last().is_some_and(|param| param.as_ref().ends_with("..."));
+    let active = if rest { argument.min(params.len() - 1) } else { argument };
+    SignatureInformation {
+        label,
+        documentation: doc.map(Documentation::String),
+        parameters: Some(
+            params
+                .iter()
+                .map(|param| ParameterInformation {
+                    label: ParameterLabel::Simple(param.as_ref().to_string()),
+                    documentation: None,
+                })
+                .collect(),
+        ),
+        active_parameter: Some(active as u32),
+    }
+}
+
+/// The position just past the last character of `source`
+fn end_position(source: &str) -> Position {
+    let line = source.matches('\n').count();
+    let last = source.rsplit('\n').next().unwrap_or("");
+    Position::new(line as u32, last.encode_utf16().count() as u32)
+}
+
+/// The file behind a `file://` URI, canonical like the paths imports resolve to
+fn file_path(uri: &Url) -> Option<PathBuf> {
+    let path = uri.to_file_path().ok()?;
+    Some(path.canonicalize().unwrap_or(path))
+}
+
+fn symbol_kind(kind: symbols::SymbolKind) -> SymbolKind {
+    use symbols::SymbolKind as Kind;
+    match kind {
+        Kind::Function => SymbolKind::FUNCTION,
+        Kind::Method => SymbolKind::METHOD,
+        Kind::Struct => SymbolKind::STRUCT,
+        Kind::Field => SymbolKind::FIELD,
+        Kind::Enum => SymbolKind::ENUM,
+        Kind::Variant => SymbolKind::ENUM_MEMBER,
+        Kind::Trait => SymbolKind::INTERFACE,
+        Kind::Constant => SymbolKind::CONSTANT,
+        Kind::TypeAlias => SymbolKind::TYPE_PARAMETER,
+        Kind::Variable | Kind::Parameter => SymbolKind::VARIABLE,
+        Kind::Module => SymbolKind::MODULE,
+    }
+}
+
+/// The LSP position of a 1-based line and char column; LSP counts UTF-16 units
+fn to_position(source: &str, line: usize, col: usize) -> Position {
+    let text = source.split('\n').nth(line.saturating_sub(1)).unwrap_or("");
+    let character: usize = text.chars().take(col.saturating_sub(1)).map(char::len_utf16).sum();
+    Position::new(line.saturating_sub(1) as u32, character as u32)
+}
+
+fn to_range(source: &str, span: &Span) -> Range {
+    Range::new(to_position(source, span.line, span.col), to_position(source, span.end_line, span.end_col))
+}
+
+/// The 1-based line and char column of an LSP position
+fn from_position(source: &str, position: Position) -> (usize, usize) {
+    let text = source.split('\n').nth(position.line as usize).unwrap_or("");
+    let mut units = 0;
+    let mut col = 1;
+    for c in text.chars() {
+        if units >= position.character as usize {
+            break;
         }
+        units += c.len_utf16();
+        col += 1;
     }
-    
-    source[start..end].to_string()
+    (position.line as usize + 1, col)
 }
 
-fn simple_format(source: &str) -> String {
-    let mut result = String::new();
-    let mut indent = 0;
-    let mut in_string = false;
-    
-    for c in source.chars() {
-        if c == '"' && !in_string {
-            in_string = true;
-        } else if c == '"' && in_string {
-            in_string = false;
+/// Apply one edit of incremental sync; a change without a range replaces the whole text
+fn apply_change(text: &mut String, change: TextDocumentContentChangeEvent) {
+    match change.range {
+        Some(range) => {
+            let (start, end) = (byte_offset(text, range.start), byte_offset(text, range.end));
+            text.replace_range(start..end.max(start), &change.text);
         }
-        
-        if !in_string {
-            if c == '{' {
-                result.push(c);
-                result.push('\n');
-                indent += 1;
-                for _ in 0..indent {
-                    result.push_str("    ");
-                }
-                continue;
-            }
-            if c == '}' {
-                indent = indent.saturating_sub(1);
-                result.push('\n');
-                for _ in 0..indent {
-                    result.push_str("    ");
-                }
-                result.push(c);
-                continue;
-            }
-            if c == '\n' {
-                result.push(c);
-                for _ in 0..indent {
-                    result.push_str("    ");
+        None => *text = change.text,
+    }
+}
+
+fn byte_offset(text: &str, position: Position) -> usize {
+    let mut offset = 0;
+    for (i, line) in text.split_inclusive('\n').enumerate() {
+        if i == position.line as usize {
+            let mut units = 0;
+            for (at, c) in line.char_indices() {
+                if units >= position.character as usize || c == '\n' {
+                    return offset + at;
                 }
-                continue;
+                units += c.len_utf16();
             }
+            return offset + line.len();
         }
-        
-        result.push(c);
+        offset += line.len();
     }
-    
-    result
+    text.len()
 }
@@ -670,6 +670,8 @@ fn make_token(kind: TokenKind, value: String, line: usize, col: usize) -> Token
         value,
         line,
         col,
+        end_line: line,
+        end_col: col,
     }
 }
 
@@ -4,27 +4,40 @@
 #![allow(dead_code)]
 
 mod ast;
+mod bench;
+mod builtins;
 mod c_codegen;
+mod checker;
 mod cli;
 mod compiler;
 mod comptime;
 mod doc;
 mod ffi;
+mod formatter;
 mod gc;
 mod incremental;
 mod interpreter;
 mod linear_check;
 mod effects;
 mod macros;
+mod match_check;
+mod modules;
 #[cfg(feature = "lsp")]
 mod lsp;
 mod lexer;
 mod ownership;
 mod parser;
 mod pkg;
+mod property;
+mod repl;
+mod shadow_check;
+#[cfg(feature = "lsp")]
+mod symbols;
+mod test_runner;
 #[cfg(feature = "debugger")]
 mod debugger;
 mod type_system;
+mod vm;
 mod wasm_codegen;
 
 #[cfg(feature = "llvm-backend")]
@@ -55,6 +68,12 @@ struct Cli {
 
     #[arg(short, long, global = true, help = "Verbose output")]
     verbose: bool,
+
+    #[arg(long, global = true, help = "Fail instead of using the network")]
+    offline: bool,
+
+    #[arg(long, global = true, help = "Fail instead of changing knull.lock")]
+    locked: bool,
 }
 
 #[derive(Subcommand)]
@@ -62,8 +81,15 @@ enum Commands {
     /// Run a Knull file
     #[command(alias = "r")]
     Run {
-        /// The .knull file to run
-        file: PathBuf,
+        /// The .knull file to run (default: the `entry` of the project's knull.toml)
+        file: Option<PathBuf>,
+        /// Run on the bytecode VM instead of the tree-walking interpreter
+        #[arg(long)]
+        vm: bool,
+        /// Maximum nesting of function calls before a RecursionError
+        /// (default: `[run] max-depth` in knull.toml, else 10000)
+        #[arg(long)]
+        max_depth: Option<usize>,
     },
     /// Compile a Knull file to binary
     #[command(alias = "b")]
@@ -89,17 +115,26 @@ enum Commands {
         #[arg(short, long)]
         output: Option<PathBuf>,
     },
-    /// Check syntax without building
+    /// Check a file for errors without running it
     #[command(alias = "c")]
     Check {
         /// The .knull file to check
         file: PathBuf,
+        /// Print findings as `text`, or as `json` for CI
+        #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+        format: String,
     },
-    /// Format a Knull file
+    /// Format Knull files, keeping comments
     #[command(alias = "f")]
     Fmt {
-        /// The .knull file to format
-        file: PathBuf,
+        /// Files or directories to format (default: the current directory)
+        files: Vec<PathBuf>,
+        /// Write nothing; show a diff and fail if any file would change
+        #[arg(long)]
+        check: bool,
+        /// Format standard input and print the result
+        #[arg(long)]
+        stdin: bool,
     },
     /// Create a new Knull project
     #[command(alias = "n")]
@@ -115,6 +150,36 @@ enum Commands {
         /// Package version
         version: Option<String>,
     },
+    /// Remove a dependency from the project
+    Remove {
+        /// Package name
+        package: String,
+    },
+    /// Update locked dependencies to the newest versions knull.toml allows
+    Update {
+        /// Only update this package (default: all of them)
+        package: Option<String>,
+    },
+    /// Fetch every package in knull.lock, resolving them first if there is none
+    Fetch,
+    /// Copy every locked package into vendor/, for builds without the network
+    Vendor,
+    /// Publish the project to the package registry
+    Publish {
+        /// Publish to the local registry in ~/.knull/registry instead
+        #[arg(long)]
+        local: bool,
+        /// Registry token (default: $KNULL_REGISTRY_TOKEN)
+        #[arg(long)]
+        token: Option<String>,
+    },
+    /// Show the project's dependencies as a tree
+    Tree,
+    /// Search the registry for packages
+    Search {
+        /// Text to look for in package names
+        query: String,
+    },
     /// Run tests
     #[command(alias = "t")]
     Test {
@@ -127,6 +192,32 @@ enum Commands {
         /// Generate documentation
         #[arg(short, long)]
         doc: bool,
+        /// Only run tests whose `file::name` contains this
+        #[arg(short, long)]
+        filter: Option<String>,
+        /// Number of tests to run in parallel (default: number of CPUs)
+        #[arg(short, long)]
+        jobs: Option<usize>,
+        /// Write a JUnit XML report to this file
+        #[arg(long, value_name = "FILE")]
+        junit: Option<PathBuf>,
+        /// Write a JSON report to this file
+        #[arg(long, value_name = "FILE")]
+        json: Option<PathBuf>,
+        /// Compare benchmarks against a baseline saved with --save-baseline
+        #[arg(long, value_name = "FILE")]
+        baseline: Option<PathBuf>,
+        /// Save benchmark results as a baseline to this file
+        #[arg(long, value_name = "FILE")]
+        save_baseline: Option<PathBuf>,
+        /// Generated cases to check each property with
+        #[arg(long, value_name = "N")]
+        cases: Option<usize>,
+        /// Seed for property tests, to replay a failing run (default: random)
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Test files or directories (default: tests/, test/, src/tests/)
+        paths: Vec<PathBuf>,
     },
     /// Start interactive REPL
     #[command(alias = "i")]
@@ -153,12 +244,12 @@ enum Commands {
         #[arg(long)]
         stdin: bool,
     },
-    /// Start debugger
+    /// Debug a file for a Debug Adapter Protocol client on stdin/stdout
     #[cfg(feature = "debugger")]
     Debug {
         /// The .knull file to debug
         file: PathBuf,
-        /// Breakpoint line numbers
+        /// Lines of the file to break at before the client sets any breakpoints
         #[arg(short, long)]
         break_at: Option<Vec<u32>>,
     },
@@ -169,9 +260,15 @@ fn main() {
     colored::control::set_override(true);
 
     let cli = Cli::parse();
+    let mode = pkg::FetchMode { offline: cli.offline, locked: cli.locked };
 
-    let result = match cli.command {
-        Some(Commands::Run { file }) => cli::run_file(&file, cli.verbose),
+    // Programs run on a thread with a large stack so deep recursion ends in a
+    // RecursionError rather than a crash
+    let result = interpreter::with_large_stack(move || match cli.command {
+        Some(Commands::Run { file, vm, max_depth }) => match file {
+            Some(file) => cli::run_file(&file, cli.verbose, vm, max_depth),
+            None => cli::run_project(cli.verbose, vm, max_depth, mode),
+        },
         Some(Commands::Build {
             file,
             output,
@@ -186,13 +283,20 @@ fn main() {
             }
         }
         Some(Commands::Asm { file, output }) => cli::generate_asm(&file, output.as_deref()),
-        Some(Commands::Check { file }) => cli::check_file(&file),
-        Some(Commands::Fmt { file }) => cli::format_file(&file),
+        Some(Commands::Check { file, format }) => cli::check_file(&file, format == "json"),
+        Some(Commands::Fmt { files, check, stdin }) => cli::format_files(&files, check, stdin),
         Some(Commands::New { name }) => cli::new_project(&name),
         Some(Commands::Add { package, version }) => {
-            cli::add_dependency(&package, version.as_deref())
+            cli::add_dependency(&package, version.as_deref(), mode)
         }
-        Some(Commands::Test { bench, property, doc }) => {
+        Some(Commands::Remove { package }) => cli::remove_dependency(&package, mode),
+        Some(Commands::Update { package }) => cli::update_dependencies(package.as_deref(), mode),
+        Some(Commands::Fetch) => cli::fetch_dependencies(mode),
+        Some(Commands::Vendor) => cli::vendor_dependencies(mode),
+        Some(Commands::Publish { local, token }) => cli::publish(local, token.as_deref(), mode),
+        Some(Commands::Tree) => cli::dependency_tree(mode),
+        Some(Commands::Search { query }) => cli::search_packages(&query, mode),
+        Some(Commands::Test { bench, property: property_flag, doc, filter, jobs, junit, json, baseline, save_baseline, cases, seed, paths }) => {
             if doc {
                 println!("{}", "Generating documentation...".bright_yellow());
                 let project_path = std::env::current_dir().unwrap_or_default();
@@ -204,10 +308,25 @@ fn main() {
                     Err(e) => Err(e)
                 }
             } else {
-                cli::run_tests_with_options(bench, property)
+                let property = property::PropertyConfig {
+                    cases: cases.unwrap_or(property::DEFAULT_CASES),
+                    seed: seed.unwrap_or_else(rand::random),
+                };
+                let options = test_runner::TestOptions {
+                    paths,
+                    filter,
+                    jobs,
+                    junit,
+                    json,
+                    baseline,
+                    save_baseline,
+                    bench: Default::default(),
+                    property,
+                };
+                cli::run_tests_with_options(bench, property_flag, &options)
             }
         }
-        Some(Commands::Repl) => cli::start_repl(),
+        Some(Commands::Repl) => repl::start(),
         Some(Commands::Eval { expr }) => cli::eval_expr(&expr, cli.verbose),
         Some(Commands::Version) => {
             show_version();
@@ -220,16 +339,16 @@ fn main() {
         #[cfg(feature = "lsp")]
         Some(Commands::Lsp { port, stdin }) => {
             if stdin {
-                crate::lsp::run_stdio()?;
+                crate::lsp::run_stdio()
             } else {
-                crate::lsp::run_server(port)?;
+                crate::lsp::run_server(port)
             }
         }
         #[cfg(feature = "debugger")]
         Some(Commands::Debug { file, break_at }) => {
-            crate::debugger::start_debug_session(&file, break_at.unwrap_or_default())?;
+            crate::debugger::start_debug_session(&file, break_at.unwrap_or_default())
         }
-    };
+    });
 
     match result {
         Ok(_) => {}
@@ -277,7 +396,7 @@ fn show_help() {
     println!("  knull [OPTIONS] <COMMAND>");
     println!();
     println!("{}", "CORE:".bright_white().bold());
-    println!("  {}  Run a .knull file",                   "run   <file>      ".bright_cyan());
+    println!("  {}  Run a .knull file (or the project)",  "run   [file]      ".bright_cyan());
     println!("  {}  Evaluate an inline snippet",           "eval  <expr>      ".bright_cyan());
     println!("  {}  Start interactive REPL",               "repl              ".bright_cyan());
     println!("  {}  Check syntax/types",                   "check <file>      ".bright_cyan());
@@ -290,10 +409,19 @@ fn show_help() {
     println!("{}", "PROJECT:".bright_white().bold());
     println!("  {}  Create a new project",                 "new   <name>      ".bright_cyan());
     println!("  {}  Add a dependency",                     "add   <pkg>       ".bright_cyan());
+    println!("  {}  Remove a dependency",                  "remove <pkg>      ".bright_cyan());
+    println!("  {}  Update locked dependencies",           "update [pkg]      ".bright_cyan());
+    println!("  {}  Fetch locked dependencies",            "fetch             ".bright_cyan());
+    println!("  {}  Copy locked dependencies to vendor/",  "vendor            ".bright_cyan());
+    println!("  {}  Show the dependency tree",             "tree              ".bright_cyan());
+    println!("  {}  Search the registry",                  "search <query>    ".bright_cyan());
+    println!("  {}  Publish to the registry",              "publish           ".bright_cyan());
     println!("  {}  Run test suite",                       "test              ".bright_cyan());
     println!();
     println!("{}", "OPTIONS:".bright_white().bold());
     println!("  -v, --verbose   Verbose output");
+    println!("      --offline   Fail instead of using the network");
+    println!("      --locked    Fail instead of changing knull.lock");
     println!("  -h, --help      Print help");
     println!("  -V, --version   Print version");
     println!();
@@ -0,0 +1,150 @@
+//! Knull Match Exhaustiveness Checker
+//!
+//! Warns about `match` expressions over an enum that neither cover every
+//! variant nor end in a catch-all arm. Runtime `match` simply yields `null`
+//! when nothing matches, so these are reported by `knull check` as warnings.
+
+use std::collections::HashMap;
+
+use crate::parser::{ASTNode, MatchArm, Pattern, Span};
+
+/// A `match` that can fall through without matching
+#[derive(Debug, Clone)]
+pub struct MatchWarning {
+    pub message: String,
+    pub span: Option<Span>,
+}
+
+/// Check every `match` in a program against the enums it defines
+pub fn check_program(ast: &ASTNode) -> Vec<MatchWarning> {
+    let mut enums = HashMap::new();
+    collect_enums(ast, &mut enums);
+    let mut warnings = Vec::new();
+    if !enums.is_empty() {
+        check_node(ast, None, &enums, &mut warnings);
+    }
+    warnings
+}
+
+fn collect_enums(node: &ASTNode, enums: &mut HashMap<String, Vec<String>>) {
+    if let ASTNode::EnumDef { name, variants } = node {
+        enums.insert(name.clone(), variants.iter().map(|v| v.name.clone()).collect());
+    }
+    for child in node.children() {
+        collect_enums(child, enums);
+    }
+}
+
+fn check_node(
+    node: &ASTNode,
+    span: Option<&Span>,
+    enums: &HashMap<String, Vec<String>>,
+    warnings: &mut Vec<MatchWarning>,
+) {
+    let span = node.span().or(span);
+    if let ASTNode::Match { arms, .. } = node {
+        if let Some(message) = missing_variants(arms, enums) {
+            warnings.push(MatchWarning { message, span: span.cloned() });
+        }
+    }
+    for child in node.children() {
+        check_node(child, span, enums, warnings);
+    }
+}
+
+/// Describe the variants a `match` misses, if it is over a known enum
+fn missing_variants(arms: &[MatchArm], enums: &HashMap<String, Vec<String>>) -> Option<String> {
+    let patterns: Vec<&Pattern> = arms.iter().flat_map(|arm| flatten(&arm.pattern)).collect();
+    let (enum_name, variants) = patterns.iter().find_map(|p| enum_of(p, enums))?;
+
+    let mut covered = Vec::new();
+    for arm in arms.iter().filter(|arm| arm.guard.is_none()) {
+        for pattern in flatten(&arm.pattern) {
+            match pattern {
+                Pattern::Wildcard => return None,
+                Pattern::Identifier(name) if !variants.contains(name) => return None,
+                Pattern::Identifier(name) => covered.push(name.as_str()),
+                Pattern::Enum { variant, data, .. } if data.as_deref().is_none_or(irrefutable) => {
+                    covered.push(variant.as_str())
+                }
+                Pattern::Struct { name, fields } if fields.iter().all(|(_, p)| irrefutable(p)) => {
+                    covered.push(name.as_str())
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let missing: Vec<&str> = variants
+        .iter()
+        .map(String::as_str)
+        .filter(|v| !covered.contains(v))
+        .collect();
+    if missing.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "non-exhaustive match on `{}`: missing {}",
+        enum_name,
+        missing.join(", ")
+    ))
+}
+
+/// The alternatives of an or-pattern, or the pattern itself
+fn flatten(pattern: &Pattern) -> Vec<&Pattern> {
+    match pattern {
+        Pattern::Or(alternatives) => alternatives.iter().flat_map(flatten).collect(),
+        other => vec![other],
+    }
+}
+
+/// The enum a pattern names: `Shape::Circle(..)` directly, a bare variant
+/// name only when exactly one enum has it
+fn enum_of<'a>(
+    pattern: &Pattern,
+    enums: &'a HashMap<String, Vec<String>>,
+) -> Option<(&'a String, &'a Vec<String>)> {
+    let variant = match pattern {
+        Pattern::Enum { name, .. } if !name.is_empty() => return enums.get_key_value(name),
+        Pattern::Enum { variant, .. } => variant,
+        Pattern::Struct { name, .. } | Pattern::Identifier(name) => name,
+        _ => return None,
+    };
+    let mut owners = enums.iter().filter(|(_, variants)| variants.contains(variant));
+    match (owners.next(), owners.next()) {
+        (Some(owner), None) => Some(owner),
+        _ => None,
+    }
+}
+
+/// Whether a pattern matches any value of the right shape
+fn irrefutable(pattern: &Pattern) -> bool {
+    match pattern {
+        Pattern::Wildcard | Pattern::Identifier(_) => true,
+        Pattern::Tuple(items) => items.iter().all(irrefutable),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn warnings(src: &str) -> Vec<String> {
+        let ast = Parser::new(src).parse().unwrap();
+        check_program(&ast).into_iter().map(|w| w.message).collect()
+    }
+
+    #[test]
+    fn test_reports_missing_variants() {
+        let src = "enum Shape { Circle(f64), Rect(f64, f64), Empty }\nfn f(s) {\n    match s {\n        Shape::Circle(r) => r,\n        Shape::Rect(w, 2) => w,\n    }\n}";
+        assert_eq!(warnings(src), vec!["non-exhaustive match on `Shape`: missing Rect, Empty"]);
+    }
+
+    #[test]
+    fn test_catch_all_and_full_cover_are_exhaustive() {
+        let src = "enum Shape { Circle(f64), Empty }\nlet a = match x { Shape::Circle(r) => r, _ => 0 }\nlet b = match x { Circle(r) => r, Empty => 0 }\nlet c = match 3 { 1 => 1 }";
+        assert!(warnings(src).is_empty());
+    }
+}
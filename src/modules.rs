@@ -0,0 +1,220 @@
+//! Knull Module Resolution
+//!
+//! Locates the source file behind `import "..."` / `use ...`. A path is tried
+//! relative to the importing file, then the project root, then the packages
+//! the project depends on (directly or through `knull.lock`), and finally the
+//! standard library. Code inside a dependency also sees the packages locked by
+//! the project being run.
+
+use std::path::{Component, Path, PathBuf};
+
+use crate::pkg::manager::{PackageManager, PackageManifest};
+
+/// Resolve an import path to the canonical path of the module's source file.
+/// `importer` is the file containing the import (the CWD is used without one);
+/// `project` is the root of the project being run, when it differs from the importer's.
+pub fn resolve_import(spec: &str, importer: Option<&Path>, project: Option<&Path>) -> Result<PathBuf, String> {
+    let rel = spec_to_path(spec);
+    let base_dir = importer
+        .and_then(Path::parent)
+        .map(Path::to_path_buf)
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_default();
+    let base_dir = base_dir.canonicalize().unwrap_or(base_dir);
+
+    let mut candidates = vec![base_dir.join(&rel)];
+    let root = project_root(&base_dir);
+    if let Some(root) = &root {
+        candidates.push(root.join(&rel));
+        if let Some(dep) = dependency_path(root, &rel) {
+            candidates.push(dep);
+        }
+    }
+    if let Some(project) = project.filter(|p| Some(*p) != root.as_deref()) {
+        if let Some(dep) = dependency_path(project, &rel) {
+            candidates.push(dep);
+        }
+    }
+    for dir in stdlib_dirs() {
+        candidates.push(dir.join(&rel));
+    }
+
+    for candidate in &candidates {
+        if let Some(file) = module_file(candidate) {
+            return Ok(file.canonicalize().unwrap_or(file));
+        }
+    }
+    Err(format!("Cannot import '{}': no such module", spec))
+}
+
+/// Module name used when an import has no `as` alias: the file stem, or the
+/// package directory name for `src/lib.knull`-style entry points
+pub fn module_name(path: &Path) -> String {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("module");
+    if matches!(stem, "lib" | "mod" | "main") {
+        let mut dir = path.parent();
+        if dir.and_then(Path::file_name).is_some_and(|d| d == "src") {
+            dir = dir.and_then(Path::parent);
+        }
+        if let Some(name) = dir.and_then(Path::file_name).and_then(|d| d.to_str()) {
+            return name.to_string();
+        }
+    }
+    stem.to_string()
+}
+
+/// `"pkg/json"` stays a path; `net`, `std.gui.sdl2` and `a::b` become `net`, `gui/sdl2`, `a/b`
+fn spec_to_path(spec: &str) -> PathBuf {
+    let spec = spec.trim_matches('"');
+    if spec.contains('/') || spec.ends_with(".knull") {
+        return PathBuf::from(spec);
+    }
+    let spec = spec.replace("::", ".");
+    let spec = spec.strip_prefix("std.").unwrap_or(&spec);
+    spec.split('.').collect()
+}
+
+/// The file a candidate path names: the path itself, `path.knull`, or a
+/// package directory's entry point
+fn module_file(path: &Path) -> Option<PathBuf> {
+    if path.is_file() {
+        return Some(path.to_path_buf());
+    }
+    if path.extension().is_none() {
+        let with_ext = path.with_extension("knull");
+        if with_ext.is_file() {
+            return Some(with_ext);
+        }
+    }
+    if !path.is_dir() {
+        return None;
+    }
+    // Registry copies carry their manifest as package.toml
+    for manifest in ["knull.toml", "package.toml"] {
+        if let Ok(manifest) = PackageManifest::load(&path.join(manifest)) {
+            let entry = path.join(&manifest.package.entry);
+            if entry.is_file() {
+                return Some(entry);
+            }
+        }
+    }
+    let own_name = path.file_name()?.to_str()?;
+    [
+        "src/lib.knull".to_string(),
+        "lib.knull".to_string(),
+        "mod.knull".to_string(),
+        format!("{}.knull", own_name),
+    ]
+    .iter()
+    .map(|f| path.join(f))
+    .find(|f| f.is_file())
+}
+
+/// Nearest ancestor of `dir` containing a `knull.toml`
+pub(crate) fn project_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|d| d.join("knull.toml").is_file())
+        .map(Path::to_path_buf)
+}
+
+/// `rel` inside the installed copy of the dependency named by its first component
+fn dependency_path(root: &Path, rel: &Path) -> Option<PathBuf> {
+    let mut components = rel.components();
+    let name = match components.next()? {
+        Component::Normal(name) => name.to_str()?,
+        _ => return None,
+    };
+    let dir = PackageManager::new(root.to_path_buf()).ok()?.installed_dependency(name)?;
+    Some(dir.join(components.as_path()))
+}
+
+/// Standard library locations: `$KNULL_STDLIB`, `~/.knull/stdlib`, and any
+/// `stdlib/` beside an ancestor of the running executable
+fn stdlib_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Ok(dir) = std::env::var("KNULL_STDLIB") {
+        dirs.push(PathBuf::from(dir));
+    }
+    if let Some(home) = dirs::home_dir() {
+        dirs.push(home.join(".knull").join("stdlib"));
+    }
+    if let Ok(exe) = std::env::current_exe() {
+        if let Some(dir) = exe.ancestors().map(|a| a.join("stdlib")).find(|d| d.is_dir()) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_to_path() {
+        assert_eq!(spec_to_path("packages/json"), PathBuf::from("packages/json"));
+        assert_eq!(spec_to_path("net"), PathBuf::from("net"));
+        assert_eq!(spec_to_path("std.gui.sdl2"), PathBuf::from("gui/sdl2"));
+        assert_eq!(spec_to_path("core::string"), PathBuf::from("core/string"));
+        assert_eq!(spec_to_path("lib.knull"), PathBuf::from("lib.knull"));
+    }
+
+    #[test]
+    fn test_module_name() {
+        assert_eq!(module_name(Path::new("/p/packages/json/src/lib.knull")), "json");
+        assert_eq!(module_name(Path::new("/p/stdlib/math/math.knull")), "math");
+        assert_eq!(module_name(Path::new("util.knull")), "util");
+    }
+
+    #[test]
+    fn test_resolves_relative_to_importer() {
+        let dir = std::env::temp_dir().join(format!("knull_modules_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("pkg/src")).unwrap();
+        std::fs::write(dir.join("util.knull"), "fn f() { 1 }").unwrap();
+        std::fs::write(dir.join("pkg/src/lib.knull"), "fn g() { 2 }").unwrap();
+        let importer = dir.join("main.knull");
+
+        let util = resolve_import("util", Some(&importer), None).unwrap();
+        assert!(util.ends_with("util.knull"));
+        let pkg = resolve_import("pkg", Some(&importer), None).unwrap();
+        assert!(pkg.ends_with("pkg/src/lib.knull"));
+        assert!(resolve_import("missing", Some(&importer), None).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_resolves_dependency_names() {
+        let dir = std::env::temp_dir().join(format!("knull_deps_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("app/src")).unwrap();
+        std::fs::create_dir_all(dir.join("shared/src")).unwrap();
+        let manifest = |name: &str, deps: &str| {
+            format!(
+                "[package]\nname = \"{}\"\nversion = \"1.0.0\"\nedition = \"2024\"\nentry = \"src/lib.knull\"\n\n[dependencies]\n{}",
+                name, deps
+            )
+        };
+        std::fs::create_dir_all(dir.join("util/src")).unwrap();
+        std::fs::write(
+            dir.join("app/knull.toml"),
+            manifest("app", "shared = { path = \"../shared\" }\nutil = { path = \"../util\" }"),
+        )
+        .unwrap();
+        std::fs::write(dir.join("shared/knull.toml"), manifest("shared", "")).unwrap();
+        std::fs::write(dir.join("shared/src/lib.knull"), "fn g() { 2 }").unwrap();
+        std::fs::write(dir.join("util/knull.toml"), manifest("util", "")).unwrap();
+        std::fs::write(dir.join("util/src/lib.knull"), "fn h() { 3 }").unwrap();
+
+        let importer = dir.join("app/src/main.knull");
+        let shared = resolve_import("shared", Some(&importer), None).unwrap();
+        assert!(shared.ends_with("shared/src/lib.knull"), "{}", shared.display());
+
+        // A dependency sees the packages of the project being run
+        let in_shared = dir.join("shared/src/lib.knull");
+        assert!(resolve_import("util", Some(&in_shared), None).is_err());
+        let util = resolve_import("util", Some(&in_shared), Some(&dir.join("app"))).unwrap();
+        assert!(util.ends_with("util/src/lib.knull"), "{}", util.display());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
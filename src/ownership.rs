@@ -3,13 +3,15 @@
 //! Implements the ownership and borrowing rules for memory safety
 //! without garbage collection. This is used in Expert and God modes.
 
-use crate::parser::ASTNode;
-use std::collections::HashMap;
+use crate::parser::{ASTNode, Literal, Span};
+use std::collections::{HashMap, HashSet};
 
 /// Ownership status of a value
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Ownership {
     Owned,
+    /// A number, bool or null, copied rather than moved
+    Copy,
     Borrowed,
     MutBorrowed,
     Moved,
@@ -44,13 +46,20 @@ impl OwnershipScope {
 /// Ownership checker
 pub struct OwnershipChecker {
     scopes: Vec<OwnershipScope>,
-    errors: Vec<String>,
+    /// Top-level Knull functions, which take their arguments even when a
+    /// builtin has the same name
+    functions: HashSet<String>,
+    /// Span of the innermost node being checked that has one
+    span: Option<Span>,
+    errors: Vec<(String, Option<Span>)>,
 }
 
 impl OwnershipChecker {
     pub fn new() -> Self {
         OwnershipChecker {
             scopes: vec![OwnershipScope::new()],
+            functions: HashSet::new(),
+            span: None,
             errors: Vec::new(),
         }
     }
@@ -61,10 +70,16 @@ impl OwnershipChecker {
         if self.errors.is_empty() {
             Ok(())
         } else {
-            Err(self.errors.join("\n"))
+            let messages: Vec<_> = self.errors.iter().map(|(message, _)| message.as_str()).collect();
+            Err(messages.join("\n"))
         }
     }
 
+    /// What `check` found, each with the span of the node it is about
+    pub fn errors(&self) -> &[(String, Option<Span>)] {
+        &self.errors
+    }
+
     fn push_scope(&mut self) {
         self.scopes.push(OwnershipScope::new());
     }
@@ -88,19 +103,28 @@ impl OwnershipChecker {
 
     fn check_node(&mut self, node: &ASTNode) -> Result<(), String> {
         match node {
+            ASTNode::Spanned { span, node } => {
+                let outer = self.span.replace(span.clone());
+                let result = self.check_node(node);
+                self.span = outer;
+                result
+            }
             ASTNode::Program(items) => {
+                for item in items {
+                    if let ASTNode::Function { name, .. } = item.inner() {
+                        self.functions.insert(name.clone());
+                    }
+                }
                 for item in items {
                     self.check_node(item)?;
                 }
                 Ok(())
             }
-            ASTNode::Function {
-                name: _,
-                params: _,
-                body,
-                ..
-            } => {
+            ASTNode::Function { params, body, .. } | ASTNode::AsyncFunction { params, body, .. } => {
                 self.push_scope();
+                for param in params {
+                    self.current_scope().declare(&param.name, Ownership::Owned);
+                }
                 self.check_node(body)?;
                 self.pop_scope();
                 Ok(())
@@ -114,61 +138,68 @@ impl OwnershipChecker {
                 Ok(())
             }
             ASTNode::Let { name, value, .. } => {
-                let ownership = self.check_expr(value);
-                match ownership {
-                    Ownership::Moved | Ownership::Owned => {
-                        self.current_scope().declare(name, Ownership::Owned);
+                let ownership = match self.check_expr(value, true) {
+                    Ownership::Moved => Ownership::Owned,
+                    other => other,
+                };
+                self.current_scope().declare(name, ownership);
+                Ok(())
+            }
+            ASTNode::Assign { target, value } => {
+                let ownership = match self.check_expr(value, true) {
+                    Ownership::Moved => Ownership::Owned,
+                    other => other,
+                };
+                match target.inner() {
+                    // Assigning gives a moved-from variable a value again
+                    ASTNode::Identifier(name) => {
+                        if let Some((i, _)) = self.find_variable(name) {
+                            self.scopes[i].set(name, ownership);
+                        }
                     }
                     _ => {
-                        self.errors
-                            .push(format!("Cannot bind borrowed value to {}", name));
+                        self.check_expr(target, false);
                     }
                 }
                 Ok(())
             }
-            ASTNode::While { cond, body } => {
-                self.check_expr(cond);
-                self.check_node(body)?;
+            ASTNode::Return(expr) => {
+                self.check_expr(expr, true);
                 Ok(())
             }
-            ASTNode::If {
-                cond,
-                then_body,
-                else_body,
-            } => {
-                self.check_expr(cond);
-                self.check_node(then_body)?;
-                if let Some(else_branch) = else_body {
-                    self.check_node(else_branch)?;
-                }
-                Ok(())
-            }
-            ASTNode::Call { func: _, args } => {
-                for arg in args {
-                    self.check_expr(arg);
-                }
+            other => {
+                self.check_expr(other, false);
                 Ok(())
             }
-            _ => Ok(()),
         }
     }
 
-    fn check_expr(&mut self, expr: &ASTNode) -> Ownership {
+    /// Check an expression; `moving` when its value is taken (bound, passed
+    /// or returned) rather than only read
+    fn check_expr(&mut self, expr: &ASTNode, moving: bool) -> Ownership {
         match expr {
+            ASTNode::Spanned { span, node } => {
+                let outer = self.span.replace(span.clone());
+                let ownership = self.check_expr(node, moving);
+                self.span = outer;
+                ownership
+            }
             ASTNode::Identifier(name) => {
                 if let Some((scope_idx, ownership)) = self.find_variable(name) {
                     match ownership {
-                        Ownership::Owned => {
+                        Ownership::Owned if moving => {
                             // Move the value
                             if scope_idx == self.scopes.len() - 1 {
                                 self.current_scope().set(name, Ownership::Moved);
                             }
                             Ownership::Moved
                         }
+                        Ownership::Owned => Ownership::Owned,
+                        Ownership::Copy => Ownership::Copy,
                         Ownership::Borrowed => Ownership::Borrowed,
                         Ownership::MutBorrowed => Ownership::MutBorrowed,
                         Ownership::Moved => {
-                            self.errors.push(format!("Use of moved value: {}", name));
+                            self.errors.push((format!("use of moved value `{}`", name), self.span.clone()));
                             Ownership::Moved
                         }
                     }
@@ -177,25 +208,80 @@ impl OwnershipChecker {
                     Ownership::Owned
                 }
             }
-            ASTNode::Literal(_) => Ownership::Owned,
-            ASTNode::Binary { left, right, .. } => {
-                self.check_expr(left);
-                self.check_expr(right);
+            ASTNode::Literal(Literal::String(_)) => Ownership::Owned,
+            ASTNode::Literal(_) => Ownership::Copy,
+            ASTNode::Binary { op, left, right } => {
+                let left = self.check_expr(left, false);
+                let right = self.check_expr(right, false);
+                let compares = matches!(op.as_str(), "==" | "!=" | "<" | ">" | "<=" | ">=" | "&&" | "||");
+                if compares || (left == Ownership::Copy && right == Ownership::Copy) {
+                    Ownership::Copy
+                } else {
+                    Ownership::Owned
+                }
+            }
+            ASTNode::Unary { op, operand } if op == "&" || op == "&mut" => {
+                self.check_expr(operand, false);
+                if op == "&" { Ownership::Borrowed } else { Ownership::MutBorrowed }
+            }
+            ASTNode::Unary { operand, .. } => match self.check_expr(operand, false) {
+                Ownership::Copy => Ownership::Copy,
+                _ => Ownership::Owned,
+            },
+            ASTNode::Call { func, args } => {
+                // Builtins only read their arguments; Knull functions take them
+                let takes = match func.inner() {
+                    ASTNode::Identifier(name) => {
+                        self.functions.contains(name) || !crate::interpreter::is_builtin(name)
+                    }
+                    other => {
+                        self.check_expr(other, false);
+                        true
+                    }
+                };
+                for arg in args {
+                    self.check_expr(arg, takes);
+                }
                 Ownership::Owned
             }
-            ASTNode::Unary { operand, .. } => self.check_expr(operand),
-            ASTNode::Call { args, .. } => {
+            ASTNode::MethodCall { obj, args, .. } => {
+                self.check_expr(obj, false);
                 for arg in args {
-                    self.check_expr(arg);
+                    self.check_expr(arg, true);
+                }
+                Ownership::Owned
+            }
+            ASTNode::Array(items) | ASTNode::Tuple(items) => {
+                for item in items {
+                    self.check_expr(item, true);
                 }
                 Ownership::Owned
             }
-            ASTNode::Index { obj, index } => {
-                self.check_expr(obj);
-                self.check_expr(index);
+            ASTNode::StructLiteral { fields, .. } => {
+                for (_, value) in fields {
+                    self.check_expr(value, true);
+                }
+                Ownership::Owned
+            }
+            ASTNode::Map(pairs) => {
+                for (key, value) in pairs {
+                    self.check_expr(key, false);
+                    self.check_expr(value, true);
+                }
+                Ownership::Owned
+            }
+            other => {
+                for child in other.children() {
+                    let _ = self.check_node(child);
+                }
                 Ownership::Owned
             }
-            _ => Ownership::Owned,
         }
     }
 }
+
+impl Default for OwnershipChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
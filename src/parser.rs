@@ -1,6 +1,27 @@
 //! Knull Parser - AST Builder
 
 use crate::lexer::{Lexer, Token, TokenKind};
+use std::sync::Arc;
+
+/// Source range of a node: 1-based line/column of its first token through
+/// the end of its last token.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Span {
+    pub file: Option<Arc<str>>,
+    pub line: usize,
+    pub col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}:{}:{}", file, self.line, self.col),
+            None => write!(f, "{}:{}", self.line, self.col),
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub enum ASTNode {
@@ -97,6 +118,14 @@ pub enum ASTNode {
     },
     Impl {
         ty: String,
+        // `impl Trait for Type`
+        trait_name: Option<String>,
+        methods: Vec<ASTNode>,
+    },
+    // Traits: required method names and default implementations
+    TraitDef {
+        name: String,
+        required: Vec<String>,
         methods: Vec<ASTNode>,
     },
     // Enums
@@ -108,7 +137,11 @@ pub enum ASTNode {
     TypeAnnotation(Type),
     // Modules
     Mod(String),
-    Use(String),
+    // `use "path"` / `import "path" as name`
+    Use {
+        path: String,
+        alias: Option<String>,
+    },
     // Unsafe
     Unsafe(Box<ASTNode>),
     // Inline assembly
@@ -225,6 +258,106 @@ pub enum ASTNode {
     },
     // Throw an error
     Throw(Box<ASTNode>),
+    // Test case: `test "name" { ... }`, `#[test] fn name() { ... }`,
+    // `#[bench] fn name() { ... }` or `#[property] fn name(x: int) { ... }`,
+    // run by `knull test`; only properties have params
+    Test {
+        name: String,
+        kind: TestKind,
+        params: Vec<Param>,
+        body: Box<ASTNode>,
+    },
+    // Source location of the wrapped statement or expression
+    Spanned {
+        span: Span,
+        node: Box<ASTNode>,
+    },
+}
+
+impl ASTNode {
+    /// The node with any `Spanned` wrappers peeled off
+    pub fn inner(&self) -> &ASTNode {
+        let mut node = self;
+        while let ASTNode::Spanned { node: inner, .. } = node {
+            node = inner;
+        }
+        node
+    }
+
+    /// Source location of this node, if the parser recorded one
+    pub fn span(&self) -> Option<&Span> {
+        match self {
+            ASTNode::Spanned { span, .. } => Some(span),
+            _ => None,
+        }
+    }
+
+    /// Direct child nodes, in source order
+    pub fn children(&self) -> Vec<&ASTNode> {
+        use ASTNode::*;
+        match self {
+            Program(items) | Block(items) | Array(items) | Tuple(items) | Syscall(items) => items.iter().collect(),
+            Function { body, .. } | AsyncFunction { body, .. } | Test { body, .. } | Loop(body) | Return(body)
+            | Unsafe(body) | Consume(body) | LinearExpr(body, _) | CompileTimeRun(body) | Await(body)
+            | TryOp(body) | Spread(body) | Spawn(body) | Defer(body) | Throw(body)
+            | Lambda { body, .. } | AsCast { expr: body, .. } | EffectAnnotation { expr: body, .. }
+            | SafeNav { obj: body, .. } | FieldAccess { obj: body, .. } | Unary { operand: body, .. }
+            | Let { value: body, .. } | Const { value: body, .. } | NamedArg { value: body, .. }
+            | Spanned { node: body, .. } => vec![body],
+            Assign { target, value } | AssignOp { target, value, .. } => vec![target, value],
+            Binary { left, right, .. } | Pipeline { left, right } | NullCoalesce { left, right } => vec![left, right],
+            Range { start, end, .. } => vec![start, end],
+            Index { obj, index } => vec![obj, index],
+            While { cond, body } => vec![cond, body],
+            DoWhile { body, cond } => vec![body, cond],
+            For { iter, body, .. } => vec![iter, body],
+            If { cond, then_body, else_body } => {
+                let mut nodes = vec![&**cond, &**then_body];
+                nodes.extend(else_body.as_deref());
+                nodes
+            }
+            IfLet { value, then_body, else_body, .. } => {
+                let mut nodes = vec![&**value, &**then_body];
+                nodes.extend(else_body.as_deref());
+                nodes
+            }
+            Match { expr, arms } => {
+                let mut nodes = vec![&**expr];
+                for arm in arms {
+                    nodes.extend(arm.guard.as_ref());
+                    nodes.push(&arm.body);
+                }
+                nodes
+            }
+            Call { func, args } => std::iter::once(&**func).chain(args).collect(),
+            MethodCall { obj, args, .. } => std::iter::once(&**obj).chain(args).collect(),
+            StructLiteral { fields, .. } => fields.iter().map(|(_, v)| v).collect(),
+            Map(pairs) => pairs.iter().flat_map(|(k, v)| [k, v]).collect(),
+            Impl { methods, .. } | TraitDef { methods, .. } => methods.iter().collect(),
+            ListComp { expr, iter, filter, .. } => {
+                let mut nodes = vec![&**iter, &**expr];
+                nodes.extend(filter.as_deref());
+                nodes
+            }
+            Capability { resource, .. } => resource.as_deref().into_iter().collect(),
+            Yield(value) => value.as_deref().into_iter().collect(),
+            TryCatch { try_body, catch_body, .. } => vec![try_body, catch_body],
+            Mode(_) | Break(_) | Continue(_) | Literal(_) | Identifier(_) | StructDef { .. }
+            | EnumDef { .. } | TypeAnnotation(_) | Mod(_) | Use { .. } | Asm(_)
+            | InterpolatedString(_) | TypeAlias { .. } => Vec::new(),
+        }
+    }
+}
+
+/// What `knull test` does with a test declaration
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TestKind {
+    /// `test "name" { }` or `#[test] fn`: run once, pass unless it errors
+    Test,
+    /// `#[bench] fn`: timed over many iterations under `knull test --bench`
+    Bench,
+    /// `#[property] fn`: called with generated arguments under `knull test --property`
+    Property,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -276,17 +409,31 @@ pub enum Pattern {
         name: String,
         fields: Vec<(String, Pattern)>,
     },
+    /// `Shape::Circle(r)` or bare `Circle(r)` (empty `name`); a tuple payload of
+    /// several values is a `Pattern::Tuple`, a struct payload a `Pattern::Struct`
     Enum {
         name: String,
         variant: String,
         data: Option<Box<Pattern>>,
     },
+    Tuple(Vec<Pattern>),
 }
 
 #[derive(Debug, Clone)]
 pub struct EnumVariant {
     pub name: String,
-    pub data: Option<Type>,
+    pub data: VariantData,
+}
+
+/// Payload carried by an enum variant
+#[derive(Debug, Clone)]
+pub enum VariantData {
+    /// `Empty`
+    Unit,
+    /// `Circle(f64)`, `Rect(f64, f64)`
+    Tuple(Vec<Type>),
+    /// `Point { x: f64, y: f64 }`
+    Struct(Vec<(String, Type)>),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -358,13 +505,78 @@ pub enum Literal {
 pub struct Parser {
     tokens: Vec<Token>,
     pos: usize,
+    file: Option<Arc<str>>,
+    track_spans: bool,
+    error_span: Option<Span>,
+    public: Vec<String>,
 }
 
 impl Parser {
     pub fn new(source: &str) -> Self {
         let mut lexer = Lexer::new(source);
         let tokens = lexer.tokenize();
-        Parser { tokens, pos: 0 }
+        Parser {
+            tokens,
+            pos: 0,
+            file: None,
+            track_spans: true,
+            public: Vec::new(),
+            error_span: None,
+        }
+    }
+
+    /// Record `file` in every span produced by this parser
+    pub fn with_file(mut self, file: &str) -> Self {
+        self.file = Some(Arc::from(file));
+        self
+    }
+
+    /// Produce a bare AST without `Spanned` wrappers (used by the codegen backends)
+    pub fn without_spans(mut self) -> Self {
+        self.track_spans = false;
+        self
+    }
+
+    /// Location of the token the last failed `parse` stopped at
+    pub fn error_span(&self) -> Option<&Span> {
+        self.error_span.as_ref()
+    }
+
+    /// Names of the top-level items declared `pub` by the last `parse`
+    pub fn public_names(&self) -> &[String] {
+        &self.public
+    }
+
+    fn span_from(&self, start: usize) -> Span {
+        self.span_between(start, self.pos)
+    }
+
+    /// Span covering tokens `start..end` (at least the start token)
+    fn span_between(&self, start: usize, end: usize) -> Span {
+        let first = &self.tokens[start];
+        let last = &self.tokens[end.saturating_sub(1).max(start)];
+        Span {
+            file: self.file.clone(),
+            line: first.line,
+            col: first.col,
+            end_line: last.end_line,
+            end_col: last.end_col,
+        }
+    }
+
+    /// Wrap `node` with the span running from token `start` to the last consumed token
+    fn spanned(&self, start: usize, node: ASTNode) -> ASTNode {
+        self.spanned_to(start, self.pos, node)
+    }
+
+    fn spanned_to(&self, start: usize, end: usize, node: ASTNode) -> ASTNode {
+        if !self.track_spans || matches!(node, ASTNode::Spanned { .. }) {
+            return node;
+        }
+        ASTNode::Spanned {
+            span: self.span_between(start, end),
+            node: Box::new(node),
+        }
     }
 
     fn current(&self) -> &Token {
@@ -378,12 +590,23 @@ impl Parser {
     }
 
     pub fn parse(&mut self) -> Result<ASTNode, String> {
+        let result = self.parse_program();
+        if result.is_err() {
+            self.error_span = Some(self.span_from(self.pos));
+        }
+        result
+    }
+
+    fn parse_program(&mut self) -> Result<ASTNode, String> {
         let mut items = Vec::new();
         while self.current().kind != TokenKind::Eof {
             self.skip_semis();
             if self.current().kind == TokenKind::Eof {
                 break;
             }
+            let start = self.pos;
+            let count = items.len();
+            let is_pub = start > 0 && self.tokens[start - 1].kind == TokenKind::Pub;
             match self.current().kind {
                 TokenKind::Mode => items.push(self.parse_mode()?),
                 TokenKind::Async => items.push(self.parse_async_function()?),
@@ -420,11 +643,7 @@ impl Parser {
                     let alias = self.parse_type_alias()?;
                     items.push(alias);
                 }
-                TokenKind::Defer => {
-                    self.advance();
-                    let expr = self.parse_expression()?;
-                    items.push(ASTNode::Defer(Box::new(expr)));
-                }
+                TokenKind::Defer => items.push(self.parse_defer()?),
                 TokenKind::Throw => {
                     self.advance();
                     let expr = self.parse_expression()?;
@@ -445,15 +664,40 @@ impl Parser {
                 TokenKind::Linear => items.push(self.parse_linear_expr()?),
                 TokenKind::Effect => items.push(self.parse_effect_annotation()?),
                 TokenKind::HashRun => items.push(self.parse_run_block()?),
+                TokenKind::Pound => items.push(self.parse_attributed_item()?),
+                TokenKind::Identifier if self.at_test_block() => items.push(self.parse_test_block()?),
                 _ => {
                     let expr = self.parse_expression()?;
                     items.push(expr);
                 }
             }
+            if items.len() > count {
+                let item = items.pop().unwrap();
+                if is_pub {
+                    if let Some(name) = Self::item_name(&item) {
+                        self.public.push(name.to_string());
+                    }
+                }
+                items.push(self.spanned(start, item));
+            }
         }
         Ok(ASTNode::Program(items))
     }
 
+    /// Name a top-level item binds, for `pub` bookkeeping
+    fn item_name(item: &ASTNode) -> Option<&str> {
+        match item.inner() {
+            ASTNode::Function { name, .. }
+            | ASTNode::AsyncFunction { name, .. }
+            | ASTNode::StructDef { name, .. }
+            | ASTNode::EnumDef { name, .. }
+            | ASTNode::TraitDef { name, .. }
+            | ASTNode::Let { name, .. }
+            | ASTNode::Const { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+
     fn skip_semis(&mut self) {
         while self.current().kind == TokenKind::Semicolon {
             self.advance();
@@ -461,8 +705,23 @@ impl Parser {
     }
 
     fn parse_function(&mut self) -> Result<ASTNode, String> {
+        let (name, params, ret_type) = self.parse_function_sig()?;
+
+        // Parse body
+        let body = self.parse_block()?;
+        Ok(ASTNode::Function {
+            name,
+            params,
+            ret_type,
+            body: Box::new(body),
+        })
+    }
+
+    /// `fn name(params) -> ret` up to the body
+    fn parse_function_sig(&mut self) -> Result<(String, Vec<Param>, Option<Type>), String> {
         self.expect(TokenKind::Fn)?;
         let name = self.parse_identifier()?;
+        self.skip_type_args();
 
         // Parse params
         let mut params = Vec::new();
@@ -470,6 +729,13 @@ impl Parser {
             self.advance();
             while self.current().kind != TokenKind::RParen && self.current().kind != TokenKind::Eof
             {
+                // `&self` / `&mut self` / `mut self` all bind plain `self`
+                if self.current().kind == TokenKind::Ampersand {
+                    self.advance();
+                }
+                if self.current().kind == TokenKind::Mut {
+                    self.advance();
+                }
                 // Parse parameter name (can be identifier or 'self')
                 let param_name = if self.current().kind == TokenKind::SelfValue {
                     self.advance();
@@ -507,15 +773,7 @@ impl Parser {
         } else {
             None
         };
-
-        // Parse body
-        let body = self.parse_block()?;
-        Ok(ASTNode::Function {
-            name,
-            params,
-            ret_type,
-            body: Box::new(body),
-        })
+        Ok((name, params, ret_type))
     }
 
     fn parse_let(&mut self) -> Result<ASTNode, String> {
@@ -671,6 +929,12 @@ impl Parser {
     }
 
     fn parse_statement(&mut self) -> Result<ASTNode, String> {
+        let start = self.pos;
+        let stmt = self.parse_statement_inner()?;
+        Ok(self.spanned(start, stmt))
+    }
+
+    fn parse_statement_inner(&mut self) -> Result<ASTNode, String> {
         match self.current().kind {
             TokenKind::Let => self.parse_let(),
             TokenKind::Const => {
@@ -722,6 +986,7 @@ impl Parser {
                 Ok(ASTNode::Spawn(Box::new(body)))
             }
             TokenKind::Try => self.parse_try_catch(),
+            TokenKind::Defer => self.parse_defer(),
             TokenKind::Throw => {
                 self.advance();
                 let expr = self.parse_expression()?;
@@ -816,6 +1081,7 @@ impl Parser {
     }
 
     fn parse_assignment(&mut self) -> Result<ASTNode, String> {
+        let start = self.pos;
         let left = self.parse_null_coalesce()?;
         // Compound assignment operators
         let compound_op: Option<&str> = match self.current().kind {
@@ -836,73 +1102,77 @@ impl Parser {
             self.advance();
             let right = self.parse_assignment()?;
             if self.current().kind == TokenKind::Semicolon { self.advance(); }
-            return Ok(ASTNode::AssignOp {
+            return Ok(self.spanned(start, ASTNode::AssignOp {
                 target: Box::new(left),
                 op,
                 value: Box::new(right),
-            });
+            }));
         }
         if self.current().kind == TokenKind::Eq {
             self.advance();
             let right = self.parse_assignment()?;
             if self.current().kind == TokenKind::Semicolon { self.advance(); }
-            return Ok(ASTNode::Assign {
+            return Ok(self.spanned(start, ASTNode::Assign {
                 target: Box::new(left),
                 value: Box::new(right),
-            });
+            }));
         }
         Ok(left)
     }
 
     fn parse_null_coalesce(&mut self) -> Result<ASTNode, String> {
+        let start = self.pos;
         let mut left = self.parse_or()?;
         while self.current().kind == TokenKind::NullCoalesce {
             self.advance();
             let right = self.parse_or()?;
-            left = ASTNode::NullCoalesce {
+            left = self.spanned(start, ASTNode::NullCoalesce {
                 left: Box::new(left),
                 right: Box::new(right),
-            };
+            });
         }
         Ok(left)
     }
 
     fn parse_or(&mut self) -> Result<ASTNode, String> {
+        let start = self.pos;
         let mut left = self.parse_and()?;
         while self.current().kind == TokenKind::Or {
             self.advance();
             let right = self.parse_and()?;
-            left = ASTNode::Binary {
+            left = self.spanned(start, ASTNode::Binary {
                 op: "||".to_string(),
                 left: Box::new(left),
                 right: Box::new(right),
-            };
+            });
         }
         Ok(left)
     }
 
     fn parse_and(&mut self) -> Result<ASTNode, String> {
+        let start = self.pos;
         let mut left = self.parse_bitwise()?;
         while self.current().kind == TokenKind::And {
             self.advance();
             let right = self.parse_bitwise()?;
-            left = ASTNode::Binary {
+            left = self.spanned(start, ASTNode::Binary {
                 op: "&&".to_string(),
                 left: Box::new(left),
                 right: Box::new(right),
-            };
+            });
         }
         Ok(left)
     }
 
     fn parse_bitwise(&mut self) -> Result<ASTNode, String> {
+        let start = self.pos;
         let mut left = self.parse_equality()?;
         loop {
             // Pipeline operator: left |> right -> right(left)
             if self.current().kind == TokenKind::Pipeline {
                 self.advance();
                 let right = self.parse_equality()?;
-                left = ASTNode::Pipeline { left: Box::new(left), right: Box::new(right) };
+                left = self.spanned(start, ASTNode::Pipeline { left: Box::new(left), right: Box::new(right) });
                 continue;
             }
             let op = match self.current().kind {
@@ -915,27 +1185,29 @@ impl Parser {
             }.to_string();
             self.advance();
             let right = self.parse_equality()?;
-            left = ASTNode::Binary { op, left: Box::new(left), right: Box::new(right) };
+            left = self.spanned(start, ASTNode::Binary { op, left: Box::new(left), right: Box::new(right) });
         }
         Ok(left)
     }
 
     fn parse_equality(&mut self) -> Result<ASTNode, String> {
+        let start = self.pos;
         let mut left = self.parse_comparison()?;
         while let TokenKind::EqEq | TokenKind::Neq = self.current().kind {
             let op = self.current().value.clone();
             self.advance();
             let right = self.parse_comparison()?;
-            left = ASTNode::Binary {
+            left = self.spanned(start, ASTNode::Binary {
                 op,
                 left: Box::new(left),
                 right: Box::new(right),
-            };
+            });
         }
         Ok(left)
     }
 
     fn parse_comparison(&mut self) -> Result<ASTNode, String> {
+        let start = self.pos;
         let mut left = self.parse_additive()?;
         while let TokenKind::Lt | TokenKind::Gt | TokenKind::Lte | TokenKind::Gte =
             self.current().kind
@@ -943,46 +1215,54 @@ impl Parser {
             let op = self.current().value.clone();
             self.advance();
             let right = self.parse_additive()?;
-            left = ASTNode::Binary {
+            left = self.spanned(start, ASTNode::Binary {
                 op,
                 left: Box::new(left),
                 right: Box::new(right),
-            };
+            });
         }
         Ok(left)
     }
 
     fn parse_additive(&mut self) -> Result<ASTNode, String> {
+        let start = self.pos;
         let mut left = self.parse_multiplicative()?;
         while let TokenKind::Plus | TokenKind::Minus = self.current().kind {
             let op = self.current().value.clone();
             self.advance();
             let right = self.parse_multiplicative()?;
-            left = ASTNode::Binary {
+            left = self.spanned(start, ASTNode::Binary {
                 op,
                 left: Box::new(left),
                 right: Box::new(right),
-            };
+            });
         }
         Ok(left)
     }
 
     fn parse_multiplicative(&mut self) -> Result<ASTNode, String> {
+        let start = self.pos;
         let mut left = self.parse_unary()?;
         while let TokenKind::Star | TokenKind::Slash | TokenKind::Percent = self.current().kind {
             let op = self.current().value.clone();
             self.advance();
             let right = self.parse_unary()?;
-            left = ASTNode::Binary {
+            left = self.spanned(start, ASTNode::Binary {
                 op,
                 left: Box::new(left),
                 right: Box::new(right),
-            };
+            });
         }
         Ok(left)
     }
 
     fn parse_unary(&mut self) -> Result<ASTNode, String> {
+        let start = self.pos;
+        let node = self.parse_unary_inner()?;
+        Ok(self.spanned(start, node))
+    }
+
+    fn parse_unary_inner(&mut self) -> Result<ASTNode, String> {
         if let TokenKind::Bang | TokenKind::Minus = self.current().kind {
             let op = self.current().value.clone();
             self.advance();
@@ -1014,9 +1294,12 @@ impl Parser {
     }
 
     fn parse_postfix(&mut self) -> Result<ASTNode, String> {
+        let start = self.pos;
         let mut node = self.parse_primary()?;
 
         loop {
+            // Everything consumed so far belongs to the operand of this postfix step
+            let end = self.pos;
             if self.current().kind == TokenKind::LParen {
                 self.advance();
                 let mut args = Vec::new();
@@ -1047,7 +1330,7 @@ impl Parser {
                         };
                     } else {
                         node = ASTNode::Call {
-                            func: Box::new(node),
+                            func: Box::new(self.spanned_to(start, end, node)),
                             args,
                         };
                     }
@@ -1057,7 +1340,7 @@ impl Parser {
                 let index = self.parse_expression()?;
                 self.expect(TokenKind::RBracket)?;
                 node = ASTNode::Index {
-                    obj: Box::new(node),
+                    obj: Box::new(self.spanned_to(start, end, node)),
                     index: Box::new(index),
                 };
             } else if self.current().kind == TokenKind::SafeNav {
@@ -1074,18 +1357,28 @@ impl Parser {
                     }
                     if self.current().kind == TokenKind::RParen { self.advance(); }
                     node = ASTNode::MethodCall {
-                        obj: Box::new(ASTNode::SafeNav { obj: Box::new(node), field }),
+                        obj: Box::new(ASTNode::SafeNav { obj: Box::new(self.spanned_to(start, end, node)), field }),
                         method: "__safe_call__".to_string(),
                         args,
                     };
                 } else {
-                    node = ASTNode::SafeNav { obj: Box::new(node), field };
+                    node = ASTNode::SafeNav { obj: Box::new(self.spanned_to(start, end, node)), field };
+                }
+            } else if self.current().kind == TokenKind::DoubleColon
+                && matches!(node, ASTNode::Identifier(_))
+            {
+                // `Shape::Circle`, `Point::new` — a single path identifier
+                self.advance();
+                let member = self.parse_identifier()?;
+                if let ASTNode::Identifier(path) = &mut node {
+                    path.push_str("::");
+                    path.push_str(&member);
                 }
             } else if self.current().kind == TokenKind::Dot {
                 self.advance();
                 let field = self.parse_identifier()?;
                 node = ASTNode::FieldAccess {
-                    obj: Box::new(node),
+                    obj: Box::new(self.spanned_to(start, end, node)),
                     field,
                 };
             } else if self.current().kind == TokenKind::LBrace {
@@ -1135,18 +1428,18 @@ impl Parser {
                 self.advance();
                 let target_type = self.parse_type()?;
                 node = ASTNode::AsCast {
-                    expr: Box::new(node),
+                    expr: Box::new(self.spanned_to(start, end, node)),
                     target_type,
                 };
             } else if self.current().kind == TokenKind::Question {
                 // Try operator: expr?
                 self.advance();
-                node = ASTNode::TryOp(Box::new(node));
+                node = ASTNode::TryOp(Box::new(self.spanned_to(start, end, node)));
             } else {
                 break;
             }
         }
-        Ok(node)
+        Ok(self.spanned(start, node))
     }
 
     fn parse_primary(&mut self) -> Result<ASTNode, String> {
@@ -1518,8 +1811,9 @@ impl Parser {
         if self.current().kind == TokenKind::String {
             let path = self.current().value.clone();
             self.advance();
+            let alias = self.parse_use_alias()?;
             if self.current().kind == TokenKind::Semicolon { self.advance(); }
-            return Ok(ASTNode::Use(path));
+            return Ok(ASTNode::Use { path, alias });
         }
         // Otherwise accumulate dotted/module path tokens until ; or EOF or {
         let mut path = String::new();
@@ -1527,6 +1821,8 @@ impl Parser {
             && self.current().kind != TokenKind::Eof
             && self.current().kind != TokenKind::LBrace
             && self.current().kind != TokenKind::String  // stop at any string
+            && self.current().kind != TokenKind::As
+            && self.current().line == self.tokens[self.pos - 1].line
         {
             path.push_str(&self.current().value);
             self.advance();
@@ -1535,10 +1831,33 @@ impl Parser {
                 self.advance();
             }
         }
+        let alias = self.parse_use_alias()?;
         if self.current().kind == TokenKind::Semicolon {
             self.advance();
         }
-        Ok(ASTNode::Use(path))
+        Ok(ASTNode::Use { path, alias })
+    }
+
+    /// Optional `as name` after an import path
+    fn parse_use_alias(&mut self) -> Result<Option<String>, String> {
+        if self.current().kind == TokenKind::As {
+            self.advance();
+            Ok(Some(self.parse_identifier()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// `defer expr` or `defer { ... }`
+    fn parse_defer(&mut self) -> Result<ASTNode, String> {
+        self.expect(TokenKind::Defer)?;
+        let body = if self.current().kind == TokenKind::LBrace {
+            self.parse_block()?
+        } else {
+            self.parse_expression()?
+        };
+        if self.current().kind == TokenKind::Semicolon { self.advance(); }
+        Ok(ASTNode::Defer(Box::new(body)))
     }
 
     fn parse_try_catch(&mut self) -> Result<ASTNode, String> {
@@ -1574,7 +1893,15 @@ impl Parser {
     // Parse mode declaration
     fn parse_mode(&mut self) -> Result<ASTNode, String> {
         self.expect(TokenKind::Mode)?;
-        let mode_name = self.parse_identifier()?;
+        // The mode names are keywords
+        let mode_name = match self.current().kind {
+            TokenKind::Novice | TokenKind::Expert | TokenKind::God => {
+                let name = self.current().value.clone();
+                self.advance();
+                name
+            }
+            _ => self.parse_identifier()?,
+        };
         Ok(ASTNode::Mode(mode_name))
     }
 
@@ -1672,9 +1999,63 @@ impl Parser {
                     Err("Expected number after `-` in pattern".to_string())
                 }
             }
+            TokenKind::LParen => {
+                self.advance();
+                let items = self.parse_pattern_list(TokenKind::RParen)?;
+                Ok(Pattern::Tuple(items))
+            }
             TokenKind::Identifier => {
-                let name = self.parse_identifier()?;
-                Ok(Pattern::Identifier(name))
+                let mut name = self.parse_identifier()?;
+                // `Enum::Variant` — otherwise a bare name
+                let mut enum_name = None;
+                if self.current().kind == TokenKind::DoubleColon {
+                    self.advance();
+                    enum_name = Some(name);
+                    name = self.parse_identifier()?;
+                }
+                let data = match self.current().kind {
+                    TokenKind::LParen => {
+                        self.advance();
+                        let mut items = self.parse_pattern_list(TokenKind::RParen)?;
+                        Some(if items.len() == 1 { items.remove(0) } else { Pattern::Tuple(items) })
+                    }
+                    TokenKind::LBrace => {
+                        self.advance();
+                        let mut fields = Vec::new();
+                        while self.current().kind != TokenKind::RBrace
+                            && self.current().kind != TokenKind::Eof
+                        {
+                            if self.current().kind == TokenKind::DotDot {
+                                // `..` ignores the remaining fields
+                                self.advance();
+                                continue;
+                            }
+                            let field = self.parse_identifier()?;
+                            let pat = if self.current().kind == TokenKind::Colon {
+                                self.advance();
+                                self.parse_pattern()?
+                            } else {
+                                Pattern::Identifier(field.clone())
+                            };
+                            fields.push((field, pat));
+                            if self.current().kind == TokenKind::Comma {
+                                self.advance();
+                            }
+                        }
+                        self.expect(TokenKind::RBrace)?;
+                        Some(Pattern::Struct { name: name.clone(), fields })
+                    }
+                    _ => None,
+                };
+                Ok(match (enum_name, data) {
+                    (None, None) => Pattern::Identifier(name),
+                    (None, Some(st @ Pattern::Struct { .. })) => st,
+                    (enum_name, data) => Pattern::Enum {
+                        name: enum_name.unwrap_or_default(),
+                        variant: name,
+                        data: data.map(Box::new),
+                    },
+                })
             }
             _ => {
                 // Fallback: treat as wildcard to not break match
@@ -1684,6 +2065,19 @@ impl Parser {
         }
     }
 
+    /// Comma-separated patterns up to and including `close`
+    fn parse_pattern_list(&mut self, close: TokenKind) -> Result<Vec<Pattern>, String> {
+        let mut items = Vec::new();
+        while self.current().kind != close && self.current().kind != TokenKind::Eof {
+            items.push(self.parse_pattern()?);
+            if self.current().kind == TokenKind::Comma {
+                self.advance();
+            }
+        }
+        self.expect(close)?;
+        Ok(items)
+    }
+
     // Parse loop expression
     fn parse_loop(&mut self) -> Result<ASTNode, String> {
         self.expect(TokenKind::Loop)?;
@@ -1724,6 +2118,7 @@ impl Parser {
     fn parse_enum_def(&mut self) -> Result<ASTNode, String> {
         self.expect(TokenKind::Enum)?;
         let name = self.parse_identifier()?;
+        self.skip_type_args();
 
         let mut variants = Vec::new();
         if self.current().kind == TokenKind::LBrace {
@@ -1731,14 +2126,52 @@ impl Parser {
             while self.current().kind != TokenKind::RBrace && self.current().kind != TokenKind::Eof
             {
                 let variant_name = self.parse_identifier()?;
-                let data = if self.current().kind == TokenKind::LParen {
-                    self.advance();
-                    let ty = self.parse_type()?;
-                    self.expect(TokenKind::RParen)?;
-                    Some(ty)
-                } else {
-                    None
+                let data = match self.current().kind {
+                    TokenKind::LParen => {
+                        self.advance();
+                        let mut types = Vec::new();
+                        while self.current().kind != TokenKind::RParen
+                            && self.current().kind != TokenKind::Eof
+                        {
+                            types.push(self.parse_type()?);
+                            self.skip_type_args();
+                            if self.current().kind == TokenKind::Comma {
+                                self.advance();
+                            }
+                        }
+                        self.expect(TokenKind::RParen)?;
+                        VariantData::Tuple(types)
+                    }
+                    TokenKind::LBrace => {
+                        self.advance();
+                        let mut fields = Vec::new();
+                        while self.current().kind != TokenKind::RBrace
+                            && self.current().kind != TokenKind::Eof
+                        {
+                            let field_name = self.parse_identifier()?;
+                            let ty = if self.current().kind == TokenKind::Colon {
+                                self.advance();
+                                let ty = self.parse_type()?;
+                                self.skip_type_args();
+                                ty
+                            } else {
+                                Type::Custom("any".to_string())
+                            };
+                            fields.push((field_name, ty));
+                            if self.current().kind == TokenKind::Comma {
+                                self.advance();
+                            }
+                        }
+                        self.expect(TokenKind::RBrace)?;
+                        VariantData::Struct(fields)
+                    }
+                    _ => VariantData::Unit,
                 };
+                // C-style discriminants (`Text = 0x1`) have no runtime meaning
+                if self.current().kind == TokenKind::Eq {
+                    self.advance();
+                    self.parse_expression()?;
+                }
                 variants.push(EnumVariant {
                     name: variant_name,
                     data,
@@ -1753,10 +2186,39 @@ impl Parser {
         Ok(ASTNode::EnumDef { name, variants })
     }
 
-    // Parse impl block
+    /// Skip a generic argument list such as `<T, E>` or `<Vec<u8>>`
+    fn skip_type_args(&mut self) {
+        if self.current().kind != TokenKind::Lt {
+            return;
+        }
+        let mut depth = 0;
+        while self.current().kind != TokenKind::Eof {
+            match self.current().kind {
+                TokenKind::Lt => depth += 1,
+                TokenKind::Gt => depth -= 1,
+                TokenKind::Shr => depth -= 2,
+                _ => {}
+            }
+            self.advance();
+            if depth <= 0 {
+                break;
+            }
+        }
+    }
+
+    // Parse impl block: `impl Type { ... }` or `impl Trait for Type { ... }`
     fn parse_impl(&mut self) -> Result<ASTNode, String> {
         self.expect(TokenKind::Impl)?;
-        let ty = self.parse_identifier()?;
+        self.skip_type_args();
+        let mut ty = self.parse_identifier()?;
+        self.skip_type_args();
+        let mut trait_name = None;
+        if self.current().kind == TokenKind::For {
+            self.advance();
+            trait_name = Some(ty);
+            ty = self.parse_identifier()?;
+            self.skip_type_args();
+        }
 
         let mut methods = Vec::new();
         if self.current().kind == TokenKind::LBrace {
@@ -1771,23 +2233,42 @@ impl Parser {
             }
             self.expect(TokenKind::RBrace)?;
         }
-        Ok(ASTNode::Impl { ty, methods })
+        Ok(ASTNode::Impl { ty, trait_name, methods })
     }
 
-    // Parse trait definition
+    // Parse trait definition: bodiless `fn`s are required, the rest are defaults
     fn parse_trait(&mut self) -> Result<ASTNode, String> {
         self.expect(TokenKind::Trait)?;
         let name = self.parse_identifier()?;
-        // Skip trait body for now
+        self.skip_type_args();
+
+        let mut required = Vec::new();
+        let mut methods = Vec::new();
         if self.current().kind == TokenKind::LBrace {
             self.advance();
             while self.current().kind != TokenKind::RBrace && self.current().kind != TokenKind::Eof
             {
-                self.advance();
+                if self.current().kind != TokenKind::Fn {
+                    self.advance();
+                    continue;
+                }
+                let (method, params, ret_type) = self.parse_function_sig()?;
+                if self.current().kind == TokenKind::LBrace {
+                    let body = self.parse_block()?;
+                    methods.push(ASTNode::Function {
+                        name: method,
+                        params,
+                        ret_type,
+                        body: Box::new(body),
+                    });
+                } else {
+                    self.skip_semis();
+                    required.push(method);
+                }
             }
             self.expect(TokenKind::RBrace)?;
         }
-        Ok(ASTNode::Identifier(name))
+        Ok(ASTNode::TraitDef { name, required, methods })
     }
 
     // Parse module declaration
@@ -1922,6 +2403,49 @@ impl Parser {
         Ok(ASTNode::CompileTimeRun(Box::new(block)))
     }
 
+    /// `test "name" {` at the current position
+    fn at_test_block(&self) -> bool {
+        self.current().value == "test"
+            && self.peek_next().kind == TokenKind::String
+            && self.tokens.get(self.pos + 2).is_some_and(|t| t.kind == TokenKind::LBrace)
+    }
+
+    // Parse a test block: `test "name" { ... }`
+    fn parse_test_block(&mut self) -> Result<ASTNode, String> {
+        self.advance(); // skip 'test'
+        let name = self.current().value.clone();
+        self.advance();
+        let body = self.parse_block()?;
+        Ok(ASTNode::Test { name, kind: TestKind::Test, params: Vec::new(), body: Box::new(body) })
+    }
+
+    // Parse an item with an attribute: `#[test]`, `#[bench]` or `#[property]` before a `fn`
+    fn parse_attributed_item(&mut self) -> Result<ASTNode, String> {
+        self.expect(TokenKind::Pound)?;
+        self.expect(TokenKind::LBracket)?;
+        let attr = self.parse_identifier()?;
+        self.expect(TokenKind::RBracket)?;
+        let kind = match attr.as_str() {
+            "test" => TestKind::Test,
+            "bench" => TestKind::Bench,
+            "property" => TestKind::Property,
+            _ => return Err(format!("Unknown attribute #[{}]", attr)),
+        };
+        match self.parse_function()? {
+            ASTNode::Function { name, params, body, .. } => {
+                if kind == TestKind::Property {
+                    if let Some(param) = params.iter().find(|p| p.ty.is_none()) {
+                        return Err(format!("Property '{}' needs a type for parameter '{}' to generate values", name, param.name));
+                    }
+                } else if !params.is_empty() {
+                    return Err(format!("#[{}] function '{}' cannot take parameters", attr, name));
+                }
+                Ok(ASTNode::Test { name, kind, params, body })
+            }
+            _ => unreachable!(),
+        }
+    }
+
     // Parse class definition: `class Foo { fields; methods }`
     // Desugars to: struct Foo { fields } + impl Foo { methods }
     fn parse_class(&mut self) -> Result<ASTNode, String> {
@@ -1964,7 +2488,7 @@ impl Parser {
 
         // Emit: StructDef + Impl as a Block
         let struct_node = ASTNode::StructDef { name: name.clone(), fields };
-        let impl_node = ASTNode::Impl { ty: name, methods };
+        let impl_node = ASTNode::Impl { ty: name, trait_name: None, methods };
         Ok(ASTNode::Block(vec![struct_node, impl_node]))
     }
 
@@ -2021,3 +2545,74 @@ pub fn parse(source: &str) -> Result<ASTNode, String> {
     parser.parse()
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn first_item(source: &str) -> ASTNode {
+        match Parser::new(source).with_file("t.knull").parse().unwrap() {
+            ASTNode::Program(mut items) => items.remove(0),
+            other => panic!("expected program, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_statement_span() {
+        let item = first_item("let x = 1\nlet y = x.foo()");
+        let span = item.span().unwrap();
+        assert_eq!(span.file.as_deref(), Some("t.knull"));
+        assert_eq!((span.line, span.col), (1, 1));
+        assert_eq!((span.end_line, span.end_col), (1, 10));
+    }
+
+    #[test]
+    fn test_method_call_span() {
+        let src = "let y = x.foo(1)";
+        let item = first_item(src);
+        let value = match item.inner() {
+            ASTNode::Let { value, .. } => value,
+            other => panic!("expected let, got {:?}", other),
+        };
+        let span = value.span().unwrap();
+        assert!(matches!(value.inner(), ASTNode::MethodCall { .. }));
+        assert_eq!((span.line, span.col, span.end_col), (1, 9, 17));
+        match value.inner() {
+            ASTNode::MethodCall { obj, .. } => {
+                let obj_span = obj.span().unwrap();
+                assert_eq!((obj_span.col, obj_span.end_col), (9, 10));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_call_keeps_bare_callee() {
+        let item = first_item("print_it(1 + 2)");
+        match item.inner() {
+            ASTNode::Call { func, args } => {
+                assert!(matches!(func.as_ref(), ASTNode::Identifier(n) if n == "print_it"));
+                let arg_span = args[0].span().unwrap();
+                assert_eq!((arg_span.col, arg_span.end_col), (10, 15));
+            }
+            other => panic!("expected call, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_without_spans() {
+        let ast = Parser::new("let x = 1 + 2").without_spans().parse().unwrap();
+        match ast {
+            ASTNode::Program(items) => assert!(items[0].span().is_none()),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_error_span() {
+        let mut parser = Parser::new("let x = 1\nlet = 2");
+        assert!(parser.parse().is_err());
+        let span = parser.error_span().unwrap();
+        assert_eq!((span.line, span.col), (2, 5));
+    }
+}
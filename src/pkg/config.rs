@@ -0,0 +1,124 @@
+//! User Configuration
+//! Settings shared by every project, read from ~/.knull/config.toml
+
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+
+pub const DEFAULT_REGISTRY: &str = "https://registry.knull-lang.dev";
+const CONFIG_PATH: &str = ".knull/config.toml";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    pub registry: RegistryConfig,
+    #[serde(default)]
+    pub net: NetConfig,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RegistryConfig {
+    /// Replaces the default registry
+    pub url: Option<String>,
+    /// Tried in order before the registry when downloading or listing versions
+    #[serde(default)]
+    pub mirrors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetConfig {
+    /// Act as if every command was given `--offline`
+    #[serde(default)]
+    pub offline: bool,
+}
+
+impl Config {
+    /// `$KNULL_CONFIG`, else ~/.knull/config.toml
+    pub fn path() -> Option<PathBuf> {
+        match std::env::var_os("KNULL_CONFIG") {
+            Some(path) => Some(PathBuf::from(path)),
+            None => dirs::home_dir().map(|home| home.join(CONFIG_PATH)),
+        }
+    }
+
+    /// The user's configuration; the defaults if there is no config file
+    pub fn load() -> Result<Self, String> {
+        let path = match Self::path() {
+            Some(path) if path.exists() => path,
+            _ => return Ok(Config::default()),
+        };
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(&content).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn parse(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|e| format!("Failed to parse config: {}", e))
+    }
+
+    /// Where packages are published and searched: `$KNULL_REGISTRY`, else `registry.url`,
+    /// else the default registry
+    pub fn registry(&self) -> String {
+        std::env::var("KNULL_REGISTRY")
+            .ok()
+            .or_else(|| self.registry.url.clone())
+            .unwrap_or_else(|| DEFAULT_REGISTRY.to_string())
+            .trim_end_matches('/')
+            .to_string()
+    }
+
+    /// Where packages are downloaded from, in the order to try them
+    pub fn registries(&self) -> Vec<String> {
+        let mut registries: Vec<String> = self
+            .registry
+            .mirrors
+            .iter()
+            .map(|m| m.trim_end_matches('/').to_string())
+            .collect();
+        registries.push(self.registry());
+        registries.dedup();
+        registries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = Config::parse(
+            r#"
+[registry]
+url = "https://packages.example.com/"
+mirrors = ["http://mirror.internal/knull", "http://backup.internal/knull/"]
+
+[net]
+offline = true
+"#,
+        )
+        .unwrap();
+        assert!(config.net.offline);
+        if std::env::var_os("KNULL_REGISTRY").is_none() {
+            assert_eq!(config.registry(), "https://packages.example.com");
+            assert_eq!(
+                config.registries(),
+                vec![
+                    "http://mirror.internal/knull",
+                    "http://backup.internal/knull",
+                    "https://packages.example.com",
+                ]
+            );
+        }
+
+        let defaults = Config::parse("").unwrap();
+        assert!(!defaults.net.offline);
+        assert!(defaults.registry.mirrors.is_empty());
+
+        let err = Config::parse("[registry]\nmirror = \"x\"").unwrap_err();
+        assert!(err.contains("unknown field `mirror`"), "{}", err);
+    }
+}
@@ -0,0 +1,304 @@
+//! Git Dependencies
+//! Clones git dependencies into the cache and checks out the commit they are locked to
+
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Which commit of a git dependency to use
+#[derive(Debug, Clone, PartialEq)]
+pub enum GitReference {
+    Branch(String),
+    Tag(String),
+    Rev(String),
+    DefaultBranch,
+}
+
+impl GitReference {
+    /// How the reference appears in a lockfile source
+    fn query(&self) -> String {
+        match self {
+            GitReference::Branch(b) => format!("?branch={}", b),
+            GitReference::Tag(t) => format!("?tag={}", t),
+            GitReference::Rev(r) => format!("?rev={}", r),
+            GitReference::DefaultBranch => String::new(),
+        }
+    }
+
+    /// The revision `git rev-parse` resolves in the bare clone
+    fn revision(&self) -> String {
+        match self {
+            GitReference::Branch(b) => format!("refs/heads/{}", b),
+            GitReference::Tag(t) => format!("refs/tags/{}", t),
+            GitReference::Rev(r) => r.clone(),
+            GitReference::DefaultBranch => "HEAD".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for GitReference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GitReference::Branch(b) => write!(f, "branch {}", b),
+            GitReference::Tag(t) => write!(f, "tag {}", t),
+            GitReference::Rev(r) => write!(f, "rev {}", r),
+            GitReference::DefaultBranch => write!(f, "the default branch"),
+        }
+    }
+}
+
+/// Identifies `url` at `reference`, whatever commit that is
+pub fn location(url: &str, reference: &GitReference) -> String {
+    format!("git+{}{}", url, reference.query())
+}
+
+/// Lockfile source of `url` at `reference`, checked out at `commit`
+pub fn source(url: &str, reference: &GitReference, commit: &str) -> String {
+    format!("{}#{}", location(url, reference), commit)
+}
+
+/// The commit a lockfile source pins, if it was locked for the same `url` and `reference`
+pub fn locked_commit<'a>(source: &'a str, url: &str, reference: &GitReference) -> Option<&'a str> {
+    let (prefix, commit) = source.rsplit_once('#')?;
+    (prefix == location(url, reference)).then_some(commit)
+}
+
+/// The url, reference and commit of a lockfile source made by `source`
+pub fn parse_source(source: &str) -> Option<(String, GitReference, String)> {
+    let (location, commit) = source.strip_prefix("git+")?.rsplit_once('#')?;
+    let (url, reference) = location
+        .rsplit_once('?')
+        .and_then(|(url, query)| {
+            let (kind, value) = query.split_once('=')?;
+            let reference = match kind {
+                "branch" => GitReference::Branch(value.to_string()),
+                "tag" => GitReference::Tag(value.to_string()),
+                "rev" => GitReference::Rev(value.to_string()),
+                _ => return None,
+            };
+            Some((url, reference))
+        })
+        .unwrap_or((location, GitReference::DefaultBranch));
+    Some((url.to_string(), reference, commit.to_string()))
+}
+
+/// Where `checkout` puts `commit` of the dependency `name`
+pub fn checkout_dir(cache_dir: &Path, name: &str, commit: &str) -> PathBuf {
+    cache_dir.join("git").join("checkouts").join(format!(
+        "{}-{}",
+        name,
+        &commit[..12.min(commit.len())]
+    ))
+}
+
+/// Check out `url` under `cache_dir`, at `locked` if given and at `reference` otherwise.
+/// Returns the checkout and the full hash of its commit.
+pub fn checkout(
+    cache_dir: &Path,
+    name: &str,
+    url: &str,
+    reference: &GitReference,
+    locked: Option<&str>,
+) -> Result<(PathBuf, String), String> {
+    // git would take a leading `-` as an option
+    if url.starts_with('-') {
+        return Err(format!("Invalid git URL for {}: {}", name, url));
+    }
+    let git_dir = cache_dir.join("git");
+    let db = git_dir
+        .join("db")
+        .join(format!("{}-{}", name, short_hash(url)));
+
+    if !db.exists() {
+        fs::create_dir_all(git_dir.join("db"))
+            .map_err(|e| format!("Failed to create git cache: {}", e))?;
+        git(
+            None,
+            &[
+                "clone",
+                "--quiet",
+                "--bare",
+                "--",
+                url,
+                &db.to_string_lossy(),
+            ],
+        )?;
+    } else if locked.is_none_or(|commit| resolve(&db, commit).is_err()) {
+        git(
+            Some(&db),
+            &[
+                "fetch",
+                "--quiet",
+                "--force",
+                "--tags",
+                "--",
+                url,
+                "+refs/heads/*:refs/heads/*",
+            ],
+        )?;
+    }
+
+    let commit = match locked {
+        Some(commit) => resolve(&db, commit).map_err(|_| {
+            format!(
+                "Locked commit {} of {} is no longer in {}",
+                commit, name, url
+            )
+        })?,
+        None => resolve(&db, &reference.revision())
+            .map_err(|_| format!("Could not find {} in {}", reference, url))?,
+    };
+
+    let dir = checkout_dir(cache_dir, name, &commit);
+    if !dir.exists() {
+        let cloned = git(
+            None,
+            &[
+                "clone",
+                "--quiet",
+                "--no-checkout",
+                "--",
+                &db.to_string_lossy(),
+                &dir.to_string_lossy(),
+            ],
+        )
+        .and_then(|_| git(Some(&dir), &["checkout", "--quiet", "--detach", &commit]));
+        if let Err(e) = cloned {
+            let _ = fs::remove_dir_all(&dir);
+            return Err(e);
+        }
+    }
+
+    Ok((dir, commit))
+}
+
+/// Full hash of the commit `revision` names in `repo`
+fn resolve(repo: &Path, revision: &str) -> Result<String, String> {
+    git(
+        Some(repo),
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{}^{{commit}}", revision),
+        ],
+    )
+}
+
+fn git(dir: Option<&Path>, args: &[&str]) -> Result<String, String> {
+    let mut cmd = Command::new("git");
+    if let Some(dir) = dir {
+        cmd.arg("-C").arg(dir);
+    }
+    let output = cmd
+        .args(args)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn short_hash(s: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    s.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Commit `file` with `content` to the repository at `repo`; returns the commit
+    fn commit(repo: &Path, file: &str, content: &str) -> String {
+        fs::write(repo.join(file), content).unwrap();
+        let run = |args: &[&str]| {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(repo)
+                .args([
+                    "-c",
+                    "user.name=knull",
+                    "-c",
+                    "user.email=knull@example.com",
+                ])
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(status.status.success(), "{:?}", status);
+        };
+        run(&["add", "."]);
+        run(&["commit", "--quiet", "-m", content]);
+        resolve(repo, "HEAD").unwrap()
+    }
+
+    #[test]
+    fn test_checkout_references_and_locks() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("repo");
+        fs::create_dir(&repo).unwrap();
+        git(Some(&repo), &["init", "--quiet", "--initial-branch=main"]).unwrap();
+        let first = commit(&repo, "lib.knull", "one");
+        git(Some(&repo), &["tag", "v1"]).unwrap();
+        let url = format!("file://{}", repo.display());
+        let cache = temp.path().join("cache");
+
+        let tag = GitReference::Tag("v1".to_string());
+        let (dir, locked) = checkout(&cache, "lib", &url, &tag, None).unwrap();
+        assert_eq!(locked, first);
+        assert_eq!(fs::read_to_string(dir.join("lib.knull")).unwrap(), "one");
+
+        let second = commit(&repo, "lib.knull", "two");
+        let branch = GitReference::Branch("main".to_string());
+        let (dir, head) = checkout(&cache, "lib", &url, &branch, None).unwrap();
+        assert_eq!(head, second);
+        assert_eq!(fs::read_to_string(dir.join("lib.knull")).unwrap(), "two");
+
+        // A lock keeps the branch where it was
+        let (dir, _) = checkout(&cache, "lib", &url, &branch, Some(&first)).unwrap();
+        assert_eq!(fs::read_to_string(dir.join("lib.knull")).unwrap(), "one");
+
+        let missing = GitReference::Tag("v9".to_string());
+        let err = checkout(&cache, "lib", &url, &missing, None).unwrap_err();
+        assert!(err.contains("tag v9"), "{}", err);
+
+        let err = checkout(&cache, "evil", "--upload-pack=touch pwned", &tag, None).unwrap_err();
+        assert!(err.contains("Invalid git URL"), "{}", err);
+    }
+
+    #[test]
+    fn test_locked_commit_matches_reference() {
+        let tag = GitReference::Tag("v1".to_string());
+        let src = source("file:///r", &tag, "abc123");
+        assert_eq!(src, "git+file:///r?tag=v1#abc123");
+        assert_eq!(locked_commit(&src, "file:///r", &tag), Some("abc123"));
+        assert_eq!(
+            locked_commit(&src, "file:///r", &GitReference::DefaultBranch),
+            None
+        );
+        assert_eq!(
+            parse_source(&src),
+            Some(("file:///r".to_string(), tag, "abc123".to_string()))
+        );
+        assert_eq!(
+            parse_source("git+https://h/r.git#abc"),
+            Some((
+                "https://h/r.git".to_string(),
+                GitReference::DefaultBranch,
+                "abc".to_string()
+            ))
+        );
+        assert_eq!(parse_source("path+/x"), None);
+    }
+}
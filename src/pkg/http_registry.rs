@@ -1,14 +1,16 @@
 //! HTTP Registry Client Module
 //! Handles package download and upload to remote registry
 
+use crate::pkg::config::Config;
+use crate::pkg::lockfile::generate_checksum;
 use crate::pkg::manager::PackageManifest;
 use crate::pkg::semver;
+use crate::pkg::signing::PackageSignature;
 use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 
-const DEFAULT_REGISTRY: &str = "https://registry.knull-lang.dev";
 const CACHE_DIR: &str = ".knull/cache";
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -17,6 +19,8 @@ pub struct RegistryResponse {
     pub version: String,
     pub download_url: String,
     pub checksum: String,
+    pub signature: Option<String>,
+    pub public_key: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -34,81 +38,227 @@ fn ensure_cache_exists() -> Result<(), String> {
     fs::create_dir_all(&cache_dir).map_err(|e| format!("Failed to create cache directory: {}", e))
 }
 
-pub fn fetch_from_registry(name: &str, version: &str) -> Result<PathBuf, String> {
+/// Path of a previously downloaded `name@version`, if it is cached
+pub fn cached_path(name: &str, version: &str) -> Option<PathBuf> {
+    let path = get_cache_dir().ok()?.join(format!("{}-{}", name, version));
+    path.is_dir().then_some(path)
+}
+
+/// Versions of `name` already in the download cache
+pub fn cached_versions(name: &str) -> Vec<String> {
+    let prefix = format!("{}-", name);
+    let entries = match get_cache_dir().and_then(|dir| fs::read_dir(dir).map_err(|e| e.to_string()))
+    {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    entries
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| {
+            e.file_name()
+                .to_string_lossy()
+                .strip_prefix(&prefix)
+                .map(str::to_string)
+        })
+        .filter(|version| semver::parse_version(version).is_ok())
+        .collect()
+}
+
+/// A cached `name@version` and its signature, once both are checked against its files
+pub fn cached(name: &str, version: &str) -> Result<Option<(PathBuf, PackageSignature)>, String> {
+    cached_in(&get_cache_dir()?, name, version)
+}
+
+fn cached_in(
+    cache_dir: &Path,
+    name: &str,
+    version: &str,
+) -> Result<Option<(PathBuf, PackageSignature)>, String> {
+    let dir = cache_dir.join(format!("{}-{}", name, version));
+    let signature_path = signature_path(cache_dir, name, version);
+    // Copies cached before packages were signed are downloaded again
+    if !dir.is_dir() || !signature_path.exists() {
+        return Ok(None);
+    }
+
+    let signature = PackageSignature::load(&signature_path)?;
+    signature.verify(name, version)?;
+    let checksum = generate_checksum(&dir)?;
+    if checksum != signature.checksum {
+        return Err(format!(
+            "Cached {}@{} no longer matches its signed checksum (expected {}, got {}); remove {} to download it again",
+            name,
+            version,
+            signature.checksum,
+            checksum,
+            dir.display()
+        ));
+    }
+    Ok(Some((dir, signature)))
+}
+
+fn signature_path(cache_dir: &Path, name: &str, version: &str) -> PathBuf {
+    cache_dir.join(format!("{}-{}.sig", name, version))
+}
+
+/// Download `name@version` into the cache from the first registry or mirror that has it,
+/// checking its files against the checksum its publisher signed. Cache hits are checked too.
+pub fn fetch_from_registry(
+    name: &str,
+    version: &str,
+) -> Result<(PathBuf, PackageSignature), String> {
     ensure_cache_exists()?;
 
     let cache_dir = get_cache_dir()?;
-    let package_cache = cache_dir.join(format!("{}-{}", name, version));
+    if let Some(cached) = cached_in(&cache_dir, name, version)? {
+        return Ok(cached);
+    }
 
-    // Check if already cached
-    if package_cache.exists() {
-        return Ok(package_cache);
+    let mut errors = Vec::new();
+    for registry in Config::load()?.registries() {
+        match download(&registry, name, version, &cache_dir) {
+            Ok(fetched) => return Ok(fetched),
+            Err(e) => errors.push(e),
+        }
     }
+    Err(errors.join("\n"))
+}
 
+fn download(
+    registry: &str,
+    name: &str,
+    version: &str,
+    cache_dir: &Path,
+) -> Result<(PathBuf, PackageSignature), String> {
     let client = Client::new();
-    let registry = std::env::var("KNULL_REGISTRY").unwrap_or_else(|_| DEFAULT_REGISTRY.to_string());
-
-    let url = format!("{}/packages/{}/{}/download", registry, name, version);
-
-    println!("  Downloading {}@{} from registry...", name, version);
+    let url = format!("{}/packages/{}/{}", registry, name, version);
 
     let response = client
         .get(&url)
         .send()
-        .map_err(|e| format!("Failed to download package: {}", e))?;
-
+        .map_err(|e| format!("Failed to query {}: {}", registry, e))?;
     if !response.status().is_success() {
         return Err(format!(
-            "Package {}@{} not found on registry (status: {})",
+            "Package {}@{} not found on {} (status: {})",
             name,
             version,
+            registry,
             response.status()
         ));
     }
+    let info: RegistryResponse = response
+        .json()
+        .map_err(|e| format!("Failed to parse registry response: {}", e))?;
+    let signature = match (info.signature, info.public_key) {
+        (Some(signature), Some(public_key)) => PackageSignature {
+            checksum: info.checksum,
+            signature,
+            public_key,
+        },
+        _ => {
+            return Err(format!(
+                "Package {}@{} on {} is not signed",
+                name, version, registry
+            ))
+        }
+    };
+    signature.verify(name, version)?;
+
+    let download_url = if info.download_url.starts_with('/') {
+        format!("{}{}", registry, info.download_url)
+    } else {
+        info.download_url
+    };
+
+    println!("  Downloading {}@{} from {}...", name, version, registry);
 
-    // Create package cache directory
-    fs::create_dir_all(&package_cache)
-        .map_err(|e| format!("Failed to create package cache: {}", e))?;
+    let response = client
+        .get(&download_url)
+        .send()
+        .map_err(|e| format!("Failed to download package from {}: {}", registry, e))?;
 
-    // Save the downloaded archive
-    let archive_path = package_cache.join("package.tar.gz");
+    if !response.status().is_success() {
+        return Err(format!(
+            "Failed to download {}@{} from {} (status: {})",
+            name,
+            version,
+            registry,
+            response.status()
+        ));
+    }
     let bytes = response
         .bytes()
         .map_err(|e| format!("Failed to read response: {}", e))?;
-    fs::write(&archive_path, &bytes).map_err(|e| format!("Failed to write archive: {}", e))?;
 
-    // Extract the archive
-    extract_archive(&archive_path, &package_cache)?;
+    // Unpack beside the cache entry, and only move it into place once it checks out
+    let package_cache = cache_dir.join(format!("{}-{}", name, version));
+    let staging = cache_dir.join(format!(".{}-{}.partial", name, version));
+    let _ = fs::remove_dir_all(&staging);
+    fs::create_dir_all(&staging).map_err(|e| format!("Failed to create package cache: {}", e))?;
+    let checked = extract_archive(&bytes, &staging)
+        .and_then(|_| generate_checksum(&staging))
+        .and_then(|checksum| {
+            if checksum == signature.checksum {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Checksum mismatch for {}@{} from {}: expected {}, got {}",
+                    name, version, registry, signature.checksum, checksum
+                ))
+            }
+        });
+    if let Err(e) = checked {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e);
+    }
 
-    // Remove the archive file
-    let _ = fs::remove_file(&archive_path);
+    let _ = fs::remove_dir_all(&package_cache);
+    signature.save(&signature_path(cache_dir, name, version))?;
+    fs::rename(&staging, &package_cache)
+        .map_err(|e| format!("Failed to write package cache: {}", e))?;
 
     println!("  Downloaded {}@{} to cache", name, version);
-    Ok(package_cache)
+    Ok((package_cache, signature))
 }
 
-pub fn resolve_version(name: &str, constraint: &str) -> Result<String, String> {
-    let client = Client::new();
-    let registry = std::env::var("KNULL_REGISTRY").unwrap_or_else(|_| DEFAULT_REGISTRY.to_string());
+/// Every version of `name` the registry has, asking the mirrors first
+pub fn list_versions(name: &str) -> Result<Vec<String>, String> {
+    let mut errors = Vec::new();
+    for registry in Config::load()?.registries() {
+        match list_versions_from(&registry, name) {
+            Ok(versions) => return Ok(versions),
+            Err(e) => errors.push(e),
+        }
+    }
+    Err(errors.join("\n"))
+}
 
+fn list_versions_from(registry: &str, name: &str) -> Result<Vec<String>, String> {
+    let client = Client::new();
     let url = format!("{}/packages/{}/versions", registry, name);
 
     let response = client
         .get(&url)
         .send()
-        .map_err(|e| format!("Failed to query registry: {}", e))?;
+        .map_err(|e| format!("Failed to query {}: {}", registry, e))?;
 
     if !response.status().is_success() {
-        return Err(format!("Package {} not found on registry", name));
+        return Err(format!("Package {} not found on {}", name, registry));
     }
 
     let version_list: VersionList = response
         .json()
         .map_err(|e| format!("Failed to parse registry response: {}", e))?;
 
+    Ok(version_list.versions)
+}
+
+pub fn resolve_version(name: &str, constraint: &str) -> Result<String, String> {
+    let versions = list_versions(name)?;
+
     // Find best matching version
-    let mut matching_versions: Vec<&str> = version_list
-        .versions
+    let mut matching_versions: Vec<&str> = versions
         .iter()
         .filter(|v: &&String| semver::satisfies(v, constraint))
         .map(|v: &String| v.as_str())
@@ -126,10 +276,11 @@ pub fn resolve_version(name: &str, constraint: &str) -> Result<String, String> {
 pub fn publish_to_registry(
     manifest: &PackageManifest,
     archive_path: &Path,
+    signature: &PackageSignature,
     token: &str,
 ) -> Result<(), String> {
     let client = Client::new();
-    let registry = std::env::var("KNULL_REGISTRY").unwrap_or_else(|_| DEFAULT_REGISTRY.to_string());
+    let registry = Config::load()?.registry();
 
     let url = format!("{}/packages/{}/publish", registry, manifest.package.name);
 
@@ -150,7 +301,10 @@ pub fn publish_to_registry(
             "manifest",
             toml::to_string(manifest)
                 .map_err(|e| format!("Failed to serialize manifest: {}", e))?,
-        );
+        )
+        .text("checksum", signature.checksum.clone())
+        .text("signature", signature.signature.clone())
+        .text("public_key", signature.public_key.clone());
 
     println!(
         "Publishing {}@{} to registry...",
@@ -183,7 +337,7 @@ pub fn publish_to_registry(
 
 pub fn search_registry(query: &str) -> Result<Vec<String>, String> {
     let client = Client::new();
-    let registry = std::env::var("KNULL_REGISTRY").unwrap_or_else(|_| DEFAULT_REGISTRY.to_string());
+    let registry = Config::load()?.registry();
 
     let url = format!("{}/search?q={}", registry, query);
 
@@ -208,13 +362,20 @@ pub fn search_registry(query: &str) -> Result<Vec<String>, String> {
     Ok(result.packages)
 }
 
-fn extract_archive(archive_path: &Path, dest_dir: &Path) -> Result<(), String> {
+/// The checksum of the files in a package archive, as they will be after downloading
+pub fn archive_checksum(archive_path: &Path) -> Result<String, String> {
+    let archive = fs::read(archive_path).map_err(|e| format!("Failed to read archive: {}", e))?;
+    let dir =
+        tempfile::TempDir::new().map_err(|e| format!("Failed to create temp directory: {}", e))?;
+    extract_archive(&archive, dir.path())?;
+    generate_checksum(dir.path())
+}
+
+fn extract_archive(archive: &[u8], dest_dir: &Path) -> Result<(), String> {
     use flate2::read::GzDecoder;
     use tar::Archive;
 
-    let tar_gz =
-        fs::File::open(archive_path).map_err(|e| format!("Failed to open archive: {}", e))?;
-    let tar = GzDecoder::new(tar_gz);
+    let tar = GzDecoder::new(archive);
     let mut archive = Archive::new(tar);
 
     archive
@@ -223,3 +384,126 @@ fn extract_archive(archive_path: &Path, dest_dir: &Path) -> Result<(), String> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::SigningKey;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use tempfile::TempDir;
+
+    /// Serve `routes` (path -> body) over HTTP on localhost; returns the base URL
+    fn serve(routes: Vec<(String, Vec<u8>)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let n = stream.read(&mut buf).unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..n]);
+                }
+                let request = String::from_utf8_lossy(&request);
+                let path = request.split_whitespace().nth(1).unwrap_or("");
+                let (status, body) = match routes.iter().find(|(p, _)| p == path) {
+                    Some((_, body)) => ("200 OK", body.clone()),
+                    None => ("404 Not Found", Vec::new()),
+                };
+                let head = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    body.len()
+                );
+                let _ = stream.write_all(head.as_bytes());
+                let _ = stream.write_all(&body);
+            }
+        });
+        url
+    }
+
+    /// A package archive holding `lib.knull` with `content`
+    fn archive(dir: &Path, content: &str) -> PathBuf {
+        let src = dir.join(format!("pkg-{}", content.len()));
+        fs::create_dir_all(src.join("src")).unwrap();
+        fs::write(src.join("package.toml"), "[package]").unwrap();
+        fs::write(src.join("src").join("lib.knull"), content).unwrap();
+
+        let path = src.with_extension("tar.gz");
+        let file = fs::File::create(&path).unwrap();
+        let mut tar = tar::Builder::new(flate2::write::GzEncoder::new(
+            file,
+            flate2::Compression::default(),
+        ));
+        tar.append_dir_all(".", &src).unwrap();
+        tar.into_inner().unwrap().finish().unwrap();
+        path
+    }
+
+    fn info(signature: &PackageSignature, signed: bool) -> Vec<u8> {
+        serde_json::to_vec(&RegistryResponse {
+            name: "json".to_string(),
+            version: "1.0.0".to_string(),
+            download_url: "/files/json-1.0.0.tar.gz".to_string(),
+            checksum: signature.checksum.clone(),
+            signature: signed.then(|| signature.signature.clone()),
+            public_key: signed.then(|| signature.public_key.clone()),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_downloads_are_checked_against_signed_checksums() {
+        let temp = TempDir::new().unwrap();
+        let cache = temp.path().join("cache");
+        fs::create_dir(&cache).unwrap();
+        let good = archive(temp.path(), "fn parse() {}");
+        let evil = archive(temp.path(), "fn parse() { steal() }");
+        let key = SigningKey::from_bytes(&[1u8; 32]);
+        let signature =
+            PackageSignature::sign(&key, "json", "1.0.0", &archive_checksum(&good).unwrap());
+
+        let route = |body: Vec<u8>| ("/files/json-1.0.0.tar.gz".to_string(), body);
+        let info_route = |body: Vec<u8>| ("/packages/json/1.0.0".to_string(), body);
+
+        // An archive that is not the one that was signed is rejected and not cached
+        let registry = serve(vec![
+            info_route(info(&signature, true)),
+            route(fs::read(&evil).unwrap()),
+        ]);
+        let err = download(&registry, "json", "1.0.0", &cache).unwrap_err();
+        assert!(err.contains("Checksum mismatch for json@1.0.0"), "{}", err);
+        assert_eq!(cached_in(&cache, "json", "1.0.0").unwrap(), None);
+
+        let registry = serve(vec![
+            info_route(info(&signature, false)),
+            route(fs::read(&good).unwrap()),
+        ]);
+        let err = download(&registry, "json", "1.0.0", &cache).unwrap_err();
+        assert!(err.contains("is not signed"), "{}", err);
+
+        let registry = serve(vec![
+            info_route(info(&signature, true)),
+            route(fs::read(&good).unwrap()),
+        ]);
+        let (dir, fetched) = download(&registry, "json", "1.0.0", &cache).unwrap();
+        assert_eq!(fetched, signature);
+        assert!(dir.join("src").join("lib.knull").exists());
+        let (_, cached) = cached_in(&cache, "json", "1.0.0").unwrap().unwrap();
+        assert_eq!(cached, signature);
+
+        // Cache hits are checked too
+        fs::write(dir.join("src").join("lib.knull"), "fn parse() { steal() }").unwrap();
+        let err = cached_in(&cache, "json", "1.0.0").unwrap_err();
+        assert!(
+            err.contains("no longer matches its signed checksum"),
+            "{}",
+            err
+        );
+    }
+}
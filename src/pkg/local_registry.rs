@@ -105,6 +105,12 @@ pub fn fetch_from_local(name: &str, version: &str) -> Result<PathBuf, String> {
     }
 }
 
+/// Path of `name@version` in the local registry, if it is there
+pub fn installed_path(name: &str, version: &str) -> Option<PathBuf> {
+    let path = get_packages_dir().ok()?.join(name).join(version);
+    path.is_dir().then_some(path)
+}
+
 pub fn publish_to_local(project_path: &Path, manifest: &PackageManifest) -> Result<(), String> {
     ensure_registry_exists()?;
 
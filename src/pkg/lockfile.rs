@@ -10,13 +10,13 @@ use std::path::Path;
 pub const LOCKFILE_VERSION: i32 = 1;
 pub const LOCKFILE_NAME: &str = "knull.lock";
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Lockfile {
     pub version: i32,
     pub packages: Vec<LockedPackage>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct LockedPackage {
     pub name: String,
     pub version: String,
@@ -155,6 +155,73 @@ impl Lockfile {
         self.packages = new_packages;
     }
 
+    /// `roots` and everything they depend on, drawn as a tree under `label`.
+    /// A package shown once is marked `(*)` the next time instead of being expanded again.
+    pub fn tree(&self, label: &str, roots: &[String]) -> String {
+        fn draw(
+            lockfile: &Lockfile,
+            names: &[String],
+            prefix: &str,
+            shown: &mut std::collections::HashSet<String>,
+            out: &mut String,
+        ) {
+            for (i, name) in names.iter().enumerate() {
+                let last = i + 1 == names.len();
+                let branch = if last { "└── " } else { "├── " };
+                let pkg = match lockfile.get_package(name) {
+                    Some(pkg) => pkg,
+                    None => {
+                        out.push_str(&format!("{}{}{} (not locked)\n", prefix, branch, name));
+                        continue;
+                    }
+                };
+
+                let deps = pkg.dependencies.clone().unwrap_or_default();
+                let repeated = !shown.insert(name.clone()) && !deps.is_empty();
+                let origin = match pkg.source.split_once('+') {
+                    Some(("registry", _)) => String::new(),
+                    Some(("git", location)) => match location.rsplit_once('#') {
+                        Some((url, commit)) => {
+                            format!(" ({}#{})", url, &commit[..8.min(commit.len())])
+                        }
+                        None => format!(" ({})", location),
+                    },
+                    Some((_, location)) => format!(" ({})", location),
+                    None => format!(" ({})", pkg.source),
+                };
+                out.push_str(&format!(
+                    "{}{}{} {}{}{}\n",
+                    prefix,
+                    branch,
+                    name,
+                    pkg.version,
+                    origin,
+                    if repeated { " (*)" } else { "" }
+                ));
+                if !repeated {
+                    let indent = if last { "    " } else { "│   " };
+                    draw(
+                        lockfile,
+                        &deps,
+                        &format!("{}{}", prefix, indent),
+                        shown,
+                        out,
+                    );
+                }
+            }
+        }
+
+        let mut out = format!("{}\n", label);
+        draw(
+            self,
+            roots,
+            "",
+            &mut std::collections::HashSet::new(),
+            &mut out,
+        );
+        out
+    }
+
     /// Get all dependencies for a package (transitive)
     pub fn get_all_dependencies(&self, name: &str) -> Vec<String> {
         let mut result = Vec::new();
@@ -194,34 +261,46 @@ impl Default for Lockfile {
     }
 }
 
-/// Generate a checksum for a package directory
+/// Generate a checksum for a package directory: a sha256 over every file's path and contents,
+/// in name order, so copies of the same package anywhere hash the same. `.git` is skipped.
 pub fn generate_checksum(package_path: &Path) -> Result<String, String> {
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
+    use sha2::{Digest, Sha256};
 
-    let mut hasher = DefaultHasher::new();
+    fn hash_dir(root: &Path, path: &Path, hasher: &mut Sha256) -> std::io::Result<()> {
+        let mut entries = fs::read_dir(path)?.collect::<Result<Vec<_>, _>>()?;
+        entries.sort_by_key(|e| e.file_name());
 
-    fn hash_dir(path: &Path, hasher: &mut DefaultHasher) -> std::io::Result<()> {
-        for entry in fs::read_dir(path)? {
-            let entry = entry?;
+        for entry in entries {
+            if entry.file_name() == ".git" {
+                continue;
+            }
             let path = entry.path();
-            let metadata = entry.metadata()?;
-
-            if metadata.is_dir() {
-                hash_dir(&path, hasher)?;
+            if entry.metadata()?.is_dir() {
+                hash_dir(root, &path, hasher)?;
             } else {
-                // Hash file contents
-                if let Ok(content) = fs::read(&path) {
-                    content.hash(hasher);
-                }
+                let content = fs::read(&path)?;
+                let relative = path.strip_prefix(root).unwrap_or(&path);
+                hasher.update(relative.to_string_lossy().replace('\\', "/").as_bytes());
+                hasher.update([0]);
+                hasher.update((content.len() as u64).to_le_bytes());
+                hasher.update(&content);
             }
         }
         Ok(())
     }
 
-    hash_dir(package_path, &mut hasher).map_err(|e| format!("Failed to hash directory: {}", e))?;
+    let mut hasher = Sha256::new();
+    hash_dir(package_path, package_path, &mut hasher)
+        .map_err(|e| format!("Failed to hash directory: {}", e))?;
 
-    Ok(format!("{:x}", hasher.finish()))
+    let digest = hasher.finalize();
+    Ok(format!(
+        "sha256:{}",
+        digest
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>()
+    ))
 }
 
 /// Build a dependency graph from the lockfile
@@ -330,6 +409,71 @@ mod tests {
         assert!(lockfile.get_package("pkg2").is_none());
     }
 
+    #[test]
+    fn test_tree() {
+        let locked = |name: &str, source: &str, deps: &[&str]| LockedPackage {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            source: source.to_string(),
+            checksum: None,
+            dependencies: if deps.is_empty() {
+                None
+            } else {
+                Some(deps.iter().map(|d| d.to_string()).collect())
+            },
+        };
+        let registry = "registry+https://registry.knull-lang.dev";
+        let lockfile = Lockfile {
+            version: 1,
+            packages: vec![
+                locked("http", registry, &["json", "url"]),
+                locked("json", registry, &[]),
+                locked("url", "path+/src/url", &["json"]),
+                locked("web", "git+file:///r?tag=v1#0123456789abcdef", &["http"]),
+            ],
+        };
+
+        let roots = vec!["http".to_string(), "web".to_string()];
+        assert_eq!(
+            lockfile.tree("app 0.1.0", &roots),
+            "app 0.1.0
+├── http 1.0.0
+│   ├── json 1.0.0
+│   └── url 1.0.0 (/src/url)
+│       └── json 1.0.0
+└── web 1.0.0 (file:///r?tag=v1#01234567)
+    └── http 1.0.0 (*)
+"
+        );
+    }
+
+    #[test]
+    fn test_checksum_follows_contents() {
+        let temp = TempDir::new().unwrap();
+        let write = |dir: &Path| {
+            fs::create_dir_all(dir.join("src")).unwrap();
+            fs::write(dir.join("knull.toml"), "[package]").unwrap();
+            fs::write(dir.join("src").join("lib.knull"), "fn f() {}").unwrap();
+        };
+        let a = temp.path().join("a");
+        let b = temp.path().join("nested").join("b");
+        write(&a);
+        write(&b);
+        fs::create_dir(b.join(".git")).unwrap();
+        fs::write(b.join(".git").join("HEAD"), "ref").unwrap();
+
+        let checksum = generate_checksum(&a).unwrap();
+        assert!(checksum.starts_with("sha256:"), "{}", checksum);
+        assert_eq!(generate_checksum(&b).unwrap(), checksum);
+
+        fs::rename(
+            b.join("src").join("lib.knull"),
+            b.join("src").join("main.knull"),
+        )
+        .unwrap();
+        assert_ne!(generate_checksum(&b).unwrap(), checksum);
+    }
+
     #[test]
     fn test_detect_cycles() {
         let mut graph = HashMap::new();
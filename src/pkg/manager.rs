@@ -2,14 +2,23 @@
 //!
 //! Handles package dependencies, building, and distribution.
 
-use serde::{Deserialize, Serialize};
+use serde::de::{self, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use crate::pkg::lockfile::{Lockfile, ResolvedDep, LOCKFILE_NAME};
+use crate::pkg::git::{self, GitReference};
+use crate::pkg::lockfile::{
+    build_dependency_graph, detect_cycles, generate_checksum, LockedPackage, Lockfile, ResolvedDep,
+    LOCKFILE_NAME,
+};
+use crate::pkg::resolver::{self, PackageSource};
 use crate::pkg::semver;
+use crate::pkg::signing::{self, Keyring, PackageSignature, KEYRING_NAME};
+use crate::pkg::vendor::{self, VendorIndex, VendoredPackage};
 
 /// Get the local packages directory path
 fn get_local_packages_dir() -> Option<PathBuf> {
@@ -46,11 +55,13 @@ fn get_local_packages_dir() -> Option<PathBuf> {
 pub struct PackageManifest {
     pub package: PackageInfo,
     #[serde(default)]
-    pub dependencies: HashMap<String, String>,
+    pub dependencies: HashMap<String, Dependency>,
     #[serde(default)]
-    pub dev_dependencies: HashMap<String, String>,
+    pub dev_dependencies: HashMap<String, Dependency>,
     #[serde(default)]
     pub build: BuildConfig,
+    #[serde(default, skip_serializing_if = "RunConfig::is_default")]
+    pub run: RunConfig,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -79,6 +90,162 @@ pub struct BuildConfig {
     pub script: Option<String>,
 }
 
+/// `[run]`: how `knull run` executes the project's programs
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct RunConfig {
+    #[serde(rename = "max-depth", default, skip_serializing_if = "Option::is_none")]
+    pub max_depth: Option<usize>,
+}
+
+impl RunConfig {
+    fn is_default(&self) -> bool {
+        *self == RunConfig::default()
+    }
+}
+
+/// A `[dependencies]` entry
+#[derive(Debug, Clone, PartialEq)]
+pub enum Dependency {
+    /// `"^1.0"` or `{ version = "^1.0" }`: a registry package
+    Registry(String),
+    /// `{ path = "../shared" }`, relative to the manifest's directory
+    Path(PathBuf),
+    /// `{ git = "https://...", tag = "v1.0" }`, or `rev` or `branch` instead of `tag`
+    Git {
+        url: String,
+        reference: GitReference,
+    },
+}
+
+/// The table form of a dependency, as written in knull.toml
+#[derive(Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct DependencyTable {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    path: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    git: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rev: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    branch: Option<String>,
+}
+
+impl Dependency {
+    /// The version constraint of a registry dependency
+    pub fn version(&self) -> Option<&str> {
+        match self {
+            Dependency::Registry(constraint) => Some(constraint),
+            _ => None,
+        }
+    }
+
+    fn from_table(table: DependencyTable) -> Result<Self, String> {
+        let reference = match (table.rev, table.tag, table.branch) {
+            (None, None, None) => None,
+            (Some(rev), None, None) => Some(GitReference::Rev(rev)),
+            (None, Some(tag), None) => Some(GitReference::Tag(tag)),
+            (None, None, Some(branch)) => Some(GitReference::Branch(branch)),
+            _ => return Err("only one of `rev`, `tag` and `branch` can be given".to_string()),
+        };
+
+        match (table.version, table.path, table.git, reference) {
+            (Some(version), None, None, None) => Ok(Dependency::Registry(version)),
+            (None, Some(path), None, None) => Ok(Dependency::Path(path)),
+            (None, None, Some(url), reference) => Ok(Dependency::Git {
+                url,
+                reference: reference.unwrap_or(GitReference::DefaultBranch),
+            }),
+            (None, None, None, None) => {
+                Err("a dependency needs a `version`, a `path` or a `git` repository".to_string())
+            }
+            (_, _, None, Some(_)) => {
+                Err("`rev`, `tag` and `branch` only apply to `git` dependencies".to_string())
+            }
+            _ => Err("only one of `version`, `path` and `git` can be given".to_string()),
+        }
+    }
+
+    fn to_table(&self) -> DependencyTable {
+        match self {
+            Dependency::Registry(version) => DependencyTable {
+                version: Some(version.clone()),
+                ..Default::default()
+            },
+            Dependency::Path(path) => DependencyTable {
+                path: Some(path.clone()),
+                ..Default::default()
+            },
+            Dependency::Git { url, reference } => {
+                let mut table = DependencyTable {
+                    git: Some(url.clone()),
+                    ..Default::default()
+                };
+                match reference {
+                    GitReference::Rev(rev) => table.rev = Some(rev.clone()),
+                    GitReference::Tag(tag) => table.tag = Some(tag.clone()),
+                    GitReference::Branch(branch) => table.branch = Some(branch.clone()),
+                    GitReference::DefaultBranch => {}
+                }
+                table
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Dependency {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DependencyVisitor;
+
+        impl<'de> Visitor<'de> for DependencyVisitor {
+            type Value = Dependency;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a version or a table with `version`, `path` or `git`")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Dependency, E> {
+                Ok(Dependency::Registry(v.to_string()))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Dependency, A::Error> {
+                let table =
+                    DependencyTable::deserialize(de::value::MapAccessDeserializer::new(map))?;
+                Dependency::from_table(table).map_err(de::Error::custom)
+            }
+        }
+
+        deserializer.deserialize_any(DependencyVisitor)
+    }
+}
+
+impl Serialize for Dependency {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Dependency::Registry(version) => serializer.serialize_str(version),
+            _ => self.to_table().serialize(serializer),
+        }
+    }
+}
+
+impl fmt::Display for Dependency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Dependency::Registry(version) => write!(f, "{}", version),
+            Dependency::Path(path) => write!(f, "path {}", path.display()),
+            Dependency::Git {
+                url,
+                reference: GitReference::DefaultBranch,
+            } => write!(f, "git {}", url),
+            Dependency::Git { url, reference } => write!(f, "git {} ({})", url, reference),
+        }
+    }
+}
+
 impl PackageManifest {
     /// Load manifest from file
     pub fn load(path: &Path) -> Result<Self, String> {
@@ -114,16 +281,28 @@ impl PackageManifest {
                 lto: false,
                 script: None,
             },
+            run: RunConfig::default(),
         }
     }
 }
 
+/// What dependency commands may do besides reading the project, vendor/ and the caches
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FetchMode {
+    /// Fail instead of using the network
+    pub offline: bool,
+    /// Fail instead of changing knull.lock
+    pub locked: bool,
+}
+
 /// Package manager
 pub struct PackageManager {
     root_path: PathBuf,
     manifest: PackageManifest,
     cache_dir: PathBuf,
     lockfile: Option<Lockfile>,
+    vendor: Option<VendorIndex>,
+    mode: FetchMode,
 }
 
 impl PackageManager {
@@ -148,14 +327,24 @@ impl PackageManager {
             None
         };
 
+        let vendor = VendorIndex::load(&vendor::vendor_dir(&root_path)).ok();
+
         Ok(PackageManager {
             root_path,
             manifest,
             cache_dir,
             lockfile,
+            vendor,
+            mode: FetchMode::default(),
         })
     }
 
+    /// Restrict network use and lockfile changes
+    pub fn with_mode(mut self, mode: FetchMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
     /// Create new project
     pub fn new_project(name: &str) -> Result<(), String> {
         let project_dir = PathBuf::from(name);
@@ -214,142 +403,318 @@ knull.lock
 
     /// Add dependency
     pub fn add_dependency(&mut self, name: &str, version: &str) -> Result<(), String> {
-        self.manifest
+        let previous = self
+            .manifest
             .dependencies
-            .insert(name.to_string(), version.to_string());
-        self.manifest.save(&self.root_path.join("knull.toml"))?;
-
-        // Fetch the dependency immediately
-        self.fetch_package(name, version)?;
-
-        // Update lockfile
-        self.update_lockfile()?;
+            .insert(name.to_string(), Dependency::Registry(version.to_string()));
+
+        // Keep the manifest as it was if the new dependency cannot be resolved
+        if let Err(e) = self.update_lockfile() {
+            match previous {
+                Some(dep) => self.manifest.dependencies.insert(name.to_string(), dep),
+                None => self.manifest.dependencies.remove(name),
+            };
+            return Err(e);
+        }
 
-        Ok(())
+        self.manifest.save(&self.root_path.join("knull.toml"))
     }
 
-    /// Remove dependency
+    /// Remove dependency, and the locked packages nothing else needs
     pub fn remove_dependency(&mut self, name: &str) -> Result<(), String> {
-        if self.manifest.dependencies.remove(name).is_none() {
-            return Err(format!("Dependency '{}' not found", name));
+        let removed = match self.manifest.dependencies.remove(name) {
+            Some(dep) => dep,
+            None => return Err(format!("Dependency '{}' not found", name)),
+        };
+
+        let pruned = self.lockfile.clone().map(|mut lockfile| {
+            let mut needed: Vec<String> = Vec::new();
+            for root in self.manifest.dependencies.keys() {
+                needed.push(root.clone());
+                needed.extend(lockfile.get_all_dependencies(root));
+            }
+            lockfile.packages.retain(|p| needed.contains(&p.name));
+            lockfile
+        });
+        if let Some(ref pruned) = pruned {
+            if let Err(e) = self.check_locked(pruned) {
+                self.manifest.dependencies.insert(name.to_string(), removed);
+                return Err(e);
+            }
         }
 
         self.manifest.save(&self.root_path.join("knull.toml"))?;
 
-        // Update lockfile to remove package
-        if let Some(ref mut lockfile) = self.lockfile {
-            lockfile.remove_package(name);
+        if let Some(lockfile) = pruned {
             lockfile.save(&self.root_path.join(LOCKFILE_NAME))?;
+            self.lockfile = Some(lockfile);
         }
 
         println!("Removed dependency: {}", name);
         Ok(())
     }
 
-    /// Update all dependencies
+    /// Update every package to the newest version the manifest allows
     pub fn update_all_dependencies(&mut self) -> Result<(), String> {
         println!("Updating all dependencies...");
 
-        let deps: Vec<(String, String)> = self
-            .manifest
-            .dependencies
-            .iter()
-            .map(|(k, v)| (k.clone(), v.clone()))
-            .collect();
-
-        for (name, constraint) in deps {
-            self.update_package(&name, &constraint)?;
-        }
-
+        self.lockfile = None;
         self.update_lockfile()?;
         println!("All dependencies updated");
         Ok(())
     }
 
-    /// Update a specific package
-    pub fn update_package(&mut self, name: &str, constraint: &str) -> Result<(), String> {
+    /// Update one package, direct or not, leaving the others at their locked versions
+    pub fn update_package(&mut self, name: &str) -> Result<(), String> {
+        let before = self
+            .lockfile
+            .as_ref()
+            .and_then(|l| l.get_package(name))
+            .cloned();
+        if before.is_none() && !self.manifest.dependencies.contains_key(name) {
+            return Err(format!("Package '{}' is not a dependency", name));
+        }
         println!("Updating {}...", name);
 
-        // Resolve the latest version matching constraint
-        // First try local packages directory
-        let resolved_version = if let Some(packages_dir) = get_local_packages_dir() {
-            let package_path = packages_dir.join(name);
-            if package_path.exists() {
-                // Load version from local package
-                let manifest_path = package_path.join("knull.toml");
-                if let Ok(manifest) = PackageManifest::load(&manifest_path) {
-                    if semver::satisfies(&manifest.package.version, constraint) {
-                        Ok(manifest.package.version)
-                    } else {
-                        Err(format!(
-                            "Version {} does not satisfy {}",
-                            manifest.package.version, constraint
-                        ))
-                    }
-                } else {
-                    Err("Could not load manifest".to_string())
-                }
-            } else {
-                // Try HTTP registry
-                crate::pkg::http_registry::resolve_version(name, constraint).or_else(|_| {
-                    // If HTTP fails, try local registry
-                    crate::pkg::local_registry::list_local_versions(name)?
-                        .into_iter()
-                        .filter(|v| semver::satisfies(v, constraint))
-                        .max_by(|a, b| semver::compare_versions(a, b))
-                        .ok_or_else(|| format!("No version of {} satisfies {}", name, constraint))
-                })
-            }
-        } else {
-            // Try HTTP registry
-            crate::pkg::http_registry::resolve_version(name, constraint).or_else(|_| {
-                // If HTTP fails, try local registry
-                crate::pkg::local_registry::list_local_versions(name)?
-                    .into_iter()
-                    .filter(|v| semver::satisfies(v, constraint))
-                    .max_by(|a, b| semver::compare_versions(a, b))
-                    .ok_or_else(|| format!("No version of {} satisfies {}", name, constraint))
-            })
-        }?;
-
-        println!("  Resolved {} to {}", name, resolved_version);
-
-        // Fetch the package
-        self.fetch_package(name, &resolved_version)?;
-
-        // Update the constraint in manifest if it was an exact version
-        if let Some(existing) = self.manifest.dependencies.get(name) {
-            if !existing.starts_with('^')
-                && !existing.starts_with('~')
-                && !existing.starts_with('>')
-                && !existing.starts_with('<')
+        self.unlock(name);
+        self.update_lockfile()?;
+
+        let after = self.lockfile.as_ref().and_then(|l| l.get_package(name));
+        match (before, after) {
+            (Some(before), Some(after))
+                if before.version != after.version || before.source != after.source =>
             {
-                // Was exact version, update it
-                self.manifest
-                    .dependencies
-                    .insert(name.to_string(), resolved_version.clone());
-                self.manifest.save(&self.root_path.join("knull.toml"))?;
+                println!("  {} {} -> {}", name, before.version, after.version)
             }
+            (_, Some(after)) => println!("  {} {} is up to date", name, after.version),
+            _ => {}
         }
-
         Ok(())
     }
 
-    /// Fetch dependencies
-    pub fn fetch_dependencies(&self) -> Result<(), String> {
+    /// Fetch every locked package, resolving the dependencies first if there is no lockfile
+    pub fn fetch_dependencies(&mut self) -> Result<(), String> {
         // Create cache directory
         fs::create_dir_all(&self.cache_dir)
             .map_err(|e| format!("Failed to create cache directory: {}", e))?;
 
-        for (name, version) in &self.manifest.dependencies {
-            self.fetch_package(name, version)?;
+        let lockfile = match &self.lockfile {
+            Some(lockfile) => lockfile,
+            // Resolving fetches everything it picks
+            None => return self.ensure_locked(),
+        };
+        for pkg in &lockfile.packages {
+            self.fetch_locked(pkg)?;
         }
 
         Ok(())
     }
 
-    /// Fetch single package - tries local first, then HTTP registry
+    /// Resolve and fetch the dependencies if they have never been locked
+    pub fn ensure_locked(&mut self) -> Result<(), String> {
+        if self.lockfile.is_none() && !self.manifest.dependencies.is_empty() {
+            self.update_lockfile()?;
+        }
+        Ok(())
+    }
+
+    /// Copy every locked registry and git package into vendor/, checking each against
+    /// the lockfile. Returns how many were vendored.
+    pub fn vendor(&mut self) -> Result<usize, String> {
+        self.ensure_locked()?;
+        let lockfile = self.lockfile.clone().unwrap_or_default();
+
+        // Build the new copies next to the old ones, which may be where they come from
+        let vendor_dir = vendor::vendor_dir(&self.root_path);
+        let staging = self.root_path.join(format!(".{}.new", vendor::VENDOR_DIR));
+        if staging.exists() {
+            fs::remove_dir_all(&staging)
+                .map_err(|e| format!("Failed to clear {}: {}", staging.display(), e))?;
+        }
+
+        let mut index = VendorIndex::default();
+        for pkg in &lockfile.packages {
+            // Path dependencies are already on disk
+            if pkg.source.starts_with("path+") {
+                continue;
+            }
+            let dir = self.fetch_locked(pkg)?;
+            let dest = staging.join(&pkg.name);
+            vendor::copy_package(&dir, &dest)?;
+            let checksum = generate_checksum(&dest)?;
+            lockfile.verify_checksum(&pkg.name, &checksum)?;
+            index.packages.insert(
+                pkg.name.clone(),
+                VendoredPackage {
+                    version: pkg.version.clone(),
+                    source: pkg.source.clone(),
+                    checksum,
+                },
+            );
+        }
+        fs::create_dir_all(&staging)
+            .map_err(|e| format!("Failed to create {}: {}", staging.display(), e))?;
+        index.save(&staging)?;
+
+        if vendor_dir.exists() {
+            fs::remove_dir_all(&vendor_dir)
+                .map_err(|e| format!("Failed to remove old {}: {}", vendor_dir.display(), e))?;
+        }
+        fs::rename(&staging, &vendor_dir)
+            .map_err(|e| format!("Failed to create {}: {}", vendor_dir.display(), e))?;
+
+        let count = index.packages.len();
+        self.vendor = Some(index);
+        Ok(count)
+    }
+
+    /// vendor/<name>, if `knull vendor` put a copy there that `wanted` accepts.
+    /// Errors if the copy no longer matches its checksum.
+    fn vendored(
+        &self,
+        name: &str,
+        wanted: impl Fn(&VendoredPackage) -> bool,
+    ) -> Result<Option<PathBuf>, String> {
+        let pkg = match self.vendor.as_ref().and_then(|v| v.packages.get(name)) {
+            Some(pkg) if wanted(pkg) => pkg,
+            _ => return Ok(None),
+        };
+        let dir = vendor::vendor_dir(&self.root_path).join(name);
+        let checksum = generate_checksum(&dir)?;
+        if checksum != pkg.checksum {
+            return Err(format!(
+                "{} does not match its checksum in {}; run `knull vendor` again",
+                dir.display(),
+                vendor::INDEX_NAME
+            ));
+        }
+        if let Some(lockfile) = &self.lockfile {
+            if lockfile
+                .get_package(name)
+                .is_some_and(|p| p.version == pkg.version && p.source == pkg.source)
+            {
+                lockfile.verify_checksum(name, &checksum)?;
+            }
+        }
+        Ok(Some(dir))
+    }
+
+    /// Error if `--locked` was given and `lockfile` differs from knull.lock
+    fn check_locked(&self, lockfile: &Lockfile) -> Result<(), String> {
+        if !self.mode.locked {
+            return Ok(());
+        }
+        let on_disk = match Lockfile::load_from_project(&self.root_path) {
+            Ok(on_disk) => on_disk,
+            Err(_) => {
+                return Err(format!(
+                    "{} is missing and --locked forbids creating it",
+                    LOCKFILE_NAME
+                ))
+            }
+        };
+        if on_disk == *lockfile {
+            return Ok(());
+        }
+
+        let mut changes = Vec::new();
+        for pkg in &lockfile.packages {
+            match on_disk.get_package(&pkg.name) {
+                None => changes.push(format!("  add {} {}", pkg.name, pkg.version)),
+                Some(old) if old != pkg => changes.push(format!(
+                    "  update {} {} -> {}",
+                    pkg.name, old.version, pkg.version
+                )),
+                Some(_) => {}
+            }
+        }
+        for old in &on_disk.packages {
+            if !lockfile.contains(&old.name) {
+                changes.push(format!("  remove {} {}", old.name, old.version));
+            }
+        }
+        Err(format!(
+            "{} needs to change, but --locked forbids it:\n{}",
+            LOCKFILE_NAME,
+            changes.join("\n")
+        ))
+    }
+
+    /// Fetch a package exactly as the lockfile records it, checking it against its checksum
+    fn fetch_locked(&self, pkg: &LockedPackage) -> Result<PathBuf, String> {
+        if let Some(path) = pkg.source.strip_prefix("path+") {
+            let path = PathBuf::from(path);
+            return if path.is_dir() {
+                Ok(path)
+            } else {
+                Err(format!(
+                    "Path dependency {} not found at {}",
+                    pkg.name,
+                    path.display()
+                ))
+            };
+        }
+        if let Some((url, reference, commit)) = git::parse_source(&pkg.source) {
+            let (dir, _) = self.checkout_git(&pkg.name, &url, &reference, Some(&commit))?;
+            return Ok(dir);
+        }
+        let dir = self.fetch_package(&pkg.name, &pkg.version)?;
+        if let Some(lockfile) = &self.lockfile {
+            if lockfile.contains(&pkg.name) {
+                lockfile.verify_checksum(&pkg.name, &generate_checksum(&dir)?)?;
+            }
+        }
+        Ok(dir)
+    }
+
+    /// Where a locked package is on disk, if it has been fetched
+    fn locked_dir(&self, pkg: &LockedPackage) -> Option<PathBuf> {
+        let vendored = self.vendor.as_ref().and_then(|v| v.packages.get(&pkg.name));
+        let dir = if vendored.is_some_and(|v| v.version == pkg.version && v.source == pkg.source) {
+            Some(vendor::vendor_dir(&self.root_path).join(&pkg.name))
+        } else if let Some(path) = pkg.source.strip_prefix("path+") {
+            Some(PathBuf::from(path))
+        } else if let Some((_, _, commit)) = git::parse_source(&pkg.source) {
+            Some(git::checkout_dir(&self.cache_dir, &pkg.name, &commit))
+        } else {
+            crate::pkg::local_registry::installed_path(&pkg.name, &pkg.version)
+                .or_else(|| crate::pkg::http_registry::cached_path(&pkg.name, &pkg.version))
+        };
+        dir.filter(|dir| dir.is_dir())
+    }
+
+    /// The locked dependency graph, drawn as a tree
+    pub fn tree(&mut self) -> Result<String, String> {
+        self.ensure_locked()?;
+        let mut roots: Vec<String> = self.manifest.dependencies.keys().cloned().collect();
+        roots.sort();
+        let label = format!(
+            "{} {}",
+            self.manifest.package.name, self.manifest.package.version
+        );
+        Ok(self
+            .lockfile
+            .clone()
+            .unwrap_or_default()
+            .tree(&label, &roots))
+    }
+
+    /// The file `knull run` runs when it is not given one
+    pub fn entry_path(&self) -> PathBuf {
+        self.root_path.join(&self.manifest.package.entry)
+    }
+
+    /// Fetch single package - path and git dependencies from where the manifest says,
+    /// others from local packages first, then vendor/, then the HTTP registry
     pub fn fetch_package(&self, name: &str, version: &str) -> Result<PathBuf, String> {
+        match self.manifest.dependencies.get(name) {
+            Some(Dependency::Path(path)) => return self.fetch_path(name, &self.root_path, path),
+            Some(Dependency::Git { url, reference }) => {
+                return self.fetch_git(name, url, reference).map(|(dir, _)| dir)
+            }
+            _ => {}
+        }
+
         // Try local packages directory first
         if let Some(packages_dir) = get_local_packages_dir() {
             let package_path = packages_dir.join(name);
@@ -359,6 +724,13 @@ knull.lock
             }
         }
 
+        if let Some(dir) = self.vendored(name, |v| {
+            v.version == version && v.source.starts_with("registry+")
+        })? {
+            println!("  {} {} (vendored)", name, version);
+            return Ok(dir);
+        }
+
         // Try local registry
         if let Ok(path) = crate::pkg::local_registry::fetch_from_local(name, version) {
             println!("  {} {} (from local registry)", name, version);
@@ -366,7 +738,152 @@ knull.lock
         }
 
         // Fall back to HTTP registry
-        crate::pkg::http_registry::fetch_from_registry(name, version)
+        let (dir, signature) = if self.mode.offline {
+            crate::pkg::http_registry::cached(name, version)?.ok_or_else(|| {
+                format!(
+                    "{} {} is not vendored or cached, and --offline forbids downloading it",
+                    name, version
+                )
+            })?
+        } else {
+            crate::pkg::http_registry::fetch_from_registry(name, version)?
+        };
+        self.trust(name, &signature)?;
+        Ok(dir)
+    }
+
+    /// Trust on first use: the first key a registry package is signed with is recorded in
+    /// knull.keys, and later fetches must be signed with the same key
+    fn trust(&self, name: &str, signature: &PackageSignature) -> Result<(), String> {
+        let mut keyring = Keyring::load(&self.root_path)?;
+        match keyring.keys.get(name) {
+            Some(key) if *key == signature.public_key => Ok(()),
+            Some(key) => Err(format!(
+                "{} is signed with key {}, but {} trusts only {} for it.\n\
+                 If its publisher changed keys, remove {} from {} to trust the new one.",
+                name, signature.public_key, KEYRING_NAME, key, name, KEYRING_NAME
+            )),
+            None if self.mode.locked => Err(format!(
+                "{} has no key in {}, and --locked forbids trusting a new one",
+                name, KEYRING_NAME
+            )),
+            None => {
+                keyring
+                    .keys
+                    .insert(name.to_string(), signature.public_key.clone());
+                keyring.save(&self.root_path)?;
+                println!(
+                    "  Trusting key {} for {} (recorded in {})",
+                    signature.public_key, name, KEYRING_NAME
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// Path dependency `name`, at `path` relative to `base`
+    fn fetch_path(&self, name: &str, base: &Path, path: &Path) -> Result<PathBuf, String> {
+        let dir = base.join(path);
+        let dir = dir.canonicalize().map_err(|e| {
+            format!(
+                "Path dependency {} not found at {}: {}",
+                name,
+                dir.display(),
+                e
+            )
+        })?;
+        println!("  {} (from {})", name, dir.display());
+        Ok(dir)
+    }
+
+    /// Git dependency `name`, at its locked commit if the lockfile has one for this reference.
+    /// Returns the checkout and its commit.
+    fn fetch_git(
+        &self,
+        name: &str,
+        url: &str,
+        reference: &GitReference,
+    ) -> Result<(PathBuf, String), String> {
+        let locked = self
+            .lockfile
+            .as_ref()
+            .and_then(|l| l.get_package(name))
+            .and_then(|p| git::locked_commit(&p.source, url, reference));
+        self.checkout_git(name, url, reference, locked)
+    }
+
+    /// Git dependency `name` at commit `locked`, or wherever `reference` points if unlocked.
+    /// Uses vendor/ or an existing checkout before running git.
+    fn checkout_git(
+        &self,
+        name: &str,
+        url: &str,
+        reference: &GitReference,
+        locked: Option<&str>,
+    ) -> Result<(PathBuf, String), String> {
+        if let Some(commit) = locked {
+            let source = git::source(url, reference, commit);
+            if let Some(dir) = self.vendored(name, |v| v.source == source)? {
+                println!("  {} {} (vendored)", name, &commit[..8.min(commit.len())]);
+                return Ok((dir, commit.to_string()));
+            }
+        }
+        let (dir, commit) = match locked {
+            Some(commit) if self.mode.offline => {
+                let dir = git::checkout_dir(&self.cache_dir, name, commit);
+                if !dir.is_dir() {
+                    return Err(format!(
+                        "{} ({}) is not vendored or cached, and --offline forbids fetching it",
+                        name, url
+                    ));
+                }
+                (dir, commit.to_string())
+            }
+            None if self.mode.offline => {
+                return Err(format!(
+                    "{} is not locked to a commit, and --offline forbids looking up {} in {}",
+                    name, reference, url
+                ))
+            }
+            _ => git::checkout(&self.cache_dir, name, url, reference, locked)?,
+        };
+        println!(
+            "  {} {} (from {})",
+            name,
+            &commit[..8.min(commit.len())],
+            url
+        );
+        Ok((dir, commit))
+    }
+
+    /// Forget the locked version of `name`, so the next resolve picks it afresh
+    fn unlock(&mut self, name: &str) {
+        if let Some(ref mut lockfile) = self.lockfile {
+            lockfile.remove_package(name);
+        }
+    }
+
+    /// Directory holding an already-installed copy of dependency `name`,
+    /// direct or locked as a dependency of one. Only looks on disk; never downloads.
+    pub fn installed_dependency(&self, name: &str) -> Option<PathBuf> {
+        let dep = self.manifest.dependencies.get(name);
+        if dep.is_some() {
+            let vendored = self.root_path.join("packages").join(name);
+            if vendored.is_dir() {
+                return Some(vendored);
+            }
+        }
+        if let Some(Dependency::Path(path)) = dep {
+            return Some(self.root_path.join(path)).filter(|dir| dir.is_dir());
+        }
+        if let Some(locked) = self.lockfile.as_ref().and_then(|l| l.get_package(name)) {
+            return self.locked_dir(locked);
+        }
+
+        // Not locked yet: only an exact version can be found
+        let version = dep?.version()?;
+        crate::pkg::local_registry::installed_path(name, version)
+            .or_else(|| crate::pkg::http_registry::cached_path(name, version))
     }
 
     /// Resolve version with semver constraint
@@ -388,34 +905,72 @@ knull.lock
         crate::pkg::http_registry::resolve_version(name, constraint)
     }
 
-    /// Update lockfile with current dependencies
+    /// Resolve the whole dependency graph and write it to the lockfile
     pub fn update_lockfile(&mut self) -> Result<(), String> {
-        let mut resolved = Vec::new();
-
-        for (name, constraint) in &self.manifest.dependencies {
-            // Fetch and resolve the package
-            let version = self
-                .resolve_version(name, constraint)
-                .or_else(|_| Ok::<String, String>(constraint.clone()))?;
-
-            let package_path = self.fetch_package(name, &version)?;
+        let mut source = FetchedPackages {
+            manager: self,
+            paths: HashMap::new(),
+            pinned: HashMap::new(),
+        };
+        let mut constraints = HashMap::new();
+        for (name, dep) in &self.manifest.dependencies {
+            let constraint = source.constraint(name, dep, &self.root_path)?;
+            constraints.insert(name.clone(), constraint);
+        }
+        let selected = resolver::resolve(&self.manifest.package.name, &constraints, &mut source)?;
+        let FetchedPackages {
+            mut paths, pinned, ..
+        } = source;
 
-            // Generate checksum
-            let checksum = crate::pkg::lockfile::generate_checksum(&package_path).ok();
+        let mut resolved = Vec::new();
+        for (name, pick) in selected {
+            // Path and git dependencies are pinned by their source instead of a checksum
+            if let Some(pin) = pinned.get(&name) {
+                resolved.push(ResolvedDep {
+                    name,
+                    version: pick.version,
+                    source: pin.source.clone(),
+                    checksum: None,
+                    dependencies: pick.dependencies,
+                });
+                continue;
+            }
 
-            // Get transitive dependencies
-            let deps = Vec::new(); // Simplified for now
+            let package_path = match paths.remove(&(name.clone(), pick.version.clone())) {
+                Some(path) => path,
+                None => self.fetch_package(&name, &pick.version)?,
+            };
+
+            // Generate checksum, which must not change while the version stays locked
+            let checksum = generate_checksum(&package_path)?;
+            let source = package_source(&package_path);
+            if let Some(lockfile) = &self.lockfile {
+                if lockfile
+                    .get_package(&name)
+                    .is_some_and(|p| p.version == pick.version && p.source == source)
+                {
+                    lockfile.verify_checksum(&name, &checksum)?;
+                }
+            }
 
             resolved.push(ResolvedDep {
-                name: name.clone(),
-                version: version.clone(),
-                source: format!("registry+https://registry.knull-lang.dev"),
-                checksum,
-                dependencies: deps,
+                name,
+                version: pick.version,
+                source,
+                checksum: Some(checksum),
+                dependencies: pick.dependencies,
             });
         }
 
         let lockfile = Lockfile::generate(&self.manifest, &resolved);
+        if let Some(cycle) = detect_cycles(&build_dependency_graph(&lockfile)) {
+            return Err(format!(
+                "Dependency cycle: {} -> {}",
+                cycle.join(" -> "),
+                cycle[0]
+            ));
+        }
+        self.check_locked(&lockfile)?;
         lockfile.save(&self.root_path.join(LOCKFILE_NAME))?;
 
         self.lockfile = Some(lockfile);
@@ -445,8 +1000,24 @@ knull.lock
         // Create package archive
         let archive_path = self.create_package_archive()?;
 
+        // Sign the checksum of the files as they will be downloaded
+        let checksum = crate::pkg::http_registry::archive_checksum(&archive_path)?;
+        let key = signing::publisher_key()?;
+        let signature = PackageSignature::sign(
+            &key,
+            &self.manifest.package.name,
+            &self.manifest.package.version,
+            &checksum,
+        );
+        println!("Signed {} with key {}", checksum, signature.public_key);
+
         // Upload to registry
-        crate::pkg::http_registry::publish_to_registry(&self.manifest, &archive_path, token)?;
+        crate::pkg::http_registry::publish_to_registry(
+            &self.manifest,
+            &archive_path,
+            &signature,
+            token,
+        )?;
 
         // Clean up archive
         let _ = fs::remove_file(&archive_path);
@@ -640,7 +1211,7 @@ knull.lock
             let entry = entry.map_err(|e| e.to_string())?;
             let path = entry.path();
 
-            if path.extension().map_or(false, |e| e == "knull") {
+            if path.extension().is_some_and(|e| e == "knull") {
                 print!(
                     "Running {}... ",
                     path.file_name().unwrap().to_string_lossy()
@@ -681,6 +1252,157 @@ knull.lock
     }
 }
 
+/// The resolver's view of the places `fetch_package` looks.
+/// Remembers where each package it read was fetched to.
+struct FetchedPackages<'a> {
+    manager: &'a PackageManager,
+    paths: HashMap<(String, String), PathBuf>,
+    /// Path and git dependencies met so far
+    pinned: HashMap<String, Pinned>,
+}
+
+/// A path or git dependency: one version, found in one place
+struct Pinned {
+    dir: PathBuf,
+    version: String,
+    /// Where it came from; git sources include the commit
+    source: String,
+    /// `source` without the commit, to tell whether two dependents want the same thing
+    location: String,
+}
+
+impl FetchedPackages<'_> {
+    /// The version constraint the resolver uses for `dep`, declared in the manifest in `base`.
+    /// Path and git dependencies are fetched and pinned, and accept whatever version they have.
+    fn constraint(&mut self, name: &str, dep: &Dependency, base: &Path) -> Result<String, String> {
+        let location = match dep {
+            Dependency::Registry(constraint) => return Ok(constraint.clone()),
+            Dependency::Path(path) => match base.join(path).canonicalize() {
+                Ok(dir) => format!("path+{}", dir.display()),
+                Err(_) => format!("path+{}", base.join(path).display()),
+            },
+            Dependency::Git { url, reference } => git::location(url, reference),
+        };
+        if let Some(pin) = self.pinned.get(name) {
+            if pin.location != location {
+                return Err(format!(
+                    "{} is required from both {} and {}",
+                    name, pin.location, location
+                ));
+            }
+            return Ok("*".to_string());
+        }
+
+        let (dir, source) = match dep {
+            Dependency::Git { url, reference } => {
+                let (dir, commit) = self.manager.fetch_git(name, url, reference)?;
+                (dir, git::source(url, reference, &commit))
+            }
+            Dependency::Path(path) => {
+                (self.manager.fetch_path(name, base, path)?, location.clone())
+            }
+            Dependency::Registry(_) => unreachable!(),
+        };
+        let version = PackageManifest::load(&dir.join("knull.toml"))
+            .map_err(|e| format!("{} ({}): {}", name, dir.display(), e))?
+            .package
+            .version;
+        self.pinned.insert(
+            name.to_string(),
+            Pinned {
+                dir,
+                version,
+                source,
+                location,
+            },
+        );
+        Ok("*".to_string())
+    }
+}
+
+impl PackageSource for FetchedPackages<'_> {
+    fn preferred(&mut self, name: &str) -> Option<String> {
+        let locked = self.manager.lockfile.as_ref()?.get_package(name)?;
+        Some(locked.version.clone())
+    }
+
+    fn versions(&mut self, name: &str) -> Result<Vec<String>, String> {
+        if let Some(pin) = self.pinned.get(name) {
+            return Ok(vec![pin.version.clone()]);
+        }
+
+        // A package in the local packages directory is used whatever version is asked for
+        if let Some(packages_dir) = get_local_packages_dir() {
+            let manifest_path = packages_dir.join(name).join("knull.toml");
+            if let Ok(manifest) = PackageManifest::load(&manifest_path) {
+                return Ok(vec![manifest.package.version]);
+            }
+        }
+
+        let mut versions = crate::pkg::local_registry::list_local_versions(name)?;
+        if let Some(vendored) = self
+            .manager
+            .vendor
+            .as_ref()
+            .and_then(|v| v.packages.get(name))
+        {
+            if vendored.source.starts_with("registry+") {
+                versions.push(vendored.version.clone());
+            }
+        }
+        if self.manager.mode.offline {
+            versions.extend(crate::pkg::http_registry::cached_versions(name));
+            return Ok(versions);
+        }
+        match crate::pkg::http_registry::list_versions(name) {
+            Ok(remote) => versions.extend(remote),
+            Err(e) if versions.is_empty() => return Err(e),
+            Err(_) => {}
+        }
+        Ok(versions)
+    }
+
+    fn dependencies(
+        &mut self,
+        name: &str,
+        version: &str,
+    ) -> Result<HashMap<String, String>, String> {
+        let path = match self.pinned.get(name) {
+            Some(pin) => pin.dir.clone(),
+            None => self.manager.fetch_package(name, version)?,
+        };
+        self.paths
+            .insert((name.to_string(), version.to_string()), path.clone());
+
+        // Registry copies carry their manifest as package.toml
+        let deps = match ["knull.toml", "package.toml"]
+            .iter()
+            .map(|file| path.join(file))
+            .find(|p| p.exists())
+        {
+            Some(manifest_path) => PackageManifest::load(&manifest_path)?.dependencies,
+            None => HashMap::new(),
+        };
+
+        let mut constraints = HashMap::new();
+        for (dep_name, dep) in &deps {
+            let constraint = self.constraint(dep_name, dep, &path)?;
+            constraints.insert(dep_name.clone(), constraint);
+        }
+        Ok(constraints)
+    }
+}
+
+/// Lockfile `source` of a package fetched to `path`
+fn package_source(path: &Path) -> String {
+    match get_local_packages_dir() {
+        Some(packages_dir) if path.starts_with(&packages_dir) => {
+            format!("path+{}", path.display())
+        }
+        _ => "registry+https://registry.knull-lang.dev".to_string(),
+    }
+}
+
 /// Find nearest manifest from current directory
 pub fn find_nearest_manifest() -> Option<PathBuf> {
     let mut current = std::env::current_dir().ok()?;
@@ -698,3 +1420,266 @@ pub fn find_nearest_manifest() -> Option<PathBuf> {
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn manifest(name: &str, deps: &str) -> String {
+        format!(
+            "[package]\nname = \"{}\"\nversion = \"1.0.0\"\nedition = \"2024\"\nentry = \"src/main.knull\"\n\n[dependencies]\n{}",
+            name, deps
+        )
+    }
+
+    fn deps(toml: &str) -> Result<HashMap<String, Dependency>, String> {
+        toml::from_str::<PackageManifest>(&manifest("app", toml))
+            .map(|m| m.dependencies)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Commit everything in `repo`; returns the commit
+    fn commit(repo: &Path, message: &str) -> String {
+        let git = |args: &[&str]| {
+            let output = Command::new("git")
+                .arg("-C")
+                .arg(repo)
+                .args([
+                    "-c",
+                    "user.name=knull",
+                    "-c",
+                    "user.email=knull@example.com",
+                ])
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "{:?}", output);
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        };
+        if !repo.join(".git").exists() {
+            git(&["init", "--quiet", "--initial-branch=main"]);
+        }
+        git(&["add", "."]);
+        git(&["commit", "--quiet", "-m", message]);
+        git(&["rev-parse", "HEAD"])
+    }
+
+    #[test]
+    fn test_dependency_forms() {
+        let parsed = deps(
+            r#"json = "^1.0"
+http = { version = "~1.2" }
+shared = { path = "../shared" }
+util = { git = "file:///repos/util", tag = "v1" }
+edge = { git = "file:///repos/edge" }
+"#,
+        )
+        .unwrap();
+        assert_eq!(parsed["json"], Dependency::Registry("^1.0".to_string()));
+        assert_eq!(parsed["http"], Dependency::Registry("~1.2".to_string()));
+        assert_eq!(
+            parsed["shared"],
+            Dependency::Path(PathBuf::from("../shared"))
+        );
+        assert_eq!(
+            parsed["util"],
+            Dependency::Git {
+                url: "file:///repos/util".to_string(),
+                reference: GitReference::Tag("v1".to_string()),
+            }
+        );
+        assert_eq!(parsed["edge"].to_string(), "git file:///repos/edge");
+
+        // Saving keeps every form
+        let mut m = PackageManifest::new("app");
+        m.dependencies = parsed.clone();
+        let saved = toml::to_string_pretty(&m).unwrap();
+        let reloaded: PackageManifest = toml::from_str(&saved).unwrap();
+        assert_eq!(reloaded.dependencies, parsed);
+
+        let err = deps(r#"x = { path = "a", git = "b" }"#).unwrap_err();
+        assert!(
+            err.contains("only one of `version`, `path` and `git`"),
+            "{}",
+            err
+        );
+        let err = deps(r#"x = { git = "b", tag = "v1", branch = "main" }"#).unwrap_err();
+        assert!(
+            err.contains("only one of `rev`, `tag` and `branch`"),
+            "{}",
+            err
+        );
+        let err = deps(r#"x = { path = "a", tag = "v1" }"#).unwrap_err();
+        assert!(err.contains("only apply to `git`"), "{}", err);
+        let err = deps(r#"x = { git = "b", tga = "v1" }"#).unwrap_err();
+        assert!(err.contains("unknown field `tga`"), "{}", err);
+    }
+
+    #[test]
+    fn test_lock_path_and_git_dependencies() {
+        let temp = TempDir::new().unwrap();
+        let util = temp.path().join("util");
+        fs::create_dir(&util).unwrap();
+        fs::write(util.join("knull.toml"), manifest("util", "")).unwrap();
+        let first = commit(&util, "one");
+        let url = format!("file://{}", util.display());
+        let git_dep = format!("util = {{ git = \"{}\", branch = \"main\" }}\n", url);
+
+        let shared = temp.path().join("shared");
+        fs::create_dir(&shared).unwrap();
+        fs::write(shared.join("knull.toml"), manifest("shared", &git_dep)).unwrap();
+
+        let app = temp.path().join("app");
+        fs::create_dir(&app).unwrap();
+        let app_deps = format!("shared = {{ path = \"../shared\" }}\n{}", git_dep);
+        fs::write(app.join("knull.toml"), manifest("app", &app_deps)).unwrap();
+
+        let mut pm = PackageManager::new(app.clone()).unwrap();
+        pm.cache_dir = temp.path().join("cache");
+        pm.update_lockfile().unwrap();
+
+        let lock = Lockfile::load_from_project(&app).unwrap();
+        let shared_lock = lock.get_package("shared").unwrap();
+        assert!(
+            shared_lock.source.starts_with("path+"),
+            "{}",
+            shared_lock.source
+        );
+        assert_eq!(shared_lock.dependencies, Some(vec!["util".to_string()]));
+        let util_lock = lock.get_package("util").unwrap();
+        assert_eq!(
+            util_lock.source,
+            format!("git+{}?branch=main#{}", url, first)
+        );
+        assert_eq!(util_lock.version, "1.0.0");
+        assert!(pm
+            .installed_dependency("util")
+            .unwrap()
+            .join("knull.toml")
+            .exists());
+
+        // New commits on the branch wait for an update
+        fs::write(util.join("lib.knull"), "fn f() {}").unwrap();
+        let second = commit(&util, "two");
+        pm.update_lockfile().unwrap();
+        assert!(pm
+            .lockfile
+            .as_ref()
+            .unwrap()
+            .packages
+            .iter()
+            .any(|p| p.source.ends_with(&first)));
+        pm.update_package("util").unwrap();
+        let lock = Lockfile::load_from_project(&app).unwrap();
+        assert!(lock.get_package("util").unwrap().source.ends_with(&second));
+        assert!(pm
+            .installed_dependency("util")
+            .unwrap()
+            .join("lib.knull")
+            .exists());
+
+        let tree = pm.tree().unwrap();
+        assert!(
+            tree.starts_with("app 1.0.0\n├── shared 1.0.0 ("),
+            "{}",
+            tree
+        );
+        assert!(tree.contains(&format!("util 1.0.0 ({}?branch=main#{}", url, &second[..8])));
+
+        // Removing a dependency keeps what the others still need
+        pm.remove_dependency("shared").unwrap();
+        let lock = Lockfile::load_from_project(&app).unwrap();
+        assert!(lock.get_package("shared").is_none());
+        assert!(lock.get_package("util").is_some());
+    }
+
+    #[test]
+    fn test_vendor_offline_and_locked() {
+        let temp = TempDir::new().unwrap();
+        let util = temp.path().join("util");
+        fs::create_dir(&util).unwrap();
+        fs::write(util.join("knull.toml"), manifest("util", "")).unwrap();
+        commit(&util, "one");
+        let url = format!("file://{}", util.display());
+
+        let app = temp.path().join("app");
+        fs::create_dir(&app).unwrap();
+        let app_deps = format!("util = {{ git = \"{}\" }}\n", url);
+        fs::write(app.join("knull.toml"), manifest("app", &app_deps)).unwrap();
+
+        let cache = temp.path().join("cache");
+        let mut pm = PackageManager::new(app.clone()).unwrap();
+        pm.cache_dir = cache.clone();
+        assert_eq!(pm.vendor().unwrap(), 1);
+        let vendored = app.join("vendor").join("util");
+        assert!(vendored.join("knull.toml").exists());
+        assert!(!vendored.join(".git").exists());
+        let index = VendorIndex::load(&app.join("vendor")).unwrap();
+        assert_eq!(
+            index.packages["util"].checksum,
+            generate_checksum(&vendored).unwrap()
+        );
+
+        // Without the cache, offline builds use vendor/
+        fs::remove_dir_all(&cache).unwrap();
+        let offline = FetchMode {
+            offline: true,
+            locked: true,
+        };
+        let mut pm = PackageManager::new(app.clone()).unwrap().with_mode(offline);
+        pm.cache_dir = cache.clone();
+        pm.fetch_dependencies().unwrap();
+        pm.update_lockfile().unwrap();
+        assert_eq!(pm.installed_dependency("util"), Some(vendored.clone()));
+
+        let err = pm.remove_dependency("util").unwrap_err();
+        assert!(err.contains("--locked forbids it"), "{}", err);
+        assert!(err.contains("remove util 1.0.0"), "{}", err);
+        assert!(PackageManifest::load(&app.join("knull.toml"))
+            .unwrap()
+            .dependencies
+            .contains_key("util"));
+
+        fs::write(vendored.join("lib.knull"), "fn f() {}").unwrap();
+        let err = pm.fetch_dependencies().unwrap_err();
+        assert!(err.contains("does not match its checksum"), "{}", err);
+
+        fs::remove_dir_all(app.join("vendor")).unwrap();
+        let mut pm = PackageManager::new(app.clone()).unwrap().with_mode(offline);
+        pm.cache_dir = cache;
+        let err = pm.fetch_dependencies().unwrap_err();
+        assert!(err.contains("--offline forbids"), "{}", err);
+    }
+
+    #[test]
+    fn test_trusts_the_first_key() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("knull.toml"), manifest("app", "")).unwrap();
+        let pm = PackageManager::new(temp.path().to_path_buf()).unwrap();
+        let sign = |seed: u8| {
+            let key = ed25519_dalek::SigningKey::from_bytes(&[seed; 32]);
+            PackageSignature::sign(&key, "json", "1.0.0", "sha256:abc")
+        };
+
+        pm.trust("json", &sign(1)).unwrap();
+        let keyring = Keyring::load(temp.path()).unwrap();
+        assert_eq!(keyring.keys["json"], sign(1).public_key);
+        pm.trust("json", &sign(1)).unwrap();
+
+        let err = pm.trust("json", &sign(2)).unwrap_err();
+        assert!(err.contains("trusts only"), "{}", err);
+        assert_eq!(Keyring::load(temp.path()).unwrap(), keyring);
+
+        let locked = FetchMode {
+            offline: false,
+            locked: true,
+        };
+        let pm = PackageManager::new(temp.path().to_path_buf())
+            .unwrap()
+            .with_mode(locked);
+        pm.trust("json", &sign(1)).unwrap();
+        let err = pm.trust("http", &sign(1)).unwrap_err();
+        assert!(err.contains("--locked forbids trusting"), "{}", err);
+    }
+}
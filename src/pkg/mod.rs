@@ -6,18 +6,24 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+pub mod config;
+pub mod git;
 pub mod http_registry;
 pub mod local_registry;
 pub mod lockfile;
 pub mod manager;
+pub mod resolver;
 pub mod semver;
+pub mod signing;
+pub mod vendor;
 
 // Re-export commonly used items
 #[allow(unused_imports)]
 pub use lockfile::{LockedPackage, Lockfile, ResolvedDep};
 #[allow(unused_imports)]
 pub use manager::{
-    find_nearest_manifest, BuildConfig, PackageInfo, PackageManager, PackageManifest,
+    find_nearest_manifest, BuildConfig, Dependency, FetchMode, PackageInfo, PackageManager,
+    PackageManifest,
 };
 #[allow(unused_imports)]
 pub use semver::{compare_versions, parse_constraint, parse_version, satisfies};
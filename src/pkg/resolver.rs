@@ -0,0 +1,364 @@
+//! Dependency Resolver
+//! Picks one version of every package in the dependency graph, backtracking on conflicts
+
+use crate::pkg::semver;
+use std::collections::{BTreeMap, HashMap};
+
+/// Give up after trying this many package versions
+const MAX_STEPS: usize = 10_000;
+
+/// Where the resolver learns which versions exist and what they depend on
+pub trait PackageSource {
+    /// Every available version of `name`
+    fn versions(&mut self, name: &str) -> Result<Vec<String>, String>;
+
+    /// The `[dependencies]` of `name@version`
+    fn dependencies(
+        &mut self,
+        name: &str,
+        version: &str,
+    ) -> Result<HashMap<String, String>, String>;
+
+    /// A version of `name` to try before newer ones, such as the one already locked
+    fn preferred(&mut self, _name: &str) -> Option<String> {
+        None
+    }
+}
+
+/// The version picked for a package, and the names of the packages it depends on
+#[derive(Debug, Clone, PartialEq)]
+pub struct Resolved {
+    pub version: String,
+    pub dependencies: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Requirement {
+    constraint: String,
+    by: String,
+}
+
+#[derive(Debug, Clone, Default)]
+struct State {
+    selected: BTreeMap<String, Resolved>,
+    requirements: BTreeMap<String, Vec<Requirement>>,
+}
+
+/// Resolve `dependencies` of the package `root` and everything they depend on
+pub fn resolve(
+    root: &str,
+    dependencies: &HashMap<String, String>,
+    source: &mut impl PackageSource,
+) -> Result<BTreeMap<String, Resolved>, String> {
+    let mut state = State::default();
+    for (name, constraint) in dependencies {
+        state.requirements.insert(
+            name.clone(),
+            vec![Requirement {
+                constraint: constraint.clone(),
+                by: root.to_string(),
+            }],
+        );
+    }
+
+    let mut resolver = Resolver {
+        source,
+        versions: HashMap::new(),
+        steps: 0,
+        conflict: None,
+    };
+    match resolver.solve(state)? {
+        Some(state) => Ok(state.selected),
+        None => Err(format!(
+            "Failed to resolve dependencies of {}: {}",
+            root,
+            resolver.conflict.unwrap_or_default()
+        )),
+    }
+}
+
+struct Resolver<'a, S: PackageSource> {
+    source: &'a mut S,
+    versions: HashMap<String, Vec<String>>,
+    steps: usize,
+    /// Explanation of the first conflict hit, reported if no solution exists
+    conflict: Option<String>,
+}
+
+impl<S: PackageSource> Resolver<'_, S> {
+    /// `Ok(None)` when no choice of versions below `state` works
+    fn solve(&mut self, state: State) -> Result<Option<State>, String> {
+        let name = match state
+            .requirements
+            .keys()
+            .find(|name| !state.selected.contains_key(*name))
+        {
+            Some(name) => name.clone(),
+            None => return Ok(Some(state)),
+        };
+
+        let requirements = &state.requirements[&name];
+        let available = self.versions(&name)?;
+        let mut candidates: Vec<String> = available
+            .iter()
+            .filter(|v| {
+                requirements
+                    .iter()
+                    .all(|r| semver::satisfies(v, &r.constraint))
+            })
+            .cloned()
+            .collect();
+        if let Some(preferred) = self.source.preferred(&name) {
+            if let Some(i) = candidates.iter().position(|v| *v == preferred) {
+                let version = candidates.remove(i);
+                candidates.insert(0, version);
+            }
+        }
+        if candidates.is_empty() {
+            self.conflict(&name, requirements, &available, None);
+            return Ok(None);
+        }
+
+        'candidates: for version in candidates {
+            self.steps += 1;
+            if self.steps > MAX_STEPS {
+                return Err(format!(
+                    "Gave up resolving dependencies after trying {} versions",
+                    MAX_STEPS
+                ));
+            }
+
+            let deps = self.source.dependencies(&name, &version)?;
+            let mut dep_names: Vec<String> = deps.keys().cloned().collect();
+            dep_names.sort();
+
+            let mut next = state.clone();
+            let by = format!("{} {}", name, version);
+            for dep in &dep_names {
+                let reqs = next.requirements.entry(dep.clone()).or_default();
+                reqs.push(Requirement {
+                    constraint: deps[dep].clone(),
+                    by: by.clone(),
+                });
+                if let Some(chosen) = next.selected.get(dep) {
+                    if !semver::satisfies(&chosen.version, &deps[dep]) {
+                        let reqs = next.requirements[dep].clone();
+                        let chosen = chosen.version.clone();
+                        let available = self.versions(dep)?;
+                        self.conflict(dep, &reqs, &available, Some(&chosen));
+                        continue 'candidates;
+                    }
+                }
+            }
+
+            next.selected.insert(
+                name.clone(),
+                Resolved {
+                    version,
+                    dependencies: dep_names,
+                },
+            );
+            if let Some(solution) = self.solve(next)? {
+                return Ok(Some(solution));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Available versions of `name`, newest first
+    fn versions(&mut self, name: &str) -> Result<Vec<String>, String> {
+        if let Some(versions) = self.versions.get(name) {
+            return Ok(versions.clone());
+        }
+        let mut versions = self.source.versions(name)?;
+        versions.sort_by(|a, b| semver::compare_versions(b, a));
+        versions.dedup();
+        self.versions.insert(name.to_string(), versions.clone());
+        Ok(versions)
+    }
+
+    fn conflict(
+        &mut self,
+        name: &str,
+        requirements: &[Requirement],
+        available: &[String],
+        chosen: Option<&str>,
+    ) {
+        if self.conflict.is_some() {
+            return;
+        }
+
+        let mut message = format!("no version of {} satisfies every requirement", name);
+        for req in requirements {
+            message.push_str(&format!(
+                "\n  {} requires {} {}",
+                req.by, name, req.constraint
+            ));
+        }
+        if let Some(chosen) = chosen {
+            message.push_str(&format!("\n  {} {} was already selected", name, chosen));
+        }
+        if available.is_empty() {
+            message.push_str(&format!("\n  no versions of {} were found", name));
+        } else {
+            message.push_str(&format!("\n  available versions: {}", available.join(", ")));
+        }
+        self.conflict = Some(message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Deps = Vec<(&'static str, &'static str)>;
+
+    /// name -> [(version, dependencies)]
+    struct Index(HashMap<&'static str, Vec<(&'static str, Deps)>>);
+
+    impl PackageSource for Index {
+        fn versions(&mut self, name: &str) -> Result<Vec<String>, String> {
+            Ok(self
+                .0
+                .get(name)
+                .map(|vs| vs.iter().map(|(v, _)| v.to_string()).collect())
+                .unwrap_or_default())
+        }
+
+        fn dependencies(
+            &mut self,
+            name: &str,
+            version: &str,
+        ) -> Result<HashMap<String, String>, String> {
+            let (_, deps) = self.0[name].iter().find(|(v, _)| *v == version).unwrap();
+            Ok(deps
+                .iter()
+                .map(|(n, c)| (n.to_string(), c.to_string()))
+                .collect())
+        }
+    }
+
+    fn root(deps: &[(&str, &str)]) -> HashMap<String, String> {
+        deps.iter()
+            .map(|(n, c)| (n.to_string(), c.to_string()))
+            .collect()
+    }
+
+    fn versions(resolved: &BTreeMap<String, Resolved>) -> Vec<(String, String)> {
+        resolved
+            .iter()
+            .map(|(n, r)| (n.clone(), r.version.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_resolves_transitive_dependencies() {
+        let mut index = Index(HashMap::from([
+            (
+                "http",
+                vec![("1.2.0", vec![("json", "^1.0")]), ("1.0.0", vec![])],
+            ),
+            (
+                "json",
+                vec![("1.0.0", vec![]), ("1.4.0", vec![]), ("2.0.0", vec![])],
+            ),
+        ]));
+        let resolved = resolve("app", &root(&[("http", "^1.0")]), &mut index).unwrap();
+        assert_eq!(
+            versions(&resolved),
+            vec![
+                ("http".to_string(), "1.2.0".to_string()),
+                ("json".to_string(), "1.4.0".to_string()),
+            ]
+        );
+        assert_eq!(resolved["http"].dependencies, vec!["json".to_string()]);
+    }
+
+    #[test]
+    fn test_backtracks_to_a_compatible_version() {
+        // http 1.2 needs json 2, which crypto cannot use; http 1.1 is fine
+        let mut index = Index(HashMap::from([
+            (
+                "http",
+                vec![
+                    ("1.2.0", vec![("json", "^2.0")]),
+                    ("1.1.0", vec![("json", "^1.0")]),
+                ],
+            ),
+            ("crypto", vec![("1.0.0", vec![("json", "~1.3")])]),
+            (
+                "json",
+                vec![("1.3.5", vec![]), ("1.4.0", vec![]), ("2.0.0", vec![])],
+            ),
+        ]));
+        let resolved = resolve(
+            "app",
+            &root(&[("crypto", "^1.0"), ("http", "^1.0")]),
+            &mut index,
+        )
+        .unwrap();
+        assert_eq!(resolved["http"].version, "1.1.0");
+        assert_eq!(resolved["json"].version, "1.3.5");
+    }
+
+    #[test]
+    fn test_prefers_locked_versions() {
+        /// `Index` with some versions locked
+        struct Locked(Index, HashMap<&'static str, &'static str>);
+
+        impl PackageSource for Locked {
+            fn versions(&mut self, name: &str) -> Result<Vec<String>, String> {
+                self.0.versions(name)
+            }
+
+            fn dependencies(
+                &mut self,
+                name: &str,
+                version: &str,
+            ) -> Result<HashMap<String, String>, String> {
+                self.0.dependencies(name, version)
+            }
+
+            fn preferred(&mut self, name: &str) -> Option<String> {
+                self.1.get(name).map(|v| v.to_string())
+            }
+        }
+
+        let index = || {
+            Index(HashMap::from([(
+                "json",
+                vec![("1.0.0", vec![]), ("1.4.0", vec![]), ("2.0.0", vec![])],
+            )]))
+        };
+        let mut locked = Locked(index(), HashMap::from([("json", "1.0.0")]));
+        let resolved = resolve("app", &root(&[("json", "^1.0")]), &mut locked).unwrap();
+        assert_eq!(resolved["json"].version, "1.0.0");
+
+        // A lock the constraints no longer allow is ignored
+        let mut locked = Locked(index(), HashMap::from([("json", "1.0.0")]));
+        let resolved = resolve("app", &root(&[("json", "^2.0")]), &mut locked).unwrap();
+        assert_eq!(resolved["json"].version, "2.0.0");
+    }
+
+    #[test]
+    fn test_explains_conflicts() {
+        let mut index = Index(HashMap::from([
+            ("a", vec![("1.0.0", vec![("c", "^1.0")])]),
+            ("b", vec![("1.0.0", vec![("c", "^2.0")])]),
+            ("c", vec![("1.5.0", vec![]), ("2.0.0", vec![])]),
+        ]));
+        let err = resolve("app", &root(&[("a", "^1.0"), ("b", "^1.0")]), &mut index).unwrap_err();
+        assert!(
+            err.contains("no version of c satisfies every requirement"),
+            "{}",
+            err
+        );
+        assert!(err.contains("a 1.0.0 requires c ^1.0"), "{}", err);
+        assert!(err.contains("b 1.0.0 requires c ^2.0"), "{}", err);
+
+        let err = resolve("app", &root(&[("missing", "^1.0")]), &mut index).unwrap_err();
+        assert!(err.contains("app requires missing ^1.0"), "{}", err);
+        assert!(err.contains("no versions of missing were found"), "{}", err);
+    }
+}
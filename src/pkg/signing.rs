@@ -0,0 +1,184 @@
+//! Package Signing
+//! Publishers sign the checksum of each package they upload with an ed25519 key.
+//! Projects trust the first key they see for a package and record it in knull.keys.
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const KEYRING_NAME: &str = "knull.keys";
+const PUBLISHER_KEY_PATH: &str = ".knull/keys/publisher.key";
+
+/// A publisher's signature over the checksum of `name@version`
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PackageSignature {
+    /// `lockfile::generate_checksum` of the package's files
+    pub checksum: String,
+    /// Hex-encoded ed25519 signature
+    pub signature: String,
+    /// Hex-encoded ed25519 public key
+    pub public_key: String,
+}
+
+impl PackageSignature {
+    pub fn sign(key: &SigningKey, name: &str, version: &str, checksum: &str) -> Self {
+        let signature = key.sign(message(name, version, checksum).as_bytes());
+        PackageSignature {
+            checksum: checksum.to_string(),
+            signature: to_hex(&signature.to_bytes()),
+            public_key: to_hex(key.verifying_key().as_bytes()),
+        }
+    }
+
+    /// Check that the signature is `public_key`'s, over this checksum of `name@version`
+    pub fn verify(&self, name: &str, version: &str) -> Result<(), String> {
+        let invalid = || format!("{}@{} has an invalid signature", name, version);
+        let key: [u8; 32] = from_hex(&self.public_key)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(invalid)?;
+        let signature: [u8; 64] = from_hex(&self.signature)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(invalid)?;
+        let key = VerifyingKey::from_bytes(&key).map_err(|_| invalid())?;
+        key.verify(
+            message(name, version, &self.checksum).as_bytes(),
+            &Signature::from_bytes(&signature),
+        )
+        .map_err(|_| invalid())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        toml::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize signature: {}", e))?;
+        fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+/// What gets signed: the checksum, bound to the package it belongs to
+fn message(name: &str, version: &str, checksum: &str) -> String {
+    format!("knull-package\n{}\n{}\n{}\n", name, version, checksum)
+}
+
+/// The key publishes are signed with: `$KNULL_SIGNING_KEY` (hex), else
+/// ~/.knull/keys/publisher.key, which is created on first use
+pub fn publisher_key() -> Result<SigningKey, String> {
+    let parse = |hex: &str, from: &str| {
+        from_hex(hex.trim())
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .map(|secret| SigningKey::from_bytes(&secret))
+            .ok_or_else(|| format!("{} is not a hex-encoded ed25519 secret key", from))
+    };
+    if let Ok(hex) = std::env::var("KNULL_SIGNING_KEY") {
+        return parse(&hex, "KNULL_SIGNING_KEY");
+    }
+
+    let path = publisher_key_path()?;
+    if path.exists() {
+        let hex = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        return parse(&hex, &path.display().to_string());
+    }
+
+    let mut secret = [0u8; 32];
+    OsRng.fill_bytes(&mut secret);
+    let key = SigningKey::from_bytes(&secret);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    fs::write(&path, to_hex(&secret))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(&path, fs::Permissions::from_mode(0o600));
+    }
+    println!("Created publisher key {}", path.display());
+    Ok(key)
+}
+
+fn publisher_key_path() -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or_else(|| "Could not determine home directory".to_string())?;
+    Ok(home.join(PUBLISHER_KEY_PATH))
+}
+
+/// knull.keys: the public key each registry package of a project was first fetched with
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct Keyring {
+    #[serde(default)]
+    pub keys: BTreeMap<String, String>,
+}
+
+impl Keyring {
+    /// The keyring of the project at `root`; empty if it has none yet
+    pub fn load(root: &Path) -> Result<Self, String> {
+        let path = root.join(KEYRING_NAME);
+        if !path.exists() {
+            return Ok(Keyring::default());
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", KEYRING_NAME, e))?;
+        toml::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", KEYRING_NAME, e))
+    }
+
+    pub fn save(&self, root: &Path) -> Result<(), String> {
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize {}: {}", KEYRING_NAME, e))?;
+        fs::write(root.join(KEYRING_NAME), content)
+            .map_err(|e| format!("Failed to write {}: {}", KEYRING_NAME, e))
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 == 1 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let signed = PackageSignature::sign(&key, "json", "1.0.0", "sha256:abc");
+        signed.verify("json", "1.0.0").unwrap();
+
+        // The signature covers the checksum, name and version
+        let mut tampered = signed.clone();
+        tampered.checksum = "sha256:abd".to_string();
+        assert!(tampered.verify("json", "1.0.0").is_err());
+        assert!(signed.verify("json", "1.0.1").is_err());
+        assert!(signed.verify("jsonx", "1.0.0").is_err());
+
+        // Another key cannot stand in
+        let other = SigningKey::from_bytes(&[8u8; 32]);
+        let mut forged = signed.clone();
+        forged.public_key = to_hex(other.verifying_key().as_bytes());
+        assert!(forged.verify("json", "1.0.0").is_err());
+
+        let mut garbage = signed;
+        garbage.signature = "zz".to_string();
+        let err = garbage.verify("json", "1.0.0").unwrap_err();
+        assert!(err.contains("invalid signature"), "{}", err);
+    }
+}
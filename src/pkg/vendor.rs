@@ -0,0 +1,70 @@
+//! Vendored Dependencies
+//! `knull vendor` copies locked packages into vendor/ and records their checksums
+//! in vendor/checksums.toml, so a project builds without the network or the caches
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const VENDOR_DIR: &str = "vendor";
+pub const INDEX_NAME: &str = "checksums.toml";
+
+/// vendor/checksums.toml
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct VendorIndex {
+    #[serde(default)]
+    pub packages: BTreeMap<String, VendoredPackage>,
+}
+
+/// A package copied to vendor/<name>
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct VendoredPackage {
+    pub version: String,
+    /// The lockfile source it was copied from
+    pub source: String,
+    pub checksum: String,
+}
+
+impl VendorIndex {
+    pub fn load(vendor_dir: &Path) -> Result<Self, String> {
+        let path = vendor_dir.join(INDEX_NAME);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        toml::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, vendor_dir: &Path) -> Result<(), String> {
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize vendor index: {}", e))?;
+        fs::write(vendor_dir.join(INDEX_NAME), content)
+            .map_err(|e| format!("Failed to write vendor index: {}", e))
+    }
+}
+
+/// The vendor directory of the project at `root`
+pub fn vendor_dir(root: &Path) -> PathBuf {
+    root.join(VENDOR_DIR)
+}
+
+/// Copy the package at `from` to `to`, leaving out `.git`
+pub fn copy_package(from: &Path, to: &Path) -> Result<(), String> {
+    fs::create_dir_all(to).map_err(|e| format!("Failed to create {}: {}", to.display(), e))?;
+    let entries =
+        fs::read_dir(from).map_err(|e| format!("Failed to read {}: {}", from.display(), e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| e.to_string())?;
+        if entry.file_name() == ".git" {
+            continue;
+        }
+        let path = entry.path();
+        let dest = to.join(entry.file_name());
+        if path.is_dir() {
+            copy_package(&path, &dest)?;
+        } else {
+            fs::copy(&path, &dest)
+                .map_err(|e| format!("Failed to copy {}: {}", path.display(), e))?;
+        }
+    }
+    Ok(())
+}
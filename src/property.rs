@@ -0,0 +1,378 @@
+//! Knull Property Testing
+//!
+//! `knull test --property` calls each `#[property] fn` with arguments generated
+//! from its parameter types. A property fails when it raises an error or
+//! returns `false`. The first failing input is shrunk — ints toward zero,
+//! arrays by removing elements, strings by truncation — until no smaller input
+//! still fails, and that counterexample is reported with the seed that found
+//! it. Every property starts from the run's seed, so `--seed` replays a run.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::interpreter::{ErrorKind, RuntimeError, Value};
+use crate::parser::{Param, Type};
+
+/// Cases tried per property unless `--cases` says otherwise
+pub const DEFAULT_CASES: usize = 100;
+
+/// Most shrink steps taken before settling for the input reached
+const MAX_SHRINK_STEPS: usize = 1000;
+
+/// Largest size reached by the last case; ints grow to ±10× this, arrays and
+/// strings to a quarter of it
+const MAX_SIZE: usize = 100;
+
+#[derive(Debug, Clone)]
+pub struct PropertyConfig {
+    pub cases: usize,
+    pub seed: u64,
+}
+
+impl Default for PropertyConfig {
+    fn default() -> Self {
+        PropertyConfig { cases: DEFAULT_CASES, seed: 0 }
+    }
+}
+
+/// Values of one parameter type
+#[derive(Debug, Clone, PartialEq)]
+pub enum Generator {
+    Int { min: i64, max: i64 },
+    Float,
+    Bool,
+    Char,
+    String,
+    Array(Box<Generator>),
+    Option(Box<Generator>),
+}
+
+impl Generator {
+    /// The generator for a parameter's type annotation
+    pub fn for_type(ty: &Type) -> Result<Generator, String> {
+        let int = |min: i64, max: i64| Ok(Generator::Int { min, max });
+        match ty {
+            Type::I8 => int(i8::MIN as i64, i8::MAX as i64),
+            Type::I16 => int(i16::MIN as i64, i16::MAX as i64),
+            Type::I32 => int(i32::MIN as i64, i32::MAX as i64),
+            Type::I64 | Type::I128 => int(i64::MIN, i64::MAX),
+            Type::U8 => int(0, u8::MAX as i64),
+            Type::U16 => int(0, u16::MAX as i64),
+            Type::U32 => int(0, u32::MAX as i64),
+            Type::U64 | Type::U128 => int(0, i64::MAX),
+            Type::F32 | Type::F64 => Ok(Generator::Float),
+            Type::Bool => Ok(Generator::Bool),
+            Type::Char => Ok(Generator::Char),
+            Type::String => Ok(Generator::String),
+            Type::Array(inner, _) | Type::Slice(inner) | Type::Vec(inner) => {
+                Ok(Generator::Array(Box::new(Generator::for_type(inner)?)))
+            }
+            Type::Option(inner) => Ok(Generator::Option(Box::new(Generator::for_type(inner)?))),
+            Type::Ref(inner) | Type::MutRef(inner) => Generator::for_type(inner),
+            Type::Custom(name) => match name.as_str() {
+                "int" | "isize" => int(i64::MIN, i64::MAX),
+                "uint" | "usize" => int(0, i64::MAX),
+                "float" | "number" => Ok(Generator::Float),
+                "string" | "str" => Ok(Generator::String),
+                _ => Err(format!("cannot generate values of type '{}'", name)),
+            },
+            other => Err(format!("cannot generate values of type {:?}", other)),
+        }
+    }
+
+    /// A random value no bigger than `size`
+    pub fn generate(&self, rng: &mut StdRng, size: usize) -> Value {
+        match self {
+            Generator::Int { min, max } => {
+                // Mostly small values; now and then one of up to a million
+                let limit = if rng.gen_ratio(1, 8) { 1_000_000 } else { (size as i64 * 10).max(1) };
+                let lo = (*min).max(-limit);
+                let hi = (*max).min(limit);
+                Value::Int(rng.gen_range(lo..=hi))
+            }
+            Generator::Float => {
+                let limit = (size as f64 * 10.0).max(1.0);
+                Value::Float(rng.gen_range(-limit..=limit))
+            }
+            Generator::Bool => Value::Bool(rng.gen()),
+            Generator::Char => Value::String(random_char(rng).to_string()),
+            Generator::String => {
+                let len = rng.gen_range(0..=size / 4);
+                Value::String((0..len).map(|_| random_char(rng)).collect())
+            }
+            Generator::Array(inner) => {
+                let len = rng.gen_range(0..=size / 4);
+                Value::Array((0..len).map(|_| inner.generate(rng, size)).collect())
+            }
+            Generator::Option(inner) => {
+                if rng.gen_ratio(1, 4) { Value::Null } else { inner.generate(rng, size) }
+            }
+        }
+    }
+
+    /// Smaller values to try in place of `value`, most aggressive first
+    pub fn shrink(&self, value: &Value) -> Vec<Value> {
+        match (self, value) {
+            (Generator::Int { .. }, Value::Int(n)) => {
+                let n = *n;
+                let mut out = Vec::new();
+                if n != 0 {
+                    out.push(0);
+                    if n < 0 && n != i64::MIN {
+                        out.push(-n);
+                    }
+                    out.push(n / 2);
+                    out.push(n - n.signum());
+                }
+                out.dedup();
+                out.retain(|&m| m != n);
+                out.into_iter().map(Value::Int).collect()
+            }
+            (Generator::Float, Value::Float(f)) => {
+                let f = *f;
+                [0.0, f.trunc(), f / 2.0]
+                    .into_iter()
+                    .filter(|&g| g != f && g.abs() < f.abs())
+                    .map(Value::Float)
+                    .collect()
+            }
+            (Generator::Bool, Value::Bool(true)) => vec![Value::Bool(false)],
+            (Generator::Char, Value::String(s)) if s != "a" => vec![Value::String("a".to_string())],
+            (Generator::String, Value::String(s)) => {
+                let chars: Vec<char> = s.chars().collect();
+                let mut lens = vec![0, chars.len() / 2, chars.len().saturating_sub(1)];
+                lens.dedup();
+                let mut out: Vec<Value> = lens
+                    .into_iter()
+                    .filter(|&len| len < chars.len())
+                    .map(|len| Value::String(chars[..len].iter().collect()))
+                    .collect();
+                if !chars.is_empty() {
+                    out.push(Value::String(chars[1..].iter().collect()));
+                }
+                out
+            }
+            (Generator::Array(inner), Value::Array(items)) => {
+                let mut out = Vec::new();
+                if !items.is_empty() {
+                    out.push(Value::Array(Vec::new()));
+                }
+                if items.len() > 1 {
+                    out.push(Value::Array(items[..items.len() / 2].to_vec()));
+                    out.push(Value::Array(items[items.len() / 2..].to_vec()));
+                }
+                for i in 0..items.len() {
+                    let mut fewer = items.clone();
+                    fewer.remove(i);
+                    out.push(Value::Array(fewer));
+                }
+                for (i, item) in items.iter().enumerate() {
+                    for smaller in inner.shrink(item) {
+                        let mut changed = items.clone();
+                        changed[i] = smaller;
+                        out.push(Value::Array(changed));
+                    }
+                }
+                out
+            }
+            (Generator::Option(_), Value::Null) => Vec::new(),
+            (Generator::Option(inner), value) => {
+                let mut out = vec![Value::Null];
+                out.extend(inner.shrink(value));
+                out
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Mostly printable ASCII, sometimes a multi-byte character
+fn random_char(rng: &mut StdRng) -> char {
+    const WIDE: &[char] = &['é', 'ü', 'ß', 'λ', '日', '本', '🦀', '\n', '\t', '\0'];
+    if rng.gen_ratio(1, 10) {
+        WIDE[rng.gen_range(0..WIDE.len())]
+    } else {
+        rng.gen_range(' '..='~')
+    }
+}
+
+/// A property that found a failing input
+#[derive(Debug)]
+pub struct Counterexample {
+    /// The case (from 1) that first failed
+    pub case: usize,
+    pub shrink_steps: usize,
+    /// The smallest failing arguments, by parameter name
+    pub args: Vec<(String, Value)>,
+    pub error: RuntimeError,
+}
+
+impl Counterexample {
+    /// The failure report for property `id`, around its formatted `error`
+    pub fn describe(&self, config: &PropertyConfig, id: &str, error: &str) -> String {
+        let mut out = format!(
+            "  Falsified after {} case{} (shrunk {} time{}), seed {}\n  Counterexample:\n",
+            self.case,
+            if self.case == 1 { "" } else { "s" },
+            self.shrink_steps,
+            if self.shrink_steps == 1 { "" } else { "s" },
+            config.seed
+        );
+        for (name, value) in &self.args {
+            out.push_str(&format!("      {} = {}\n", name, repr(value)));
+        }
+        out.push_str(error.trim_end());
+        out.push_str(&format!("\n  Replay with: knull test --property --seed {} --filter '{}'", config.seed, id));
+        out
+    }
+}
+
+/// Check a property: call `run` with up to `config.cases` generated argument
+/// lists, and shrink the first that fails. `Err` is a setup problem, such as
+/// a parameter type with no generator.
+pub fn check(
+    params: &[Param],
+    config: &PropertyConfig,
+    mut run: impl FnMut(Vec<Value>) -> Result<Value, RuntimeError>,
+) -> Result<Option<Counterexample>, String> {
+    let generators = params
+        .iter()
+        .map(|p| match &p.ty {
+            Some(ty) => Generator::for_type(ty).map_err(|e| format!("parameter '{}': {}", p.name, e)),
+            None => Err(format!("parameter '{}' has no type", p.name)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut fails = |args: &[Value]| match run(args.to_vec()) {
+        Ok(Value::Bool(false)) => Some(RuntimeError::new(ErrorKind::AssertionError, "AssertionError: property returned false")),
+        Ok(_) => None,
+        Err(e) => Some(e),
+    };
+
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let cases = config.cases.max(1);
+    for case in 0..cases {
+        let size = case * MAX_SIZE / cases;
+        let args: Vec<Value> = generators.iter().map(|g| g.generate(&mut rng, size)).collect();
+        let Some(error) = fails(&args) else { continue };
+
+        let (args, error, shrink_steps) = shrink(&generators, args, error, &mut fails);
+        return Ok(Some(Counterexample {
+            case: case + 1,
+            shrink_steps,
+            args: params.iter().map(|p| p.name.clone()).zip(args).collect(),
+            error,
+        }));
+    }
+    Ok(None)
+}
+
+/// Greedily replace one argument at a time with a smaller value that still
+/// fails, until none does
+fn shrink(
+    generators: &[Generator],
+    mut args: Vec<Value>,
+    mut error: RuntimeError,
+    fails: &mut impl FnMut(&[Value]) -> Option<RuntimeError>,
+) -> (Vec<Value>, RuntimeError, usize) {
+    let mut steps = 0;
+    'search: while steps < MAX_SHRINK_STEPS {
+        for (i, generator) in generators.iter().enumerate() {
+            for smaller in generator.shrink(&args[i]) {
+                let mut candidate = args.clone();
+                candidate[i] = smaller;
+                if let Some(e) = fails(&candidate) {
+                    args = candidate;
+                    error = e;
+                    steps += 1;
+                    continue 'search;
+                }
+            }
+        }
+        break;
+    }
+    (args, error, steps)
+}
+
+/// A value as Knull source would write it, so strings show their quotes
+pub fn repr(value: &Value) -> String {
+    match value {
+        Value::String(s) => format!("{:?}", s),
+        Value::Array(items) => format!("[{}]", items.iter().map(repr).collect::<Vec<_>>().join(", ")),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn param(name: &str, ty: Type) -> Param {
+        Param { name: name.to_string(), ty: Some(ty) }
+    }
+
+    #[test]
+    fn test_generators_follow_types() {
+        let ints = Type::Slice(Box::new(Type::Custom("int".to_string())));
+        assert_eq!(
+            Generator::for_type(&ints),
+            Ok(Generator::Array(Box::new(Generator::Int { min: i64::MIN, max: i64::MAX })))
+        );
+        assert!(Generator::for_type(&Type::Custom("Point".to_string())).is_err());
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let bytes = Generator::Int { min: 0, max: 255 };
+        for _ in 0..100 {
+            match bytes.generate(&mut rng, 100) {
+                Value::Int(n) => assert!((0..=255).contains(&n)),
+                other => panic!("expected an int, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_shrinks_to_minimal_counterexample() {
+        // Fails for any array holding a value of at least 10, and any string longer than 2
+        let params = [
+            param("xs", Type::Slice(Box::new(Type::Custom("int".to_string())))),
+            param("s", Type::Custom("string".to_string())),
+        ];
+        let config = PropertyConfig { cases: 200, seed: 42 };
+        let found = check(&params, &config, |args| match (&args[0], &args[1]) {
+            (Value::Array(xs), Value::String(s)) => {
+                let big = xs.iter().any(|x| matches!(x, Value::Int(n) if *n >= 10));
+                Ok(Value::Bool(!(big && s.chars().count() > 2)))
+            }
+            _ => unreachable!(),
+        })
+        .unwrap()
+        .expect("property should fail");
+        assert_eq!(repr(&found.args[0].1), "[10]");
+        match &found.args[1].1 {
+            Value::String(s) => assert_eq!(s.chars().count(), 3),
+            other => panic!("expected a string, got {:?}", other),
+        }
+
+        // The same seed finds the same case
+        let again = check(&params, &config, |args| match (&args[0], &args[1]) {
+            (Value::Array(xs), Value::String(s)) => {
+                let big = xs.iter().any(|x| matches!(x, Value::Int(n) if *n >= 10));
+                Ok(Value::Bool(!(big && s.chars().count() > 2)))
+            }
+            _ => unreachable!(),
+        })
+        .unwrap()
+        .unwrap();
+        assert_eq!(again.case, found.case);
+    }
+
+    #[test]
+    fn test_passing_property() {
+        let params = [param("n", Type::I32)];
+        let found = check(&params, &PropertyConfig::default(), |args| match &args[0] {
+            Value::Int(n) => Ok(Value::Bool(n * 2 == n + n)),
+            _ => unreachable!(),
+        });
+        assert!(matches!(found, Ok(None)));
+    }
+}
@@ -0,0 +1,788 @@
+//! Knull REPL
+//!
+//! An interactive session over one `Interpreter`. Input is read by a small line
+//! editor on crossterm: arrow keys and the usual Emacs bindings, history kept
+//! in `~/.knull_history`, and Tab completion of the session's variables and
+//! functions, builtins and keywords. Input with unclosed brackets continues on
+//! the next line. When stdin is not a terminal, lines are read as they come.
+
+use crate::interpreter::{Interpreter, Value};
+use crate::lexer::{Lexer, TokenKind};
+use colored::Colorize;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, queue, terminal};
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::PathBuf;
+use std::time::Instant;
+
+const HISTORY_FILE: &str = ".knull_history";
+const HISTORY_LIMIT: usize = 1000;
+
+// Printed values wider than this are split one element per line
+const LINE_WIDTH: usize = 80;
+// Elements shown per collection, nesting shown, and characters shown per string
+const MAX_ITEMS: usize = 100;
+const MAX_DEPTH: usize = 6;
+const MAX_STRING: usize = 200;
+
+const KEYWORDS: &[&str] = &[
+    "break", "catch", "continue", "defer", "else", "enum", "false", "fn", "for", "if", "impl", "import", "in",
+    "let", "loop", "match", "null", "return", "struct", "throw", "trait", "true", "try", "while",
+];
+
+const COMMANDS: &[&str] = &[
+    ":ast", ":clear", ":doc", ":fns", ":help", ":load", ":quit", ":reset", ":save", ":time", ":type", ":vars",
+];
+
+const HELP: &str = r#"
+Knull REPL — interactive session
+
+  Type any Knull expression or statement and press Enter.
+  Input with unclosed brackets continues on the next line; a blank line submits it anyway.
+  Up/Down browse history, Tab completes names, Ctrl-C discards the input, Ctrl-D exits.
+
+Commands:
+  :help   :h        Show this help
+  :quit   :q        Exit the REPL
+  :vars             List all variables in scope
+  :fns              List all defined functions
+  :reset            Reset interpreter state
+  :load <file>      Load and execute a .knull file into this session
+  :save <file>      Write the code entered this session to a file
+  :type <expr>      Show the type of an expression
+  :time <code>      Run code and show how long it took
+  :ast <code>       Show the syntax tree of code
+  :doc <builtin>    Show a builtin's signature and documentation
+  :clear            Clear the screen
+"#;
+
+/// Start the interactive REPL
+pub fn start() -> Result<(), String> {
+    println!("{}", r#"
+  _  __              _ _
+ | |/ /_ __  _   _| | |
+ | ' /| '_ \| | | | | |
+ | . \| | | | |_| | | |
+ |_|\_\_| |_|\__,_|_|_|
+"#.bright_purple().bold());
+    println!(
+        "  {} {}  —  {}",
+        "Knull".bright_purple().bold(),
+        "v2.0.0".bright_white(),
+        "The God Programming Language".bright_yellow()
+    );
+    println!("  Type {} for available commands\n", ":help".bright_cyan());
+
+    let mut session = Session::new();
+    let mut editor = LineEditor::new(dirs::home_dir().map(|home| home.join(HISTORY_FILE)));
+    let mut input = String::new();
+    loop {
+        let prompt = if input.is_empty() { "knull❯ " } else { "  ... " };
+        let line = match editor.read_line(prompt, &session.words()).map_err(|e| e.to_string())? {
+            Input::Line(line) => line,
+            Input::Interrupt => {
+                input.clear();
+                continue;
+            }
+            Input::Eof => break,
+        };
+
+        if input.is_empty() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            if trimmed.starts_with(':') {
+                if !session.command(trimmed) {
+                    break;
+                }
+                continue;
+            }
+        } else if line.trim().is_empty() {
+            session.run(&std::mem::take(&mut input));
+            continue;
+        }
+
+        if !input.is_empty() {
+            input.push('\n');
+        }
+        input.push_str(&line);
+        if open_brackets(&input) == 0 {
+            session.run(&std::mem::take(&mut input));
+        }
+    }
+    println!("{}", "Goodbye! 👋".bright_green());
+    Ok(())
+}
+
+/// The interpreter, and the code entered so far for `:save`
+struct Session {
+    interp: Interpreter,
+    source: Vec<String>,
+}
+
+impl Session {
+    fn new() -> Self {
+        Session { interp: Interpreter::new(), source: Vec::new() }
+    }
+
+    /// Names Tab can complete
+    fn words(&self) -> Vec<String> {
+        let mut words: Vec<String> = self.interp.repl_variables().into_iter().map(|(name, _)| name).collect();
+        words.extend(self.interp.repl_functions());
+        for builtin in crate::builtins::BUILTINS {
+            words.push(builtin.name.to_string());
+            words.extend(builtin.aliases.iter().map(|alias| alias.to_string()));
+        }
+        words.extend(KEYWORDS.iter().chain(COMMANDS).map(|word| word.to_string()));
+        words
+    }
+
+    /// Run entered code, printing its value
+    fn run(&mut self, source: &str) {
+        let source = source.trim();
+        if source.is_empty() {
+            return;
+        }
+        match self.interp.repl_exec(source) {
+            Ok(value) => {
+                self.source.push(source.to_string());
+                if let Some(value) = value {
+                    show(&value);
+                }
+            }
+            Err(e) => println!("{} {}", "error:".bright_red().bold(), e),
+        }
+    }
+
+    /// Carry out a `:command`; false when it ends the session
+    fn command(&mut self, command: &str) -> bool {
+        let (name, arg) = command.split_once(char::is_whitespace).unwrap_or((command, ""));
+        let arg = arg.trim();
+        match name {
+            ":quit" | ":q" | ":exit" => return false,
+            ":help" | ":h" => println!("{}", HELP.bright_cyan()),
+            ":vars" => {
+                let vars: Vec<_> = self
+                    .interp
+                    .repl_variables()
+                    .into_iter()
+                    .filter(|(_, v)| !matches!(v, Value::StructDef(_) | Value::EnumDef(_) | Value::Trait(_)))
+                    .collect();
+                if vars.is_empty() {
+                    println!("{}", "(no variables defined)".bright_black());
+                } else {
+                    println!("{}", "Variables:".bright_yellow().bold());
+                    for (name, value) in &vars {
+                        println!("  {} = {}", name.bright_cyan(), flat(value, 0).bright_white());
+                    }
+                }
+            }
+            ":fns" => {
+                let fns = self.interp.repl_functions();
+                if fns.is_empty() {
+                    println!("{}", "(no functions defined)".bright_black());
+                } else {
+                    println!("{}", "Functions:".bright_yellow().bold());
+                    for name in &fns {
+                        println!("  {}", name.bright_cyan());
+                    }
+                }
+            }
+            ":reset" => {
+                *self = Session::new();
+                println!("{}", "State reset.".bright_green());
+            }
+            ":clear" => {
+                print!("\x1B[2J\x1B[H");
+                io::stdout().flush().ok();
+            }
+            ":load" if !arg.is_empty() => match fs::read_to_string(arg) {
+                Ok(src) => match crate::parser::Parser::new(&src).with_file(arg).parse() {
+                    Ok(ast) => match self.interp.execute(&ast) {
+                        Ok(_) => {
+                            self.source.push(src.trim_end().to_string());
+                            println!("{} Loaded {}", "✓".green(), arg);
+                        }
+                        Err(e) => println!("{} {}", "error:".bright_red(), e),
+                    },
+                    Err(e) => println!("{} {}", "parse error:".bright_red(), e),
+                },
+                Err(e) => println!("{} {}", "error:".bright_red(), e),
+            },
+            ":save" if !arg.is_empty() => {
+                let mut text = self.source.join("\n");
+                text.push('\n');
+                match fs::write(arg, text) {
+                    Ok(_) => println!("{} Saved {} entries to {}", "✓".green(), self.source.len(), arg),
+                    Err(e) => println!("{} {}", "error:".bright_red(), e),
+                }
+            }
+            ":type" if !arg.is_empty() => match self.interp.repl_peek(arg) {
+                Ok(Some(value)) => println!("{} : {}", arg.bright_white(), value.type_name().bright_cyan()),
+                Ok(None) => println!("{}", "(no value)".bright_black()),
+                Err(e) => println!("{} {}", "error:".bright_red(), e),
+            },
+            ":time" if !arg.is_empty() => {
+                let start = Instant::now();
+                let result = self.interp.repl_exec(arg);
+                let elapsed = crate::bench::format_ns(start.elapsed().as_nanos() as f64);
+                match result {
+                    Ok(value) => {
+                        self.source.push(arg.to_string());
+                        if let Some(value) = value {
+                            show(&value);
+                        }
+                        println!("{}", format!("time: {}", elapsed).bright_black());
+                    }
+                    Err(e) => println!("{} {}", "error:".bright_red(), e),
+                }
+            }
+            ":ast" if !arg.is_empty() => match crate::parser::Parser::new(arg).without_spans().parse() {
+                Ok(ast) => println!("{:#?}", ast),
+                Err(e) => println!("{} {}", "parse error:".bright_red(), e),
+            },
+            ":doc" if !arg.is_empty() => {
+                let found: Vec<_> = crate::builtins::lookup(arg).into_iter().chain(crate::builtins::methods(arg)).collect();
+                if found.is_empty() {
+                    println!("{} no builtin named '{}'", "error:".bright_red(), arg);
+                }
+                for builtin in found {
+                    println!("{}  {}", builtin.signature().bright_cyan().bold(), builtin.category.bright_black());
+                    if !builtin.doc.is_empty() {
+                        println!("  {}", builtin.doc);
+                    }
+                }
+            }
+            ":load" | ":save" | ":type" | ":time" | ":ast" | ":doc" => {
+                println!("{} {} needs an argument; see :help", "error:".bright_red(), name)
+            }
+            _ => println!("{} unknown command {}; see :help", "error:".bright_red(), name),
+        }
+        true
+    }
+}
+
+/// Print a value the REPL computed, with its type
+fn show(value: &Value) {
+    println!(
+        "{} {}",
+        pretty(value).bright_white().bold(),
+        format!("// {}", value.type_name()).bright_black()
+    );
+}
+
+/// How many brackets `source` leaves open; strings and comments don't count
+fn open_brackets(source: &str) -> usize {
+    let mut depth = 0usize;
+    for token in Lexer::new(source).tokenize() {
+        match token.kind {
+            TokenKind::LParen | TokenKind::LBrace | TokenKind::LBracket => depth += 1,
+            TokenKind::RParen | TokenKind::RBrace | TokenKind::RBracket => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    depth
+}
+
+/// `value` on one line if it fits, otherwise with one element per line.
+/// Long collections and strings, and deep nesting, are cut short.
+fn pretty(value: &Value) -> String {
+    let mut out = String::new();
+    write_pretty(&mut out, value, 0, 0);
+    out
+}
+
+fn write_pretty(out: &mut String, value: &Value, indent: usize, depth: usize) {
+    let line = flat(value, depth);
+    let (open, close, entries) = match value {
+        _ if indent + line.chars().count() <= LINE_WIDTH || depth >= MAX_DEPTH => {
+            out.push_str(&line);
+            return;
+        }
+        Value::Array(items) => ("[".to_string(), "]", entries(items.iter().map(|v| (None, v)))),
+        Value::Tuple(items) => ("(".to_string(), ")", entries(items.iter().map(|v| (None, v)))),
+        Value::Map(map) => ("{".to_string(), "}", entries(sorted(map).into_iter().map(|(k, v)| (Some(format!("{:?}", k)), v)))),
+        Value::StructInstance(inst) => (
+            format!("{} {{", inst.def.name),
+            "}",
+            entries(sorted(&inst.fields).into_iter().map(|(k, v)| (Some(k.clone()), v))),
+        ),
+        _ => {
+            out.push_str(&line);
+            return;
+        }
+    };
+    let (shown, hidden) = entries;
+    out.push_str(&open);
+    out.push('\n');
+    let pad = " ".repeat(indent + 2);
+    for (key, item) in shown {
+        out.push_str(&pad);
+        if let Some(key) = key {
+            out.push_str(&key);
+            out.push_str(": ");
+        }
+        write_pretty(out, item, indent + 2, depth + 1);
+        out.push_str(",\n");
+    }
+    if hidden > 0 {
+        out.push_str(&format!("{}… {} more\n", pad, hidden));
+    }
+    out.push_str(&" ".repeat(indent));
+    out.push_str(close);
+}
+
+/// The first `MAX_ITEMS` entries, and how many more there are
+fn entries<'a, I>(all: I) -> (Vec<(Option<String>, &'a Value)>, usize)
+where
+    I: ExactSizeIterator<Item = (Option<String>, &'a Value)>,
+{
+    let total = all.len();
+    let shown: Vec<_> = all.take(MAX_ITEMS).collect();
+    let hidden = total - shown.len();
+    (shown, hidden)
+}
+
+fn sorted(map: &std::collections::HashMap<String, Value>) -> Vec<(&String, &Value)> {
+    let mut pairs: Vec<_> = map.iter().collect();
+    pairs.sort_by(|a, b| a.0.cmp(b.0));
+    pairs
+}
+
+/// `value` on one line, with strings quoted and collections cut short
+fn flat(value: &Value, depth: usize) -> String {
+    let nested = depth + 1 > MAX_DEPTH;
+    let join = |parts: Vec<String>, hidden: usize| {
+        let mut parts = parts;
+        if hidden > 0 {
+            parts.push(format!("… {} more", hidden));
+        }
+        parts.join(", ")
+    };
+    match value {
+        Value::String(s) if s.chars().count() > MAX_STRING => {
+            let cut: String = s.chars().take(MAX_STRING).collect();
+            format!("{:?}…", cut)
+        }
+        Value::String(s) => format!("{:?}", s),
+        Value::Array(_) if nested => "[…]".to_string(),
+        Value::Map(_) | Value::StructInstance(_) if nested => "{…}".to_string(),
+        Value::Array(items) => {
+            let (shown, hidden) = entries(items.iter().map(|v| (None, v)));
+            format!("[{}]", join(shown.iter().map(|(_, v)| flat(v, depth + 1)).collect(), hidden))
+        }
+        Value::Tuple(items) => {
+            let (shown, hidden) = entries(items.iter().map(|v| (None, v)));
+            format!("({})", join(shown.iter().map(|(_, v)| flat(v, depth + 1)).collect(), hidden))
+        }
+        Value::Map(map) => {
+            let (shown, hidden) = entries(sorted(map).into_iter().map(|(k, v)| (Some(format!("{:?}", k)), v)));
+            let parts = shown.iter().map(|(k, v)| format!("{}: {}", k.as_deref().unwrap_or(""), flat(v, depth + 1)));
+            format!("{{{}}}", join(parts.collect(), hidden))
+        }
+        Value::StructInstance(inst) => {
+            let (shown, hidden) = entries(sorted(&inst.fields).into_iter().map(|(k, v)| (Some(k.clone()), v)));
+            let parts = shown.iter().map(|(k, v)| format!("{}: {}", k.as_deref().unwrap_or(""), flat(v, depth + 1)));
+            format!("{} {{ {} }}", inst.def.name, join(parts.collect(), hidden))
+        }
+        other => other.to_string(),
+    }
+}
+
+/// What reading a line produced
+#[derive(Debug, PartialEq)]
+enum Input {
+    Line(String),
+    /// Ctrl-C: drop whatever has been entered
+    Interrupt,
+    /// Ctrl-D on an empty line, or the end of piped input
+    Eof,
+}
+
+/// The text being edited, and the cursor's position in it
+#[derive(Debug, Default)]
+struct Line {
+    chars: Vec<char>,
+    cursor: usize,
+}
+
+impl Line {
+    fn text(&self) -> String {
+        self.chars.iter().collect()
+    }
+
+    fn set(&mut self, text: &str) {
+        self.chars = text.chars().collect();
+        self.cursor = self.chars.len();
+    }
+
+    fn insert(&mut self, text: &str) {
+        for c in text.chars() {
+            self.chars.insert(self.cursor, c);
+            self.cursor += 1;
+        }
+    }
+
+    fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            self.chars.remove(self.cursor);
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.cursor < self.chars.len() {
+            self.chars.remove(self.cursor);
+        }
+    }
+
+    fn left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    fn right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.chars.len());
+    }
+
+    fn kill_to_start(&mut self) {
+        self.chars.drain(..self.cursor);
+        self.cursor = 0;
+    }
+
+    fn kill_to_end(&mut self) {
+        self.chars.truncate(self.cursor);
+    }
+
+    /// Ctrl-W: the spaces before the cursor and the word before them
+    fn delete_word(&mut self) {
+        let mut start = self.cursor;
+        while start > 0 && self.chars[start - 1].is_whitespace() {
+            start -= 1;
+        }
+        while start > 0 && !self.chars[start - 1].is_whitespace() {
+            start -= 1;
+        }
+        self.chars.drain(start..self.cursor);
+        self.cursor = start;
+    }
+
+    /// The identifier (or `:command`) the cursor is at the end of
+    fn word(&self) -> String {
+        let mut start = self.cursor;
+        while start > 0 && (self.chars[start - 1].is_alphanumeric() || self.chars[start - 1] == '_') {
+            start -= 1;
+        }
+        if start == 1 && self.chars[0] == ':' {
+            start = 0;
+        }
+        self.chars[start..self.cursor].iter().collect()
+    }
+}
+
+/// Entered lines, oldest first, appended to the history file as they come
+struct History {
+    entries: Vec<String>,
+    path: Option<PathBuf>,
+    // Entry shown by Up/Down, and the line being typed before browsing started
+    browsing: Option<usize>,
+    draft: String,
+}
+
+impl History {
+    fn load(path: Option<PathBuf>) -> Self {
+        let mut entries: Vec<String> = path
+            .as_ref()
+            .and_then(|p| fs::read_to_string(p).ok())
+            .map(|text| text.lines().filter(|l| !l.trim().is_empty()).map(String::from).collect())
+            .unwrap_or_default();
+        if entries.len() > HISTORY_LIMIT {
+            entries.drain(..entries.len() - HISTORY_LIMIT);
+            if let Some(path) = &path {
+                let _ = fs::write(path, entries.join("\n") + "\n");
+            }
+        }
+        History { entries, path, browsing: None, draft: String::new() }
+    }
+
+    fn add(&mut self, line: &str) {
+        self.browsing = None;
+        if line.trim().is_empty() || self.entries.last().map(String::as_str) == Some(line) {
+            return;
+        }
+        self.entries.push(line.to_string());
+        if self.entries.len() > HISTORY_LIMIT {
+            self.entries.remove(0);
+        }
+        if let Some(path) = &self.path {
+            if let Ok(mut file) = fs::OpenOptions::new().create(true).append(true).open(path) {
+                let _ = writeln!(file, "{}", line);
+            }
+        }
+    }
+
+    /// The entry before the one shown; `current` is kept to come back to
+    fn previous(&mut self, current: &str) -> Option<&str> {
+        let index = match self.browsing {
+            None if self.entries.is_empty() => return None,
+            None => {
+                self.draft = current.to_string();
+                self.entries.len() - 1
+            }
+            Some(0) => 0,
+            Some(i) => i - 1,
+        };
+        self.browsing = Some(index);
+        Some(&self.entries[index])
+    }
+
+    /// The entry after the one shown, or the line typed before browsing
+    fn next(&mut self) -> Option<&str> {
+        let index = self.browsing?;
+        if index + 1 < self.entries.len() {
+            self.browsing = Some(index + 1);
+            Some(&self.entries[index + 1])
+        } else {
+            self.browsing = None;
+            Some(&self.draft)
+        }
+    }
+}
+
+/// The words in `words` that start with `prefix`, sorted without repeats
+fn completions(prefix: &str, words: &[String]) -> Vec<String> {
+    let mut found: Vec<String> = words.iter().filter(|w| w.starts_with(prefix) && w.len() > prefix.len()).cloned().collect();
+    found.sort();
+    found.dedup();
+    found
+}
+
+fn common_prefix(words: &[String]) -> String {
+    let Some(first) = words.first() else {
+        return String::new();
+    };
+    let mut len = first.len();
+    for word in &words[1..] {
+        len = first.bytes().zip(word.bytes()).take(len).take_while(|(a, b)| a == b).count();
+    }
+    while !first.is_char_boundary(len) {
+        len -= 1;
+    }
+    first[..len].to_string()
+}
+
+// Raw mode for as long as a line is being edited
+struct RawMode;
+
+impl RawMode {
+    fn enable() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        Ok(RawMode)
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+struct LineEditor {
+    history: History,
+}
+
+impl LineEditor {
+    fn new(history: Option<PathBuf>) -> Self {
+        LineEditor { history: History::load(history) }
+    }
+
+    /// Read one line after `prompt`; Tab completes from `words`
+    fn read_line(&mut self, prompt: &str, words: &[String]) -> io::Result<Input> {
+        if !io::stdin().is_terminal() {
+            print!("{}", prompt.bright_magenta().bold());
+            io::stdout().flush()?;
+            let mut line = String::new();
+            if io::stdin().lock().read_line(&mut line)? == 0 {
+                println!();
+                return Ok(Input::Eof);
+            }
+            return Ok(Input::Line(line.trim_end_matches(['\n', '\r']).to_string()));
+        }
+
+        let _raw = RawMode::enable()?;
+        let mut out = io::stdout();
+        let mut line = Line::default();
+        let mut listed = false;
+        loop {
+            render(&mut out, prompt, &line)?;
+            let Event::Key(KeyEvent { code, modifiers, kind, .. }) = event::read()? else {
+                continue;
+            };
+            if kind != KeyEventKind::Press {
+                continue;
+            }
+            let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+            let tab = code == KeyCode::Tab;
+            match code {
+                KeyCode::Enter => {
+                    write!(out, "\r\n")?;
+                    let text = line.text();
+                    self.history.add(&text);
+                    return Ok(Input::Line(text));
+                }
+                KeyCode::Char('c') if ctrl => {
+                    write!(out, "^C\r\n")?;
+                    self.history.browsing = None;
+                    return Ok(Input::Interrupt);
+                }
+                KeyCode::Char('d') if ctrl && line.chars.is_empty() => {
+                    write!(out, "\r\n")?;
+                    return Ok(Input::Eof);
+                }
+                KeyCode::Char('d') if ctrl => line.delete(),
+                KeyCode::Char('a') if ctrl => line.cursor = 0,
+                KeyCode::Char('e') if ctrl => line.cursor = line.chars.len(),
+                KeyCode::Char('b') if ctrl => line.left(),
+                KeyCode::Char('f') if ctrl => line.right(),
+                KeyCode::Char('k') if ctrl => line.kill_to_end(),
+                KeyCode::Char('u') if ctrl => line.kill_to_start(),
+                KeyCode::Char('w') if ctrl => line.delete_word(),
+                KeyCode::Char('l') if ctrl => {
+                    queue!(out, terminal::Clear(terminal::ClearType::All), cursor::MoveTo(0, 0))?;
+                }
+                KeyCode::Char('p') if ctrl => self.show_previous(&mut line),
+                KeyCode::Char('n') if ctrl => self.show_next(&mut line),
+                KeyCode::Char(c) if !ctrl => line.insert(&c.to_string()),
+                KeyCode::Backspace => line.backspace(),
+                KeyCode::Delete => line.delete(),
+                KeyCode::Left => line.left(),
+                KeyCode::Right => line.right(),
+                KeyCode::Home => line.cursor = 0,
+                KeyCode::End => line.cursor = line.chars.len(),
+                KeyCode::Up => self.show_previous(&mut line),
+                KeyCode::Down => self.show_next(&mut line),
+                KeyCode::Tab => {
+                    let word = line.word();
+                    let found = completions(&word, words);
+                    let prefix = common_prefix(&found);
+                    if prefix.len() > word.len() {
+                        line.insert(&prefix[word.len()..]);
+                    } else if found.len() > 1 && listed {
+                        // A second Tab with nothing more to add lists the candidates
+                        write!(out, "\r\n{}\r\n", found.join("  "))?;
+                    } else if word.is_empty() {
+                        // Indent when there is nothing to complete
+                        line.insert("    ");
+                    }
+                }
+                _ => {}
+            }
+            listed = tab;
+        }
+    }
+
+    fn show_previous(&mut self, line: &mut Line) {
+        let current = line.text();
+        if let Some(entry) = self.history.previous(&current) {
+            line.set(entry);
+        }
+    }
+
+    fn show_next(&mut self, line: &mut Line) {
+        if let Some(entry) = self.history.next() {
+            line.set(entry);
+        }
+    }
+}
+
+/// Redraw the prompt and line, and put the cursor back where it belongs
+fn render(out: &mut io::Stdout, prompt: &str, line: &Line) -> io::Result<()> {
+    queue!(out, cursor::MoveToColumn(0), terminal::Clear(terminal::ClearType::CurrentLine))?;
+    write!(out, "{}{}", prompt.bright_magenta().bold(), line.text())?;
+    let column = prompt.chars().count() + line.cursor;
+    queue!(out, cursor::MoveToColumn(column.min(u16::MAX as usize) as u16))?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_open_brackets() {
+        assert_eq!(open_brackets("fn f() {"), 1);
+        assert_eq!(open_brackets("let m = {\"a\": [1, 2"), 2);
+        assert_eq!(open_brackets("fn f() { 1 }"), 0);
+        // Brackets in strings and comments don't count
+        assert_eq!(open_brackets("print(\"{[(\") // {"), 0);
+        assert_eq!(open_brackets("/* ( */ let x = '('"), 0);
+    }
+
+    #[test]
+    fn test_line_editing() {
+        let mut line = Line::default();
+        line.insert("let total = 1");
+        line.cursor = 4;
+        line.delete_word();
+        assert_eq!((line.text().as_str(), line.cursor), ("total = 1", 0));
+        line.cursor = 5;
+        assert_eq!(line.word(), "total");
+        line.backspace();
+        line.insert("s");
+        line.right();
+        line.kill_to_end();
+        assert_eq!(line.text(), "totas ");
+        line.set(":ty");
+        assert_eq!(line.word(), ":ty");
+    }
+
+    #[test]
+    fn test_completion() {
+        let words: Vec<String> = ["print", "println", "print", "prime", "x", ":type", ":time"].iter().map(|w| w.to_string()).collect();
+        assert_eq!(completions("pri", &words), ["prime", "print", "println"]);
+        assert_eq!(common_prefix(&completions("prin", &words)), "print");
+        assert_eq!(common_prefix(&completions(":t", &words)), ":t");
+        assert!(completions("x", &words).is_empty());
+    }
+
+    #[test]
+    fn test_history() {
+        let path = std::env::temp_dir().join(format!("knull_history_{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut history = History::load(Some(path.clone()));
+        history.add("let a = 1");
+        history.add("let a = 1");
+        history.add("a + 1");
+        assert_eq!(history.previous("draft"), Some("a + 1"));
+        assert_eq!(history.previous("draft"), Some("let a = 1"));
+        assert_eq!(history.previous("draft"), Some("let a = 1"));
+        assert_eq!(history.next(), Some("a + 1"));
+        assert_eq!(history.next(), Some("draft"));
+        assert_eq!(history.next(), None);
+
+        let reloaded = History::load(Some(path.clone()));
+        assert_eq!(reloaded.entries, ["let a = 1", "a + 1"]);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_pretty_printing() {
+        let mut map = HashMap::new();
+        map.insert("b".to_string(), Value::Array(vec![Value::Int(1), Value::String("two".to_string())]));
+        map.insert("a".to_string(), Value::Null);
+        assert_eq!(pretty(&Value::Map(map)), "{\"a\": null, \"b\": [1, \"two\"]}");
+
+        let long = Value::Array((0..150).map(Value::Int).collect());
+        let shown = pretty(&long);
+        assert!(shown.starts_with("[\n  0,\n  1,\n"));
+        assert!(shown.ends_with("  99,\n  … 50 more\n]"), "{}", shown);
+
+        let mut nested = Value::Int(0);
+        for _ in 0..10 {
+            nested = Value::Array(vec![nested]);
+        }
+        assert_eq!(pretty(&nested), "[[[[[[[…]]]]]]]");
+    }
+}
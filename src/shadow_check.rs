@@ -0,0 +1,54 @@
+//! Knull Builtin Shadowing Lint
+//!
+//! A top-level `fn` takes precedence over a builtin of the same name, which is
+//! easy to do by accident given how many builtins there are. `knull check`
+//! reports each such definition; `builtin::name(..)` still reaches the builtin.
+
+use crate::interpreter::is_builtin;
+use crate::parser::{ASTNode, Span};
+
+/// A function definition that hides a builtin
+#[derive(Debug, Clone)]
+pub struct ShadowWarning {
+    pub message: String,
+    pub span: Option<Span>,
+}
+
+/// Check a program's top-level function definitions against the builtins
+pub fn check_program(ast: &ASTNode) -> Vec<ShadowWarning> {
+    let items = match ast {
+        ASTNode::Program(items) => items,
+        _ => return Vec::new(),
+    };
+    items
+        .iter()
+        .filter_map(|item| match item.inner() {
+            ASTNode::Function { name, .. } | ASTNode::AsyncFunction { name, .. } if is_builtin(name) => {
+                Some(ShadowWarning {
+                    message: format!(
+                        "function `{}` shadows the builtin of the same name (call it as `builtin::{}`)",
+                        name, name
+                    ),
+                    span: item.span().cloned(),
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_reports_shadowed_builtins() {
+        let src = "fn len(x) { 0 }\nfn my_len(x) { len(x) }\nimpl S { fn len(self) { 1 } }";
+        let ast = Parser::new(src).parse().unwrap();
+        let warnings = check_program(&ast);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.starts_with("function `len` shadows"));
+        assert_eq!(warnings[0].span.as_ref().map(|s| s.line), Some(1));
+    }
+}
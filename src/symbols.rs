@@ -0,0 +1,1151 @@
+//! Knull Symbol Index
+//!
+//! The language server answers go-to-definition, find-references, rename and
+//! symbol searches from this index. Each file is parsed with spans and walked
+//! with the scoping the interpreter uses: blocks, functions, lambdas, loops
+//! and match arms open a scope, and `let`s, parameters and loop variables bind
+//! in it. The parser records spans for statements and expressions but not for
+//! the names inside them, so the walk keeps a cursor into the file's tokens
+//! and finds each name as the next identifier with that text within the span
+//! of the node being walked.
+//!
+//! Names no scope binds refer to a top-level item of the file or of a file it
+//! `use`s. Those are resolved when queried rather than when indexed, so an
+//! edit re-indexes only the edited file. Methods, fields and variants are
+//! matched by name alone, since the type of a receiver is only known at run
+//! time.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::lexer::{Lexer, Token, TokenKind};
+use crate::parser::{ASTNode, Param, Parser, Pattern, Span, Type, VariantData};
+
+/// What a symbol names
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    Function,
+    Method,
+    Struct,
+    Field,
+    Enum,
+    Variant,
+    Trait,
+    Constant,
+    TypeAlias,
+    Variable,
+    Parameter,
+    /// The alias of `use "path" as name`
+    Module,
+}
+
+impl SymbolKind {
+    /// Reached through a value or type rather than a scope
+    pub fn is_member(self) -> bool {
+        matches!(self, SymbolKind::Method | SymbolKind::Field | SymbolKind::Variant)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// The declaring occurrence of the name
+    pub span: Span,
+    /// The whole declaration
+    pub extent: Span,
+    /// The type or trait a method, field or variant belongs to
+    pub container: Option<String>,
+    /// Declared at the top level of its file
+    pub global: bool,
+    /// The parameters of a function or method, without `self`
+    pub params: Vec<String>,
+    /// For a module alias, the import it names
+    import: Option<usize>,
+}
+
+/// What a use of a name refers to
+#[derive(Debug, Clone, PartialEq)]
+enum Target {
+    /// A symbol of the same file, bound by an enclosing scope
+    Local(usize),
+    /// A top-level item of the file, or one exported by a file it `use`s
+    Global(String),
+    /// An item exported by the file behind the import with this index
+    Export(usize, String),
+    /// A method, field or variant of any type
+    Member(String),
+}
+
+#[derive(Debug, Clone)]
+struct Reference {
+    span: Span,
+    target: Target,
+}
+
+#[derive(Debug, Clone)]
+pub struct Import {
+    /// The imported file, if it could be found
+    pub path: Option<PathBuf>,
+    pub alias: Option<String>,
+    /// The import path as written
+    pub span: Span,
+}
+
+/// The symbols of one file and the uses of names in it
+#[derive(Debug, Clone)]
+pub struct FileIndex {
+    /// The text the spans refer to
+    pub source: String,
+    pub symbols: Vec<Symbol>,
+    pub imports: Vec<Import>,
+    refs: Vec<Reference>,
+    /// Names declared `pub`; with none, every top-level item is exported
+    public: Vec<String>,
+    /// The file stopped parsing, and this index is of its last version that did
+    pub stale: bool,
+}
+
+impl FileIndex {
+    /// Parse and index `source` as the contents of `path`
+    pub fn build(path: &Path, source: &str) -> Result<FileIndex, String> {
+        let file = path.to_string_lossy();
+        let mut parser = Parser::new(source).with_file(&file);
+        let ast = parser.parse()?;
+
+        let chars: Vec<char> = source.chars().collect();
+        let mut indexer = Indexer {
+            path,
+            file: Arc::from(&*file),
+            tokens: Lexer::new(source).tokenize(),
+            line_starts: line_starts(&chars),
+            chars,
+            cursor: 0,
+            limit: usize::MAX,
+            extent: Span::default(),
+            scopes: vec![HashMap::new()],
+            symbols: Vec::new(),
+            imports: Vec::new(),
+            refs: Vec::new(),
+        };
+        indexer.walk(&ast);
+        Ok(FileIndex {
+            source: source.to_string(),
+            symbols: indexer.symbols,
+            imports: indexer.imports,
+            refs: indexer.refs,
+            public: parser.public_names().to_vec(),
+            stale: false,
+        })
+    }
+
+    fn exports(&self, symbol: &Symbol) -> bool {
+        symbol.global && (self.public.is_empty() || self.public.contains(&symbol.name))
+    }
+
+    /// Top-level items, each with the members declared for it in this file.
+    /// Members of a type declared elsewhere are listed on their own.
+    pub fn outline(&self) -> Vec<(&Symbol, Vec<&Symbol>)> {
+        let items: Vec<&Symbol> = self.symbols.iter().filter(|s| s.global).collect();
+        let mut outline: Vec<(&Symbol, Vec<&Symbol>)> = items.iter().map(|&item| (item, Vec::new())).collect();
+        for member in self.symbols.iter().filter(|s| s.kind.is_member()) {
+            let owner = items.iter().position(|item| {
+                member.container.as_deref() == Some(item.name.as_str())
+                    && matches!(item.kind, SymbolKind::Struct | SymbolKind::Enum | SymbolKind::Trait)
+            });
+            match owner {
+                Some(i) => outline[i].1.push(member),
+                None => outline.push((member, Vec::new())),
+            }
+        }
+        outline
+    }
+
+    /// The name a reference is spelled with
+    fn name_of<'a>(&'a self, target: &'a Target) -> &'a str {
+        match target {
+            Target::Local(i) => &self.symbols[*i].name,
+            Target::Global(name) | Target::Export(_, name) | Target::Member(name) => name,
+        }
+    }
+}
+
+/// Char offset of the start of each line
+fn line_starts(chars: &[char]) -> Vec<usize> {
+    let mut starts = vec![0];
+    starts.extend(chars.iter().enumerate().filter(|(_, c)| **c == '\n').map(|(i, _)| i + 1));
+    starts
+}
+
+fn contains(span: &Span, line: usize, col: usize) -> bool {
+    (span.line, span.col) <= (line, col) && (line, col) <= (span.end_line, span.end_col)
+}
+
+fn encloses(outer: &Span, inner: &Span) -> bool {
+    contains(outer, inner.line, inner.col) && contains(outer, inner.end_line, inner.end_col)
+}
+
+fn param_names(params: &[Param]) -> Vec<String> {
+    params.iter().filter(|p| p.name != "self").map(|p| p.name.clone()).collect()
+}
+
+/// Whether `name` can stand where an identifier does
+pub fn is_identifier(name: &str) -> bool {
+    let tokens = Lexer::new(name).tokenize();
+    tokens.len() == 2 && tokens[0].kind == TokenKind::Identifier && tokens[0].value == name
+}
+
+struct Indexer<'a> {
+    path: &'a Path,
+    file: Arc<str>,
+    tokens: Vec<Token>,
+    chars: Vec<char>,
+    line_starts: Vec<usize>,
+    /// The next token a name may be found at
+    cursor: usize,
+    /// Tokens from this index on lie outside the node being walked
+    limit: usize,
+    /// Span of the innermost spanned node being walked
+    extent: Span,
+    scopes: Vec<HashMap<String, usize>>,
+    symbols: Vec<Symbol>,
+    imports: Vec<Import>,
+    refs: Vec<Reference>,
+}
+
+impl Indexer<'_> {
+    /// Index of the first token at or after a 1-based position
+    fn token_at(&self, line: usize, col: usize) -> usize {
+        self.tokens.partition_point(|t| (t.line, t.col) < (line, col))
+    }
+
+    fn span_of(&self, first: usize, last: usize) -> Span {
+        let (first, last) = (&self.tokens[first], &self.tokens[last]);
+        Span {
+            file: Some(self.file.clone()),
+            line: first.line,
+            col: first.col,
+            end_line: last.end_line,
+            end_col: last.end_col,
+        }
+    }
+
+    /// Span of the chars `start..end` of the file
+    fn span_between(&self, start: usize, end: usize) -> Span {
+        let position = |offset: usize| {
+            let line = self.line_starts.partition_point(|&s| s <= offset);
+            (line, offset - self.line_starts[line - 1] + 1)
+        };
+        let ((line, col), (end_line, end_col)) = (position(start), position(end));
+        Span { file: Some(self.file.clone()), line, col, end_line, end_col }
+    }
+
+    fn end(&self) -> usize {
+        self.limit.min(self.tokens.len())
+    }
+
+    /// Find `name` among the tokens left in the node being walked
+    fn find(&mut self, name: &str) -> Option<Span> {
+        let i = (self.cursor..self.end())
+            .find(|&i| self.tokens[i].kind == TokenKind::Identifier && self.tokens[i].value == name)?;
+        self.cursor = i + 1;
+        Some(self.span_of(i, i))
+    }
+
+    /// Find `fn name` after token `from`, returning the index of the `fn`
+    fn find_fn(&self, from: usize, name: &str) -> Option<usize> {
+        (from..self.end().saturating_sub(1)).find(|&i| {
+            self.tokens[i].kind == TokenKind::Fn
+                && self.tokens[i + 1].kind == TokenKind::Identifier
+                && self.tokens[i + 1].value == name
+        })
+    }
+
+    /// Index of the `}` closing the first `{` after token `from`
+    fn block_end(&self, from: usize) -> Option<usize> {
+        let open = (from..self.end()).find(|&i| self.tokens[i].kind == TokenKind::LBrace)?;
+        let mut depth = 0;
+        for i in open..self.end() {
+            match self.tokens[i].kind {
+                TokenKind::LBrace => depth += 1,
+                TokenKind::RBrace => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(i);
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    fn lookup(&self, name: &str) -> Option<usize> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name).copied())
+    }
+
+    /// The import a name bound to a module alias refers to
+    fn module_of(&self, node: &ASTNode) -> Option<usize> {
+        match node.inner() {
+            ASTNode::Identifier(name) => self.lookup(name).and_then(|id| self.symbols[id].import),
+            _ => None,
+        }
+    }
+
+    fn declare(&mut self, name: &str, kind: SymbolKind, span: Span, extent: Span, container: Option<&str>) -> usize {
+        let id = self.symbols.len();
+        self.symbols.push(Symbol {
+            name: name.to_string(),
+            kind,
+            span,
+            extent,
+            container: container.map(str::to_string),
+            global: self.scopes.len() == 1 && !kind.is_member(),
+            params: Vec::new(),
+            import: None,
+        });
+        if !kind.is_member() {
+            self.scopes.last_mut().unwrap().insert(name.to_string(), id);
+        }
+        id
+    }
+
+    /// Find and declare a name that is its own whole declaration, like a parameter
+    fn declare_here(&mut self, name: &str, kind: SymbolKind) {
+        if let Some(span) = self.find(name) {
+            self.declare(name, kind, span.clone(), span, None);
+        }
+    }
+
+    fn reference(&mut self, name: &str) {
+        if let Some(span) = self.find(name) {
+            let target = self.lookup(name).map_or_else(|| Target::Global(name.to_string()), Target::Local);
+            self.refs.push(Reference { span, target });
+        }
+    }
+
+    /// A method, field or variant name, or an item of the module `import` names
+    fn member(&mut self, import: Option<usize>, name: &str) {
+        if let Some(span) = self.find(name) {
+            let target = match import {
+                Some(import) => Target::Export(import, name.to_string()),
+                None => Target::Member(name.to_string()),
+            };
+            self.refs.push(Reference { span, target });
+        }
+    }
+
+    fn type_refs(&mut self, ty: &Type) {
+        match ty {
+            Type::Custom(name) => self.reference(name),
+            Type::Ref(inner)
+            | Type::MutRef(inner)
+            | Type::RawPtr(inner)
+            | Type::MutRawPtr(inner)
+            | Type::Array(inner, _)
+            | Type::Slice(inner)
+            | Type::Vec(inner)
+            | Type::Option(inner) => self.type_refs(inner),
+            Type::Result(ok, err) => {
+                self.type_refs(ok);
+                self.type_refs(err);
+            }
+            Type::Fn(args, ret) => {
+                for arg in args {
+                    self.type_refs(arg);
+                }
+                self.type_refs(ret);
+            }
+            _ => {}
+        }
+    }
+
+    fn scoped(&mut self, walk: impl FnOnce(&mut Self)) {
+        self.scopes.push(HashMap::new());
+        walk(self);
+        self.scopes.pop();
+    }
+
+    fn walk(&mut self, node: &ASTNode) {
+        use ASTNode::*;
+        match node {
+            Spanned { span, node } => {
+                let (limit, extent) = (self.limit, self.extent.clone());
+                self.cursor = self.token_at(span.line, span.col);
+                self.limit = self.token_at(span.end_line, span.end_col);
+                self.extent = span.clone();
+                self.walk(node);
+                self.cursor = self.limit;
+                self.limit = limit;
+                self.extent = extent;
+            }
+            Program(items) => {
+                for item in items {
+                    self.walk(item);
+                }
+            }
+            Block(stmts) => self.scoped(|this| {
+                for stmt in stmts {
+                    this.walk(stmt);
+                }
+            }),
+            Function { name, params, ret_type, body } | AsyncFunction { name, params, ret_type, body } => {
+                let extent = self.extent.clone();
+                self.function(name, params, ret_type.as_ref(), body, SymbolKind::Function, extent, None);
+            }
+            Test { name, params, body, .. } => match self.find_fn(self.cursor, name) {
+                Some(i) => {
+                    self.cursor = i + 1;
+                    let extent = self.extent.clone();
+                    self.function(name, params, None, body, SymbolKind::Function, extent, None);
+                }
+                // `test "name" { ... }`
+                None => self.scoped(|this| this.walk(body)),
+            },
+            Let { name, ty, value, .. } | Const { name, ty, value } => {
+                let span = self.find(name);
+                if let Some(ty) = ty {
+                    self.type_refs(ty);
+                }
+                self.walk(value);
+                if let Some(span) = span {
+                    let kind = if matches!(node, Const { .. }) { SymbolKind::Constant } else { SymbolKind::Variable };
+                    let extent = self.extent.clone();
+                    self.declare(name, kind, span, extent, None);
+                }
+            }
+            For { var, iter, body } => {
+                let span = self.find(var);
+                self.walk(iter);
+                self.scoped(|this| {
+                    if let Some(span) = span {
+                        this.declare(var, SymbolKind::Variable, span.clone(), span, None);
+                    }
+                    this.walk(body);
+                });
+            }
+            ListComp { expr, var, iter, filter } => {
+                // `[expr for var in iter if filter]`: the variable comes after its uses
+                if let Some(span) = expr.span() {
+                    self.cursor = self.token_at(span.end_line, span.end_col);
+                }
+                let span = self.find(var);
+                self.walk(iter);
+                self.scoped(|this| {
+                    if let Some(span) = span {
+                        this.declare(var, SymbolKind::Variable, span.clone(), span, None);
+                    }
+                    if let Some(filter) = filter {
+                        this.walk(filter);
+                    }
+                    this.walk(expr);
+                });
+            }
+            Lambda { params, body } => self.scoped(|this| {
+                for param in params {
+                    this.declare_here(param, SymbolKind::Parameter);
+                }
+                this.walk(body);
+            }),
+            IfLet { pattern, value, then_body, else_body } => {
+                let span = self.find(pattern);
+                self.walk(value);
+                self.scoped(|this| {
+                    if let Some(span) = span {
+                        this.declare(pattern, SymbolKind::Variable, span.clone(), span, None);
+                    }
+                    this.walk(then_body);
+                });
+                if let Some(else_body) = else_body {
+                    self.walk(else_body);
+                }
+            }
+            TryCatch { try_body, catch_var, catch_body } => {
+                self.walk(try_body);
+                self.scoped(|this| {
+                    this.declare_here(catch_var, SymbolKind::Variable);
+                    this.walk(catch_body);
+                });
+            }
+            Match { expr, arms } => {
+                self.walk(expr);
+                for arm in arms {
+                    let limit = self.limit;
+                    // The pattern lies between the previous arm and this one's guard or body
+                    if let Some(span) = arm.guard.as_ref().unwrap_or(&arm.body).span() {
+                        self.limit = self.token_at(span.line, span.col);
+                    }
+                    self.scoped(|this| {
+                        this.pattern(&arm.pattern);
+                        this.limit = limit;
+                        if let Some(guard) = &arm.guard {
+                            this.walk(guard);
+                        }
+                        this.walk(&arm.body);
+                    });
+                }
+            }
+            StructDef { name, fields } => {
+                let Some(span) = self.find(name) else { return };
+                let extent = self.extent.clone();
+                self.declare(name, SymbolKind::Struct, span, extent, None);
+                self.fields(name, fields);
+            }
+            EnumDef { name, variants } => {
+                let Some(span) = self.find(name) else { return };
+                let extent = self.extent.clone();
+                self.declare(name, SymbolKind::Enum, span, extent, None);
+                for variant in variants {
+                    let Some(span) = self.find(&variant.name) else { continue };
+                    self.declare(&variant.name, SymbolKind::Variant, span.clone(), span, Some(name));
+                    match &variant.data {
+                        VariantData::Unit => {}
+                        VariantData::Tuple(types) => {
+                            for ty in types {
+                                self.type_refs(ty);
+                            }
+                        }
+                        VariantData::Struct(fields) => self.fields(name, fields),
+                    }
+                }
+            }
+            TypeAlias { name, ty } => {
+                let Some(span) = self.find(name) else { return };
+                let extent = self.extent.clone();
+                self.declare(name, SymbolKind::TypeAlias, span, extent, None);
+                self.type_refs(ty);
+            }
+            Impl { ty, trait_name, methods } => {
+                if let Some(trait_name) = trait_name {
+                    self.reference(trait_name);
+                }
+                self.reference(ty);
+                let start = self.cursor;
+                for method in methods {
+                    if let Function { name, params, ret_type, body } = method.inner() {
+                        self.method(start, ty, name, params, ret_type.as_ref(), Some(&**body));
+                    }
+                }
+            }
+            TraitDef { name, required, methods } => {
+                let Some(span) = self.find(name) else { return };
+                let extent = self.extent.clone();
+                self.declare(name, SymbolKind::Trait, span, extent, None);
+                let start = self.cursor;
+                for method in required {
+                    self.method(start, name, method, &[], None, None);
+                }
+                for method in methods {
+                    if let Function { name: method, params, ret_type, body } = method.inner() {
+                        self.method(start, name, method, params, ret_type.as_ref(), Some(&**body));
+                    }
+                }
+            }
+            Use { path, alias } => {
+                let span = (self.cursor..self.end())
+                    .find(|&i| self.tokens[i].kind == TokenKind::String)
+                    .map_or_else(|| self.extent.clone(), |i| self.span_of(i, i));
+                self.imports.push(Import {
+                    path: crate::modules::resolve_import(path, Some(self.path), None).ok(),
+                    alias: alias.clone(),
+                    span,
+                });
+                // The alias is the last token; an unquoted path may spell the same name
+                let alias_at = alias.as_ref().and_then(|alias| {
+                    (self.cursor..self.end())
+                        .rev()
+                        .find(|&i| self.tokens[i].kind == TokenKind::Identifier && self.tokens[i].value == *alias)
+                });
+                if let (Some(alias), Some(i)) = (alias, alias_at) {
+                    let span = self.span_of(i, i);
+                    let extent = self.extent.clone();
+                    let id = self.declare(alias, SymbolKind::Module, span, extent, None);
+                    self.symbols[id].import = Some(self.imports.len() - 1);
+                }
+            }
+            Identifier(name) => {
+                if name == "self" {
+                    return;
+                }
+                // `Shape::Circle`, `Point::new`, `math::sqrt`
+                let mut parts = name.split("::");
+                let first = parts.next().unwrap_or_default();
+                let import = self.lookup(first).and_then(|id| self.symbols[id].import);
+                self.reference(first);
+                for part in parts {
+                    self.member(import, part);
+                }
+            }
+            FieldAccess { obj, field } | SafeNav { obj, field } => {
+                self.walk(obj);
+                let import = self.module_of(obj);
+                self.member(import, field);
+            }
+            MethodCall { obj, method, args } => {
+                self.walk(obj);
+                if method != "__safe_call__" {
+                    let import = self.module_of(obj);
+                    self.member(import, method);
+                }
+                for arg in args {
+                    self.walk(arg);
+                }
+            }
+            StructLiteral { name, fields } => {
+                self.reference(name);
+                for (field, value) in fields {
+                    self.member(None, field);
+                    self.walk(value);
+                }
+            }
+            NamedArg { name, value } => {
+                self.find(name);
+                self.walk(value);
+            }
+            AsCast { expr, target_type } => {
+                self.walk(expr);
+                self.type_refs(target_type);
+            }
+            InterpolatedString(segments) => self.interpolation(segments),
+            other => {
+                for child in other.children() {
+                    self.walk(child);
+                }
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn function(
+        &mut self,
+        name: &str,
+        params: &[Param],
+        ret_type: Option<&Type>,
+        body: &ASTNode,
+        kind: SymbolKind,
+        extent: Span,
+        container: Option<&str>,
+    ) {
+        let Some(span) = self.find(name) else { return };
+        // Declared before the body so recursive calls resolve
+        let id = self.declare(name, kind, span, extent, container);
+        self.symbols[id].params = param_names(params);
+        self.scoped(|this| {
+            for param in params {
+                // `self` is a keyword, not a name to find
+                if param.name == "self" {
+                    continue;
+                }
+                let Some(span) = this.find(&param.name) else { continue };
+                if let Some(ty) = &param.ty {
+                    this.type_refs(ty);
+                }
+                this.declare(&param.name, SymbolKind::Parameter, span.clone(), span, None);
+            }
+            if let Some(ty) = ret_type {
+                this.type_refs(ty);
+            }
+            this.walk(body);
+        });
+    }
+
+    /// A method of an impl or trait, found by its `fn` from token `start` on.
+    /// Methods carry no spans, so their extent runs to the closing brace.
+    fn method(
+        &mut self,
+        start: usize,
+        container: &str,
+        name: &str,
+        params: &[Param],
+        ret_type: Option<&Type>,
+        body: Option<&ASTNode>,
+    ) {
+        let Some(fn_at) = self.find_fn(start, name) else { return };
+        let limit = self.limit;
+        let end = match body {
+            Some(_) => self.block_end(fn_at).unwrap_or(fn_at + 1),
+            None => fn_at + 1,
+        };
+        self.cursor = fn_at + 1;
+        self.limit = end + 1;
+        let extent = self.span_of(fn_at, end);
+        match body {
+            Some(body) => self.function(name, params, ret_type, body, SymbolKind::Method, extent, Some(container)),
+            None => {
+                let span = self.span_of(fn_at + 1, fn_at + 1);
+                let id = self.declare(name, SymbolKind::Method, span, extent, Some(container));
+                self.symbols[id].params = param_names(params);
+            }
+        }
+        self.limit = limit;
+    }
+
+    fn fields(&mut self, container: &str, fields: &[(String, Type)]) {
+        for (field, ty) in fields {
+            let Some(span) = self.find(field) else { continue };
+            self.declare(field, SymbolKind::Field, span.clone(), span, Some(container));
+            self.type_refs(ty);
+        }
+    }
+
+    fn pattern(&mut self, pattern: &Pattern) {
+        match pattern {
+            Pattern::Identifier(name) => self.declare_here(name, SymbolKind::Variable),
+            Pattern::Or(patterns) | Pattern::Tuple(patterns) => {
+                for pattern in patterns {
+                    self.pattern(pattern);
+                }
+            }
+            Pattern::Struct { name, fields } => {
+                self.reference(name);
+                for (field, pattern) in fields {
+                    match pattern {
+                        // `Point { x, y }` binds the field names themselves
+                        Pattern::Identifier(binding) if binding == field => {
+                            self.declare_here(field, SymbolKind::Variable)
+                        }
+                        _ => {
+                            self.member(None, field);
+                            self.pattern(pattern);
+                        }
+                    }
+                }
+            }
+            Pattern::Enum { name, variant, data } => {
+                if !name.is_empty() {
+                    self.reference(name);
+                }
+                self.member(None, variant);
+                if let Some(data) = data {
+                    self.pattern(data);
+                }
+            }
+            Pattern::Wildcard | Pattern::Literal(_) => {}
+        }
+    }
+
+    /// Names used inside the `{...}` of an f-string
+    fn interpolation(&mut self, segments: &[(bool, String)]) {
+        let Some(at) = (self.cursor..self.end()).find(|&i| self.tokens[i].kind == TokenKind::FString) else {
+            return;
+        };
+        self.cursor = at + 1;
+        let token = &self.tokens[at];
+        let offset = |line: usize, col: usize| self.line_starts[line - 1] + col - 1;
+        let start = offset(token.line, token.col);
+        let text: String = self.chars[start..offset(token.end_line, token.end_col)].iter().collect();
+
+        let mut refs = Vec::new();
+        let mut from = 0;
+        for (_, expr) in segments.iter().filter(|(is_expr, _)| *is_expr) {
+            let Some(found) = text[from..].find(expr.as_str()) else { continue };
+            let base = start + text[..from + found].chars().count();
+            from += found + expr.len();
+
+            let expr_starts = line_starts(&expr.chars().collect::<Vec<_>>());
+            let tokens = Lexer::new(expr).tokenize();
+            for (i, tok) in tokens.iter().enumerate() {
+                if tok.kind != TokenKind::Identifier {
+                    continue;
+                }
+                let begin = base + expr_starts[tok.line - 1] + tok.col - 1;
+                let span = self.span_between(begin, begin + tok.value.chars().count());
+                let before = i.checked_sub(1).map(|p| &tokens[p].kind);
+                let target = if matches!(before, Some(TokenKind::Dot | TokenKind::SafeNav | TokenKind::DoubleColon)) {
+                    Target::Member(tok.value.clone())
+                } else {
+                    self.lookup(&tok.value).map_or_else(|| Target::Global(tok.value.clone()), Target::Local)
+                };
+                refs.push(Reference { span, target });
+            }
+        }
+        self.refs.extend(refs);
+    }
+}
+
+/// A symbol as the file it is declared in and its index there
+pub type SymbolId = (PathBuf, usize);
+
+/// What is under a position
+enum Hit {
+    Symbols(Vec<SymbolId>),
+    /// The path of an import
+    File(PathBuf),
+}
+
+/// The indexed files: documents open in the editor, the files they `use`,
+/// and those of the workspace folder
+#[derive(Debug, Default)]
+pub struct Workspace {
+    files: HashMap<PathBuf, FileIndex>,
+}
+
+impl Workspace {
+    pub fn new() -> Self {
+        Workspace::default()
+    }
+
+    pub fn file(&self, path: &Path) -> Option<&FileIndex> {
+        self.files.get(path)
+    }
+
+    /// Index `source` as the contents of `path`, and the files it uses that
+    /// are not indexed yet. Source that does not parse keeps the last index.
+    pub fn update(&mut self, path: &Path, source: &str) {
+        let index = match FileIndex::build(path, source) {
+            Ok(index) => index,
+            Err(_) => {
+                if let Some(index) = self.files.get_mut(path) {
+                    index.stale = true;
+                }
+                return;
+            }
+        };
+        let imports: Vec<PathBuf> = index.imports.iter().filter_map(|import| import.path.clone()).collect();
+        self.files.insert(path.to_path_buf(), index);
+        for import in imports {
+            if !self.files.contains_key(&import) {
+                self.load(&import);
+            }
+        }
+    }
+
+    /// Index `path` as it is on disk
+    pub fn load(&mut self, path: &Path) {
+        if let Ok(source) = fs::read_to_string(path) {
+            self.update(path, &source);
+        }
+    }
+
+    /// Index the `.knull` files under `root` that are not indexed yet
+    pub fn load_dir(&mut self, root: &Path) {
+        let entries = walkdir::WalkDir::new(root).into_iter().filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            entry.depth() == 0 || !(name.starts_with('.') || name == "target")
+        });
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|e| e == "knull") {
+                let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+                if !self.files.contains_key(&path) {
+                    self.load(&path);
+                }
+            }
+        }
+    }
+
+    /// The top-level items of `path` named `name`
+    fn globals(&self, path: &Path, name: &str, exported: bool) -> Vec<SymbolId> {
+        let Some(file) = self.files.get(path) else { return Vec::new() };
+        file.symbols
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.global && s.name == name && (!exported || file.exports(s)))
+            .map(|(i, _)| (path.to_path_buf(), i))
+            .collect()
+    }
+
+    fn members(&self, name: &str) -> Vec<SymbolId> {
+        let mut ids: Vec<SymbolId> = self
+            .files
+            .iter()
+            .flat_map(|(path, file)| {
+                file.symbols
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, s)| s.kind.is_member() && s.name == name)
+                    .map(move |(i, _)| (path.clone(), i))
+            })
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// The symbols a use of a name in `path` may refer to
+    fn resolve(&self, path: &Path, target: &Target) -> Vec<SymbolId> {
+        let Some(file) = self.files.get(path) else { return Vec::new() };
+        match target {
+            Target::Local(i) => vec![(path.to_path_buf(), *i)],
+            Target::Global(name) => {
+                let own = self.globals(path, name, false);
+                if !own.is_empty() {
+                    return own;
+                }
+                file.imports
+                    .iter()
+                    .filter(|import| import.alias.is_none())
+                    .filter_map(|import| import.path.as_deref())
+                    .flat_map(|import| self.globals(import, name, true))
+                    .collect()
+            }
+            Target::Export(i, name) => match file.imports.get(*i).and_then(|import| import.path.as_deref()) {
+                Some(import) => self.globals(import, name, true),
+                None => Vec::new(),
+            },
+            Target::Member(name) => self.members(name),
+        }
+    }
+
+    fn symbol(&self, (path, i): &SymbolId) -> &Symbol {
+        &self.files[path].symbols[*i]
+    }
+
+    fn at(&self, path: &Path, line: usize, col: usize) -> Option<Hit> {
+        let file = self.files.get(path)?;
+        if let Some(i) = file.symbols.iter().position(|s| contains(&s.span, line, col)) {
+            let symbol = &file.symbols[i];
+            // One method name may be declared for many types, and any of them may be the one called
+            if symbol.kind.is_member() {
+                return Some(Hit::Symbols(self.members(&symbol.name)));
+            }
+            return Some(Hit::Symbols(vec![(path.to_path_buf(), i)]));
+        }
+        if let Some(r) = file.refs.iter().find(|r| contains(&r.span, line, col)) {
+            return Some(Hit::Symbols(self.resolve(path, &r.target)));
+        }
+        let import = file.imports.iter().find(|import| contains(&import.span, line, col))?;
+        import.path.clone().map(Hit::File)
+    }
+
+    /// Where the name at a 1-based position is declared; on an import path, the imported file
+    pub fn definition(&self, path: &Path, line: usize, col: usize) -> Vec<Span> {
+        match self.at(path, line, col) {
+            Some(Hit::Symbols(ids)) => ids.iter().map(|id| self.symbol(id).span.clone()).collect(),
+            Some(Hit::File(file)) => vec![Span {
+                file: Some(Arc::from(file.to_string_lossy().as_ref())),
+                line: 1,
+                col: 1,
+                end_line: 1,
+                end_col: 1,
+            }],
+            None => Vec::new(),
+        }
+    }
+
+    /// Every use of the name at a 1-based position, in the indexed files
+    pub fn references(&self, path: &Path, line: usize, col: usize, include_declaration: bool) -> Vec<Span> {
+        match self.at(path, line, col) {
+            Some(Hit::Symbols(ids)) => self.uses(&ids, include_declaration),
+            _ => Vec::new(),
+        }
+    }
+
+    fn uses(&self, ids: &[SymbolId], include_declaration: bool) -> Vec<Span> {
+        let mut spans = Vec::new();
+        if include_declaration {
+            spans.extend(ids.iter().map(|id| self.symbol(id).span.clone()));
+        }
+        let mut paths: Vec<&PathBuf> = self.files.keys().collect();
+        paths.sort();
+        for path in paths {
+            let file = &self.files[path];
+            for r in &file.refs {
+                let name = file.name_of(&r.target);
+                if !ids.iter().any(|id| self.symbol(id).name == name) {
+                    continue;
+                }
+                if self.resolve(path, &r.target).iter().any(|id| ids.contains(id)) {
+                    spans.push(r.span.clone());
+                }
+            }
+        }
+        spans
+    }
+
+    /// The spans to replace to rename the name at a 1-based position
+    pub fn rename(&self, path: &Path, line: usize, col: usize, new_name: &str) -> Result<Vec<Span>, String> {
+        if !is_identifier(new_name) {
+            return Err(format!("'{}' is not a valid name", new_name));
+        }
+        let ids = match self.at(path, line, col) {
+            Some(Hit::Symbols(ids)) if !ids.is_empty() => ids,
+            _ => return Err("No symbol to rename here".to_string()),
+        };
+        let spans = self.uses(&ids, true);
+        let stale = spans.iter().filter_map(|span| span.file.as_deref()).find(|file| {
+            self.files.get(Path::new(file)).is_some_and(|index| index.stale)
+        });
+        if let Some(file) = stale {
+            return Err(format!("{} has parse errors; fix them before renaming", file));
+        }
+        Ok(spans)
+    }
+
+    /// The symbols under a 1-based position
+    pub fn symbols_at(&self, path: &Path, line: usize, col: usize) -> Vec<&Symbol> {
+        match self.at(path, line, col) {
+            Some(Hit::Symbols(ids)) => ids.iter().map(|id| self.symbol(id)).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The names in scope at a 1-based position: the locals of the enclosing
+    /// function declared before it, the file's top-level items and what its
+    /// imports export. A later declaration hides an earlier one of the same name.
+    pub fn visible(&self, path: &Path, line: usize, col: usize) -> Vec<&Symbol> {
+        let Some(file) = self.files.get(path) else { return Vec::new() };
+        let functions: Vec<&Symbol> =
+            file.symbols.iter().filter(|s| matches!(s.kind, SymbolKind::Function | SymbolKind::Method)).collect();
+        // The innermost function around a span, by the smallest extent
+        let enclosing = |span: &Span| {
+            functions
+                .iter()
+                .filter(|f| encloses(&f.extent, span))
+                .max_by_key(|f| (f.extent.line, f.extent.col))
+                .map(|f| (f.extent.line, f.extent.col))
+        };
+        let here = Span { file: None, line, col, end_line: line, end_col: col };
+        let scope = enclosing(&here);
+
+        let mut found: Vec<&Symbol> = file
+            .imports
+            .iter()
+            .filter(|import| import.alias.is_none())
+            .filter_map(|import| self.files.get(import.path.as_deref()?))
+            .flat_map(|import| import.symbols.iter().filter(|s| import.exports(s)))
+            .collect();
+        found.extend(file.symbols.iter().filter(|s| {
+            s.global || !s.kind.is_member() && (s.span.line, s.span.col) < (line, col) && enclosing(&s.span) == scope
+        }));
+        let mut seen = HashSet::new();
+        let mut visible: Vec<&Symbol> = found.into_iter().rev().filter(|&s| seen.insert(s.name.as_str())).collect();
+        visible.sort_by(|a, b| a.name.cmp(&b.name));
+        visible
+    }
+
+    /// One field or method of each name declared in the indexed files
+    pub fn member_names(&self) -> Vec<&Symbol> {
+        let mut members: Vec<&Symbol> =
+            self.files.values().flat_map(|file| &file.symbols).filter(|s| s.kind.is_member()).collect();
+        members.sort_by(|a, b| a.name.cmp(&b.name));
+        members.dedup_by(|a, b| a.name == b.name);
+        members
+    }
+
+    /// Top-level items and members whose name contains `query`, ignoring case
+    pub fn search(&self, query: &str) -> Vec<&Symbol> {
+        let query = query.to_lowercase();
+        let mut found: Vec<&Symbol> = self
+            .files
+            .values()
+            .flat_map(|file| &file.symbols)
+            .filter(|s| (s.global || s.kind.is_member()) && s.name.to_lowercase().contains(&query))
+            .collect();
+        found.sort_by(|a, b| (&a.name, &a.span.file).cmp(&(&b.name, &b.span.file)));
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1-based position of the `nth` occurrence of `needle` in `source`
+    fn pos(source: &str, needle: &str, nth: usize) -> (usize, usize) {
+        let offset = source.match_indices(needle).nth(nth).unwrap().0;
+        let before = &source[..offset];
+        let line = before.matches('\n').count() + 1;
+        let col = before.rsplit('\n').next().unwrap().chars().count() + 1;
+        (line, col)
+    }
+
+    fn lines(spans: &[Span]) -> Vec<(usize, usize)> {
+        spans.iter().map(|s| (s.line, s.col)).collect()
+    }
+
+    #[test]
+    fn test_scopes_and_shadowing() {
+        let src = "fn add(a, b) {\n    return a + b\n}\nfn main() {\n    let x = 1\n    let y = add(x, 2)\n    let x = x + y\n    println(f\"{x}\")\n    for i in range(0, 3) { println(i) }\n}\n";
+        let path = Path::new("/virtual/main.knull");
+        let mut ws = Workspace::new();
+        ws.update(path, src);
+
+        // `add` resolves to the function from its call
+        let (line, col) = pos(src, "add", 1);
+        assert_eq!(lines(&ws.definition(path, line, col)), vec![pos(src, "add", 0)]);
+
+        // The first `x` is used once, by the second `let x`; the f-string sees the second
+        let (line, col) = pos(src, "x", 0);
+        assert_eq!(lines(&ws.references(path, line, col, false)), vec![pos(src, "x", 1), pos(src, "x", 3)]);
+        let (line, col) = pos(src, "{x}", 0);
+        assert_eq!(lines(&ws.definition(path, line, col + 1)), vec![pos(src, "x", 2)]);
+
+        let (line, col) = pos(src, "i)", 0);
+        assert_eq!(lines(&ws.definition(path, line, col)), vec![pos(src, "i in", 0)]);
+        assert!(ws.rename(path, line, col, "2bad").is_err());
+    }
+
+    #[test]
+    fn test_members_and_outline() {
+        let src = "struct Point { x: int, y: int }\nimpl Point {\n    fn new(x, y) { return Point { x: x, y: y } }\n    fn norm(self) { return self.x * self.x }\n}\nlet p = Point::new(1, 2)\nprintln(p.norm())\n";
+        let path = Path::new("/virtual/point.knull");
+        let mut ws = Workspace::new();
+        ws.update(path, src);
+
+        let (line, col) = pos(src, "norm", 1);
+        assert_eq!(lines(&ws.definition(path, line, col)), vec![pos(src, "norm", 0)]);
+        let (line, col) = pos(src, "new", 1);
+        assert_eq!(lines(&ws.definition(path, line, col)), vec![pos(src, "new", 0)]);
+
+        // Field `x` is used by the literal and both accesses, not by parameter `x`
+        let (line, col) = pos(src, "x", 0);
+        assert_eq!(
+            lines(&ws.references(path, line, col, false)),
+            vec![pos(src, "x:", 1), pos(src, "x * ", 0), pos(src, "x }", 0)]
+        );
+
+        let outline = ws.file(path).unwrap().outline();
+        let names: Vec<(&str, usize)> = outline.iter().map(|(s, members)| (s.name.as_str(), members.len())).collect();
+        assert_eq!(names, vec![("Point", 4), ("p", 0)]);
+    }
+
+    #[test]
+    fn test_visible_names() {
+        let src = "let limit = 3\nfn grow(n) {\n    let step = 2\n    \n    let later = 1\n    return n + step + later\n}\nfn other() { let hidden = 0 }\n";
+        let path = Path::new("/virtual/visible.knull");
+        let mut ws = Workspace::new();
+        ws.update(path, src);
+
+        let names: Vec<&str> = ws.visible(path, 4, 5).iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["grow", "limit", "n", "other", "step"]);
+        let grow = ws.visible(path, 4, 5).into_iter().find(|s| s.name == "grow").unwrap();
+        assert_eq!(grow.params, vec!["n"]);
+    }
+
+    #[test]
+    fn test_across_files() {
+        let dir = std::env::temp_dir().join(format!("knull_symbols_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let lib = dir.join("geometry.knull");
+        fs::write(&lib, "pub fn area(w, h) {\n    return w * h\n}\n").unwrap();
+        let lib = lib.canonicalize().unwrap();
+        let main = dir.canonicalize().unwrap().join("main.knull");
+        let src = "use \"geometry\"\nuse \"geometry\" as geo\nprintln(area(2, 3))\nprintln(geo.area(4, 5))\n";
+        fs::write(&main, src).unwrap();
+
+        let mut ws = Workspace::new();
+        ws.update(&main, src);
+        assert!(ws.file(&lib).is_some(), "imports are indexed from disk");
+
+        let (line, col) = pos(src, "area", 0);
+        let found = ws.definition(&main, line, col);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].file.as_deref(), Some(lib.to_string_lossy().as_ref()));
+        assert_eq!((found[0].line, found[0].col), (1, 8));
+
+        let (line, col) = pos(src, "\"geometry\"", 0);
+        assert_eq!(ws.definition(&main, line, col)[0].file.as_deref(), Some(lib.to_string_lossy().as_ref()));
+
+        // Renaming from the declaration reaches both call styles
+        let spans = ws.rename(&lib, 1, 8, "surface").unwrap();
+        assert_eq!(spans.len(), 3);
+
+        // A broken edit keeps the old index but blocks renames
+        ws.update(&main, "fn main( {");
+        assert!(ws.file(&main).unwrap().stale);
+        assert!(ws.rename(&lib, 1, 8, "surface").is_err());
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,455 @@
+//! Knull Test Runner
+//!
+//! `knull test` collects the `test "name" { ... }` blocks and `#[test] fn`s of
+//! every file under tests/, test/ and src/tests, and runs each one in a fresh
+//! interpreter so tests never see each other's state. A file's top-level
+//! statements run before each of its tests, as setup. A file without tests
+//! runs whole, and passes if it finishes without an error.
+//!
+//! With `--bench`, the `#[bench] fn`s are collected instead and timed one at a
+//! time by `crate::bench`; with `--property`, the `#[property] fn`s, checked by
+//! `crate::property`. Files without any are left out.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::bench::{self, BenchConfig, BenchStats};
+use crate::interpreter::{with_large_stack, ErrorKind, Interpreter, RuntimeError};
+use crate::parser::{ASTNode, Parser, TestKind};
+use crate::property::{self, PropertyConfig};
+
+/// Directories searched for test files, relative to the working directory
+pub const TEST_DIRS: &[&str] = &["tests", "test", "src/tests"];
+
+/// Settings for a `knull test` run
+#[derive(Debug, Clone, Default)]
+pub struct TestOptions {
+    /// Files or directories to take tests from instead of `TEST_DIRS`
+    pub paths: Vec<PathBuf>,
+    /// Only run tests whose `file::name` contains this
+    pub filter: Option<String>,
+    /// Tests to run at once; defaults to the number of CPUs
+    pub jobs: Option<usize>,
+    /// Write a JUnit XML report here
+    pub junit: Option<PathBuf>,
+    /// Write a JSON report here
+    pub json: Option<PathBuf>,
+    /// Compare benchmarks against the baseline saved in this file
+    pub baseline: Option<PathBuf>,
+    /// Save benchmark results as a baseline to this file
+    pub save_baseline: Option<PathBuf>,
+    /// How long to time each benchmark
+    pub bench: BenchConfig,
+    /// How many cases to check each property with, and the seed
+    pub property: PropertyConfig,
+}
+
+/// A test file that parsed, shared by the cases taken from it
+struct Program {
+    source: String,
+    ast: ASTNode,
+}
+
+/// One test to run
+pub struct TestCase {
+    pub path: PathBuf,
+    /// The test's name, or `None` for a file without tests, which runs whole
+    pub name: Option<String>,
+    pub kind: TestKind,
+    program: Option<Arc<Program>>,
+}
+
+impl TestCase {
+    /// `file::name`, what `--filter` matches and reports show
+    pub fn id(&self) -> String {
+        match &self.name {
+            Some(name) => format!("{}::{}", self.path.display(), name),
+            None => self.path.display().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Passed,
+    /// The error the test ended with, formatted as `knull run` shows it
+    Failed(String),
+    Skipped,
+}
+
+impl Outcome {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Outcome::Passed => "passed",
+            Outcome::Failed(_) => "failed",
+            Outcome::Skipped => "skipped",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TestResult {
+    pub file: String,
+    pub name: String,
+    pub outcome: Outcome,
+    pub duration: Duration,
+    /// Timings, for a benchmark that ran to the end
+    pub stats: Option<BenchStats>,
+}
+
+impl TestResult {
+    pub fn id(&self) -> String {
+        if self.name == self.file {
+            self.file.clone()
+        } else {
+            format!("{}::{}", self.file, self.name)
+        }
+    }
+}
+
+/// Names of the tests of `kind` a program defines, in source order
+pub fn test_names(ast: &ASTNode, kind: TestKind) -> Vec<String> {
+    let items = match ast {
+        ASTNode::Program(items) => items.as_slice(),
+        other => std::slice::from_ref(other),
+    };
+    items
+        .iter()
+        .filter_map(|item| match item.inner() {
+            ASTNode::Test { name, kind: k, .. } if *k == kind => Some(name.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Find the `.knull` files among `roots` (files, or directories to search)
+/// and list their tests of `kind`, keeping those whose id contains `filter`
+pub fn discover(roots: &[PathBuf], kind: TestKind, filter: Option<&str>) -> Vec<TestCase> {
+    let is_knull = |path: &Path| path.extension().is_some_and(|e| e == "knull");
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for root in roots {
+        if root.is_file() {
+            paths.push(root.clone());
+        } else if let Ok(entries) = fs::read_dir(root) {
+            paths.extend(entries.flatten().map(|entry| entry.path()).filter(|path| is_knull(path)));
+        }
+    }
+    paths.sort();
+    paths.dedup();
+
+    let mut cases = Vec::new();
+    for path in paths {
+        cases.extend(file_cases(&path, kind));
+    }
+    if let Some(filter) = filter {
+        cases.retain(|case| case.id().contains(filter));
+    }
+    cases
+}
+
+/// The cases of one file: its tests, or the whole file when it has none or
+/// does not parse (running it then reports the parse error). Benchmarks and
+/// properties have no whole-file fallback.
+fn file_cases(path: &Path, kind: TestKind) -> Vec<TestCase> {
+    let whole = || match kind {
+        TestKind::Test => vec![TestCase { path: path.to_path_buf(), name: None, kind, program: None }],
+        TestKind::Bench | TestKind::Property => Vec::new(),
+    };
+    let Ok(source) = fs::read_to_string(path) else {
+        return whole();
+    };
+    let file = path.to_string_lossy().to_string();
+    let Ok(ast) = Parser::new(&source).with_file(&file).parse() else {
+        return whole();
+    };
+    let names = test_names(&ast, kind);
+    if names.is_empty() {
+        return whole();
+    }
+    let program = Arc::new(Program { source, ast });
+    names
+        .into_iter()
+        .map(|name| TestCase { path: path.to_path_buf(), name: Some(name), kind, program: Some(program.clone()) })
+        .collect()
+}
+
+/// Run one case in a fresh interpreter, on a stack of its own
+pub fn run_case(case: &TestCase, options: &TestOptions) -> TestResult {
+    let file = case.path.display().to_string();
+    let start = Instant::now();
+    let mut stats = None;
+    let outcome = if file.contains("@ignore") {
+        Outcome::Skipped
+    } else {
+        let result = with_large_stack(|| match (&case.name, &case.program) {
+            (Some(name), Some(program)) => {
+                let mut interp = Interpreter::new()
+                    .with_file(&case.path)
+                    .with_max_depth(crate::cli::resolve_max_depth(&case.path, None));
+                let format = |e: &RuntimeError| crate::cli::format_runtime_error(&program.source, &file, e);
+                match case.kind {
+                    TestKind::Test => interp.run_test(&program.ast, name).map_err(|e| format(&e)),
+                    TestKind::Bench => interp
+                        .with_test(&program.ast, name, |interp, test| {
+                            bench::measure(|| interp.call_test(test, Vec::new()).map(|_| ()), &options.bench)
+                        })
+                        .map(|measured| stats = Some(measured))
+                        .map_err(|e| format(&e)),
+                    TestKind::Property => {
+                        let found = interp.with_test(&program.ast, name, |interp, test| {
+                            let ASTNode::Test { params, .. } = test else { unreachable!() };
+                            property::check(params, &options.property, |args| interp.call_test(test, args))
+                                .map_err(|e| RuntimeError::new(ErrorKind::Error, e))
+                        });
+                        match found {
+                            Ok(None) => Ok(()),
+                            Ok(Some(found)) => Err(found.describe(&options.property, &case.id(), &format(&found.error))),
+                            Err(e) => Err(format(&e)),
+                        }
+                    }
+                }
+            }
+            _ => crate::cli::run_file(&case.path, false, false, None),
+        });
+        match result {
+            Ok(()) => Outcome::Passed,
+            Err(e) => Outcome::Failed(e),
+        }
+    };
+    TestResult {
+        name: case.name.clone().unwrap_or_else(|| file.clone()),
+        file,
+        outcome,
+        duration: start.elapsed(),
+        stats,
+    }
+}
+
+/// Run `cases` on up to `jobs` threads, handing each result to `report` as it
+/// comes in. The returned results are in the order of `cases`.
+pub fn run_all(
+    cases: &[TestCase],
+    jobs: usize,
+    options: &TestOptions,
+    report: impl Fn(&TestResult) + Sync,
+) -> Vec<TestResult> {
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(cases.len()));
+    std::thread::scope(|s| {
+        for _ in 0..jobs.clamp(1, cases.len().max(1)) {
+            s.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(case) = cases.get(i) else { break };
+                let result = run_case(case, options);
+                report(&result);
+                results.lock().unwrap().push((i, result));
+            });
+        }
+    });
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// `(passed, failed, skipped)`
+pub fn counts<'a>(results: impl IntoIterator<Item = &'a TestResult>) -> (usize, usize, usize) {
+    results.into_iter().fold((0, 0, 0), |(passed, failed, skipped), r| match r.outcome {
+        Outcome::Passed => (passed + 1, failed, skipped),
+        Outcome::Failed(_) => (passed, failed + 1, skipped),
+        Outcome::Skipped => (passed, failed, skipped + 1),
+    })
+}
+
+/// The benchmark timings among `results`, as a baseline to save
+pub fn to_baseline(results: &[TestResult]) -> bench::Baseline {
+    bench::Baseline {
+        benchmarks: results.iter().filter_map(|r| Some((r.id(), r.stats.clone()?))).collect(),
+    }
+}
+
+/// Error text without the terminal colours `knull run` adds
+fn plain(text: &str) -> String {
+    let ansi = regex::Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+    ansi.replace_all(text, "").trim_end().to_string()
+}
+
+/// The results as a JSON document
+pub fn to_json(results: &[TestResult]) -> String {
+    let (passed, failed, skipped) = counts(results);
+    let total: Duration = results.iter().map(|r| r.duration).sum();
+    let tests: Vec<serde_json::Value> = results
+        .iter()
+        .map(|r| {
+            let mut test = serde_json::json!({
+                "file": r.file,
+                "name": r.name,
+                "status": r.outcome.label(),
+                "duration_ms": r.duration.as_secs_f64() * 1000.0,
+            });
+            if let Outcome::Failed(msg) = &r.outcome {
+                test["message"] = plain(msg).into();
+            }
+            test
+        })
+        .collect();
+    let report = serde_json::json!({
+        "passed": passed,
+        "failed": failed,
+        "skipped": skipped,
+        "duration_ms": total.as_secs_f64() * 1000.0,
+        "tests": tests,
+    });
+    serde_json::to_string_pretty(&report).unwrap()
+}
+
+/// The results as JUnit XML, one `<testsuite>` per file
+pub fn to_junit(results: &[TestResult]) -> String {
+    use quick_xml::escape::escape;
+
+    let suite_header = |tag: &str, name: &str, results: &[&TestResult]| {
+        let (_, failed, skipped) = counts(results.iter().copied());
+        let time: Duration = results.iter().map(|r| r.duration).sum();
+        format!(
+            "<{} name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">\n",
+            tag,
+            escape(name),
+            results.len(),
+            failed,
+            skipped,
+            time.as_secs_f64()
+        )
+    };
+
+    let all: Vec<&TestResult> = results.iter().collect();
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&suite_header("testsuites", "knull", &all));
+    let mut files: Vec<&str> = Vec::new();
+    for r in results {
+        if !files.contains(&r.file.as_str()) {
+            files.push(&r.file);
+        }
+    }
+    for file in files {
+        let suite: Vec<&TestResult> = results.iter().filter(|r| r.file == file).collect();
+        out.push_str("  ");
+        out.push_str(&suite_header("testsuite", file, &suite));
+        for r in suite {
+            out.push_str(&format!(
+                "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+                escape(&r.name),
+                escape(&r.file),
+                r.duration.as_secs_f64()
+            ));
+            match &r.outcome {
+                Outcome::Passed => out.push_str("/>\n"),
+                Outcome::Skipped => out.push_str(">\n      <skipped/>\n    </testcase>\n"),
+                Outcome::Failed(msg) => {
+                    let msg = plain(msg);
+                    let summary = msg.lines().find(|l| !l.trim().is_empty()).unwrap_or("").trim();
+                    out.push_str(&format!(
+                        ">\n      <failure message=\"{}\">{}</failure>\n    </testcase>\n",
+                        escape(summary),
+                        escape(&msg)
+                    ));
+                }
+            }
+        }
+        out.push_str("  </testsuite>\n");
+    }
+    out.push_str("</testsuites>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn case(src: &str, name: &str, kind: TestKind) -> TestCase {
+        TestCase {
+            path: PathBuf::from("t.knull"),
+            name: Some(name.to_string()),
+            kind,
+            program: Some(Arc::new(Program { source: src.to_string(), ast: Parser::new(src).parse().unwrap() })),
+        }
+    }
+
+    #[test]
+    fn test_each_test_runs_in_a_fresh_interpreter() {
+        let src = "let mut hits = 0\nfn double(x) { x * 2 }\ntest \"counts\" { hits = hits + 1\n assert_eq(hits, 1) }\n#[test]\nfn doubles() { hits = hits + 1\n assert_eq(hits, 1)\n assert_eq(double(2), 4) }\ntest \"fails\" { assert_eq(double(2), 5) }\nfn main() { throw \"main must not run\" }";
+        let ast = Parser::new(src).parse().unwrap();
+        assert_eq!(test_names(&ast, TestKind::Test), ["counts", "doubles", "fails"]);
+        assert!(test_names(&ast, TestKind::Bench).is_empty());
+
+        let cases: Vec<TestCase> = test_names(&ast, TestKind::Test).iter().map(|name| case(src, name, TestKind::Test)).collect();
+        let results = run_all(&cases, 2, &TestOptions::default(), |_| {});
+        assert_eq!(results[0].outcome, Outcome::Passed);
+        assert_eq!(results[1].outcome, Outcome::Passed);
+        match &results[2].outcome {
+            Outcome::Failed(msg) => assert!(plain(msg).contains("expected 5, got 4"), "{}", msg),
+            other => panic!("expected a failure, got {:?}", other),
+        }
+        assert_eq!(counts(&results), (2, 1, 0));
+        assert_eq!(results[2].id(), "t.knull::fails");
+    }
+
+    #[test]
+    fn test_bench_cases_are_timed() {
+        let src = "fn work(n) { n * 2 }\n#[bench]\nfn doubling() { work(21) }\n#[test]\nfn not_a_bench() { }";
+        let ast = Parser::new(src).parse().unwrap();
+        assert_eq!(test_names(&ast, TestKind::Bench), ["doubling"]);
+
+        let options = TestOptions {
+            bench: BenchConfig { warmup_ns: 1_000_000, measure_ns: 2_000_000, samples: 5 },
+            ..TestOptions::default()
+        };
+        let result = run_case(&case(src, "doubling", TestKind::Bench), &options);
+        assert_eq!(result.outcome, Outcome::Passed);
+        let stats = result.stats.clone().unwrap();
+        assert!(stats.iterations >= 5 && stats.mean_ns > 0.0);
+        assert_eq!(to_baseline(&[result]).benchmarks["t.knull::doubling"], stats);
+    }
+
+    #[test]
+    fn test_property_cases_report_counterexample() {
+        let src = "#[property]\nfn short(xs: [int]) { assert(len(xs) < 3, \"too long\") }";
+        let ast = Parser::new(src).parse().unwrap();
+        assert_eq!(test_names(&ast, TestKind::Property), ["short"]);
+
+        let options = TestOptions { property: PropertyConfig { cases: 100, seed: 3 }, ..TestOptions::default() };
+        let result = run_case(&case(src, "short", TestKind::Property), &options);
+        match &result.outcome {
+            Outcome::Failed(msg) => {
+                let msg = plain(msg);
+                assert!(msg.contains("xs = [0, 0, 0]"), "{}", msg);
+                assert!(msg.contains("too long"), "{}", msg);
+                assert!(msg.contains("--seed 3"), "{}", msg);
+            }
+            other => panic!("expected a failure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_reports() {
+        let results = vec![
+            TestResult { file: "a.knull".into(), name: "ok".into(), outcome: Outcome::Passed, duration: Duration::from_millis(2), stats: None },
+            TestResult {
+                file: "a.knull".into(),
+                name: "<bad>".into(),
+                outcome: Outcome::Failed("\x1b[1;91mAssertionError:\x1b[0m expected 1, got 2".into()),
+                duration: Duration::from_millis(1),
+                stats: None,
+            },
+        ];
+        let json: serde_json::Value = serde_json::from_str(&to_json(&results)).unwrap();
+        assert_eq!(json["failed"], 1);
+        assert_eq!(json["tests"][1]["message"], "AssertionError: expected 1, got 2");
+
+        let xml = to_junit(&results);
+        assert!(xml.contains("<testsuite name=\"a.knull\" tests=\"2\" failures=\"1\" skipped=\"0\" time=\"0.003\">"));
+        assert!(xml.contains("<testcase name=\"&lt;bad&gt;\""));
+        assert!(xml.contains("<failure message=\"AssertionError: expected 1, got 2\">"));
+    }
+}
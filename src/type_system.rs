@@ -7,6 +7,7 @@ use crate::effects::{EffectChecker, EffectSet};
 use crate::linear_check::LinearChecker;
 use crate::parser::ASTNode;
 use crate::parser::Literal;
+use crate::parser::Span;
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -20,10 +21,70 @@ pub enum Type {
     Unknown,
     Linear(Box<Type>),
     Capability(CapabilityType),
-    EffectType(EffectSet),
+    Effect(EffectSet),
     Resource(ResourceType),
 }
 
+impl Type {
+    /// The type an annotation names; `Unknown` for those not checked
+    pub fn declared(ty: Option<&crate::parser::Type>) -> Type {
+        use crate::parser::Type as Ann;
+        match ty {
+            Some(Ann::I8 | Ann::I16 | Ann::I32 | Ann::I64 | Ann::I128) => Type::Int,
+            Some(Ann::U8 | Ann::U16 | Ann::U32 | Ann::U64 | Ann::U128) => Type::Int,
+            Some(Ann::F32 | Ann::F64) => Type::Float,
+            Some(Ann::Bool) => Type::Bool,
+            Some(Ann::String) => Type::String,
+            Some(Ann::Void) => Type::Void,
+            Some(Ann::Custom(name)) => Type::named(name),
+            _ => Type::Unknown,
+        }
+    }
+
+    /// The type called `name` in annotations and builtin signatures
+    pub fn named(name: &str) -> Type {
+        match name {
+            "int" | "i64" => Type::Int,
+            "float" => Type::Float,
+            "string" | "str" => Type::String,
+            "bool" => Type::Bool,
+            "void" => Type::Void,
+            _ => Type::Unknown,
+        }
+    }
+
+    fn is_number(&self) -> bool {
+        matches!(self, Type::Int | Type::Float)
+    }
+
+    /// Whether a value of this type may go where `expected` is declared.
+    /// Unknown types and `null` fit anywhere; ints widen to floats.
+    pub fn fits(&self, expected: &Type) -> bool {
+        match (self, expected) {
+            (Type::Unknown | Type::Null, _) | (_, Type::Unknown) => true,
+            (Type::Int, Type::Float) => true,
+            (actual, expected) => actual == expected,
+        }
+    }
+
+    /// The name used for this type in messages
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Type::Int => "int",
+            Type::Float => "float",
+            Type::String => "string",
+            Type::Bool => "bool",
+            Type::Void => "void",
+            Type::Null => "null",
+            Type::Linear(_) => "linear",
+            Type::Capability(_) => "capability",
+            Type::Effect(_) => "effect",
+            Type::Resource(_) => "resource",
+            Type::Unknown => "unknown",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct CapabilityType {
     pub name: String,
@@ -38,18 +99,34 @@ pub struct ResourceType {
 }
 
 pub struct TypeChecker {
-    scopes: Vec<HashMap<String, Type>>,
-    errors: Vec<String>,
+    scopes: Vec<HashMap<String, Var>>,
+    /// Parameter and return types of top-level functions
+    functions: HashMap<String, (Vec<Type>, Type)>,
+    /// The function being checked and its declared return type
+    returns: Option<(String, Type)>,
+    /// Span of the innermost node being checked that has one
+    span: Option<Span>,
+    errors: Vec<(String, Option<Span>)>,
     linear_checker: LinearChecker,
     effect_checker: EffectChecker,
     enable_linear: bool,
     enable_effects: bool,
 }
 
+/// A variable's type, and whether it was annotated or inferred
+#[derive(Debug, Clone)]
+struct Var {
+    ty: Type,
+    declared: bool,
+}
+
 impl TypeChecker {
     pub fn new() -> Self {
         TypeChecker {
             scopes: vec![HashMap::new()],
+            functions: HashMap::new(),
+            returns: None,
+            span: None,
             errors: Vec::new(),
             linear_checker: LinearChecker::new(),
             effect_checker: EffectChecker::new(),
@@ -59,60 +136,43 @@ impl TypeChecker {
     }
 
     pub fn with_linear_types(enable: bool) -> Self {
-        TypeChecker {
-            scopes: vec![HashMap::new()],
-            errors: Vec::new(),
-            linear_checker: LinearChecker::new(),
-            effect_checker: EffectChecker::new(),
-            enable_linear: enable,
-            enable_effects: false,
-        }
+        TypeChecker { enable_linear: enable, ..Self::new() }
     }
 
     pub fn with_effects(enable: bool) -> Self {
-        TypeChecker {
-            scopes: vec![HashMap::new()],
-            errors: Vec::new(),
-            linear_checker: LinearChecker::new(),
-            effect_checker: EffectChecker::new(),
-            enable_linear: false,
-            enable_effects: enable,
-        }
+        TypeChecker { enable_effects: enable, ..Self::new() }
     }
 
     pub fn with_all_features() -> Self {
-        TypeChecker {
-            scopes: vec![HashMap::new()],
-            errors: Vec::new(),
-            linear_checker: LinearChecker::new(),
-            effect_checker: EffectChecker::new(),
-            enable_linear: true,
-            enable_effects: true,
-        }
+        TypeChecker { enable_linear: true, enable_effects: true, ..Self::new() }
     }
 
     pub fn check(&mut self, ast: &ASTNode) -> Result<(), String> {
         self.check_node(ast)?;
 
         if self.enable_linear {
-            if let Err(e) = self.linear_checker.check(ast) {
-                self.errors.push(e);
-            }
+            let _ = self.linear_checker.check(ast);
+            self.errors.extend_from_slice(self.linear_checker.errors());
         }
 
         if self.enable_effects {
-            if let Err(e) = self.effect_checker.check(ast) {
-                self.errors.push(e);
-            }
+            let _ = self.effect_checker.check(ast);
+            self.errors.extend_from_slice(self.effect_checker.errors());
         }
 
         if self.errors.is_empty() {
             Ok(())
         } else {
-            Err(self.errors.join("\n"))
+            let messages: Vec<_> = self.errors.iter().map(|(message, _)| message.as_str()).collect();
+            Err(messages.join("\n"))
         }
     }
 
+    /// What `check` found, each with the span of the node it is about
+    pub fn errors(&self) -> &[(String, Option<Span>)] {
+        &self.errors
+    }
+
     pub fn get_linear_drop_code(&self) -> &[crate::linear_check::DropInstruction] {
         self.linear_checker.get_drop_code()
     }
@@ -121,6 +181,10 @@ impl TypeChecker {
         self.effect_checker.infer_effects(ast)
     }
 
+    fn error(&mut self, message: String) {
+        self.errors.push((message, self.span.clone()));
+    }
+
     fn push_scope(&mut self) {
         self.scopes.push(HashMap::new());
     }
@@ -129,38 +193,61 @@ impl TypeChecker {
         self.scopes.pop();
     }
 
-    fn current_scope(&mut self) -> &mut HashMap<String, Type> {
-        self.scopes.last_mut().expect("No scope available")
+    fn declare(&mut self, name: &str, ty: Type, declared: bool) {
+        let scope = self.scopes.last_mut().expect("No scope available");
+        scope.insert(name.to_string(), Var { ty, declared });
     }
 
-    fn find_variable(&self, name: &str) -> Option<Type> {
-        for scope in self.scopes.iter().rev() {
-            if let Some(ty) = scope.get(name) {
-                return Some(ty.clone());
-            }
-        }
-        None
+    fn find_variable(&mut self, name: &str) -> Option<&mut Var> {
+        self.scopes.iter_mut().rev().find_map(|scope| scope.get_mut(name))
     }
 
     fn check_node(&mut self, node: &ASTNode) -> Result<Type, String> {
         match node {
+            ASTNode::Spanned { span, node } => {
+                let outer = self.span.replace(span.clone());
+                let ty = self.check_node(node);
+                self.span = outer;
+                ty
+            }
             ASTNode::Program(items) => {
+                for item in items {
+                    if let ASTNode::Function { name, params, ret_type, .. } = item.inner() {
+                        let params = params.iter().map(|p| Type::declared(p.ty.as_ref())).collect();
+                        self.functions.insert(name.clone(), (params, Type::declared(ret_type.as_ref())));
+                    }
+                }
                 for item in items {
                     self.check_node(item)?;
                 }
                 Ok(Type::Void)
             }
-            ASTNode::Function {
-                name: _,
-                params: _,
-                body,
-                ..
-            } => {
+            ASTNode::Function { name, params, ret_type, body }
+            | ASTNode::AsyncFunction { name, params, ret_type, body } => {
                 self.push_scope();
+                for param in params {
+                    let ty = Type::declared(param.ty.as_ref());
+                    let declared = ty != Type::Unknown;
+                    self.declare(&param.name, ty, declared);
+                }
+                let returns = (name.clone(), Type::declared(ret_type.as_ref()));
+                let outer = self.returns.replace(returns);
                 self.check_node(body)?;
+                self.returns = outer;
                 self.pop_scope();
                 Ok(Type::Void)
             }
+            ASTNode::Lambda { params, body } => {
+                self.push_scope();
+                for param in params {
+                    self.declare(param, Type::Unknown, false);
+                }
+                let outer = self.returns.take();
+                self.check_node(body)?;
+                self.returns = outer;
+                self.pop_scope();
+                Ok(Type::Unknown)
+            }
             ASTNode::Block(stmts) => {
                 self.push_scope();
                 let mut last_type = Type::Void;
@@ -170,20 +257,49 @@ impl TypeChecker {
                 self.pop_scope();
                 Ok(last_type)
             }
-            ASTNode::Let { name, value, ty, .. } => {
+            ASTNode::Let { name, value, ty, .. } | ASTNode::Const { name, value, ty } => {
                 let val_type = self.check_node(value)?;
-                // TODO: Check against declared type if present
-                let _ = ty;
-                self.current_scope().insert(name.clone(), val_type);
+                let declared = Type::declared(ty.as_ref());
+                if !val_type.fits(&declared) {
+                    self.error(format!(
+                        "`{}` is declared {} but assigned {}",
+                        name,
+                        declared.describe(),
+                        val_type.describe()
+                    ));
+                }
+                if declared == Type::Unknown {
+                    self.declare(name, val_type, false);
+                } else {
+                    self.declare(name, declared, true);
+                }
                 Ok(Type::Void)
             }
-            ASTNode::While { cond, body } => {
-                let cond_type = self.check_node(cond)?;
-                if cond_type != Type::Bool && cond_type != Type::Int {
-                    self.errors
-                        .push("While condition must be boolean".to_string());
+            ASTNode::Assign { target, value } => {
+                let val_type = self.check_node(value)?;
+                let ASTNode::Identifier(name) = target.inner() else {
+                    self.check_node(target)?;
+                    return Ok(Type::Void);
+                };
+                let mismatch = match self.find_variable(name) {
+                    Some(var) if var.declared => (!val_type.fits(&var.ty)).then(|| var.ty.clone()),
+                    // Knull variables may change type; stop trusting the inferred one
+                    Some(var) => {
+                        if var.ty != val_type {
+                            var.ty = Type::Unknown;
+                        }
+                        None
+                    }
+                    None => None,
+                };
+                if let Some(declared) = mismatch {
+                    self.error(format!(
+                        "`{}` is declared {} but assigned {}",
+                        name,
+                        declared.describe(),
+                        val_type.describe()
+                    ));
                 }
-                self.check_node(body)?;
                 Ok(Type::Void)
             }
             ASTNode::If {
@@ -191,34 +307,40 @@ impl TypeChecker {
                 then_body,
                 else_body,
             } => {
-                let cond_type = self.check_node(cond)?;
-                if cond_type != Type::Bool && cond_type != Type::Int {
-                    self.errors.push("If condition must be boolean".to_string());
-                }
+                // Any value can be a condition; Knull tests its truthiness
+                self.check_node(cond)?;
                 let then_type = self.check_node(then_body)?;
                 if let Some(else_branch) = else_body {
                     let _ = self.check_node(else_branch)?;
                 }
                 Ok(then_type)
             }
-            ASTNode::Return(expr) => self.check_node(expr),
+            ASTNode::Return(expr) => {
+                let ty = self.check_node(expr)?;
+                if let Some((name, returns)) = &self.returns {
+                    if !ty.fits(returns) {
+                        let message = format!("`{}` returns {} but this returns {}", name, returns.describe(), ty.describe());
+                        self.error(message);
+                    }
+                }
+                Ok(ty)
+            }
             ASTNode::Binary { op, left, right } => {
                 let left_type = self.check_node(left)?;
                 let right_type = self.check_node(right)?;
+                let types = (&left_type, &right_type);
+                let text = |ty: &Type| matches!(ty, Type::String | Type::Bool);
 
                 match op.as_str() {
-                    "+" | "-" | "*" | "/" | "%" => {
-                        if left_type == Type::Int && right_type == Type::Int {
-                            Ok(Type::Int)
-                        } else if left_type == Type::Float || right_type == Type::Float {
-                            Ok(Type::Float)
-                        } else {
-                            self.errors.push(format!(
-                                "Cannot apply {} to {:?} and {:?}",
-                                op, left_type, right_type
-                            ));
-                            Ok(Type::Unknown)
-                        }
+                    "+" if types == (&Type::Int, &Type::Int) => Ok(Type::Int),
+                    "+" if left_type == Type::String || right_type == Type::String => Ok(Type::String),
+                    "-" | "*" | "/" | "%" if types == (&Type::Int, &Type::Int) => Ok(Type::Int),
+                    "+" | "-" | "*" | "/" if left_type.is_number() && right_type.is_number() => Ok(Type::Float),
+                    "+" if left_type == Type::Bool || right_type == Type::Bool => {
+                        self.binary_error(op, &left_type, &right_type)
+                    }
+                    "-" | "*" | "/" | "%" if text(&left_type) || text(&right_type) => {
+                        self.binary_error(op, &left_type, &right_type)
                     }
                     "==" | "!=" | "<" | ">" | "<=" | ">=" => Ok(Type::Bool),
                     "&&" | "||" => Ok(Type::Bool),
@@ -234,22 +356,39 @@ impl TypeChecker {
                 }
             }
             ASTNode::Call { func, args } => {
+                let mut arg_types = Vec::new();
                 for arg in args {
-                    self.check_node(arg)?;
+                    arg_types.push(self.check_node(arg)?);
                 }
-                // Return type depends on function
-                let func_name = match func.as_ref() {
+                let func_name = match func.inner() {
                     ASTNode::Identifier(name) => name.as_str(),
                     _ => return Ok(Type::Unknown),
                 };
-                match func_name {
-                    "println" | "print" => Ok(Type::Void),
-                    _ => Ok(Type::Unknown),
+                // A local closure of the same name hides the function
+                if self.scopes[1..].iter().any(|scope| scope.contains_key(func_name)) {
+                    return Ok(Type::Unknown);
                 }
+                let Some((params, returns)) = self.functions.get(func_name).cloned() else {
+                    return Ok(crate::builtins::lookup(func_name).map_or(Type::Unknown, |b| Type::named(b.returns)));
+                };
+                for (i, (arg, param)) in arg_types.iter().zip(&params).enumerate() {
+                    if !arg.fits(param) {
+                        self.error(format!(
+                            "argument {} of `{}` should be {}, not {}",
+                            i + 1,
+                            func_name,
+                            param.describe(),
+                            arg.describe()
+                        ));
+                    }
+                }
+                Ok(returns)
+            }
+            ASTNode::Identifier(name) => {
+                // Names the checker can't see (globals set at runtime, functions,
+                // builtins) are left to `checker`, which resolves them
+                Ok(self.find_variable(name).map_or(Type::Unknown, |var| var.ty.clone()))
             }
-            ASTNode::Identifier(name) => self
-                .find_variable(name)
-                .ok_or_else(|| format!("Unknown variable: {}", name)),
             ASTNode::Literal(lit) => match lit {
                 Literal::Int(_) => Ok(Type::Int),
                 Literal::Float(_) => Ok(Type::Float),
@@ -257,8 +396,7 @@ impl TypeChecker {
                 Literal::Bool(_) => Ok(Type::Bool),
                 Literal::Null => Ok(Type::Null),
             },
-            ASTNode::Array(_) => Ok(Type::Unknown),
-            ASTNode::Index { obj: _, index: _ } => Ok(Type::Unknown),
+            ASTNode::InterpolatedString(_) => Ok(Type::String),
             ASTNode::Consume(expr) => {
                 self.check_node(expr)?;
                 Ok(Type::Void)
@@ -296,7 +434,7 @@ impl TypeChecker {
                         }
                     }
                 }
-                Ok(Type::EffectType(effect_set))
+                Ok(Type::Effect(effect_set))
             }
             ASTNode::Capability {
                 name,
@@ -313,7 +451,17 @@ impl TypeChecker {
                     resource: None,
                 }))
             }
-            _ => Ok(Type::Unknown),
+            other => {
+                for child in other.children() {
+                    self.check_node(child)?;
+                }
+                Ok(Type::Unknown)
+            }
         }
     }
+
+    fn binary_error(&mut self, op: &str, left: &Type, right: &Type) -> Result<Type, String> {
+        self.error(format!("cannot apply `{}` to {} and {}", op, left.describe(), right.describe()));
+        Ok(Type::Unknown)
+    }
 }
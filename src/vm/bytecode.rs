@@ -0,0 +1,220 @@
+//! Bytecode: the instruction set, and the chunks and functions it lives in
+
+use crate::interpreter::{FunctionDef, Value};
+use crate::parser::{ASTNode, Pattern, Span};
+
+/// Jump target of a `CallMethod` whose receiver is not a place to store back into
+pub const NO_STORE: u32 = u32::MAX;
+
+/// Binary operators with a dedicated instruction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Eq,
+    Ne,
+    Lt,
+    Gt,
+    Le,
+    Ge,
+    And,
+    Or,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
+}
+
+impl BinOp {
+    pub fn from_symbol(op: &str) -> Option<Self> {
+        Some(match op {
+            "+" => BinOp::Add,
+            "-" => BinOp::Sub,
+            "*" => BinOp::Mul,
+            "/" => BinOp::Div,
+            "%" => BinOp::Rem,
+            "==" => BinOp::Eq,
+            "!=" => BinOp::Ne,
+            "<" => BinOp::Lt,
+            ">" => BinOp::Gt,
+            "<=" => BinOp::Le,
+            ">=" => BinOp::Ge,
+            "&&" | "and" => BinOp::And,
+            "||" | "or" => BinOp::Or,
+            "&" => BinOp::BitAnd,
+            "|" => BinOp::BitOr,
+            "^" => BinOp::BitXor,
+            "<<" => BinOp::Shl,
+            ">>" => BinOp::Shr,
+            _ => return None,
+        })
+    }
+
+    /// The operator as the interpreter spells it
+    pub fn symbol(self) -> &'static str {
+        match self {
+            BinOp::Add => "+",
+            BinOp::Sub => "-",
+            BinOp::Mul => "*",
+            BinOp::Div => "/",
+            BinOp::Rem => "%",
+            BinOp::Eq => "==",
+            BinOp::Ne => "!=",
+            BinOp::Lt => "<",
+            BinOp::Gt => ">",
+            BinOp::Le => "<=",
+            BinOp::Ge => ">=",
+            BinOp::And => "&&",
+            BinOp::Or => "||",
+            BinOp::BitAnd => "&",
+            BinOp::BitOr => "|",
+            BinOp::BitXor => "^",
+            BinOp::Shl => "<<",
+            BinOp::Shr => ">>",
+        }
+    }
+}
+
+/// One VM instruction. Operands index the chunk's pools (`constants`, `names`,
+/// `lambdas`, `arms`, `host_nodes`), local slots of the current frame, or code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Const(u32),
+    Null,
+    Pop,
+    Dup,
+    LoadLocal(u32),
+    /// Pop into a local slot
+    StoreLocal(u32),
+    /// Read a name the compiler could not resolve to a slot: a global,
+    /// a function used as a value, or a unit enum variant
+    LoadGlobal(u32),
+    StoreGlobal(u32),
+    Binary(BinOp),
+    Neg,
+    Not,
+    Jump(u32),
+    /// Pop, and jump if the value is falsy
+    JumpIfFalse(u32),
+    /// Jump without popping if the top of the stack is (or is not) null
+    JumpIfNull(u32),
+    JumpIfNotNull(u32),
+    /// Call a compiled function with the top `argc` values as arguments
+    Call { func: u32, argc: u32 },
+    /// Call by name through the interpreter: builtins, interpreted functions,
+    /// closures held in globals, enum variants
+    CallNamed { name: u32, argc: u32 },
+    /// Call the value below the top `argc` arguments
+    CallValue(u32),
+    /// Call a method on the value below the arguments. For an impl method on a
+    /// place, the updated receiver is pushed above the result for the code that
+    /// follows to store; otherwise execution continues at `store`.
+    CallMethod { name: u32, argc: u32, store: u32 },
+    Return,
+    Array(u32),
+    Tuple(u32),
+    /// Build a map from `n` key/value pairs
+    Map(u32),
+    Range { inclusive: bool },
+    /// Build a struct (or struct-like variant) named `names[name]` from one
+    /// value per field in `names[fields..fields + count]`
+    Struct { name: u32, fields: u32, count: u32 },
+    /// Pop index then container, push the element
+    Index,
+    /// Pop index, push the element of the container in a local slot
+    IndexLocal(u32),
+    /// Pop index, container and value; push the updated container
+    SetIndex,
+    /// Pop index and value; update the container in a local slot
+    SetIndexLocal(u32),
+    Field(u32),
+    /// `obj?.field`: null for a null or fieldless object
+    SafeField(u32),
+    /// Pop container and value; push the container with the field set
+    SetField(u32),
+    /// Replace the top value with its display string
+    Display,
+    /// Join the top `n` strings
+    Concat(u32),
+    /// Turn the top value into something `ForNext` can walk; `comprehension`
+    /// also accepts strings (by character) and single values
+    IntoIter { comprehension: bool },
+    /// Step the iterable in `iter` using the counter in `index`: store the next
+    /// element in `var`, or jump to `exit` when done
+    ForNext { iter: u32, index: u32, var: u32, exit: u32 },
+    /// Pop a value onto the array in a local slot
+    PushLocal(u32),
+    Closure(u32),
+    /// Match the value in `subject` against `arms[arm]`, binding its variables
+    /// or jumping to the arm's `fail` target
+    MatchArm { subject: u32, arm: u32 },
+    /// Route errors to `catch` (pushing the caught value) until `TryEnd`
+    TryStart { catch: u32 },
+    TryEnd,
+    Throw,
+    /// Execute `host_nodes[i]` as a top-level statement in the interpreter
+    Host(u32),
+}
+
+/// A lambda expression, captured with the locals visible where it appears
+#[derive(Debug, Clone)]
+pub struct Lambda {
+    pub params: Vec<String>,
+    pub body: Box<ASTNode>,
+    pub captures: Vec<(String, u32)>,
+}
+
+/// A `match` arm's pattern, the slots of the variables it may bind, and where
+/// to continue when it does not match
+#[derive(Debug, Clone)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub bindings: Vec<(String, u32)>,
+    pub fail: u32,
+}
+
+/// Code and the pools its operands refer to
+#[derive(Debug, Default)]
+pub struct Chunk {
+    pub code: Vec<Op>,
+    pub constants: Vec<Value>,
+    pub names: Vec<String>,
+    pub lambdas: Vec<Lambda>,
+    pub arms: Vec<MatchArm>,
+    pub host_nodes: Vec<ASTNode>,
+    /// `(first instruction, span)` for each run of code from the same source location
+    pub spans: Vec<(usize, Option<Span>)>,
+}
+
+impl Chunk {
+    /// Source location of the instruction at `ip`
+    pub fn span_at(&self, ip: usize) -> Option<&Span> {
+        let i = self.spans.partition_point(|(start, _)| *start <= ip);
+        self.spans[..i].last().and_then(|(_, span)| span.as_ref())
+    }
+}
+
+/// A compiled function, or the top-level code of a program
+#[derive(Debug)]
+pub struct Function {
+    pub name: String,
+    pub arity: usize,
+    /// Local slots the frame needs, parameters first
+    pub locals: usize,
+    pub chunk: Chunk,
+    /// Trait bounds on parameters, checked at call time like the interpreter does
+    pub def: Option<FunctionDef>,
+}
+
+/// A compiled program: its top-level code followed by the functions it defines
+#[derive(Debug)]
+pub struct Program {
+    pub main: Function,
+    pub functions: Vec<Function>,
+    /// Functions and top-level statements left to the interpreter, with the reason
+    pub interpreted: Vec<(String, String)>,
+}
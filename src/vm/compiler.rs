@@ -0,0 +1,947 @@
+//! AST → bytecode
+//!
+//! Locals resolve to frame slots at compile time; anything else is looked up by
+//! name at run time, the way the interpreter would. A construct the compiler does
+//! not handle is an `Err`: the enclosing function is then left to the
+//! interpreter, or, at top level, the statement runs through a `Host` instruction.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::interpreter::{FunctionDef, Interpreter, Value};
+use crate::parser::{ASTNode, Literal, Param, Parser, Pattern, Span};
+
+use super::bytecode::{BinOp, Chunk, Function, Lambda, MatchArm, Op, Program, NO_STORE};
+
+type CompileResult = Result<(), String>;
+
+/// Names the whole program shares while its functions are compiled
+struct Context {
+    /// Compiled functions by name, as indices into `Program::functions`
+    functions: HashMap<String, u32>,
+    /// Names bound at top level, which functions reach by name rather than slot
+    globals: HashSet<String>,
+}
+
+/// Jumps out of the loop being compiled, patched once its exit is known
+struct Loop {
+    breaks: Vec<usize>,
+    continues: Vec<usize>,
+    /// `try` blocks already open when the loop started
+    tries: u32,
+}
+
+/// Compiler state that a failed top-level statement rolls back
+struct Checkpoint {
+    code: usize,
+    spans: usize,
+    scopes: Vec<Vec<(String, u32)>>,
+    next_slot: u32,
+}
+
+struct FunctionCompiler<'a> {
+    ctx: &'a Context,
+    chunk: Chunk,
+    /// Visible locals, innermost scope last; hidden slots have an empty name
+    scopes: Vec<Vec<(String, u32)>>,
+    next_slot: u32,
+    max_slots: u32,
+    loops: Vec<Loop>,
+    tries: u32,
+    span: Option<Span>,
+    name_ids: HashMap<String, u32>,
+    /// Compiling top-level code rather than a function body
+    toplevel: bool,
+}
+
+/// Compile a program whose items the interpreter has already defined
+pub fn compile(items: &[ASTNode]) -> Program {
+    let mut ctx = Context {
+        functions: HashMap::new(),
+        globals: HashSet::new(),
+    };
+    for item in items {
+        collect_globals(item.inner(), &mut ctx.globals);
+    }
+
+    // Find the functions the compiler can handle, then compile them knowing
+    // which calls can go straight to another compiled function
+    let definitions: HashMap<&str, (&[Param], &ASTNode)> = items
+        .iter()
+        .filter_map(|item| match item.inner() {
+            ASTNode::Function { name, params, body, .. } => Some((name.as_str(), (params.as_slice(), &**body))),
+            _ => None,
+        })
+        .collect();
+    let mut names: Vec<&str> = definitions.keys().copied().collect();
+    names.sort_unstable();
+    let mut interpreted = Vec::new();
+    for name in names {
+        let (params, body) = definitions[name];
+        match compile_function(&ctx, name, params, body) {
+            Ok(_) => {
+                let index = ctx.functions.len() as u32;
+                ctx.functions.insert(name.to_string(), index);
+            }
+            Err(reason) => interpreted.push((name.to_string(), reason)),
+        }
+    }
+
+    let mut functions: Vec<(u32, Function)> = ctx
+        .functions
+        .iter()
+        .map(|(name, &index)| {
+            let (params, body) = definitions[name.as_str()];
+            let function = compile_function(&ctx, name, params, body).expect("compiled before");
+            (index, function)
+        })
+        .collect();
+    functions.sort_by_key(|(index, _)| *index);
+
+    let main = compile_main(&ctx, items, &mut interpreted);
+    Program {
+        main,
+        functions: functions.into_iter().map(|(_, f)| f).collect(),
+        interpreted,
+    }
+}
+
+/// Names a top-level statement binds in the global scope
+fn collect_globals(item: &ASTNode, globals: &mut HashSet<String>) {
+    match item {
+        ASTNode::Let { name, .. } | ASTNode::Const { name, .. } => {
+            globals.insert(name.clone());
+        }
+        ASTNode::Use { alias: Some(alias), .. } => {
+            globals.insert(alias.clone());
+        }
+        ASTNode::Assign { target, .. } | ASTNode::AssignOp { target, .. } => {
+            if let ASTNode::Identifier(name) = target.inner() {
+                globals.insert(name.clone());
+            }
+        }
+        _ => {}
+    }
+}
+
+fn compile_function(ctx: &Context, name: &str, params: &[Param], body: &ASTNode) -> Result<Function, String> {
+    let mut c = FunctionCompiler::new(ctx);
+    c.scopes.push(Vec::new());
+    for param in params {
+        c.declare(&param.name);
+    }
+    match body {
+        // Like the interpreter's call_body: statements, then the last one as the
+        // implicit return value, all in the parameters' scope
+        ASTNode::Block(nodes) => {
+            if let Some((last, rest)) = nodes.split_last() {
+                for node in rest {
+                    c.stmt(node)?;
+                }
+                c.expr(last)?;
+            } else {
+                c.emit(Op::Null);
+            }
+        }
+        other => c.expr(other)?,
+    }
+    c.emit(Op::Return);
+
+    let def = FunctionDef::new(name, params, body, None);
+    Ok(Function {
+        name: name.to_string(),
+        arity: params.len(),
+        locals: c.max_slots as usize,
+        chunk: c.chunk,
+        def: (!def.bounds.is_empty()).then_some(def),
+    })
+}
+
+/// The top-level statements, then `main()` if the program has one
+fn compile_main(ctx: &Context, items: &[ASTNode], interpreted: &mut Vec<(String, String)>) -> Function {
+    let mut c = FunctionCompiler::new(ctx);
+    c.toplevel = true;
+    let mut has_main = false;
+    for item in items {
+        match item.inner() {
+            ASTNode::Function { name, .. } => has_main |= name == "main",
+            ASTNode::StructDef { .. } | ASTNode::EnumDef { .. } | ASTNode::Impl { .. } | ASTNode::Test { .. } => {}
+            _ => {
+                let checkpoint = c.checkpoint();
+                if let Err(reason) = c.stmt(item) {
+                    c.rollback(checkpoint);
+                    let line = item.span().map_or(0, |s| s.line);
+                    interpreted.push((format!("statement at line {}", line), reason));
+                    c.chunk.host_nodes.push(item.clone());
+                    let index = c.chunk.host_nodes.len() as u32 - 1;
+                    c.emit(Op::Host(index));
+                }
+            }
+        }
+    }
+    if has_main {
+        c.call_named("main", 0);
+        c.emit(Op::Pop);
+    }
+    c.emit(Op::Null);
+    c.emit(Op::Return);
+
+    Function {
+        name: "<main>".to_string(),
+        arity: 0,
+        locals: c.max_slots as usize,
+        chunk: c.chunk,
+        def: None,
+    }
+}
+
+impl<'a> FunctionCompiler<'a> {
+    fn new(ctx: &'a Context) -> Self {
+        FunctionCompiler {
+            ctx,
+            chunk: Chunk::default(),
+            scopes: Vec::new(),
+            next_slot: 0,
+            max_slots: 0,
+            loops: Vec::new(),
+            tries: 0,
+            span: None,
+            name_ids: HashMap::new(),
+            toplevel: false,
+        }
+    }
+
+    fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            code: self.chunk.code.len(),
+            spans: self.chunk.spans.len(),
+            scopes: self.scopes.clone(),
+            next_slot: self.next_slot,
+        }
+    }
+
+    fn rollback(&mut self, checkpoint: Checkpoint) {
+        self.chunk.code.truncate(checkpoint.code);
+        self.chunk.spans.truncate(checkpoint.spans);
+        self.scopes = checkpoint.scopes;
+        self.next_slot = checkpoint.next_slot;
+        self.loops.clear();
+        self.tries = 0;
+        self.set_span(None);
+    }
+
+    // ── Emitting ──────────────────────────────────────────────────────
+
+    fn emit(&mut self, op: Op) -> usize {
+        self.chunk.code.push(op);
+        self.chunk.code.len() - 1
+    }
+
+    fn here(&self) -> u32 {
+        self.chunk.code.len() as u32
+    }
+
+    /// Point the jump at `at` to `target`
+    fn patch(&mut self, at: usize, target: u32) {
+        match &mut self.chunk.code[at] {
+            Op::Jump(t) | Op::JumpIfFalse(t) | Op::JumpIfNull(t) | Op::JumpIfNotNull(t) => *t = target,
+            Op::TryStart { catch } => *catch = target,
+            Op::ForNext { exit, .. } => *exit = target,
+            Op::CallMethod { store, .. } => *store = target,
+            op => unreachable!("{:?} has no jump target", op),
+        }
+    }
+
+    fn constant(&mut self, value: Value) {
+        self.chunk.constants.push(value);
+        let index = self.chunk.constants.len() as u32 - 1;
+        self.emit(Op::Const(index));
+    }
+
+    fn name(&mut self, name: &str) -> u32 {
+        if let Some(&id) = self.name_ids.get(name) {
+            return id;
+        }
+        self.chunk.names.push(name.to_string());
+        let id = self.chunk.names.len() as u32 - 1;
+        self.name_ids.insert(name.to_string(), id);
+        id
+    }
+
+    /// Attribute the code emitted from here on to `span`, returning the previous one
+    fn set_span(&mut self, span: Option<Span>) -> Option<Span> {
+        let start = self.chunk.code.len();
+        match self.chunk.spans.last_mut() {
+            Some((at, last)) if *at == start => *last = span.clone(),
+            _ => self.chunk.spans.push((start, span.clone())),
+        }
+        std::mem::replace(&mut self.span, span)
+    }
+
+    // ── Scopes ────────────────────────────────────────────────────────
+
+    fn begin_scope(&mut self) {
+        self.scopes.push(Vec::new());
+    }
+
+    fn end_scope(&mut self) {
+        if let Some(scope) = self.scopes.pop() {
+            self.next_slot -= scope.len() as u32;
+        }
+    }
+
+    fn declare(&mut self, name: &str) -> u32 {
+        let slot = self.next_slot;
+        self.next_slot += 1;
+        self.max_slots = self.max_slots.max(self.next_slot);
+        self.scopes
+            .last_mut()
+            .expect("declare outside any scope")
+            .push((name.to_string(), slot));
+        slot
+    }
+
+    fn resolve(&self, name: &str) -> Option<u32> {
+        self.scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.iter().rev())
+            .find(|(n, _)| n == name)
+            .map(|(_, slot)| *slot)
+    }
+
+    /// Every named local in view, innermost binding of each name only
+    fn visible(&self) -> Vec<(String, u32)> {
+        let mut seen = HashSet::new();
+        self.scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.iter().rev())
+            .filter(|(n, _)| !n.is_empty() && seen.insert(n.as_str()))
+            .cloned()
+            .collect()
+    }
+
+    // ── Statements ────────────────────────────────────────────────────
+
+    /// Compile a node for its effect, leaving the stack as it was
+    fn stmt(&mut self, node: &ASTNode) -> CompileResult {
+        match node {
+            ASTNode::Spanned { span, node } => {
+                let outer = self.set_span(Some(span.clone()));
+                let result = self.stmt(node);
+                self.set_span(outer);
+                result
+            }
+            ASTNode::Let { name, value, .. } => {
+                self.expr(value)?;
+                if self.scopes.is_empty() {
+                    let id = self.name(name);
+                    self.emit(Op::StoreGlobal(id));
+                } else {
+                    let slot = self.declare(name);
+                    self.emit(Op::StoreLocal(slot));
+                }
+                Ok(())
+            }
+            ASTNode::Const { name, value, .. } => {
+                self.expr(value)?;
+                self.store_name(name);
+                Ok(())
+            }
+            ASTNode::Assign { target, value } => {
+                self.expr(value)?;
+                self.store(target)
+            }
+            ASTNode::AssignOp { target, op, value } => {
+                self.compound(target, op, value)?;
+                self.store(target)
+            }
+            ASTNode::Break(None) => self.exit_loop(true),
+            ASTNode::Continue(None) => self.exit_loop(false),
+            ASTNode::Return(expr) => {
+                if self.toplevel {
+                    return Err("top-level return".to_string());
+                }
+                self.expr(expr)?;
+                self.emit(Op::Return);
+                Ok(())
+            }
+            ASTNode::If { cond, then_body, else_body } => {
+                self.expr(cond)?;
+                let to_else = self.emit(Op::JumpIfFalse(0));
+                self.stmt(then_body)?;
+                match else_body {
+                    Some(else_body) => {
+                        let to_end = self.emit(Op::Jump(0));
+                        self.patch(to_else, self.here());
+                        self.stmt(else_body)?;
+                        self.patch(to_end, self.here());
+                    }
+                    None => self.patch(to_else, self.here()),
+                }
+                Ok(())
+            }
+            ASTNode::While { cond, body } => {
+                let start = self.here();
+                self.expr(cond)?;
+                let exit = self.emit(Op::JumpIfFalse(0));
+                self.loop_body(body)?;
+                self.emit(Op::Jump(start));
+                self.patch(exit, self.here());
+                self.end_loop(start);
+                Ok(())
+            }
+            ASTNode::Loop(body) => {
+                let start = self.here();
+                self.loop_body(body)?;
+                self.emit(Op::Jump(start));
+                self.end_loop(start);
+                Ok(())
+            }
+            ASTNode::DoWhile { body, cond } => {
+                let start = self.here();
+                self.loop_body(body)?;
+                let check = self.here();
+                self.expr(cond)?;
+                let exit = self.emit(Op::JumpIfFalse(0));
+                self.emit(Op::Jump(start));
+                self.patch(exit, self.here());
+                self.end_loop(check);
+                Ok(())
+            }
+            ASTNode::For { var, iter, body } => {
+                self.expr(iter)?;
+                self.emit(Op::IntoIter { comprehension: false });
+                self.begin_scope();
+                let (iter_slot, index_slot) = self.start_iteration();
+                let var_slot = self.declare(var);
+                let next = self.emit(Op::ForNext { iter: iter_slot, index: index_slot, var: var_slot, exit: 0 });
+                self.loop_body(body)?;
+                self.emit(Op::Jump(next as u32));
+                self.patch(next, self.here());
+                self.end_loop(next as u32);
+                self.end_scope();
+                Ok(())
+            }
+            ASTNode::Block(nodes) => {
+                self.begin_scope();
+                for node in nodes {
+                    self.stmt(node)?;
+                }
+                self.end_scope();
+                Ok(())
+            }
+            ASTNode::TypeAlias { .. } => Ok(()),
+            other => {
+                self.expr(other)?;
+                self.emit(Op::Pop);
+                Ok(())
+            }
+        }
+    }
+
+    fn loop_body(&mut self, body: &ASTNode) -> CompileResult {
+        self.loops.push(Loop {
+            breaks: Vec::new(),
+            continues: Vec::new(),
+            tries: self.tries,
+        });
+        self.stmt(body)
+    }
+
+    /// Patch the innermost loop's `break`s to here and its `continue`s to `next`
+    fn end_loop(&mut self, next: u32) {
+        let exit = self.here();
+        if let Some(lp) = self.loops.pop() {
+            for at in lp.breaks {
+                self.patch(at, exit);
+            }
+            for at in lp.continues {
+                self.patch(at, next);
+            }
+        }
+    }
+
+    fn exit_loop(&mut self, is_break: bool) -> CompileResult {
+        let open = match self.loops.last() {
+            Some(lp) => self.tries - lp.tries,
+            None => return Err("break or continue outside a loop".to_string()),
+        };
+        for _ in 0..open {
+            self.emit(Op::TryEnd);
+        }
+        let at = self.emit(Op::Jump(0));
+        let lp = self.loops.last_mut().expect("checked above");
+        if is_break {
+            lp.breaks.push(at);
+        } else {
+            lp.continues.push(at);
+        }
+        Ok(())
+    }
+
+    /// Store the iterable on the stack in a hidden slot beside a zeroed counter
+    fn start_iteration(&mut self) -> (u32, u32) {
+        let iter_slot = self.declare("");
+        let index_slot = self.declare("");
+        self.emit(Op::StoreLocal(iter_slot));
+        self.constant(Value::Int(0));
+        self.emit(Op::StoreLocal(index_slot));
+        (iter_slot, index_slot)
+    }
+
+    /// Push `target op value`
+    fn compound(&mut self, target: &ASTNode, op: &str, value: &ASTNode) -> CompileResult {
+        let op = BinOp::from_symbol(op).ok_or_else(|| format!("operator `{}=`", op))?;
+        self.expr(target)?;
+        self.expr(value)?;
+        self.emit(Op::Binary(op));
+        Ok(())
+    }
+
+    // ── Stores ────────────────────────────────────────────────────────
+
+    /// Pop a value into a name: its local slot, a global, or a new local
+    fn store_name(&mut self, name: &str) {
+        if let Some(slot) = self.resolve(name) {
+            self.emit(Op::StoreLocal(slot));
+        } else if self.scopes.is_empty() || self.ctx.globals.contains(name) {
+            let id = self.name(name);
+            self.emit(Op::StoreGlobal(id));
+        } else {
+            let slot = self.declare(name);
+            self.emit(Op::StoreLocal(slot));
+        }
+    }
+
+    /// Pop a value into an assignment target, writing containers back to their places
+    fn store(&mut self, target: &ASTNode) -> CompileResult {
+        match target.inner() {
+            ASTNode::Identifier(name) => {
+                self.store_name(name);
+                Ok(())
+            }
+            ASTNode::Index { obj, index } if Interpreter::is_place(obj) => {
+                self.expr(index)?;
+                if let Some(slot) = self.local(obj) {
+                    self.emit(Op::SetIndexLocal(slot));
+                    return Ok(());
+                }
+                self.expr(obj)?;
+                self.emit(Op::SetIndex);
+                self.store(obj)
+            }
+            ASTNode::FieldAccess { obj, field } if Interpreter::is_place(obj) => {
+                self.expr(obj)?;
+                let id = self.name(field);
+                self.emit(Op::SetField(id));
+                self.store(obj)
+            }
+            _ => Err("assignment to a computed target".to_string()),
+        }
+    }
+
+    /// The slot of a node that is just a local variable
+    fn local(&self, node: &ASTNode) -> Option<u32> {
+        match node.inner() {
+            ASTNode::Identifier(name) => self.resolve(name),
+            _ => None,
+        }
+    }
+
+    // ── Expressions ───────────────────────────────────────────────────
+
+    /// Compile a node for its value, pushing exactly one
+    fn expr(&mut self, node: &ASTNode) -> CompileResult {
+        match node {
+            ASTNode::Spanned { span, node } => {
+                let outer = self.set_span(Some(span.clone()));
+                let result = self.expr(node);
+                self.set_span(outer);
+                return result;
+            }
+            ASTNode::Literal(Literal::Null) => {
+                self.emit(Op::Null);
+            }
+            ASTNode::Literal(lit) => {
+                let value = match lit {
+                    Literal::Int(n) => Value::Int(*n),
+                    Literal::Float(f) => Value::Float(*f),
+                    Literal::String(s) => Value::String(s.clone()),
+                    Literal::Bool(b) => Value::Bool(*b),
+                    Literal::Null => Value::Null,
+                };
+                self.constant(value);
+            }
+            ASTNode::Identifier(name) => match self.resolve(name) {
+                Some(slot) => {
+                    self.emit(Op::LoadLocal(slot));
+                }
+                None => {
+                    let id = self.name(name);
+                    self.emit(Op::LoadGlobal(id));
+                }
+            },
+            ASTNode::Binary { op, left, right } => {
+                let op = BinOp::from_symbol(op).ok_or_else(|| format!("operator `{}`", op))?;
+                self.expr(left)?;
+                self.expr(right)?;
+                self.emit(Op::Binary(op));
+            }
+            ASTNode::Unary { op, operand } => {
+                self.expr(operand)?;
+                match op.as_str() {
+                    "-" => self.emit(Op::Neg),
+                    "!" | "not" => self.emit(Op::Not),
+                    other => return Err(format!("unary operator `{}`", other)),
+                };
+            }
+            ASTNode::Call { func, args } => match func.as_ref() {
+                ASTNode::Identifier(name) => self.call(name, None, args)?,
+                other => {
+                    self.expr(other)?;
+                    self.args(args)?;
+                    self.emit(Op::CallValue(args.len() as u32));
+                }
+            },
+            ASTNode::MethodCall { obj, method, args } => {
+                self.expr(obj)?;
+                self.args(args)?;
+                let name = self.name(method);
+                let call = self.emit(Op::CallMethod { name, argc: args.len() as u32, store: NO_STORE });
+                if Interpreter::is_place(obj) {
+                    // Reached with the updated receiver above the result
+                    self.store(obj)?;
+                    self.patch(call, self.here());
+                }
+            }
+            ASTNode::Index { obj, index } => {
+                if let Some(slot) = self.local(obj) {
+                    self.expr(index)?;
+                    self.emit(Op::IndexLocal(slot));
+                } else {
+                    self.expr(obj)?;
+                    self.expr(index)?;
+                    self.emit(Op::Index);
+                }
+            }
+            ASTNode::FieldAccess { obj, field } => {
+                self.expr(obj)?;
+                let id = self.name(field);
+                self.emit(Op::Field(id));
+            }
+            ASTNode::SafeNav { obj, field } => {
+                self.expr(obj)?;
+                let id = self.name(field);
+                self.emit(Op::SafeField(id));
+            }
+            ASTNode::Array(elements) => {
+                self.args(elements)?;
+                self.emit(Op::Array(elements.len() as u32));
+            }
+            ASTNode::Tuple(elements) => {
+                self.args(elements)?;
+                self.emit(Op::Tuple(elements.len() as u32));
+            }
+            ASTNode::Map(pairs) => {
+                for (key, value) in pairs {
+                    self.expr(key)?;
+                    self.expr(value)?;
+                }
+                self.emit(Op::Map(pairs.len() as u32));
+            }
+            ASTNode::Range { start, end, inclusive } => {
+                self.expr(start)?;
+                self.expr(end)?;
+                self.emit(Op::Range { inclusive: *inclusive });
+            }
+            ASTNode::StructLiteral { name, fields } => {
+                for (_, value) in fields {
+                    self.expr(value)?;
+                }
+                let name = self.name(name);
+                let first = self.chunk.names.len() as u32;
+                self.chunk.names.extend(fields.iter().map(|(field, _)| field.clone()));
+                self.emit(Op::Struct { name, fields: first, count: fields.len() as u32 });
+            }
+            ASTNode::InterpolatedString(segments) => {
+                for (is_expr, content) in segments {
+                    let parsed = match is_expr {
+                        true => Parser::new(content).without_spans().parse_expression_pub().ok(),
+                        false => None,
+                    };
+                    match parsed {
+                        Some(ast) => {
+                            self.expr(&ast)?;
+                            self.emit(Op::Display);
+                        }
+                        None => self.constant(Value::String(content.clone())),
+                    }
+                }
+                self.emit(Op::Concat(segments.len() as u32));
+            }
+            ASTNode::Lambda { params, body } => {
+                self.chunk.lambdas.push(Lambda {
+                    params: params.clone(),
+                    body: body.clone(),
+                    captures: self.visible(),
+                });
+                let index = self.chunk.lambdas.len() as u32 - 1;
+                self.emit(Op::Closure(index));
+            }
+            ASTNode::Pipeline { left, right } => match right.inner() {
+                ASTNode::Call { func, args } => match func.inner() {
+                    ASTNode::Identifier(name) => self.call(name, Some(left), args)?,
+                    _ => return Err("pipeline into a computed function".to_string()),
+                },
+                ASTNode::Identifier(name) => self.call(name, Some(left), &[])?,
+                _ => return Err("pipeline into an expression".to_string()),
+            },
+            ASTNode::NullCoalesce { left, right } => {
+                self.expr(left)?;
+                let to_end = self.emit(Op::JumpIfNotNull(0));
+                self.emit(Op::Pop);
+                self.expr(right)?;
+                self.patch(to_end, self.here());
+            }
+            ASTNode::NamedArg { value, .. } => self.expr(value)?,
+            ASTNode::If { cond, then_body, else_body } => {
+                self.expr(cond)?;
+                let to_else = self.emit(Op::JumpIfFalse(0));
+                self.expr(then_body)?;
+                let to_end = self.emit(Op::Jump(0));
+                self.patch(to_else, self.here());
+                match else_body {
+                    Some(else_body) => self.expr(else_body)?,
+                    None => {
+                        self.emit(Op::Null);
+                    }
+                }
+                self.patch(to_end, self.here());
+            }
+            ASTNode::IfLet { pattern, value, then_body, else_body } => {
+                self.expr(value)?;
+                let to_else = self.emit(Op::JumpIfNull(0));
+                self.begin_scope();
+                let slot = self.declare(pattern);
+                self.emit(Op::StoreLocal(slot));
+                self.expr(then_body)?;
+                self.end_scope();
+                let to_end = self.emit(Op::Jump(0));
+                self.patch(to_else, self.here());
+                self.emit(Op::Pop);
+                match else_body {
+                    Some(else_body) => self.expr(else_body)?,
+                    None => {
+                        self.emit(Op::Null);
+                    }
+                }
+                self.patch(to_end, self.here());
+            }
+            ASTNode::Match { expr, arms } => {
+                self.expr(expr)?;
+                self.begin_scope();
+                let subject = self.declare("");
+                self.emit(Op::StoreLocal(subject));
+                let mut to_end = Vec::new();
+                for arm in arms {
+                    self.begin_scope();
+                    let mut names = Vec::new();
+                    pattern_names(&arm.pattern, &mut names);
+                    let bindings = names.into_iter().map(|n| (n.to_string(), self.declare(n))).collect();
+                    self.chunk.arms.push(MatchArm { pattern: arm.pattern.clone(), bindings, fail: 0 });
+                    let index = self.chunk.arms.len() - 1;
+                    self.emit(Op::MatchArm { subject, arm: index as u32 });
+                    let guard = match &arm.guard {
+                        Some(guard) => {
+                            self.expr(guard)?;
+                            Some(self.emit(Op::JumpIfFalse(0)))
+                        }
+                        None => None,
+                    };
+                    self.expr(&arm.body)?;
+                    to_end.push(self.emit(Op::Jump(0)));
+                    self.end_scope();
+                    let fail = self.here();
+                    self.chunk.arms[index].fail = fail;
+                    if let Some(guard) = guard {
+                        self.patch(guard, fail);
+                    }
+                }
+                self.emit(Op::Null);
+                for at in to_end {
+                    self.patch(at, self.here());
+                }
+                self.end_scope();
+            }
+            ASTNode::TryCatch { try_body, catch_var, catch_body } => {
+                let start = self.emit(Op::TryStart { catch: 0 });
+                self.tries += 1;
+                self.expr(try_body)?;
+                self.tries -= 1;
+                self.emit(Op::TryEnd);
+                let to_end = self.emit(Op::Jump(0));
+                self.patch(start, self.here());
+                self.begin_scope();
+                let slot = self.declare(catch_var);
+                self.emit(Op::StoreLocal(slot));
+                self.expr(catch_body)?;
+                self.end_scope();
+                self.patch(to_end, self.here());
+            }
+            ASTNode::Throw(value) => {
+                self.expr(value)?;
+                self.emit(Op::Throw);
+            }
+            ASTNode::Assign { target, value } => {
+                self.expr(value)?;
+                self.emit(Op::Dup);
+                self.store(target)?;
+            }
+            ASTNode::AssignOp { target, op, value } => {
+                self.compound(target, op, value)?;
+                self.emit(Op::Dup);
+                self.store(target)?;
+            }
+            ASTNode::Let { name, value, .. } | ASTNode::Const { name, value, .. } => {
+                self.expr(value)?;
+                self.emit(Op::Dup);
+                self.store_name(name);
+            }
+            ASTNode::Block(nodes) => {
+                self.begin_scope();
+                match nodes.split_last() {
+                    Some((last, rest)) => {
+                        for node in rest {
+                            self.stmt(node)?;
+                        }
+                        self.expr(last)?;
+                    }
+                    None => {
+                        self.emit(Op::Null);
+                    }
+                }
+                self.end_scope();
+            }
+            ASTNode::ListComp { expr, var, iter, filter } => {
+                self.expr(iter)?;
+                self.emit(Op::IntoIter { comprehension: true });
+                self.begin_scope();
+                let (iter_slot, index_slot) = self.start_iteration();
+                let result = self.declare("");
+                self.emit(Op::Array(0));
+                self.emit(Op::StoreLocal(result));
+                let var_slot = self.declare(var);
+                let next = self.emit(Op::ForNext { iter: iter_slot, index: index_slot, var: var_slot, exit: 0 });
+                if let Some(filter) = filter {
+                    self.expr(filter)?;
+                    self.emit(Op::JumpIfFalse(next as u32));
+                }
+                self.expr(expr)?;
+                self.emit(Op::PushLocal(result));
+                self.emit(Op::Jump(next as u32));
+                self.patch(next, self.here());
+                self.emit(Op::LoadLocal(result));
+                self.end_scope();
+            }
+            ASTNode::While { .. }
+            | ASTNode::Loop(_)
+            | ASTNode::DoWhile { .. }
+            | ASTNode::For { .. }
+            | ASTNode::Break(None)
+            | ASTNode::Continue(None)
+            | ASTNode::Return(_)
+            | ASTNode::TypeAlias { .. } => {
+                self.stmt(node)?;
+                self.emit(Op::Null);
+            }
+            other => return Err(describe(other)),
+        }
+        Ok(())
+    }
+
+    fn args(&mut self, args: &[ASTNode]) -> CompileResult {
+        for arg in args {
+            if let ASTNode::Spread(_) = arg.inner() {
+                return Err("spread arguments".to_string());
+            }
+            self.expr(arg)?;
+        }
+        Ok(())
+    }
+
+    /// Call a function by name, with `first` (a piped value) before `args`
+    fn call(&mut self, name: &str, first: Option<&ASTNode>, args: &[ASTNode]) -> CompileResult {
+        let argc = (args.len() + first.is_some() as usize) as u32;
+        if let Some(slot) = self.resolve(name) {
+            self.emit(Op::LoadLocal(slot));
+            if let Some(first) = first {
+                self.expr(first)?;
+            }
+            self.args(args)?;
+            self.emit(Op::CallValue(argc));
+            return Ok(());
+        }
+        if let Some(first) = first {
+            self.expr(first)?;
+        }
+        self.args(args)?;
+        self.call_named(name, argc);
+        Ok(())
+    }
+
+    /// Call a compiled function directly, anything else through the interpreter
+    fn call_named(&mut self, name: &str, argc: u32) {
+        match self.ctx.functions.get(name) {
+            Some(&func) => self.emit(Op::Call { func, argc }),
+            None => {
+                let name = self.name(name);
+                self.emit(Op::CallNamed { name, argc })
+            }
+        };
+    }
+}
+
+/// The variables a pattern can bind, each once
+fn pattern_names<'p>(pattern: &'p Pattern, out: &mut Vec<&'p str>) {
+    match pattern {
+        Pattern::Identifier(name) => {
+            if !out.contains(&name.as_str()) {
+                out.push(name);
+            }
+        }
+        Pattern::Tuple(items) | Pattern::Or(items) => {
+            for item in items {
+                pattern_names(item, out);
+            }
+        }
+        Pattern::Struct { fields, .. } => {
+            for (_, item) in fields {
+                pattern_names(item, out);
+            }
+        }
+        Pattern::Enum { data: Some(data), .. } => pattern_names(data, out),
+        Pattern::Wildcard | Pattern::Literal(_) | Pattern::Enum { data: None, .. } => {}
+    }
+}
+
+/// Why a node keeps its function in the interpreter
+fn describe(node: &ASTNode) -> String {
+    let what = match node {
+        ASTNode::Spread(_) => "spread",
+        ASTNode::Defer(_) => "defer",
+        ASTNode::TryOp(_) => "the `?` operator",
+        ASTNode::Spawn(_) => "spawn",
+        ASTNode::Await(_) | ASTNode::Yield(_) => "async code",
+        ASTNode::AsCast { .. } => "casts",
+        ASTNode::Unsafe(_) => "unsafe blocks",
+        ASTNode::Break(Some(_)) | ASTNode::Continue(Some(_)) => "labelled loops",
+        ASTNode::Function { .. } | ASTNode::AsyncFunction { .. } => "nested functions",
+        ASTNode::StructDef { .. } | ASTNode::EnumDef { .. } | ASTNode::TraitDef { .. } | ASTNode::Impl { .. } => {
+            "nested type definitions"
+        }
+        ASTNode::Use { .. } | ASTNode::Mod(_) => "imports",
+        _ => "this construct",
+    };
+    format!("{} not supported by the VM", what)
+}
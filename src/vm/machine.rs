@@ -0,0 +1,439 @@
+//! The bytecode execution loop
+//!
+//! Frames keep their locals on the value stack starting at `base`. Everything
+//! beyond plain arithmetic, locals and control flow is delegated to the
+//! interpreter the VM runs alongside, so builtins, methods, closures and error
+//! values behave exactly as they do there.
+
+use std::collections::HashMap;
+
+use crate::interpreter::{ErrorKind, Interpreter, RuntimeError, Value};
+
+use super::bytecode::{BinOp, Function, Op, Program, NO_STORE};
+
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    /// Index into `Program::functions`, or `None` for the top-level code
+    func: Option<usize>,
+    ip: usize,
+    base: usize,
+}
+
+/// An open `try`: the frame it belongs to, where its catch code starts, and
+/// the stack height to restore before running it
+#[derive(Debug)]
+struct Handler {
+    depth: usize,
+    catch: usize,
+    sp: usize,
+}
+
+pub struct Vm<'a> {
+    host: &'a mut Interpreter,
+    program: &'a Program,
+    stack: Vec<Value>,
+    frame: Frame,
+    frames: Vec<Frame>,
+    handlers: Vec<Handler>,
+}
+
+fn function_of(program: &Program, func: Option<usize>) -> &Function {
+    match func {
+        Some(i) => &program.functions[i],
+        None => &program.main,
+    }
+}
+
+impl<'a> Vm<'a> {
+    pub fn new(host: &'a mut Interpreter, program: &'a Program) -> Self {
+        Vm {
+            host,
+            program,
+            stack: vec![Value::Null; program.main.locals],
+            frame: Frame { func: None, ip: 0, base: 0 },
+            frames: Vec::new(),
+            handlers: Vec::new(),
+        }
+    }
+
+    /// Run the program's top-level code to completion
+    pub fn run(&mut self) -> Result<(), RuntimeError> {
+        loop {
+            match self.execute() {
+                Ok(()) => return Ok(()),
+                Err(err) => self.recover(err)?,
+            }
+        }
+    }
+
+    /// Unwind to the innermost `try` and resume at its catch code, or give the
+    /// error back once no frame handles it
+    fn recover(&mut self, mut err: RuntimeError) -> Result<(), RuntimeError> {
+        // Scopes an interpreted callee pushed are gone with the error
+        self.host.unwind_scopes(1);
+        let handler = self.handlers.pop();
+        loop {
+            let function = function_of(self.program, self.frame.func);
+            if let Some(span) = function.chunk.span_at(self.frame.ip.saturating_sub(1)) {
+                err = err.at(span);
+            }
+            if let Some(handler) = handler.as_ref().filter(|h| h.depth == self.frames.len()) {
+                self.stack.truncate(handler.sp);
+                self.stack.push(err.to_value());
+                self.frame.ip = handler.catch;
+                return Ok(());
+            }
+            match self.frames.pop() {
+                Some(caller) => {
+                    self.host.leave_call();
+                    err = err.in_frame(&function.name);
+                    self.stack.truncate(self.frame.base);
+                    self.frame = caller;
+                }
+                None => return Err(err),
+            }
+        }
+    }
+
+    fn pop(&mut self) -> Value {
+        self.stack.pop().expect("VM stack underflow")
+    }
+
+    /// Remove and return the top `n` values, deepest first
+    fn pop_n(&mut self, n: u32) -> Vec<Value> {
+        let at = self.stack.len() - n as usize;
+        self.stack.split_off(at)
+    }
+
+    fn execute(&mut self) -> Result<(), RuntimeError> {
+        let program = self.program;
+        let mut function = function_of(program, self.frame.func);
+        loop {
+            let op = function.chunk.code[self.frame.ip];
+            self.frame.ip += 1;
+            let base = self.frame.base;
+            match op {
+                Op::Const(i) => self.stack.push(function.chunk.constants[i as usize].clone()),
+                Op::Null => self.stack.push(Value::Null),
+                Op::Pop => {
+                    self.pop();
+                }
+                Op::Dup => {
+                    let top = self.stack.last().expect("VM stack underflow").clone();
+                    self.stack.push(top);
+                }
+                Op::LoadLocal(slot) => self.stack.push(self.stack[base + slot as usize].clone()),
+                Op::StoreLocal(slot) => {
+                    let value = self.pop();
+                    self.stack[base + slot as usize] = value;
+                }
+                Op::LoadGlobal(name) => {
+                    let value = self.host.lookup(&function.chunk.names[name as usize])?;
+                    self.stack.push(value);
+                }
+                Op::StoreGlobal(name) => {
+                    let value = self.pop();
+                    self.host.set_variable(function.chunk.names[name as usize].clone(), value);
+                }
+                Op::Binary(op) => {
+                    let right = self.pop();
+                    let left = self.pop();
+                    let result = self.binary(op, left, right)?;
+                    self.stack.push(result);
+                }
+                Op::Neg => {
+                    let result = match self.pop() {
+                        Value::Int(n) => Value::Int(-n),
+                        Value::Float(f) => Value::Float(-f),
+                        other => self.host.unary_op("-", other)?,
+                    };
+                    self.stack.push(result);
+                }
+                Op::Not => {
+                    let value = self.pop();
+                    self.stack.push(Value::Bool(!value.is_truthy()));
+                }
+                Op::Jump(target) => self.frame.ip = target as usize,
+                Op::JumpIfFalse(target) => {
+                    if !self.pop().is_truthy() {
+                        self.frame.ip = target as usize;
+                    }
+                }
+                Op::JumpIfNull(target) => {
+                    if matches!(self.stack.last(), Some(Value::Null)) {
+                        self.frame.ip = target as usize;
+                    }
+                }
+                Op::JumpIfNotNull(target) => {
+                    if !matches!(self.stack.last(), Some(Value::Null)) {
+                        self.frame.ip = target as usize;
+                    }
+                }
+                Op::Call { func, argc } => {
+                    let callee = &program.functions[func as usize];
+                    let base = self.stack.len() - argc as usize;
+                    if let Some(def) = &callee.def {
+                        self.host.check_bounds(def, &self.stack[base..])?;
+                    }
+                    self.host.enter_call()?;
+                    // Extra arguments are ignored and missing ones left null
+                    self.stack.truncate(base + callee.arity.min(argc as usize));
+                    self.stack.resize(base + callee.locals, Value::Null);
+                    let caller = std::mem::replace(&mut self.frame, Frame { func: Some(func as usize), ip: 0, base });
+                    self.frames.push(caller);
+                    function = callee;
+                }
+                Op::CallNamed { name, argc } => {
+                    let args = self.pop_n(argc);
+                    let result = self.host.call_function(&function.chunk.names[name as usize], args)?;
+                    self.stack.push(result);
+                }
+                Op::CallValue(argc) => {
+                    let args = self.pop_n(argc);
+                    let callee = self.pop();
+                    let result = self.host.call_value(callee, args)?;
+                    self.stack.push(result);
+                }
+                Op::CallMethod { name, argc, store } => {
+                    let args = self.pop_n(argc);
+                    let receiver = self.pop();
+                    let (result, updated) =
+                        self.host.call_method_on(receiver, &function.chunk.names[name as usize], args)?;
+                    self.stack.push(result);
+                    match updated {
+                        Some(updated) if store != NO_STORE => self.stack.push(updated),
+                        _ if store != NO_STORE => self.frame.ip = store as usize,
+                        _ => {}
+                    }
+                }
+                Op::Return => {
+                    let result = self.pop();
+                    while self.handlers.last().is_some_and(|h| h.depth == self.frames.len()) {
+                        self.handlers.pop();
+                    }
+                    match self.frames.pop() {
+                        Some(caller) => {
+                            self.host.leave_call();
+                            self.stack.truncate(self.frame.base);
+                            self.stack.push(result);
+                            self.frame = caller;
+                            function = function_of(program, caller.func);
+                        }
+                        None => return Ok(()),
+                    }
+                }
+                Op::Array(n) => {
+                    let items = self.pop_n(n);
+                    self.stack.push(Value::Array(items));
+                }
+                Op::Tuple(n) => {
+                    let items = self.pop_n(n);
+                    self.stack.push(Value::Tuple(items));
+                }
+                Op::Map(n) => {
+                    let items = self.pop_n(n * 2);
+                    let mut map = HashMap::new();
+                    let mut items = items.into_iter();
+                    while let (Some(key), Some(value)) = (items.next(), items.next()) {
+                        map.insert(key.as_string(), value);
+                    }
+                    self.stack.push(Value::Map(map));
+                }
+                Op::Range { inclusive } => {
+                    let end = self.pop().as_int();
+                    let start = self.pop().as_int();
+                    self.stack.push(Value::Range { start, end, inclusive });
+                }
+                Op::Struct { name, fields, count } => {
+                    let values = self.pop_n(count);
+                    let names = &function.chunk.names[fields as usize..(fields + count) as usize];
+                    let values = names.iter().cloned().zip(values).collect();
+                    let value = self.host.construct_struct(&function.chunk.names[name as usize], values)?;
+                    self.stack.push(value);
+                }
+                Op::Index => {
+                    let index = self.pop();
+                    let container = self.pop();
+                    self.stack.push(Interpreter::index_value(&container, &index)?);
+                }
+                Op::IndexLocal(slot) => {
+                    let index = self.pop();
+                    let value = Interpreter::index_value(&self.stack[base + slot as usize], &index)?;
+                    self.stack.push(value);
+                }
+                Op::SetIndex => {
+                    let mut container = self.pop();
+                    let index = self.pop();
+                    let value = self.pop();
+                    Interpreter::apply_index_assign(&mut container, index, value)?;
+                    self.stack.push(container);
+                }
+                Op::SetIndexLocal(slot) => {
+                    let index = self.pop();
+                    let value = self.pop();
+                    Interpreter::apply_index_assign(&mut self.stack[base + slot as usize], index, value)?;
+                }
+                Op::Field(name) => {
+                    let object = self.pop();
+                    let value = self.host.field_of(object, &function.chunk.names[name as usize])?;
+                    self.stack.push(value);
+                }
+                Op::SafeField(name) => {
+                    let field = &function.chunk.names[name as usize];
+                    let value = match self.pop() {
+                        Value::Map(m) => m.get(field).cloned(),
+                        Value::StructInstance(s) => s.fields.get(field).cloned(),
+                        _ => None,
+                    };
+                    self.stack.push(value.unwrap_or(Value::Null));
+                }
+                Op::SetField(name) => {
+                    let mut container = self.pop();
+                    let value = self.pop();
+                    let field = &function.chunk.names[name as usize];
+                    match &mut container {
+                        Value::Map(m) => {
+                            m.insert(field.clone(), value);
+                        }
+                        other => {
+                            other.set_field(field, value);
+                        }
+                    }
+                    self.stack.push(container);
+                }
+                Op::Display => {
+                    let value = self.pop();
+                    let text = self.host.display(&value)?;
+                    self.stack.push(Value::String(text));
+                }
+                Op::Concat(n) => {
+                    let parts = self.pop_n(n);
+                    let text: String = parts.iter().map(Value::as_string).collect();
+                    self.stack.push(Value::String(text));
+                }
+                Op::IntoIter { comprehension } => {
+                    let iterable = match self.pop() {
+                        value @ (Value::Array(_) | Value::Range { .. }) => value,
+                        Value::String(s) if comprehension => {
+                            Value::Array(s.chars().map(|c| Value::String(c.to_string())).collect())
+                        }
+                        other if comprehension => Value::Array(vec![other]),
+                        other => return Err(RuntimeError::new(ErrorKind::TypeError, format!("Cannot iterate over {:?}", other))),
+                    };
+                    self.stack.push(iterable);
+                }
+                Op::ForNext { iter, index, var, exit } => {
+                    let i = self.stack[base + index as usize].as_int();
+                    let next = match &self.stack[base + iter as usize] {
+                        Value::Array(items) => items.get(i as usize).cloned(),
+                        Value::Range { start, end, inclusive } => {
+                            let limit = if *inclusive { end + 1 } else { *end };
+                            (start + i < limit).then_some(Value::Int(start + i))
+                        }
+                        _ => None,
+                    };
+                    match next {
+                        Some(value) => {
+                            self.stack[base + var as usize] = value;
+                            self.stack[base + index as usize] = Value::Int(i + 1);
+                        }
+                        None => self.frame.ip = exit as usize,
+                    }
+                }
+                Op::PushLocal(slot) => {
+                    let value = self.pop();
+                    if let Value::Array(items) = &mut self.stack[base + slot as usize] {
+                        items.push(value);
+                    }
+                }
+                Op::Closure(index) => {
+                    let lambda = &function.chunk.lambdas[index as usize];
+                    let env = lambda
+                        .captures
+                        .iter()
+                        .map(|(name, slot)| (name.clone(), self.stack[base + *slot as usize].clone()))
+                        .collect();
+                    self.stack.push(Value::Closure {
+                        params: lambda.params.clone(),
+                        body: lambda.body.clone(),
+                        env,
+                    });
+                }
+                Op::MatchArm { subject, arm } => {
+                    let arm = &function.chunk.arms[arm as usize];
+                    let value = &self.stack[base + subject as usize];
+                    if !self.host.pattern_matches(&arm.pattern, value) {
+                        self.frame.ip = arm.fail as usize;
+                        continue;
+                    }
+                    let mut bound = Vec::new();
+                    self.host.pattern_bindings(&arm.pattern, value, &mut bound);
+                    for (name, slot) in &arm.bindings {
+                        // A name the pattern did not bind is a unit variant or an
+                        // outer variable, as in the interpreter
+                        let value = match bound.iter().position(|(n, _)| n == name) {
+                            Some(i) => bound.swap_remove(i).1,
+                            None => self.host.lookup(name).unwrap_or(Value::Null),
+                        };
+                        self.stack[base + *slot as usize] = value;
+                    }
+                }
+                Op::TryStart { catch } => self.handlers.push(Handler {
+                    depth: self.frames.len(),
+                    catch: catch as usize,
+                    sp: self.stack.len(),
+                }),
+                Op::TryEnd => {
+                    self.handlers.pop();
+                }
+                Op::Throw => {
+                    let value = self.pop();
+                    return Err(RuntimeError::thrown(value));
+                }
+                Op::Host(index) => {
+                    self.host.execute_node(&function.chunk.host_nodes[index as usize])?;
+                }
+            }
+        }
+    }
+
+    /// Integer and float arithmetic inline, everything else (including errors
+    /// such as division by zero) as the interpreter does it
+    fn binary(&mut self, op: BinOp, left: Value, right: Value) -> Result<Value, RuntimeError> {
+        match (&left, &right) {
+            (Value::Int(a), Value::Int(b)) => {
+                let (a, b) = (*a, *b);
+                let value = match op {
+                    BinOp::Add => Value::Int(a + b),
+                    BinOp::Sub => Value::Int(a - b),
+                    BinOp::Mul => Value::Int(a * b),
+                    BinOp::Div if b != 0 => Value::Int(a / b),
+                    BinOp::Rem if b != 0 => Value::Int(a % b),
+                    BinOp::Eq => Value::Bool(a == b),
+                    BinOp::Ne => Value::Bool(a != b),
+                    BinOp::Lt => Value::Bool(a < b),
+                    BinOp::Gt => Value::Bool(a > b),
+                    BinOp::Le => Value::Bool(a <= b),
+                    BinOp::Ge => Value::Bool(a >= b),
+                    _ => return self.host.binary_op(op.symbol(), left, right),
+                };
+                Ok(value)
+            }
+            (Value::Float(a), Value::Float(b)) => {
+                let (a, b) = (*a, *b);
+                let value = match op {
+                    BinOp::Add => Value::Float(a + b),
+                    BinOp::Sub => Value::Float(a - b),
+                    BinOp::Mul => Value::Float(a * b),
+                    BinOp::Lt => Value::Bool(a < b),
+                    BinOp::Gt => Value::Bool(a > b),
+                    BinOp::Le => Value::Bool(a <= b),
+                    BinOp::Ge => Value::Bool(a >= b),
+                    _ => return self.host.binary_op(op.symbol(), left, right),
+                };
+                Ok(value)
+            }
+            _ => self.host.binary_op(op.symbol(), left, right),
+        }
+    }
+}
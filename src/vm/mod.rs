@@ -0,0 +1,94 @@
+//! Knull Bytecode VM
+//!
+//! Compiles a program's functions and top-level code to stack bytecode with
+//! locals resolved to slots, and runs it on top of the tree-walking
+//! interpreter, which still owns globals, builtins, methods and closures.
+//! Whatever the compiler does not handle yet (a whole function, or a single
+//! top-level statement) stays with the interpreter. Selected by `knull run --vm`.
+
+pub mod bytecode;
+pub mod compiler;
+pub mod machine;
+
+use crate::interpreter::{Interpreter, RuntimeError};
+use crate::parser::ASTNode;
+
+/// Run a parsed program on the VM, with `host` providing everything it delegates
+pub fn run(ast: &ASTNode, host: &mut Interpreter, verbose: bool) -> Result<(), RuntimeError> {
+    let items = match ast {
+        ASTNode::Program(items) => items.as_slice(),
+        other => std::slice::from_ref(other),
+    };
+    host.define_items(items)?;
+    let program = compiler::compile(items);
+    if verbose {
+        for (what, reason) in &program.interpreted {
+            eprintln!("vm: interpreting {}: {}", what, reason);
+        }
+    }
+    let result = machine::Vm::new(host, &program).run();
+    host.finish_program(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::Value;
+    use crate::parser::Parser;
+
+    fn run_vm(src: &str) -> Result<Interpreter, RuntimeError> {
+        let ast = Parser::new(src).parse().unwrap();
+        let mut interp = Interpreter::new();
+        run(&ast, &mut interp, false)?;
+        Ok(interp)
+    }
+
+    fn global(interp: &mut Interpreter, name: &str) -> Value {
+        interp.lookup(name).unwrap()
+    }
+
+    #[test]
+    fn test_functions_loops_and_recursion() {
+        let src = "fn fib(n) { if n < 2 { return n }\n fib(n - 1) + fib(n - 2) }\nlet total = 0\nfor i in range(0, 5) { total += i }\nlet mut j = 0\nwhile true { j += 1\n if j == 3 { break } }\nlet f = fib(15)";
+        let mut interp = run_vm(src).unwrap();
+        assert_eq!(global(&mut interp, "total"), Value::Int(10));
+        assert_eq!(global(&mut interp, "j"), Value::Int(3));
+        assert_eq!(global(&mut interp, "f"), Value::Int(610));
+    }
+
+    #[test]
+    fn test_closures_match_and_comprehensions() {
+        let src = "fn adder(n) { |x| x + n }\nlet add2 = adder(2)\nlet a = add2(5)\nfn describe(v) { match v { 0 => \"zero\", (x, y) => f\"pair {x}\", n if n < 0 => \"negative\", _ => \"other\" } }\nlet b = [describe(0), describe(-1), describe((1, 2)), describe(9)]\nlet c = [x * x for x in range(0, 5) if x % 2 == 0]";
+        let mut interp = run_vm(src).unwrap();
+        assert_eq!(global(&mut interp, "a"), Value::Int(7));
+        assert_eq!(global(&mut interp, "b").as_string(), "[zero, negative, pair 1, other]");
+        assert_eq!(global(&mut interp, "c").as_string(), "[0, 4, 16]");
+    }
+
+    #[test]
+    fn test_try_catch_unwinds_frames() {
+        let src = "fn inner(x) { if x > 1 { throw \"too big\" }\n x }\nfn outer(x) { inner(x) * 2 }\nlet caught = try { outer(5) } catch e { e }\nlet fine = try { outer(1) } catch e { -1 }";
+        let mut interp = run_vm(src).unwrap();
+        assert_eq!(global(&mut interp, "caught"), Value::String("too big".to_string()));
+        assert_eq!(global(&mut interp, "fine"), Value::Int(2));
+
+        let err = run_vm("fn f() { [1][3] }\nf()").err().unwrap();
+        assert_eq!(err.stack.len(), 1);
+        assert_eq!(err.stack[0].function, "f");
+    }
+
+    #[test]
+    fn test_method_write_back_and_interpreter_fallback() {
+        let src = "struct C { n: i64 }\nimpl C { fn bump(self) { self.n += 1 } }\nfn g() { let c = C { n: 1 }\n c.bump()\n c.bump()\n c.n }\nfn deferred() { defer 1\n 5 }\nlet a = g()\nlet b = deferred()";
+        let mut interp = run_vm(src).unwrap();
+        assert_eq!(global(&mut interp, "a"), Value::Int(3));
+        assert_eq!(global(&mut interp, "b"), Value::Int(5));
+
+        let program = compiler::compile(match &Parser::new(src).parse().unwrap() {
+            ASTNode::Program(items) => items,
+            _ => unreachable!(),
+        });
+        assert_eq!(program.interpreted.len(), 1);
+        assert_eq!(program.interpreted[0].0, "deferred");
+    }
+}
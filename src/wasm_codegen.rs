@@ -815,7 +815,7 @@ pub fn compile_to_wasm(source: &str, output_path: &str) -> Result<(), String> {
     let mut lexer = Lexer::new(source);
     let _tokens = lexer.tokenize();
 
-    let mut parser = Parser::new(source);
+    let mut parser = Parser::new(source).without_spans();
     let ast = parser.parse().map_err(|e| format!("Parse error: {}", e))?;
 
     let mut codegen = WasmCodeGen::new();
@@ -838,7 +838,7 @@ pub fn compile_to_wat(source: &str, output_path: &str) -> Result<(), String> {
     let mut lexer = Lexer::new(source);
     let _tokens = lexer.tokenize();
 
-    let mut parser = Parser::new(source);
+    let mut parser = Parser::new(source).without_spans();
     let ast = parser.parse().map_err(|e| format!("Parse error: {}", e))?;
 
     let mut codegen = WasmCodeGen::new();